@@ -10,6 +10,7 @@ use collab_plugins::connect_state::{CollabConnectReachability, CollabConnectStat
 use collab_plugins::local_storage::kv::snapshot::SnapshotPersistence;
 if_native! {
 use collab_plugins::local_storage::rocksdb::rocksdb_plugin::{RocksdbBackup, RocksdbDiskPlugin};
+use crate::native::snapshot::{spawn_auto_snapshot, support_auto_snapshot};
 }
 
 if_wasm! {
@@ -294,6 +295,19 @@ impl AppFlowyCollabBuilder {
       arc_collab.lock().initialize();
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    if build_config.sync_enable && support_auto_snapshot(&object_type) {
+      if let Some(snapshot_persistence) = self.snapshot_persistence.lock().clone() {
+        spawn_auto_snapshot(
+          uid,
+          object_id.to_string(),
+          object_type.clone(),
+          Arc::downgrade(&arc_collab),
+          snapshot_persistence,
+        );
+      }
+    }
+
     trace!("collab initialized: {}:{}", object_type, object_id);
     Ok(arc_collab)
   }
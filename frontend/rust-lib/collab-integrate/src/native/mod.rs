@@ -1 +1,2 @@
 pub mod plugin_provider;
+pub(crate) mod snapshot;
@@ -0,0 +1,78 @@
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use collab::core::collab::MutexCollab;
+use collab_entity::CollabType;
+use collab_plugins::local_storage::kv::snapshot::SnapshotPersistence;
+use tracing::{trace, warn};
+
+/// The interval of taking the automatic snapshots of the collab objects that are being edited.
+const AUTO_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Returns true if the automatic snapshots are taken for the given collab type. The snapshots of
+/// the database are taken by the database manager, because the rows are stored in other collab
+/// objects.
+pub(crate) fn support_auto_snapshot(collab_type: &CollabType) -> bool {
+  matches!(collab_type, CollabType::Document | CollabType::Folder)
+}
+
+/// Takes a snapshot of the collab every [AUTO_SNAPSHOT_INTERVAL] if the collab was changed since
+/// the last snapshot. The task stops once the collab is dropped.
+pub(crate) fn spawn_auto_snapshot(
+  uid: i64,
+  object_id: String,
+  collab_type: CollabType,
+  collab: Weak<MutexCollab>,
+  snapshot_persistence: Arc<dyn SnapshotPersistence>,
+) {
+  let mut last_state_vector = match collab.upgrade() {
+    None => return,
+    Some(collab) => match collab
+      .lock()
+      .encode_collab_v1(|_| Ok::<(), anyhow::Error>(()))
+    {
+      Ok(encoded_collab) => encoded_collab.state_vector,
+      Err(err) => {
+        warn!("Failed to encode collab {}: {}", object_id, err);
+        return;
+      },
+    },
+  };
+
+  tokio::spawn(async move {
+    let mut interval = tokio::time::interval(AUTO_SNAPSHOT_INTERVAL);
+    // The first tick completes immediately.
+    interval.tick().await;
+    loop {
+      interval.tick().await;
+      let encoded_collab = match collab.upgrade() {
+        None => break,
+        Some(collab) => collab
+          .lock()
+          .encode_collab_v1(|_| Ok::<(), anyhow::Error>(())),
+      };
+
+      match encoded_collab {
+        Ok(encoded_collab) => {
+          if encoded_collab.state_vector == last_state_vector {
+            continue;
+          }
+          last_state_vector = encoded_collab.state_vector.clone();
+          match encoded_collab.encode_to_bytes() {
+            Ok(encoded_v1) => {
+              trace!("create auto snapshot for {}:{}", collab_type, object_id);
+              if let Err(err) =
+                snapshot_persistence.create_snapshot(uid, &object_id, &collab_type, encoded_v1)
+              {
+                warn!("Failed to create snapshot for {}: {}", object_id, err);
+              }
+            },
+            Err(err) => warn!("Failed to encode snapshot of {}: {}", object_id, err),
+          }
+        },
+        Err(err) => warn!("Failed to encode collab {}: {}", object_id, err),
+      }
+    }
+    trace!("stop auto snapshot for {}:{}", collab_type, object_id);
+  });
+}
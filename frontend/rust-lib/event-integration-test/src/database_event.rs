@@ -56,6 +56,35 @@ impl EventIntegrationTest {
       .parse::<ViewPB>()
   }
 
+  /// Creates a grid view of the existing database with `database_id`.
+  pub async fn create_linked_grid(
+    &self,
+    parent_id: &str,
+    name: String,
+    database_id: &str,
+  ) -> ViewPB {
+    let payload = CreateViewPayloadPB {
+      parent_view_id: parent_id.to_string(),
+      name,
+      desc: "".to_string(),
+      thumbnail: None,
+      layout: ViewLayoutPB::Grid,
+      initial_data: vec![],
+      meta: HashMap::from([("database_id".to_string(), database_id.to_string())]),
+      set_as_current: true,
+      index: None,
+      section: None,
+      view_id: None,
+      extra: None,
+    };
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::CreateView)
+      .payload(payload)
+      .async_send()
+      .await
+      .parse::<ViewPB>()
+  }
+
   pub async fn open_database(&self, view_id: &str) {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::GetDatabase)
@@ -153,6 +182,364 @@ impl EventIntegrationTest {
       .parse::<FieldPB>()
   }
 
+  pub async fn create_relation_field(
+    &self,
+    view_id: &str,
+    related_database_id: &str,
+    show_on_related_database: bool,
+  ) -> FieldPB {
+    let type_option = RelationTypeOptionPB {
+      database_id: related_database_id.to_string(),
+      show_on_related_database,
+      ..Default::default()
+    };
+    let type_option_data: Bytes = type_option.try_into().unwrap();
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CreateField)
+      .payload(CreateFieldPayloadPB {
+        view_id: view_id.to_string(),
+        field_type: FieldType::Relation,
+        type_option_data: Some(type_option_data.to_vec()),
+        ..Default::default()
+      })
+      .async_send()
+      .await
+      .parse::<FieldPB>()
+  }
+
+  pub async fn create_rollup_field(
+    &self,
+    view_id: &str,
+    type_option: RollupTypeOptionPB,
+  ) -> FieldPB {
+    let type_option_data: Bytes = type_option.try_into().unwrap();
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CreateField)
+      .payload(CreateFieldPayloadPB {
+        view_id: view_id.to_string(),
+        field_type: FieldType::Rollup,
+        type_option_data: Some(type_option_data.to_vec()),
+        ..Default::default()
+      })
+      .async_send()
+      .await
+      .parse::<FieldPB>()
+  }
+
+  pub async fn get_relation_type_option(
+    &self,
+    view_id: &str,
+    field_id: &str,
+  ) -> RelationTypeOptionPB {
+    let field = self
+      .get_all_database_fields(view_id)
+      .await
+      .items
+      .into_iter()
+      .find(|field| field.id == field_id)
+      .unwrap();
+    RelationTypeOptionPB::try_from(Bytes::from(field.type_option_data)).unwrap()
+  }
+
+  pub async fn fill_ai_field(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    overwrite: bool,
+  ) -> AIFillResultPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::FillAIField)
+      .payload(FillAIFieldPB {
+        view_id: view_id.to_string(),
+        field_id: field_id.to_string(),
+        overwrite,
+      })
+      .async_send()
+      .await
+      .parse::<AIFillResultPB>()
+  }
+
+  pub async fn cancel_fill_ai_field(&self, view_id: &str, field_id: &str) {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CancelFillAIField)
+      .payload(CancelFillAIFieldPB {
+        view_id: view_id.to_string(),
+        field_id: field_id.to_string(),
+      })
+      .async_send()
+      .await;
+  }
+
+  pub async fn get_automations(&self, view_id: &str) -> RepeatedAutomationPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetDatabaseAutomations)
+      .payload(DatabaseViewIdPB {
+        value: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedAutomationPB>()
+  }
+
+  pub async fn create_automation(
+    &self,
+    payload: CreateAutomationPayloadPB,
+  ) -> Result<AutomationPB, FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CreateDatabaseAutomation)
+      .payload(payload)
+      .async_send()
+      .await
+      .try_parse::<AutomationPB>()
+  }
+
+  pub async fn update_automation(
+    &self,
+    view_id: &str,
+    automation: AutomationPB,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::UpdateDatabaseAutomation)
+      .payload(UpdateAutomationPayloadPB {
+        view_id: view_id.to_string(),
+        automation,
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn delete_automation(&self, view_id: &str, automation_id: &str) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::DeleteDatabaseAutomation)
+      .payload(DeleteAutomationPayloadPB {
+        view_id: view_id.to_string(),
+        automation_id: automation_id.to_string(),
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn get_automation_logs(&self, view_id: &str) -> RepeatedAutomationLogPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetDatabaseAutomationLogs)
+      .payload(DatabaseViewIdPB {
+        value: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedAutomationLogPB>()
+  }
+
+  pub async fn get_conditional_formats(&self, view_id: &str) -> RepeatedConditionalFormatPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetConditionalFormats)
+      .payload(DatabaseViewIdPB {
+        value: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedConditionalFormatPB>()
+  }
+
+  pub async fn create_conditional_format(
+    &self,
+    payload: CreateConditionalFormatPayloadPB,
+  ) -> Result<ConditionalFormatPB, FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CreateConditionalFormat)
+      .payload(payload)
+      .async_send()
+      .await
+      .try_parse::<ConditionalFormatPB>()
+  }
+
+  pub async fn update_conditional_format(
+    &self,
+    view_id: &str,
+    format: ConditionalFormatPB,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::UpdateConditionalFormat)
+      .payload(UpdateConditionalFormatPayloadPB {
+        view_id: view_id.to_string(),
+        format,
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn delete_conditional_format(
+    &self,
+    view_id: &str,
+    format_id: &str,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::DeleteConditionalFormat)
+      .payload(DeleteConditionalFormatPayloadPB {
+        view_id: view_id.to_string(),
+        format_id: format_id.to_string(),
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn get_row_comments(&self, view_id: &str, row_id: &str) -> RepeatedRowCommentPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetRowComments)
+      .payload(RowIdPB {
+        view_id: view_id.to_string(),
+        row_id: row_id.to_string(),
+        group_id: None,
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedRowCommentPB>()
+  }
+
+  pub async fn add_row_comment(
+    &self,
+    view_id: &str,
+    row_id: &str,
+    content: &str,
+    parent_comment_id: Option<String>,
+  ) -> Result<RowCommentPB, FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::AddRowComment)
+      .payload(AddRowCommentPayloadPB {
+        view_id: view_id.to_string(),
+        row_id: row_id.to_string(),
+        content: content.to_string(),
+        parent_comment_id,
+      })
+      .async_send()
+      .await
+      .try_parse::<RowCommentPB>()
+  }
+
+  pub async fn update_row_comment(
+    &self,
+    view_id: &str,
+    row_id: &str,
+    comment_id: &str,
+    content: &str,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::UpdateRowComment)
+      .payload(UpdateRowCommentPayloadPB {
+        view_id: view_id.to_string(),
+        row_id: row_id.to_string(),
+        comment_id: comment_id.to_string(),
+        content: content.to_string(),
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn delete_row_comment(
+    &self,
+    view_id: &str,
+    row_id: &str,
+    comment_id: &str,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::DeleteRowComment)
+      .payload(RowCommentIdPB {
+        view_id: view_id.to_string(),
+        row_id: row_id.to_string(),
+        comment_id: comment_id.to_string(),
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn resolve_row_comment(
+    &self,
+    view_id: &str,
+    row_id: &str,
+    comment_id: &str,
+    is_resolved: bool,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::ResolveRowComment)
+      .payload(ResolveRowCommentPayloadPB {
+        view_id: view_id.to_string(),
+        row_id: row_id.to_string(),
+        comment_id: comment_id.to_string(),
+        is_resolved,
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn update_calculation(&self, changeset: UpdateCalculationChangesetPB) {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::UpdateCalculation)
+      .payload(changeset)
+      .async_send()
+      .await;
+  }
+
+  pub async fn get_all_calculations(&self, view_id: &str) -> RepeatedCalculationsPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetAllCalculations)
+      .payload(DatabaseViewIdPB {
+        value: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedCalculationsPB>()
+  }
+
+  pub async fn update_field_validation(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    validation: FieldValidationPB,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::UpdateField)
+      .payload(FieldChangesetPB {
+        view_id: view_id.to_string(),
+        field_id: field_id.to_string(),
+        validation: Some(validation),
+        ..Default::default()
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn validate_database(&self, view_id: &str) -> RepeatedCellViolationPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::ValidateDatabase)
+      .payload(DatabaseViewIdPB {
+        value: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedCellViolationPB>()
+  }
+
+  pub async fn export_database_template(&self, view_id: &str, include_rows: bool) -> String {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::ExportDatabaseTemplate)
+      .payload(DatabaseTemplateExportPayloadPB {
+        view_id: view_id.to_string(),
+        include_rows,
+      })
+      .async_send()
+      .await
+      .parse::<DatabaseExportDataPB>()
+      .data
+  }
+
   pub async fn update_field(&self, changeset: FieldChangesetPB) {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::UpdateField)
@@ -203,6 +590,18 @@ impl EventIntegrationTest {
       .error()
   }
 
+  pub async fn clear_field(&self, view_id: &str, field_id: &str) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::ClearField)
+      .payload(ClearFieldPayloadPB {
+        field_id: field_id.to_string(),
+        view_id: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
   pub async fn get_primary_field(&self, database_view_id: &str) -> FieldPB {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::GetPrimaryField)
@@ -339,6 +738,28 @@ impl EventIntegrationTest {
       .error()
   }
 
+  pub async fn complete_date_occurrence(
+    &self,
+    view_id: &str,
+    row_id: &str,
+    field_id: &str,
+    checkbox_field_id: Option<String>,
+  ) -> Result<OptionalRowPB, FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CompleteDateOccurrence)
+      .payload(CompleteDateOccurrencePB {
+        cell_id: CellIdPB {
+          view_id: view_id.to_string(),
+          row_id: row_id.to_string(),
+          field_id: field_id.to_string(),
+        },
+        checkbox_field_id,
+      })
+      .async_send()
+      .await
+      .try_parse::<OptionalRowPB>()
+  }
+
   pub async fn get_cell(&self, view_id: &str, row_id: &str, field_id: &str) -> CellPB {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::GetCell)
@@ -382,6 +803,16 @@ impl EventIntegrationTest {
     RelationCellDataPB::try_from(Bytes::from(cell.data)).unwrap_or_default()
   }
 
+  pub async fn get_rollup_cell(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    row_id: &str,
+  ) -> RollupCellDataPB {
+    let cell = self.get_cell(view_id, row_id, field_id).await;
+    RollupCellDataPB::try_from(Bytes::from(cell.data)).unwrap_or_default()
+  }
+
   pub async fn update_checklist_cell(
     &self,
     changeset: ChecklistCellDataChangesetPB,
@@ -516,6 +947,26 @@ impl EventIntegrationTest {
       .event(DatabaseEvent::GetAllCalendarEvents)
       .payload(CalendarEventRequestPB {
         view_id: view_id.to_string(),
+        ..Default::default()
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedCalendarEventPB>()
+      .items
+  }
+
+  pub async fn get_calendar_events_between(
+    &self,
+    view_id: &str,
+    start: i64,
+    end: i64,
+  ) -> Vec<CalendarEventPB> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetAllCalendarEvents)
+      .payload(CalendarEventRequestPB {
+        view_id: view_id.to_string(),
+        start: Some(start),
+        end: Some(end),
       })
       .async_send()
       .await
@@ -523,6 +974,74 @@ impl EventIntegrationTest {
       .items
   }
 
+  pub async fn get_all_timeline_events(&self, view_id: &str) -> Vec<TimelineEventPB> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetAllTimelineEvents)
+      .payload(TimelineEventRequestPB {
+        view_id: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedTimelineEventPB>()
+      .items
+  }
+
+  pub async fn move_timeline_event(&self, payload: MoveTimelineEventPB) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::MoveTimelineEvent)
+      .payload(payload)
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn create_database_snapshot(&self, view_id: &str, title: &str) -> DatabaseSnapshotPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CreateDatabaseSnapshot)
+      .payload(CreateDatabaseSnapshotPayloadPB {
+        view_id: view_id.to_string(),
+        title: title.to_string(),
+        desc: "".to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<DatabaseSnapshotPB>()
+  }
+
+  pub async fn restore_database_snapshot(
+    &self,
+    view_id: &str,
+    snapshot_id: i64,
+  ) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::RestoreDatabaseSnapshot)
+      .payload(RestoreDatabaseSnapshotPayloadPB {
+        view_id: view_id.to_string(),
+        snapshot_id,
+      })
+      .async_send()
+      .await
+      .error()
+  }
+
+  pub async fn get_database_snapshot_diff(
+    &self,
+    view_id: &str,
+    from_snapshot_id: i64,
+    to_snapshot_id: Option<i64>,
+  ) -> DatabaseDiffPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::GetDatabaseSnapshotDiff)
+      .payload(DatabaseSnapshotDiffPayloadPB {
+        view_id: view_id.to_string(),
+        from_snapshot_id,
+        to_snapshot_id,
+      })
+      .async_send()
+      .await
+      .parse::<DatabaseDiffPB>()
+  }
+
   pub async fn update_relation_cell(
     &self,
     changeset: RelationCellChangesetPB,
@@ -118,6 +118,22 @@ impl EventIntegrationTest {
       .unwrap()
   }
 
+  pub fn get_document_search_handler(&self) -> &Arc<dyn SearchHandler> {
+    self
+      .appflowy_core
+      .search_manager
+      .get_handler(SearchType::Document)
+      .unwrap()
+  }
+
+  pub fn get_database_search_handler(&self) -> &Arc<dyn SearchHandler> {
+    self
+      .appflowy_core
+      .search_manager
+      .get_handler(SearchType::Database)
+      .unwrap()
+  }
+
   /// create views in the folder.
   pub async fn create_views(&self, views: Vec<View>) {
     let create_view_params = views
@@ -286,13 +302,47 @@ impl EventIntegrationTest {
   }
 
   pub async fn import_data(&self, data: ImportPayloadPB) -> Vec<ViewPB> {
+    self.import_data_with_result(data).await.items
+  }
+
+  pub async fn import_data_with_result(&self, data: ImportPayloadPB) -> ImportResultPB {
     EventBuilder::new(self.clone())
       .event(FolderEvent::ImportData)
       .payload(data)
       .async_send()
       .await
-      .parse::<RepeatedViewPB>()
-      .items
+      .parse::<ImportResultPB>()
+  }
+
+  pub async fn export_workspace(&self, file_path: &str) -> WorkspaceExportPB {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::ExportWorkspace)
+      .payload(ExportWorkspacePayloadPB {
+        file_path: file_path.to_string(),
+        document_format: DocumentExportFormatPB::Markdown,
+      })
+      .async_send()
+      .await
+      .parse::<WorkspaceExportPB>()
+  }
+
+  pub async fn publish_view_to_local_site(
+    &self,
+    view_id: &str,
+    site_dir: &str,
+    namespace: Option<String>,
+  ) -> LocalPublishedSitePB {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::PublishViewToLocalSite)
+      .payload(PublishViewToLocalSitePayloadPB {
+        view_id: view_id.to_string(),
+        site_dir: site_dir.to_string(),
+        publish_name: None,
+        namespace,
+      })
+      .async_send()
+      .await
+      .parse::<LocalPublishedSitePB>()
   }
 
   pub async fn get_view_ancestors(&self, view_id: &str) -> Vec<ViewPB> {
@@ -306,6 +356,39 @@ impl EventIntegrationTest {
       .parse::<RepeatedViewPB>()
       .items
   }
+
+  pub async fn get_view_backlinks(&self, view_id: &str) -> Vec<ViewBacklinkPB> {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::GetViewBacklinks)
+      .payload(ViewIdPB {
+        value: view_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RepeatedViewBacklinkPB>()
+      .items
+  }
+
+  pub async fn create_folder_snapshot(&self, title: &str) -> FolderSnapshotPB {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::CreateFolderSnapshot)
+      .payload(CreateFolderSnapshotPayloadPB {
+        title: title.to_string(),
+        desc: "".to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<FolderSnapshotPB>()
+  }
+
+  pub async fn restore_folder_snapshot(&self, snapshot_id: i64) -> Option<FlowyError> {
+    EventBuilder::new(self.clone())
+      .event(FolderEvent::RestoreFolderSnapshot)
+      .payload(RestoreFolderSnapshotPayloadPB { snapshot_id })
+      .async_send()
+      .await
+      .error()
+  }
 }
 
 pub struct ViewTest {
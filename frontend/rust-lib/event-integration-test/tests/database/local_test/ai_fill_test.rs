@@ -0,0 +1,54 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::FieldType;
+
+#[tokio::test]
+async fn fill_summary_field_without_local_ai_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let rows = test.get_database(&grid_view.id).await.rows;
+  let field = test.create_field(&grid_view.id, FieldType::Summary).await;
+
+  // The local AI isn't running, so every row is reported as failed.
+  let result = test.fill_ai_field(&grid_view.id, &field.id, true).await;
+  assert!(!result.is_cancelled);
+  assert_eq!(result.rows.len(), rows.len());
+  for row in result.rows {
+    assert!(row.error.is_some());
+  }
+}
+
+#[tokio::test]
+async fn cancel_fill_ai_field_between_rows_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let rows = test.get_database(&grid_view.id).await.rows;
+  let field = test.create_field(&grid_view.id, FieldType::Summary).await;
+
+  let cancel = async {
+    tokio::task::yield_now().await;
+    test.cancel_fill_ai_field(&grid_view.id, &field.id).await;
+  };
+  let (result, _) = tokio::join!(test.fill_ai_field(&grid_view.id, &field.id, true), cancel);
+
+  // The cancellation is only checked between the rows, so the filled rows are the first rows of
+  // the view and each of them is finished.
+  assert!(result.rows.len() <= rows.len());
+  if result.is_cancelled {
+    assert!(result.rows.len() < rows.len());
+  }
+  for (row, result_row) in rows.iter().zip(result.rows.iter()) {
+    assert_eq!(row.id, result_row.row_id);
+    assert!(result_row.error.is_some());
+  }
+
+  // The fill is removed once it ends, so the field can be filled again.
+  let result = test.fill_ai_field(&grid_view.id, &field.id, true).await;
+  assert!(!result.is_cancelled);
+  assert_eq!(result.rows.len(), rows.len());
+}
@@ -0,0 +1,233 @@
+use std::time::Duration;
+
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  AutomationActionPB, AutomationActionTypePB, AutomationLogStatusPB, AutomationTriggerPB,
+  AutomationTriggerTypePB, CellChangesetPB, CreateAutomationPayloadPB, FieldType,
+};
+
+#[tokio::test]
+async fn create_update_and_delete_automation_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  assert!(test.get_automations(&grid_view.id).await.items.is_empty());
+
+  let automation = test
+    .create_automation(CreateAutomationPayloadPB {
+      view_id: grid_view.id.clone(),
+      name: "Remind me".to_string(),
+      trigger: AutomationTriggerPB::default(),
+      condition: None,
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::SendReminder,
+        value: "A new row".to_string(),
+        ..Default::default()
+      }],
+    })
+    .await
+    .unwrap();
+  assert!(automation.is_enabled);
+  assert_eq!(test.get_automations(&grid_view.id).await.items.len(), 1);
+
+  let mut changed = automation.clone();
+  changed.name = "Set the name".to_string();
+  changed.is_enabled = false;
+  changed.actions = vec![AutomationActionPB {
+    action_type: AutomationActionTypePB::UpdateCell,
+    field_id: fields[0].id.clone(),
+    value: "new row".to_string(),
+    ..Default::default()
+  }];
+  let error = test.update_automation(&grid_view.id, changed).await;
+  assert!(error.is_none());
+
+  let automations = test.get_automations(&grid_view.id).await.items;
+  assert_eq!(automations[0].id, automation.id);
+  assert_eq!(automations[0].name, "Set the name");
+  assert!(!automations[0].is_enabled);
+  assert_eq!(
+    automations[0].actions[0].action_type,
+    AutomationActionTypePB::UpdateCell
+  );
+
+  let error = test.delete_automation(&grid_view.id, &automation.id).await;
+  assert!(error.is_none());
+  assert!(test.get_automations(&grid_view.id).await.items.is_empty());
+}
+
+#[tokio::test]
+async fn create_automation_with_unknown_field_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+
+  let result = test
+    .create_automation(CreateAutomationPayloadPB {
+      view_id: grid_view.id.clone(),
+      name: "Unknown field".to_string(),
+      trigger: AutomationTriggerPB {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: "unknown".to_string(),
+        ..Default::default()
+      },
+      condition: None,
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::SendReminder,
+        value: "changed".to_string(),
+        ..Default::default()
+      }],
+    })
+    .await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn check_cell_runs_update_cell_automation_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let row_id = database.rows[0].id.clone();
+  let primary_field_id = fields[0].id.clone();
+  let checkbox_field_id = fields[2].id.clone();
+  assert_eq!(fields[2].field_type, FieldType::Checkbox);
+
+  test
+    .create_automation(CreateAutomationPayloadPB {
+      view_id: grid_view.id.clone(),
+      name: "Mark as done".to_string(),
+      trigger: AutomationTriggerPB {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: checkbox_field_id.clone(),
+        from_value: None,
+        to_value: Some("Yes".to_string()),
+      },
+      condition: None,
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::UpdateCell,
+        field_id: primary_field_id.clone(),
+        value: "done".to_string(),
+        ..Default::default()
+      }],
+    })
+    .await
+    .unwrap();
+
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: checkbox_field_id,
+      cell_changeset: "yes".to_string(),
+    })
+    .await;
+  assert!(error.is_none());
+
+  // The automation runs in the background.
+  let mut logs = vec![];
+  for _ in 0..20 {
+    logs = test.get_automation_logs(&grid_view.id).await.items;
+    if !logs.is_empty() {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+  }
+  assert_eq!(logs.len(), 1);
+  assert_eq!(logs[0].status, AutomationLogStatusPB::Succeeded);
+
+  let cell = test
+    .get_cell(&grid_view.id, &row_id, &primary_field_id)
+    .await;
+  assert_eq!(String::from_utf8(cell.data).unwrap(), "done");
+}
+
+#[tokio::test]
+async fn move_to_group_automation_keeps_row_position_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let board_view = test
+    .create_board(&current_workspace.id, "my board view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&board_view.id).await.items;
+  let row_ids = test
+    .get_database(&board_view.id)
+    .await
+    .rows
+    .into_iter()
+    .map(|row| row.id)
+    .collect::<Vec<_>>();
+  let row_id = row_ids[0].clone();
+  let to_group_id = test
+    .get_groups(&board_view.id)
+    .await
+    .into_iter()
+    .find(|group| !group.rows.iter().any(|row| row.id == row_id))
+    .unwrap()
+    .group_id;
+
+  test
+    .create_automation(CreateAutomationPayloadPB {
+      view_id: board_view.id.clone(),
+      name: "Move".to_string(),
+      trigger: AutomationTriggerPB {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: fields[0].id.clone(),
+        from_value: None,
+        to_value: Some("move".to_string()),
+      },
+      condition: None,
+      actions: vec![AutomationActionPB {
+        action_type: AutomationActionTypePB::MoveToGroup,
+        view_id: board_view.id.clone(),
+        group_id: to_group_id.clone(),
+        ..Default::default()
+      }],
+    })
+    .await
+    .unwrap();
+
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: board_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: fields[0].id.clone(),
+      cell_changeset: "move".to_string(),
+    })
+    .await;
+  assert!(error.is_none());
+
+  let mut logs = vec![];
+  for _ in 0..20 {
+    logs = test.get_automation_logs(&board_view.id).await.items;
+    if !logs.is_empty() {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+  }
+  assert_eq!(logs.len(), 1);
+  assert_eq!(logs[0].status, AutomationLogStatusPB::Succeeded);
+
+  let groups = test.get_groups(&board_view.id).await;
+  let to_group = groups
+    .iter()
+    .find(|group| group.group_id == to_group_id)
+    .unwrap();
+  assert!(to_group.rows.iter().any(|row| row.id == row_id));
+  let new_row_ids = test
+    .get_database(&board_view.id)
+    .await
+    .rows
+    .into_iter()
+    .map(|row| row.id)
+    .collect::<Vec<_>>();
+  assert_eq!(new_row_ids, row_ids);
+}
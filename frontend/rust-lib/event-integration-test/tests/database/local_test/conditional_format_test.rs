@@ -0,0 +1,151 @@
+use bytes::Bytes;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  CellChangesetPB, CellFormatPB, CheckboxFilterConditionPB, CheckboxFilterPB,
+  ConditionalFormatTargetPB, CreateConditionalFormatPayloadPB, FieldType, FilterDataPB,
+};
+
+fn checkbox_condition(field_id: &str, condition: CheckboxFilterConditionPB) -> FilterDataPB {
+  let data: Bytes = CheckboxFilterPB { condition }.try_into().unwrap();
+  FilterDataPB {
+    field_id: field_id.to_string(),
+    field_type: FieldType::Checkbox,
+    data: data.to_vec(),
+  }
+}
+
+#[tokio::test]
+async fn create_update_and_delete_conditional_format_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let checkbox_field_id = fields[2].id.clone();
+  assert_eq!(fields[2].field_type, FieldType::Checkbox);
+  assert!(test
+    .get_conditional_formats(&grid_view.id)
+    .await
+    .items
+    .is_empty());
+
+  let format = test
+    .create_conditional_format(CreateConditionalFormatPayloadPB {
+      view_id: grid_view.id.clone(),
+      condition: checkbox_condition(&checkbox_field_id, CheckboxFilterConditionPB::IsChecked),
+      target: ConditionalFormatTargetPB::Row,
+      background_color: Some("#00FF00".to_string()),
+      text_color: None,
+    })
+    .await
+    .unwrap();
+  let formats = test.get_conditional_formats(&grid_view.id).await.items;
+  assert_eq!(formats, vec![format.clone()]);
+
+  let mut changed = format.clone();
+  changed.target = ConditionalFormatTargetPB::Cell;
+  changed.text_color = Some("#FF0000".to_string());
+  let error = test.update_conditional_format(&grid_view.id, changed).await;
+  assert!(error.is_none());
+
+  let formats = test.get_conditional_formats(&grid_view.id).await.items;
+  assert_eq!(formats[0].id, format.id);
+  assert_eq!(formats[0].target, ConditionalFormatTargetPB::Cell);
+  assert_eq!(formats[0].text_color, Some("#FF0000".to_string()));
+
+  let error = test
+    .delete_conditional_format(&grid_view.id, &format.id)
+    .await;
+  assert!(error.is_none());
+  assert!(test
+    .get_conditional_formats(&grid_view.id)
+    .await
+    .items
+    .is_empty());
+}
+
+#[tokio::test]
+async fn create_conditional_format_with_unknown_field_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+
+  let result = test
+    .create_conditional_format(CreateConditionalFormatPayloadPB {
+      view_id: grid_view.id.clone(),
+      condition: checkbox_condition("unknown", CheckboxFilterConditionPB::IsChecked),
+      target: ConditionalFormatTargetPB::Row,
+      background_color: Some("#00FF00".to_string()),
+      text_color: None,
+    })
+    .await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn conditional_format_follows_cell_changes_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let checkbox_field_id = fields[2].id.clone();
+
+  test
+    .create_conditional_format(CreateConditionalFormatPayloadPB {
+      view_id: grid_view.id.clone(),
+      condition: checkbox_condition(&checkbox_field_id, CheckboxFilterConditionPB::IsChecked),
+      target: ConditionalFormatTargetPB::Row,
+      background_color: Some("#00FF00".to_string()),
+      text_color: None,
+    })
+    .await
+    .unwrap();
+  test
+    .create_conditional_format(CreateConditionalFormatPayloadPB {
+      view_id: grid_view.id.clone(),
+      condition: checkbox_condition(&checkbox_field_id, CheckboxFilterConditionPB::IsUnChecked),
+      target: ConditionalFormatTargetPB::Cell,
+      background_color: None,
+      text_color: Some("#FF0000".to_string()),
+    })
+    .await
+    .unwrap();
+
+  let unchecked_format = CellFormatPB {
+    field_id: Some(checkbox_field_id.clone()),
+    background_color: None,
+    text_color: Some("#FF0000".to_string()),
+  };
+  let database = test.get_database(&grid_view.id).await;
+  for row in database.rows.iter() {
+    assert_eq!(row.formats, vec![unchecked_format.clone()]);
+  }
+
+  let row_id = database.rows[0].id.clone();
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: checkbox_field_id.clone(),
+      cell_changeset: "yes".to_string(),
+    })
+    .await;
+  assert!(error.is_none());
+
+  let row_meta = test.get_row_meta(&grid_view.id, &row_id).await;
+  assert_eq!(
+    row_meta.formats,
+    vec![CellFormatPB {
+      field_id: None,
+      background_color: Some("#00FF00".to_string()),
+      text_color: None,
+    }]
+  );
+  let row_meta = test.get_row_meta(&grid_view.id, &database.rows[1].id).await;
+  assert_eq!(row_meta.formats, vec![unchecked_format]);
+}
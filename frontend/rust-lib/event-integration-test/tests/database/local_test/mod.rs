@@ -1,2 +1,12 @@
+mod ai_fill_test;
+mod automation_test;
+mod conditional_format_test;
 mod group_test;
+mod recurrence_test;
+mod relation_test;
+mod row_comment_test;
+mod search_test;
+mod snapshot_test;
+mod template_test;
 mod test;
+mod validation_test;
@@ -0,0 +1,142 @@
+use bytes::Bytes;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  CellIdPB, CheckboxCellDataPB, DateCellChangesetPB, FieldType, OrderObjectPositionPB,
+};
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[tokio::test]
+async fn expand_recurring_calendar_event_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let calendar_view = test
+    .create_calendar(&current_workspace.id, "my calendar view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&calendar_view.id).await.items;
+  let date_field = fields
+    .iter()
+    .find(|field| field.field_type == FieldType::DateTime)
+    .unwrap();
+  let row = test
+    .create_row(&calendar_view.id, OrderObjectPositionPB::default(), None)
+    .await;
+
+  let start = 1710000000;
+  let error = test
+    .update_date_cell(DateCellChangesetPB {
+      cell_id: CellIdPB {
+        view_id: calendar_view.id.clone(),
+        field_id: date_field.id.clone(),
+        row_id: row.id.clone(),
+      },
+      date: Some(start),
+      recurrence: Some("FREQ=DAILY;INTERVAL=2".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+
+  // Without a range, the row has a single event.
+  let events = test.get_all_calendar_events(&calendar_view.id).await;
+  assert_eq!(events.len(), 1);
+  assert!(events[0].is_recurring);
+
+  let events = test
+    .get_calendar_events_between(&calendar_view.id, start, start + 7 * DAY)
+    .await;
+  let timestamps = events
+    .iter()
+    .map(|event| event.timestamp)
+    .collect::<Vec<_>>();
+  assert_eq!(
+    timestamps,
+    vec![start, start + 2 * DAY, start + 4 * DAY, start + 6 * DAY]
+  );
+  assert!(events.iter().all(|event| event.row_meta.id == row.id));
+}
+
+#[tokio::test]
+async fn complete_recurring_task_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let date_field = test.create_field(&grid_view.id, FieldType::DateTime).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let checkbox_field = fields
+    .iter()
+    .find(|field| field.field_type == FieldType::Checkbox)
+    .unwrap();
+  let row_id = test.get_database(&grid_view.id).await.rows[0].id.clone();
+
+  let start = 1710000000;
+  let error = test
+    .update_date_cell(DateCellChangesetPB {
+      cell_id: CellIdPB {
+        view_id: grid_view.id.clone(),
+        field_id: date_field.id.clone(),
+        row_id: row_id.clone(),
+      },
+      date: Some(start),
+      recurrence: Some("FREQ=WEEKLY;COUNT=2".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+
+  let next_row = test
+    .complete_date_occurrence(
+      &grid_view.id,
+      &row_id,
+      &date_field.id,
+      Some(checkbox_field.id.clone()),
+    )
+    .await
+    .unwrap()
+    .row
+    .unwrap();
+
+  // The completed row keeps its date without the recurrence.
+  let date = test
+    .get_date_cell(&grid_view.id, &row_id, &date_field.id)
+    .await;
+  assert_eq!(date.timestamp, start);
+  assert!(date.recurrence.is_empty());
+  let cell = test
+    .get_cell(&grid_view.id, &row_id, &checkbox_field.id)
+    .await;
+  assert!(
+    CheckboxCellDataPB::try_from(Bytes::from(cell.data))
+      .unwrap()
+      .is_checked
+  );
+
+  // The copy is the next occurrence, which is the last one of the series.
+  let date = test
+    .get_date_cell(&grid_view.id, &next_row.id, &date_field.id)
+    .await;
+  assert_eq!(date.timestamp, start + 7 * DAY);
+  assert_eq!(date.recurrence, "FREQ=WEEKLY;COUNT=1");
+  let cell = test
+    .get_cell(&grid_view.id, &next_row.id, &checkbox_field.id)
+    .await;
+  assert!(
+    !CheckboxCellDataPB::try_from(Bytes::from(cell.data))
+      .unwrap()
+      .is_checked
+  );
+
+  let result = test
+    .complete_date_occurrence(&grid_view.id, &next_row.id, &date_field.id, None)
+    .await
+    .unwrap();
+  assert!(result.row.is_none());
+
+  // A date that doesn't repeat can't be completed.
+  let result = test
+    .complete_date_occurrence(&grid_view.id, &row_id, &date_field.id, None)
+    .await;
+  assert!(result.is_err());
+}
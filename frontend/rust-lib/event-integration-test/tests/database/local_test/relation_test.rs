@@ -0,0 +1,279 @@
+use std::time::Duration;
+
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  CalculationType, CellIdPB, FieldType, RelationCellChangesetPB, RollupTypeOptionPB,
+};
+
+/// Creates a tasks and a projects database, with a two-way relation from the tasks to the
+/// projects. Returns the view ids and the ids of the two relation fields.
+async fn create_two_way_relation(test: &EventIntegrationTest) -> (String, String, String, String) {
+  let current_workspace = test.get_current_workspace().await;
+  let tasks_view = test
+    .create_grid(&current_workspace.id, "Tasks".to_owned(), vec![])
+    .await;
+  let projects_view = test
+    .create_grid(&current_workspace.id, "Projects".to_owned(), vec![])
+    .await;
+  let projects_database = test.get_database(&projects_view.id).await;
+  let relation_field = test
+    .create_relation_field(&tasks_view.id, &projects_database.id, true)
+    .await;
+  let paired_field = test
+    .get_all_database_fields(&projects_view.id)
+    .await
+    .items
+    .into_iter()
+    .find(|field| field.field_type == FieldType::Relation)
+    .unwrap();
+  (
+    tasks_view.id,
+    relation_field.id,
+    projects_view.id,
+    paired_field.id,
+  )
+}
+
+fn relation_changeset(
+  view_id: &str,
+  field_id: &str,
+  row_id: &str,
+  inserted_row_ids: Vec<String>,
+  removed_row_ids: Vec<String>,
+) -> RelationCellChangesetPB {
+  RelationCellChangesetPB {
+    view_id: view_id.to_string(),
+    cell_id: CellIdPB {
+      view_id: view_id.to_string(),
+      field_id: field_id.to_string(),
+      row_id: row_id.to_string(),
+    },
+    inserted_row_ids,
+    removed_row_ids,
+  }
+}
+
+#[tokio::test]
+async fn create_two_way_relation_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let (tasks_view_id, relation_field_id, projects_view_id, paired_field_id) =
+    create_two_way_relation(&test).await;
+  let tasks_database = test.get_database(&tasks_view_id).await;
+
+  let type_option = test
+    .get_relation_type_option(&tasks_view_id, &relation_field_id)
+    .await;
+  assert!(type_option.show_on_related_database);
+  assert_eq!(type_option.paired_field_id, paired_field_id);
+
+  let paired_type_option = test
+    .get_relation_type_option(&projects_view_id, &paired_field_id)
+    .await;
+  assert_eq!(paired_type_option.database_id, tasks_database.id);
+  assert_eq!(paired_type_option.paired_field_id, relation_field_id);
+}
+
+#[tokio::test]
+async fn update_two_way_relation_cell_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let (tasks_view_id, relation_field_id, projects_view_id, paired_field_id) =
+    create_two_way_relation(&test).await;
+  let tasks = test.get_database(&tasks_view_id).await.rows;
+  let projects = test.get_database(&projects_view_id).await.rows;
+
+  test
+    .update_relation_cell(relation_changeset(
+      &tasks_view_id,
+      &relation_field_id,
+      &tasks[0].id,
+      vec![projects[0].id.clone(), projects[1].id.clone()],
+      vec![],
+    ))
+    .await;
+  for project in projects[0..2].iter() {
+    let cell = test
+      .get_relation_cell(&projects_view_id, &paired_field_id, &project.id)
+      .await;
+    assert_eq!(cell.row_ids, vec![tasks[0].id.clone()]);
+  }
+
+  // editing the paired field updates the original field
+  test
+    .update_relation_cell(relation_changeset(
+      &projects_view_id,
+      &paired_field_id,
+      &projects[1].id,
+      vec![],
+      vec![tasks[0].id.clone()],
+    ))
+    .await;
+  let cell = test
+    .get_relation_cell(&tasks_view_id, &relation_field_id, &tasks[0].id)
+    .await;
+  assert_eq!(cell.row_ids, vec![projects[0].id.clone()]);
+
+  // deleting a row removes the links to it
+  test.delete_row(&tasks_view_id, &tasks[0].id).await;
+  let cell = test
+    .get_relation_cell(&projects_view_id, &paired_field_id, &projects[0].id)
+    .await;
+  assert!(cell.row_ids.is_empty());
+}
+
+#[tokio::test]
+async fn delete_two_way_relation_field_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let (tasks_view_id, relation_field_id, projects_view_id, paired_field_id) =
+    create_two_way_relation(&test).await;
+  let tasks = test.get_database(&tasks_view_id).await.rows;
+  let projects = test.get_database(&projects_view_id).await.rows;
+  test
+    .update_relation_cell(relation_changeset(
+      &tasks_view_id,
+      &relation_field_id,
+      &tasks[0].id,
+      vec![projects[0].id.clone()],
+      vec![],
+    ))
+    .await;
+
+  let error = test.delete_field(&projects_view_id, &paired_field_id).await;
+  assert!(error.is_none());
+
+  // the remaining field becomes a one-way relation and keeps its cells
+  let type_option = test
+    .get_relation_type_option(&tasks_view_id, &relation_field_id)
+    .await;
+  assert!(!type_option.show_on_related_database);
+  assert!(type_option.paired_field_id.is_empty());
+  let cell = test
+    .get_relation_cell(&tasks_view_id, &relation_field_id, &tasks[0].id)
+    .await;
+  assert_eq!(cell.row_ids, vec![projects[0].id.clone()]);
+}
+
+#[tokio::test]
+async fn convert_two_way_relation_field_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let (tasks_view_id, relation_field_id, projects_view_id, paired_field_id) =
+    create_two_way_relation(&test).await;
+
+  let error = test
+    .update_field_type(&tasks_view_id, &relation_field_id, FieldType::RichText)
+    .await;
+  assert!(error.is_none());
+
+  let paired_type_option = test
+    .get_relation_type_option(&projects_view_id, &paired_field_id)
+    .await;
+  assert!(!paired_type_option.show_on_related_database);
+}
+
+#[tokio::test]
+async fn one_way_relation_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let tasks_view = test
+    .create_grid(&current_workspace.id, "Tasks".to_owned(), vec![])
+    .await;
+  let projects_view = test
+    .create_grid(&current_workspace.id, "Projects".to_owned(), vec![])
+    .await;
+  let projects_database = test.get_database(&projects_view.id).await;
+  let field_count = projects_database.fields.len();
+  test
+    .create_relation_field(&tasks_view.id, &projects_database.id, false)
+    .await;
+
+  let fields = test.get_all_database_fields(&projects_view.id).await.items;
+  assert_eq!(fields.len(), field_count);
+}
+
+#[tokio::test]
+async fn rollup_follows_relation_and_deleted_rows_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let tasks_view = test
+    .create_grid(&current_workspace.id, "Tasks".to_owned(), vec![])
+    .await;
+  let projects_view = test
+    .create_grid(&current_workspace.id, "Projects".to_owned(), vec![])
+    .await;
+  let projects_database = test.get_database(&projects_view.id).await;
+  let relation_field = test
+    .create_relation_field(&tasks_view.id, &projects_database.id, false)
+    .await;
+  let primary_field_id = test
+    .get_all_database_fields(&projects_view.id)
+    .await
+    .items
+    .into_iter()
+    .find(|field| field.is_primary)
+    .unwrap()
+    .id;
+  let rollup_field = test
+    .create_rollup_field(
+      &tasks_view.id,
+      RollupTypeOptionPB {
+        relation_field_id: relation_field.id.clone(),
+        target_field_id: primary_field_id,
+        calculation_type: CalculationType::Count,
+        show_original: false,
+      },
+    )
+    .await;
+  let tasks = test.get_database(&tasks_view.id).await.rows;
+  let projects = projects_database.rows;
+
+  test
+    .update_relation_cell(relation_changeset(
+      &tasks_view.id,
+      &relation_field.id,
+      &tasks[0].id,
+      vec![projects[0].id.clone(), projects[1].id.clone()],
+      vec![],
+    ))
+    .await;
+  // The rollup cells are updated in the background.
+  let mut cell = test
+    .get_rollup_cell(&tasks_view.id, &rollup_field.id, &tasks[0].id)
+    .await;
+  for _ in 0..20 {
+    if cell.value == "2" {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    cell = test
+      .get_rollup_cell(&tasks_view.id, &rollup_field.id, &tasks[0].id)
+      .await;
+  }
+  assert_eq!(cell.value, "2");
+
+  // removing a link drops the linked row from the rollup
+  test
+    .update_relation_cell(relation_changeset(
+      &tasks_view.id,
+      &relation_field.id,
+      &tasks[0].id,
+      vec![],
+      vec![projects[0].id.clone()],
+    ))
+    .await;
+  for _ in 0..20 {
+    if cell.value == "1" {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    cell = test
+      .get_rollup_cell(&tasks_view.id, &rollup_field.id, &tasks[0].id)
+      .await;
+  }
+  assert_eq!(cell.value, "1");
+
+  // the one-way relation keeps the link to a deleted row, but the rollup skips it
+  test.delete_row(&projects_view.id, &projects[1].id).await;
+  let cell = test
+    .get_rollup_cell(&tasks_view.id, &rollup_field.id, &tasks[0].id)
+    .await;
+  assert!(cell.value.is_empty());
+}
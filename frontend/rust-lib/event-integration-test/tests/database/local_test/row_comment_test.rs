@@ -0,0 +1,230 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  CalculationType, RowCommentPayloadPB, UpdateCalculationChangesetPB, UpdateRowMetaChangesetPB,
+};
+
+#[tokio::test]
+async fn add_reply_and_resolve_row_comment_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let row_id = database.rows[0].id.clone();
+  assert!(test
+    .get_row_comments(&grid_view.id, &row_id)
+    .await
+    .items
+    .is_empty());
+
+  let comment = test
+    .add_row_comment(&grid_view.id, &row_id, "Is this done?", None)
+    .await
+    .unwrap();
+  assert_eq!(comment.content, r#"[{"insert":"Is this done?"}]"#);
+  let reply = test
+    .add_row_comment(&grid_view.id, &row_id, "Yes", Some(comment.id.clone()))
+    .await
+    .unwrap();
+
+  let error = test
+    .resolve_row_comment(&grid_view.id, &row_id, &reply.id, true)
+    .await;
+  assert!(error.is_none());
+
+  let comments = test.get_row_comments(&grid_view.id, &row_id).await.items;
+  assert_eq!(comments.len(), 1);
+  assert_eq!(comments[0].id, comment.id);
+  assert!(comments[0].is_resolved);
+  assert_eq!(comments[0].replies, vec![reply]);
+
+  // The comments of a row aren't shared with the other rows.
+  assert!(test
+    .get_row_comments(&grid_view.id, &database.rows[1].id)
+    .await
+    .items
+    .is_empty());
+}
+
+#[tokio::test]
+async fn update_and_delete_row_comment_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let row_id = database.rows[0].id.clone();
+
+  let comment = test
+    .add_row_comment(&grid_view.id, &row_id, "Frist", None)
+    .await
+    .unwrap();
+  test
+    .add_row_comment(&grid_view.id, &row_id, "Reply", Some(comment.id.clone()))
+    .await
+    .unwrap();
+
+  let error = test
+    .update_row_comment(&grid_view.id, &row_id, &comment.id, "First")
+    .await;
+  assert!(error.is_none());
+  let comments = test.get_row_comments(&grid_view.id, &row_id).await.items;
+  assert_eq!(comments[0].content, r#"[{"insert":"First"}]"#);
+
+  let error = test
+    .delete_row_comment(&grid_view.id, &row_id, &comment.id)
+    .await;
+  assert!(error.is_none());
+  assert!(test
+    .get_row_comments(&grid_view.id, &row_id)
+    .await
+    .items
+    .is_empty());
+
+  let error = test
+    .delete_row_comment(&grid_view.id, &row_id, &comment.id)
+    .await;
+  assert!(error.is_some());
+}
+
+#[tokio::test]
+async fn count_comments_calculation_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let database = test.get_database(&grid_view.id).await;
+
+  let comment = test
+    .add_row_comment(&grid_view.id, &database.rows[0].id, "One", None)
+    .await
+    .unwrap();
+  test
+    .add_row_comment(
+      &grid_view.id,
+      &database.rows[0].id,
+      "Two",
+      Some(comment.id.clone()),
+    )
+    .await
+    .unwrap();
+  test
+    .add_row_comment(&grid_view.id, &database.rows[1].id, "Three", None)
+    .await
+    .unwrap();
+
+  test
+    .update_calculation(UpdateCalculationChangesetPB {
+      view_id: grid_view.id.clone(),
+      calculation_id: None,
+      field_id: fields[0].id.clone(),
+      calculation_type: CalculationType::CountComments,
+    })
+    .await;
+
+  let calculations = test.get_all_calculations(&grid_view.id).await.items;
+  assert_eq!(calculations.len(), 1);
+  assert_eq!(
+    calculations[0].calculation_type,
+    CalculationType::CountComments
+  );
+  assert_eq!(calculations[0].value, "3");
+}
+
+#[tokio::test]
+async fn duplicate_row_without_comments_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let row_id = database.rows[0].id.clone();
+  test
+    .add_row_comment(&grid_view.id, &row_id, "Is this done?", None)
+    .await
+    .unwrap();
+
+  let error = test.duplicate_row(&grid_view.id, &row_id).await;
+  assert!(error.is_none());
+
+  // The comments belong to the original row.
+  let rows = test.get_database(&grid_view.id).await.rows;
+  assert_eq!(rows.len(), database.rows.len() + 1);
+  let copy = rows
+    .iter()
+    .find(|row| database.rows.iter().all(|old_row| old_row.id != row.id))
+    .unwrap();
+  assert!(test
+    .get_row_comments(&grid_view.id, &copy.id)
+    .await
+    .items
+    .is_empty());
+  assert_eq!(
+    test
+      .get_row_comments(&grid_view.id, &row_id)
+      .await
+      .items
+      .len(),
+    1
+  );
+}
+
+#[tokio::test]
+async fn insert_row_comment_with_row_meta_changeset_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let row_id = database.rows[0].id.clone();
+  let uid = test.get_user_profile().await.unwrap().id;
+
+  let changeset = UpdateRowMetaChangesetPB {
+    id: row_id.clone(),
+    view_id: grid_view.id.clone(),
+    icon_url: Some("icon_url".to_owned()),
+    cover_url: None,
+    is_document_empty: None,
+    insert_comment: Some(RowCommentPayloadPB {
+      uid: uid.to_string(),
+      comment: "Is this done?".to_owned(),
+    }),
+  };
+  let error = test.update_row_meta(changeset).await;
+  assert!(error.is_none());
+
+  let comments = test.get_row_comments(&grid_view.id, &row_id).await.items;
+  assert_eq!(comments.len(), 1);
+  assert_eq!(comments[0].content, r#"[{"insert":"Is this done?"}]"#);
+  let row = test.get_row_meta(&grid_view.id, &row_id).await;
+  assert_eq!(row.icon, Some("icon_url".to_owned()));
+
+  // A comment can't be added in the name of another user.
+  let changeset = UpdateRowMetaChangesetPB {
+    id: row_id.clone(),
+    view_id: grid_view.id.clone(),
+    icon_url: None,
+    cover_url: None,
+    is_document_empty: None,
+    insert_comment: Some(RowCommentPayloadPB {
+      uid: (uid + 1).to_string(),
+      comment: "Yes".to_owned(),
+    }),
+  };
+  let error = test.update_row_meta(changeset).await;
+  assert!(error.is_some());
+  assert_eq!(
+    test
+      .get_row_comments(&grid_view.id, &row_id)
+      .await
+      .items
+      .len(),
+    1
+  );
+}
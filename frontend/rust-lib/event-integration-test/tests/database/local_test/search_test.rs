@@ -0,0 +1,117 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::CellChangesetPB;
+use flowy_search::entities::{IndexTypePB, SearchFilterPB};
+use flowy_search::services::query::SearchQuery;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn search_database_row_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "CRM".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let row_id = database.rows[0].id.clone();
+  let field = fields[0].clone();
+
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: field.id.clone(),
+      cell_changeset: "Acme Corporation".to_string(),
+    })
+    .await;
+  assert!(error.is_none());
+  // The rows are indexed in the background
+  sleep(Duration::from_secs(3)).await;
+
+  let filter = SearchFilterPB {
+    workspace_id: Some(current_workspace.id.clone()),
+    ..Default::default()
+  };
+  let results = test
+    .get_database_search_handler()
+    .perform_search(SearchQuery::parse("acme"), Some(filter.clone()))
+    .await
+    .unwrap();
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].index_type, IndexTypePB::DatabaseRow);
+  assert_eq!(results[0].view_id, grid_view.id);
+  assert_eq!(results[0].id, row_id);
+  assert_eq!(results[0].data, grid_view.name);
+  assert_eq!(results[0].preview, Some("Acme Corporation".to_string()));
+  let result_field = results[0].field.clone().unwrap();
+  assert_eq!(result_field.field_id, field.id);
+  assert_eq!(result_field.name, field.name);
+
+  // The index follows the changes of the cell
+  test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: field.id.clone(),
+      cell_changeset: "Globex".to_string(),
+    })
+    .await;
+  sleep(Duration::from_secs(3)).await;
+  let results = test
+    .get_database_search_handler()
+    .perform_search(SearchQuery::parse("acme"), Some(filter.clone()))
+    .await
+    .unwrap();
+  assert!(results.is_empty());
+
+  // Deleted rows are removed from the index
+  test.delete_row(&grid_view.id, &row_id).await;
+  let results = test
+    .get_database_search_handler()
+    .perform_search(SearchQuery::parse("globex"), Some(filter))
+    .await
+    .unwrap();
+  assert!(results.is_empty());
+}
+
+#[tokio::test]
+async fn search_trashed_database_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Customers".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+
+  test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: database.rows[0].id.clone(),
+      field_id: fields[0].id.clone(),
+      cell_changeset: "Initech".to_string(),
+    })
+    .await;
+  sleep(Duration::from_secs(3)).await;
+
+  let filter = SearchFilterPB {
+    workspace_id: Some(current_workspace.id),
+    ..Default::default()
+  };
+  let results = test
+    .get_database_search_handler()
+    .perform_search(SearchQuery::parse("initech"), Some(filter.clone()))
+    .await
+    .unwrap();
+  assert_eq!(results.len(), 1);
+
+  // The rows of a database in the trash should not be returned
+  test.delete_view(&grid_view.id).await;
+  let results = test
+    .get_database_search_handler()
+    .perform_search(SearchQuery::parse("initech"), Some(filter))
+    .await
+    .unwrap();
+  assert!(results.is_empty());
+}
@@ -0,0 +1,151 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  CellChangesetPB, DiffChangeTypePB, FieldType, OrderObjectPositionPB,
+};
+
+#[tokio::test]
+async fn restore_database_snapshot_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Grid".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let primary_field = fields.iter().find(|field| field.is_primary).unwrap();
+  let row_id = database.rows[0].id.clone();
+
+  test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: primary_field.id.clone(),
+      cell_changeset: "v1".to_string(),
+    })
+    .await;
+  let snapshot = test.create_database_snapshot(&grid_view.id, "v1").await;
+  assert!(snapshot.snapshot_id > 0);
+
+  // Edit the database after taking the snapshot
+  test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: primary_field.id.clone(),
+      cell_changeset: "v2".to_string(),
+    })
+    .await;
+  test
+    .create_row(&grid_view.id, OrderObjectPositionPB::default(), None)
+    .await;
+  test.create_field(&grid_view.id, FieldType::Checkbox).await;
+  test.delete_row(&grid_view.id, &database.rows[1].id).await;
+
+  let error = test
+    .restore_database_snapshot(&grid_view.id, snapshot.snapshot_id)
+    .await;
+  assert!(error.is_none());
+
+  let restored_database = test.get_database(&grid_view.id).await;
+  let restored_row_ids = restored_database
+    .rows
+    .iter()
+    .map(|row| row.id.clone())
+    .collect::<Vec<_>>();
+  assert_eq!(restored_row_ids.len(), database.rows.len());
+  for row in database.rows.iter() {
+    assert!(restored_row_ids.contains(&row.id));
+  }
+  let restored_fields = test.get_all_database_fields(&grid_view.id).await.items;
+  assert_eq!(restored_fields.len(), fields.len());
+  let text = test
+    .get_text_cell(&grid_view.id, &row_id, &primary_field.id)
+    .await;
+  assert_eq!(text, "v1");
+}
+
+#[tokio::test]
+async fn restore_database_snapshot_of_other_database_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Grid".to_owned(), vec![])
+    .await;
+  let other_grid_view = test
+    .create_grid(&current_workspace.id, "Other Grid".to_owned(), vec![])
+    .await;
+  let snapshot = test
+    .create_database_snapshot(&other_grid_view.id, "v1")
+    .await;
+
+  let error = test
+    .restore_database_snapshot(&grid_view.id, snapshot.snapshot_id)
+    .await;
+  assert!(error.is_some());
+}
+
+#[tokio::test]
+async fn database_snapshot_diff_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "Grid".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let primary_field = fields.iter().find(|field| field.is_primary).unwrap();
+  let row_id = database.rows[0].id.clone();
+
+  test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: primary_field.id.clone(),
+      cell_changeset: "v1".to_string(),
+    })
+    .await;
+  let v1 = test.create_database_snapshot(&grid_view.id, "v1").await;
+
+  test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: primary_field.id.clone(),
+      cell_changeset: "v2".to_string(),
+    })
+    .await;
+  let new_field = test.create_field(&grid_view.id, FieldType::Checkbox).await;
+  test.delete_row(&grid_view.id, &database.rows[1].id).await;
+  let v2 = test.create_database_snapshot(&grid_view.id, "v2").await;
+
+  let diff = test
+    .get_database_snapshot_diff(&grid_view.id, v1.snapshot_id, Some(v2.snapshot_id))
+    .await;
+  assert_eq!(diff.fields.len(), 1);
+  assert_eq!(diff.fields[0].field_id, new_field.id);
+  assert_eq!(diff.fields[0].change_type, DiffChangeTypePB::Added);
+
+  let updated_row = diff.rows.iter().find(|row| row.row_id == row_id).unwrap();
+  assert_eq!(updated_row.change_type, DiffChangeTypePB::Updated);
+  assert_eq!(updated_row.cells.len(), 1);
+  assert_eq!(updated_row.cells[0].old_value, "v1");
+  assert_eq!(updated_row.cells[0].new_value, "v2");
+
+  let removed_row = diff
+    .rows
+    .iter()
+    .find(|row| row.row_id == database.rows[1].id)
+    .unwrap();
+  assert_eq!(removed_row.change_type, DiffChangeTypePB::Removed);
+
+  // compare the snapshot with the current state of the database
+  test
+    .create_row(&grid_view.id, OrderObjectPositionPB::default(), None)
+    .await;
+  let diff = test
+    .get_database_snapshot_diff(&grid_view.id, v2.snapshot_id, None)
+    .await;
+  assert!(diff.fields.is_empty());
+  assert_eq!(diff.rows.len(), 1);
+  assert_eq!(diff.rows[0].change_type, DiffChangeTypePB::Added);
+}
@@ -0,0 +1,212 @@
+use std::convert::TryFrom;
+
+use bytes::Bytes;
+
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{
+  CalculationType, CellChangesetPB, FieldType, SelectOptionCellDataPB, UpdateCalculationChangesetPB,
+};
+use flowy_folder::entities::{ImportPayloadPB, ImportTypePB, ImportValuePayloadPB, ViewLayoutPB};
+
+#[tokio::test]
+async fn create_databases_from_template_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let row_id = database.rows[0].id.clone();
+  let select_field_id = fields[1].id.clone();
+  let error = test
+    .insert_option(&grid_view.id, &select_field_id, &row_id, "task 1")
+    .await;
+  assert!(error.is_none());
+  let cell = test
+    .get_cell(&grid_view.id, &row_id, &select_field_id)
+    .await;
+  let option = SelectOptionCellDataPB::try_from(Bytes::from(cell.data))
+    .unwrap()
+    .select_options
+    .remove(0);
+
+  let template = test.export_database_template(&grid_view.id, true).await;
+
+  // The template can be used many times, every database has its own ids.
+  let mut database_ids = vec![database.id.clone()];
+  for _ in 0..2 {
+    let views = test
+      .import_data(gen_import_data(&current_workspace.id, &template))
+      .await;
+    let view_id = views[0].id.clone();
+    let new_database = test.get_database(&view_id).await;
+    assert!(!database_ids.contains(&new_database.id));
+    assert_eq!(new_database.rows.len(), 3);
+    assert!(new_database
+      .rows
+      .iter()
+      .all(|row| database.rows.iter().all(|old_row| old_row.id != row.id)));
+
+    let new_fields = test.get_all_database_fields(&view_id).await.items;
+    assert_eq!(new_fields.len(), fields.len());
+    assert!(new_fields
+      .iter()
+      .all(|field| fields.iter().all(|old_field| old_field.id != field.id)));
+
+    // The cell refers to the regenerated option.
+    let cell = test
+      .get_cell(&view_id, &new_database.rows[0].id, &new_fields[1].id)
+      .await;
+    let new_options = SelectOptionCellDataPB::try_from(Bytes::from(cell.data))
+      .unwrap()
+      .select_options;
+    assert_eq!(new_options.len(), 1);
+    assert_eq!(new_options[0].name, option.name);
+    assert_ne!(new_options[0].id, option.id);
+
+    database_ids.push(new_database.id);
+  }
+}
+
+#[tokio::test]
+async fn create_database_from_template_without_rows_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+
+  let template = test.export_database_template(&grid_view.id, false).await;
+  let views = test
+    .import_data(gen_import_data(&current_workspace.id, &template))
+    .await;
+
+  let database = test.get_database(&views[0].id).await;
+  assert!(database.rows.is_empty());
+  let fields = test.get_all_database_fields(&views[0].id).await.items;
+  assert_eq!(fields.len(), 3);
+}
+
+#[tokio::test]
+async fn create_database_from_template_keeps_cell_content_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let row_id = database.rows[0].id.clone();
+
+  // A text that happens to be the id of a field is content, not a reference to the field
+  let content = fields[1].id.clone();
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: fields[0].id.clone(),
+      cell_changeset: content.clone(),
+    })
+    .await;
+  assert!(error.is_none());
+
+  let template = test.export_database_template(&grid_view.id, true).await;
+  let views = test
+    .import_data(gen_import_data(&current_workspace.id, &template))
+    .await;
+  let view_id = views[0].id.clone();
+  let new_database = test.get_database(&view_id).await;
+  let new_fields = test.get_all_database_fields(&view_id).await.items;
+  assert_ne!(new_fields[1].id, content);
+
+  let text = test
+    .get_text_cell(&view_id, &new_database.rows[0].id, &new_fields[0].id)
+    .await;
+  assert_eq!(text, content);
+}
+
+#[tokio::test]
+async fn create_database_from_template_keeps_calculations_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  test
+    .update_calculation(UpdateCalculationChangesetPB {
+      view_id: grid_view.id.clone(),
+      calculation_id: None,
+      field_id: fields[0].id.clone(),
+      calculation_type: CalculationType::Count,
+    })
+    .await;
+
+  let template = test.export_database_template(&grid_view.id, true).await;
+  let views = test
+    .import_data(gen_import_data(&current_workspace.id, &template))
+    .await;
+  let view_id = views[0].id.clone();
+  let new_fields = test.get_all_database_fields(&view_id).await.items;
+
+  // The calculation is on the regenerated field.
+  let calculations = test.get_all_calculations(&view_id).await.items;
+  assert_eq!(calculations.len(), 1);
+  assert_eq!(calculations[0].field_id, new_fields[0].id);
+  assert_eq!(calculations[0].calculation_type, CalculationType::Count);
+}
+
+#[tokio::test]
+async fn create_database_from_template_with_two_way_self_relation_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  test
+    .create_relation_field(&grid_view.id, &database.id, true)
+    .await;
+
+  let template = test.export_database_template(&grid_view.id, true).await;
+  let views = test
+    .import_data(gen_import_data(&current_workspace.id, &template))
+    .await;
+  let view_id = views[0].id.clone();
+  let new_database = test.get_database(&view_id).await;
+  let relation_field_ids = test
+    .get_all_database_fields(&view_id)
+    .await
+    .items
+    .into_iter()
+    .filter(|field| field.field_type == FieldType::Relation)
+    .map(|field| field.id)
+    .collect::<Vec<_>>();
+  assert_eq!(relation_field_ids.len(), 2);
+
+  // The two relation fields point to the new database and are paired with each other.
+  for (field_id, paired_field_id) in [
+    (&relation_field_ids[0], &relation_field_ids[1]),
+    (&relation_field_ids[1], &relation_field_ids[0]),
+  ] {
+    let type_option = test.get_relation_type_option(&view_id, field_id).await;
+    assert_eq!(type_option.database_id, new_database.id);
+    assert_eq!(&type_option.paired_field_id, paired_field_id);
+  }
+}
+
+fn gen_import_data(workspace_id: &str, template: &str) -> ImportPayloadPB {
+  ImportPayloadPB {
+    parent_view_id: workspace_id.to_string(),
+    sync_after_create: false,
+    values: vec![ImportValuePayloadPB {
+      name: "template".to_string(),
+      data: Some(template.as_bytes().to_vec()),
+      file_path: None,
+      view_layout: ViewLayoutPB::Grid,
+      import_type: ImportTypePB::DatabaseTemplate,
+      csv_options: None,
+    }],
+  }
+}
@@ -7,7 +7,8 @@ use event_integration_test::EventIntegrationTest;
 use flowy_database2::entities::{
   CellChangesetPB, CellIdPB, CheckboxCellDataPB, ChecklistCellDataChangesetPB, DatabaseLayoutPB,
   DatabaseSettingChangesetPB, DatabaseViewIdPB, DateCellChangesetPB, FieldType,
-  OrderObjectPositionPB, RelationCellChangesetPB, SelectOptionCellDataPB, UpdateRowMetaChangesetPB,
+  MoveTimelineEventPB, OrderObjectPositionPB, RelationCellChangesetPB, SelectOptionCellDataPB,
+  UpdateRowMetaChangesetPB,
 };
 use lib_infra::util::timestamp;
 
@@ -268,6 +269,7 @@ async fn update_row_meta_event_with_url_test() {
     icon_url: Some("icon_url".to_owned()),
     cover_url: None,
     is_document_empty: None,
+    insert_comment: None,
   };
   let error = test.update_row_meta(changeset).await;
   assert!(error.is_none());
@@ -297,6 +299,7 @@ async fn update_row_meta_event_with_cover_test() {
     cover_url: Some("cover url".to_owned()),
     icon_url: None,
     is_document_empty: None,
+    insert_comment: None,
   };
   let error = test.update_row_meta(changeset).await;
   assert!(error.is_none());
@@ -682,6 +685,28 @@ async fn update_database_layout_event_test() {
   assert_eq!(database.layout_type, DatabaseLayoutPB::Board);
 }
 
+// Update the database layout type from grid to gallery
+#[tokio::test]
+async fn update_database_layout_to_gallery_event_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+
+  let error = test
+    .update_setting(DatabaseSettingChangesetPB {
+      view_id: grid_view.id.clone(),
+      layout_type: Some(DatabaseLayoutPB::Gallery),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+
+  let database = test.get_database(&grid_view.id).await;
+  assert_eq!(database.layout_type, DatabaseLayoutPB::Gallery);
+}
+
 // Update the database layout type from grid to board. Set the checkbox field as the grouping field
 #[tokio::test]
 async fn update_database_layout_event_test2() {
@@ -781,6 +806,50 @@ async fn create_calendar_event_test() {
   assert_eq!(events.len(), 1);
 }
 
+#[tokio::test]
+async fn move_timeline_event_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let error = test
+    .update_setting(DatabaseSettingChangesetPB {
+      view_id: grid_view.id.clone(),
+      layout_type: Some(DatabaseLayoutPB::Timeline),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+
+  // Each row is displayed as an unscheduled bar
+  let database = test.get_database(&grid_view.id).await;
+  let events = test.get_all_timeline_events(&grid_view.id).await;
+  assert_eq!(events.len(), database.rows.len());
+  assert!(events.iter().all(|event| !event.is_scheduled));
+
+  let start_timestamp = timestamp();
+  let end_timestamp = start_timestamp + 3 * 24 * 60 * 60;
+  let error = test
+    .move_timeline_event(MoveTimelineEventPB {
+      view_id: grid_view.id.clone(),
+      row_id: database.rows[0].id.clone(),
+      start_timestamp,
+      end_timestamp,
+    })
+    .await;
+  assert!(error.is_none());
+
+  let events = test.get_all_timeline_events(&grid_view.id).await;
+  let event = events
+    .iter()
+    .find(|event| event.row_meta.id == database.rows[0].id)
+    .unwrap();
+  assert!(event.is_scheduled);
+  assert_eq!(event.start_timestamp, start_timestamp);
+  assert_eq!(event.end_timestamp, end_timestamp);
+}
+
 #[tokio::test]
 async fn update_relation_cell_test() {
   let test = EventIntegrationTest::new_anon().await;
@@ -0,0 +1,222 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{CellChangesetPB, FieldValidationPB, ValidationRulePB};
+use flowy_user::errors::ErrorCode;
+
+#[tokio::test]
+async fn validate_database_with_required_field_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let primary_field_id = fields[0].id.clone();
+  assert!(test.validate_database(&grid_view.id).await.items.is_empty());
+
+  let error = test
+    .update_field_validation(
+      &grid_view.id,
+      &primary_field_id,
+      FieldValidationPB {
+        is_required: true,
+        ..Default::default()
+      },
+    )
+    .await;
+  assert!(error.is_none());
+
+  // The rows of the default grid are empty, so all of them break the rule.
+  let violations = test.validate_database(&grid_view.id).await.items;
+  assert_eq!(violations.len(), 3);
+  assert!(violations.iter().all(|violation| {
+    violation.field_id == primary_field_id && violation.rule == ValidationRulePB::Required
+  }));
+
+  let database = test.get_database(&grid_view.id).await;
+  let row_id = database.rows[0].id.clone();
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: row_id.clone(),
+      field_id: primary_field_id.clone(),
+      cell_changeset: "hello".to_string(),
+    })
+    .await;
+  assert!(error.is_none());
+
+  let violations = test.validate_database(&grid_view.id).await.items;
+  assert_eq!(violations.len(), 2);
+  assert!(violations
+    .iter()
+    .all(|violation| violation.row_id != row_id));
+}
+
+#[tokio::test]
+async fn reject_cell_that_breaks_validation_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let primary_field_id = fields[0].id.clone();
+  test
+    .update_field_validation(
+      &grid_view.id,
+      &primary_field_id,
+      FieldValidationPB {
+        is_unique: true,
+        pattern: Some("^[a-z]+$".to_string()),
+        ..Default::default()
+      },
+    )
+    .await;
+
+  let database = test.get_database(&grid_view.id).await;
+  let changeset = |row_id: &str, text: &str| CellChangesetPB {
+    view_id: grid_view.id.clone(),
+    row_id: row_id.to_string(),
+    field_id: primary_field_id.clone(),
+    cell_changeset: text.to_string(),
+  };
+  let error = test
+    .update_cell(changeset(&database.rows[0].id, "hello"))
+    .await;
+  assert!(error.is_none());
+
+  let error = test
+    .update_cell(changeset(&database.rows[1].id, "hello"))
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::CellValidationFailed);
+
+  let error = test
+    .update_cell(changeset(&database.rows[1].id, "Hello World"))
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::CellValidationFailed);
+
+  let cell = test
+    .get_text_cell(&grid_view.id, &database.rows[1].id, &primary_field_id)
+    .await;
+  assert!(cell.is_empty());
+  assert!(test.validate_database(&grid_view.id).await.items.is_empty());
+}
+
+#[tokio::test]
+async fn update_field_validation_with_invalid_pattern_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+
+  let error = test
+    .update_field_validation(
+      &grid_view.id,
+      &fields[0].id,
+      FieldValidationPB {
+        pattern: Some("[a-z".to_string()),
+        ..Default::default()
+      },
+    )
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+}
+
+#[tokio::test]
+async fn validation_applies_to_every_view_of_the_database_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let linked_view = test
+    .create_linked_grid(&grid_view.id, "linked view".to_owned(), &database.id)
+    .await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let primary_field_id = fields[0].id.clone();
+  let error = test
+    .update_field_validation(
+      &grid_view.id,
+      &primary_field_id,
+      FieldValidationPB {
+        is_unique: true,
+        ..Default::default()
+      },
+    )
+    .await;
+  assert!(error.is_none());
+
+  let fields = test.get_all_database_fields(&linked_view.id).await.items;
+  assert!(fields[0].validation.is_unique);
+
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: grid_view.id.clone(),
+      row_id: database.rows[0].id.clone(),
+      field_id: primary_field_id.clone(),
+      cell_changeset: "hello".to_string(),
+    })
+    .await;
+  assert!(error.is_none());
+
+  let error = test
+    .update_cell(CellChangesetPB {
+      view_id: linked_view.id.clone(),
+      row_id: database.rows[1].id.clone(),
+      field_id: primary_field_id.clone(),
+      cell_changeset: "hello".to_string(),
+    })
+    .await
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::CellValidationFailed);
+}
+
+#[tokio::test]
+async fn validation_does_not_block_system_writes_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let fields = test.get_all_database_fields(&grid_view.id).await.items;
+  let primary_field_id = fields[0].id.clone();
+  for row in database.rows.iter() {
+    test
+      .update_cell(CellChangesetPB {
+        view_id: grid_view.id.clone(),
+        row_id: row.id.clone(),
+        field_id: primary_field_id.clone(),
+        cell_changeset: "hello".to_string(),
+      })
+      .await;
+  }
+  test
+    .update_field_validation(
+      &grid_view.id,
+      &primary_field_id,
+      FieldValidationPB {
+        is_required: true,
+        ..Default::default()
+      },
+    )
+    .await;
+
+  // Clearing the field writes every cell of the field, the cells that break the rules are
+  // reported by the validation of the database instead.
+  let error = test.clear_field(&grid_view.id, &primary_field_id).await;
+  assert!(error.is_none());
+  for row in database.rows.iter() {
+    let cell = test
+      .get_text_cell(&grid_view.id, &row.id, &primary_field_id)
+      .await;
+    assert!(cell.is_empty());
+  }
+  let violations = test.validate_database(&grid_view.id).await.items;
+  assert_eq!(violations.len(), database.rows.len());
+}
@@ -1,2 +1,3 @@
 mod edit_test;
+mod search_test;
 // mod snapshot_test;
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use event_integration_test::document::document_event::DocumentEventTest;
+use event_integration_test::EventIntegrationTest;
+use flowy_search::entities::{IndexTypePB, SearchFilterPB};
+use flowy_search::services::query::SearchQuery;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn search_document_content_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document_test = DocumentEventTest::new_with_core(test.clone());
+  let workspace_id = test.get_current_workspace().await.id;
+  let view = document_test.create_document().await;
+  document_test
+    .insert_index(&view.id, "Grocery list for the weekend", 1, None)
+    .await;
+  let block_id = document_test
+    .insert_index(&view.id, "Buy apples, pears and more apples", 2, None)
+    .await;
+
+  // Wait for the changes to be indexed
+  sleep(Duration::from_secs(3)).await;
+
+  let filter = SearchFilterPB {
+    workspace_id: Some(workspace_id.clone()),
+    ..Default::default()
+  };
+  let results = test
+    .get_document_search_handler()
+    .perform_search(SearchQuery::parse("apples"), Some(filter.clone()))
+    .await
+    .unwrap();
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].index_type, IndexTypePB::DocumentBlock);
+  assert_eq!(results[0].view_id, view.id);
+  assert_eq!(results[0].id, block_id);
+  assert_eq!(results[0].data, view.name);
+
+  let preview = results[0].preview.clone().unwrap();
+  assert_eq!(preview, "Buy apples, pears and more apples");
+  let highlighted = results[0]
+    .highlights
+    .iter()
+    .map(|highlight| {
+      preview
+        .chars()
+        .skip(highlight.start as usize)
+        .take((highlight.end - highlight.start) as usize)
+        .collect::<String>()
+    })
+    .collect::<Vec<_>>();
+  assert_eq!(highlighted, vec!["apples", "apples"]);
+
+  // All the terms must appear in the same block
+  let results = test
+    .get_document_search_handler()
+    .perform_search(SearchQuery::parse("grocery pears"), Some(filter))
+    .await
+    .unwrap();
+  assert!(results.is_empty());
+}
+
+#[tokio::test]
+async fn search_deleted_document_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document_test = DocumentEventTest::new_with_core(test.clone());
+  let workspace_id = test.get_current_workspace().await.id;
+  let view = document_test.create_document().await;
+  document_test
+    .insert_index(&view.id, "Meeting notes", 1, None)
+    .await;
+
+  // Wait for the changes to be indexed
+  sleep(Duration::from_secs(3)).await;
+
+  let filter = SearchFilterPB {
+    workspace_id: Some(workspace_id),
+    ..Default::default()
+  };
+  let results = test
+    .get_document_search_handler()
+    .perform_search(SearchQuery::parse("meeting"), Some(filter.clone()))
+    .await
+    .unwrap();
+  assert_eq!(results.len(), 1);
+
+  // The document in the trash should not be returned
+  test.delete_view(&view.id).await;
+  let results = test
+    .get_document_search_handler()
+    .perform_search(SearchQuery::parse("meeting"), Some(filter))
+    .await
+    .unwrap();
+  assert!(results.is_empty());
+}
+
+#[tokio::test]
+async fn search_document_with_query_syntax_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let document_test = DocumentEventTest::new_with_core(test.clone());
+  let workspace_id = test.get_current_workspace().await.id;
+  let projects = test.create_view(&workspace_id, "Projects".to_owned()).await;
+  let roadmap = test.create_view(&projects.id, "Roadmap".to_owned()).await;
+  document_test
+    .insert_index(&roadmap.id, "Launch the mobile app in spring", 1, None)
+    .await;
+  let notes = document_test.create_document().await;
+  document_test
+    .insert_index(&notes.id, "Launch party for the desktop app", 1, None)
+    .await;
+
+  // Wait for the changes to be indexed
+  sleep(Duration::from_secs(3)).await;
+
+  let filter = SearchFilterPB {
+    workspace_id: Some(workspace_id.clone()),
+    ..Default::default()
+  };
+  let search = |query: &'static str, filter: SearchFilterPB| {
+    let test = test.clone();
+    async move {
+      test
+        .get_document_search_handler()
+        .perform_search(SearchQuery::parse(query), Some(filter))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|result| result.view_id)
+        .collect::<Vec<_>>()
+    }
+  };
+
+  assert_eq!(search("launch app", filter.clone()).await.len(), 2);
+  assert_eq!(
+    search("\"mobile app\"", filter.clone()).await,
+    vec![roadmap.id.clone()]
+  );
+  assert_eq!(
+    search("launch -mobile", filter.clone()).await,
+    vec![notes.id.clone()]
+  );
+  assert_eq!(
+    search("launch in:projects", filter.clone()).await,
+    vec![roadmap.id.clone()]
+  );
+  assert!(search("launch type:grid", filter.clone()).await.is_empty());
+
+  let space_filter = SearchFilterPB {
+    ancestor_view_id: Some(projects.id.clone()),
+    ..filter.clone()
+  };
+  assert_eq!(
+    search("launch", space_filter).await,
+    vec![roadmap.id.clone()]
+  );
+
+  let favorites_filter = SearchFilterPB {
+    favorites_only: true,
+    ..filter
+  };
+  assert!(search("launch", favorites_filter).await.is_empty());
+}
@@ -0,0 +1,88 @@
+use event_integration_test::event_builder::EventBuilder;
+use event_integration_test::EventIntegrationTest;
+use flowy_core::DEFAULT_NAME;
+use flowy_folder::entities::{
+  ImportPayloadPB, ImportTypePB, ImportValuePayloadPB, RepeatedTrashIdPB, TrashIdPB, ViewLayoutPB,
+};
+use flowy_folder::event_map::FolderEvent;
+use nanoid::nanoid;
+use std::env::temp_dir;
+use std::io::Write;
+use zip::write::FileOptions;
+use zip::ZipWriter;
+
+#[tokio::test]
+async fn page_mention_backlinks_test() {
+  let zip_path = temp_dir().join(format!("{}.zip", nanoid!(6)));
+  {
+    let mut zip = ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+    let files = [
+      ("Home.md", "# Home\n\nSee [Notes](Notes.md)"),
+      ("Notes.md", "# Notes\n\nHello"),
+    ];
+    for (name, content) in files {
+      zip.start_file(name, FileOptions::default()).unwrap();
+      zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+  }
+
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let import_data = ImportPayloadPB {
+    parent_view_id: workspace_id,
+    sync_after_create: false,
+    values: vec![ImportValuePayloadPB {
+      name: "".to_string(),
+      data: None,
+      file_path: Some(zip_path.to_str().unwrap().to_string()),
+      view_layout: ViewLayoutPB::Document,
+      import_type: ImportTypePB::Zip,
+      csv_options: None,
+    }],
+  };
+  let views = test.import_data(import_data).await;
+  let (home, notes) = (&views[0], &views[1]);
+  assert_eq!(notes.name, "Notes");
+
+  // The link to the page is imported as a mention, which is indexed when the document is created
+  let backlinks = test.get_view_backlinks(&notes.id).await;
+  assert_eq!(backlinks.len(), 1);
+  assert_eq!(backlinks[0].view.id, home.id);
+  assert_eq!(backlinks[0].block_ids.len(), 1);
+  assert!(test.get_view_backlinks(&home.id).await.is_empty());
+
+  // The mention is marked when the page is moved to trash
+  test.delete_view(&notes.id).await;
+  let deltas = test
+    .get_document_data(&home.id)
+    .await
+    .meta
+    .text_map
+    .unwrap();
+  assert!(deltas
+    .values()
+    .any(|delta| delta.contains("\"is_trashed\":true")));
+
+  // The mention is replaced by the name of the page when the page is deleted permanently
+  EventBuilder::new(test.clone())
+    .event(FolderEvent::PermanentlyDeleteTrashItem)
+    .payload(RepeatedTrashIdPB {
+      items: vec![TrashIdPB {
+        id: notes.id.clone(),
+      }],
+    })
+    .async_send()
+    .await;
+  let deltas = test
+    .get_document_data(&home.id)
+    .await
+    .meta
+    .text_map
+    .unwrap();
+  assert!(!deltas.values().any(|delta| delta.contains(&notes.id)));
+  assert!(deltas.values().any(|delta| delta.contains("Notes")));
+
+  std::fs::remove_file(zip_path).unwrap();
+}
@@ -0,0 +1,51 @@
+use std::collections::HashSet;
+use std::env::temp_dir;
+use std::fs::File;
+use std::time::Duration;
+
+use event_integration_test::folder_event::ViewTest;
+use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::WorkspaceExportProgressPB;
+use flowy_folder::notification::FolderNotification;
+use nanoid::nanoid;
+use tokio::time::timeout;
+use zip::ZipArchive;
+
+#[tokio::test]
+async fn export_workspace_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let parent = test.create_view(&workspace_id, "Notes".to_string()).await;
+  test.create_view(&parent.id, "Notes".to_string()).await;
+  // The grid is named "View A"
+  ViewTest::new_grid_view(&test, vec![]).await;
+  test.create_view(&workspace_id, "View A".to_string()).await;
+
+  let mut rx = test
+    .notification_sender
+    .subscribe::<WorkspaceExportProgressPB>(
+      &workspace_id,
+      FolderNotification::DidUpdateWorkspaceExportProgress,
+    );
+  let file_path = temp_dir().join(format!("{}.zip", nanoid!(6)));
+  let export = test.export_workspace(file_path.to_str().unwrap()).await;
+  assert!(export.num_of_views >= 4);
+  assert!(export.failed_view_ids.is_empty());
+
+  // The progress is reported after every view
+  let mut last_progress = None;
+  while let Ok(Some(progress)) = timeout(Duration::from_secs(1), rx.recv()).await {
+    last_progress = Some(progress);
+  }
+  let last_progress = last_progress.unwrap();
+  assert_eq!(last_progress.num_of_exported_views, export.num_of_views);
+
+  let archive = ZipArchive::new(File::open(&file_path).unwrap()).unwrap();
+  let names = archive.file_names().collect::<HashSet<_>>();
+  assert!(names.contains("Notes.md"));
+  assert!(names.contains("Notes/Notes.md"));
+  assert!(names.contains("View A.csv"));
+  assert!(names.contains("View A.json"));
+  assert!(names.contains("View A (2).md"));
+  std::fs::remove_file(file_path).unwrap();
+}
@@ -1,7 +1,15 @@
 use crate::util::unzip;
 use event_integration_test::EventIntegrationTest;
 use flowy_core::DEFAULT_NAME;
-use flowy_folder::entities::{ImportPayloadPB, ImportTypePB, ImportValuePayloadPB, ViewLayoutPB};
+use flowy_database2::entities::FieldType;
+use flowy_folder::entities::{
+  CSVImportOptionsPB, ImportPayloadPB, ImportTypePB, ImportValuePayloadPB, ViewLayoutPB,
+};
+use nanoid::nanoid;
+use std::env::temp_dir;
+use std::io::Write;
+use zip::write::FileOptions;
+use zip::ZipWriter;
 
 #[tokio::test]
 async fn import_492_row_csv_file_test() {
@@ -44,6 +52,288 @@ async fn import_10240_row_csv_file_test() {
   drop(cleaner);
 }
 
+#[tokio::test]
+async fn import_csv_with_options_test() {
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+
+  let workspace_id = test.get_current_workspace().await.id;
+  let mut import_data = gen_import_data(
+    "options.csv".to_string(),
+    "Apple;3;yes\nBanana;5;no\nCherry;7;yes".to_string(),
+    workspace_id,
+  );
+  import_data.values[0].csv_options = Some(CSVImportOptionsPB {
+    delimiter: ";".to_string(),
+    has_header: false,
+    encoding: "utf-8".to_string(),
+  });
+
+  let views = test.import_data(import_data).await;
+  let view_id = views[0].clone().id;
+  let database = test.get_database(&view_id).await;
+  assert_eq!(database.rows.len(), 3);
+
+  let fields = test.get_all_database_fields(&view_id).await.items;
+  let fields = fields
+    .iter()
+    .map(|field| (field.name.as_str(), field.field_type))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    fields,
+    vec![
+      ("Column 1", FieldType::RichText),
+      ("Column 2", FieldType::Number),
+      ("Column 3", FieldType::Checkbox),
+    ]
+  );
+}
+
+#[tokio::test]
+async fn import_csv_returns_failed_rows_test() {
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+
+  let workspace_id = test.get_current_workspace().await.id;
+  let import_data = gen_import_data(
+    "failed_rows.csv".to_string(),
+    "Name,Number\na,1\nb,2,3\nc,3".to_string(),
+    workspace_id,
+  );
+
+  let result = test.import_data_with_result(import_data).await;
+  let view_id = result.items[0].id.clone();
+  assert_eq!(test.get_database(&view_id).await.rows.len(), 2);
+  assert_eq!(result.failed_rows.len(), 1);
+  assert_eq!(result.failed_rows[0].view_id, view_id);
+  assert_eq!(result.failed_rows[0].line, 3);
+}
+
+#[tokio::test]
+async fn import_csv_file_path_with_options_test() {
+  let file_path = temp_dir().join(format!("{}.csv", nanoid!(6)));
+  std::fs::write(&file_path, "Apple;3\nBanana;5").unwrap();
+
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let import_data = ImportPayloadPB {
+    parent_view_id: workspace_id,
+    sync_after_create: false,
+    values: vec![ImportValuePayloadPB {
+      name: "fruits".to_string(),
+      data: None,
+      file_path: Some(file_path.to_str().unwrap().to_string()),
+      view_layout: ViewLayoutPB::Grid,
+      import_type: ImportTypePB::CSV,
+      csv_options: Some(CSVImportOptionsPB {
+        delimiter: ";".to_string(),
+        has_header: false,
+        encoding: "utf-8".to_string(),
+      }),
+    }],
+  };
+
+  let result = test.import_data_with_result(import_data).await;
+  assert!(result.failed_rows.is_empty());
+  let database = test.get_database(&result.items[0].id).await;
+  assert_eq!(database.rows.len(), 2);
+  std::fs::remove_file(file_path).unwrap();
+}
+
+#[tokio::test]
+async fn import_markdown_folder_test() {
+  let folder = temp_dir().join(nanoid!(6));
+  std::fs::create_dir_all(&folder).unwrap();
+  std::fs::write(folder.join("b.md"), "# Second\n\n- item").unwrap();
+  std::fs::write(folder.join("a.md"), "# First\n\nHello **World**").unwrap();
+  std::fs::write(folder.join("ignored.txt"), "not markdown").unwrap();
+
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let import_data = ImportPayloadPB {
+    parent_view_id: workspace_id,
+    sync_after_create: false,
+    values: vec![ImportValuePayloadPB {
+      name: "".to_string(),
+      data: None,
+      file_path: Some(folder.to_str().unwrap().to_string()),
+      view_layout: ViewLayoutPB::Document,
+      import_type: ImportTypePB::Markdown,
+      csv_options: None,
+    }],
+  };
+
+  let views = test.import_data(import_data).await;
+  let names = views
+    .iter()
+    .map(|view| view.name.clone())
+    .collect::<Vec<String>>();
+  assert_eq!(names, vec!["a", "b"]);
+
+  let document = test.get_document_data(&views[0].id).await;
+  let types = document
+    .blocks
+    .values()
+    .map(|block| block.ty.clone())
+    .collect::<Vec<String>>();
+  assert!(types.contains(&"heading".to_string()));
+  assert!(types.contains(&"paragraph".to_string()));
+
+  std::fs::remove_dir_all(folder).unwrap();
+}
+
+#[tokio::test]
+async fn import_notion_zip_test() {
+  let zip_path = temp_dir().join(format!("{}.zip", nanoid!(6)));
+  {
+    let mut zip = ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+    let files = [
+      (
+        "Home 8e3ff4e4a5a14a2fa5b8b2b2a7f2e4c1.md",
+        "# Home\n\nSee [Notes](Home%208e3ff4e4a5a14a2fa5b8b2b2a7f2e4c1/Notes%200f1e2d3c4b5a69788796a5b4c3d2e1f0.md)\n\n![logo](Home%208e3ff4e4a5a14a2fa5b8b2b2a7f2e4c1/logo.png)",
+      ),
+      (
+        "Home 8e3ff4e4a5a14a2fa5b8b2b2a7f2e4c1/Notes 0f1e2d3c4b5a69788796a5b4c3d2e1f0.md",
+        "# Notes\n\nHello",
+      ),
+      (
+        "Home 8e3ff4e4a5a14a2fa5b8b2b2a7f2e4c1/logo.png",
+        "png",
+      ),
+      (
+        "Tasks 1a2b3c4d5e6f708192a3b4c5d6e7f809.csv",
+        "Name,Done,Points\nWrite,Yes,1\nRead,No,2\nSleep,Yes,3",
+      ),
+      (
+        "Tasks 1a2b3c4d5e6f708192a3b4c5d6e7f809/Write 9a8b7c6d5e4f30211203f4e5d6c7b8a9.md",
+        "# Write\n\nWrite the docs",
+      ),
+    ];
+    for (name, content) in files {
+      zip.start_file(name, FileOptions::default()).unwrap();
+      zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+  }
+
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let import_data = ImportPayloadPB {
+    parent_view_id: workspace_id,
+    sync_after_create: false,
+    values: vec![ImportValuePayloadPB {
+      name: "".to_string(),
+      data: None,
+      file_path: Some(zip_path.to_str().unwrap().to_string()),
+      view_layout: ViewLayoutPB::Document,
+      import_type: ImportTypePB::Zip,
+      csv_options: None,
+    }],
+  };
+  let views = test.import_data(import_data).await;
+  let names = views
+    .iter()
+    .map(|view| view.name.clone())
+    .collect::<Vec<String>>();
+  assert_eq!(names, vec!["Home", "Tasks"]);
+
+  // The sub-pages are the children of the page, the pages of the rows are imported to the
+  // documents of the rows
+  let home = test.get_view(&views[0].id).await;
+  assert_eq!(home.child_views.len(), 1);
+  let notes = &home.child_views[0];
+  assert_eq!(notes.name, "Notes");
+  let tasks = test.get_view(&views[1].id).await;
+  assert_eq!(tasks.layout, ViewLayoutPB::Grid);
+  assert!(tasks.child_views.is_empty());
+  let write_row = test.get_database(&tasks.id).await.rows[0].clone();
+  assert!(!write_row.is_document_empty);
+  let row_document = test.get_document_data(&write_row.document_id).await;
+  let row_deltas = row_document.meta.text_map.unwrap();
+  assert!(row_deltas
+    .values()
+    .any(|delta| delta.contains("Write the docs")));
+
+  // The link to the sub-page is converted to a mention, and the title heading is removed
+  let document = test.get_document_data(&home.id).await;
+  let deltas = document.meta.text_map.unwrap();
+  assert!(deltas.values().any(|delta| delta.contains(&notes.id)));
+  assert!(!deltas.values().any(|delta| delta.contains("\"Home\"")));
+
+  // The attachment can't be uploaded without the cloud, so it's copied into the storage of the
+  // app before the unzipped files are deleted
+  let image_path = document
+    .blocks
+    .values()
+    .find_map(|block| block.data.get("url").and_then(|url| url.as_str()))
+    .unwrap();
+  assert!(std::path::Path::new(image_path).is_file());
+  assert_eq!(std::fs::read_to_string(image_path).unwrap(), "png");
+
+  let field_types = test
+    .get_all_database_fields(&tasks.id)
+    .await
+    .items
+    .into_iter()
+    .map(|field| field.field_type)
+    .collect::<Vec<FieldType>>();
+  assert_eq!(
+    field_types,
+    vec![FieldType::RichText, FieldType::Checkbox, FieldType::Number]
+  );
+
+  std::fs::remove_file(zip_path).unwrap();
+}
+
+#[tokio::test]
+async fn import_zip_returns_failed_paths_test() {
+  let zip_path = temp_dir().join(format!("{}.zip", nanoid!(6)));
+  {
+    let mut zip = ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+    let files: [(&str, &[u8]); 2] = [
+      // A page that isn't valid utf-8 can't be imported
+      ("Broken.md", &[0xff, 0xfe, 0xfd]),
+      ("Broken/Child.md", b"# Child\n\nHello"),
+    ];
+    for (name, content) in files {
+      zip.start_file(name, FileOptions::default()).unwrap();
+      zip.write_all(content).unwrap();
+    }
+    zip.finish().unwrap();
+  }
+
+  let test = EventIntegrationTest::new_with_name(DEFAULT_NAME).await;
+  test.sign_up_as_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let import_data = ImportPayloadPB {
+    parent_view_id: workspace_id,
+    sync_after_create: false,
+    values: vec![ImportValuePayloadPB {
+      name: "".to_string(),
+      data: None,
+      file_path: Some(zip_path.to_str().unwrap().to_string()),
+      view_layout: ViewLayoutPB::Document,
+      import_type: ImportTypePB::Zip,
+      csv_options: None,
+    }],
+  };
+  let result = test.import_data_with_result(import_data).await;
+  assert_eq!(result.failed_paths, vec!["Broken.md".to_string()]);
+
+  // The child of the page that can't be imported takes its place
+  let names = result
+    .items
+    .iter()
+    .map(|view| view.name.clone())
+    .collect::<Vec<String>>();
+  assert_eq!(names, vec!["Child"]);
+
+  std::fs::remove_file(zip_path).unwrap();
+}
+
 fn gen_import_data(file_name: String, csv_string: String, workspace_id: String) -> ImportPayloadPB {
   ImportPayloadPB {
     parent_view_id: workspace_id.clone(),
@@ -54,6 +344,7 @@ fn gen_import_data(file_name: String, csv_string: String, workspace_id: String)
       file_path: None,
       view_layout: ViewLayoutPB::Grid,
       import_type: ImportTypePB::CSV,
+      csv_options: None,
     }],
   }
 }
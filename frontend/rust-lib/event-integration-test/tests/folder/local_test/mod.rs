@@ -1,6 +1,9 @@
+mod backlinks_test;
+mod export_test;
 mod folder_test;
 mod import_test;
 mod script;
+mod snapshot_test;
 mod subscription_test;
 mod test;
 mod view_publish_test;
@@ -0,0 +1,52 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_folder::entities::UpdateViewPayloadPB;
+
+#[tokio::test]
+async fn restore_folder_snapshot_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let renamed_view = test.create_view(&workspace_id, "A".to_string()).await;
+  let trashed_view = test.create_view(&workspace_id, "B".to_string()).await;
+  let snapshot = test.create_folder_snapshot("v1").await;
+  assert!(snapshot.snapshot_id > 0);
+
+  // Edit the folder after taking the snapshot
+  test
+    .update_view(UpdateViewPayloadPB {
+      view_id: renamed_view.id.clone(),
+      name: Some("Renamed".to_string()),
+      ..Default::default()
+    })
+    .await;
+  test.delete_view(&trashed_view.id).await;
+  let new_view = test.create_view(&workspace_id, "C".to_string()).await;
+
+  let error = test.restore_folder_snapshot(snapshot.snapshot_id).await;
+  assert!(error.is_none());
+
+  assert_eq!(test.get_view(&renamed_view.id).await.name, "A");
+  let trash_ids = test
+    .get_trash()
+    .await
+    .items
+    .into_iter()
+    .map(|trash| trash.id)
+    .collect::<Vec<_>>();
+  assert!(!trash_ids.contains(&trashed_view.id));
+  assert!(trash_ids.contains(&new_view.id));
+
+  // The state before restoring is saved as a checkpoint, so the restore can be reverted
+  let snapshots = test
+    .folder_manager
+    .get_folder_snapshots(&workspace_id, 10)
+    .await
+    .unwrap();
+  assert!(snapshots.len() >= 2);
+}
+
+#[tokio::test]
+async fn restore_not_exist_folder_snapshot_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let error = test.restore_folder_snapshot(1).await;
+  assert!(error.is_some());
+}
@@ -1,3 +1,5 @@
+use std::env::temp_dir;
+
 use collab_folder::ViewLayout;
 use event_integration_test::EventIntegrationTest;
 use flowy_folder::entities::{ViewLayoutPB, ViewPB};
@@ -5,6 +7,7 @@ use flowy_folder::publish_util::generate_publish_name;
 use flowy_folder_pub::entities::{
   PublishViewInfo, PublishViewMeta, PublishViewMetaData, PublishViewPayload,
 };
+use nanoid::nanoid;
 
 async fn mock_single_document_view_publish_payload(
   test: &EventIntegrationTest,
@@ -190,3 +193,54 @@ async fn no_children_publish_view_payload_test() {
   assert_eq!(&payload[0].data, &data[0]);
   assert_eq!(&payload[0].meta, &meta[0]);
 }
+
+#[tokio::test]
+async fn publish_view_to_local_site_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let workspace_id = test.get_current_workspace().await.id;
+  let parent = test.create_view(&workspace_id, "Notes".to_string()).await;
+  let child = test.create_view(&parent.id, "Meeting".to_string()).await;
+  let grid = test
+    .create_grid(&parent.id, "Tasks".to_string(), vec![])
+    .await;
+
+  let site_dir = temp_dir().join(format!("site_{}", nanoid!(6)));
+  let site = test
+    .publish_view_to_local_site(
+      &parent.id,
+      site_dir.to_str().unwrap(),
+      Some("my site".to_string()),
+    )
+    .await;
+  assert_eq!(site.num_of_pages, 3);
+
+  let namespace_dir = site_dir.join("my-site");
+  assert_eq!(
+    site.index_path,
+    namespace_dir.join("index.html").to_str().unwrap()
+  );
+  let parent_publish_name = generate_publish_name(&parent.id, &parent.name);
+  let index = std::fs::read_to_string(&site.index_path).unwrap();
+  assert!(index.contains(&format!("./{}/index.html", parent_publish_name)));
+
+  // Every page links to the other pages in the sidebar
+  let page =
+    std::fs::read_to_string(namespace_dir.join(&parent_publish_name).join("index.html")).unwrap();
+  for view in [&child, &grid] {
+    let publish_name = generate_publish_name(&view.id, &view.name);
+    assert!(page.contains(&format!("../{}/index.html", publish_name)));
+    assert!(namespace_dir
+      .join(&publish_name)
+      .join("index.html")
+      .is_file());
+  }
+
+  let grid_page = std::fs::read_to_string(
+    namespace_dir
+      .join(generate_publish_name(&grid.id, &grid.name))
+      .join("index.html"),
+  )
+  .unwrap();
+  assert!(grid_page.contains("<table class=\"database-grid\">"));
+  std::fs::remove_dir_all(site_dir).unwrap();
+}
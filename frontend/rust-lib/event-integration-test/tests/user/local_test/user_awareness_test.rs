@@ -1,9 +1,21 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
+use chrono::{TimeZone, Utc};
+use event_integration_test::document::document_event::DocumentEventTest;
 use event_integration_test::event_builder::EventBuilder;
 use event_integration_test::EventIntegrationTest;
-use flowy_user::entities::{ReminderPB, RepeatedReminderPB};
+use flowy_database2::entities::{CellIdPB, DateCellChangesetPB, FieldType};
+use flowy_user::entities::{
+  ReminderFiredPB, ReminderPB, RepeatedReminderPB, SnoozeReminderPB, REMINDER_FIRED_AT_KEY,
+  REMINDER_ROW_ID_KEY,
+};
 use flowy_user::event_map::UserEvent::*;
+use flowy_user::notification::UserNotification;
+use lib_infra::util::timestamp;
+use serde_json::json;
+
+use crate::util::receive_with_timeout;
 
 #[tokio::test]
 async fn user_update_with_reminder() {
@@ -21,6 +33,7 @@ async fn user_update_with_reminder() {
     message: "".to_string(),
     object_id: "".to_string(),
     meta,
+    recurrence: "".to_string(),
   };
 
   let _ = EventBuilder::new(sdk.clone())
@@ -38,3 +51,349 @@ async fn user_update_with_reminder() {
 
   assert_eq!(reminders.len(), 1);
 }
+
+#[tokio::test]
+async fn acknowledge_repeating_reminder_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.sign_up_as_anon().await;
+  let scheduled_at = 1710000000;
+  let payload = ReminderPB {
+    id: "reminder".to_string(),
+    scheduled_at,
+    title: "Water the plants".to_string(),
+    recurrence: "FREQ=DAILY;COUNT=2".to_string(),
+    ..Default::default()
+  };
+  let error = EventBuilder::new(sdk.clone())
+    .event(CreateReminder)
+    .payload(payload.clone())
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  // Acknowledging the reminder moves it to the next occurrence.
+  let error = EventBuilder::new(sdk.clone())
+    .event(UpdateReminder)
+    .payload(ReminderPB {
+      is_ack: true,
+      ..payload
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let reminder = get_reminders(&sdk).await.remove(0);
+  assert_eq!(reminder.scheduled_at, scheduled_at + 24 * 60 * 60);
+  assert!(!reminder.is_ack);
+  assert_eq!(reminder.recurrence, "FREQ=DAILY;COUNT=1");
+
+  // The series ends after the last occurrence.
+  let error = EventBuilder::new(sdk.clone())
+    .event(UpdateReminder)
+    .payload(ReminderPB {
+      is_ack: true,
+      ..reminder
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let reminder = get_reminders(&sdk).await.remove(0);
+  assert!(reminder.is_ack);
+  assert!(reminder.recurrence.is_empty());
+}
+
+#[tokio::test]
+async fn create_reminder_with_invalid_recurrence_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.sign_up_as_anon().await;
+  let error = EventBuilder::new(sdk.clone())
+    .event(CreateReminder)
+    .payload(ReminderPB {
+      id: "reminder".to_string(),
+      recurrence: "FREQ=HOURLY".to_string(),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+}
+
+#[tokio::test]
+async fn fire_missed_reminder_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let uid = sdk.sign_up_as_anon().await.user_profile.id;
+  let rx = sdk
+    .notification_sender
+    .subscribe::<ReminderFiredPB>(&uid.to_string(), UserNotification::DidFireReminder);
+
+  // The reminder came due before the app started.
+  let scheduled_at = 1710000000;
+  let error = EventBuilder::new(sdk.clone())
+    .event(CreateReminder)
+    .payload(ReminderPB {
+      id: "reminder".to_string(),
+      scheduled_at,
+      title: "Water the plants".to_string(),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let fired = receive_with_timeout(rx, Duration::from_secs(10))
+    .await
+    .unwrap();
+  assert_eq!(fired.reminder.id, "reminder");
+  assert!(fired.is_missed);
+
+  // The reminder fires once for its scheduled time.
+  let reminder = get_reminders(&sdk).await.remove(0);
+  assert_eq!(
+    reminder.meta.get(REMINDER_FIRED_AT_KEY),
+    Some(&scheduled_at.to_string())
+  );
+}
+
+#[tokio::test]
+async fn snooze_reminder_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.sign_up_as_anon().await;
+  let error = EventBuilder::new(sdk.clone())
+    .event(CreateReminder)
+    .payload(ReminderPB {
+      id: "reminder".to_string(),
+      scheduled_at: timestamp(),
+      is_read: true,
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let snooze_until = timestamp() + 10 * 60;
+  let error = EventBuilder::new(sdk.clone())
+    .event(SnoozeReminder)
+    .payload(SnoozeReminderPB {
+      id: "reminder".to_string(),
+      snooze_until,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let reminder = get_reminders(&sdk).await.remove(0);
+  assert_eq!(reminder.scheduled_at, snooze_until);
+  assert!(!reminder.is_read);
+
+  // A reminder can't be snoozed to the past.
+  let error = EventBuilder::new(sdk.clone())
+    .event(SnoozeReminder)
+    .payload(SnoozeReminderPB {
+      id: "reminder".to_string(),
+      snooze_until: timestamp() - 60,
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_some());
+}
+
+#[tokio::test]
+async fn reschedule_reminder_with_date_cell_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.sign_up_as_anon().await;
+  let current_workspace = sdk.get_current_workspace().await;
+  let grid_view = sdk
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = sdk.get_database(&grid_view.id).await;
+  let date_field = sdk.create_field(&grid_view.id, FieldType::DateTime).await;
+  let cell_id = CellIdPB {
+    view_id: grid_view.id.clone(),
+    field_id: date_field.id.clone(),
+    row_id: database.rows[0].id.clone(),
+  };
+
+  // The reminder fires an hour before the date.
+  let date = timestamp() + 24 * 60 * 60;
+  let error = sdk
+    .update_date_cell(DateCellChangesetPB {
+      cell_id: cell_id.clone(),
+      date: Some(date),
+      reminder_id: Some("reminder".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  let error = EventBuilder::new(sdk.clone())
+    .event(CreateReminder)
+    .payload(ReminderPB {
+      id: "reminder".to_string(),
+      object_id: grid_view.id.clone(),
+      scheduled_at: date - 60 * 60,
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let new_date = date + 2 * 24 * 60 * 60;
+  let error = sdk
+    .update_date_cell(DateCellChangesetPB {
+      cell_id,
+      date: Some(new_date),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+
+  // The reminder is rescheduled in the background.
+  let mut scheduled_at = 0;
+  for _ in 0..20 {
+    scheduled_at = get_reminders(&sdk).await.remove(0).scheduled_at;
+    if scheduled_at != date - 60 * 60 {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+  }
+  assert_eq!(scheduled_at, new_date - 60 * 60);
+}
+
+#[tokio::test]
+async fn reschedule_reminder_with_recurring_date_cell_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.sign_up_as_anon().await;
+  let current_workspace = sdk.get_current_workspace().await;
+  let grid_view = sdk
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = sdk.get_database(&grid_view.id).await;
+  let date_field = sdk.create_field(&grid_view.id, FieldType::DateTime).await;
+  let row_id = database.rows[0].id.clone();
+
+  let date = timestamp() + 24 * 60 * 60;
+  let error = sdk
+    .update_date_cell(DateCellChangesetPB {
+      cell_id: CellIdPB {
+        view_id: grid_view.id.clone(),
+        field_id: date_field.id.clone(),
+        row_id: row_id.clone(),
+      },
+      date: Some(date),
+      recurrence: Some("FREQ=DAILY".to_string()),
+      reminder_id: Some("reminder".to_string()),
+      ..Default::default()
+    })
+    .await;
+  assert!(error.is_none());
+  let error = EventBuilder::new(sdk.clone())
+    .event(CreateReminder)
+    .payload(ReminderPB {
+      id: "reminder".to_string(),
+      object_id: grid_view.id.clone(),
+      scheduled_at: date,
+      meta: HashMap::from([(REMINDER_ROW_ID_KEY.to_string(), row_id.clone())]),
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  // The reminder moves to the row of the next occurrence.
+  let next_row = sdk
+    .complete_date_occurrence(&grid_view.id, &row_id, &date_field.id, None)
+    .await
+    .unwrap()
+    .row
+    .unwrap();
+  let mut reminder = get_reminders(&sdk).await.remove(0);
+  for _ in 0..20 {
+    if reminder.scheduled_at != date {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    reminder = get_reminders(&sdk).await.remove(0);
+  }
+  assert_eq!(reminder.scheduled_at, date + 24 * 60 * 60);
+  assert_eq!(reminder.meta.get(REMINDER_ROW_ID_KEY), Some(&next_row.id));
+}
+
+#[tokio::test]
+async fn reschedule_reminder_with_date_mention_test() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.sign_up_as_anon().await;
+  let document_test = DocumentEventTest::new_with_core(sdk.clone());
+  let view = document_test.create_document().await;
+  let block_id = document_test.insert_index(&view.id, "Due", 1, None).await;
+
+  // The reminder fires at the date.
+  let date = timestamp() + 24 * 60 * 60;
+  document_test
+    .apply_delta_for_block(&view.id, &block_id, date_mention_delta(date, false))
+    .await;
+  let error = EventBuilder::new(sdk.clone())
+    .event(CreateReminder)
+    .payload(ReminderPB {
+      id: "reminder".to_string(),
+      object_id: view.id.clone(),
+      scheduled_at: date,
+      ..Default::default()
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let new_date = date + 2 * 24 * 60 * 60;
+  document_test
+    .apply_delta_for_block(&view.id, &block_id, date_mention_delta(new_date, true))
+    .await;
+
+  // The reminder is rescheduled in the background.
+  let mut scheduled_at = 0;
+  for _ in 0..20 {
+    scheduled_at = get_reminders(&sdk).await.remove(0).scheduled_at;
+    if scheduled_at != date {
+      break;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+  }
+  assert_eq!(scheduled_at, new_date);
+}
+
+/// Inserts the date mention with the reminder after the text of the block, "Due", or replaces
+/// the one that is there.
+fn date_mention_delta(date: i64, replace: bool) -> String {
+  let date = Utc.timestamp_opt(date, 0).unwrap().to_rfc3339();
+  let mention = json!({
+    "insert": "$",
+    "attributes": {
+      "mention": { "type": "date", "date": date, "reminder_id": "reminder" }
+    }
+  });
+  let ops = if replace {
+    json!([{ "retain": 3 }, { "delete": 1 }, mention])
+  } else {
+    json!([{ "retain": 3 }, mention])
+  };
+  ops.to_string()
+}
+
+async fn get_reminders(sdk: &EventIntegrationTest) -> Vec<ReminderPB> {
+  EventBuilder::new(sdk.clone())
+    .event(GetAllReminders)
+    .async_send()
+    .await
+    .parse::<RepeatedReminderPB>()
+    .items
+}
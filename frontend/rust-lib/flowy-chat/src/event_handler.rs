@@ -338,3 +338,24 @@ pub(crate) async fn get_local_ai_state_handler(
   let enabled = chat_manager.local_ai_controller.is_enabled();
   data_result_ok(LocalAIPB { enabled })
 }
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn toggle_local_ai_database_handler(
+  chat_manager: AFPluginState<Weak<ChatManager>>,
+) -> DataResult<LocalAIPB, FlowyError> {
+  let chat_manager = upgrade_chat_manager(chat_manager)?;
+  let enabled = chat_manager
+    .local_ai_controller
+    .toggle_local_ai_database()
+    .await?;
+  data_result_ok(LocalAIPB { enabled })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_local_ai_database_state_handler(
+  chat_manager: AFPluginState<Weak<ChatManager>>,
+) -> DataResult<LocalAIPB, FlowyError> {
+  let chat_manager = upgrade_chat_manager(chat_manager)?;
+  let enabled = chat_manager.local_ai_controller.is_database_enabled();
+  data_result_ok(LocalAIPB { enabled })
+}
@@ -53,6 +53,14 @@ pub fn init(chat_manager: Weak<ChatManager>) -> AFPlugin {
       ChatEvent::ToggleChatWithFile,
       toggle_local_ai_chat_file_handler,
     )
+    .event(
+      ChatEvent::ToggleLocalAIDatabase,
+      toggle_local_ai_database_handler,
+    )
+    .event(
+      ChatEvent::GetLocalAIDatabaseState,
+      get_local_ai_database_state_handler,
+    )
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Display, Hash, ProtoBuf_Enum, Flowy_Event)]
@@ -126,4 +134,12 @@ pub enum ChatEvent {
 
   #[event()]
   ToggleChatWithFile = 20,
+
+  /// Use the local AI for the summary and the translate fields of the databases, even if the
+  /// user is signed in to AppFlowy Cloud.
+  #[event(output = "LocalAIPB")]
+  ToggleLocalAIDatabase = 21,
+
+  #[event(output = "LocalAIPB")]
+  GetLocalAIDatabaseState = 22,
 }
@@ -4,7 +4,7 @@ pub mod event_map;
 mod chat;
 pub mod chat_manager;
 pub mod entities;
-mod local_ai;
+pub mod local_ai;
 mod middleware;
 pub mod notification;
 mod persistence;
@@ -22,6 +22,55 @@ use std::sync::Arc;
 use tokio_stream::StreamExt;
 use tracing::{debug, error, info, trace};
 
+/// The chat operations of the local AI plugin that are used to answer a prompt.
+#[async_trait]
+trait PromptChat: Send + Sync {
+  async fn create_chat(&self, chat_id: &str) -> FlowyResult<()>;
+  async fn ask_question(&self, chat_id: &str, message: &str) -> FlowyResult<String>;
+  async fn close_chat(&self, chat_id: &str) -> FlowyResult<()>;
+}
+
+#[async_trait]
+impl PromptChat for LocalChatLLMChat {
+  async fn create_chat(&self, chat_id: &str) -> FlowyResult<()> {
+    LocalChatLLMChat::create_chat(self, chat_id)
+      .await
+      .map_err(|err| FlowyError::local_ai().with_context(err))
+  }
+
+  async fn ask_question(&self, chat_id: &str, message: &str) -> FlowyResult<String> {
+    LocalChatLLMChat::ask_question(self, chat_id, message)
+      .await
+      .map_err(|err| FlowyError::local_ai().with_context(err))
+  }
+
+  async fn close_chat(&self, chat_id: &str) -> FlowyResult<()> {
+    LocalChatLLMChat::close_chat(self, chat_id)
+      .await
+      .map_err(|err| FlowyError::local_ai().with_context(err))
+  }
+}
+
+/// Answers the prompt in the database chat. The plugin keeps only one chat loaded at a time, so
+/// the chat that the user has open, if any, is loaded again afterwards.
+async fn complete_prompt_in_database_chat(
+  chat: &dyn PromptChat,
+  prompt: &str,
+  current_chat_id: Option<String>,
+) -> FlowyResult<String> {
+  chat.create_chat(LOCAL_AI_DATABASE_CHAT_ID).await?;
+  let answer = chat.ask_question(LOCAL_AI_DATABASE_CHAT_ID, prompt).await;
+  if let Err(err) = chat.close_chat(LOCAL_AI_DATABASE_CHAT_ID).await {
+    error!("[AI Plugin] failed to close database chat: {:?}", err);
+  }
+  if let Some(chat_id) = current_chat_id {
+    if let Err(err) = chat.create_chat(&chat_id).await {
+      error!("[AI Plugin] failed to reopen chat {}: {:?}", chat_id, err);
+    }
+  }
+  Ok(answer?.trim().to_string())
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LLMSetting {
   pub plugin: AppFlowyAIPlugin,
@@ -36,12 +85,17 @@ pub struct LLMModelInfo {
 const APPFLOWY_LOCAL_AI_ENABLED: &str = "appflowy_local_ai_enabled";
 const APPFLOWY_LOCAL_AI_CHAT_ENABLED: &str = "appflowy_local_ai_chat_enabled";
 const APPFLOWY_LOCAL_AI_CHAT_RAG_ENABLED: &str = "appflowy_local_ai_chat_rag_enabled";
+const APPFLOWY_LOCAL_AI_DATABASE_ENABLED: &str = "appflowy_local_ai_database_enabled";
 const LOCAL_AI_SETTING_KEY: &str = "appflowy_local_ai_setting:v0";
+/// The chat that is used to answer the prompts of the AI fields of the databases.
+const LOCAL_AI_DATABASE_CHAT_ID: &str = "appflowy_local_ai_database";
 
 pub struct LocalAIController {
   llm_chat: Arc<LocalChatLLMChat>,
   llm_res: Arc<LLMResourceController>,
   current_chat_id: Mutex<Option<String>>,
+  /// The prompts of the database are answered one at a time in the database chat.
+  database_chat_lock: tokio::sync::Mutex<()>,
   store_preferences: Arc<KVStorePreferences>,
 }
 
@@ -91,6 +145,7 @@ impl LocalAIController {
       llm_chat,
       llm_res,
       current_chat_id,
+      database_chat_lock: Default::default(),
       store_preferences,
     };
 
@@ -181,6 +236,55 @@ impl LocalAIController {
       .get_bool(APPFLOWY_LOCAL_AI_CHAT_RAG_ENABLED)
   }
 
+  /// Returns true if the summary and the translate fields of the databases use the local AI even
+  /// if the user is signed in to AppFlowy Cloud.
+  pub fn is_database_enabled(&self) -> bool {
+    self
+      .store_preferences
+      .get_bool(APPFLOWY_LOCAL_AI_DATABASE_ENABLED)
+  }
+
+  /// Summarizes a row of a database. Each item of `cells` is the name of a field and the content
+  /// of the cell.
+  pub async fn summarize_row(&self, cells: &[(String, String)]) -> FlowyResult<String> {
+    let content = cells
+      .iter()
+      .filter(|(_, content)| !content.is_empty())
+      .map(|(name, content)| format!("{}: {}", name, content))
+      .collect::<Vec<_>>()
+      .join("\n");
+    if content.is_empty() {
+      return Ok("".to_string());
+    }
+    let prompt = format!(
+      "Summarize the following record in one or two sentences. Only reply with the summary.\n\n{}",
+      content
+    );
+    self.complete_prompt(&prompt).await
+  }
+
+  /// Translates the text to `language`.
+  pub async fn translate_text(&self, text: &str, language: &str) -> FlowyResult<String> {
+    if text.trim().is_empty() {
+      return Ok("".to_string());
+    }
+    let prompt = format!(
+      "Translate the following text to {}. Only reply with the translation.\n\n{}",
+      language, text
+    );
+    self.complete_prompt(&prompt).await
+  }
+
+  async fn complete_prompt(&self, prompt: &str) -> FlowyResult<String> {
+    if !self.is_running() {
+      return Err(FlowyError::local_ai_unavailable());
+    }
+
+    let _guard = self.database_chat_lock.lock().await;
+    let current_chat_id = self.current_chat_id.lock().clone();
+    complete_prompt_in_database_chat(self.llm_chat.as_ref(), prompt, current_chat_id).await
+  }
+
   pub fn open_chat(&self, chat_id: &str) {
     if !self.is_chat_enabled() {
       return;
@@ -317,6 +421,16 @@ impl LocalAIController {
     Ok(enabled)
   }
 
+  pub async fn toggle_local_ai_database(&self) -> FlowyResult<bool> {
+    let enabled = !self
+      .store_preferences
+      .get_bool(APPFLOWY_LOCAL_AI_DATABASE_ENABLED);
+    self
+      .store_preferences
+      .set_bool(APPFLOWY_LOCAL_AI_DATABASE_ENABLED, enabled)?;
+    Ok(enabled)
+  }
+
   async fn enable_chat_plugin(&self, enabled: bool) -> FlowyResult<()> {
     if enabled {
       let (tx, rx) = tokio::sync::oneshot::channel();
@@ -405,3 +519,49 @@ impl LLMResourceService for LLMResourceServiceImpl {
       .get_bool(APPFLOWY_LOCAL_AI_CHAT_RAG_ENABLED)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Default)]
+  struct FakeChat {
+    calls: Mutex<Vec<String>>,
+  }
+
+  #[async_trait]
+  impl PromptChat for FakeChat {
+    async fn create_chat(&self, chat_id: &str) -> FlowyResult<()> {
+      self.calls.lock().push(format!("create {}", chat_id));
+      Ok(())
+    }
+
+    async fn ask_question(&self, chat_id: &str, message: &str) -> FlowyResult<String> {
+      self.calls.lock().push(format!("ask {}", chat_id));
+      Ok(format!("  answer to {}\n", message))
+    }
+
+    async fn close_chat(&self, chat_id: &str) -> FlowyResult<()> {
+      self.calls.lock().push(format!("close {}", chat_id));
+      Ok(())
+    }
+  }
+
+  #[tokio::test]
+  async fn complete_prompt_reopens_current_chat_test() {
+    let chat = FakeChat::default();
+    let answer = complete_prompt_in_database_chat(&chat, "prompt", Some("user_chat".to_string()))
+      .await
+      .unwrap();
+    assert_eq!(answer, "answer to prompt");
+    assert_eq!(
+      *chat.calls.lock(),
+      vec![
+        format!("create {}", LOCAL_AI_DATABASE_CHAT_ID),
+        format!("ask {}", LOCAL_AI_DATABASE_CHAT_ID),
+        format!("close {}", LOCAL_AI_DATABASE_CHAT_ID),
+        "create user_chat".to_string(),
+      ]
+    );
+  }
+}
@@ -1,21 +1,146 @@
 use collab_entity::CollabType;
 use collab_integrate::{CollabSnapshot, PersistenceError, SnapshotPersistence};
-use diesel::dsl::count_star;
+use diesel::dsl::sql;
+use diesel::sql_types::BigInt;
 use diesel::SqliteConnection;
-use flowy_error::FlowyError;
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::kv::KVStorePreferences;
+use flowy_sqlite::DBConnection;
 use flowy_sqlite::{
   prelude::*,
   schema::{collab_snapshot, collab_snapshot::dsl},
 };
 use flowy_user::services::authenticate_user::AuthenticateUser;
+use serde::{Deserialize, Serialize};
 
 use collab_integrate::collab_builder::WorkspaceCollabIntegrate;
 use lib_infra::util::timestamp;
 use std::sync::{Arc, Weak};
 use tracing::debug;
 
+/// The key of the [SnapshotRetention] in the [KVStorePreferences]. The value is the json string
+/// of the [SnapshotRetention].
+pub const SNAPSHOT_RETENTION_KEY: &str = "collab_snapshot_retention";
+
+/// Controls how many automatic snapshots are kept for each collab object. A limit of zero means
+/// unlimited. The snapshots with a title, which are created by the user, are never removed.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotRetention {
+  pub max_count: i64,
+  pub max_age_in_secs: i64,
+  pub max_size_in_bytes: i64,
+}
+
+impl Default for SnapshotRetention {
+  fn default() -> Self {
+    Self {
+      max_count: 0,
+      // 30 days
+      max_age_in_secs: 30 * 24 * 60 * 60,
+      // 50 MB
+      max_size_in_bytes: 50 * 1024 * 1024,
+    }
+  }
+}
+
+impl SnapshotRetention {
+  pub fn from_preferences(store_preferences: &KVStorePreferences) -> Self {
+    store_preferences
+      .get_object::<SnapshotRetention>(SNAPSHOT_RETENTION_KEY)
+      .unwrap_or_default()
+  }
+
+  /// Returns the ids of the snapshots that should be removed. The snapshots are ordered from the
+  /// newest to the oldest and each item is the (id, timestamp, size) of the snapshot. The newest
+  /// snapshot is always kept.
+  fn expired_snapshot_ids(&self, snapshots: Vec<(String, i64, i64)>, now: i64) -> Vec<String> {
+    let mut total_size = 0;
+    snapshots
+      .into_iter()
+      .enumerate()
+      .filter_map(|(index, (id, timestamp, size))| {
+        total_size += size;
+        if index == 0 {
+          return None;
+        }
+
+        let exceed_count = self.max_count > 0 && index as i64 >= self.max_count;
+        let exceed_age = self.max_age_in_secs > 0 && now - timestamp > self.max_age_in_secs;
+        let exceed_size = self.max_size_in_bytes > 0 && total_size > self.max_size_in_bytes;
+        if exceed_count || exceed_age || exceed_size {
+          Some(id)
+        } else {
+          None
+        }
+      })
+      .collect()
+  }
+}
+
+#[derive(Clone)]
 pub struct SnapshotDBImpl(pub Weak<AuthenticateUser>);
 
+impl SnapshotDBImpl {
+  fn get_authenticate_user(&self) -> FlowyResult<Arc<AuthenticateUser>> {
+    self
+      .0
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))
+  }
+
+  fn get_connection(&self) -> FlowyResult<(Arc<AuthenticateUser>, DBConnection)> {
+    let authenticate_user = self.get_authenticate_user()?;
+    let uid = authenticate_user.user_id()?;
+    let conn = authenticate_user.get_sqlite_connection(uid)?;
+    Ok((authenticate_user, conn))
+  }
+
+  /// Creates a snapshot with the given title. A snapshot with a title is kept until it's deleted
+  /// explicitly, while the untitled ones are removed according to the [SnapshotRetention].
+  pub(crate) fn create_named_snapshot(
+    &self,
+    object_id: &str,
+    collab_type: &CollabType,
+    title: &str,
+    desc: &str,
+    data: Vec<u8>,
+  ) -> FlowyResult<CollabSnapshotMeta> {
+    let (authenticate_user, mut conn) = self.get_connection()?;
+    let row = CollabSnapshotRow::new(object_id.to_string(), collab_type.to_string(), data)
+      .with_title(title, desc);
+    let meta = CollabSnapshotMeta::from(&row);
+    let retention = authenticate_user
+      .get_store_preferences()
+      .upgrade()
+      .map(|store_preferences| SnapshotRetention::from_preferences(&store_preferences))
+      .unwrap_or_default();
+    CollabSnapshotSql::create(row, &retention, &mut conn)?;
+    Ok(meta)
+  }
+
+  pub(crate) fn get_snapshot_metas(&self, object_id: &str) -> FlowyResult<Vec<CollabSnapshotMeta>> {
+    let (_, mut conn) = self.get_connection()?;
+    CollabSnapshotSql::get_all_snapshots(object_id, &mut conn)
+  }
+
+  pub(crate) fn get_snapshots(
+    &self,
+    object_id: &str,
+    limit: usize,
+  ) -> FlowyResult<Vec<CollabSnapshotRow>> {
+    let (_, mut conn) = self.get_connection()?;
+    CollabSnapshotSql::get_snapshots(object_id, limit, &mut conn)
+  }
+
+  pub(crate) fn get_snapshot(&self, snapshot_id: &str) -> FlowyResult<CollabSnapshotRow> {
+    let (_, mut conn) = self.get_connection()?;
+    CollabSnapshotSql::get_snapshot(snapshot_id, &mut conn).ok_or(
+      FlowyError::record_not_found().with_context(format!("Snapshot {} not found", snapshot_id)),
+    )
+  }
+}
+
 impl SnapshotPersistence for SnapshotDBImpl {
   fn create_snapshot(
     &self,
@@ -28,18 +153,24 @@ impl SnapshotPersistence for SnapshotDBImpl {
     let object_id = object_id.to_string();
     let weak_user = self.0.clone();
     tokio::task::spawn_blocking(move || {
-      if let Some(mut conn) = weak_user
-        .upgrade()
-        .and_then(|authenticate_user| authenticate_user.get_sqlite_connection(uid).ok())
-      {
-        // Save the snapshot data to disk
-        let result = CollabSnapshotSql::create(
-          CollabSnapshotRow::new(object_id.clone(), collab_type.to_string(), encoded_v1),
-          &mut conn,
-        )
-        .map_err(|e| PersistenceError::Internal(e.into()));
-        if let Err(e) = result {
-          tracing::warn!("create snapshot error: {:?}", e);
+      if let Some(authenticate_user) = weak_user.upgrade() {
+        if let Ok(mut conn) = authenticate_user.get_sqlite_connection(uid) {
+          let retention = authenticate_user
+            .get_store_preferences()
+            .upgrade()
+            .map(|store_preferences| SnapshotRetention::from_preferences(&store_preferences))
+            .unwrap_or_default();
+
+          // Save the snapshot data to disk
+          let result = CollabSnapshotSql::create(
+            CollabSnapshotRow::new(object_id.clone(), collab_type.to_string(), encoded_v1),
+            &retention,
+            &mut conn,
+          )
+          .map_err(|e| PersistenceError::Internal(e.into()));
+          if let Err(e) = result {
+            tracing::warn!("create snapshot error: {:?}", e);
+          }
         }
       }
       Ok::<(), PersistenceError>(())
@@ -48,13 +179,19 @@ impl SnapshotPersistence for SnapshotDBImpl {
   }
 }
 
+/// Generates the id of the snapshot. The id is a positive i64 in string format, so it can be
+/// used as the snapshot id of the cloud services.
+fn gen_snapshot_id() -> String {
+  ((uuid::Uuid::new_v4().as_u128() >> 65) as i64).to_string()
+}
+
 #[derive(PartialEq, Clone, Debug, Queryable, Identifiable, Insertable)]
 #[diesel(table_name = collab_snapshot)]
 pub(crate) struct CollabSnapshotRow {
   pub(crate) id: String,
-  object_id: String,
-  title: String,
-  desc: String,
+  pub(crate) object_id: String,
+  pub(crate) title: String,
+  pub(crate) desc: String,
   collab_type: String,
   pub(crate) timestamp: i64,
   pub(crate) data: Vec<u8>,
@@ -63,7 +200,7 @@ pub(crate) struct CollabSnapshotRow {
 impl CollabSnapshotRow {
   pub fn new(object_id: String, collab_type: String, data: Vec<u8>) -> Self {
     Self {
-      id: uuid::Uuid::new_v4().to_string(),
+      id: gen_snapshot_id(),
       object_id,
       title: "".to_string(),
       desc: "".to_string(),
@@ -72,6 +209,12 @@ impl CollabSnapshotRow {
       data,
     }
   }
+
+  pub fn with_title(mut self, title: &str, desc: &str) -> Self {
+    self.title = title.to_string();
+    self.desc = desc.to_string();
+    self
+  }
 }
 
 impl From<CollabSnapshotRow> for CollabSnapshot {
@@ -86,13 +229,28 @@ impl From<CollabSnapshotRow> for CollabSnapshot {
 pub struct CollabSnapshotMeta {
   pub id: String,
   pub object_id: String,
+  pub title: String,
+  pub desc: String,
   pub timestamp: i64,
 }
 
+impl From<&CollabSnapshotRow> for CollabSnapshotMeta {
+  fn from(row: &CollabSnapshotRow) -> Self {
+    Self {
+      id: row.id.clone(),
+      object_id: row.object_id.clone(),
+      title: row.title.clone(),
+      desc: row.desc.clone(),
+      timestamp: row.timestamp,
+    }
+  }
+}
+
 pub(crate) struct CollabSnapshotSql;
 impl CollabSnapshotSql {
   pub(crate) fn create(
     row: CollabSnapshotRow,
+    retention: &SnapshotRetention,
     conn: &mut SqliteConnection,
   ) -> Result<(), FlowyError> {
     conn.immediate_transaction::<_, Error, _>(|conn| {
@@ -109,29 +267,22 @@ impl CollabSnapshotSql {
         ))
         .execute(conn)?;
 
-      // Count the total number of snapshots for the specific object_id
-      let total_snapshots: i64 = dsl::collab_snapshot
+      // Only the automatic snapshots, which have no title, are removed by the retention.
+      let auto_snapshots: Vec<(String, i64, i64)> = dsl::collab_snapshot
         .filter(dsl::object_id.eq(&row.object_id))
-        .select(count_star())
-        .first(conn)?;
-
-      // If there are more than 5 snapshots, delete the oldest one
-      if total_snapshots > 5 {
-        let ids_to_delete: Vec<String> = dsl::collab_snapshot
-          .filter(dsl::object_id.eq(&row.object_id))
-          .order(dsl::timestamp.asc())
-          .select(dsl::id)
-          .limit(1)
-          .load(conn)?;
+        .filter(dsl::title.eq(""))
+        .order(dsl::timestamp.desc())
+        .select((dsl::id, dsl::timestamp, sql::<BigInt>("length(data)")))
+        .load(conn)?;
 
+      let ids_to_delete = retention.expired_snapshot_ids(auto_snapshots, timestamp());
+      if !ids_to_delete.is_empty() {
         debug!(
           "Delete {} snapshots for object_id: {}",
           ids_to_delete.len(),
           row.object_id
         );
-        for id in ids_to_delete {
-          delete(dsl::collab_snapshot.filter(dsl::id.eq(id))).execute(conn)?;
-        }
+        delete(dsl::collab_snapshot.filter(dsl::id.eq_any(ids_to_delete))).execute(conn)?;
       }
 
       Ok(())
@@ -139,33 +290,54 @@ impl CollabSnapshotSql {
     Ok(())
   }
 
+  /// Returns the metas of the snapshots of the object, ordered from the newest to the oldest.
   pub(crate) fn get_all_snapshots(
     object_id: &str,
     conn: &mut SqliteConnection,
   ) -> Result<Vec<CollabSnapshotMeta>, FlowyError> {
     let results = collab_snapshot::table
       .filter(collab_snapshot::object_id.eq(object_id))
+      .order(collab_snapshot::timestamp.desc())
       .select((
         collab_snapshot::id,
         collab_snapshot::object_id,
+        collab_snapshot::title,
+        collab_snapshot::desc,
         collab_snapshot::timestamp,
       ))
-      .load::<(String, String, i64)>(conn)
-      .expect("Error loading collab_snapshot");
+      .load::<(String, String, String, String, i64)>(conn)?;
 
     // Map the results to CollabSnapshotMeta
     let snapshots: Vec<CollabSnapshotMeta> = results
       .into_iter()
-      .map(|(id, object_id, timestamp)| CollabSnapshotMeta {
-        id,
-        object_id,
-        timestamp,
-      })
+      .map(
+        |(id, object_id, title, desc, timestamp)| CollabSnapshotMeta {
+          id,
+          object_id,
+          title,
+          desc,
+          timestamp,
+        },
+      )
       .collect();
 
     Ok(snapshots)
   }
 
+  /// Returns the newest snapshots of the object, including the data.
+  pub(crate) fn get_snapshots(
+    object_id: &str,
+    limit: usize,
+    conn: &mut SqliteConnection,
+  ) -> Result<Vec<CollabSnapshotRow>, FlowyError> {
+    let rows = dsl::collab_snapshot
+      .filter(dsl::object_id.eq(object_id))
+      .order(dsl::timestamp.desc())
+      .limit(limit as i64)
+      .load::<CollabSnapshotRow>(conn)?;
+    Ok(rows)
+  }
+
   pub(crate) fn get_snapshot(
     object_id: &str,
     conn: &mut SqliteConnection,
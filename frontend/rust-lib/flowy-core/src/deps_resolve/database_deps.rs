@@ -1,10 +1,22 @@
+use crate::deps_resolve::SnapshotDBImpl;
+use crate::integrate::server::ServerProvider;
+use anyhow::Error;
+use collab_entity::CollabType;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
-use flowy_database2::{DatabaseManager, DatabaseUser};
-use flowy_database_pub::cloud::DatabaseCloudService;
-use flowy_error::FlowyError;
+use flowy_chat::local_ai::local_llm_chat::LocalAIController;
+use flowy_database2::{DatabaseManager, DatabaseSnapshotService, DatabaseUser};
+use flowy_database_pub::cloud::{
+  CollabDocStateByOid, DatabaseCloudService, DatabaseSnapshot, SummaryRowContent,
+  TranslateRowContent, TranslateRowResponse,
+};
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::{BacklinkIndexManager, DatabaseIndexManager};
+use flowy_sqlite::kv::KVStorePreferences;
 use flowy_user::services::authenticate_user::AuthenticateUser;
+use lib_infra::future::FutureResult;
 use lib_infra::priority_task::TaskDispatcher;
+use std::collections::HashMap;
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
 pub struct DatabaseDepsResolver();
@@ -14,15 +26,159 @@ impl DatabaseDepsResolver {
     authenticate_user: Weak<AuthenticateUser>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
-    cloud_service: Arc<dyn DatabaseCloudService>,
+    server_provider: Arc<ServerProvider>,
+    local_ai: Arc<LocalAIController>,
+    database_indexer: Arc<dyn DatabaseIndexManager>,
+    backlink_indexer: Arc<dyn BacklinkIndexManager>,
+    store_preferences: Arc<KVStorePreferences>,
   ) -> Arc<DatabaseManager> {
-    let user = Arc::new(DatabaseUserImpl(authenticate_user));
-    Arc::new(DatabaseManager::new(
+    let user = Arc::new(DatabaseUserImpl(authenticate_user.clone()));
+    let cloud_service = Arc::new(DatabaseCloudServiceMiddleware {
+      server_provider,
+      local_ai,
+    });
+    let snapshot_service = Arc::new(DatabaseSnapshotImpl(SnapshotDBImpl(authenticate_user)));
+    DatabaseManager::new(
       user,
       task_scheduler,
       collab_builder,
       cloud_service,
-    ))
+      database_indexer,
+      backlink_indexer,
+      snapshot_service,
+      store_preferences,
+    )
+  }
+}
+
+/// Routes the AI summary and the AI translate requests of the database to the local AI when it's
+/// running and either the user isn't signed in to AppFlowy Cloud or has chosen to use the local AI
+/// for databases. Every other request goes to the current server.
+struct DatabaseCloudServiceMiddleware {
+  server_provider: Arc<ServerProvider>,
+  local_ai: Arc<LocalAIController>,
+}
+
+impl DatabaseCloudServiceMiddleware {
+  fn use_local_ai(&self) -> bool {
+    self.local_ai.is_running()
+      && (self.server_provider.get_server_type().is_local() || self.local_ai.is_database_enabled())
+  }
+}
+
+impl DatabaseCloudService for DatabaseCloudServiceMiddleware {
+  fn get_database_object_doc_state(
+    &self,
+    object_id: &str,
+    collab_type: CollabType,
+    workspace_id: &str,
+  ) -> FutureResult<Option<Vec<u8>>, Error> {
+    self
+      .server_provider
+      .get_database_object_doc_state(object_id, collab_type, workspace_id)
+  }
+
+  fn batch_get_database_object_doc_state(
+    &self,
+    object_ids: Vec<String>,
+    object_ty: CollabType,
+    workspace_id: &str,
+  ) -> FutureResult<CollabDocStateByOid, Error> {
+    self
+      .server_provider
+      .batch_get_database_object_doc_state(object_ids, object_ty, workspace_id)
+  }
+
+  fn get_database_collab_object_snapshots(
+    &self,
+    object_id: &str,
+    limit: usize,
+  ) -> FutureResult<Vec<DatabaseSnapshot>, Error> {
+    self
+      .server_provider
+      .get_database_collab_object_snapshots(object_id, limit)
+  }
+
+  fn summary_database_row(
+    &self,
+    workspace_id: &str,
+    object_id: &str,
+    summary_row: SummaryRowContent,
+  ) -> FutureResult<String, Error> {
+    if !self.use_local_ai() {
+      return self
+        .server_provider
+        .summary_database_row(workspace_id, object_id, summary_row);
+    }
+
+    let local_ai = self.local_ai.clone();
+    FutureResult::new(async move {
+      let cells = summary_row.into_iter().collect::<Vec<_>>();
+      let summary = local_ai.summarize_row(&cells).await?;
+      Ok(summary)
+    })
+  }
+
+  fn translate_database_row(
+    &self,
+    workspace_id: &str,
+    translate_row: TranslateRowContent,
+    language: &str,
+  ) -> FutureResult<TranslateRowResponse, Error> {
+    if !self.use_local_ai() {
+      return self
+        .server_provider
+        .translate_database_row(workspace_id, translate_row, language);
+    }
+
+    let local_ai = self.local_ai.clone();
+    let language = language.to_string();
+    FutureResult::new(async move {
+      let mut items = vec![];
+      for item in translate_row {
+        let translation = local_ai.translate_text(&item.content, &language).await?;
+        if !translation.is_empty() {
+          items.push(HashMap::from([(item.title, translation)]));
+        }
+      }
+      Ok(TranslateRowResponse { items })
+    })
+  }
+}
+
+struct DatabaseSnapshotImpl(SnapshotDBImpl);
+
+impl DatabaseSnapshotService for DatabaseSnapshotImpl {
+  fn create_database_snapshot(
+    &self,
+    database_id: &str,
+    title: &str,
+    desc: &str,
+    data: Vec<u8>,
+  ) -> FlowyResult<DatabaseSnapshot> {
+    let meta = self.0.create_named_snapshot(
+      database_id,
+      &CollabType::Database,
+      title,
+      desc,
+      data.clone(),
+    )?;
+    Ok(DatabaseSnapshot {
+      snapshot_id: meta.id.parse::<i64>().unwrap_or_default(),
+      database_id: meta.object_id,
+      data,
+      created_at: meta.timestamp,
+    })
+  }
+
+  fn get_database_snapshot(&self, snapshot_id: i64) -> FlowyResult<DatabaseSnapshot> {
+    let row = self.0.get_snapshot(&snapshot_id.to_string())?;
+    Ok(DatabaseSnapshot {
+      snapshot_id,
+      database_id: row.object_id,
+      data: row.data,
+      created_at: row.timestamp,
+    })
   }
 }
 
@@ -1,6 +1,7 @@
 use std::sync::{Arc, Weak};
 
-use crate::deps_resolve::CollabSnapshotSql;
+use crate::deps_resolve::SnapshotDBImpl;
+use collab_entity::CollabType;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
 use flowy_database2::DatabaseManager;
@@ -8,6 +9,7 @@ use flowy_document::entities::{DocumentSnapshotData, DocumentSnapshotMeta};
 use flowy_document::manager::{DocumentManager, DocumentSnapshotService, DocumentUserService};
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::{BacklinkIndexManager, DocumentIndexManager};
 use flowy_storage_pub::storage::StorageService;
 use flowy_user::services::authenticate_user::AuthenticateUser;
 
@@ -19,63 +21,72 @@ impl DocumentDepsResolver {
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DocumentCloudService>,
     storage_service: Weak<dyn StorageService>,
+    document_indexer: Arc<dyn DocumentIndexManager>,
+    backlink_indexer: Arc<dyn BacklinkIndexManager>,
   ) -> Arc<DocumentManager> {
     let user_service: Arc<dyn DocumentUserService> =
       Arc::new(DocumentUserImpl(authenticate_user.clone()));
-    let snapshot_service = Arc::new(DocumentSnapshotImpl(authenticate_user));
+    let snapshot_service = Arc::new(DocumentSnapshotImpl(SnapshotDBImpl(authenticate_user)));
     Arc::new(DocumentManager::new(
       user_service.clone(),
       collab_builder,
       cloud_service,
       storage_service,
       snapshot_service,
+      document_indexer,
+      backlink_indexer,
     ))
   }
 }
 
-struct DocumentSnapshotImpl(Weak<AuthenticateUser>);
-
-impl DocumentSnapshotImpl {
-  pub fn get_authenticate_user(&self) -> FlowyResult<Arc<AuthenticateUser>> {
-    self
-      .0
-      .upgrade()
-      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))
-  }
-}
+struct DocumentSnapshotImpl(SnapshotDBImpl);
 
 impl DocumentSnapshotService for DocumentSnapshotImpl {
   fn get_document_snapshot_metas(
     &self,
     document_id: &str,
   ) -> FlowyResult<Vec<DocumentSnapshotMeta>> {
-    let authenticate_user = self.get_authenticate_user()?;
-    let uid = authenticate_user.user_id()?;
-    let mut db = authenticate_user.get_sqlite_connection(uid)?;
-    CollabSnapshotSql::get_all_snapshots(document_id, &mut db).map(|rows| {
-      rows
+    self.0.get_snapshot_metas(document_id).map(|metas| {
+      metas
         .into_iter()
-        .map(|row| DocumentSnapshotMeta {
-          snapshot_id: row.id,
-          object_id: row.object_id,
-          created_at: row.timestamp,
+        .map(|meta| DocumentSnapshotMeta {
+          snapshot_id: meta.id,
+          object_id: meta.object_id,
+          created_at: meta.timestamp,
+          title: meta.title,
+          desc: meta.desc,
         })
         .collect()
     })
   }
 
   fn get_document_snapshot(&self, snapshot_id: &str) -> FlowyResult<DocumentSnapshotData> {
-    let authenticate_user = self.get_authenticate_user()?;
-    let uid = authenticate_user.user_id()?;
-    let mut db = authenticate_user.get_sqlite_connection(uid)?;
-    CollabSnapshotSql::get_snapshot(snapshot_id, &mut db)
+    self
+      .0
+      .get_snapshot(snapshot_id)
       .map(|row| DocumentSnapshotData {
-        object_id: row.id,
+        object_id: row.object_id,
         encoded_v1: row.data,
       })
-      .ok_or(
-        FlowyError::record_not_found().with_context(format!("Snapshot {} not found", snapshot_id)),
-      )
+  }
+
+  fn create_document_snapshot(
+    &self,
+    document_id: &str,
+    title: &str,
+    desc: &str,
+    encoded_v1: Vec<u8>,
+  ) -> FlowyResult<DocumentSnapshotMeta> {
+    self
+      .0
+      .create_named_snapshot(document_id, &CollabType::Document, title, desc, encoded_v1)
+      .map(|meta| DocumentSnapshotMeta {
+        snapshot_id: meta.id,
+        object_id: meta.object_id,
+        created_at: meta.timestamp,
+        title: meta.title,
+        desc: meta.desc,
+      })
   }
 }
 
@@ -1,24 +1,37 @@
 use bytes::Bytes;
-use collab_entity::EncodedCollab;
+use collab_document::blocks::DocumentData;
+use collab_entity::{CollabType, EncodedCollab};
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
 use flowy_chat::chat_manager::ChatManager;
 use flowy_database2::entities::DatabaseLayoutPB;
-use flowy_database2::services::share::csv::CSVFormat;
+use flowy_database2::services::share::csv::{CSVFormat, CSVImportOptions, ImportResult};
 use flowy_database2::template::{make_default_board, make_default_calendar, make_default_grid};
 use flowy_database2::DatabaseManager;
+use flowy_document::document_data::document_data_from_doc_state;
 use flowy_document::entities::DocumentDataPB;
 use flowy_document::manager::DocumentManager;
+use flowy_document::mention::PageMentionUpdate;
+use flowy_document::parser::constant::{PAGE, URL};
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_document::parser::external::parser::ExternalDataToNestedJSONParser;
 use flowy_document::parser::json::parser::JsonToDocumentParser;
-use flowy_error::FlowyError;
+use flowy_document::parser::parser_entities::{InputType, LinkReplacement, NestedBlock};
+use flowy_error::{FlowyError, FlowyResult};
 use flowy_folder::entities::{CreateViewParams, ViewLayoutPB};
-use flowy_folder::manager::{FolderManager, FolderUser};
-use flowy_folder::share::ImportType;
+use flowy_folder::manager::{FolderManager, FolderSnapshotService, FolderUser};
+use flowy_folder::publish_util::generate_publish_name;
+use flowy_folder::share::{
+  self, DocumentExportFormat, ExportedFile, ImportFailedRow, ImportType, ImportedData,
+  MarkdownPage, PublishedHtml, RowDocument, ZipImportLink,
+};
 use flowy_folder::view_operation::{
-  FolderOperationHandler, FolderOperationHandlers, View, ViewData,
+  FolderOperationHandler, FolderOperationHandlers, View, ViewData, ViewReferenceUpdate,
 };
 use flowy_folder::ViewLayout;
+use flowy_folder_pub::cloud::FolderSnapshot;
 use flowy_folder_pub::folder_builder::NestedViewBuilder;
+use flowy_search::backlinks::indexer::BacklinkIndexManagerImpl;
 use flowy_search::folder::indexer::FolderIndexManagerImpl;
 use flowy_sqlite::kv::KVStorePreferences;
 use flowy_user::services::authenticate_user::AuthenticateUser;
@@ -26,9 +39,11 @@ use lib_dispatch::prelude::ToBytes;
 use lib_infra::future::FutureResult;
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
 
+use crate::deps_resolve::SnapshotDBImpl;
 use crate::integrate::server::ServerProvider;
 
 pub struct FolderDepsResolver();
@@ -39,12 +54,14 @@ impl FolderDepsResolver {
     collab_builder: Arc<AppFlowyCollabBuilder>,
     server_provider: Arc<ServerProvider>,
     folder_indexer: Arc<FolderIndexManagerImpl>,
+    backlink_indexer: Arc<BacklinkIndexManagerImpl>,
     store_preferences: Arc<KVStorePreferences>,
     operation_handlers: FolderOperationHandlers,
   ) -> Arc<FolderManager> {
     let user: Arc<dyn FolderUser> = Arc::new(FolderUserImpl {
       authenticate_user: authenticate_user.clone(),
     });
+    let snapshot_service = Arc::new(FolderSnapshotImpl(SnapshotDBImpl(authenticate_user)));
 
     Arc::new(
       FolderManager::new(
@@ -53,21 +70,60 @@ impl FolderDepsResolver {
         operation_handlers,
         server_provider.clone(),
         folder_indexer,
+        backlink_indexer,
         store_preferences,
+        snapshot_service,
       )
       .unwrap(),
     )
   }
 }
 
+struct FolderSnapshotImpl(SnapshotDBImpl);
+
+impl FolderSnapshotService for FolderSnapshotImpl {
+  fn create_folder_snapshot(
+    &self,
+    workspace_id: &str,
+    title: &str,
+    desc: &str,
+    encoded_v1: Vec<u8>,
+  ) -> FlowyResult<FolderSnapshot> {
+    let meta = self.0.create_named_snapshot(
+      workspace_id,
+      &CollabType::Folder,
+      title,
+      desc,
+      encoded_v1.clone(),
+    )?;
+    Ok(FolderSnapshot {
+      snapshot_id: meta.id.parse::<i64>().unwrap_or_default(),
+      database_id: meta.object_id,
+      data: encoded_v1,
+      created_at: meta.timestamp,
+    })
+  }
+
+  fn get_folder_snapshot(&self, snapshot_id: i64) -> FlowyResult<FolderSnapshot> {
+    let row = self.0.get_snapshot(&snapshot_id.to_string())?;
+    Ok(FolderSnapshot {
+      snapshot_id,
+      database_id: row.object_id,
+      data: row.data,
+      created_at: row.timestamp,
+    })
+  }
+}
+
 pub fn folder_operation_handlers(
   document_manager: Arc<DocumentManager>,
   database_manager: Arc<DatabaseManager>,
   chat_manager: Arc<ChatManager>,
+  storage_dir: PathBuf,
 ) -> FolderOperationHandlers {
   let mut map: HashMap<ViewLayout, Arc<dyn FolderOperationHandler + Send + Sync>> = HashMap::new();
 
-  let document_folder_operation = Arc::new(DocumentFolderOperation(document_manager));
+  let document_folder_operation = Arc::new(DocumentFolderOperation(document_manager, storage_dir));
   map.insert(ViewLayout::Document, document_folder_operation);
 
   let database_folder_operation = Arc::new(DatabaseFolderOperation(database_manager));
@@ -107,7 +163,9 @@ impl FolderUser for FolderUserImpl {
   }
 }
 
-struct DocumentFolderOperation(Arc<DocumentManager>);
+/// The [PathBuf] is the directory of the application's data. Only the local files inside it
+/// are copied when exporting or publishing a document.
+struct DocumentFolderOperation(Arc<DocumentManager>, PathBuf);
 impl FolderOperationHandler for DocumentFolderOperation {
   fn create_workspace_view(
     &self,
@@ -243,17 +301,32 @@ impl FolderOperationHandler for DocumentFolderOperation {
     uid: i64,
     view_id: &str,
     _name: &str,
-    _import_type: ImportType,
+    import_type: ImportType,
     bytes: Vec<u8>,
-  ) -> FutureResult<EncodedCollab, FlowyError> {
+    _csv_options: Option<share::CSVImportOptions>,
+  ) -> FutureResult<ImportedData, FlowyError> {
     let view_id = view_id.to_string();
     let manager = self.0.clone();
     FutureResult::new(async move {
-      let data = DocumentDataPB::try_from(Bytes::from(bytes))?;
+      let data = match import_type {
+        ImportType::Markdown => {
+          let markdown =
+            String::from_utf8(bytes).map_err(|err| FlowyError::invalid_data().with_context(err))?;
+          // an empty markdown file is imported as an empty page
+          let block = ExternalDataToNestedJSONParser::new(markdown, InputType::Markdown)
+            .to_nested_block()
+            .unwrap_or_else(|| NestedBlock {
+              ty: PAGE.to_string(),
+              ..Default::default()
+            });
+          JsonToDocumentParser::json_str_to_document(&serde_json::to_string(&block)?)?
+        },
+        _ => DocumentDataPB::try_from(Bytes::from(bytes))?,
+      };
       let encoded_collab = manager
         .create_document(uid, &view_id, Some(data.into()))
         .await?;
-      Ok(encoded_collab)
+      Ok(encoded_collab.into())
     })
   }
 
@@ -263,9 +336,213 @@ impl FolderOperationHandler for DocumentFolderOperation {
     _view_id: &str,
     _name: &str,
     _path: String,
+    _csv_options: Option<share::CSVImportOptions>,
+  ) -> FutureResult<Option<ImportedData>, FlowyError> {
+    FutureResult::new(async move { Ok(None) })
+  }
+
+  fn import_markdown_page(
+    &self,
+    uid: i64,
+    view_id: &str,
+    page: MarkdownPage,
+  ) -> FutureResult<EncodedCollab, FlowyError> {
+    let view_id = view_id.to_string();
+    let manager = self.0.clone();
+    let storage_dir = self.1.clone();
+    FutureResult::new(async move {
+      let mut block =
+        ExternalDataToNestedJSONParser::new(page.markdown.clone(), InputType::Markdown)
+          .to_nested_block()
+          .unwrap_or_else(|| NestedBlock {
+            ty: PAGE.to_string(),
+            ..Default::default()
+          });
+
+      // Upload the attachments that the page links to. If the upload fails, e.g. the user isn't
+      // signed in to the cloud, the attachment is copied into the storage of the app instead,
+      // because the unzipped files are deleted once the import is done.
+      let mut uploaded_urls = HashMap::new();
+      for link in block.links() {
+        if uploaded_urls.contains_key(&link) {
+          continue;
+        }
+        if let Some(ZipImportLink::File(path)) = page.resolve_link(&link) {
+          let result = manager
+            .upload_file(page.workspace_id.clone(), &view_id, &path.to_string_lossy())
+            .await;
+          let url = match result {
+            Ok(url) => url,
+            Err(err) => {
+              tracing::warn!("Failed to upload the attachment {:?}: {}", path, err);
+              match copy_file_to_storage_dir(&path, &storage_dir).await {
+                Ok(copied_path) => copied_path,
+                Err(err) => {
+                  tracing::warn!("Failed to copy the attachment {:?}: {}", path, err);
+                  continue;
+                },
+              }
+            },
+          };
+          uploaded_urls.insert(link, url);
+        }
+      }
+      block.replace_links(&|link| match page.resolve_link(link) {
+        Some(ZipImportLink::View(view_id)) => Some(LinkReplacement::PageMention(view_id)),
+        _ => uploaded_urls.get(link).cloned().map(LinkReplacement::Url),
+      });
+
+      let data = JsonToDocumentParser::json_str_to_document(&serde_json::to_string(&block)?)?;
+      let encoded_collab = manager
+        .create_document(uid, &view_id, Some(data.into()))
+        .await?;
+      Ok(encoded_collab)
+    })
+  }
+
+  fn export_view(
+    &self,
+    view_id: &str,
+    file_stem: &str,
+    document_format: DocumentExportFormat,
+  ) -> FutureResult<Vec<ExportedFile>, FlowyError> {
+    let manager = self.0.clone();
+    let storage_dir = self.1.clone();
+    let view_id = view_id.to_string();
+    let file_stem = file_stem.to_string();
+    FutureResult::new(async move {
+      let mut data = manager.get_document_data(&view_id).await?;
+      let dir = format!("{}_files", file_stem);
+      let mut files = copy_local_files(&mut data, &storage_dir, &dir).await;
+      let parser = DocumentDataParser::new(Arc::new(data), None);
+      let (extension, content) = match document_format {
+        DocumentExportFormat::Markdown => ("md", parser.to_markdown()),
+        DocumentExportFormat::Html => ("html", parser.to_html()),
+      };
+      files.push(ExportedFile {
+        path: format!("{}.{}", file_stem, extension),
+        data: content.into_bytes(),
+      });
+      Ok(files)
+    })
+  }
+
+  fn publish_view_html(
+    &self,
+    view_id: &str,
+    data: Option<Vec<u8>>,
+  ) -> FutureResult<PublishedHtml, FlowyError> {
+    let manager = self.0.clone();
+    let storage_dir = self.1.clone();
+    let view_id = view_id.to_string();
+    FutureResult::new(async move {
+      let mut data = match data {
+        Some(doc_state) => document_data_from_doc_state(&view_id, doc_state)?,
+        None => manager.get_document_data(&view_id).await?,
+      };
+      let files = copy_local_files(&mut data, &storage_dir, "images").await;
+      let html = DocumentDataParser::new(Arc::new(data), None).to_html();
+      // The page of the site already declares the charset
+      let body = html
+        .strip_prefix("<meta charset=\"UTF-8\">")
+        .unwrap_or(&html)
+        .to_string();
+      Ok(PublishedHtml { body, files })
+    })
+  }
+
+  fn update_view_references(
+    &self,
+    view_id: &str,
+    block_ids: Vec<String>,
+    target_view_id: &str,
+    update: ViewReferenceUpdate,
   ) -> FutureResult<(), FlowyError> {
-    FutureResult::new(async move { Ok(()) })
+    // The mentions only contain the id of the page, so renaming the page doesn't change them
+    let update = match update {
+      ViewReferenceUpdate::Renamed { .. } => return FutureResult::new(async { Ok(()) }),
+      ViewReferenceUpdate::Trashed => PageMentionUpdate::Trashed,
+      ViewReferenceUpdate::Restored => PageMentionUpdate::Restored,
+      ViewReferenceUpdate::Deleted { name } => PageMentionUpdate::Deleted { name },
+    };
+    let manager = self.0.clone();
+    let view_id = view_id.to_string();
+    let target_view_id = target_view_id.to_string();
+    FutureResult::new(async move {
+      manager
+        .update_page_mentions(&view_id, &block_ids, &target_view_id, update)
+        .await
+    })
+  }
+}
+
+/// Copies the local files referenced by the blocks, eg. the images, into the directory `dir`
+/// and points the blocks to the copies. Only the files inside `storage_dir` are copied, so a
+/// document can't be used to read arbitrary files of the machine. The other files, and the
+/// ones that can't be read, are skipped.
+async fn copy_local_files(
+  data: &mut DocumentData,
+  storage_dir: &Path,
+  dir: &str,
+) -> Vec<ExportedFile> {
+  let storage_dir = match tokio::fs::canonicalize(storage_dir).await {
+    Ok(storage_dir) => storage_dir,
+    Err(err) => {
+      tracing::warn!(
+        "Failed to resolve the storage dir {:?}: {}",
+        storage_dir,
+        err
+      );
+      return vec![];
+    },
+  };
+  let mut files = vec![];
+  for block in data.blocks.values_mut() {
+    let path = match block.data.get(URL).and_then(|url| url.as_str()) {
+      Some(url) => PathBuf::from(url.strip_prefix("file://").unwrap_or(url)),
+      None => continue,
+    };
+    if !path.is_absolute() {
+      continue;
+    }
+    // Resolve the symlinks and `..` before checking that the file is inside the storage dir
+    let path = match tokio::fs::canonicalize(&path).await {
+      Ok(path) if path.starts_with(&storage_dir) && path.is_file() => path,
+      _ => continue,
+    };
+    let file_name = path
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    let copied_path = format!("{}/{}_{}", dir, files.len(), file_name);
+    match tokio::fs::read(&path).await {
+      Ok(bytes) => {
+        block
+          .data
+          .insert(URL.to_string(), copied_path.clone().into());
+        files.push(ExportedFile {
+          path: copied_path,
+          data: bytes,
+        });
+      },
+      Err(err) => tracing::warn!("Failed to read the file {:?}: {}", path, err),
+    }
   }
+  files
+}
+
+/// Copies the file into the images directory of the storage dir, where the app keeps the local
+/// images of the documents. Returns the path of the copy.
+async fn copy_file_to_storage_dir(path: &Path, storage_dir: &Path) -> FlowyResult<String> {
+  let dir = storage_dir.join("images");
+  tokio::fs::create_dir_all(&dir).await?;
+  let file_name = match path.extension() {
+    Some(extension) => format!("{}.{}", uuid::Uuid::new_v4(), extension.to_string_lossy()),
+    None => uuid::Uuid::new_v4().to_string(),
+  };
+  let copied_path = dir.join(file_name);
+  tokio::fs::copy(path, &copied_path).await?;
+  Ok(copied_path.to_string_lossy().to_string())
 }
 
 struct DatabaseFolderOperation(Arc<DatabaseManager>);
@@ -417,37 +694,152 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     _name: &str,
     import_type: ImportType,
     bytes: Vec<u8>,
-  ) -> FutureResult<EncodedCollab, FlowyError> {
+    csv_options: Option<share::CSVImportOptions>,
+  ) -> FutureResult<ImportedData, FlowyError> {
     let database_manager = self.0.clone();
     let view_id = view_id.to_string();
+    if let ImportType::DatabaseTemplate = import_type {
+      return FutureResult::new(async move {
+        let encoded_collab = database_manager
+          .create_database_from_template(&view_id, bytes)
+          .await?;
+        Ok(encoded_collab.into())
+      });
+    }
     let format = match import_type {
       ImportType::CSV => CSVFormat::Original,
       ImportType::HistoryDatabase => CSVFormat::META,
       ImportType::RawDatabase => CSVFormat::META,
       _ => CSVFormat::Original,
     };
+    let options = csv_import_options(csv_options);
     FutureResult::new(async move {
-      let content = tokio::task::spawn_blocking(move || {
-        String::from_utf8(bytes).map_err(|err| FlowyError::internal().with_context(err))
-      })
-      .await??;
       let result = database_manager
-        .import_csv(view_id, content, format)
+        .import_csv_with_options(view_id, bytes, format, options)
         .await?;
-      Ok(result.encoded_collab)
+      Ok(imported_data_from_csv_result(result))
     })
   }
 
   fn import_from_file_path(
     &self,
-    _view_id: &str,
+    view_id: &str,
     _name: &str,
     path: String,
+    csv_options: Option<share::CSVImportOptions>,
+  ) -> FutureResult<Option<ImportedData>, FlowyError> {
+    let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    let options = csv_import_options(csv_options);
+    FutureResult::new(async move {
+      let result = database_manager
+        .import_csv_from_file(view_id, path, CSVFormat::Original, options)
+        .await?;
+      Ok(Some(imported_data_from_csv_result(result)))
+    })
+  }
+
+  fn get_row_documents(&self, view_id: &str) -> FutureResult<Vec<RowDocument>, FlowyError> {
+    let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    FutureResult::new(async move {
+      let row_documents = database_manager
+        .get_row_documents(&view_id)
+        .await?
+        .into_iter()
+        .map(|(row_id, name, document_id)| RowDocument {
+          row_id,
+          name,
+          document_id,
+        })
+        .collect();
+      Ok(row_documents)
+    })
+  }
+
+  fn did_import_row_documents(
+    &self,
+    view_id: &str,
+    row_ids: Vec<String>,
+  ) -> FutureResult<(), FlowyError> {
+    let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    FutureResult::new(async move {
+      database_manager
+        .set_row_documents_not_empty(&view_id, row_ids)
+        .await
+    })
+  }
+
+  /// Exports the rows as a csv file, and the fields and the settings of the view, eg. the
+  /// filters, sorts and groups, as a json file.
+  fn export_view(
+    &self,
+    view_id: &str,
+    file_stem: &str,
+    _document_format: DocumentExportFormat,
+  ) -> FutureResult<Vec<ExportedFile>, FlowyError> {
+    let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    let file_stem = file_stem.to_string();
+    FutureResult::new(async move {
+      let csv = database_manager
+        .export_csv(&view_id, CSVFormat::Original)
+        .await?;
+      let metadata = database_manager
+        .export_database_template(&view_id, false)
+        .await?;
+      Ok(vec![
+        ExportedFile {
+          path: format!("{}.csv", file_stem),
+          data: csv.into_bytes(),
+        },
+        ExportedFile {
+          path: format!("{}.json", file_stem),
+          data: metadata.into_bytes(),
+        },
+      ])
+    })
+  }
+
+  fn publish_view_html(
+    &self,
+    view_id: &str,
+    _data: Option<Vec<u8>>,
+  ) -> FutureResult<PublishedHtml, FlowyError> {
+    let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    FutureResult::new(async move {
+      let body = database_manager.export_html(&view_id).await?;
+      Ok(PublishedHtml {
+        body,
+        files: vec![],
+      })
+    })
+  }
+
+  fn update_view_references(
+    &self,
+    view_id: &str,
+    block_ids: Vec<String>,
+    target_view_id: &str,
+    update: ViewReferenceUpdate,
   ) -> FutureResult<(), FlowyError> {
+    // The urls that link to the published view contain the name of the view. The other
+    // references only contain the id of the view.
+    let name = match update {
+      ViewReferenceUpdate::Renamed { name } => name,
+      _ => return FutureResult::new(async { Ok(()) }),
+    };
     let database_manager = self.0.clone();
+    let view_id = view_id.to_string();
+    let target_view_id = target_view_id.to_string();
     FutureResult::new(async move {
+      let publish_name = generate_publish_name(&target_view_id, &name);
       database_manager
-        .import_csv_from_file(path, CSVFormat::META)
+        .get_database_with_view_id(&view_id)
+        .await?
+        .update_view_urls(block_ids, &target_view_id, &publish_name)
         .await?;
       Ok(())
     })
@@ -480,6 +872,30 @@ impl FolderOperationHandler for DatabaseFolderOperation {
   }
 }
 
+fn csv_import_options(options: Option<share::CSVImportOptions>) -> CSVImportOptions {
+  options
+    .map(|options| CSVImportOptions {
+      delimiter: options.delimiter,
+      has_header: options.has_header,
+      encoding: options.encoding,
+    })
+    .unwrap_or_default()
+}
+
+fn imported_data_from_csv_result(result: ImportResult) -> ImportedData {
+  ImportedData {
+    encoded_collab: result.encoded_collab,
+    failed_rows: result
+      .failed_rows
+      .into_iter()
+      .map(|row| ImportFailedRow {
+        line: row.line,
+        message: row.message,
+      })
+      .collect(),
+  }
+}
+
 #[derive(Debug, serde::Deserialize)]
 struct CreateDatabaseExtParams {
   database_id: String,
@@ -555,7 +971,8 @@ impl FolderOperationHandler for ChatFolderOperation {
     _name: &str,
     _import_type: ImportType,
     _bytes: Vec<u8>,
-  ) -> FutureResult<EncodedCollab, FlowyError> {
+    _csv_options: Option<share::CSVImportOptions>,
+  ) -> FutureResult<ImportedData, FlowyError> {
     FutureResult::new(async move { Err(FlowyError::not_support()) })
   }
 
@@ -564,7 +981,8 @@ impl FolderOperationHandler for ChatFolderOperation {
     _view_id: &str,
     _name: &str,
     _path: String,
-  ) -> FutureResult<(), FlowyError> {
+    _csv_options: Option<share::CSVImportOptions>,
+  ) -> FutureResult<Option<ImportedData>, FlowyError> {
     FutureResult::new(async move { Err(FlowyError::not_support()) })
   }
 
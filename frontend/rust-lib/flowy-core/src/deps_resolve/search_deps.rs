@@ -1,4 +1,9 @@
+use flowy_database2::DatabaseManager;
 use flowy_folder::manager::FolderManager;
+use flowy_search::database::handler::DatabaseSearchHandler;
+use flowy_search::database::indexer::DatabaseIndexManagerImpl;
+use flowy_search::document::handler::DocumentSearchHandler;
+use flowy_search::document::indexer::DocumentIndexManagerImpl;
 use flowy_search::folder::handler::FolderSearchHandler;
 use flowy_search::folder::indexer::FolderIndexManagerImpl;
 use flowy_search::services::manager::SearchManager;
@@ -9,11 +14,30 @@ pub struct SearchDepsResolver();
 impl SearchDepsResolver {
   pub async fn resolve(
     folder_indexer: Arc<FolderIndexManagerImpl>,
-    _cloud_service: Arc<dyn SearchCloudService>,
-    _folder_manager: Arc<FolderManager>,
+    document_indexer: Arc<DocumentIndexManagerImpl>,
+    database_indexer: Arc<DatabaseIndexManagerImpl>,
+    cloud_service: Arc<dyn SearchCloudService>,
+    folder_manager: Arc<FolderManager>,
+    database_manager: Arc<DatabaseManager>,
   ) -> Arc<SearchManager> {
-    let folder_handler = Arc::new(FolderSearchHandler::new(folder_indexer));
-    // let document_handler = Arc::new(DocumentSearchHandler::new(cloud_service, folder_manager));
-    Arc::new(SearchManager::new(vec![folder_handler]))
+    let folder_handler = Arc::new(FolderSearchHandler::new(
+      folder_indexer,
+      folder_manager.clone(),
+    ));
+    let document_handler = Arc::new(DocumentSearchHandler::new(
+      cloud_service,
+      folder_manager.clone(),
+      document_indexer,
+    ));
+    let database_handler = Arc::new(DatabaseSearchHandler::new(
+      database_indexer,
+      folder_manager,
+      database_manager,
+    ));
+    Arc::new(SearchManager::new(vec![
+      folder_handler,
+      document_handler,
+      database_handler,
+    ]))
   }
 }
@@ -1,6 +1,7 @@
 use crate::integrate::server::ServerProvider;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use flowy_database2::DatabaseManager;
+use flowy_document::manager::DocumentManager;
 use flowy_error::FlowyResult;
 use flowy_folder::manager::FolderManager;
 use flowy_folder_pub::folder_builder::ParentChildViews;
@@ -8,9 +9,13 @@ use flowy_sqlite::kv::KVStorePreferences;
 use flowy_user::services::authenticate_user::AuthenticateUser;
 use flowy_user::user_manager::UserManager;
 use flowy_user_pub::workspace_service::UserWorkspaceService;
+use lib_dispatch::prelude::af_spawn;
 use lib_infra::async_trait::async_trait;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
 
 pub struct UserDepsResolver();
 
@@ -21,22 +26,75 @@ impl UserDepsResolver {
     server_provider: Arc<ServerProvider>,
     store_preference: Arc<KVStorePreferences>,
     database_manager: Arc<DatabaseManager>,
+    document_manager: Arc<DocumentManager>,
     folder_manager: Arc<FolderManager>,
   ) -> Arc<UserManager> {
+    let database_reminder_date_rx = database_manager.subscribe_reminder_date_change();
+    let document_reminder_date_rx = document_manager.subscribe_reminder_date_change();
     let workspace_service_impl = Arc::new(UserWorkspaceServiceImpl {
       database_manager,
       folder_manager,
     });
-    UserManager::new(
+    let user_manager = UserManager::new(
       server_provider,
       store_preference,
       Arc::downgrade(&collab_builder),
       authenticate_user,
       workspace_service_impl,
-    )
+    );
+    reschedule_reminders_on_date_change(
+      database_reminder_date_rx,
+      Arc::downgrade(&user_manager),
+      |change| {
+        (
+          change.reminder_id,
+          change.old_date,
+          change.new_date,
+          change.row_id,
+        )
+      },
+    );
+    reschedule_reminders_on_date_change(
+      document_reminder_date_rx,
+      Arc::downgrade(&user_manager),
+      |change| (change.reminder_id, change.old_date, change.new_date, None),
+    );
+    user_manager
   }
 }
 
+/// Moves the reminders of the date cells and of the date mentions along with their dates. The
+/// change is the id of the reminder, the old and the new date, and the row the date cell has
+/// moved to.
+fn reschedule_reminders_on_date_change<T, F>(
+  mut reminder_date_rx: broadcast::Receiver<T>,
+  user_manager: Weak<UserManager>,
+  date_change: F,
+) where
+  T: Clone + Send + 'static,
+  F: Fn(T) -> (String, i64, i64, Option<String>) + Send + 'static,
+{
+  af_spawn(async move {
+    loop {
+      let (reminder_id, old_date, new_date, row_id) = match reminder_date_rx.recv().await {
+        Ok(change) => date_change(change),
+        Err(RecvError::Lagged(_)) => continue,
+        Err(RecvError::Closed) => break,
+      };
+      let user_manager = match user_manager.upgrade() {
+        None => break,
+        Some(user_manager) => user_manager,
+      };
+      if let Err(err) = user_manager
+        .reschedule_reminder(&reminder_id, old_date, new_date, row_id.as_deref())
+        .await
+      {
+        warn!("Failed to reschedule reminder {}: {}", reminder_id, err);
+      }
+    }
+  });
+}
+
 pub struct UserWorkspaceServiceImpl {
   pub database_manager: Arc<DatabaseManager>,
   pub folder_manager: Arc<FolderManager>,
@@ -8,15 +8,17 @@ use serde_repr::*;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_server::af_cloud::define::ServerUser;
 use flowy_server::af_cloud::AppFlowyCloudServer;
-use flowy_server::local_server::{LocalServer, LocalServerDB};
+use flowy_server::local_server::{LocalCollabSnapshot, LocalServer, LocalServerDB};
 use flowy_server::supabase::SupabaseServer;
 use flowy_server::{AppFlowyEncryption, AppFlowyServer, EncryptionImpl};
 use flowy_server_pub::af_cloud_config::AFCloudConfiguration;
 use flowy_server_pub::supabase_config::SupabaseConfiguration;
 use flowy_server_pub::AuthenticatorType;
 use flowy_sqlite::kv::KVStorePreferences;
+use flowy_user::services::authenticate_user::AuthenticateUser;
 use flowy_user_pub::entities::*;
 
+use crate::deps_resolve::SnapshotDBImpl;
 use crate::AppFlowyCoreConfig;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize_repr, Deserialize_repr)]
@@ -65,6 +67,7 @@ pub struct ServerProvider {
   /// The authenticator type of the user.
   authenticator: RwLock<Authenticator>,
   user: Arc<dyn ServerUser>,
+  authenticate_user: Weak<AuthenticateUser>,
   pub(crate) uid: Arc<RwLock<Option<i64>>>,
 }
 
@@ -74,6 +77,7 @@ impl ServerProvider {
     server: Server,
     store_preferences: Weak<KVStorePreferences>,
     server_user: impl ServerUser + 'static,
+    authenticate_user: Weak<AuthenticateUser>,
   ) -> Self {
     let user = Arc::new(server_user);
     let encryption = EncryptionImpl::new(None);
@@ -86,6 +90,7 @@ impl ServerProvider {
       store_preferences,
       uid: Default::default(),
       user,
+      authenticate_user,
     }
   }
 
@@ -123,6 +128,7 @@ impl ServerProvider {
       Server::Local => {
         let local_db = Arc::new(LocalServerDBImpl {
           storage_path: self.config.storage_path.clone(),
+          snapshot_db: SnapshotDBImpl(self.authenticate_user.clone()),
         });
         let server = Arc::new(LocalServer::new(local_db));
         Ok::<Arc<dyn AppFlowyServer>, FlowyError>(server)
@@ -198,6 +204,7 @@ pub fn current_server_type() -> Server {
 struct LocalServerDBImpl {
   #[allow(dead_code)]
   storage_path: String,
+  snapshot_db: SnapshotDBImpl,
 }
 
 impl LocalServerDB for LocalServerDBImpl {
@@ -214,4 +221,28 @@ impl LocalServerDB for LocalServerDBImpl {
         .with_context("LocalServer doesn't support get_user_workspace"),
     )
   }
+
+  fn get_collab_snapshots(
+    &self,
+    object_id: &str,
+    limit: usize,
+  ) -> Result<Vec<LocalCollabSnapshot>, FlowyError> {
+    let snapshots = self
+      .snapshot_db
+      .get_snapshots(object_id, limit)?
+      .into_iter()
+      // The snapshots created by the old versions use the uuid as the id, which can't be
+      // converted to the snapshot id.
+      .filter_map(|row| {
+        let snapshot_id = row.id.parse::<i64>().ok()?;
+        Some(LocalCollabSnapshot {
+          snapshot_id,
+          object_id: row.object_id,
+          data: row.data,
+          created_at: row.timestamp,
+        })
+      })
+      .collect();
+    Ok(snapshots)
+  }
 }
@@ -1,18 +1,20 @@
 use std::sync::Arc;
 
 use anyhow::Context;
-use tracing::{event, trace};
+use tracing::{error, event, trace};
 
 use collab_entity::CollabType;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use flowy_database2::DatabaseManager;
 use flowy_document::manager::DocumentManager;
 use flowy_error::{FlowyError, FlowyResult};
+use flowy_folder::entities::ViewLayoutPB;
 use flowy_folder::manager::{FolderInitDataSource, FolderManager};
 use flowy_storage::manager::StorageManager;
 use flowy_user::event_map::UserStatusCallback;
 use flowy_user_pub::cloud::{UserCloudConfig, UserCloudServiceProvider};
 use flowy_user_pub::entities::{Authenticator, UserProfile, UserWorkspace};
+use lib_dispatch::prelude::af_spawn;
 use lib_infra::future::{to_fut, Fut};
 
 use crate::integrate::server::{Server, ServerProvider};
@@ -68,6 +70,8 @@ impl UserStatusCallback for UserStatusCallbackImpl {
         .await?;
       database_manager.initialize(user_id).await?;
       document_manager.initialize(user_id).await?;
+      index_documents_in_background(folder_manager, document_manager);
+      index_databases_in_background(database_manager);
       Ok(())
     })
   }
@@ -96,6 +100,8 @@ impl UserStatusCallback for UserStatusCallbackImpl {
       folder_manager.initialize_with_workspace_id(user_id).await?;
       database_manager.initialize(user_id).await?;
       document_manager.initialize(user_id).await?;
+      index_documents_in_background(folder_manager, document_manager);
+      index_databases_in_background(database_manager);
       Ok(())
     })
   }
@@ -186,6 +192,8 @@ impl UserStatusCallback for UserStatusCallbackImpl {
         .initialize_with_new_user(user_profile.uid)
         .await
         .context("DocumentManager error")?;
+      index_documents_in_background(folder_manager, document_manager);
+      index_databases_in_background(database_manager);
       Ok(())
     })
   }
@@ -207,6 +215,8 @@ impl UserStatusCallback for UserStatusCallbackImpl {
       folder_manager.initialize_with_workspace_id(user_id).await?;
       database_manager.initialize(user_id).await?;
       document_manager.initialize(user_id).await?;
+      index_documents_in_background(folder_manager, document_manager);
+      index_databases_in_background(database_manager);
       Ok(())
     })
   }
@@ -217,3 +227,35 @@ impl UserStatusCallback for UserStatusCallbackImpl {
     self.storage_manager.update_network_reachable(reachable);
   }
 }
+
+/// Index the documents that are not indexed yet, e.g. the documents that were created before
+/// the document index existed, or after the schema of the index changed.
+fn index_documents_in_background(
+  folder_manager: Arc<FolderManager>,
+  document_manager: Arc<DocumentManager>,
+) {
+  af_spawn(async move {
+    let doc_ids = match folder_manager.get_all_views_pb().await {
+      Ok(views) => views
+        .into_iter()
+        .filter(|view| view.layout == ViewLayoutPB::Document)
+        .map(|view| view.id)
+        .collect::<Vec<_>>(),
+      Err(err) => {
+        error!("Failed to get views for indexing documents: {}", err);
+        return;
+      },
+    };
+
+    if let Err(err) = document_manager.index_documents(doc_ids).await {
+      error!("Failed to index documents: {}", err);
+    }
+  });
+}
+
+/// Index the rows of the databases that are not indexed yet.
+fn index_databases_in_background(database_manager: Arc<DatabaseManager>) {
+  af_spawn(async move {
+    database_manager.index_databases().await;
+  });
+}
@@ -1,7 +1,11 @@
 #![allow(unused_doc_comments)]
 
+use flowy_search::backlinks::indexer::BacklinkIndexManagerImpl;
+use flowy_search::database::indexer::DatabaseIndexManagerImpl;
+use flowy_search::document::indexer::DocumentIndexManagerImpl;
 use flowy_search::folder::indexer::FolderIndexManagerImpl;
 use flowy_search::services::manager::SearchManager;
+use std::path::PathBuf;
 use std::sync::{Arc, Weak};
 use std::time::Duration;
 use sysinfo::System;
@@ -132,6 +136,7 @@ impl AppFlowyCore {
       server_type,
       Arc::downgrade(&store_preference),
       ServerUserImpl(Arc::downgrade(&authenticate_user)),
+      Arc::downgrade(&authenticate_user),
     ));
 
     event!(tracing::Level::DEBUG, "Init managers",);
@@ -161,26 +166,44 @@ impl AppFlowyCore {
       collab_builder
         .set_snapshot_persistence(Arc::new(SnapshotDBImpl(Arc::downgrade(&authenticate_user))));
 
+      let database_indexer = Arc::new(DatabaseIndexManagerImpl::new(Some(Arc::downgrade(
+        &authenticate_user,
+      ))));
+
+      let backlink_indexer = Arc::new(BacklinkIndexManagerImpl::new(Some(Arc::downgrade(
+        &authenticate_user,
+      ))));
+
+      let chat_manager = ChatDepsResolver::resolve(
+        Arc::downgrade(&authenticate_user),
+        server_provider.clone(),
+        store_preference.clone(),
+      );
+
       let database_manager = DatabaseDepsResolver::resolve(
         Arc::downgrade(&authenticate_user),
         task_dispatcher.clone(),
         collab_builder.clone(),
         server_provider.clone(),
+        chat_manager.local_ai_controller.clone(),
+        database_indexer.clone(),
+        backlink_indexer.clone(),
+        store_preference.clone(),
       )
       .await;
 
+      let document_indexer = Arc::new(DocumentIndexManagerImpl::new(Some(Arc::downgrade(
+        &authenticate_user,
+      ))));
+
       let document_manager = DocumentDepsResolver::resolve(
         Arc::downgrade(&authenticate_user),
         &database_manager,
         collab_builder.clone(),
         server_provider.clone(),
         Arc::downgrade(&storage_manager.storage_service),
-      );
-
-      let chat_manager = ChatDepsResolver::resolve(
-        Arc::downgrade(&authenticate_user),
-        server_provider.clone(),
-        store_preference.clone(),
+        document_indexer.clone(),
+        backlink_indexer.clone(),
       );
 
       let folder_indexer = Arc::new(FolderIndexManagerImpl::new(Some(Arc::downgrade(
@@ -191,6 +214,7 @@ impl AppFlowyCore {
         document_manager.clone(),
         database_manager.clone(),
         chat_manager.clone(),
+        PathBuf::from(authenticate_user.get_application_root_dir()),
       );
 
       let folder_manager = FolderDepsResolver::resolve(
@@ -198,6 +222,7 @@ impl AppFlowyCore {
         collab_builder.clone(),
         server_provider.clone(),
         folder_indexer.clone(),
+        backlink_indexer,
         store_preference.clone(),
         folder_operation_handlers,
       )
@@ -209,14 +234,18 @@ impl AppFlowyCore {
         server_provider.clone(),
         store_preference.clone(),
         database_manager.clone(),
+        document_manager.clone(),
         folder_manager.clone(),
       )
       .await;
 
       let search_manager = SearchDepsResolver::resolve(
         folder_indexer,
+        document_indexer,
+        database_indexer,
         server_provider.clone(),
         folder_manager.clone(),
+        database_manager.clone(),
       )
       .await;
 
@@ -0,0 +1,410 @@
+use std::collections::HashMap;
+
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+use validator::Validate;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::FilterPB;
+use crate::services::automation::{
+  Automation, AutomationAction, AutomationFilter, AutomationLog, AutomationLogStatus,
+  AutomationTrigger,
+};
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct AutomationPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub is_enabled: bool,
+
+  #[pb(index = 4)]
+  pub trigger: AutomationTriggerPB,
+
+  /// The filter tree that the row must meet to run the actions. Every row meets it if None.
+  #[pb(index = 5, one_of)]
+  pub condition: Option<FilterPB>,
+
+  #[pb(index = 6)]
+  pub actions: Vec<AutomationActionPB>,
+
+  #[pb(index = 7)]
+  pub created_at: i64,
+}
+
+impl From<&Automation> for AutomationPB {
+  fn from(automation: &Automation) -> Self {
+    Self {
+      id: automation.id.clone(),
+      name: automation.name.clone(),
+      is_enabled: automation.is_enabled,
+      trigger: AutomationTriggerPB::from(&automation.trigger),
+      condition: automation.condition.as_ref().map(FilterPB::from),
+      actions: automation
+        .actions
+        .iter()
+        .map(AutomationActionPB::from)
+        .collect(),
+      created_at: automation.created_at,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedAutomationPB {
+  #[pb(index = 1)]
+  pub items: Vec<AutomationPB>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum AutomationTriggerTypePB {
+  #[default]
+  RowCreated = 0,
+  CellChanged = 1,
+  DateReached = 2,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct AutomationTriggerPB {
+  #[pb(index = 1)]
+  pub trigger_type: AutomationTriggerTypePB,
+
+  /// The field whose cell fires the trigger. Empty if the trigger is RowCreated.
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  /// The text of the cell before the change. Any value matches if None.
+  #[pb(index = 3, one_of)]
+  pub from_value: Option<String>,
+
+  /// The text of the cell after the change. Any value matches if None.
+  #[pb(index = 4, one_of)]
+  pub to_value: Option<String>,
+}
+
+impl From<&AutomationTrigger> for AutomationTriggerPB {
+  fn from(trigger: &AutomationTrigger) -> Self {
+    match trigger {
+      AutomationTrigger::RowCreated => Self::default(),
+      AutomationTrigger::CellChanged { field_id, from, to } => Self {
+        trigger_type: AutomationTriggerTypePB::CellChanged,
+        field_id: field_id.clone(),
+        from_value: from.clone(),
+        to_value: to.clone(),
+      },
+      AutomationTrigger::DateReached { field_id } => Self {
+        trigger_type: AutomationTriggerTypePB::DateReached,
+        field_id: field_id.clone(),
+        ..Default::default()
+      },
+    }
+  }
+}
+
+impl TryFrom<AutomationTriggerPB> for AutomationTrigger {
+  type Error = ErrorCode;
+
+  fn try_from(value: AutomationTriggerPB) -> Result<Self, Self::Error> {
+    let trigger = match value.trigger_type {
+      AutomationTriggerTypePB::RowCreated => AutomationTrigger::RowCreated,
+      AutomationTriggerTypePB::CellChanged => AutomationTrigger::CellChanged {
+        field_id: parse_field_id(value.field_id)?,
+        from: value.from_value,
+        to: value.to_value,
+      },
+      AutomationTriggerTypePB::DateReached => AutomationTrigger::DateReached {
+        field_id: parse_field_id(value.field_id)?,
+      },
+    };
+    Ok(trigger)
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum AutomationActionTypePB {
+  #[default]
+  UpdateCell = 0,
+  CreateRow = 1,
+  MoveToGroup = 2,
+  SetRelation = 3,
+  SendReminder = 4,
+}
+
+/// Only the properties of the type of the action are used:
+/// - UpdateCell: `field_id` and `value`. `now` is the current time for a date field, and an
+///   empty value clears the cell.
+/// - CreateRow: `database_id` and `cells`, the data of the new row by field id.
+/// - MoveToGroup: `view_id` and `group_id`.
+/// - SetRelation: `field_id` and `row_ids`, the rows that are added to the relation cell.
+/// - SendReminder: `value`, the message of the reminder.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct AutomationActionPB {
+  #[pb(index = 1)]
+  pub action_type: AutomationActionTypePB,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub value: String,
+
+  #[pb(index = 4)]
+  pub database_id: String,
+
+  #[pb(index = 5)]
+  pub cells: HashMap<String, String>,
+
+  #[pb(index = 6)]
+  pub view_id: String,
+
+  #[pb(index = 7)]
+  pub group_id: String,
+
+  #[pb(index = 8)]
+  pub row_ids: Vec<String>,
+}
+
+impl From<&AutomationAction> for AutomationActionPB {
+  fn from(action: &AutomationAction) -> Self {
+    match action {
+      AutomationAction::UpdateCell { field_id, value } => Self {
+        action_type: AutomationActionTypePB::UpdateCell,
+        field_id: field_id.clone(),
+        value: value.clone(),
+        ..Default::default()
+      },
+      AutomationAction::CreateRow { database_id, cells } => Self {
+        action_type: AutomationActionTypePB::CreateRow,
+        database_id: database_id.clone(),
+        cells: cells.clone(),
+        ..Default::default()
+      },
+      AutomationAction::MoveToGroup { view_id, group_id } => Self {
+        action_type: AutomationActionTypePB::MoveToGroup,
+        view_id: view_id.clone(),
+        group_id: group_id.clone(),
+        ..Default::default()
+      },
+      AutomationAction::SetRelation { field_id, row_ids } => Self {
+        action_type: AutomationActionTypePB::SetRelation,
+        field_id: field_id.clone(),
+        row_ids: row_ids.clone(),
+        ..Default::default()
+      },
+      AutomationAction::SendReminder { message } => Self {
+        action_type: AutomationActionTypePB::SendReminder,
+        value: message.clone(),
+        ..Default::default()
+      },
+    }
+  }
+}
+
+impl TryFrom<AutomationActionPB> for AutomationAction {
+  type Error = ErrorCode;
+
+  fn try_from(value: AutomationActionPB) -> Result<Self, Self::Error> {
+    let action = match value.action_type {
+      AutomationActionTypePB::UpdateCell => AutomationAction::UpdateCell {
+        field_id: parse_field_id(value.field_id)?,
+        value: value.value,
+      },
+      AutomationActionTypePB::CreateRow => AutomationAction::CreateRow {
+        database_id: NotEmptyStr::parse(value.database_id)
+          .map_err(|_| ErrorCode::DatabaseIdIsEmpty)?
+          .0,
+        cells: value.cells,
+      },
+      AutomationActionTypePB::MoveToGroup => AutomationAction::MoveToGroup {
+        view_id: NotEmptyStr::parse(value.view_id)
+          .map_err(|_| ErrorCode::ViewIdIsInvalid)?
+          .0,
+        group_id: NotEmptyStr::parse(value.group_id)
+          .map_err(|_| ErrorCode::InvalidParams)?
+          .0,
+      },
+      AutomationActionTypePB::SetRelation => AutomationAction::SetRelation {
+        field_id: parse_field_id(value.field_id)?,
+        row_ids: value.row_ids,
+      },
+      AutomationActionTypePB::SendReminder => AutomationAction::SendReminder {
+        message: value.value,
+      },
+    };
+    Ok(action)
+  }
+}
+
+fn parse_field_id(field_id: String) -> Result<String, ErrorCode> {
+  NotEmptyStr::parse(field_id)
+    .map(|field_id| field_id.0)
+    .map_err(|_| ErrorCode::FieldIdIsEmpty)
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct CreateAutomationPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  #[pb(index = 3)]
+  pub trigger: AutomationTriggerPB,
+
+  #[pb(index = 4, one_of)]
+  pub condition: Option<FilterPB>,
+
+  #[pb(index = 5)]
+  pub actions: Vec<AutomationActionPB>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct UpdateAutomationPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub automation: AutomationPB,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct DeleteAutomationPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub automation_id: String,
+}
+
+/// The automation without id and creation time.
+pub struct AutomationParams {
+  pub name: String,
+  pub is_enabled: bool,
+  pub trigger: AutomationTrigger,
+  pub condition: Option<AutomationFilter>,
+  pub actions: Vec<AutomationAction>,
+}
+
+impl TryFrom<CreateAutomationPayloadPB> for AutomationParams {
+  type Error = ErrorCode;
+
+  fn try_from(value: CreateAutomationPayloadPB) -> Result<Self, Self::Error> {
+    Ok(Self {
+      name: value.name,
+      is_enabled: true,
+      trigger: value.trigger.try_into()?,
+      condition: value.condition.as_ref().map(AutomationFilter::from),
+      actions: value
+        .actions
+        .into_iter()
+        .map(AutomationAction::try_from)
+        .collect::<Result<Vec<_>, _>>()?,
+    })
+  }
+}
+
+impl TryFrom<AutomationPB> for AutomationParams {
+  type Error = ErrorCode;
+
+  fn try_from(value: AutomationPB) -> Result<Self, Self::Error> {
+    Ok(Self {
+      name: value.name,
+      is_enabled: value.is_enabled,
+      trigger: value.trigger.try_into()?,
+      condition: value.condition.as_ref().map(AutomationFilter::from),
+      actions: value
+        .actions
+        .into_iter()
+        .map(AutomationAction::try_from)
+        .collect::<Result<Vec<_>, _>>()?,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum AutomationLogStatusPB {
+  #[default]
+  Succeeded = 0,
+  Failed = 1,
+  Skipped = 2,
+}
+
+impl From<AutomationLogStatus> for AutomationLogStatusPB {
+  fn from(status: AutomationLogStatus) -> Self {
+    match status {
+      AutomationLogStatus::Succeeded => Self::Succeeded,
+      AutomationLogStatus::Failed => Self::Failed,
+      AutomationLogStatus::Skipped => Self::Skipped,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct AutomationLogPB {
+  #[pb(index = 1)]
+  pub automation_id: String,
+
+  #[pb(index = 2)]
+  pub automation_name: String,
+
+  #[pb(index = 3)]
+  pub row_id: String,
+
+  #[pb(index = 4)]
+  pub status: AutomationLogStatusPB,
+
+  /// Why the automation failed or was skipped. Empty if it succeeded.
+  #[pb(index = 5)]
+  pub message: String,
+
+  #[pb(index = 6)]
+  pub timestamp: i64,
+}
+
+impl From<AutomationLog> for AutomationLogPB {
+  fn from(log: AutomationLog) -> Self {
+    Self {
+      automation_id: log.automation_id,
+      automation_name: log.automation_name,
+      row_id: log.row_id,
+      status: log.status.into(),
+      message: log.message,
+      timestamp: log.timestamp,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedAutomationLogPB {
+  #[pb(index = 1)]
+  pub items: Vec<AutomationLogPB>,
+}
+
+/// Sent with the [crate::notification::DatabaseNotification::DidTriggerAutomationReminder]
+/// notification when an automation sends a reminder.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct AutomationReminderPB {
+  #[pb(index = 1)]
+  pub database_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub automation_id: String,
+
+  #[pb(index = 4)]
+  pub message: String,
+}
@@ -63,6 +63,7 @@ pub enum CalculationType {
   Count = 5,         // All
   CountEmpty = 6,    // All
   CountNonEmpty = 7, // All
+  CountComments = 8, // All, counts the comments of the rows instead of the cells
 }
 
 impl Display for CalculationType {
@@ -122,7 +123,7 @@ impl CalculationType {
         FieldType::URL | FieldType::Checkbox | FieldType::CreatedTime | FieldType::LastEditedTime
       ),
       // All fields
-      CalculationType::Count => true,
+      CalculationType::Count | CalculationType::CountComments => true,
     }
   }
 }
@@ -81,11 +81,20 @@ impl std::convert::From<CalendarLayout> for CalendarLayoutPB {
 pub struct CalendarEventRequestPB {
   #[pb(index = 1)]
   pub view_id: String,
+
+  /// The start of the visible range. The recurring dates are expanded into their occurrences
+  /// between `start` and `end` if both are set.
+  #[pb(index = 2, one_of)]
+  pub start: Option<i64>,
+
+  #[pb(index = 3, one_of)]
+  pub end: Option<i64>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct CalendarEventRequestParams {
   pub view_id: String,
+  pub range: Option<(i64, i64)>,
 }
 
 impl TryInto<CalendarEventRequestParams> for CalendarEventRequestPB {
@@ -93,7 +102,15 @@ impl TryInto<CalendarEventRequestParams> for CalendarEventRequestPB {
 
   fn try_into(self) -> Result<CalendarEventRequestParams, Self::Error> {
     let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
-    Ok(CalendarEventRequestParams { view_id: view_id.0 })
+    let range = match (self.start, self.end) {
+      (Some(start), Some(end)) if start > end => return Err(ErrorCode::InvalidParams),
+      (Some(start), Some(end)) => Some((start, end)),
+      _ => None,
+    };
+    Ok(CalendarEventRequestParams {
+      view_id: view_id.0,
+      range,
+    })
   }
 }
 
@@ -113,6 +130,11 @@ pub struct CalendarEventPB {
 
   #[pb(index = 5)]
   pub is_scheduled: bool,
+
+  /// The date of the row repeats. The event is one of its occurrences if the events were
+  /// requested for a range.
+  #[pb(index = 6)]
+  pub is_recurring: bool,
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
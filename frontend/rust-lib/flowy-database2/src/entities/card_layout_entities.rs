@@ -0,0 +1,98 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+use crate::services::setting::{CardLayoutSetting, CardSize, CoverFitMode};
+
+/// The setting of the gallery and the list layouts, which display each row as a card.
+#[derive(Debug, Clone, Default, Eq, PartialEq, ProtoBuf)]
+pub struct CardLayoutSettingPB {
+  #[pb(index = 1)]
+  pub card_size: CardSizePB,
+
+  /// The id of the url field used as the cover of the cards. The cover of the row is used
+  /// if it's empty.
+  #[pb(index = 2)]
+  pub cover_field_id: String,
+
+  #[pb(index = 3)]
+  pub fit_mode: CoverFitModePB,
+
+  #[pb(index = 4)]
+  pub visible_field_ids: Vec<String>,
+}
+
+impl From<CardLayoutSetting> for CardLayoutSettingPB {
+  fn from(setting: CardLayoutSetting) -> Self {
+    Self {
+      card_size: setting.card_size.into(),
+      cover_field_id: setting.cover_field_id,
+      fit_mode: setting.fit_mode.into(),
+      visible_field_ids: setting.visible_field_ids,
+    }
+  }
+}
+
+impl From<CardLayoutSettingPB> for CardLayoutSetting {
+  fn from(setting: CardLayoutSettingPB) -> Self {
+    Self {
+      card_size: setting.card_size.into(),
+      cover_field_id: setting.cover_field_id,
+      fit_mode: setting.fit_mode.into(),
+      visible_field_ids: setting.visible_field_ids,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum CardSizePB {
+  Small = 0,
+  #[default]
+  Medium = 1,
+  Large = 2,
+}
+
+impl From<CardSizePB> for CardSize {
+  fn from(pb: CardSizePB) -> Self {
+    match pb {
+      CardSizePB::Small => CardSize::Small,
+      CardSizePB::Medium => CardSize::Medium,
+      CardSizePB::Large => CardSize::Large,
+    }
+  }
+}
+
+impl From<CardSize> for CardSizePB {
+  fn from(size: CardSize) -> Self {
+    match size {
+      CardSize::Small => CardSizePB::Small,
+      CardSize::Medium => CardSizePB::Medium,
+      CardSize::Large => CardSizePB::Large,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum CoverFitModePB {
+  #[default]
+  Fill = 0,
+  Fit = 1,
+}
+
+impl From<CoverFitModePB> for CoverFitMode {
+  fn from(pb: CoverFitModePB) -> Self {
+    match pb {
+      CoverFitModePB::Fill => CoverFitMode::Fill,
+      CoverFitModePB::Fit => CoverFitMode::Fit,
+    }
+  }
+}
+
+impl From<CoverFitMode> for CoverFitModePB {
+  fn from(mode: CoverFitMode) -> Self {
+    match mode {
+      CoverFitMode::Fill => CoverFitModePB::Fill,
+      CoverFitMode::Fit => CoverFitModePB::Fit,
+    }
+  }
+}
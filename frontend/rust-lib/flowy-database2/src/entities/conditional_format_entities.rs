@@ -0,0 +1,177 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::{FieldType, FilterDataPB};
+use crate::services::conditional_format::{CellFormat, ConditionalFormat, ConditionalFormatTarget};
+use crate::services::filter::FilterInner;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum ConditionalFormatTargetPB {
+  #[default]
+  Cell = 0,
+  Row = 1,
+}
+
+impl From<ConditionalFormatTarget> for ConditionalFormatTargetPB {
+  fn from(target: ConditionalFormatTarget) -> Self {
+    match target {
+      ConditionalFormatTarget::Cell => Self::Cell,
+      ConditionalFormatTarget::Row => Self::Row,
+    }
+  }
+}
+
+impl From<ConditionalFormatTargetPB> for ConditionalFormatTarget {
+  fn from(target: ConditionalFormatTargetPB) -> Self {
+    match target {
+      ConditionalFormatTargetPB::Cell => Self::Cell,
+      ConditionalFormatTargetPB::Row => Self::Row,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, PartialEq, Eq)]
+pub struct ConditionalFormatPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  /// The condition on the cell of a field. It's the same as the data of a filter of the field,
+  /// e.g. a [crate::entities::NumberFilterPB] for a number field.
+  #[pb(index = 2)]
+  pub condition: FilterDataPB,
+
+  #[pb(index = 3)]
+  pub target: ConditionalFormatTargetPB,
+
+  #[pb(index = 4, one_of)]
+  pub background_color: Option<String>,
+
+  #[pb(index = 5, one_of)]
+  pub text_color: Option<String>,
+}
+
+impl From<&ConditionalFormat> for ConditionalFormatPB {
+  fn from(format: &ConditionalFormat) -> Self {
+    Self {
+      id: format.id.clone(),
+      condition: FilterDataPB {
+        field_id: format.field_id.clone(),
+        field_type: FieldType::from(format.field_type),
+        data: format.condition.clone(),
+      },
+      target: format.target.into(),
+      background_color: format.background_color.clone(),
+      text_color: format.text_color.clone(),
+    }
+  }
+}
+
+impl TryFrom<ConditionalFormatPB> for ConditionalFormat {
+  type Error = ErrorCode;
+
+  fn try_from(value: ConditionalFormatPB) -> Result<Self, Self::Error> {
+    let field_id = NotEmptyStr::parse(value.condition.field_id.clone())
+      .map_err(|_| ErrorCode::FieldIdIsEmpty)?
+      .0;
+    // Makes sure that the condition is a valid filter of the field type.
+    FilterInner::try_from(value.condition.clone())?;
+
+    Ok(Self {
+      id: value.id,
+      field_id,
+      field_type: value.condition.field_type.into(),
+      condition: value.condition.data,
+      target: value.target.into(),
+      background_color: value.background_color,
+      text_color: value.text_color,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedConditionalFormatPB {
+  #[pb(index = 1)]
+  pub items: Vec<ConditionalFormatPB>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct CreateConditionalFormatPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub condition: FilterDataPB,
+
+  #[pb(index = 3)]
+  pub target: ConditionalFormatTargetPB,
+
+  #[pb(index = 4, one_of)]
+  pub background_color: Option<String>,
+
+  #[pb(index = 5, one_of)]
+  pub text_color: Option<String>,
+}
+
+impl TryFrom<CreateConditionalFormatPayloadPB> for ConditionalFormat {
+  type Error = ErrorCode;
+
+  fn try_from(value: CreateConditionalFormatPayloadPB) -> Result<Self, Self::Error> {
+    ConditionalFormat::try_from(ConditionalFormatPB {
+      id: nanoid::nanoid!(10),
+      condition: value.condition,
+      target: value.target,
+      background_color: value.background_color,
+      text_color: value.text_color,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct UpdateConditionalFormatPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub format: ConditionalFormatPB,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct DeleteConditionalFormatPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub format_id: String,
+}
+
+/// The colors that the conditional formatting rules give to a row, or to one of its cells.
+#[derive(Debug, Clone, Default, ProtoBuf, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellFormatPB {
+  /// The field of the cell. The colors apply to the whole row if None.
+  #[pb(index = 1, one_of)]
+  pub field_id: Option<String>,
+
+  #[pb(index = 2, one_of)]
+  pub background_color: Option<String>,
+
+  #[pb(index = 3, one_of)]
+  pub text_color: Option<String>,
+}
+
+impl From<CellFormat> for CellFormatPB {
+  fn from(format: CellFormat) -> Self {
+    Self {
+      field_id: format.field_id,
+      background_color: format.background_color,
+      text_color: format.text_color,
+    }
+  }
+}
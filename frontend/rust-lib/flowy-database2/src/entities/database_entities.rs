@@ -323,3 +323,44 @@ pub struct DatabaseSnapshotPB {
   #[pb(index = 4)]
   pub data: Vec<u8>,
 }
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct CreateDatabaseSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub title: String,
+
+  #[pb(index = 3)]
+  pub desc: String,
+}
+
+pub struct CreateDatabaseSnapshotParams {
+  pub view_id: String,
+  pub title: String,
+  pub desc: String,
+}
+
+impl TryInto<CreateDatabaseSnapshotParams> for CreateDatabaseSnapshotPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<CreateDatabaseSnapshotParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    let title = NotEmptyStr::parse(self.title).map_err(|_| ErrorCode::InvalidParams)?;
+    Ok(CreateDatabaseSnapshotParams {
+      view_id: view_id.0,
+      title: title.0,
+      desc: self.desc,
+    })
+  }
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RestoreDatabaseSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub snapshot_id: i64,
+}
@@ -14,8 +14,10 @@ use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::position_entities::OrderObjectPositionPB;
+use crate::entities::FieldValidationPB;
 use crate::impl_into_field_type;
 use crate::services::field::{default_type_option_data_from_type, type_option_to_pb};
+use crate::services::validation::FieldValidation;
 
 /// [FieldPB] defines a Field's attributes. Such as the name, field_type, and width. etc.
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -34,6 +36,10 @@ pub struct FieldPB {
 
   #[pb(index = 7)]
   pub type_option_data: Vec<u8>,
+
+  /// The constraints that the cells of the field must satisfy in every view.
+  #[pb(index = 8)]
+  pub validation: FieldValidationPB,
 }
 
 impl FieldPB {
@@ -42,12 +48,14 @@ impl FieldPB {
     let type_option = field
       .get_any_type_option(field_type)
       .unwrap_or_else(|| default_type_option_data_from_type(field_type));
+    let validation = FieldValidation::from_field(&field);
     Self {
       id: field.id,
       name: field.name,
       field_type,
       is_primary: field.is_primary,
       type_option_data: type_option_to_pb(type_option, &field_type).to_vec(),
+      validation: validation.into(),
     }
   }
 }
@@ -380,6 +388,9 @@ pub struct FieldChangesetPB {
 
   #[pb(index = 5, one_of)]
   pub frozen: Option<bool>,
+
+  #[pb(index = 6, one_of)]
+  pub validation: Option<FieldValidationPB>,
 }
 
 impl TryInto<FieldChangesetParams> for FieldChangesetPB {
@@ -395,6 +406,7 @@ impl TryInto<FieldChangesetParams> for FieldChangesetPB {
       name: self.name,
       desc: self.desc,
       frozen: self.frozen,
+      validation: self.validation.map(FieldValidation::from),
     })
   }
 }
@@ -410,6 +422,8 @@ pub struct FieldChangesetParams {
   pub desc: Option<String>,
 
   pub frozen: Option<bool>,
+
+  pub validation: Option<FieldValidation>,
 }
 /// Certain field types have user-defined options such as color, date format, number format,
 /// or a list of values for a multi-select list. These options are defined within a specialization
@@ -451,6 +465,8 @@ pub enum FieldType {
   Summary = 11,
   Translate = 12,
   Time = 13,
+  Formula = 14,
+  Rollup = 15,
 }
 
 impl Display for FieldType {
@@ -493,6 +509,8 @@ impl FieldType {
       FieldType::Summary => "Summarize",
       FieldType::Translate => "Translate",
       FieldType::Time => "Time",
+      FieldType::Formula => "Formula",
+      FieldType::Rollup => "Rollup",
     };
     s.to_string()
   }
@@ -553,6 +571,14 @@ impl FieldType {
     matches!(self, FieldType::Time)
   }
 
+  pub fn is_formula(&self) -> bool {
+    matches!(self, FieldType::Formula)
+  }
+
+  pub fn is_rollup(&self) -> bool {
+    matches!(self, FieldType::Rollup)
+  }
+
   pub fn can_be_group(&self) -> bool {
     self.is_select_option() || self.is_checkbox() || self.is_url()
   }
@@ -5,7 +5,7 @@ use std::ops::Deref;
 use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
-use crate::entities::RepeatedFieldIdPB;
+use crate::entities::{ConditionalFormatPB, RepeatedFieldIdPB};
 use crate::impl_into_field_visibility;
 use crate::services::field_settings::FieldSettings;
 
@@ -23,6 +23,9 @@ pub struct FieldSettingsPB {
 
   #[pb(index = 4)]
   pub wrap_cell_content: bool,
+
+  #[pb(index = 5)]
+  pub conditional_formats: Vec<ConditionalFormatPB>,
 }
 
 impl From<FieldSettings> for FieldSettingsPB {
@@ -32,6 +35,11 @@ impl From<FieldSettings> for FieldSettingsPB {
       visibility: value.visibility,
       width: value.width,
       wrap_cell_content: value.wrap_cell_content,
+      conditional_formats: value
+        .conditional_formats
+        .iter()
+        .map(ConditionalFormatPB::from)
+        .collect(),
     }
   }
 }
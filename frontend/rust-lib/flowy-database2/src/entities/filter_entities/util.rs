@@ -117,6 +117,10 @@ impl From<&Filter> for FilterPB {
             .cloned::<TextFilterPB>()
             .unwrap()
             .try_into(),
+          FieldType::Formula | FieldType::Rollup => condition_and_content
+            .cloned::<TextFilterPB>()
+            .unwrap()
+            .try_into(),
         };
 
         Self {
@@ -170,6 +174,9 @@ impl TryFrom<FilterDataPB> for FilterInner {
       FieldType::Translate => {
         BoxAny::new(TextFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
+      FieldType::Formula | FieldType::Rollup => {
+        BoxAny::new(TextFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
+      },
     };
 
     Ok(Self::Data {
@@ -180,6 +187,35 @@ impl TryFrom<FilterDataPB> for FilterInner {
   }
 }
 
+impl TryFrom<FilterPB> for Filter {
+  type Error = ErrorCode;
+
+  fn try_from(value: FilterPB) -> Result<Self, Self::Error> {
+    let inner = match value.filter_type {
+      FilterType::And => FilterInner::And {
+        children: value
+          .children
+          .into_iter()
+          .map(Filter::try_from)
+          .collect::<Result<Vec<_>, _>>()?,
+      },
+      FilterType::Or => FilterInner::Or {
+        children: value
+          .children
+          .into_iter()
+          .map(Filter::try_from)
+          .collect::<Result<Vec<_>, _>>()?,
+      },
+      FilterType::Data => value.data.ok_or(ErrorCode::InvalidParams)?.try_into()?,
+    };
+
+    Ok(Self {
+      id: value.id,
+      inner,
+    })
+  }
+}
+
 #[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
 pub struct RepeatedFilterPB {
   #[pb(index = 1)]
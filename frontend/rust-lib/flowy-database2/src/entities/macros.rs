@@ -18,6 +18,8 @@ macro_rules! impl_into_field_type {
           11 => FieldType::Summary,
           12 => FieldType::Translate,
           13 => FieldType::Time,
+          14 => FieldType::Formula,
+          15 => FieldType::Rollup,
           _ => {
             tracing::error!("🔴Can't parse FieldType from value: {}", ty);
             FieldType::RichText
@@ -61,6 +63,7 @@ macro_rules! impl_into_calculation_type {
           5 => CalculationType::Count,
           6 => CalculationType::CountEmpty,
           7 => CalculationType::CountNonEmpty,
+          8 => CalculationType::CountComments,
           _ => {
             tracing::error!("🔴 Can't parse CalculationType from value: {}", ty);
             CalculationType::Average
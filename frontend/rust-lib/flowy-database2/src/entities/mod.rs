@@ -1,7 +1,10 @@
+mod automation_entities;
 mod board_entities;
 pub mod calculation;
 mod calendar_entities;
+mod card_layout_entities;
 mod cell_entities;
+mod conditional_format_entities;
 mod database_entities;
 mod field_entities;
 mod field_settings_entities;
@@ -9,31 +12,42 @@ pub mod filter_entities;
 mod group_entities;
 pub mod parser;
 mod position_entities;
+mod row_comment_entities;
 mod row_entities;
 pub mod setting_entities;
 mod share_entities;
+mod snapshot_entities;
 mod sort_entities;
+mod timeline_entities;
 mod type_option_entities;
+mod validation_entities;
 mod view_entities;
 
 #[macro_use]
 mod macros;
 
+pub use automation_entities::*;
 pub use board_entities::*;
 pub use calculation::*;
 pub use calendar_entities::*;
+pub use card_layout_entities::*;
 pub use cell_entities::*;
+pub use conditional_format_entities::*;
 pub use database_entities::*;
 pub use field_entities::*;
 pub use field_settings_entities::*;
 pub use filter_entities::*;
 pub use group_entities::*;
 pub use position_entities::*;
+pub use row_comment_entities::*;
 pub use row_entities::*;
 pub use setting_entities::*;
 pub use share_entities::*;
+pub use snapshot_entities::*;
 pub use sort_entities::*;
+pub use timeline_entities::*;
 pub use type_option_entities::*;
+pub use validation_entities::*;
 pub use view_entities::*;
 
 mod utils {
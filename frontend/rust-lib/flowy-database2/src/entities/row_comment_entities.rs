@@ -0,0 +1,145 @@
+use flowy_derive::ProtoBuf;
+use validator::Validate;
+
+use crate::services::comment::{RowComment, RowComments};
+
+#[derive(Debug, Clone, Default, ProtoBuf, PartialEq, Eq)]
+pub struct RowCommentPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  #[pb(index = 2)]
+  pub author_uid: i64,
+
+  /// The json string of a delta. A person is mentioned with the attributes
+  /// `{ "mention": { "type": "person", "person_id": "<uid>" } }`.
+  #[pb(index = 3)]
+  pub content: String,
+
+  #[pb(index = 4)]
+  pub created_at: i64,
+
+  #[pb(index = 5)]
+  pub updated_at: i64,
+
+  #[pb(index = 6)]
+  pub is_resolved: bool,
+
+  /// Always empty for a reply.
+  #[pb(index = 7)]
+  pub replies: Vec<RowCommentPB>,
+}
+
+impl From<&RowComment> for RowCommentPB {
+  fn from(comment: &RowComment) -> Self {
+    Self {
+      id: comment.id.clone(),
+      author_uid: comment.author_uid,
+      content: comment.content.clone(),
+      created_at: comment.created_at,
+      updated_at: comment.updated_at,
+      is_resolved: comment.is_resolved,
+      replies: comment.replies.iter().map(RowCommentPB::from).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedRowCommentPB {
+  #[pb(index = 1)]
+  pub items: Vec<RowCommentPB>,
+}
+
+impl From<&RowComments> for RepeatedRowCommentPB {
+  fn from(comments: &RowComments) -> Self {
+    Self {
+      items: comments.0.iter().map(RowCommentPB::from).collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct AddRowCommentPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub row_id: String,
+
+  /// A delta, or a plain text.
+  #[pb(index = 3)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub content: String,
+
+  /// Adds a reply to the thread of the comment if set.
+  #[pb(index = 4, one_of)]
+  pub parent_comment_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct UpdateRowCommentPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub comment_id: String,
+
+  #[pb(index = 4)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub content: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct RowCommentIdPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub comment_id: String,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf, Validate)]
+pub struct ResolveRowCommentPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub row_id: String,
+
+  /// The comment that starts the thread, or one of its replies.
+  #[pb(index = 3)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub comment_id: String,
+
+  #[pb(index = 4)]
+  pub is_resolved: bool,
+}
+
+/// Sent to the current user when a comment of another user mentions them.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RowCommentMentionPB {
+  #[pb(index = 1)]
+  pub database_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub comment: RowCommentPB,
+}
@@ -11,6 +11,7 @@ use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::position_entities::OrderObjectPositionPB;
+use crate::entities::CellFormatPB;
 use crate::services::database::{InsertedRow, UpdatedRow};
 
 /// [RowPB] Describes a row. Has the id of the parent Block. Has the metadata of the row.
@@ -66,6 +67,18 @@ pub struct RowMetaPB {
 
   #[pb(index = 5)]
   pub is_document_empty: bool,
+
+  /// The colors of the row and of its cells that are given by the conditional formatting rules of
+  /// the view.
+  #[pb(index = 6)]
+  pub formats: Vec<CellFormatPB>,
+}
+
+impl RowMetaPB {
+  pub fn with_formats(mut self, formats: Vec<CellFormatPB>) -> Self {
+    self.formats = formats;
+    self
+  }
 }
 
 impl std::convert::From<&RowDetail> for RowMetaPB {
@@ -76,6 +89,7 @@ impl std::convert::From<&RowDetail> for RowMetaPB {
       icon: row_detail.meta.icon_url.clone(),
       cover: row_detail.meta.cover_url.clone(),
       is_document_empty: row_detail.meta.is_document_empty,
+      formats: vec![],
     }
   }
 }
@@ -87,6 +101,7 @@ impl std::convert::From<RowDetail> for RowMetaPB {
       icon: row_detail.meta.icon_url,
       cover: row_detail.meta.cover_url,
       is_document_empty: row_detail.meta.is_document_empty,
+      formats: vec![],
     }
   }
 }
@@ -108,6 +123,10 @@ pub struct UpdateRowMetaChangesetPB {
 
   #[pb(index = 5, one_of)]
   pub is_document_empty: Option<bool>,
+
+  /// Adds a comment of the current user to the row.
+  #[pb(index = 6, one_of)]
+  pub insert_comment: Option<RowCommentPayloadPB>,
 }
 
 #[derive(Debug)]
@@ -117,6 +136,7 @@ pub struct UpdateRowMetaParams {
   pub icon_url: Option<String>,
   pub cover_url: Option<String>,
   pub is_document_empty: Option<bool>,
+  pub insert_comment: Option<RowCommentParams>,
 }
 
 impl TryInto<UpdateRowMetaParams> for UpdateRowMetaChangesetPB {
@@ -130,12 +150,17 @@ impl TryInto<UpdateRowMetaParams> for UpdateRowMetaChangesetPB {
     let view_id = NotEmptyStr::parse(self.view_id)
       .map_err(|_| ErrorCode::ViewIdIsInvalid)?
       .0;
+    let insert_comment = self
+      .insert_comment
+      .map(|comment| comment.try_into())
+      .transpose()?;
     Ok(UpdateRowMetaParams {
       id: row_id,
       view_id,
       icon_url: self.icon_url,
       cover_url: self.cover_url,
       is_document_empty: self.is_document_empty,
+      insert_comment,
     })
   }
 }
@@ -395,3 +420,50 @@ pub struct TranslateRowPB {
   #[validate(custom = "required_not_empty_str")]
   pub field_id: String,
 }
+
+/// Fills the cells of an AI field, the summary or the translate field, for every row of the view.
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct FillAIFieldPB {
+  #[pb(index = 1)]
+  #[validate(custom = "required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "required_not_empty_str")]
+  pub field_id: String,
+
+  /// The cells that already have content are skipped unless it's true.
+  #[pb(index = 3)]
+  pub overwrite: bool,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct CancelFillAIFieldPB {
+  #[pb(index = 1)]
+  #[validate(custom = "required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "required_not_empty_str")]
+  pub field_id: String,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct AIFillRowResultPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  /// None if the cell of the row was filled successfully.
+  #[pb(index = 2, one_of)]
+  pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct AIFillResultPB {
+  /// The results of the rows that were processed, in the order of the rows.
+  #[pb(index = 1)]
+  pub rows: Vec<AIFillRowResultPB>,
+
+  #[pb(index = 2)]
+  pub is_cancelled: bool,
+}
@@ -9,11 +9,15 @@ use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::{
-  CalendarLayoutSettingPB, DeleteFilterPB, DeleteSortPayloadPB, InsertFilterPB,
-  RepeatedFieldSettingsPB, RepeatedFilterPB, RepeatedGroupSettingPB, RepeatedSortPB,
-  UpdateFilterDataPB, UpdateFilterTypePB, UpdateGroupPB, UpdateSortPayloadPB,
+  CalendarLayoutSettingPB, CardLayoutSettingPB, DeleteFilterPB, DeleteSortPayloadPB,
+  InsertFilterPB, RepeatedFieldSettingsPB, RepeatedFilterPB, RepeatedGroupSettingPB,
+  RepeatedSortPB, TimelineLayoutSettingPB, UpdateFilterDataPB, UpdateFilterTypePB, UpdateGroupPB,
+  UpdateSortPayloadPB,
+};
+use crate::services::setting::{
+  BoardLayoutSetting, CalendarLayoutSetting, CardLayoutSetting, GridLayout, GridLayoutSetting,
+  TimelineLayoutSetting,
 };
-use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
 
 use super::{BoardLayoutSettingPB, ReorderSortPayloadPB};
 
@@ -46,6 +50,48 @@ pub enum DatabaseLayoutPB {
   Grid = 0,
   Board = 1,
   Calendar = 2,
+  /// Stored as a [DatabaseLayout::Grid], see [GridLayout].
+  Gallery = 3,
+  /// Stored as a [DatabaseLayout::Grid], see [GridLayout].
+  List = 4,
+  /// Stored as a [DatabaseLayout::Grid], see [GridLayout].
+  Timeline = 5,
+}
+
+impl DatabaseLayoutPB {
+  /// Returns the layout of a view from the layout stored in the view and, for a grid view, the
+  /// [GridLayoutSetting] of the view.
+  pub fn from_view_layout(layout: DatabaseLayout, grid_setting: Option<GridLayoutSetting>) -> Self {
+    match layout {
+      DatabaseLayout::Grid => grid_setting
+        .map(|setting| setting.layout_ty)
+        .unwrap_or_default()
+        .into(),
+      _ => layout.into(),
+    }
+  }
+
+  /// Returns the [GridLayout] if the layout is displayed using the grid layout of the view.
+  pub fn grid_layout(&self) -> Option<GridLayout> {
+    match self {
+      DatabaseLayoutPB::Grid => Some(GridLayout::Table),
+      DatabaseLayoutPB::Gallery => Some(GridLayout::Gallery),
+      DatabaseLayoutPB::List => Some(GridLayout::List),
+      DatabaseLayoutPB::Timeline => Some(GridLayout::Timeline),
+      DatabaseLayoutPB::Board | DatabaseLayoutPB::Calendar => None,
+    }
+  }
+}
+
+impl std::convert::From<GridLayout> for DatabaseLayoutPB {
+  fn from(layout: GridLayout) -> Self {
+    match layout {
+      GridLayout::Table => DatabaseLayoutPB::Grid,
+      GridLayout::Gallery => DatabaseLayoutPB::Gallery,
+      GridLayout::List => DatabaseLayoutPB::List,
+      GridLayout::Timeline => DatabaseLayoutPB::Timeline,
+    }
+  }
 }
 
 impl std::convert::From<DatabaseLayout> for DatabaseLayoutPB {
@@ -61,7 +107,10 @@ impl std::convert::From<DatabaseLayout> for DatabaseLayoutPB {
 impl std::convert::From<DatabaseLayoutPB> for DatabaseLayout {
   fn from(layout: DatabaseLayoutPB) -> Self {
     match layout {
-      DatabaseLayoutPB::Grid => DatabaseLayout::Grid,
+      DatabaseLayoutPB::Grid
+      | DatabaseLayoutPB::Gallery
+      | DatabaseLayoutPB::List
+      | DatabaseLayoutPB::Timeline => DatabaseLayout::Grid,
       DatabaseLayoutPB::Board => DatabaseLayout::Board,
       DatabaseLayoutPB::Calendar => DatabaseLayout::Calendar,
     }
@@ -120,6 +169,15 @@ pub struct DatabaseLayoutSettingPB {
 
   #[pb(index = 3, one_of)]
   pub calendar: Option<CalendarLayoutSettingPB>,
+
+  #[pb(index = 4, one_of)]
+  pub gallery: Option<CardLayoutSettingPB>,
+
+  #[pb(index = 5, one_of)]
+  pub list: Option<CardLayoutSettingPB>,
+
+  #[pb(index = 6, one_of)]
+  pub timeline: Option<TimelineLayoutSettingPB>,
 }
 
 impl DatabaseLayoutSettingPB {
@@ -127,7 +185,7 @@ impl DatabaseLayoutSettingPB {
     Self {
       layout_type: DatabaseLayoutPB::Board,
       board: Some(layout_setting.into()),
-      calendar: None,
+      ..Default::default()
     }
   }
 
@@ -135,7 +193,19 @@ impl DatabaseLayoutSettingPB {
     Self {
       layout_type: DatabaseLayoutPB::Calendar,
       calendar: Some(layout_setting.into()),
-      board: None,
+      ..Default::default()
+    }
+  }
+
+  /// The gallery, list and timeline settings are all part of the [GridLayoutSetting], the layout
+  /// type is the one that the grid view currently uses.
+  pub fn from_grid(layout_setting: GridLayoutSetting) -> Self {
+    Self {
+      layout_type: layout_setting.layout_ty.into(),
+      gallery: Some(layout_setting.gallery.into()),
+      list: Some(layout_setting.list.into()),
+      timeline: Some(layout_setting.timeline.into()),
+      ..Default::default()
     }
   }
 }
@@ -145,6 +215,7 @@ pub struct LayoutSettingParams {
   pub layout_type: DatabaseLayout,
   pub board: Option<BoardLayoutSetting>,
   pub calendar: Option<CalendarLayoutSetting>,
+  pub grid: Option<GridLayoutSetting>,
 }
 
 impl LayoutSettingParams {
@@ -158,11 +229,16 @@ impl LayoutSettingParams {
 
 impl From<LayoutSettingParams> for DatabaseLayoutSettingPB {
   fn from(data: LayoutSettingParams) -> Self {
-    Self {
-      layout_type: data.layout_type.into(),
-      board: data.board.map(|board| board.into()),
-      calendar: data.calendar.map(|calendar| calendar.into()),
-    }
+    let mut layout_setting = match data.grid {
+      Some(grid) => DatabaseLayoutSettingPB::from_grid(grid),
+      None => DatabaseLayoutSettingPB {
+        layout_type: data.layout_type.into(),
+        ..Default::default()
+      },
+    };
+    layout_setting.board = data.board.map(|board| board.into());
+    layout_setting.calendar = data.calendar.map(|calendar| calendar.into());
+    layout_setting
   }
 }
 
@@ -179,6 +255,15 @@ pub struct LayoutSettingChangesetPB {
 
   #[pb(index = 4, one_of)]
   pub calendar: Option<CalendarLayoutSettingPB>,
+
+  #[pb(index = 5, one_of)]
+  pub gallery: Option<CardLayoutSettingPB>,
+
+  #[pb(index = 6, one_of)]
+  pub list: Option<CardLayoutSettingPB>,
+
+  #[pb(index = 7, one_of)]
+  pub timeline: Option<TimelineLayoutSettingPB>,
 }
 
 #[derive(Debug)]
@@ -187,12 +272,17 @@ pub struct LayoutSettingChangeset {
   pub layout_type: DatabaseLayout,
   pub board: Option<BoardLayoutSetting>,
   pub calendar: Option<CalendarLayoutSetting>,
+  pub gallery: Option<CardLayoutSetting>,
+  pub list: Option<CardLayoutSetting>,
+  pub timeline: Option<TimelineLayoutSetting>,
 }
 
 impl LayoutSettingChangeset {
   pub fn is_valid(&self) -> bool {
     self.board.is_some() && self.layout_type == DatabaseLayout::Board
       || self.calendar.is_some() && self.layout_type == DatabaseLayout::Calendar
+      || (self.gallery.is_some() || self.list.is_some() || self.timeline.is_some())
+        && self.layout_type == DatabaseLayout::Grid
   }
 }
 
@@ -209,6 +299,9 @@ impl TryInto<LayoutSettingChangeset> for LayoutSettingChangesetPB {
       layout_type: self.layout_type.into(),
       board: self.board.map(Into::into),
       calendar: self.calendar.map(Into::into),
+      gallery: self.gallery.map(Into::into),
+      list: self.list.map(Into::into),
+      timeline: self.timeline.map(Into::into),
     })
   }
 }
@@ -1,9 +1,12 @@
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use validator::Validate;
 
 #[derive(Debug, ProtoBuf_Enum, Clone, Default)]
 pub enum DatabaseExportDataType {
   #[default]
   CSV = 0,
+  /// The json of a [crate::services::share::template::DatabaseTemplate].
+  Template = 1,
 }
 
 #[derive(Debug, ProtoBuf, Default, Clone)]
@@ -14,3 +17,14 @@ pub struct DatabaseExportDataPB {
   #[pb(index = 2)]
   pub data: String,
 }
+
+#[derive(Debug, ProtoBuf, Default, Clone, Validate)]
+pub struct DatabaseTemplateExportPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  /// The rows are exported as sample data of the template.
+  #[pb(index = 2)]
+  pub include_rows: bool,
+}
@@ -0,0 +1,105 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::FieldType;
+
+/// Compares two snapshots of the database, or a snapshot with the current state of the database
+/// if `to_snapshot_id` is None.
+#[derive(Debug, Default, ProtoBuf)]
+pub struct DatabaseSnapshotDiffPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub from_snapshot_id: i64,
+
+  #[pb(index = 3, one_of)]
+  pub to_snapshot_id: Option<i64>,
+}
+
+pub struct DatabaseSnapshotDiffParams {
+  pub view_id: String,
+  pub from_snapshot_id: i64,
+  pub to_snapshot_id: Option<i64>,
+}
+
+impl TryInto<DatabaseSnapshotDiffParams> for DatabaseSnapshotDiffPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DatabaseSnapshotDiffParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    Ok(DatabaseSnapshotDiffParams {
+      view_id: view_id.0,
+      from_snapshot_id: self.from_snapshot_id,
+      to_snapshot_id: self.to_snapshot_id,
+    })
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct DatabaseDiffPB {
+  #[pb(index = 1)]
+  pub fields: Vec<FieldDiffPB>,
+
+  #[pb(index = 2)]
+  pub rows: Vec<RowDiffPB>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum DiffChangeTypePB {
+  #[default]
+  Added = 0,
+  Removed = 1,
+  Updated = 2,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FieldDiffPB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  #[pb(index = 2)]
+  pub change_type: DiffChangeTypePB,
+
+  /// Empty if the field is added.
+  #[pb(index = 3)]
+  pub old_name: String,
+
+  /// Empty if the field is removed.
+  #[pb(index = 4)]
+  pub new_name: String,
+
+  #[pb(index = 5)]
+  pub old_field_type: FieldType,
+
+  #[pb(index = 6)]
+  pub new_field_type: FieldType,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RowDiffPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub change_type: DiffChangeTypePB,
+
+  /// The cells of the added or the removed row, or the changed cells of the updated row.
+  #[pb(index = 3)]
+  pub cells: Vec<CellDiffPB>,
+}
+
+/// The values of the cell are displayed as text.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct CellDiffPB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  #[pb(index = 2)]
+  pub old_value: String,
+
+  #[pb(index = 3)]
+  pub new_value: String,
+}
@@ -0,0 +1,169 @@
+use collab_database::rows::RowId;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::entities::parser::NotEmptyStr;
+use crate::entities::RowMetaPB;
+use crate::services::setting::{TimelineLayoutSetting, TimelineZoom};
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf)]
+pub struct TimelineLayoutSettingPB {
+  #[pb(index = 1)]
+  pub start_field_id: String,
+
+  /// The end date of the start field is used if it's empty.
+  #[pb(index = 2)]
+  pub end_field_id: String,
+
+  #[pb(index = 3)]
+  pub zoom: TimelineZoomPB,
+
+  #[pb(index = 4)]
+  pub dependency_field_id: String,
+}
+
+impl std::convert::From<TimelineLayoutSettingPB> for TimelineLayoutSetting {
+  fn from(pb: TimelineLayoutSettingPB) -> Self {
+    TimelineLayoutSetting {
+      start_field_id: pb.start_field_id,
+      end_field_id: pb.end_field_id,
+      zoom: pb.zoom.into(),
+      dependency_field_id: pb.dependency_field_id,
+    }
+  }
+}
+
+impl std::convert::From<TimelineLayoutSetting> for TimelineLayoutSettingPB {
+  fn from(params: TimelineLayoutSetting) -> Self {
+    TimelineLayoutSettingPB {
+      start_field_id: params.start_field_id,
+      end_field_id: params.end_field_id,
+      zoom: params.zoom.into(),
+      dependency_field_id: params.dependency_field_id,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Default, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum TimelineZoomPB {
+  Day = 0,
+  #[default]
+  Week = 1,
+  Month = 2,
+  Quarter = 3,
+}
+
+impl std::convert::From<TimelineZoomPB> for TimelineZoom {
+  fn from(pb: TimelineZoomPB) -> Self {
+    match pb {
+      TimelineZoomPB::Day => TimelineZoom::Day,
+      TimelineZoomPB::Week => TimelineZoom::Week,
+      TimelineZoomPB::Month => TimelineZoom::Month,
+      TimelineZoomPB::Quarter => TimelineZoom::Quarter,
+    }
+  }
+}
+
+impl std::convert::From<TimelineZoom> for TimelineZoomPB {
+  fn from(zoom: TimelineZoom) -> Self {
+    match zoom {
+      TimelineZoom::Day => TimelineZoomPB::Day,
+      TimelineZoom::Week => TimelineZoomPB::Week,
+      TimelineZoom::Month => TimelineZoomPB::Month,
+      TimelineZoom::Quarter => TimelineZoomPB::Quarter,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct TimelineEventRequestPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TimelineEventRequestParams {
+  pub view_id: String,
+}
+
+impl TryInto<TimelineEventRequestParams> for TimelineEventRequestPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<TimelineEventRequestParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    Ok(TimelineEventRequestParams { view_id: view_id.0 })
+  }
+}
+
+/// A bar of the timeline.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct TimelineEventPB {
+  #[pb(index = 1)]
+  pub row_meta: RowMetaPB,
+
+  #[pb(index = 2)]
+  pub title: String,
+
+  #[pb(index = 3)]
+  pub start_timestamp: i64,
+
+  /// Equals to the start timestamp if the row has no end date.
+  #[pb(index = 4)]
+  pub end_timestamp: i64,
+
+  #[pb(index = 5)]
+  pub is_scheduled: bool,
+
+  /// The ids of the rows this row depends on.
+  #[pb(index = 6)]
+  pub dependencies: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedTimelineEventPB {
+  #[pb(index = 1)]
+  pub items: Vec<TimelineEventPB>,
+}
+
+/// Moves or resizes a bar of the timeline.
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct MoveTimelineEventPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub start_timestamp: i64,
+
+  #[pb(index = 4)]
+  pub end_timestamp: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveTimelineEventParams {
+  pub view_id: String,
+  pub row_id: RowId,
+  pub start_timestamp: i64,
+  pub end_timestamp: i64,
+}
+
+impl TryInto<MoveTimelineEventParams> for MoveTimelineEventPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<MoveTimelineEventParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
+    let row_id = NotEmptyStr::parse(self.row_id).map_err(|_| ErrorCode::RowIdIsEmpty)?;
+    if self.end_timestamp < self.start_timestamp {
+      return Err(ErrorCode::InvalidParams);
+    }
+    Ok(MoveTimelineEventParams {
+      view_id: view_id.0,
+      row_id: RowId::from(row_id.0),
+      start_timestamp: self.start_timestamp,
+      end_timestamp: self.end_timestamp,
+    })
+  }
+}
@@ -35,6 +35,11 @@ pub struct DateCellDataPB {
 
   #[pb(index = 9)]
   pub reminder_id: String,
+
+  /// The recurrence rule in the RRULE style, e.g. `FREQ=WEEKLY;BYDAY=MO,FR`. Empty if the date
+  /// doesn't repeat.
+  #[pb(index = 10)]
+  pub recurrence: String,
 }
 
 #[derive(Clone, Debug, Default, ProtoBuf)]
@@ -65,6 +70,21 @@ pub struct DateCellChangesetPB {
 
   #[pb(index = 9, one_of)]
   pub reminder_id: Option<String>,
+
+  /// An empty rule removes the recurrence.
+  #[pb(index = 10, one_of)]
+  pub recurrence: Option<String>,
+}
+
+/// Completes the current occurrence of a recurring date. `checkbox_field_id` is the field that
+/// marks a task as done, if any.
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct CompleteDateOccurrencePB {
+  #[pb(index = 1)]
+  pub cell_id: CellIdPB,
+
+  #[pb(index = 2, one_of)]
+  pub checkbox_field_id: Option<String>,
 }
 
 // Date
@@ -0,0 +1,34 @@
+use crate::services::field::FormulaTypeOption;
+use flowy_derive::ProtoBuf;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FormulaTypeOptionPB {
+  #[pb(index = 1)]
+  pub expression: String,
+}
+
+impl From<FormulaTypeOption> for FormulaTypeOptionPB {
+  fn from(value: FormulaTypeOption) -> Self {
+    FormulaTypeOptionPB {
+      expression: value.expression,
+    }
+  }
+}
+
+impl From<FormulaTypeOptionPB> for FormulaTypeOption {
+  fn from(value: FormulaTypeOptionPB) -> Self {
+    FormulaTypeOption {
+      expression: value.expression,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct FormulaCellDataPB {
+  #[pb(index = 1)]
+  pub value: String,
+
+  /// Set if the formula can't be evaluated, e.g. syntax errors or circular references.
+  #[pb(index = 2, one_of)]
+  pub error: Option<String>,
+}
@@ -1,8 +1,10 @@
 mod checkbox_entities;
 mod checklist_entities;
 mod date_entities;
+mod formula_entities;
 mod number_entities;
 mod relation_entities;
+mod rollup_entities;
 mod select_option_entities;
 mod summary_entities;
 mod text_entities;
@@ -14,8 +16,10 @@ mod url_entities;
 pub use checkbox_entities::*;
 pub use checklist_entities::*;
 pub use date_entities::*;
+pub use formula_entities::*;
 pub use number_entities::*;
 pub use relation_entities::*;
+pub use rollup_entities::*;
 pub use select_option_entities::*;
 pub use summary_entities::*;
 pub use text_entities::*;
@@ -44,12 +44,24 @@ pub struct RelationCellChangesetPB {
 pub struct RelationTypeOptionPB {
   #[pb(index = 1)]
   pub database_id: String,
+
+  /// The id of the mirrored relation field in the related database. It's set by the backend and
+  /// ignored when the type option is updated.
+  #[pb(index = 2)]
+  pub paired_field_id: String,
+
+  /// Creates a relation field in the related database that links the related rows back to the
+  /// rows of this database, and keeps the two fields in sync.
+  #[pb(index = 3)]
+  pub show_on_related_database: bool,
 }
 
 impl From<RelationTypeOption> for RelationTypeOptionPB {
   fn from(value: RelationTypeOption) -> Self {
     RelationTypeOptionPB {
+      show_on_related_database: value.is_two_way(),
       database_id: value.database_id,
+      paired_field_id: value.paired_field_id,
     }
   }
 }
@@ -58,6 +70,7 @@ impl From<RelationTypeOptionPB> for RelationTypeOption {
   fn from(value: RelationTypeOptionPB) -> Self {
     RelationTypeOption {
       database_id: value.database_id,
+      paired_field_id: "".to_string(),
     }
   }
 }
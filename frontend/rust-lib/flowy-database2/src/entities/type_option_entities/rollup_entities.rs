@@ -0,0 +1,50 @@
+use crate::entities::CalculationType;
+use crate::services::field::RollupTypeOption;
+use flowy_derive::ProtoBuf;
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RollupTypeOptionPB {
+  /// The relation field whose linked rows are rolled up.
+  #[pb(index = 1)]
+  pub relation_field_id: String,
+
+  /// The field of the related database whose values are aggregated.
+  #[pb(index = 2)]
+  pub target_field_id: String,
+
+  #[pb(index = 3)]
+  pub calculation_type: CalculationType,
+
+  /// List the values of the linked rows instead of aggregating them. The calculation type is
+  /// ignored if it's true.
+  #[pb(index = 4)]
+  pub show_original: bool,
+}
+
+impl From<RollupTypeOption> for RollupTypeOptionPB {
+  fn from(value: RollupTypeOption) -> Self {
+    RollupTypeOptionPB {
+      relation_field_id: value.relation_field_id,
+      target_field_id: value.target_field_id,
+      calculation_type: value.calculation_type,
+      show_original: value.show_original,
+    }
+  }
+}
+
+impl From<RollupTypeOptionPB> for RollupTypeOption {
+  fn from(value: RollupTypeOptionPB) -> Self {
+    RollupTypeOption {
+      relation_field_id: value.relation_field_id,
+      target_field_id: value.target_field_id,
+      calculation_type: value.calculation_type,
+      show_original: value.show_original,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RollupCellDataPB {
+  #[pb(index = 1)]
+  pub value: String,
+}
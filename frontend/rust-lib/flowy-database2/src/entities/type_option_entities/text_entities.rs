@@ -5,16 +5,26 @@ use flowy_derive::ProtoBuf;
 pub struct RichTextTypeOptionPB {
   #[pb(index = 1)]
   data: String,
+
+  /// If true, the cell data of the field is a delta instead of a plain text.
+  #[pb(index = 2)]
+  pub is_rich_text: bool,
 }
 
 impl From<RichTextTypeOption> for RichTextTypeOptionPB {
   fn from(data: RichTextTypeOption) -> Self {
-    Self { data: data.inner }
+    Self {
+      data: data.inner,
+      is_rich_text: data.is_rich_text,
+    }
   }
 }
 
 impl From<RichTextTypeOptionPB> for RichTextTypeOption {
   fn from(data: RichTextTypeOptionPB) -> Self {
-    Self { inner: data.data }
+    Self {
+      inner: data.data,
+      is_rich_text: data.is_rich_text,
+    }
   }
 }
@@ -0,0 +1,112 @@
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+
+use crate::services::validation::{CellViolation, FieldValidation, ValidationRule};
+
+/// The validation constraints of a field. The constraints that don't apply to the type of the
+/// field are ignored:
+/// - `pattern`: text and URL fields.
+/// - `min` and `max`: number fields, and date fields where they are timestamps in seconds.
+/// - `max_selected_options`: multi-select fields.
+#[derive(Debug, Default, Clone, ProtoBuf, PartialEq)]
+pub struct FieldValidationPB {
+  #[pb(index = 1)]
+  pub is_required: bool,
+
+  #[pb(index = 2)]
+  pub is_unique: bool,
+
+  #[pb(index = 3, one_of)]
+  pub pattern: Option<String>,
+
+  #[pb(index = 4, one_of)]
+  pub min: Option<f64>,
+
+  #[pb(index = 5, one_of)]
+  pub max: Option<f64>,
+
+  #[pb(index = 6, one_of)]
+  pub max_selected_options: Option<u32>,
+}
+
+impl From<FieldValidation> for FieldValidationPB {
+  fn from(validation: FieldValidation) -> Self {
+    Self {
+      is_required: validation.is_required,
+      is_unique: validation.is_unique,
+      pattern: validation.pattern,
+      min: validation.min,
+      max: validation.max,
+      max_selected_options: validation.max_selected_options,
+    }
+  }
+}
+
+impl From<FieldValidationPB> for FieldValidation {
+  fn from(validation: FieldValidationPB) -> Self {
+    Self {
+      is_required: validation.is_required,
+      is_unique: validation.is_unique,
+      pattern: validation.pattern.filter(|pattern| !pattern.is_empty()),
+      min: validation.min.filter(|min| !min.is_nan()),
+      max: validation.max.filter(|max| !max.is_nan()),
+      max_selected_options: validation.max_selected_options,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum ValidationRulePB {
+  #[default]
+  Required = 0,
+  Unique = 1,
+  Pattern = 2,
+  Min = 3,
+  Max = 4,
+  MaxSelectedOptions = 5,
+}
+
+impl From<ValidationRule> for ValidationRulePB {
+  fn from(rule: ValidationRule) -> Self {
+    match rule {
+      ValidationRule::Required => Self::Required,
+      ValidationRule::Unique => Self::Unique,
+      ValidationRule::Pattern => Self::Pattern,
+      ValidationRule::Min => Self::Min,
+      ValidationRule::Max => Self::Max,
+      ValidationRule::MaxSelectedOptions => Self::MaxSelectedOptions,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct CellViolationPB {
+  #[pb(index = 1)]
+  pub row_id: String,
+
+  #[pb(index = 2)]
+  pub field_id: String,
+
+  #[pb(index = 3)]
+  pub rule: ValidationRulePB,
+
+  #[pb(index = 4)]
+  pub message: String,
+}
+
+impl From<CellViolation> for CellViolationPB {
+  fn from(violation: CellViolation) -> Self {
+    Self {
+      row_id: violation.row_id.into_inner(),
+      field_id: violation.field_id,
+      rule: violation.rule.into(),
+      message: violation.message,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, ProtoBuf)]
+pub struct RepeatedCellViolationPB {
+  #[pb(index = 1)]
+  pub items: Vec<CellViolationPB>,
+}
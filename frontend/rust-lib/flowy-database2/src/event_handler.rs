@@ -1,5 +1,6 @@
 use std::sync::{Arc, Weak};
 
+use bytes::Bytes;
 use collab_database::rows::RowId;
 use lib_infra::box_any::BoxAny;
 use tokio::sync::oneshot;
@@ -10,6 +11,7 @@ use lib_dispatch::prelude::{af_spawn, data_result_ok, AFPluginData, AFPluginStat
 
 use crate::entities::*;
 use crate::manager::DatabaseManager;
+use crate::services::conditional_format::ConditionalFormat;
 use crate::services::field::{
   type_option_data_from_pb, ChecklistCellChangeset, DateCellChangeset, RelationCellChangeset,
   SelectOptionCellChangeset,
@@ -125,8 +127,8 @@ pub(crate) async fn update_database_setting_handler(
   }
 
   if let Some(layout_type) = params.layout_type {
-    database_editor
-      .update_view_layout(&params.view_id, layout_type.into())
+    manager
+      .update_database_layout(&params.view_id, layout_type)
       .await?;
   }
   Ok(())
@@ -236,10 +238,32 @@ pub(crate) async fn update_field_type_option_handler(
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   if let Some(old_field) = database_editor.get_field(&params.field_id) {
     let field_type = FieldType::from(old_field.field_type);
+    if field_type.is_relation() {
+      let type_option = RelationTypeOptionPB::try_from(Bytes::from(params.type_option_data))?;
+      return manager
+        .update_relation_type_option(&database_editor, old_field, type_option)
+        .await;
+    }
+
     let type_option_data = type_option_data_from_pb(params.type_option_data, &field_type)?;
+    let was_auto_fill = database_editor.is_auto_fill_ai_field(&params.field_id);
     database_editor
       .update_field_type_option(&params.field_id, type_option_data, old_field)
       .await?;
+
+    if !was_auto_fill && database_editor.is_auto_fill_ai_field(&params.field_id) {
+      spawn_ai_fill(
+        manager.clone(),
+        params.view_id.clone(),
+        params.field_id.clone(),
+      );
+    }
+
+    if field_type.is_rollup() {
+      manager
+        .update_rollup_cells_of_field(&database_editor, &params.field_id)
+        .await?;
+    }
   }
   Ok(())
 }
@@ -252,6 +276,9 @@ pub(crate) async fn delete_field_handler(
   let manager = upgrade_manager(manager)?;
   let params: FieldIdParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  manager
+    .unpair_relation_field(&database_editor, &params.field_id)
+    .await?;
   database_editor.delete_field(&params.field_id).await?;
   Ok(())
 }
@@ -267,6 +294,9 @@ pub(crate) async fn clear_field_handler(
   database_editor
     .clear_field(&params.view_id, &params.field_id)
     .await?;
+  manager
+    .did_clear_field(&database_editor, &params.field_id)
+    .await?;
   Ok(())
 }
 
@@ -279,6 +309,11 @@ pub(crate) async fn switch_to_field_handler(
   let params: EditFieldParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   let old_field = database_editor.get_field(&params.field_id);
+  if !params.field_type.is_relation() {
+    manager
+      .unpair_relation_field(&database_editor, &params.field_id)
+      .await?;
+  }
   database_editor
     .switch_to_field_type(&params.field_id, params.field_type)
     .await?;
@@ -298,6 +333,12 @@ pub(crate) async fn switch_to_field_handler(
       },
     }
   }
+
+  if params.field_type.is_rollup() {
+    manager
+      .update_rollup_cells_of_field(&database_editor, &params.field_id)
+      .await?;
+  }
   Ok(())
 }
 
@@ -323,11 +364,36 @@ pub(crate) async fn create_field_handler(
 ) -> DataResult<FieldPB, FlowyError> {
   let manager = upgrade_manager(manager)?;
   let params: CreateFieldParams = data.into_inner().try_into()?;
+  let field_type = params.field_type;
+  let view_id = params.view_id.clone();
+  let show_on_related_database = field_type.is_relation()
+    && params
+      .type_option_data
+      .clone()
+      .and_then(|data| RelationTypeOptionPB::try_from(Bytes::from(data)).ok())
+      .map(|type_option| type_option.show_on_related_database)
+      .unwrap_or(false);
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   let data = database_editor
     .create_field_with_type_option(params)
     .await?;
 
+  if show_on_related_database {
+    manager
+      .create_paired_relation_field(&database_editor, &data.id)
+      .await?;
+  }
+
+  if database_editor.is_auto_fill_ai_field(&data.id) {
+    spawn_ai_fill(manager.clone(), view_id, data.id.clone());
+  }
+
+  if field_type.is_rollup() {
+    manager
+      .update_rollup_cells_of_field(&database_editor, &data.id)
+      .await?;
+  }
+
   data_result_ok(data)
 }
 
@@ -364,7 +430,10 @@ pub(crate) async fn get_row_meta_handler(
   let manager = upgrade_manager(manager)?;
   let params: RowIdParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
-  match database_editor.get_row_meta(&params.view_id, &params.row_id) {
+  match database_editor
+    .get_row_meta(&params.view_id, &params.row_id)
+    .await
+  {
     None => Err(FlowyError::record_not_found()),
     Some(row) => data_result_ok(row),
   }
@@ -380,7 +449,7 @@ pub(crate) async fn update_row_meta_handler(
   let row_id = RowId::from(params.id.clone());
   database_editor
     .update_row_meta(&row_id.clone(), params)
-    .await;
+    .await?;
   Ok(())
 }
 
@@ -398,6 +467,7 @@ pub(crate) async fn delete_rows_handler(
     .map(RowId::from)
     .collect::<Vec<_>>();
   database_editor.delete_rows(&row_ids).await;
+  manager.did_delete_rows(&database_editor, &row_ids).await?;
   Ok(())
 }
 
@@ -439,7 +509,12 @@ pub(crate) async fn create_row_handler(
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
 
   match database_editor.create_row(params).await? {
-    Some(row) => data_result_ok(RowMetaPB::from(row)),
+    Some(row) => {
+      manager
+        .did_create_row(&database_editor, &row.row.id)
+        .await?;
+      data_result_ok(RowMetaPB::from(row))
+    },
     None => Err(FlowyError::internal().with_context("Error creating row")),
   }
 }
@@ -607,6 +682,7 @@ pub(crate) async fn update_date_cell_handler(
     is_range: data.is_range,
     clear_flag: data.clear_flag,
     reminder_id: data.reminder_id,
+    recurrence: data.recurrence,
   };
 
   let database_editor = manager.get_database_with_view_id(&cell_id.view_id).await?;
@@ -621,6 +697,27 @@ pub(crate) async fn update_date_cell_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn complete_date_occurrence_handler(
+  data: AFPluginData<CompleteDateOccurrencePB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<OptionalRowPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.into_inner();
+  let cell_id: CellIdParams = data.cell_id.try_into()?;
+  let database_editor = manager.get_database_with_view_id(&cell_id.view_id).await?;
+  let row = database_editor
+    .complete_date_occurrence(
+      &cell_id.view_id,
+      &cell_id.row_id,
+      &cell_id.field_id,
+      data.checkbox_field_id,
+    )
+    .await?
+    .map(|row_detail| RowPB::from(&row_detail.row));
+  data_result_ok(OptionalRowPB { row })
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn get_groups_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -821,7 +918,7 @@ pub(crate) async fn get_calendar_events_handler(
   let params: CalendarEventRequestParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   let events = database_editor
-    .get_all_calendar_events(&params.view_id)
+    .get_all_calendar_events(&params.view_id, params.range)
     .await;
   data_result_ok(RepeatedCalendarEventPB { items: events })
 }
@@ -881,6 +978,32 @@ pub(crate) async fn move_calendar_event_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn get_timeline_events_handler(
+  data: AFPluginData<TimelineEventRequestPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedTimelineEventPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: TimelineEventRequestParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let events = database_editor
+    .get_all_timeline_events(&params.view_id)
+    .await;
+  data_result_ok(RepeatedTimelineEventPB { items: events })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn move_timeline_event_handler(
+  data: AFPluginData<MoveTimelineEventPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params: MoveTimelineEventParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  database_editor.move_timeline_event(params).await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn create_database_view(
   _data: AFPluginData<CreateDatabaseViewPayloadPB>,
@@ -905,6 +1028,22 @@ pub(crate) async fn export_csv_handler(
   })
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_database_template_handler(
+  data: AFPluginData<DatabaseTemplateExportPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let data = manager
+    .export_database_template(&params.view_id, params.include_rows)
+    .await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::Template,
+    data,
+  })
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_snapshots_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -916,6 +1055,49 @@ pub(crate) async fn get_snapshots_handler(
   data_result_ok(RepeatedDatabaseSnapshotPB { items: snapshots })
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn create_snapshot_handler(
+  data: AFPluginData<CreateDatabaseSnapshotPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseSnapshotPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: CreateDatabaseSnapshotParams = data.into_inner().try_into()?;
+  let snapshot = manager
+    .create_database_snapshot(&params.view_id, &params.title, &params.desc)
+    .await?;
+  data_result_ok(snapshot)
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn restore_snapshot_handler(
+  data: AFPluginData<RestoreDatabaseSnapshotPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  manager
+    .restore_database_snapshot(&params.view_id, params.snapshot_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_snapshot_diff_handler(
+  data: AFPluginData<DatabaseSnapshotDiffPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseDiffPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: DatabaseSnapshotDiffParams = data.into_inner().try_into()?;
+  let diff = manager
+    .get_database_snapshot_diff(
+      &params.view_id,
+      params.from_snapshot_id,
+      params.to_snapshot_id,
+    )
+    .await?;
+  data_result_ok(diff)
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_field_settings_handler(
   data: AFPluginData<FieldIdsPB>,
@@ -972,6 +1154,157 @@ pub(crate) async fn update_field_settings_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_conditional_formats_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedConditionalFormatPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager.get_database_with_view_id(view_id.as_ref()).await?;
+  let formats = database_editor
+    .get_conditional_formats(view_id.as_ref())
+    .await?;
+  data_result_ok(RepeatedConditionalFormatPB {
+    items: formats.iter().map(ConditionalFormatPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn create_conditional_format_handler(
+  data: AFPluginData<CreateConditionalFormatPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<ConditionalFormatPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let view_id = data.view_id.clone();
+  let format = ConditionalFormat::try_from(data)?;
+  let database_editor = manager.get_database_with_view_id(&view_id).await?;
+  database_editor
+    .create_conditional_format(&view_id, format.clone())
+    .await?;
+  data_result_ok(ConditionalFormatPB::from(&format))
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn update_conditional_format_handler(
+  data: AFPluginData<UpdateConditionalFormatPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let format = ConditionalFormat::try_from(data.format)?;
+  let database_editor = manager.get_database_with_view_id(&data.view_id).await?;
+  database_editor
+    .update_conditional_format(&data.view_id, format)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn delete_conditional_format_handler(
+  data: AFPluginData<DeleteConditionalFormatPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&data.view_id).await?;
+  database_editor
+    .delete_conditional_format(&data.view_id, &data.format_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn validate_database_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedCellViolationPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager.get_database_with_view_id(view_id.as_ref()).await?;
+  let violations = database_editor.validate_database(view_id.as_ref()).await?;
+  data_result_ok(RepeatedCellViolationPB {
+    items: violations.into_iter().map(CellViolationPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_row_comments_handler(
+  data: AFPluginData<RowIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedRowCommentPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: RowIdParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let comments = database_editor.get_row_comments(&params.row_id);
+  data_result_ok(RepeatedRowCommentPB::from(&comments))
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn add_row_comment_handler(
+  data: AFPluginData<AddRowCommentPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RowCommentPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&data.view_id).await?;
+  let comment = database_editor
+    .add_row_comment(
+      &RowId::from(data.row_id),
+      &data.content,
+      data.parent_comment_id,
+    )
+    .await?;
+  data_result_ok(RowCommentPB::from(&comment))
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn update_row_comment_handler(
+  data: AFPluginData<UpdateRowCommentPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&data.view_id).await?;
+  database_editor
+    .update_row_comment(&RowId::from(data.row_id), &data.comment_id, &data.content)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn delete_row_comment_handler(
+  data: AFPluginData<RowCommentIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&data.view_id).await?;
+  database_editor
+    .delete_row_comment(&RowId::from(data.row_id), &data.comment_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn resolve_row_comment_handler(
+  data: AFPluginData<ResolveRowCommentPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&data.view_id).await?;
+  database_editor
+    .resolve_row_comment(
+      &RowId::from(data.row_id),
+      &data.comment_id,
+      data.is_resolved,
+    )
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_all_calculations_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -1060,9 +1393,14 @@ pub(crate) async fn update_relation_cell_handler(
       &view_id,
       &cell_id.row_id,
       &cell_id.field_id,
-      BoxAny::new(params),
+      BoxAny::new(params.clone()),
     )
     .await?;
+
+  // link the related rows back to the row if the relation is two-way
+  manager
+    .did_update_relation_cell(&database_editor, &cell_id.field_id, &cell_id.row_id, params)
+    .await?;
   Ok(())
 }
 
@@ -1117,3 +1455,103 @@ pub(crate) async fn translate_row_handler(
     .await?;
   Ok(())
 }
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn fill_ai_field_handler(
+  data: AFPluginData<FillAIFieldPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<AIFillResultPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let result = manager
+    .fill_ai_field(&data.view_id, &data.field_id, data.overwrite)
+    .await?;
+  data_result_ok(result)
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn cancel_fill_ai_field_handler(
+  data: AFPluginData<CancelFillAIFieldPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  manager.cancel_fill_ai_field(&data.field_id);
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_automations_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedAutomationPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let automations = manager.get_automations(view_id.as_ref()).await?;
+  data_result_ok(RepeatedAutomationPB {
+    items: automations.iter().map(AutomationPB::from).collect(),
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn create_automation_handler(
+  data: AFPluginData<CreateAutomationPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<AutomationPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let view_id = data.view_id.clone();
+  let params = AutomationParams::try_from(data)?;
+  let automation = manager.create_automation(&view_id, params).await?;
+  data_result_ok(AutomationPB::from(&automation))
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn update_automation_handler(
+  data: AFPluginData<UpdateAutomationPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  let automation_id = data.automation.id.clone();
+  let params = AutomationParams::try_from(data.automation)?;
+  manager
+    .update_automation(&data.view_id, &automation_id, params)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn delete_automation_handler(
+  data: AFPluginData<DeleteAutomationPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> Result<(), FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let data = data.try_into_inner()?;
+  manager
+    .delete_automation(&data.view_id, &data.automation_id)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn get_automation_logs_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedAutomationLogPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id: DatabaseViewIdPB = data.into_inner();
+  let logs = manager.get_automation_logs(view_id.as_ref()).await?;
+  data_result_ok(RepeatedAutomationLogPB {
+    items: logs.into_iter().map(AutomationLogPB::from).collect(),
+  })
+}
+
+/// Fills the empty cells of the auto fill AI field in the background.
+fn spawn_ai_fill(manager: Arc<DatabaseManager>, view_id: String, field_id: String) {
+  af_spawn(async move {
+    if let Err(err) = manager.fill_ai_field(&view_id, &field_id, false).await {
+      error!("[AI]:auto fill field {} failed: {}", field_id, err);
+    }
+  });
+}
@@ -52,6 +52,7 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::UpdateChecklistCell, update_checklist_cell_handler)
         // Date
         .event(DatabaseEvent::UpdateDateCell, update_date_cell_handler)
+        .event(DatabaseEvent::CompleteDateOccurrence, complete_date_occurrence_handler)
         // Group
         .event(DatabaseEvent::SetGroupByField, set_group_by_field_handler)
         .event(DatabaseEvent::MoveGroup, move_group_handler)
@@ -69,17 +70,37 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::GetNoDateCalendarEvents, get_no_date_calendar_events_handler)
         .event(DatabaseEvent::GetCalendarEvent, get_calendar_event_handler)
         .event(DatabaseEvent::MoveCalendarEvent, move_calendar_event_handler)
+        // Timeline
+        .event(DatabaseEvent::GetAllTimelineEvents, get_timeline_events_handler)
+        .event(DatabaseEvent::MoveTimelineEvent, move_timeline_event_handler)
         // Layout setting
         .event(DatabaseEvent::SetLayoutSetting, set_layout_setting_handler)
         .event(DatabaseEvent::GetLayoutSetting, get_layout_setting_handler)
         .event(DatabaseEvent::CreateDatabaseView, create_database_view)
         // Export
         .event(DatabaseEvent::ExportCSV, export_csv_handler)
+        .event(DatabaseEvent::ExportDatabaseTemplate, export_database_template_handler)
         .event(DatabaseEvent::GetDatabaseSnapshots, get_snapshots_handler)
+        .event(DatabaseEvent::CreateDatabaseSnapshot, create_snapshot_handler)
+        .event(DatabaseEvent::RestoreDatabaseSnapshot, restore_snapshot_handler)
+        .event(DatabaseEvent::GetDatabaseSnapshotDiff, get_snapshot_diff_handler)
         // Field settings
         .event(DatabaseEvent::GetFieldSettings, get_field_settings_handler)
         .event(DatabaseEvent::GetAllFieldSettings, get_all_field_settings_handler)
         .event(DatabaseEvent::UpdateFieldSettings, update_field_settings_handler)
+        // Conditional formatting
+        .event(DatabaseEvent::GetConditionalFormats, get_conditional_formats_handler)
+        .event(DatabaseEvent::CreateConditionalFormat, create_conditional_format_handler)
+        .event(DatabaseEvent::UpdateConditionalFormat, update_conditional_format_handler)
+        .event(DatabaseEvent::DeleteConditionalFormat, delete_conditional_format_handler)
+        // Validation
+        .event(DatabaseEvent::ValidateDatabase, validate_database_handler)
+        // Row comments
+        .event(DatabaseEvent::GetRowComments, get_row_comments_handler)
+        .event(DatabaseEvent::AddRowComment, add_row_comment_handler)
+        .event(DatabaseEvent::UpdateRowComment, update_row_comment_handler)
+        .event(DatabaseEvent::DeleteRowComment, delete_row_comment_handler)
+        .event(DatabaseEvent::ResolveRowComment, resolve_row_comment_handler)
         // Calculations
         .event(DatabaseEvent::GetAllCalculations, get_all_calculations_handler)
         .event(DatabaseEvent::UpdateCalculation, update_calculation_handler)
@@ -92,6 +113,14 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         // AI
         .event(DatabaseEvent::SummarizeRow, summarize_row_handler)
         .event(DatabaseEvent::TranslateRow, translate_row_handler)
+        .event(DatabaseEvent::FillAIField, fill_ai_field_handler)
+        .event(DatabaseEvent::CancelFillAIField, cancel_fill_ai_field_handler)
+        // Automation
+        .event(DatabaseEvent::GetDatabaseAutomations, get_automations_handler)
+        .event(DatabaseEvent::CreateDatabaseAutomation, create_automation_handler)
+        .event(DatabaseEvent::UpdateDatabaseAutomation, update_automation_handler)
+        .event(DatabaseEvent::DeleteDatabaseAutomation, delete_automation_handler)
+        .event(DatabaseEvent::GetDatabaseAutomationLogs, get_automation_logs_handler)
 }
 
 /// [DatabaseEvent] defines events that are used to interact with the Grid. You could check [this](https://appflowy.gitbook.io/docs/essential-documentation/contribute-to-appflowy/architecture/backend/protobuf)
@@ -324,6 +353,14 @@ pub enum DatabaseEvent {
   #[event(input = "MoveCalendarEventPB")]
   MoveCalendarEvent = 126,
 
+  /// Returns the bars of the timeline, one for each row of the view.
+  #[event(input = "TimelineEventRequestPB", output = "RepeatedTimelineEventPB")]
+  GetAllTimelineEvents = 127,
+
+  /// Moves or resizes the bar of a row by updating its start and end dates.
+  #[event(input = "MoveTimelineEventPB")]
+  MoveTimelineEvent = 128,
+
   #[event(input = "CreateDatabaseViewPayloadPB")]
   CreateDatabaseView = 130,
 
@@ -334,6 +371,22 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseSnapshotPB")]
   GetDatabaseSnapshots = 150,
 
+  /// Creates a named checkpoint of the database, which includes its rows.
+  #[event(
+    input = "CreateDatabaseSnapshotPayloadPB",
+    output = "DatabaseSnapshotPB"
+  )]
+  CreateDatabaseSnapshot = 151,
+
+  /// Replaces the fields and the rows of the database with the ones in the snapshot.
+  #[event(input = "RestoreDatabaseSnapshotPayloadPB")]
+  RestoreDatabaseSnapshot = 152,
+
+  /// Returns the changes of the fields and the rows between two snapshots, or between a snapshot
+  /// and the current state of the database.
+  #[event(input = "DatabaseSnapshotDiffPayloadPB", output = "DatabaseDiffPB")]
+  GetDatabaseSnapshotDiff = 153,
+
   /// Returns the field settings for the provided fields in the given view
   #[event(input = "FieldIdsPB", output = "RepeatedFieldSettingsPB")]
   GetFieldSettings = 160,
@@ -377,4 +430,84 @@ pub enum DatabaseEvent {
 
   #[event(input = "TranslateRowPB")]
   TranslateRow = 175,
+
+  /// Fills the cells of a summary or a translate field for every row of the view, and returns
+  /// the result of each row.
+  #[event(input = "FillAIFieldPB", output = "AIFillResultPB")]
+  FillAIField = 176,
+
+  /// Stops filling the cells of the field.
+  #[event(input = "CancelFillAIFieldPB")]
+  CancelFillAIField = 177,
+
+  /// Returns the automations of the database of the view.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedAutomationPB")]
+  GetDatabaseAutomations = 178,
+
+  #[event(input = "CreateAutomationPayloadPB", output = "AutomationPB")]
+  CreateDatabaseAutomation = 179,
+
+  #[event(input = "UpdateAutomationPayloadPB")]
+  UpdateDatabaseAutomation = 180,
+
+  #[event(input = "DeleteAutomationPayloadPB")]
+  DeleteDatabaseAutomation = 181,
+
+  /// Returns the execution log of the automations of the database, the latest entry first.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedAutomationLogPB")]
+  GetDatabaseAutomationLogs = 182,
+
+  /// Completes the current occurrence of a recurring date and creates a copy of the row for the
+  /// next occurrence. Returns the copy, or None if the series has ended.
+  #[event(input = "CompleteDateOccurrencePB", output = "OptionalRowPB")]
+  CompleteDateOccurrence = 183,
+
+  /// Returns the conditional formatting rules of the view. The colors that they give to the rows
+  /// are in the `RowMetaPB` of the rows.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedConditionalFormatPB")]
+  GetConditionalFormats = 184,
+
+  #[event(
+    input = "CreateConditionalFormatPayloadPB",
+    output = "ConditionalFormatPB"
+  )]
+  CreateConditionalFormat = 185,
+
+  #[event(input = "UpdateConditionalFormatPayloadPB")]
+  UpdateConditionalFormat = 186,
+
+  #[event(input = "DeleteConditionalFormatPayloadPB")]
+  DeleteConditionalFormat = 187,
+
+  /// Returns the cells of the view that break the validation rules of the fields. The rules are
+  /// only enforced on new changes, so the existing data can break them.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedCellViolationPB")]
+  ValidateDatabase = 188,
+
+  /// Returns the comment threads of the row.
+  #[event(input = "RowIdPB", output = "RepeatedRowCommentPB")]
+  GetRowComments = 189,
+
+  /// Adds a comment of the current user to the row, or a reply to a thread of the row.
+  #[event(input = "AddRowCommentPayloadPB", output = "RowCommentPB")]
+  AddRowComment = 190,
+
+  #[event(input = "UpdateRowCommentPayloadPB")]
+  UpdateRowComment = 191,
+
+  /// Deletes a comment with its replies.
+  #[event(input = "RowCommentIdPB")]
+  DeleteRowComment = 192,
+
+  /// Resolves or reopens the thread of a comment.
+  #[event(input = "ResolveRowCommentPayloadPB")]
+  ResolveRowComment = 193,
+
+  /// Exports the view as a template, see [crate::services::share::template::DatabaseTemplate].
+  /// The template can be imported many times as a new database with the folder import.
+  #[event(
+    input = "DatabaseTemplateExportPayloadPB",
+    output = "DatabaseExportDataPB"
+  )]
+  ExportDatabaseTemplate = 194,
 }
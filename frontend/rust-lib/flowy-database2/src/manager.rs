@@ -1,37 +1,60 @@
 use anyhow::anyhow;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 use collab::core::collab::{DataSource, MutexCollab};
 use collab_database::database::{DatabaseData, MutexDatabase};
 use collab_database::error::DatabaseError;
-use collab_database::rows::RowId;
+use collab_database::fields::Field;
+use collab_database::rows::{RowChange, RowId};
 use collab_database::views::{CreateDatabaseParams, CreateViewParams, DatabaseLayout};
 use collab_database::workspace_database::{
   CollabDocStateByOid, CollabFuture, DatabaseCollabService, DatabaseMeta, WorkspaceDatabase,
 };
 use collab_entity::{CollabType, EncodedCollab};
 use collab_plugins::local_storage::kv::KVTransactionDB;
-use tokio::sync::{Mutex, RwLock};
-use tracing::{event, instrument, trace};
+use lib_dispatch::prelude::af_spawn;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc, Mutex, RwLock};
+use tracing::{event, instrument, trace, warn};
 
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
 use collab_integrate::{CollabKVAction, CollabKVDB, CollabPersistenceConfig};
 use flowy_database_pub::cloud::{
-  DatabaseCloudService, SummaryRowContent, TranslateItem, TranslateRowContent,
+  DatabaseCloudService, DatabaseSnapshot, SummaryRowContent, TranslateItem, TranslateRowContent,
 };
 use flowy_error::{internal_error, FlowyError, FlowyResult};
+use flowy_search_pub::entities::{BacklinkIndexManager, DatabaseIndexManager};
+use flowy_sqlite::kv::KVStorePreferences;
 use lib_infra::box_any::BoxAny;
 use lib_infra::priority_task::TaskDispatcher;
+use lib_infra::util::{md5, timestamp};
 
-use crate::entities::{DatabaseLayoutPB, DatabaseSnapshotPB, FieldType};
-use crate::services::cell::stringify_cell;
-use crate::services::database::DatabaseEditor;
+use crate::entities::{
+  AIFillResultPB, AIFillRowResultPB, AutomationParams, AutomationReminderPB, CreateRowPayloadPB,
+  DatabaseDiffPB, DatabaseLayoutPB, DatabaseSnapshotPB, FieldType, RelationTypeOptionPB,
+  UpdateRowMetaParams,
+};
+use crate::notification::{send_notification, DatabaseNotification};
+use crate::services::automation::{
+  is_automation_condition_met, resolve_automation_cells, run_in_automation_chain, Automation,
+  AutomationAction, AutomationController, AutomationLog, AutomationLogStatus, AutomationTask,
+  AutomationTaskHandler, AutomationTrigger,
+};
+use crate::services::cell::{stringify_cell, CellBuilder};
+use crate::services::database::{
+  DatabaseEditor, DatabaseRowIndexer, DatabaseViewIdsCache, ReminderDateChange,
+};
 use crate::services::database_view::DatabaseLayoutDepsResolver;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
+use crate::services::field::{RelationCellChangeset, RelationTypeOption};
 
 use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::share::csv::{CSVFormat, CSVImporter, ImportResult};
+use crate::services::share::csv::{CSVFormat, CSVImportOptions, CSVImporter, ImportResult};
+use crate::services::share::template::DatabaseTemplate;
+use crate::services::snapshot::diff::diff_database_data;
 
 pub trait DatabaseUser: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
@@ -40,13 +63,46 @@ pub trait DatabaseUser: Send + Sync {
   fn workspace_database_object_id(&self) -> Result<String, FlowyError>;
 }
 
+/// The interval of the automatic snapshots of the open databases.
+const AUTO_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// The interval of the checks of the date reached automations.
+const AUTOMATION_DATE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The title of the checkpoint that is created before restoring a snapshot of the database.
+const RESTORE_CHECKPOINT_TITLE: &str = "Before restore";
+
+/// Stores the snapshots of the databases locally. A snapshot is the json of the [DatabaseData],
+/// so it contains the rows of the database as well. The snapshots without a title are created
+/// automatically and are removed according to the snapshot retention.
+pub trait DatabaseSnapshotService: Send + Sync {
+  fn create_database_snapshot(
+    &self,
+    database_id: &str,
+    title: &str,
+    desc: &str,
+    data: Vec<u8>,
+  ) -> FlowyResult<DatabaseSnapshot>;
+
+  fn get_database_snapshot(&self, snapshot_id: i64) -> FlowyResult<DatabaseSnapshot>;
+}
+
 pub struct DatabaseManager {
   user: Arc<dyn DatabaseUser>,
   workspace_database: Arc<RwLock<Option<Arc<WorkspaceDatabase>>>>,
   task_scheduler: Arc<RwLock<TaskDispatcher>>,
-  editors: Mutex<HashMap<String, Arc<DatabaseEditor>>>,
+  editors: Arc<Mutex<HashMap<String, Arc<DatabaseEditor>>>>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
   cloud_service: Arc<dyn DatabaseCloudService>,
+  index_manager: Arc<dyn DatabaseIndexManager>,
+  backlink_manager: Arc<dyn BacklinkIndexManager>,
+  /// The views of the databases, used to index the references of the relation cells.
+  database_view_ids: DatabaseViewIdsCache,
+  snapshot_service: Arc<dyn DatabaseSnapshotService>,
+  /// The cancellation flags of the running AI fills, by the id of the field.
+  ai_fill_tasks: parking_lot::Mutex<HashMap<String, Arc<AtomicBool>>>,
+  automations: Arc<AutomationController>,
+  reminder_date_tx: broadcast::Sender<ReminderDateChange>,
 }
 
 impl DatabaseManager {
@@ -55,15 +111,41 @@ impl DatabaseManager {
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DatabaseCloudService>,
-  ) -> Self {
-    Self {
-      user: database_user,
-      workspace_database: Default::default(),
-      task_scheduler,
-      editors: Default::default(),
-      collab_builder,
-      cloud_service,
-    }
+    index_manager: Arc<dyn DatabaseIndexManager>,
+    backlink_manager: Arc<dyn BacklinkIndexManager>,
+    snapshot_service: Arc<dyn DatabaseSnapshotService>,
+    store_preferences: Arc<KVStorePreferences>,
+  ) -> Arc<Self> {
+    let automations = Arc::new(AutomationController::new(
+      store_preferences,
+      task_scheduler.clone(),
+    ));
+    let (reminder_date_tx, _) = broadcast::channel(100);
+    Arc::new_cyclic(|manager| {
+      spawn_automation_runner(manager.clone(), task_scheduler.clone());
+      spawn_automation_date_checker(manager.clone());
+      Self {
+        user: database_user,
+        workspace_database: Default::default(),
+        task_scheduler,
+        editors: Default::default(),
+        collab_builder,
+        cloud_service,
+        index_manager,
+        backlink_manager,
+        database_view_ids: Default::default(),
+        snapshot_service,
+        ai_fill_tasks: Default::default(),
+        automations,
+        reminder_date_tx,
+      }
+    })
+  }
+
+  /// Subscribes to the changes of the dates that have a reminder, so the reminders can be
+  /// rescheduled. Only the changes made on this device are sent.
+  pub fn subscribe_reminder_date_change(&self) -> broadcast::Receiver<ReminderDateChange> {
+    self.reminder_date_tx.subscribe()
   }
 
   fn is_collab_exist(&self, uid: i64, collab_db: &Weak<CollabKVDB>, object_id: &str) -> bool {
@@ -180,11 +262,62 @@ impl DatabaseManager {
     items
   }
 
+  /// Indexes the rows of the databases that aren't in the search index yet, eg. the databases
+  /// that were created before the search index existed or that were synced from the server.
+  pub async fn index_databases(&self) {
+    let (wdb, workspace_id) = match (self.get_database_indexer().await, self.user.workspace_id()) {
+      (Ok(wdb), Ok(workspace_id)) => (wdb, workspace_id),
+      _ => return,
+    };
+
+    for meta in wdb.get_all_database_meta() {
+      if self.index_manager.is_database_indexed(&meta.database_id)
+        && self.backlink_manager.is_object_indexed(&meta.database_id)
+      {
+        continue;
+      }
+
+      let is_opened = self.editors.lock().await.contains_key(&meta.database_id);
+      let database = match wdb.get_database(&meta.database_id).await {
+        Some(database) => database,
+        None => {
+          trace!("[Database Index] skip database: {}", meta.database_id);
+          continue;
+        },
+      };
+
+      let (rows, fields, inline_view_id) = {
+        let database = database.lock();
+        (
+          database.get_database_rows(),
+          database.get_fields(None),
+          database.get_inline_view_id(),
+        )
+      };
+      DatabaseRowIndexer::new(
+        self.index_manager.clone(),
+        self.backlink_manager.clone(),
+        Arc::downgrade(&wdb),
+        self.database_view_ids.clone(),
+        meta.database_id.clone(),
+        inline_view_id,
+        workspace_id.clone(),
+      )
+      .index_database(&rows, &fields);
+
+      // Release the databases that were only opened to be indexed
+      if !is_opened {
+        wdb.close_database(&meta.database_id);
+      }
+    }
+  }
+
   pub async fn update_database_indexing(
     &self,
     view_ids_by_database_id: HashMap<String, Vec<String>>,
   ) -> FlowyResult<()> {
     let wdb = self.get_database_indexer().await?;
+    self.database_view_ids.clear();
     view_ids_by_database_id
       .into_iter()
       .for_each(|(database_id, view_ids)| {
@@ -216,22 +349,297 @@ impl DatabaseManager {
 
   pub async fn open_database(&self, database_id: &str) -> FlowyResult<Arc<DatabaseEditor>> {
     trace!("open database editor:{}", database_id);
-    let database = self
-      .get_database_indexer()
-      .await?
+    let wdb = self.get_database_indexer().await?;
+    let database = wdb
       .get_database(database_id)
       .await
       .ok_or_else(|| FlowyError::collab_not_sync().with_context("open database error"))?;
+    let inline_view_id = database.lock().get_inline_view_id();
 
-    let editor = Arc::new(DatabaseEditor::new(database, self.task_scheduler.clone()).await?);
+    let row_indexer = DatabaseRowIndexer::new(
+      self.index_manager.clone(),
+      self.backlink_manager.clone(),
+      Arc::downgrade(&wdb),
+      self.database_view_ids.clone(),
+      database_id.to_string(),
+      inline_view_id.clone(),
+      self.user.workspace_id()?,
+    );
+    let editor = Arc::new(
+      DatabaseEditor::new(
+        database,
+        self.task_scheduler.clone(),
+        row_indexer,
+        self.automations.clone(),
+        self.user.user_id()?,
+        self.reminder_date_tx.clone(),
+      )
+      .await?,
+    );
     self
       .editors
       .lock()
       .await
       .insert(database_id.to_string(), editor.clone());
+    observe_rollup_sources(database_id, &editor, Arc::downgrade(&self.editors));
+    spawn_auto_snapshot(
+      database_id,
+      inline_view_id,
+      Arc::downgrade(&editor),
+      Arc::downgrade(&wdb),
+      self.snapshot_service.clone(),
+    );
     Ok(editor)
   }
 
+  /// Recomputes the cells of the rollup field with `field_id`. The databases that the rollup
+  /// fields aggregate values from are opened if they aren't yet.
+  pub async fn update_rollup_cells_of_field(
+    &self,
+    database: &DatabaseEditor,
+    field_id: &str,
+  ) -> FlowyResult<()> {
+    let related_databases = self.get_rollup_related_databases(database).await?;
+    database
+      .update_rollup_cells_of_field(field_id, &related_databases)
+      .await;
+    Ok(())
+  }
+
+  /// Computes the rollup cells of the row that was just created, since it may be created with the
+  /// cells of its relation fields.
+  pub async fn did_create_row(&self, database: &DatabaseEditor, row_id: &RowId) -> FlowyResult<()> {
+    let related_databases = self.get_rollup_related_databases(database).await?;
+    database
+      .update_rollup_cells_of_row(row_id, &related_databases)
+      .await;
+    Ok(())
+  }
+
+  async fn get_rollup_related_databases(
+    &self,
+    database: &DatabaseEditor,
+  ) -> FlowyResult<HashMap<String, Arc<DatabaseEditor>>> {
+    let mut related_databases = HashMap::new();
+    for database_id in database.get_rollup_related_database_ids() {
+      let related_database = self.get_database(&database_id).await?;
+      related_databases.insert(database_id, related_database);
+    }
+    Ok(related_databases)
+  }
+
+  /// Creates the relation field in the related database that mirrors the relation field with
+  /// `field_id`, and links the related rows back to the rows that already link to them.
+  pub async fn create_paired_relation_field(
+    &self,
+    database: &DatabaseEditor,
+    field_id: &str,
+  ) -> FlowyResult<()> {
+    let field = database
+      .get_field(field_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Relation field not found"))?;
+    let type_option = database
+      .get_relation_type_option(field_id)
+      .ok_or_else(|| FlowyError::invalid_data().with_context("The field isn't a relation field"))?;
+    if type_option.database_id.is_empty() || type_option.is_two_way() {
+      return Ok(());
+    }
+
+    let related_database = self.get_database(&type_option.database_id).await?;
+    let paired_field = related_database
+      .create_paired_relation_field(&field.name, &database.get_database_id(), field_id)
+      .await?;
+    database.set_paired_relation_field_id(field_id, &paired_field.id)?;
+
+    for (row_id, related_row_ids) in database.get_relation_cells(field_id) {
+      for related_row_id in related_row_ids {
+        let changeset = RelationCellChangeset {
+          inserted_row_ids: vec![row_id.clone()],
+          removed_row_ids: vec![],
+        };
+        related_database
+          .update_relation_cell(&related_row_id, &paired_field.id, changeset)
+          .await?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Updates the type option of the relation field. The relation is paired with a field in the
+  /// related database, or unpaired, according to `show_on_related_database`. Changing the related
+  /// database of a two-way relation pairs it with a field in the new database.
+  pub async fn update_relation_type_option(
+    &self,
+    database: &DatabaseEditor,
+    old_field: Field,
+    type_option: RelationTypeOptionPB,
+  ) -> FlowyResult<()> {
+    let field_id = old_field.id.clone();
+    let old_type_option = database
+      .get_relation_type_option(&field_id)
+      .unwrap_or_default();
+    let show_on_related_database = type_option.show_on_related_database;
+    let mut new_type_option = RelationTypeOption::from(type_option);
+    if old_type_option.is_two_way()
+      && (!show_on_related_database || new_type_option.database_id != old_type_option.database_id)
+    {
+      self.unpair_relation_field(database, &field_id).await?;
+    } else {
+      new_type_option.paired_field_id = old_type_option.paired_field_id;
+    }
+
+    database
+      .update_field_type_option(&field_id, new_type_option.into(), old_field)
+      .await?;
+    if show_on_related_database {
+      self
+        .create_paired_relation_field(database, &field_id)
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Applies the change of the relation cell of `field_id` in the row `row_id` to the paired
+  /// relation field, so the related rows link back to the row.
+  pub async fn did_update_relation_cell(
+    &self,
+    database: &DatabaseEditor,
+    field_id: &str,
+    row_id: &RowId,
+    changeset: RelationCellChangeset,
+  ) -> FlowyResult<()> {
+    let (related_database, paired_field_id) =
+      match self.get_paired_relation_field(database, field_id).await? {
+        Some(paired) => paired,
+        None => return Ok(()),
+      };
+
+    for inserted_row_id in changeset.inserted_row_ids {
+      let changeset = RelationCellChangeset {
+        inserted_row_ids: vec![row_id.clone()],
+        removed_row_ids: vec![],
+      };
+      related_database
+        .update_relation_cell(&inserted_row_id, &paired_field_id, changeset)
+        .await?;
+    }
+    for removed_row_id in changeset.removed_row_ids {
+      let changeset = RelationCellChangeset {
+        inserted_row_ids: vec![],
+        removed_row_ids: vec![row_id.clone()],
+      };
+      related_database
+        .update_relation_cell(&removed_row_id, &paired_field_id, changeset)
+        .await?;
+    }
+    Ok(())
+  }
+
+  /// Removes the links to the deleted rows from the paired relation fields of the related
+  /// databases, and recomputes the rollup cells that aggregated values from the deleted rows.
+  pub async fn did_delete_rows(
+    &self,
+    database: &DatabaseEditor,
+    row_ids: &[RowId],
+  ) -> FlowyResult<()> {
+    for field_id in database.get_two_way_relation_field_ids() {
+      if let Some((related_database, paired_field_id)) =
+        self.get_paired_relation_field(database, &field_id).await?
+      {
+        related_database
+          .remove_related_row_ids(&paired_field_id, row_ids)
+          .await?;
+      }
+    }
+
+    let database_id = database.get_database_id();
+    let editors = self.editors.lock().await.clone();
+    for editor in editors.values() {
+      editor
+        .did_delete_rollup_source_rows(&database_id, row_ids, &editors)
+        .await;
+    }
+    Ok(())
+  }
+
+  /// Clears the paired relation field as well when the relation field with `field_id` is cleared,
+  /// since none of the related rows is linked anymore. The rollup cells that depend on the field
+  /// are recomputed.
+  pub async fn did_clear_field(
+    &self,
+    database: &DatabaseEditor,
+    field_id: &str,
+  ) -> FlowyResult<()> {
+    if let Some((related_database, paired_field_id)) =
+      self.get_paired_relation_field(database, field_id).await?
+    {
+      related_database
+        .clear_field_of_all_rows(&paired_field_id)
+        .await?;
+    }
+
+    let editors = self.editors.lock().await.clone();
+    update_rollups_related_to(&database.get_database_id(), &editors).await;
+    Ok(())
+  }
+
+  /// Turns the two-way relation field with `field_id` and its paired field into one-way
+  /// relations. It's called before the field is deleted or converted to another field type.
+  pub async fn unpair_relation_field(
+    &self,
+    database: &DatabaseEditor,
+    field_id: &str,
+  ) -> FlowyResult<()> {
+    if let Some((related_database, paired_field_id)) =
+      self.get_paired_relation_field(database, field_id).await?
+    {
+      related_database.set_paired_relation_field_id(&paired_field_id, "")?;
+    }
+    let is_two_way = database
+      .get_relation_type_option(field_id)
+      .map(|type_option| type_option.is_two_way())
+      .unwrap_or(false);
+    if is_two_way {
+      database.set_paired_relation_field_id(field_id, "")?;
+    }
+    Ok(())
+  }
+
+  /// Returns the related database and the id of the field that mirrors the relation field with
+  /// `field_id`. Returns None if the relation is one-way, or if the paired field doesn't point
+  /// back to the field anymore.
+  async fn get_paired_relation_field(
+    &self,
+    database: &DatabaseEditor,
+    field_id: &str,
+  ) -> FlowyResult<Option<(Arc<DatabaseEditor>, String)>> {
+    let type_option = match database.get_relation_type_option(field_id) {
+      Some(type_option) if type_option.is_two_way() => type_option,
+      _ => return Ok(None),
+    };
+    let related_database = match self.get_database(&type_option.database_id).await {
+      Ok(related_database) => related_database,
+      Err(err) => {
+        warn!(
+          "[Relation] open related database {} failed: {}",
+          type_option.database_id, err
+        );
+        return Ok(None);
+      },
+    };
+    let is_paired = related_database
+      .get_relation_type_option(&type_option.paired_field_id)
+      .map(|paired| {
+        paired.paired_field_id == field_id && paired.database_id == database.get_database_id()
+      })
+      .unwrap_or(false);
+    if is_paired {
+      Ok(Some((related_database, type_option.paired_field_id)))
+    } else {
+      Ok(None)
+    }
+  }
+
   pub async fn open_database_view<T: AsRef<str>>(&self, view_id: T) -> FlowyResult<()> {
     let view_id = view_id.as_ref();
     let wdb = self.get_database_indexer().await?;
@@ -273,16 +681,47 @@ impl DatabaseManager {
   pub async fn delete_database_view(&self, view_id: &str) -> FlowyResult<()> {
     let database = self.get_database_with_view_id(view_id).await?;
     let _ = database.delete_database_view(view_id).await?;
+    self.database_view_ids.clear();
     Ok(())
   }
 
   pub async fn duplicate_database(&self, view_id: &str) -> FlowyResult<Vec<u8>> {
     let wdb = self.get_database_indexer().await?;
-    let data = wdb.get_database_data(view_id).await?;
+    let mut data = wdb.get_database_data(view_id).await?;
+    // The related databases mirror the relation fields of the original database only, so the
+    // relation fields of the copy are one-way.
+    unpair_relation_fields(&mut data.fields);
     let json_bytes = data.to_json_bytes()?;
     Ok(json_bytes)
   }
 
+  /// Exports the view as a [DatabaseTemplate]. The rows are exported only if `include_rows` is
+  /// true.
+  pub async fn export_database_template(
+    &self,
+    view_id: &str,
+    include_rows: bool,
+  ) -> FlowyResult<String> {
+    let wdb = self.get_database_indexer().await?;
+    let mut data = wdb.get_database_data(view_id).await?;
+    unpair_relation_fields(&mut data.fields);
+    let template = DatabaseTemplate::from_database_data(data, view_id, include_rows)?;
+    template.to_json_string()
+  }
+
+  /// Creates a new database from the json of a [DatabaseTemplate]. The ids are regenerated every
+  /// time, so a template can be used many times in the same workspace.
+  pub async fn create_database_from_template(
+    &self,
+    view_id: &str,
+    template: Vec<u8>,
+  ) -> FlowyResult<EncodedCollab> {
+    let data = DatabaseTemplate::from_json_bytes(&template)?.instantiate()?;
+    self
+      .create_database_with_database_data(view_id, data.to_json_bytes()?)
+      .await
+  }
+
   /// Create a new database with the given data that can be deserialized to [DatabaseData].
   #[tracing::instrument(level = "trace", skip_all, err)]
   pub async fn create_database_with_database_data(
@@ -306,6 +745,7 @@ impl DatabaseManager {
 
     let wdb = self.get_database_indexer().await?;
     let database = wdb.create_database(create_database_params)?;
+    self.database_view_ids.clear();
     let encoded_collab = database
       .lock()
       .get_collab()
@@ -320,6 +760,7 @@ impl DatabaseManager {
   ) -> FlowyResult<Arc<MutexDatabase>> {
     let wdb = self.get_database_indexer().await?;
     let database = wdb.create_database(params)?;
+    self.database_view_ids.clear();
     Ok(database)
   }
 
@@ -350,6 +791,7 @@ impl DatabaseManager {
       }
     };
     wdb.create_database_linked_view(params).await?;
+    self.database_view_ids.clear();
     Ok(())
   }
 
@@ -359,11 +801,31 @@ impl DatabaseManager {
     content: String,
     format: CSVFormat,
   ) -> FlowyResult<ImportResult> {
-    let params = tokio::task::spawn_blocking(move || {
-      CSVImporter.import_csv_from_string(view_id, content, format)
+    self
+      .import_csv_with_options(
+        view_id,
+        content.into_bytes(),
+        format,
+        CSVImportOptions::default(),
+      )
+      .await
+  }
+
+  /// Imports the csv content that is read with the given options. The rows that can't be parsed
+  /// are skipped and returned in [ImportResult::failed_rows].
+  pub async fn import_csv_with_options(
+    &self,
+    view_id: String,
+    content: Vec<u8>,
+    format: CSVFormat,
+    options: CSVImportOptions,
+  ) -> FlowyResult<ImportResult> {
+    let data = tokio::task::spawn_blocking(move || {
+      CSVImporter.import_csv_from_bytes(view_id, content, format, options)
     })
     .await
     .map_err(internal_error)??;
+    let params = data.params;
 
     // Currently, we only support importing up to 500 rows. We can support more rows in the future.
     if !cfg!(debug_assertions) && params.rows.len() > 500 {
@@ -382,16 +844,63 @@ impl DatabaseManager {
       database_id,
       view_id,
       encoded_collab,
+      failed_rows: data.failed_rows,
     };
     Ok(result)
   }
 
-  // will implement soon
+  /// Imports the csv file that is read with the given options, see [Self::import_csv_with_options].
   pub async fn import_csv_from_file(
     &self,
-    _file_path: String,
-    _format: CSVFormat,
+    view_id: String,
+    file_path: String,
+    format: CSVFormat,
+    options: CSVImportOptions,
+  ) -> FlowyResult<ImportResult> {
+    let content = tokio::fs::read(&file_path).await?;
+    self
+      .import_csv_with_options(view_id, content, format, options)
+      .await
+  }
+
+  /// Returns the id, the name and the id of the document of every row of the database. The name
+  /// of a row is the text of its primary cell.
+  pub async fn get_row_documents(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Vec<(String, String, String)>> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    let rows = database.get_related_rows(None).await?;
+    let row_documents = rows
+      .into_iter()
+      .filter_map(|row| {
+        let row_detail = database.get_row_detail(view_id, &RowId::from(row.row_id.clone()))?;
+        Some((row.row_id, row.name, row_detail.document_id))
+      })
+      .collect();
+    Ok(row_documents)
+  }
+
+  /// Marks the documents of the rows as not empty, e.g. after content is imported to them.
+  pub async fn set_row_documents_not_empty(
+    &self,
+    view_id: &str,
+    row_ids: Vec<String>,
   ) -> FlowyResult<()> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    for row_id in row_ids {
+      let params = UpdateRowMetaParams {
+        id: row_id.clone(),
+        view_id: view_id.to_string(),
+        icon_url: None,
+        cover_url: None,
+        is_document_empty: Some(false),
+        insert_comment: None,
+      };
+      database
+        .update_row_meta(&RowId::from(row_id), params)
+        .await?;
+    }
     Ok(())
   }
 
@@ -400,13 +909,21 @@ impl DatabaseManager {
     database.export_csv(style).await
   }
 
+  pub async fn export_html(&self, view_id: &str) -> FlowyResult<String> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    database.export_html(view_id).await
+  }
+
   pub async fn update_database_layout(
     &self,
     view_id: &str,
     layout: DatabaseLayoutPB,
   ) -> FlowyResult<()> {
     let database = self.get_database_with_view_id(view_id).await?;
-    database.update_view_layout(view_id, layout.into()).await
+    match layout.grid_layout() {
+      Some(grid_layout) => database.update_view_grid_layout(view_id, grid_layout).await,
+      None => database.update_view_layout(view_id, layout.into()).await,
+    }
   }
 
   pub async fn get_database_snapshots(
@@ -431,6 +948,82 @@ impl DatabaseManager {
     Ok(snapshots)
   }
 
+  /// Creates a named checkpoint of the database. Unlike the automatic snapshots, the checkpoints
+  /// are never removed by the snapshot retention.
+  pub async fn create_database_snapshot(
+    &self,
+    view_id: &str,
+    title: &str,
+    desc: &str,
+  ) -> FlowyResult<DatabaseSnapshotPB> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    let wdb = self.get_database_indexer().await?;
+    let data = wdb.get_database_data(view_id).await?.to_json_bytes()?;
+    let snapshot =
+      self
+        .snapshot_service
+        .create_database_snapshot(&database_id, title, desc, data)?;
+    Ok(DatabaseSnapshotPB {
+      snapshot_id: snapshot.snapshot_id,
+      snapshot_desc: desc.to_string(),
+      created_at: snapshot.created_at,
+      data: snapshot.data,
+    })
+  }
+
+  /// Replaces the fields and the rows of the database with the ones in the snapshot. The current
+  /// state is saved as a checkpoint first, so the restore can be reverted.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn restore_database_snapshot(
+    &self,
+    view_id: &str,
+    snapshot_id: i64,
+  ) -> FlowyResult<()> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    let data = self.get_database_data_of_snapshot(&database_id, snapshot_id)?;
+    self
+      .create_database_snapshot(view_id, RESTORE_CHECKPOINT_TITLE, "")
+      .await?;
+
+    let database = self.get_database(&database_id).await?;
+    database.restore_database_data(view_id, data).await
+  }
+
+  /// Returns the changes of the fields and the rows between two snapshots of the database. The
+  /// snapshot is compared with the current state of the database if `to_snapshot_id` is None.
+  pub async fn get_database_snapshot_diff(
+    &self,
+    view_id: &str,
+    from_snapshot_id: i64,
+    to_snapshot_id: Option<i64>,
+  ) -> FlowyResult<DatabaseDiffPB> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    let old = self.get_database_data_of_snapshot(&database_id, from_snapshot_id)?;
+    let new = match to_snapshot_id {
+      Some(to_snapshot_id) => self.get_database_data_of_snapshot(&database_id, to_snapshot_id)?,
+      None => {
+        let wdb = self.get_database_indexer().await?;
+        wdb.get_database_data(view_id).await?
+      },
+    };
+    Ok(diff_database_data(&old, &new))
+  }
+
+  fn get_database_data_of_snapshot(
+    &self,
+    database_id: &str,
+    snapshot_id: i64,
+  ) -> FlowyResult<DatabaseData> {
+    let snapshot = self.snapshot_service.get_database_snapshot(snapshot_id)?;
+    if snapshot.database_id != database_id {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "Snapshot {} doesn't belong to database {}",
+        snapshot_id, database_id
+      )));
+    }
+    Ok(DatabaseData::from_json_bytes(snapshot.data)?)
+  }
+
   /// Return the database indexer.
   /// Each workspace has itw own Database indexer that manages all the databases and database views
   async fn get_database_indexer(&self) -> FlowyResult<Arc<WorkspaceDatabase>> {
@@ -480,7 +1073,7 @@ impl DatabaseManager {
 
     // Update the cell with the response from the cloud service.
     database
-      .update_cell_with_changeset(&view_id, &row_id, &field_id, BoxAny::new(response))
+      .update_cell_with_system_changeset(&view_id, &row_id, &field_id, BoxAny::new(response))
       .await?;
     Ok(())
   }
@@ -555,11 +1148,356 @@ impl DatabaseManager {
     trace!("[AI]:translate row response: {}", content);
     // Update the cell with the response from the cloud service.
     database
-      .update_cell_with_changeset(&view_id, &row_id, &field_id, BoxAny::new(content))
+      .update_cell_with_system_changeset(&view_id, &row_id, &field_id, BoxAny::new(content))
       .await?;
     Ok(())
   }
 
+  /// Fills the cells of the summary or the translate field for every row of the view, one row
+  /// after another. A row that fails doesn't stop the others, its error is returned in the result
+  /// instead. The fill can be stopped by [Self::cancel_fill_ai_field], the cancellation is only
+  /// checked between the rows, so the row that is being filled is finished first.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn fill_ai_field(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    overwrite: bool,
+  ) -> FlowyResult<AIFillResultPB> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    let field = database
+      .get_field(field_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Field not found"))?;
+    let field_type = FieldType::from(field.field_type);
+    if !field_type.is_ai_field() {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "Can not fill the cells of a {} field with AI",
+        field_type.default_name()
+      )));
+    }
+    let rows = database.get_rows(view_id).await?;
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+      let mut ai_fill_tasks = self.ai_fill_tasks.lock();
+      if ai_fill_tasks.contains_key(field_id) {
+        return Err(
+          FlowyError::invalid_data().with_context("The field is already being filled with AI"),
+        );
+      }
+      ai_fill_tasks.insert(field_id.to_string(), cancelled.clone());
+    }
+    // Removes the task when the fill ends, or when the fill is dropped before it ends
+    let _task_guard = AIFillTaskGuard {
+      ai_fill_tasks: &self.ai_fill_tasks,
+      field_id,
+    };
+
+    let mut result = AIFillResultPB::default();
+    for row_detail in rows {
+      if cancelled.load(Ordering::SeqCst) {
+        result.is_cancelled = true;
+        break;
+      }
+
+      let row_id = row_detail.row.id.clone();
+      if !overwrite {
+        let content = row_detail
+          .row
+          .cells
+          .get(field_id)
+          .map(|cell| stringify_cell(cell, &field))
+          .unwrap_or_default();
+        if !content.is_empty() {
+          continue;
+        }
+      }
+
+      let fill_result = if field_type == FieldType::Summary {
+        self
+          .summarize_row(view_id.to_string(), row_id.clone(), field_id.to_string())
+          .await
+      } else {
+        self
+          .translate_row(view_id.to_string(), row_id.clone(), field_id.to_string())
+          .await
+      };
+      if let Err(err) = &fill_result {
+        warn!("[AI]:fill row {} failed: {}", row_id, err);
+      }
+      result.rows.push(AIFillRowResultPB {
+        row_id: row_id.to_string(),
+        error: fill_result.err().map(|err| err.msg),
+      });
+    }
+    Ok(result)
+  }
+
+  /// Stops filling the field after the row that is being filled, see [Self::fill_ai_field]. Does
+  /// nothing if the field isn't being filled.
+  pub fn cancel_fill_ai_field(&self, field_id: &str) {
+    if let Some(cancelled) = self.ai_fill_tasks.lock().get(field_id) {
+      cancelled.store(true, Ordering::SeqCst);
+    }
+  }
+
+  pub async fn get_automations(&self, view_id: &str) -> FlowyResult<Vec<Automation>> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    Ok(database.get_automations())
+  }
+
+  pub async fn create_automation(
+    &self,
+    view_id: &str,
+    params: AutomationParams,
+  ) -> FlowyResult<Automation> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    validate_automation_trigger(&database, &params.trigger)?;
+    let automation = Automation {
+      id: nanoid::nanoid!(10),
+      name: params.name,
+      is_enabled: params.is_enabled,
+      trigger: params.trigger,
+      condition: params.condition,
+      actions: params.actions,
+      created_at: timestamp(),
+    };
+    database.save_automation(&automation)?;
+    Ok(automation)
+  }
+
+  pub async fn update_automation(
+    &self,
+    view_id: &str,
+    automation_id: &str,
+    params: AutomationParams,
+  ) -> FlowyResult<()> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    validate_automation_trigger(&database, &params.trigger)?;
+    let mut automation = database
+      .get_automation(automation_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Automation not found"))?;
+    automation.name = params.name;
+    automation.is_enabled = params.is_enabled;
+    automation.trigger = params.trigger;
+    automation.condition = params.condition;
+    automation.actions = params.actions;
+    database.save_automation(&automation)
+  }
+
+  pub async fn delete_automation(&self, view_id: &str, automation_id: &str) -> FlowyResult<()> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    database.delete_automation(automation_id);
+    self
+      .automations
+      .remove_fired_dates(&database.get_database_id(), automation_id);
+    Ok(())
+  }
+
+  pub async fn get_automation_logs(&self, view_id: &str) -> FlowyResult<Vec<AutomationLog>> {
+    let database_id = self.get_database_id_with_view_id(view_id).await?;
+    Ok(self.automations.get_logs(&database_id))
+  }
+
+  /// Runs the actions of the automation on the row if the row meets the condition, and records
+  /// the result in the execution log.
+  async fn run_automation(&self, task: AutomationTask) {
+    let database = match self.get_database(&task.database_id).await {
+      Ok(database) => database,
+      Err(err) => {
+        warn!(
+          "[Automation]: can't open database {}: {}",
+          task.database_id, err
+        );
+        return;
+      },
+    };
+    let automation = match database
+      .get_automations()
+      .into_iter()
+      .find(|automation| automation.id == task.automation_id)
+    {
+      Some(automation) if automation.is_enabled => automation,
+      _ => return,
+    };
+
+    let (status, message) = match self
+      .run_automation_actions(&database, &task, &automation)
+      .await
+    {
+      Ok(true) => (AutomationLogStatus::Succeeded, "".to_string()),
+      Ok(false) => (
+        AutomationLogStatus::Skipped,
+        "The row doesn't meet the condition".to_string(),
+      ),
+      Err(err) => (AutomationLogStatus::Failed, err.msg),
+    };
+    self.automations.append_log(
+      &task.database_id,
+      AutomationLog {
+        automation_id: automation.id,
+        automation_name: automation.name,
+        row_id: task.row_id,
+        status,
+        message,
+        timestamp: timestamp(),
+      },
+    );
+  }
+
+  /// Returns false if the row doesn't meet the condition of the automation. Every action is run
+  /// even if one of them fails.
+  async fn run_automation_actions(
+    &self,
+    database: &DatabaseEditor,
+    task: &AutomationTask,
+    automation: &Automation,
+  ) -> FlowyResult<bool> {
+    let view_id = database.get_inline_view_id();
+    let row_id = RowId::from(task.row_id.clone());
+    let row = database
+      .get_row(&view_id, &row_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("The row was deleted"))?;
+    let fields = database.get_fields(&view_id, None);
+    if !is_automation_condition_met(automation, &row, &fields) {
+      return Ok(false);
+    }
+
+    let mut chain = task.chain.clone();
+    chain.push(automation.id.clone());
+    let errors = run_in_automation_chain(chain, async {
+      let mut errors = vec![];
+      for action in &automation.actions {
+        if let Err(err) = self
+          .run_automation_action(database, &view_id, &row_id, automation, action)
+          .await
+        {
+          warn!("[Automation]: action of {} failed: {}", automation.id, err);
+          errors.push(err.msg);
+        }
+      }
+      errors
+    })
+    .await;
+
+    if errors.is_empty() {
+      Ok(true)
+    } else {
+      Err(FlowyError::internal().with_context(errors.join("; ")))
+    }
+  }
+
+  async fn run_automation_action(
+    &self,
+    database: &DatabaseEditor,
+    view_id: &str,
+    row_id: &RowId,
+    automation: &Automation,
+    action: &AutomationAction,
+  ) -> FlowyResult<()> {
+    match action {
+      AutomationAction::UpdateCell { field_id, value } => {
+        if value.is_empty() {
+          return database.clear_cell(view_id, row_id.clone(), field_id).await;
+        }
+        let field = database.get_field(field_id).ok_or_else(|| {
+          FlowyError::record_not_found().with_context(format!("Field {} not found", field_id))
+        })?;
+        let fields = vec![field];
+        let cell_by_field_id =
+          resolve_automation_cells(HashMap::from([(field_id.clone(), value.clone())]), &fields);
+        let cell = CellBuilder::with_cells(cell_by_field_id, &fields)
+          .build()
+          .get(field_id)
+          .cloned()
+          .ok_or_else(|| {
+            FlowyError::invalid_data().with_context(format!(
+              "Invalid value of the {} field: {}",
+              fields[0].name, value
+            ))
+          })?;
+        database.update_cell(view_id, row_id, field_id, cell).await
+      },
+      AutomationAction::CreateRow { database_id, cells } => {
+        let related_database = self.get_database(database_id).await?;
+        let related_view_id = related_database.get_inline_view_id();
+        let fields = related_database.get_fields(&related_view_id, None);
+        related_database
+          .create_row(CreateRowPayloadPB {
+            view_id: related_view_id,
+            data: resolve_automation_cells(cells.clone(), &fields),
+            ..Default::default()
+          })
+          .await?;
+        Ok(())
+      },
+      AutomationAction::MoveToGroup {
+        view_id: group_view_id,
+        group_id,
+      } => {
+        database
+          .move_row_to_group(group_view_id, row_id.clone(), group_id)
+          .await
+      },
+      AutomationAction::SetRelation { field_id, row_ids } => {
+        let changeset = RelationCellChangeset {
+          inserted_row_ids: row_ids.iter().cloned().map(RowId::from).collect(),
+          removed_row_ids: vec![],
+        };
+        database
+          .update_relation_cell(row_id, field_id, changeset.clone())
+          .await?;
+        self
+          .did_update_relation_cell(database, field_id, row_id, changeset)
+          .await
+      },
+      AutomationAction::SendReminder { message } => {
+        let database_id = database.get_database_id();
+        send_notification(
+          &database_id,
+          DatabaseNotification::DidTriggerAutomationReminder,
+        )
+        .payload(AutomationReminderPB {
+          database_id: database_id.clone(),
+          row_id: row_id.to_string(),
+          automation_id: automation.id.clone(),
+          message: message.clone(),
+        })
+        .send();
+        Ok(())
+      },
+    }
+  }
+
+  /// Schedules the date reached automations of the rows whose date has been reached. Only the
+  /// open databases are checked, the dates that are reached while a database is closed fire
+  /// when it's opened again.
+  async fn check_date_reached_automations(&self) {
+    let editors = self
+      .editors
+      .lock()
+      .await
+      .iter()
+      .map(|(database_id, editor)| (database_id.clone(), editor.clone()))
+      .collect::<Vec<_>>();
+    for (database_id, editor) in editors {
+      let automations = editor.get_automations();
+      let has_date_trigger = automations.iter().any(|automation| {
+        automation.is_enabled && matches!(automation.trigger, AutomationTrigger::DateReached { .. })
+      });
+      if !has_date_trigger {
+        continue;
+      }
+
+      // The fired dates are kept on this device, the automations are not written.
+      let fired =
+        self
+          .automations
+          .did_reach_dates(&database_id, &automations, &editor.get_all_rows());
+      self.automations.schedule_all(&database_id, fired).await;
+    }
+  }
+
   /// Only expose this method for testing
   #[cfg(debug_assertions)]
   pub fn get_cloud_service(&self) -> &Arc<dyn DatabaseCloudService> {
@@ -567,6 +1505,199 @@ impl DatabaseManager {
   }
 }
 
+/// Removes the AI fill task of the field from [DatabaseManager::ai_fill_tasks] when it's dropped.
+struct AIFillTaskGuard<'a> {
+  ai_fill_tasks: &'a parking_lot::Mutex<HashMap<String, Arc<AtomicBool>>>,
+  field_id: &'a str,
+}
+
+impl Drop for AIFillTaskGuard<'_> {
+  fn drop(&mut self) {
+    self.ai_fill_tasks.lock().remove(self.field_id);
+  }
+}
+
+fn validate_automation_trigger(
+  database: &DatabaseEditor,
+  trigger: &AutomationTrigger,
+) -> FlowyResult<()> {
+  if let Some(field_id) = trigger.field_id() {
+    let field = database.get_field(field_id).ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Field {} not found", field_id))
+    })?;
+    if matches!(trigger, AutomationTrigger::DateReached { .. })
+      && FieldType::from(field.field_type) != FieldType::DateTime
+    {
+      return Err(
+        FlowyError::invalid_data().with_context("The date reached trigger requires a date field"),
+      );
+    }
+  }
+  Ok(())
+}
+
+/// Registers the handler of the automation tasks and runs the tasks one after another.
+fn spawn_automation_runner(
+  manager: Weak<DatabaseManager>,
+  task_scheduler: Arc<RwLock<TaskDispatcher>>,
+) {
+  let (sender, mut receiver) = mpsc::unbounded_channel();
+  af_spawn(async move {
+    task_scheduler
+      .write()
+      .await
+      .register_handler(AutomationTaskHandler::new(sender));
+    while let Some(task) = receiver.recv().await {
+      match manager.upgrade() {
+        None => break,
+        Some(manager) => manager.run_automation(task).await,
+      }
+    }
+  });
+}
+
+fn spawn_automation_date_checker(manager: Weak<DatabaseManager>) {
+  af_spawn(async move {
+    let mut interval = tokio::time::interval(AUTOMATION_DATE_CHECK_INTERVAL);
+    loop {
+      interval.tick().await;
+      match manager.upgrade() {
+        None => break,
+        Some(manager) => manager.check_date_reached_automations().await,
+      }
+    }
+  });
+}
+
+/// Takes a snapshot of the database periodically while its editor is open. The snapshot is
+/// skipped if the database hasn't changed since the last check.
+fn spawn_auto_snapshot(
+  database_id: &str,
+  view_id: String,
+  editor: Weak<DatabaseEditor>,
+  workspace_database: Weak<WorkspaceDatabase>,
+  snapshot_service: Arc<dyn DatabaseSnapshotService>,
+) {
+  let database_id = database_id.to_string();
+  af_spawn(async move {
+    let mut interval = tokio::time::interval(AUTO_SNAPSHOT_INTERVAL);
+    let mut last_digest: Option<String> = None;
+    loop {
+      interval.tick().await;
+      if editor.upgrade().is_none() {
+        break;
+      }
+      let wdb = match workspace_database.upgrade() {
+        None => break,
+        Some(wdb) => wdb,
+      };
+      let data = match wdb.get_database_data(&view_id).await {
+        Ok(data) => data.to_json_bytes(),
+        Err(err) => Err(err),
+      };
+      let data = match data {
+        Ok(data) => data,
+        Err(err) => {
+          warn!(
+            "[Database Snapshot] read database {} failed: {}",
+            database_id, err
+          );
+          continue;
+        },
+      };
+
+      // The first check only records the current state.
+      let digest = md5(&data);
+      let is_changed = last_digest
+        .as_ref()
+        .map_or(false, |last_digest| last_digest != &digest);
+      last_digest = Some(digest);
+      if is_changed {
+        if let Err(err) = snapshot_service.create_database_snapshot(&database_id, "", "", data) {
+          warn!(
+            "[Database Snapshot] create snapshot for {} failed: {}",
+            database_id, err
+          );
+        }
+      }
+    }
+  });
+}
+
+fn unpair_relation_fields(fields: &mut [Field]) {
+  for field in fields.iter_mut() {
+    if let Some(mut type_option) = field.get_type_option::<RelationTypeOption>(FieldType::Relation)
+    {
+      if type_option.is_two_way() {
+        type_option.paired_field_id = "".to_string();
+        field
+          .type_options
+          .insert(FieldType::Relation.to_string(), type_option.into());
+      }
+    }
+  }
+}
+
+/// Refreshes the rollup cells of the open databases whenever a cell of the database with
+/// `database_id` changes. A rollup can aggregate values from its own database, so the database
+/// itself is checked as well.
+fn observe_rollup_sources(
+  database_id: &str,
+  database: &DatabaseEditor,
+  editors: Weak<Mutex<HashMap<String, Arc<DatabaseEditor>>>>,
+) {
+  let database_id = database_id.to_string();
+  let mut row_change = database.subscribe_row_change();
+  af_spawn(async move {
+    // The rollup cells are only kept up to date while both databases are open, so they are
+    // recomputed once the database is opened.
+    if let Some(editors) = editors.upgrade() {
+      let editors = editors.lock().await.clone();
+      update_rollups_related_to(&database_id, &editors).await;
+    }
+
+    loop {
+      let row_change = match row_change.recv().await {
+        Ok(row_change) => row_change,
+        Err(RecvError::Lagged(_)) => continue,
+        Err(RecvError::Closed) => break,
+      };
+
+      if let RowChange::DidUpdateCell {
+        field_id, row_id, ..
+      } = row_change
+      {
+        let editors = match editors.upgrade() {
+          None => break,
+          Some(editors) => editors.lock().await.clone(),
+        };
+        for editor in editors.values() {
+          editor
+            .did_update_rollup_source(&database_id, &row_id, &field_id, &editors)
+            .await;
+        }
+      }
+    }
+  });
+}
+
+/// Recomputes the rollup cells of the database with `database_id` and the rollup cells of the open
+/// databases that aggregate values from it.
+async fn update_rollups_related_to(
+  database_id: &str,
+  editors: &HashMap<String, Arc<DatabaseEditor>>,
+) {
+  for (editor_database_id, editor) in editors {
+    if editor_database_id == database_id {
+      editor.update_all_rollup_cells(None, editors).await;
+    } else {
+      editor
+        .update_all_rollup_cells(Some(database_id), editors)
+        .await;
+    }
+  }
+}
+
 struct UserDatabaseCollabServiceImpl {
   user: Arc<dyn DatabaseUser>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
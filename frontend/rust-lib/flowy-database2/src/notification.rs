@@ -52,6 +52,12 @@ pub enum DatabaseNotification {
   DidUpdateFieldSettings = 86,
   // Trigger when Calculation changed
   DidUpdateCalculation = 87,
+  // Trigger when an automation of the database sends a reminder
+  DidTriggerAutomationReminder = 88,
+  // Trigger when the comments of a row are changed
+  DidUpdateRowComments = 89,
+  // Trigger when a comment of another user mentions the current user
+  DidMentionInRowComment = 90,
 }
 
 impl std::convert::From<DatabaseNotification> for i32 {
@@ -84,6 +90,9 @@ impl std::convert::From<i32> for DatabaseNotification {
       84 => DatabaseNotification::DidMoveDatabaseViewToTrash,
       86 => DatabaseNotification::DidUpdateFieldSettings,
       87 => DatabaseNotification::DidUpdateCalculation,
+      88 => DatabaseNotification::DidTriggerAutomationReminder,
+      89 => DatabaseNotification::DidUpdateRowComments,
+      90 => DatabaseNotification::DidMentionInRowComment,
       _ => DatabaseNotification::Unknown,
     }
   }
@@ -0,0 +1,404 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use collab::preclude::{Collab, Map, MapRefExtension};
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row};
+use tokio::sync::RwLock;
+
+use flowy_error::{internal_error, FlowyResult};
+use flowy_sqlite::kv::KVStorePreferences;
+use lib_infra::priority_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
+use lib_infra::util::timestamp;
+
+use crate::entities::{FieldType, FilterPB};
+use crate::services::automation::{
+  Automation, AutomationLog, AutomationLogStatus, AutomationTask, AutomationTrigger,
+};
+use crate::services::cell::stringify_cell;
+use crate::services::field::DateCellData;
+use crate::services::filter::{apply_filter, Filter};
+use crate::utils::cache::AnyTypeCache;
+
+pub const AUTOMATION_HANDLER_ID: &str = "database_automation";
+
+/// The maximum number of automations in a chain, where the actions of each automation trigger
+/// the next one.
+const MAX_AUTOMATION_CHAIN_LEN: usize = 8;
+
+/// The maximum number of entries in the execution log of a database.
+const MAX_AUTOMATION_LOGS: usize = 100;
+
+/// The value of a date cell that is the time when the action runs.
+const AUTOMATION_NOW: &str = "now";
+
+/// The key of the automations in the database collab, so they are synced with the rest of the
+/// database. Every automation is a separate entry of the map, by the id of the automation, so the
+/// automations that are changed on different devices at the same time are merged.
+const DATABASE_AUTOMATIONS: &str = "automations";
+const AUTOMATION_LOGS_KEY: &str = "database_automation_logs";
+const AUTOMATION_FIRED_DATES_KEY: &str = "database_automation_fired_dates";
+
+tokio::task_local! {
+  /// The chain of the automation whose actions are run by the current task. The changes that
+  /// are made by other tasks, e.g. the user editing another row, don't belong to the chain.
+  static AUTOMATION_CHAIN: Vec<String>;
+}
+
+/// Runs the actions of the last automation of the chain. The automations triggered by the
+/// changes that the actions make are scheduled with this chain.
+pub async fn run_in_automation_chain<F: Future>(chain: Vec<String>, actions: F) -> F::Output {
+  AUTOMATION_CHAIN.scope(chain, actions).await
+}
+
+/// Returns the automations that are stored in the database collab, in the order they were
+/// created.
+pub fn get_automations_from_collab(collab: &Collab) -> Vec<Automation> {
+  let txn = collab.transact();
+  let mut automations = collab
+    .get_map_with_txn(&txn, vec![DATABASE_AUTOMATIONS])
+    .map(|map| {
+      map
+        .iter(&txn)
+        .filter_map(|(_, value)| {
+          let json = value.to_string(&txn);
+          serde_json::from_str::<Automation>(&json)
+            .map_err(|err| tracing::error!("Failed to parse the automation: {}", err))
+            .ok()
+        })
+        .collect::<Vec<Automation>>()
+    })
+    .unwrap_or_default();
+  automations.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+  automations
+}
+
+/// Inserts the automation, or replaces the automation with the same id.
+pub fn save_automation_in_collab(collab: &Collab, automation: &Automation) -> FlowyResult<()> {
+  let json = serde_json::to_string(automation).map_err(internal_error)?;
+  collab.with_origin_transact_mut(|txn| {
+    let map = collab.insert_map_with_txn_if_not_exist(txn, DATABASE_AUTOMATIONS);
+    map.insert_str_with_txn(txn, &automation.id, json);
+  });
+  Ok(())
+}
+
+pub fn remove_automation_in_collab(collab: &Collab, automation_id: &str) {
+  collab.with_origin_transact_mut(|txn| {
+    if let Some(map) = collab.get_map_with_txn(txn, vec![DATABASE_AUTOMATIONS]) {
+      map.remove(txn, automation_id);
+    }
+  });
+}
+
+/// Schedules the automation tasks in the task dispatcher when a row of a database fires the
+/// trigger of an automation, and keeps the execution log and the fired dates of the automations
+/// on this device. The automations themselves are stored in the database, see [get_automations_from_collab]. The
+/// tasks are run by the [crate::manager::DatabaseManager].
+pub struct AutomationController {
+  store_preferences: Arc<KVStorePreferences>,
+  task_scheduler: Arc<RwLock<TaskDispatcher>>,
+}
+
+impl AutomationController {
+  pub fn new(
+    store_preferences: Arc<KVStorePreferences>,
+    task_scheduler: Arc<RwLock<TaskDispatcher>>,
+  ) -> Self {
+    Self {
+      store_preferences,
+      task_scheduler,
+    }
+  }
+
+  /// Returns the execution log of the database, the latest entry first.
+  pub fn get_logs(&self, database_id: &str) -> Vec<AutomationLog> {
+    self
+      .store_preferences
+      .get_object::<Vec<AutomationLog>>(&automation_logs_key(database_id))
+      .unwrap_or_default()
+  }
+
+  pub fn append_log(&self, database_id: &str, log: AutomationLog) {
+    let mut logs = self.get_logs(database_id);
+    logs.insert(0, log);
+    logs.truncate(MAX_AUTOMATION_LOGS);
+    if let Err(err) = self
+      .store_preferences
+      .set_object(&automation_logs_key(database_id), &logs)
+    {
+      tracing::error!("Failed to save the automation log: {}", err);
+    }
+  }
+
+  /// Forgets the dates that fired the automation, so they don't fire an automation that is
+  /// created later with the same id.
+  pub fn remove_fired_dates(&self, database_id: &str, automation_id: &str) {
+    let mut fired_dates = self.get_fired_dates(database_id);
+    if fired_dates.remove(automation_id).is_some() {
+      self.save_fired_dates(database_id, &fired_dates);
+    }
+  }
+
+  /// The dates that already fired the [AutomationTrigger::DateReached] trigger, by the id of the
+  /// automation and the id of the row.
+  fn get_fired_dates(&self, database_id: &str) -> HashMap<String, HashMap<String, i64>> {
+    self
+      .store_preferences
+      .get_object::<HashMap<String, HashMap<String, i64>>>(&automation_fired_dates_key(database_id))
+      .unwrap_or_default()
+  }
+
+  fn save_fired_dates(
+    &self,
+    database_id: &str,
+    fired_dates: &HashMap<String, HashMap<String, i64>>,
+  ) {
+    if let Err(err) = self
+      .store_preferences
+      .set_object(&automation_fired_dates_key(database_id), fired_dates)
+    {
+      tracing::error!("Failed to save the fired dates of the automations: {}", err);
+    }
+  }
+
+  pub async fn did_create_row(&self, database_id: &str, automations: &[Automation], row_id: &str) {
+    let automations = automations.iter().filter(|automation| {
+      automation.is_enabled && matches!(automation.trigger, AutomationTrigger::RowCreated)
+    });
+    for automation in automations {
+      self.schedule(database_id, automation, row_id).await;
+    }
+  }
+
+  pub async fn did_update_cell(
+    &self,
+    database_id: &str,
+    automations: &[Automation],
+    field: &Field,
+    row_id: &str,
+    old_cell: Option<&Cell>,
+    new_cell: Option<&Cell>,
+  ) {
+    let old_value = old_cell
+      .map(|cell| stringify_cell(cell, field))
+      .unwrap_or_default();
+    let new_value = new_cell
+      .map(|cell| stringify_cell(cell, field))
+      .unwrap_or_default();
+    if old_value == new_value {
+      return;
+    }
+
+    let is_match = |expected: &Option<String>, value: &str| {
+      expected
+        .as_ref()
+        .map(|expected| expected.trim().eq_ignore_ascii_case(value.trim()))
+        .unwrap_or(true)
+    };
+    let automations = automations.iter().filter(|automation| {
+      if !automation.is_enabled {
+        return false;
+      }
+      match &automation.trigger {
+        AutomationTrigger::CellChanged { field_id, from, to } => {
+          field_id == &field.id && is_match(from, &old_value) && is_match(to, &new_value)
+        },
+        _ => false,
+      }
+    });
+    for automation in automations {
+      self.schedule(database_id, automation, row_id).await;
+    }
+  }
+
+  /// Finds the rows whose date has been reached since the date reached automation was created,
+  /// and remembers the dates on this device so that they fire only once. A row fires again if
+  /// its date changes. Returns the automations to run with the id of their row, they are
+  /// scheduled by [Self::schedule_all].
+  pub fn did_reach_dates(
+    &self,
+    database_id: &str,
+    automations: &[Automation],
+    rows: &[Row],
+  ) -> Vec<(Automation, String)> {
+    let now = timestamp();
+    let mut all_fired_dates = self.get_fired_dates(database_id);
+    let mut fired = vec![];
+    for automation in automations.iter() {
+      let field_id = match &automation.trigger {
+        AutomationTrigger::DateReached { field_id } if automation.is_enabled => field_id.clone(),
+        _ => continue,
+      };
+      let fired_dates = all_fired_dates
+        .entry(automation.id.clone())
+        .or_insert_with(HashMap::new);
+      for row in rows {
+        let date = row
+          .cells
+          .get(&field_id)
+          .and_then(|cell| DateCellData::from(cell).timestamp);
+        if let Some(date) = date {
+          let row_id = row.id.to_string();
+          if date <= now && date >= automation.created_at && fired_dates.get(&row_id) != Some(&date)
+          {
+            fired_dates.insert(row_id.clone(), date);
+            fired.push((automation.clone(), row_id));
+          }
+        }
+      }
+    }
+
+    if !fired.is_empty() {
+      self.save_fired_dates(database_id, &all_fired_dates);
+    }
+    fired
+  }
+
+  pub async fn schedule_all(&self, database_id: &str, fired: Vec<(Automation, String)>) {
+    for (automation, row_id) in fired {
+      self.schedule(database_id, &automation, &row_id).await;
+    }
+  }
+
+  async fn schedule(&self, database_id: &str, automation: &Automation, row_id: &str) {
+    let chain = AUTOMATION_CHAIN
+      .try_with(|chain| chain.clone())
+      .unwrap_or_default();
+    let skipped_reason = if chain.contains(&automation.id) {
+      Some("The automation was triggered by its own actions".to_string())
+    } else if chain.len() >= MAX_AUTOMATION_CHAIN_LEN {
+      Some(format!(
+        "The automation was triggered by a chain of more than {} automations",
+        MAX_AUTOMATION_CHAIN_LEN
+      ))
+    } else {
+      None
+    };
+    if let Some(message) = skipped_reason {
+      self.append_log(
+        database_id,
+        AutomationLog {
+          automation_id: automation.id.clone(),
+          automation_name: automation.name.clone(),
+          row_id: row_id.to_string(),
+          status: AutomationLogStatus::Skipped,
+          message,
+          timestamp: timestamp(),
+        },
+      );
+      return;
+    }
+
+    let task = AutomationTask {
+      database_id: database_id.to_string(),
+      automation_id: automation.id.clone(),
+      row_id: row_id.to_string(),
+      chain,
+    };
+    let task_id = self.task_scheduler.read().await.next_task_id();
+    let task = Task::new(
+      AUTOMATION_HANDLER_ID,
+      task_id,
+      TaskContent::Text(task.to_string()),
+      QualityOfService::Background,
+    );
+    self.task_scheduler.write().await.add_task(task);
+  }
+}
+
+/// Returns true if the row meets the condition of the automation. An automation without condition
+/// applies to every row.
+pub fn is_automation_condition_met(automation: &Automation, row: &Row, fields: &[Field]) -> bool {
+  let condition = match &automation.condition {
+    None => return true,
+    Some(condition) => condition,
+  };
+  let filter = match Filter::try_from(FilterPB::from(condition)) {
+    Ok(filter) => filter,
+    Err(err) => {
+      tracing::error!(
+        "Invalid condition of the automation {}: {:?}",
+        automation.id,
+        err
+      );
+      return false;
+    },
+  };
+  let field_by_field_id = fields
+    .iter()
+    .map(|field| (field.id.clone(), field.clone()))
+    .collect::<HashMap<String, Field>>();
+  let cell_cache = AnyTypeCache::<u64>::new();
+  apply_filter(row, &field_by_field_id, &cell_cache, &filter).unwrap_or(true)
+}
+
+/// Replaces `now` with the current time in the values of the date fields.
+pub fn resolve_automation_cells(
+  cell_by_field_id: HashMap<String, String>,
+  fields: &[Field],
+) -> HashMap<String, String> {
+  cell_by_field_id
+    .into_iter()
+    .map(|(field_id, value)| {
+      let is_date = fields.iter().any(|field| {
+        field.id == field_id && FieldType::from(field.field_type) == FieldType::DateTime
+      });
+      if is_date && value.trim().eq_ignore_ascii_case(AUTOMATION_NOW) {
+        (field_id, timestamp().to_string())
+      } else {
+        (field_id, value)
+      }
+    })
+    .collect()
+}
+
+fn automation_logs_key(database_id: &str) -> String {
+  format!("{}:{}", AUTOMATION_LOGS_KEY, database_id)
+}
+
+fn automation_fired_dates_key(database_id: &str) -> String {
+  format!("{}:{}", AUTOMATION_FIRED_DATES_KEY, database_id)
+}
+
+#[cfg(test)]
+mod tests {
+  use collab::core::origin::CollabOrigin;
+
+  use super::*;
+
+  fn automation(id: &str, created_at: i64) -> Automation {
+    Automation {
+      id: id.to_string(),
+      name: id.to_string(),
+      is_enabled: true,
+      trigger: AutomationTrigger::RowCreated,
+      condition: None,
+      actions: vec![],
+      created_at,
+    }
+  }
+
+  #[test]
+  fn automations_are_saved_as_separate_entries() {
+    let collab = Collab::new_with_origin(CollabOrigin::Empty, "database_id", vec![], false);
+    save_automation_in_collab(&collab, &automation("b", 200)).unwrap();
+    save_automation_in_collab(&collab, &automation("a", 100)).unwrap();
+
+    let mut changed = automation("b", 200);
+    changed.is_enabled = false;
+    save_automation_in_collab(&collab, &changed).unwrap();
+
+    let automations = get_automations_from_collab(&collab);
+    assert_eq!(automations.len(), 2);
+    assert_eq!(automations[0].id, "a");
+    assert_eq!(automations[1].id, "b");
+    assert!(!automations[1].is_enabled);
+
+    remove_automation_in_collab(&collab, "a");
+    let automations = get_automations_from_collab(&collab);
+    assert_eq!(automations.len(), 1);
+    assert_eq!(automations[0].id, "b");
+  }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{FieldType, FilterDataPB, FilterPB, FilterType};
+
+/// A rule of a database: when the trigger fires for a row and the row meets the condition, the
+/// actions are run one after another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Automation {
+  pub id: String,
+  pub name: String,
+  pub is_enabled: bool,
+  pub trigger: AutomationTrigger,
+  pub condition: Option<AutomationFilter>,
+  pub actions: Vec<AutomationAction>,
+  pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AutomationTrigger {
+  RowCreated,
+  /// Fires when the cell of the field changes from `from` to `to`. The values are compared with
+  /// the text of the cells, and None matches any value.
+  CellChanged {
+    field_id: String,
+    from: Option<String>,
+    to: Option<String>,
+  },
+  /// Fires when the date in the cell of the date field is reached.
+  DateReached {
+    field_id: String,
+  },
+}
+
+impl AutomationTrigger {
+  pub fn field_id(&self) -> Option<&str> {
+    match self {
+      AutomationTrigger::RowCreated => None,
+      AutomationTrigger::CellChanged { field_id, .. } => Some(field_id.as_str()),
+      AutomationTrigger::DateReached { field_id } => Some(field_id.as_str()),
+    }
+  }
+}
+
+/// The values of the cells are in the same format as the data of a new row, except that `now`
+/// is the current time for the date fields and an empty value clears the cell.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AutomationAction {
+  UpdateCell {
+    field_id: String,
+    value: String,
+  },
+  CreateRow {
+    database_id: String,
+    cells: HashMap<String, String>,
+  },
+  MoveToGroup {
+    view_id: String,
+    group_id: String,
+  },
+  SetRelation {
+    field_id: String,
+    row_ids: Vec<String>,
+  },
+  SendReminder {
+    message: String,
+  },
+}
+
+/// The serializable form of the filter tree that is used as the condition of an automation.
+/// The data of a data filter is the protobuf of the filter of its field type.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationFilter {
+  pub id: String,
+  pub filter_type: u8,
+  pub children: Vec<AutomationFilter>,
+  pub data: Option<AutomationFilterData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationFilterData {
+  pub field_id: String,
+  pub field_type: i64,
+  pub data: Vec<u8>,
+}
+
+impl From<&FilterPB> for AutomationFilter {
+  fn from(filter: &FilterPB) -> Self {
+    Self {
+      id: filter.id.clone(),
+      filter_type: filter.filter_type as u8,
+      children: filter.children.iter().map(AutomationFilter::from).collect(),
+      data: filter.data.as_ref().map(|data| AutomationFilterData {
+        field_id: data.field_id.clone(),
+        field_type: data.field_type.into(),
+        data: data.data.clone(),
+      }),
+    }
+  }
+}
+
+impl From<&AutomationFilter> for FilterPB {
+  fn from(filter: &AutomationFilter) -> Self {
+    let filter_type = match filter.filter_type {
+      1 => FilterType::And,
+      2 => FilterType::Or,
+      _ => FilterType::Data,
+    };
+    Self {
+      id: filter.id.clone(),
+      filter_type,
+      children: filter.children.iter().map(FilterPB::from).collect(),
+      data: filter.data.as_ref().map(|data| FilterDataPB {
+        field_id: data.field_id.clone(),
+        field_type: FieldType::from(data.field_type),
+        data: data.data.clone(),
+      }),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AutomationLogStatus {
+  Succeeded,
+  Failed,
+  Skipped,
+}
+
+/// An entry of the execution log of the automations of a database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationLog {
+  pub automation_id: String,
+  pub automation_name: String,
+  pub row_id: String,
+  pub status: AutomationLogStatus,
+  pub message: String,
+  pub timestamp: i64,
+}
+
+/// The content of the automation task that is scheduled in the task dispatcher. `chain` is the
+/// ids of the automations whose actions led to this task, it's used to break loops.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutomationTask {
+  pub database_id: String,
+  pub automation_id: String,
+  pub row_id: String,
+  pub chain: Vec<String>,
+}
+
+impl ToString for AutomationTask {
+  fn to_string(&self) -> String {
+    serde_json::to_string(self).unwrap()
+  }
+}
+
+impl FromStr for AutomationTask {
+  type Err = serde_json::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    serde_json::from_str(s)
+  }
+}
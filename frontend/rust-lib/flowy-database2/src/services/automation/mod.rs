@@ -0,0 +1,7 @@
+mod controller;
+mod entities;
+mod task;
+
+pub use controller::*;
+pub use entities::*;
+pub(crate) use task::*;
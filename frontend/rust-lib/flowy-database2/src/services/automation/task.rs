@@ -0,0 +1,41 @@
+use std::str::FromStr;
+
+use lib_infra::future::BoxResultFuture;
+use lib_infra::priority_task::{TaskContent, TaskHandler};
+use tokio::sync::mpsc;
+
+use crate::services::automation::{AutomationTask, AUTOMATION_HANDLER_ID};
+
+/// Hands the automation tasks over to the receiver of the channel. The actions of the
+/// automations update the database, which schedules other tasks, so they can't run while the task
+/// dispatcher is processing the task.
+pub struct AutomationTaskHandler {
+  sender: mpsc::UnboundedSender<AutomationTask>,
+}
+
+impl AutomationTaskHandler {
+  pub fn new(sender: mpsc::UnboundedSender<AutomationTask>) -> Self {
+    Self { sender }
+  }
+}
+
+impl TaskHandler for AutomationTaskHandler {
+  fn handler_id(&self) -> &str {
+    AUTOMATION_HANDLER_ID
+  }
+
+  fn handler_name(&self) -> &str {
+    "AutomationTaskHandler"
+  }
+
+  fn run(&self, content: TaskContent) -> BoxResultFuture<(), anyhow::Error> {
+    let sender = self.sender.clone();
+    Box::pin(async move {
+      if let TaskContent::Text(predicate) = content {
+        let task = AutomationTask::from_str(&predicate)?;
+        sender.send(task)?;
+      }
+      Ok(())
+    })
+  }
+}
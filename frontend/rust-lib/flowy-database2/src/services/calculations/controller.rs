@@ -21,6 +21,8 @@ use super::{Calculation, CalculationChangeset, CalculationsService};
 
 pub trait CalculationsDelegate: Send + Sync + 'static {
   fn get_cells_for_field(&self, view_id: &str, field_id: &str) -> Fut<Vec<Arc<RowCell>>>;
+  /// Returns the number of comments, including the replies, on the rows of the view.
+  fn count_row_comments(&self, view_id: &str) -> Fut<usize>;
   fn get_field(&self, field_id: &str) -> Option<Field>;
   fn get_calculation(&self, view_id: &str, field_id: &str) -> Fut<Option<Arc<Calculation>>>;
   fn get_all_calculations(&self, view_id: &str) -> Fut<Arc<Vec<Arc<Calculation>>>>;
@@ -102,6 +104,7 @@ impl CalculationsController {
       CalculationEvent::RowChanged(row) => self.handle_row_changed(row).await,
       CalculationEvent::CellUpdated(field_id) => self.handle_cell_changed(field_id).await,
       CalculationEvent::FieldDeleted(field_id) => self.handle_field_deleted(field_id).await,
+      CalculationEvent::CommentsChanged => self.handle_comments_changed().await,
       CalculationEvent::FieldTypeChanged(field_id, new_field_type) => {
         self
           .handle_field_type_changed(field_id, new_field_type)
@@ -268,17 +271,61 @@ impl CalculationsController {
     }
   }
 
+  pub async fn did_receive_comments_changed(&self) {
+    self
+      .gen_task(
+        CalculationEvent::CommentsChanged,
+        QualityOfService::UserInteractive,
+      )
+      .await
+  }
+
+  async fn handle_comments_changed(&self) {
+    let calculations = self.delegate.get_all_calculations(&self.view_id).await;
+    let mut updates = vec![];
+    for calculation in calculations.iter() {
+      if CalculationType::from(calculation.calculation_type) != CalculationType::CountComments {
+        continue;
+      }
+      if let Some(update) = self.get_updated_calculation(calculation.clone()).await {
+        updates.push(CalculationPB::from(&update));
+        self.delegate.update_calculation(&self.view_id, update);
+      }
+    }
+
+    if !updates.is_empty() {
+      let notification = CalculationChangesetNotificationPB::from_update(&self.view_id, updates);
+      let _ = self
+        .notifier
+        .send(DatabaseViewChanged::CalculationValueNotification(
+          notification,
+        ));
+    }
+  }
+
+  /// Returns the value of the calculation. The comments of the rows are counted instead of the
+  /// cells of the field.
+  async fn calculate(&self, field: &Field, calculation_type: i64) -> String {
+    match CalculationType::from(calculation_type) {
+      CalculationType::CountComments => {
+        let count = self.delegate.count_row_comments(&self.view_id).await;
+        format!("{}", count)
+      },
+      _ => {
+        let row_cells = self
+          .delegate
+          .get_cells_for_field(&self.view_id, &field.id)
+          .await;
+        self
+          .calculations_service
+          .calculate(field, calculation_type, row_cells)
+      },
+    }
+  }
+
   async fn get_updated_calculation(&self, calculation: Arc<Calculation>) -> Option<Calculation> {
-    let field_cells = self
-      .delegate
-      .get_cells_for_field(&self.view_id, &calculation.field_id)
-      .await;
     let field = self.delegate.get_field(&calculation.field_id)?;
-
-    let value =
-      self
-        .calculations_service
-        .calculate(&field, calculation.calculation_type, field_cells);
+    let value = self.calculate(&field, calculation.calculation_type).await;
 
     if value != calculation.value {
       return Some(calculation.with_value(value));
@@ -294,16 +341,9 @@ impl CalculationsController {
     let mut notification: Option<CalculationChangesetNotificationPB> = None;
 
     if let Some(insert) = &changeset.insert_calculation {
-      let row_cells: Vec<Arc<RowCell>> = self
-        .delegate
-        .get_cells_for_field(&self.view_id, &insert.field_id)
-        .await;
-
       let field = self.delegate.get_field(&insert.field_id)?;
 
-      let value = self
-        .calculations_service
-        .calculate(&field, insert.calculation_type, row_cells);
+      let value = self.calculate(&field, insert.calculation_type).await;
 
       notification = Some(CalculationChangesetNotificationPB::from_insert(
         &self.view_id,
@@ -348,6 +388,7 @@ enum CalculationEvent {
   CellUpdated(String),
   FieldTypeChanged(String, FieldType),
   FieldDeleted(String),
+  CommentsChanged,
 }
 
 impl ToString for CalculationEvent {
@@ -30,6 +30,9 @@ impl CalculationsService {
       CalculationType::Count => self.calculate_count(row_cells),
       CalculationType::CountEmpty => self.calculate_count_empty(field, row_cells),
       CalculationType::CountNonEmpty => self.calculate_count_non_empty(field, row_cells),
+      // The comments aren't cells, they're counted by the
+      // [crate::services::calculations::CalculationsController].
+      CalculationType::CountComments => String::new(),
     }
   }
 
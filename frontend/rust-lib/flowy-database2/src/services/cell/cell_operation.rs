@@ -265,6 +265,12 @@ impl<'a> CellBuilder<'a> {
           FieldType::Translate => {
             cells.insert(field_id, insert_text_cell(cell_str, field));
           },
+          FieldType::Formula | FieldType::Rollup => {
+            tracing::warn!(
+              "Shouldn't insert cell data to cell whose field type is {}",
+              field_type.default_name()
+            );
+          },
         }
       }
     }
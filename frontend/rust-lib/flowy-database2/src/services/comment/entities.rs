@@ -0,0 +1,340 @@
+use collab::preclude::{Collab, Map, MapRefExtension, Observable, Subscription};
+use collab_database::database::MutexDatabase;
+use collab_database::rows::RowId;
+use flowy_error::{internal_error, FlowyResult};
+use serde::{Deserialize, Serialize};
+
+use crate::services::field::{delta_mentioned_person_ids, parse_delta, text_to_delta_json};
+
+/// The comments of a row are stored in a map of the row's collab, so they are kept apart from
+/// the cells and synced with the row. Every comment and every reply is a separate entry of the
+/// map, by the id of the comment, so the comments that are written on different devices at the
+/// same time are merged instead of overwriting each other.
+pub const ROW_COMMENTS: &str = "row_comments";
+
+/// Runs `f` with the collab of the row. Returns None if the row doesn't exist.
+pub fn with_row_collab<T>(
+  database: &MutexDatabase,
+  row_id: &RowId,
+  f: impl FnOnce(&Collab) -> T,
+) -> Option<T> {
+  let database_row = database.lock().get_database_row(row_id)?;
+  let database_row = database_row.lock();
+  let collab = database_row.get_collab().lock();
+  Some(f(&collab))
+}
+
+pub fn get_row_comments_from_collab(collab: &Collab) -> RowComments {
+  let txn = collab.transact();
+  let entries = collab
+    .get_map_with_txn(&txn, vec![ROW_COMMENTS])
+    .map(|map| {
+      map
+        .iter(&txn)
+        .filter_map(|(_, value)| {
+          let json = value.to_string(&txn);
+          serde_json::from_str::<RowCommentEntry>(&json)
+            .map_err(|err| tracing::error!("Failed to parse the comment of the row: {}", err))
+            .ok()
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+  RowComments::from_entries(entries)
+}
+
+/// Writes the comments that differ between `old` and `new`, and removes the ones that are not
+/// in `new` anymore. The other entries are left untouched.
+pub fn save_row_comments_in_collab(
+  collab: &Collab,
+  old: &RowComments,
+  new: &RowComments,
+) -> FlowyResult<()> {
+  let old_entries = old.entries();
+  let new_entries = new.entries();
+  let mut inserted = vec![];
+  for entry in new_entries.iter() {
+    if !old_entries.contains(entry) {
+      let json = serde_json::to_string(entry).map_err(internal_error)?;
+      inserted.push((entry.comment.id.clone(), json));
+    }
+  }
+  let removed = old_entries
+    .iter()
+    .filter(|old_entry| {
+      !new_entries
+        .iter()
+        .any(|entry| entry.comment.id == old_entry.comment.id)
+    })
+    .map(|entry| entry.comment.id.clone())
+    .collect::<Vec<String>>();
+  if inserted.is_empty() && removed.is_empty() {
+    return Ok(());
+  }
+
+  collab.with_origin_transact_mut(|txn| {
+    let map = collab.insert_map_with_txn_if_not_exist(txn, ROW_COMMENTS);
+    for (comment_id, json) in inserted {
+      map.insert_str_with_txn(txn, &comment_id, json);
+    }
+    for comment_id in removed {
+      map.remove(txn, &comment_id);
+    }
+  });
+  Ok(())
+}
+
+/// Calls `f` whenever the comments of the row change, until the subscription is dropped.
+pub fn observe_row_comments_in_collab<F>(collab: &Collab, f: F) -> Subscription
+where
+  F: Fn() + Send + Sync + 'static,
+{
+  let map = collab
+    .with_origin_transact_mut(|txn| collab.insert_map_with_txn_if_not_exist(txn, ROW_COMMENTS));
+  map.observe(move |_, _| f())
+}
+
+/// The stored form of a comment. The replies are stored as separate entries that point to the
+/// comment that starts their thread.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct RowCommentEntry {
+  #[serde(flatten)]
+  comment: RowComment,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  thread_id: Option<String>,
+}
+
+/// A comment on a row. A comment that starts a thread can have replies, a reply never has.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RowComment {
+  pub id: String,
+  pub author_uid: i64,
+  /// The json string of a delta, see [crate::services::field::TextDeltaOp].
+  pub content: String,
+  pub created_at: i64,
+  pub updated_at: i64,
+  #[serde(default)]
+  pub is_resolved: bool,
+  /// The replies are stored as separate entries, see [ROW_COMMENTS].
+  #[serde(skip)]
+  pub replies: Vec<RowComment>,
+}
+
+impl RowComment {
+  /// Creates a comment. The content is either a delta or a plain text that is converted into a
+  /// delta.
+  pub fn new(author_uid: i64, content: &str, timestamp: i64) -> Self {
+    Self {
+      id: nanoid::nanoid!(10),
+      author_uid,
+      content: to_delta_json(content),
+      created_at: timestamp,
+      updated_at: timestamp,
+      is_resolved: false,
+      replies: vec![],
+    }
+  }
+
+  pub fn set_content(&mut self, content: &str, timestamp: i64) {
+    self.content = to_delta_json(content);
+    self.updated_at = timestamp;
+  }
+
+  pub fn mentions(&self, uid: i64) -> bool {
+    let uid = uid.to_string();
+    parse_delta(&self.content)
+      .map(|delta| delta_mentioned_person_ids(&delta).contains(&uid))
+      .unwrap_or(false)
+  }
+}
+
+fn to_delta_json(content: &str) -> String {
+  match parse_delta(content) {
+    Some(_) => content.to_string(),
+    None => text_to_delta_json(content),
+  }
+}
+
+/// The comment threads of a row, ordered by creation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowComments(pub Vec<RowComment>);
+
+impl RowComments {
+  /// The number of comments, including the replies.
+  pub fn count(&self) -> usize {
+    self.0.iter().map(|comment| 1 + comment.replies.len()).sum()
+  }
+
+  /// Returns the comment or the reply with the id.
+  pub fn get(&self, comment_id: &str) -> Option<&RowComment> {
+    self.iter_all().find(|comment| comment.id == comment_id)
+  }
+
+  pub fn get_mut(&mut self, comment_id: &str) -> Option<&mut RowComment> {
+    self.0.iter_mut().find_map(|comment| {
+      if comment.id == comment_id {
+        Some(comment)
+      } else {
+        comment
+          .replies
+          .iter_mut()
+          .find(|reply| reply.id == comment_id)
+      }
+    })
+  }
+
+  /// Returns the thread that contains the comment, which is the comment itself if it's not a
+  /// reply.
+  pub fn get_thread_mut(&mut self, comment_id: &str) -> Option<&mut RowComment> {
+    self.0.iter_mut().find(|comment| {
+      comment.id == comment_id || comment.replies.iter().any(|reply| reply.id == comment_id)
+    })
+  }
+
+  /// Removes the comment or the reply with the id. Removing a comment removes its replies.
+  pub fn remove(&mut self, comment_id: &str) -> Option<RowComment> {
+    if let Some(index) = self.0.iter().position(|comment| comment.id == comment_id) {
+      return Some(self.0.remove(index));
+    }
+    self.0.iter_mut().find_map(|comment| {
+      let index = comment
+        .replies
+        .iter()
+        .position(|reply| reply.id == comment_id)?;
+      Some(comment.replies.remove(index))
+    })
+  }
+
+  /// Builds the threads from the stored comments. The replies whose thread was deleted are
+  /// dropped.
+  fn from_entries(entries: Vec<RowCommentEntry>) -> Self {
+    let (mut threads, replies): (Vec<_>, Vec<_>) = entries
+      .into_iter()
+      .partition(|entry| entry.thread_id.is_none());
+    threads.sort_by(|a, b| {
+      (a.comment.created_at, &a.comment.id).cmp(&(b.comment.created_at, &b.comment.id))
+    });
+    let mut threads = threads
+      .into_iter()
+      .map(|entry| entry.comment)
+      .collect::<Vec<RowComment>>();
+    for reply in replies {
+      let thread = threads
+        .iter_mut()
+        .find(|thread| Some(&thread.id) == reply.thread_id.as_ref());
+      if let Some(thread) = thread {
+        thread.replies.push(reply.comment);
+      }
+    }
+    for thread in threads.iter_mut() {
+      thread
+        .replies
+        .sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+    }
+    Self(threads)
+  }
+
+  /// The comments and the replies in their stored form.
+  fn entries(&self) -> Vec<RowCommentEntry> {
+    self
+      .0
+      .iter()
+      .flat_map(|thread| {
+        let entry = RowCommentEntry {
+          comment: RowComment {
+            replies: vec![],
+            ..thread.clone()
+          },
+          thread_id: None,
+        };
+        std::iter::once(entry).chain(thread.replies.iter().map(|reply| RowCommentEntry {
+          comment: reply.clone(),
+          thread_id: Some(thread.id.clone()),
+        }))
+      })
+      .collect()
+  }
+
+  /// Iterates over the comments and their replies.
+  pub fn iter_all(&self) -> impl Iterator<Item = &RowComment> {
+    self
+      .0
+      .iter()
+      .flat_map(|comment| std::iter::once(comment).chain(comment.replies.iter()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use collab::core::origin::CollabOrigin;
+
+  use super::*;
+
+  #[test]
+  fn row_comments_round_trip_through_collab() {
+    let collab = Collab::new_with_origin(CollabOrigin::Empty, "row_id", vec![], false);
+    let mut thread = RowComment::new(1, "Looks good", 100);
+    thread.replies.push(RowComment::new(2, "Thanks", 200));
+    let comments = RowComments(vec![thread.clone(), RowComment::new(1, "Done?", 300)]);
+    save_row_comments_in_collab(&collab, &RowComments::default(), &comments).unwrap();
+
+    let saved_comments = get_row_comments_from_collab(&collab);
+    assert_eq!(saved_comments, comments);
+    assert_eq!(saved_comments.count(), 3);
+    assert_eq!(saved_comments.0[0].content, r#"[{"insert":"Looks good"}]"#);
+  }
+
+  #[test]
+  fn concurrent_row_comments_are_merged() {
+    let collab = Collab::new_with_origin(CollabOrigin::Empty, "row_id", vec![], false);
+    let thread = RowComment::new(1, "Question", 100);
+    let comments = RowComments(vec![thread.clone()]);
+    save_row_comments_in_collab(&collab, &RowComments::default(), &comments).unwrap();
+
+    // Two devices add a comment to the comments they have read before the other one's comment
+    // is synced. Only the added comments are written, so neither comment is lost.
+    let mut first = comments.clone();
+    first.0[0].replies.push(RowComment::new(2, "Answer", 200));
+    let mut second = comments.clone();
+    second.0.push(RowComment::new(3, "Another question", 300));
+    save_row_comments_in_collab(&collab, &comments, &first).unwrap();
+    save_row_comments_in_collab(&collab, &comments, &second).unwrap();
+
+    let saved_comments = get_row_comments_from_collab(&collab);
+    assert_eq!(saved_comments.count(), 3);
+    assert_eq!(saved_comments.0[0].replies, first.0[0].replies);
+    assert_eq!(saved_comments.0[1], second.0[1]);
+
+    save_row_comments_in_collab(&collab, &saved_comments, &RowComments::default()).unwrap();
+    assert_eq!(get_row_comments_from_collab(&collab).count(), 0);
+  }
+
+  #[test]
+  fn remove_reply_and_thread() {
+    let mut thread = RowComment::new(1, "Question", 100);
+    let reply = RowComment::new(2, "Answer", 200);
+    thread.replies.push(reply.clone());
+    let mut comments = RowComments(vec![thread.clone()]);
+
+    assert_eq!(
+      comments
+        .get_thread_mut(&reply.id)
+        .map(|thread| thread.id.clone()),
+      Some(thread.id.clone())
+    );
+    assert_eq!(comments.remove(&reply.id), Some(reply));
+    assert_eq!(comments.count(), 1);
+    assert!(comments.remove(&thread.id).is_some());
+    assert_eq!(comments.count(), 0);
+  }
+
+  #[test]
+  fn comment_mentions_person() {
+    let content = r#"[{"insert":"Ping "},{"insert":"$","attributes":{"mention":{"type":"person","person_id":"42"}}}]"#;
+    let comment = RowComment::new(1, content, 100);
+    assert_eq!(comment.content, content);
+    assert!(comment.mentions(42));
+    assert!(!comment.mentions(1));
+    assert!(!RowComment::new(1, "42", 100).mentions(42));
+  }
+}
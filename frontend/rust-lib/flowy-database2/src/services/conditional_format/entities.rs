@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::entities::{FieldType, FilterDataPB};
+use crate::services::filter::{Filter, FilterInner};
+
+/// A conditional formatting rule of a field in a view. The cell of the field, or the whole row,
+/// gets the colors of the rule when the cell meets the condition.
+///
+/// The rules are stored in the field settings of the view, see
+/// [crate::services::field_settings::FieldSettings].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConditionalFormat {
+  pub id: String,
+  pub field_id: String,
+  /// The field type that the condition was written for. The rule matches no row after the type of
+  /// the field changes.
+  pub field_type: i64,
+  /// The protobuf of the filter of the field type, e.g. the
+  /// [crate::entities::NumberFilterPB] of a number field.
+  pub condition: Vec<u8>,
+  pub target: ConditionalFormatTarget,
+  pub background_color: Option<String>,
+  pub text_color: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionalFormatTarget {
+  Cell,
+  Row,
+}
+
+impl ConditionalFormat {
+  pub fn to_filter(&self) -> FlowyResult<Filter> {
+    let data = FilterDataPB {
+      field_id: self.field_id.clone(),
+      field_type: FieldType::from(self.field_type),
+      data: self.condition.clone(),
+    };
+    let inner = FilterInner::try_from(data).map_err(FlowyError::from)?;
+    Ok(Filter {
+      id: self.id.clone(),
+      inner,
+    })
+  }
+}
+
+/// The colors of a cell, or of the whole row if `field_id` is None.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellFormat {
+  pub field_id: Option<String>,
+  pub background_color: Option<String>,
+  pub text_color: Option<String>,
+}
+
+impl From<&ConditionalFormat> for CellFormat {
+  fn from(format: &ConditionalFormat) -> Self {
+    let field_id = match format.target {
+      ConditionalFormatTarget::Cell => Some(format.field_id.clone()),
+      ConditionalFormatTarget::Row => None,
+    };
+    Self {
+      field_id,
+      background_color: format.background_color.clone(),
+      text_color: format.text_color.clone(),
+    }
+  }
+}
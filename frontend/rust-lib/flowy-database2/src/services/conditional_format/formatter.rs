@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use collab_database::fields::Field;
+use collab_database::rows::{Row, RowDetail};
+
+use crate::entities::{CellFormatPB, RowMetaPB};
+use crate::services::cell::CellCache;
+use crate::services::conditional_format::{CellFormat, ConditionalFormat};
+use crate::services::filter::{apply_filter, Filter};
+
+/// Evaluates the conditional formatting rules of a view against its rows.
+///
+/// The rules are checked in order: the first rule that a row meets gives the colors of the row,
+/// and the first cell rule of each field that the row meets gives the colors of that cell.
+pub struct ConditionalFormatter {
+  rules: Vec<(ConditionalFormat, Filter)>,
+  field_by_field_id: HashMap<String, Field>,
+  cell_cache: CellCache,
+}
+
+impl ConditionalFormatter {
+  pub fn new(formats: Vec<ConditionalFormat>, fields: Vec<Field>, cell_cache: CellCache) -> Self {
+    let rules = formats
+      .into_iter()
+      .filter_map(|format| match format.to_filter() {
+        Ok(filter) => Some((format, filter)),
+        Err(err) => {
+          tracing::error!(
+            "Invalid condition of the conditional format {}: {}",
+            format.id,
+            err
+          );
+          None
+        },
+      })
+      .collect();
+    let field_by_field_id = fields
+      .into_iter()
+      .map(|field| (field.id.clone(), field))
+      .collect();
+    Self {
+      rules,
+      field_by_field_id,
+      cell_cache,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.rules.is_empty()
+  }
+
+  pub fn format_row(&self, row: &Row) -> Vec<CellFormat> {
+    let mut formats: Vec<CellFormat> = vec![];
+    for (format, filter) in self.rules.iter() {
+      let cell_format = CellFormat::from(format);
+      if formats
+        .iter()
+        .any(|other| other.field_id == cell_format.field_id)
+      {
+        continue;
+      }
+
+      let is_met = apply_filter(row, &self.field_by_field_id, &self.cell_cache, filter);
+      if is_met == Some(true) {
+        formats.push(cell_format);
+      }
+    }
+    formats
+  }
+
+  /// Returns the meta of the row together with its formats.
+  pub fn format_row_meta(&self, row_detail: &RowDetail) -> RowMetaPB {
+    let formats = self
+      .format_row(&row_detail.row)
+      .into_iter()
+      .map(CellFormatPB::from)
+      .collect();
+    RowMetaPB::from(row_detail).with_formats(formats)
+  }
+
+  /// Returns true if the formats of the row may change when the cell of the field changes.
+  pub fn depends_on_field(&self, field_id: &str) -> bool {
+    self
+      .rules
+      .iter()
+      .any(|(format, _)| format.field_id == field_id)
+  }
+}
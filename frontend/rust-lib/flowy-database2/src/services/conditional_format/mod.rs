@@ -0,0 +1,5 @@
+mod entities;
+mod formatter;
+
+pub use entities::*;
+pub use formatter::*;
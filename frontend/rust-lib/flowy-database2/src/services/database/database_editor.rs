@@ -1,27 +1,54 @@
 use crate::entities::*;
 use crate::notification::{send_notification, DatabaseNotification};
+use crate::services::automation::{
+  get_automations_from_collab, remove_automation_in_collab, save_automation_in_collab, Automation,
+  AutomationController,
+};
 use crate::services::calculations::Calculation;
-use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, CellCache};
+use crate::services::cell::{
+  apply_cell_changeset, get_cell_protobuf, insert_checkbox_cell, stringify_cell, CellCache,
+};
+use crate::services::comment::{
+  get_row_comments_from_collab, save_row_comments_in_collab, with_row_collab, RowComment,
+  RowComments,
+};
+use crate::services::conditional_format::ConditionalFormat;
 use crate::services::database::database_observe::*;
-use crate::services::database::util::database_view_setting_pb_from_view;
+use crate::services::database::entities::ReminderDateChange;
+use crate::services::database::row_indexer::DatabaseRowIndexer;
+use crate::services::database::util::{
+  database_layout_pb_from_view, database_view_setting_pb_from_view,
+};
 use crate::services::database_view::{
   DatabaseViewChanged, DatabaseViewEditor, DatabaseViewOperation, DatabaseViews, EditorByViewId,
 };
+use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
+use crate::services::field::translate_type_option::translate::TranslateTypeOption;
 use crate::services::field::{
-  default_type_option_data_from_type, select_type_option_from_field, transform_type_option,
-  type_option_data_from_pb, ChecklistCellChangeset, RelationTypeOption, SelectOptionCellChangeset,
-  StringCellData, TimestampCellData, TimestampCellDataWrapper, TypeOptionCellDataHandler,
-  TypeOptionCellExt,
+  default_type_option_data_from_type, evaluate_formula_for_row, find_formula_cycle,
+  formula_fields_depending_on, rename_prop_references, select_type_option_from_field,
+  transform_type_option, type_option_data_from_pb, ChecklistCellChangeset, DateCellChangeset,
+  DateCellData, DateTypeOption, FormulaTypeOption, RelationCellChangeset, RelationCellData,
+  RelationTypeOption, RichTextCellData, RollupTypeOption, SelectOptionCellChangeset,
+  TimestampCellData, TimestampCellDataWrapper, TypeOptionCellDataHandler, TypeOptionCellExt,
+  URLCellData,
 };
 use crate::services::field_settings::{default_field_settings_by_layout_map, FieldSettings};
 use crate::services::filter::{Filter, FilterChangeset};
 use crate::services::group::{default_group_setting, GroupChangeset, GroupSetting, RowChangeset};
+use crate::services::setting::GridLayout;
 use crate::services::share::csv::{CSVExport, CSVFormat};
+use crate::services::share::html::{BoardColumnHtml, DatabaseHtml};
+use crate::services::share::url::replace_publish_name_in_url;
 use crate::services::sort::Sort;
+use crate::services::validation::{
+  parse_validation_pattern, CellViolation, FieldValidation, FieldValidator, ValidationRule,
+  FIELD_VALIDATION,
+};
 use crate::utils::cache::AnyTypeCache;
-use collab_database::database::MutexDatabase;
+use collab_database::database::{DatabaseData, MutexDatabase};
 use collab_database::fields::{Field, TypeOptionData};
-use collab_database::rows::{Cell, Cells, Row, RowCell, RowDetail, RowId};
+use collab_database::rows::{Cell, Cells, Row, RowCell, RowChange, RowDetail, RowId};
 use collab_database::views::{
   DatabaseLayout, DatabaseView, FilterMap, LayoutSetting, OrderObjectPosition,
 };
@@ -44,12 +71,23 @@ pub struct DatabaseEditor {
   #[allow(dead_code)]
   /// Used to send notification to the frontend.
   notification_sender: Arc<DebounceNotificationSender>,
+  row_indexer: DatabaseRowIndexer,
+  automations: Arc<AutomationController>,
+  /// The current user, who is the author of the comments added with this editor.
+  uid: i64,
+  reminder_date_tx: broadcast::Sender<ReminderDateChange>,
+  /// Keeps the comments of the rows observed while the database is open.
+  row_comments_observer: Arc<RowCommentsObserver>,
 }
 
 impl DatabaseEditor {
   pub async fn new(
     database: Arc<MutexDatabase>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
+    row_indexer: DatabaseRowIndexer,
+    automations: Arc<AutomationController>,
+    uid: i64,
+    reminder_date_tx: broadcast::Sender<ReminderDateChange>,
   ) -> FlowyResult<Self> {
     let notification_sender = Arc::new(DebounceNotificationSender::new(200));
     let cell_cache = AnyTypeCache::<u64>::new();
@@ -80,12 +118,23 @@ impl DatabaseEditor {
       )
       .await?,
     );
+    let row_comments_observer = Arc::new(RowCommentsObserver::new(
+      &database_id,
+      &database,
+      &database_views,
+      uid,
+    ));
 
     Ok(Self {
       database,
       cell_cache,
       database_views,
       notification_sender,
+      row_indexer,
+      automations,
+      uid,
+      reminder_date_tx,
+      row_comments_observer,
     })
   }
 
@@ -119,7 +168,24 @@ impl DatabaseEditor {
     layout_type: DatabaseLayout,
   ) -> FlowyResult<()> {
     let view_editor = self.database_views.get_view_editor(view_id).await?;
-    view_editor.v_update_layout_type(layout_type).await?;
+    view_editor
+      .v_update_layout_type(layout_type, GridLayout::default())
+      .await?;
+
+    Ok(())
+  }
+
+  /// Updates the layout of the view to the grid layout that displays the rows with `grid_layout`,
+  /// e.g. as a gallery.
+  pub async fn update_view_grid_layout(
+    &self,
+    view_id: &str,
+    grid_layout: GridLayout,
+  ) -> FlowyResult<()> {
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    view_editor
+      .v_update_layout_type(DatabaseLayout::Grid, grid_layout)
+      .await?;
 
     Ok(())
   }
@@ -132,6 +198,11 @@ impl DatabaseEditor {
     Ok(view_editor.notifier.subscribe())
   }
 
+  /// Subscribes to the changes of the rows of the database, including the changes made locally.
+  pub fn subscribe_row_change(&self) -> broadcast::Receiver<RowChange> {
+    self.database.lock().subscribe_row_change()
+  }
+
   pub fn get_field(&self, field_id: &str) -> Option<Field> {
     self.database.lock().fields.get_field(field_id)
   }
@@ -298,14 +369,72 @@ impl DatabaseEditor {
   }
 
   pub async fn update_field(&self, params: FieldChangesetParams) -> FlowyResult<()> {
+    if let Some(pattern) = params
+      .validation
+      .as_ref()
+      .and_then(|validation| validation.pattern.as_deref())
+    {
+      parse_validation_pattern(pattern)?;
+    }
+    let old_name = self.get_field(&params.field_id).map(|field| field.name);
+    let new_name = params.name.clone();
     self
       .database
       .lock()
       .fields
       .update_field(&params.field_id, |update| {
         update.set_name_if_not_none(params.name);
+        if let Some(validation) = &params.validation {
+          update.update_type_options(|type_options_update| {
+            type_options_update.insert(FIELD_VALIDATION, validation.into());
+          });
+        }
       });
     notify_did_update_database_field(&self.database, &params.field_id)?;
+
+    if let (Some(old_name), Some(new_name)) = (old_name, new_name) {
+      if old_name != new_name {
+        self.rename_formula_references(&old_name, &new_name)?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Rewrites the `prop("...")` references of all formula fields after a field was renamed, so
+  /// that the formulas keep pointing at the same field.
+  fn rename_formula_references(&self, old_name: &str, new_name: &str) -> FlowyResult<()> {
+    let formula_fields = self
+      .database
+      .lock()
+      .get_fields(None)
+      .into_iter()
+      .filter(|field| FieldType::from(field.field_type).is_formula())
+      .collect::<Vec<Field>>();
+
+    for field in formula_fields {
+      let type_option = match field.get_type_option::<FormulaTypeOption>(FieldType::Formula) {
+        Some(type_option) => type_option,
+        None => continue,
+      };
+      let expression = rename_prop_references(&type_option.expression, old_name, new_name);
+      if expression == type_option.expression {
+        continue;
+      }
+
+      self
+        .database
+        .lock()
+        .fields
+        .update_field(&field.id, |update| {
+          update.update_type_options(|type_options_update| {
+            type_options_update.insert(
+              &FieldType::Formula.to_string(),
+              FormulaTypeOption { expression }.into(),
+            );
+          });
+        });
+      notify_did_update_database_field(&self.database, &field.id)?;
+    }
     Ok(())
   }
 
@@ -325,10 +454,12 @@ impl DatabaseEditor {
       ));
     }
 
-    let database_id = {
+    let (database_id, dependent_formula_fields) = {
       let database = self.database.lock();
+      let dependent_formula_fields =
+        formula_fields_depending_on(&database.get_fields(None), field_id);
       database.delete_field(field_id);
-      database.get_database_id()
+      (database.get_database_id(), dependent_formula_fields)
     };
     let notified_changeset =
       DatabaseFieldChangesetPB::delete(&database_id, vec![FieldIdPB::from(field_id)]);
@@ -338,6 +469,10 @@ impl DatabaseEditor {
       view.v_did_delete_field(field_id).await;
     }
 
+    self
+      .update_formula_cells_of_all_rows(dependent_formula_fields)
+      .await;
+
     Ok(())
   }
 
@@ -369,16 +504,47 @@ impl DatabaseEditor {
   /// Do nothing if the [TypeOptionData] is empty.
   pub async fn update_field_type_option(
     &self,
-    _field_id: &str,
+    field_id: &str,
     type_option_data: TypeOptionData,
     old_field: Field,
   ) -> FlowyResult<()> {
+    let is_formula = FieldType::from(old_field.field_type).is_formula();
+    if is_formula {
+      self.check_formula_cycle(field_id, type_option_data.clone())?;
+    }
+
     let view_editors = self.database_views.editors().await;
     update_field_type_option_fn(&self.database, &view_editors, type_option_data, old_field).await?;
 
+    if is_formula {
+      self.update_formula_cells_of_field(field_id).await;
+    }
     Ok(())
   }
 
+  /// Returns an error if applying the formula `type_option_data` to the field with `field_id`
+  /// would make the formula fields reference each other in a cycle.
+  fn check_formula_cycle(
+    &self,
+    field_id: &str,
+    type_option_data: TypeOptionData,
+  ) -> FlowyResult<()> {
+    let mut fields = self.database.lock().get_fields(None);
+    if let Some(field) = fields.iter_mut().find(|field| field.id == field_id) {
+      field
+        .type_options
+        .insert(FieldType::Formula.to_string(), type_option_data);
+    }
+
+    match find_formula_cycle(&fields) {
+      None => Ok(()),
+      Some(field_name) => Err(FlowyError::new(
+        ErrorCode::InvalidParams,
+        format!("The formula of {} references itself", field_name),
+      )),
+    }
+  }
+
   pub async fn switch_to_field_type(
     &self,
     field_id: &str,
@@ -424,6 +590,9 @@ impl DatabaseEditor {
     }
 
     notify_did_update_database_field(&self.database, field_id)?;
+    if new_field_type.is_formula() {
+      self.update_formula_cells_of_field(field_id).await;
+    }
     Ok(())
   }
 
@@ -453,6 +622,10 @@ impl DatabaseEditor {
         .await;
 
       let new_field_id = duplicated_field.id.clone();
+      // The related database mirrors the original field only, so the copy is a one-way relation.
+      if self.get_relation_type_option(&new_field_id).is_some() {
+        self.set_paired_relation_field_id(&new_field_id, "")?;
+      }
       let cells = self.get_cells_for_field(view_id, field_id).await;
       for cell in cells {
         if let Some(new_cell) = cell.cell.clone() {
@@ -466,12 +639,28 @@ impl DatabaseEditor {
   }
 
   pub async fn duplicate_row(&self, view_id: &str, row_id: &RowId) -> FlowyResult<()> {
+    self
+      .duplicate_row_with_cells(view_id, row_id, vec![])
+      .await?;
+    Ok(())
+  }
+
+  /// Duplicates the row, replacing the cells of the copy with the given cells. Returns the copy.
+  async fn duplicate_row_with_cells(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    cells: Vec<(String, Cell)>,
+  ) -> FlowyResult<Option<RowDetail>> {
     let (row_detail, index) = {
       let database = self.database.lock();
 
-      let params = database
+      let mut params = database
         .duplicate_row(row_id)
         .ok_or_else(|| FlowyError::internal().with_context("error while copying row"))?;
+      for (field_id, cell) in cells {
+        params.cells.insert(field_id, cell);
+      }
 
       let (index, row_order) = database
         .create_row_in_view(view_id, params)
@@ -485,13 +674,107 @@ impl DatabaseEditor {
       (row_detail, index)
     };
 
-    if let Some(row_detail) = row_detail {
+    if let Some(row_detail) = &row_detail {
+      self.index_rows(&[row_detail.row.id.clone()]);
+      self
+        .row_comments_observer
+        .observe_rows(&[row_detail.row.id.clone()]);
       for view in self.database_views.editors().await {
-        view.v_did_create_row(&row_detail, index).await;
+        view.v_did_create_row(row_detail, index).await;
       }
     }
 
-    Ok(())
+    Ok(row_detail)
+  }
+
+  /// Completes the current occurrence of the recurring date of the row. The row keeps the date
+  /// without the recurrence, and a copy of the row is created for the next occurrence unless the
+  /// series has ended. The reminder of the date moves to the copy. If a checkbox field is given,
+  /// its cell is checked in the row and unchecked in the copy.
+  ///
+  /// Returns the copy of the row.
+  pub async fn complete_date_occurrence(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    checkbox_field_id: Option<String>,
+  ) -> FlowyResult<Option<RowDetail>> {
+    let type_option = self
+      .get_field(field_id)
+      .and_then(|field| field.get_type_option::<DateTypeOption>(FieldType::DateTime))
+      .ok_or_else(|| {
+        FlowyError::record_not_found().with_context(format!("Date field {} not found", field_id))
+      })?;
+    let checkbox_field = match checkbox_field_id {
+      None => None,
+      Some(checkbox_field_id) => {
+        let field = self
+          .get_field(&checkbox_field_id)
+          .filter(|field| FieldType::from(field.field_type).is_checkbox())
+          .ok_or_else(|| {
+            FlowyError::record_not_found()
+              .with_context(format!("Checkbox field {} not found", checkbox_field_id))
+          })?;
+        Some(field)
+      },
+    };
+
+    let cell_data = self
+      .get_cell(field_id, row_id)
+      .await
+      .map(|cell| DateCellData::from(&cell))
+      .unwrap_or_default();
+    if cell_data.get_recurrence().is_none() {
+      return Err(FlowyError::invalid_data().with_context("The date doesn't repeat"));
+    }
+    let next_cell_data = type_option.next_occurrence(&cell_data);
+    let old_date = cell_data.timestamp;
+
+    let completed_cell_data = DateCellData {
+      recurrence: String::new(),
+      reminder_id: String::new(),
+      ..cell_data
+    };
+    self
+      .update_cell(view_id, row_id, field_id, Cell::from(&completed_cell_data))
+      .await?;
+    if let Some(checkbox_field) = &checkbox_field {
+      let cell = insert_checkbox_cell(true, checkbox_field);
+      self
+        .update_cell(view_id, row_id, &checkbox_field.id, cell)
+        .await?;
+    }
+
+    let next_cell_data = match next_cell_data {
+      None => return Ok(None),
+      Some(next_cell_data) => next_cell_data,
+    };
+    let mut cells = vec![(field_id.to_string(), Cell::from(&next_cell_data))];
+    if let Some(checkbox_field) = &checkbox_field {
+      cells.push((
+        checkbox_field.id.clone(),
+        insert_checkbox_cell(false, checkbox_field),
+      ));
+    }
+    let row_detail = self
+      .duplicate_row_with_cells(view_id, row_id, cells)
+      .await?;
+
+    // The reminder of the date moves to the row of the next occurrence
+    if let (Some(row_detail), Some(old_date), Some(new_date)) =
+      (&row_detail, old_date, next_cell_data.timestamp)
+    {
+      if !next_cell_data.reminder_id.is_empty() {
+        let _ = self.reminder_date_tx.send(ReminderDateChange {
+          reminder_id: next_cell_data.reminder_id.clone(),
+          old_date,
+          new_date,
+          row_id: Some(row_detail.row.id.to_string()),
+        });
+      }
+    }
+    Ok(row_detail)
   }
 
   pub async fn move_row(
@@ -516,7 +799,9 @@ impl DatabaseEditor {
 
     if let Some(index) = new_index {
       let delete_row_id = from_row_id.into_inner();
-      let insert_row = InsertedRowPB::new(RowMetaPB::from(row_detail)).with_index(index as i32);
+      let view_editor = self.database_views.get_view_editor(view_id).await?;
+      let row_meta = view_editor.v_get_row_meta(&row_detail).await;
+      let insert_row = InsertedRowPB::new(row_meta).with_index(index as i32);
       let changes = RowsChangePB::from_move(vec![delete_row_id], vec![insert_row]);
 
       send_notification(view_id, DatabaseNotification::DidUpdateRow)
@@ -535,6 +820,21 @@ impl DatabaseEditor {
       open_after_create: _,
     } = view_editor.v_will_create_row(params).await?;
 
+    // The cells that are left empty are reported by [Self::validate_database] instead, otherwise
+    // a row could never be added to a database with required fields.
+    for (field_id, cell) in collab_params.cells.iter() {
+      if let Some(violation) = self
+        .validate_cell(&collab_params.id, field_id, Some(cell))
+        .await?
+        .filter(|violation| violation.rule != ValidationRule::Required)
+      {
+        return Err(FlowyError::new(
+          ErrorCode::CellValidationFailed,
+          violation.message,
+        ));
+      }
+    }
+
     let result = self
       .database
       .lock()
@@ -542,11 +842,24 @@ impl DatabaseEditor {
 
     if let Some((index, row_order)) = result {
       tracing::trace!("created row: {:?} at {}", row_order, index);
+      self.write_formula_cells(&row_order.id, None);
       let row_detail = self.database.lock().get_row_detail(&row_order.id);
       if let Some(row_detail) = row_detail {
+        self.index_rows(&[row_order.id.clone()]);
+        self
+          .row_comments_observer
+          .observe_rows(&[row_order.id.clone()]);
         for view in self.database_views.editors().await {
           view.v_did_create_row(&row_detail, index).await;
         }
+        self
+          .automations
+          .did_create_row(
+            &self.get_database_id(),
+            &self.get_automations(),
+            &row_order.id,
+          )
+          .await;
         return Ok(Some(row_detail));
       }
     }
@@ -585,6 +898,10 @@ impl DatabaseEditor {
       .notify_did_insert_database_field(field.clone(), index)
       .await;
 
+    if params.field_type.is_formula() {
+      self.update_formula_cells_of_field(&field.id).await;
+    }
+
     Ok(FieldPB::new(field))
   }
 
@@ -645,21 +962,12 @@ impl DatabaseEditor {
     }
   }
 
-  pub fn get_row_meta(&self, view_id: &str, row_id: &RowId) -> Option<RowMetaPB> {
-    if self.database.lock().views.is_row_exist(view_id, row_id) {
-      let row_meta = self.database.lock().get_row_meta(row_id)?;
-      let row_document_id = self.database.lock().get_row_document_id(row_id)?;
-      Some(RowMetaPB {
-        id: row_id.clone().into_inner(),
-        document_id: row_document_id,
-        icon: row_meta.icon_url,
-        cover: row_meta.cover_url,
-        is_document_empty: row_meta.is_document_empty,
-      })
-    } else {
-      warn!("the row:{} is exist in view:{}", row_id.as_str(), view_id);
-      None
-    }
+  /// Returns the meta of the row, with the colors given by the conditional formatting rules of
+  /// the view.
+  pub async fn get_row_meta(&self, view_id: &str, row_id: &RowId) -> Option<RowMetaPB> {
+    let row_detail = self.get_row_detail(view_id, row_id)?;
+    let view_editor = self.database_views.get_view_editor(view_id).await.ok()?;
+    Some(view_editor.v_get_row_meta(&row_detail).await)
   }
 
   pub fn get_row_detail(&self, view_id: &str, row_id: &RowId) -> Option<RowDetail> {
@@ -673,6 +981,8 @@ impl DatabaseEditor {
 
   pub async fn delete_rows(&self, row_ids: &[RowId]) {
     let rows = self.database.lock().remove_rows(row_ids);
+    self.row_indexer.remove_rows(row_ids);
+    self.row_comments_observer.remove_rows(row_ids);
 
     for row in rows {
       tracing::trace!("Did delete row:{:?}", row);
@@ -683,7 +993,21 @@ impl DatabaseEditor {
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn update_row_meta(&self, row_id: &RowId, changeset: UpdateRowMetaParams) {
+  pub async fn update_row_meta(
+    &self,
+    row_id: &RowId,
+    changeset: UpdateRowMetaParams,
+  ) -> FlowyResult<()> {
+    if let Some(comment) = changeset.insert_comment {
+      if comment.uid != self.uid.to_string() {
+        return Err(FlowyError::new(
+          ErrorCode::NotEnoughPermissions,
+          "Only the current user can add a comment",
+        ));
+      }
+      self.add_row_comment(row_id, &comment.comment, None).await?;
+    }
+
     self.database.lock().update_row_meta(row_id, |meta_update| {
       meta_update
         .insert_cover_if_not_none(changeset.cover_url)
@@ -708,6 +1032,121 @@ impl DatabaseEditor {
         .update_last_modified_time(row_detail.clone(), &changeset.view_id)
         .await;
     }
+    Ok(())
+  }
+
+  pub fn get_row_comments(&self, row_id: &RowId) -> RowComments {
+    self
+      .row_comments_observer
+      .observe_rows(std::slice::from_ref(row_id));
+    with_row_collab(&self.database, row_id, get_row_comments_from_collab).unwrap_or_default()
+  }
+
+  /// Adds a comment of the current user to the row. The comment is a reply in the thread of
+  /// `parent_comment_id` if it's set.
+  pub async fn add_row_comment(
+    &self,
+    row_id: &RowId,
+    content: &str,
+    parent_comment_id: Option<String>,
+  ) -> FlowyResult<RowComment> {
+    let comment = RowComment::new(self.uid, content, timestamp());
+    self
+      .update_row_comments(row_id, |comments| {
+        match parent_comment_id {
+          Some(parent_comment_id) => comments
+            .get_thread_mut(&parent_comment_id)
+            .ok_or_else(|| comment_not_found(&parent_comment_id))?
+            .replies
+            .push(comment.clone()),
+          None => comments.0.push(comment.clone()),
+        }
+        Ok(())
+      })
+      .await?;
+    Ok(comment)
+  }
+
+  /// Edits the content of a comment. Only the author can edit it.
+  pub async fn update_row_comment(
+    &self,
+    row_id: &RowId,
+    comment_id: &str,
+    content: &str,
+  ) -> FlowyResult<()> {
+    self
+      .update_row_comments(row_id, |comments| {
+        let comment = comments
+          .get_mut(comment_id)
+          .ok_or_else(|| comment_not_found(comment_id))?;
+        self.check_comment_author(comment)?;
+        comment.set_content(content, timestamp());
+        Ok(())
+      })
+      .await
+  }
+
+  /// Deletes a comment, with its replies. Only the author can delete it.
+  pub async fn delete_row_comment(&self, row_id: &RowId, comment_id: &str) -> FlowyResult<()> {
+    self
+      .update_row_comments(row_id, |comments| {
+        let comment = comments
+          .get(comment_id)
+          .ok_or_else(|| comment_not_found(comment_id))?;
+        self.check_comment_author(comment)?;
+        comments.remove(comment_id);
+        Ok(())
+      })
+      .await
+  }
+
+  /// Resolves or reopens the thread that contains the comment.
+  pub async fn resolve_row_comment(
+    &self,
+    row_id: &RowId,
+    comment_id: &str,
+    is_resolved: bool,
+  ) -> FlowyResult<()> {
+    self
+      .update_row_comments(row_id, |comments| {
+        comments
+          .get_thread_mut(comment_id)
+          .ok_or_else(|| comment_not_found(comment_id))?
+          .is_resolved = is_resolved;
+        Ok(())
+      })
+      .await
+  }
+
+  fn check_comment_author(&self, comment: &RowComment) -> FlowyResult<()> {
+    if comment.author_uid != self.uid {
+      return Err(FlowyError::new(
+        ErrorCode::NotEnoughPermissions,
+        "Only the author can change the comment",
+      ));
+    }
+    Ok(())
+  }
+
+  async fn update_row_comments<F>(&self, row_id: &RowId, f: F) -> FlowyResult<()>
+  where
+    F: FnOnce(&mut RowComments) -> FlowyResult<()>,
+  {
+    if self.database.lock().get_row_detail(row_id).is_none() {
+      return Err(
+        FlowyError::record_not_found().with_context(format!("Row with id:{} not found", row_id)),
+      );
+    }
+
+    // The frontend and the views are notified by the observer of the comments, see
+    // [RowCommentsObserver]. Only the comments that are changed by `f` are written.
+    let comments = self.get_row_comments(row_id);
+    let mut new_comments = comments.clone();
+    f(&mut new_comments)?;
+    with_row_collab(&self.database, row_id, |collab| {
+      save_row_comments_in_collab(collab, &comments, &new_comments)
+    })
+    .unwrap_or(Ok(()))
   }
 
   pub async fn get_cell(&self, field_id: &str, row_id: &RowId) -> Option<Cell> {
@@ -773,6 +1212,8 @@ impl DatabaseEditor {
     }
   }
 
+  /// Applies the changeset of the user to the cell. The new cell must pass the validation rules
+  /// of the field.
   #[instrument(level = "trace", skip_all)]
   pub async fn update_cell_with_changeset(
     &self,
@@ -781,6 +1222,39 @@ impl DatabaseEditor {
     field_id: &str,
     cell_changeset: BoxAny,
   ) -> FlowyResult<()> {
+    let new_cell = self.cell_from_changeset(row_id, field_id, cell_changeset)?;
+    if let Some(violation) = self
+      .validate_cell(row_id, field_id, Some(&new_cell))
+      .await?
+    {
+      return Err(FlowyError::new(
+        ErrorCode::CellValidationFailed,
+        violation.message,
+      ));
+    }
+    self.update_cell(view_id, row_id, field_id, new_cell).await
+  }
+
+  /// Applies a changeset that is written by the app itself, like a mirrored relation or the
+  /// result of the AI. Unlike [Self::update_cell_with_changeset], the validation rules of the
+  /// field are not checked.
+  pub async fn update_cell_with_system_changeset(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    cell_changeset: BoxAny,
+  ) -> FlowyResult<()> {
+    let new_cell = self.cell_from_changeset(row_id, field_id, cell_changeset)?;
+    self.update_cell(view_id, row_id, field_id, new_cell).await
+  }
+
+  fn cell_from_changeset(
+    &self,
+    row_id: &RowId,
+    field_id: &str,
+    cell_changeset: BoxAny,
+  ) -> FlowyResult<Cell> {
     let (field, cell) = {
       let database = self.database.lock();
       let field = match database.fields.get_field(field_id) {
@@ -793,9 +1267,18 @@ impl DatabaseEditor {
       (field, database.get_cell(field_id, row_id).cell)
     };
 
-    let new_cell =
-      apply_cell_changeset(cell_changeset, cell, &field, Some(self.cell_cache.clone()))?;
-    self.update_cell(view_id, row_id, field_id, new_cell).await
+    let field_type = FieldType::from(field.field_type);
+    if field_type.is_formula() || field_type.is_rollup() {
+      return Err(FlowyError::new(
+        ErrorCode::InvalidParams,
+        format!(
+          "Can not edit the cell of a {} field",
+          field_type.default_name()
+        ),
+      ));
+    }
+
+    apply_cell_changeset(cell_changeset, cell, &field, Some(self.cell_cache.clone()))
   }
 
   async fn update_last_modified_time(&self, row_detail: RowDetail, view_id: &str) {
@@ -816,6 +1299,9 @@ impl DatabaseEditor {
 
   /// Update a cell in the database.
   /// This will notify all views that the cell has been updated.
+  ///
+  /// The validation rules of the field are not checked, the edits of the user are validated by
+  /// [Self::update_cell_with_changeset] and [Self::create_row].
   pub async fn update_cell(
     &self,
     view_id: &str,
@@ -825,34 +1311,538 @@ impl DatabaseEditor {
   ) -> FlowyResult<()> {
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = { self.get_row_detail(view_id, row_id) };
+    let old_cell = get_cell_of_row(&old_row, field_id);
+    let reminder_date_change = self
+      .get_field(field_id)
+      .filter(|field| FieldType::from(field.field_type) == FieldType::DateTime)
+      .and_then(|_| reminder_date_change(old_cell.as_ref(), &new_cell));
     self.database.lock().update_row(row_id, |row_update| {
       row_update.update_cells(|cell_update| {
         cell_update.insert(field_id, new_cell);
       });
     });
+    if let Some(change) = reminder_date_change {
+      let _ = self.reminder_date_tx.send(change);
+    }
 
     self
       .did_update_row(view_id, row_id, field_id, old_row)
       .await;
+    self
+      .did_update_cell_for_automations(view_id, row_id, field_id, old_cell)
+      .await;
+    self
+      .update_dependent_formula_cells(view_id, row_id, field_id)
+      .await;
+    self.index_rows(&[row_id.clone()]);
 
     Ok(())
   }
 
+  /// Clears the cell without checking the validation rules of the field, see [Self::update_cell].
   pub async fn clear_cell(&self, view_id: &str, row_id: RowId, field_id: &str) -> FlowyResult<()> {
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = { self.get_row_detail(view_id, &row_id) };
+    let old_cell = get_cell_of_row(&old_row, field_id);
+
+    self.database.lock().update_row(&row_id, |row_update| {
+      row_update.update_cells(|cell_update| {
+        cell_update.clear(field_id);
+      });
+    });
+
+    self
+      .did_update_row(view_id, &row_id, field_id, old_row)
+      .await;
+    self
+      .did_update_cell_for_automations(view_id, &row_id, field_id, old_cell)
+      .await;
+    self
+      .update_dependent_formula_cells(view_id, &row_id, field_id)
+      .await;
+    self.index_rows(&[row_id]);
+
+    Ok(())
+  }
+
+  /// Returns the first validation rule of the field that the cell would break. The rules apply
+  /// to every view of the database, and the uniqueness is checked against all the rows.
+  async fn validate_cell(
+    &self,
+    row_id: &RowId,
+    field_id: &str,
+    cell: Option<&Cell>,
+  ) -> FlowyResult<Option<CellViolation>> {
+    let field = match self.get_field(field_id) {
+      Some(field) => field,
+      None => return Ok(None),
+    };
+    let validation = FieldValidation::from_field(&field);
+    if validation.is_empty() {
+      return Ok(None);
+    }
+
+    let other_texts = if validation.is_unique {
+      self
+        .get_all_rows()
+        .into_iter()
+        .filter(|row| &row.id != row_id)
+        .flat_map(|row| row.cells.get(field_id).cloned())
+        .map(|cell| stringify_cell(&cell, &field).trim().to_string())
+        .collect::<Vec<_>>()
+    } else {
+      vec![]
+    };
+    let validator = FieldValidator::new(&field, &validation, Some(self.cell_cache.clone()))?;
+    Ok(validator.validate(row_id, cell, |text| {
+      other_texts.iter().any(|other| other == text)
+    }))
+  }
+
+  /// Returns the cells of the view that break the validation rules of their fields, so that the
+  /// data that was added before the rules can be cleaned up. A cell breaks the uniqueness if
+  /// any row of the database has the same value, even if the row isn't in the view.
+  pub async fn validate_database(&self, view_id: &str) -> FlowyResult<Vec<CellViolation>> {
+    let validations = self
+      .get_fields(view_id, None)
+      .into_iter()
+      .map(|field| {
+        let validation = FieldValidation::from_field(&field);
+        (field, validation)
+      })
+      .filter(|(_, validation)| !validation.is_empty())
+      .collect::<Vec<_>>();
+    if validations.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let (rows, all_rows) = {
+      let database = self.database.lock();
+      (
+        database.get_rows_for_view(view_id),
+        database.get_database_rows(),
+      )
+    };
+    let mut violations = vec![];
+    for (field, validation) in validations.iter() {
+      let validator = FieldValidator::new(field, validation, Some(self.cell_cache.clone()))?;
+      let mut text_counts: HashMap<String, usize> = HashMap::new();
+      if validation.is_unique {
+        for cell in all_rows.iter().flat_map(|row| row.cells.get(&field.id)) {
+          let text = stringify_cell(cell, field).trim().to_string();
+          *text_counts.entry(text).or_default() += 1;
+        }
+      }
+      violations.extend(rows.iter().flat_map(|row| {
+        validator.validate(&row.id, row.cells.get(&field.id), |text| {
+          text_counts.get(text).map_or(false, |count| *count > 1)
+        })
+      }));
+    }
+
+    Ok(violations)
+  }
+
+  pub fn get_automations(&self) -> Vec<Automation> {
+    get_automations_from_collab(&self.database.lock().get_collab().lock())
+  }
+
+  pub fn get_automation(&self, automation_id: &str) -> Option<Automation> {
+    self
+      .get_automations()
+      .into_iter()
+      .find(|automation| automation.id == automation_id)
+  }
+
+  /// Inserts the automation, or replaces the automation with the same id. The other automations
+  /// are left untouched.
+  pub fn save_automation(&self, automation: &Automation) -> FlowyResult<()> {
+    save_automation_in_collab(&self.database.lock().get_collab().lock(), automation)
+  }
+
+  pub fn delete_automation(&self, automation_id: &str) {
+    remove_automation_in_collab(&self.database.lock().get_collab().lock(), automation_id);
+  }
+
+  /// Schedules the automations whose trigger is the change of the cell.
+  async fn did_update_cell_for_automations(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    old_cell: Option<Cell>,
+  ) {
+    let field = match self.get_field(field_id) {
+      Some(field) => field,
+      None => return,
+    };
+    let new_cell = get_cell_of_row(&self.get_row_detail(view_id, row_id), field_id);
+    self
+      .automations
+      .did_update_cell(
+        &self.get_database_id(),
+        &self.get_automations(),
+        &field,
+        row_id,
+        old_cell.as_ref(),
+        new_cell.as_ref(),
+      )
+      .await;
+  }
+
+  /// Queues the rows to update their search index with their current cells.
+  fn index_rows(&self, row_ids: &[RowId]) {
+    self.row_indexer.did_change_rows(row_ids, &self.database);
+  }
+
+  /// Recomputes the formula cells of the row whose result depends on the field with `field_id`.
+  async fn update_dependent_formula_cells(&self, view_id: &str, row_id: &RowId, field_id: &str) {
+    let fields = self.database.lock().get_fields(None);
+    let formula_fields = formula_fields_depending_on(&fields, field_id);
+    if formula_fields.is_empty() {
+      return;
+    }
+
+    let old_row = self.get_row_detail(view_id, row_id);
+    let updated_field_ids = self.write_formula_cells(row_id, Some(formula_fields));
+    for updated_field_id in updated_field_ids {
+      self
+        .did_update_row(view_id, row_id, &updated_field_id, old_row.clone())
+        .await;
+    }
+  }
+
+  /// Recomputes the cells of the formula field with `field_id` and of the formula fields that
+  /// depend on it, for every row of the database.
+  async fn update_formula_cells_of_field(&self, field_id: &str) {
+    let formula_fields = {
+      let database = self.database.lock();
+      let fields = database.get_fields(None);
+      let mut formula_fields = formula_fields_depending_on(&fields, field_id);
+      if let Some(field) = fields.into_iter().find(|field| field.id == field_id) {
+        formula_fields.insert(0, field);
+      }
+      formula_fields
+    };
+    self.update_formula_cells_of_all_rows(formula_fields).await;
+  }
+
+  async fn update_formula_cells_of_all_rows(&self, formula_fields: Vec<Field>) {
+    if formula_fields.is_empty() {
+      return;
+    }
+
+    let row_ids = self
+      .database
+      .lock()
+      .get_database_rows()
+      .into_iter()
+      .map(|row| row.id)
+      .collect::<Vec<RowId>>();
+    let view_editors = self.database_views.editors().await;
+    for row_id in row_ids {
+      let old_row = self.database.lock().get_row_detail(&row_id);
+      let updated_field_ids = self.write_formula_cells(&row_id, Some(formula_fields.clone()));
+      let new_row = match self.database.lock().get_row_detail(&row_id) {
+        Some(row_detail) => row_detail,
+        None => continue,
+      };
+      for field_id in updated_field_ids {
+        for view_editor in &view_editors {
+          view_editor
+            .v_did_update_row(&old_row, &new_row, Some(field_id.clone()))
+            .await;
+        }
+      }
+    }
+  }
+
+  /// Evaluates the given formula fields, or all the formula fields if `formula_fields` is None,
+  /// against the row and writes the results into its cells. Returns the ids of the fields whose
+  /// cell changed.
+  fn write_formula_cells(&self, row_id: &RowId, formula_fields: Option<Vec<Field>>) -> Vec<String> {
+    let database = self.database.lock();
+    let fields = database.get_fields(None);
+    let row = database.get_row(row_id);
+    let formula_fields = formula_fields.unwrap_or_else(|| {
+      fields
+        .iter()
+        .filter(|field| FieldType::from(field.field_type).is_formula())
+        .cloned()
+        .collect()
+    });
+
+    let mut updated_field_ids = vec![];
+    for field in formula_fields {
+      let new_cell = Cell::from(&evaluate_formula_for_row(&field, &fields, &row));
+      if row.cells.get(&field.id) == Some(&new_cell) {
+        continue;
+      }
+
+      database.update_row(row_id, |row_update| {
+        row_update.update_cells(|cell_update| {
+          cell_update.insert(&field.id, new_cell);
+        });
+      });
+      updated_field_ids.push(field.id);
+    }
+    updated_field_ids
+  }
+
+  /// Returns the ids of the databases that the rollup fields of this database aggregate values
+  /// from.
+  pub fn get_rollup_related_database_ids(&self) -> Vec<String> {
+    let mut database_ids = self
+      .get_rollups()
+      .into_iter()
+      .map(|rollup| rollup.related_database_id)
+      .collect::<Vec<String>>();
+    database_ids.sort();
+    database_ids.dedup();
+    database_ids
+  }
+
+  /// Recomputes the rollup cells that are affected by the change of the cell of `field_id` in the
+  /// row `row_id` of the database with `database_id`. The changed cell is either the relation cell
+  /// of a row of this database, or a rolled up value in the related database.
+  pub async fn did_update_rollup_source(
+    &self,
+    database_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    related_databases: &HashMap<String, Arc<DatabaseEditor>>,
+  ) {
+    let current_database_id = self.database.lock().get_database_id();
+    for rollup in self.get_rollups() {
+      let row_ids =
+        if database_id == current_database_id && field_id == rollup.type_option.relation_field_id {
+          vec![row_id.clone()]
+        } else if database_id == rollup.related_database_id
+          && field_id == rollup.type_option.target_field_id
+        {
+          self.get_rows_linked_to(&rollup.type_option.relation_field_id, row_id)
+        } else {
+          continue;
+        };
+
+      if let Some(related_database) = related_databases.get(&rollup.related_database_id) {
+        self
+          .update_rollup_cells(&rollup, related_database, row_ids)
+          .await;
+      }
+    }
+  }
+
+  /// Recomputes the rollup cells of the rows that link to the rows deleted from the database with
+  /// `database_id`. A one-way relation keeps the links to the deleted rows, so its cells don't
+  /// change when the related rows are deleted.
+  pub async fn did_delete_rollup_source_rows(
+    &self,
+    database_id: &str,
+    deleted_row_ids: &[RowId],
+    related_databases: &HashMap<String, Arc<DatabaseEditor>>,
+  ) {
+    for rollup in self.get_rollups() {
+      if rollup.related_database_id != database_id {
+        continue;
+      }
+
+      let mut row_ids: Vec<RowId> = vec![];
+      for deleted_row_id in deleted_row_ids {
+        for row_id in self.get_rows_linked_to(&rollup.type_option.relation_field_id, deleted_row_id)
+        {
+          if !row_ids.contains(&row_id) {
+            row_ids.push(row_id);
+          }
+        }
+      }
+      if let Some(related_database) = related_databases.get(&rollup.related_database_id) {
+        self
+          .update_rollup_cells(&rollup, related_database, row_ids)
+          .await;
+      }
+    }
+  }
+
+  /// Recomputes the rollup cells of the row `row_id`, e.g. after the row is created with the
+  /// cells of its relation fields.
+  pub async fn update_rollup_cells_of_row(
+    &self,
+    row_id: &RowId,
+    related_databases: &HashMap<String, Arc<DatabaseEditor>>,
+  ) {
+    for rollup in self.get_rollups() {
+      if let Some(related_database) = related_databases.get(&rollup.related_database_id) {
+        self
+          .update_rollup_cells(&rollup, related_database, vec![row_id.clone()])
+          .await;
+      }
+    }
+  }
+
+  /// Recomputes the rollup cells of every row. Only the rollups that aggregate values from the
+  /// database with `related_database_id` are recomputed if it's given.
+  pub async fn update_all_rollup_cells(
+    &self,
+    related_database_id: Option<&str>,
+    related_databases: &HashMap<String, Arc<DatabaseEditor>>,
+  ) {
+    for rollup in self.get_rollups() {
+      if related_database_id.map_or(false, |id| id != rollup.related_database_id) {
+        continue;
+      }
+      if let Some(related_database) = related_databases.get(&rollup.related_database_id) {
+        let row_ids = self
+          .database
+          .lock()
+          .get_database_rows()
+          .into_iter()
+          .map(|row| row.id)
+          .collect();
+        self
+          .update_rollup_cells(&rollup, related_database, row_ids)
+          .await;
+      }
+    }
+  }
+
+  /// Recomputes the cells of the rollup field with `field_id` for every row of the database.
+  pub async fn update_rollup_cells_of_field(
+    &self,
+    field_id: &str,
+    related_databases: &HashMap<String, Arc<DatabaseEditor>>,
+  ) {
+    let rollup = self
+      .get_rollups()
+      .into_iter()
+      .find(|rollup| rollup.field.id == field_id);
+    if let Some(rollup) = rollup {
+      if let Some(related_database) = related_databases.get(&rollup.related_database_id) {
+        let row_ids = self
+          .database
+          .lock()
+          .get_database_rows()
+          .into_iter()
+          .map(|row| row.id)
+          .collect();
+        self
+          .update_rollup_cells(&rollup, related_database, row_ids)
+          .await;
+      }
+    }
+  }
+
+  fn get_rollups(&self) -> Vec<Rollup> {
+    let fields = self.database.lock().get_fields(None);
+    fields
+      .iter()
+      .filter(|field| FieldType::from(field.field_type).is_rollup())
+      .flat_map(|field| {
+        let type_option = field.get_type_option::<RollupTypeOption>(FieldType::Rollup)?;
+        let related_database_id = fields
+          .iter()
+          .find(|relation_field| relation_field.id == type_option.relation_field_id)?
+          .get_type_option::<RelationTypeOption>(FieldType::Relation)?
+          .database_id;
+        Some(Rollup {
+          field: field.clone(),
+          type_option,
+          related_database_id,
+        })
+      })
+      .collect()
+  }
+
+  /// Returns the rows whose relation cell of `relation_field_id` links to `linked_row_id`.
+  fn get_rows_linked_to(&self, relation_field_id: &str, linked_row_id: &RowId) -> Vec<RowId> {
+    self
+      .database
+      .lock()
+      .get_database_rows()
+      .into_iter()
+      .filter(|row| {
+        row
+          .cells
+          .get(relation_field_id)
+          .map(|cell| RelationCellData::from(cell).row_ids.contains(linked_row_id))
+          .unwrap_or(false)
+      })
+      .map(|row| row.id)
+      .collect()
+  }
+
+  async fn update_rollup_cells(
+    &self,
+    rollup: &Rollup,
+    related_database: &DatabaseEditor,
+    row_ids: Vec<RowId>,
+  ) {
+    let target_field = match related_database.get_field(&rollup.type_option.target_field_id) {
+      Some(field) => field,
+      None => return,
+    };
+
+    let view_editors = self.database_views.editors().await;
+    for row_id in row_ids {
+      let linked_row_ids = self
+        .get_cell(&rollup.type_option.relation_field_id, &row_id)
+        .await
+        .map(|cell| RelationCellData::from(&cell).row_ids)
+        .unwrap_or_default();
+
+      let mut related_cells = vec![];
+      for linked_row_id in linked_row_ids {
+        // Skip the rows that were deleted from the related database.
+        if related_database
+          .database
+          .lock()
+          .get_row_detail(&linked_row_id)
+          .is_none()
+        {
+          continue;
+        }
+        let cell = related_database
+          .get_cell(&target_field.id, &linked_row_id)
+          .await;
+        related_cells.push(Arc::new(RowCell {
+          row_id: linked_row_id,
+          cell,
+        }));
+      }
+
+      let new_cell = Cell::from(&rollup.type_option.calculate(&target_field, related_cells));
+      let old_row = self.database.lock().get_row_detail(&row_id);
+      let is_changed = old_row
+        .as_ref()
+        .map(|row_detail| row_detail.row.cells.get(&rollup.field.id) != Some(&new_cell))
+        .unwrap_or(false);
+      if !is_changed {
+        continue;
+      }
 
-    self.database.lock().update_row(&row_id, |row_update| {
-      row_update.update_cells(|cell_update| {
-        cell_update.clear(field_id);
+      self.database.lock().update_row(&row_id, |row_update| {
+        row_update.update_cells(|cell_update| {
+          cell_update.insert(&rollup.field.id, new_cell);
+        });
       });
-    });
 
-    self
-      .did_update_row(view_id, &row_id, field_id, old_row)
-      .await;
+      let fields = self.database.lock().get_fields(None);
+      let formula_fields = formula_fields_depending_on(&fields, &rollup.field.id);
+      let mut updated_field_ids = vec![rollup.field.id.clone()];
+      if !formula_fields.is_empty() {
+        updated_field_ids.extend(self.write_formula_cells(&row_id, Some(formula_fields)));
+      }
 
-    Ok(())
+      if let Some(new_row) = self.database.lock().get_row_detail(&row_id) {
+        for field_id in updated_field_ids {
+          for view_editor in &view_editors {
+            view_editor
+              .v_did_update_row(&old_row, &new_row, Some(field_id.clone()))
+              .await;
+          }
+        }
+      }
+    }
   }
 
   async fn did_update_row(
@@ -1080,7 +2070,7 @@ impl DatabaseEditor {
             .last()
             .map(|row_detail| row_detail.row.id.clone())
         };
-        if let Some(row_id) = to_row.clone() {
+        if let Some(row_id) = to_row.clone().filter(|row_id| row_id != &from_row) {
           self.move_row(view_id, from_row.clone(), row_id).await?;
         }
 
@@ -1092,12 +2082,46 @@ impl DatabaseEditor {
         self.database.lock().update_row(&row_detail.row.id, |row| {
           row.set_cells(Cells::from(row_changeset.cell_by_field_id.clone()));
         });
+        let old_row = Some(row_detail.clone());
+        for field_id in row_changeset.cell_by_field_id.keys() {
+          self
+            .did_update_cell_for_automations(
+              view_id,
+              &row_detail.row.id,
+              field_id,
+              get_cell_of_row(&old_row, field_id),
+            )
+            .await;
+        }
       },
     }
 
     Ok(())
   }
 
+  /// Moves the row from its current group to the group with `to_group` in the view. The row
+  /// keeps its position in the view.
+  pub async fn move_row_to_group(
+    &self,
+    view_id: &str,
+    row_id: RowId,
+    to_group: &str,
+  ) -> FlowyResult<()> {
+    let from_group = self
+      .load_groups(view_id)
+      .await?
+      .items
+      .into_iter()
+      .find(|group| group.rows.iter().any(|row| row.id == row_id.as_str()))
+      .map(|group| group.group_id)
+      .ok_or_else(|| {
+        FlowyError::record_not_found().with_context("The row doesn't belong to any group")
+      })?;
+    self
+      .move_group_row(view_id, &from_group, to_group, row_id.clone(), Some(row_id))
+      .await
+  }
+
   pub async fn group_by_field(&self, view_id: &str, field_id: &str) -> FlowyResult<()> {
     let view = self.database_views.get_view_editor(view_id).await?;
     view.v_group_by_field(field_id).await?;
@@ -1132,9 +2156,16 @@ impl DatabaseEditor {
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn get_all_calendar_events(&self, view_id: &str) -> Vec<CalendarEventPB> {
+  pub async fn get_all_calendar_events(
+    &self,
+    view_id: &str,
+    range: Option<(i64, i64)>,
+  ) -> Vec<CalendarEventPB> {
     match self.database_views.get_view_editor(view_id).await {
-      Ok(view) => view.v_get_all_calendar_events().await.unwrap_or_default(),
+      Ok(view) => view
+        .v_get_all_calendar_events(range)
+        .await
+        .unwrap_or_default(),
       Err(_) => {
         warn!("Can not find the view: {}", view_id);
         vec![]
@@ -1157,6 +2188,69 @@ impl DatabaseEditor {
     view.v_get_calendar_event(row_id).await
   }
 
+  #[tracing::instrument(level = "trace", skip_all)]
+  pub async fn get_all_timeline_events(&self, view_id: &str) -> Vec<TimelineEventPB> {
+    match self.database_views.get_view_editor(view_id).await {
+      Ok(view) => view.v_get_all_timeline_events().await.unwrap_or_default(),
+      Err(_) => {
+        warn!("Can not find the view: {}", view_id);
+        vec![]
+      },
+    }
+  }
+
+  /// Moves or resizes the bar of a row in the timeline. The dates are written to the start and
+  /// end fields of the timeline layout setting.
+  #[tracing::instrument(level = "trace", skip_all, err)]
+  pub async fn move_timeline_event(&self, params: MoveTimelineEventParams) -> FlowyResult<()> {
+    let timeline_setting = self
+      .get_layout_setting(&params.view_id, DatabaseLayout::Grid)
+      .await
+      .and_then(|layout_setting| layout_setting.grid)
+      .map(|grid_setting| grid_setting.timeline)
+      .filter(|timeline_setting| !timeline_setting.start_field_id.is_empty())
+      .ok_or_else(|| {
+        FlowyError::record_not_found().with_context("Can't find the timeline layout setting")
+      })?;
+
+    if timeline_setting.end_field_id.is_empty() {
+      let cell_changeset = DateCellChangeset {
+        date: Some(params.start_timestamp),
+        end_date: Some(params.end_timestamp),
+        is_range: Some(true),
+        ..Default::default()
+      };
+      self
+        .update_cell_with_changeset(
+          &params.view_id,
+          &params.row_id,
+          &timeline_setting.start_field_id,
+          BoxAny::new(cell_changeset),
+        )
+        .await?;
+    } else {
+      let dates = [
+        (timeline_setting.start_field_id, params.start_timestamp),
+        (timeline_setting.end_field_id, params.end_timestamp),
+      ];
+      for (field_id, timestamp) in dates {
+        let cell_changeset = DateCellChangeset {
+          date: Some(timestamp),
+          ..Default::default()
+        };
+        self
+          .update_cell_with_changeset(
+            &params.view_id,
+            &params.row_id,
+            &field_id,
+            BoxAny::new(cell_changeset),
+          )
+          .await?;
+      }
+    }
+    Ok(())
+  }
+
   #[tracing::instrument(level = "trace", skip_all, err)]
   async fn notify_did_insert_database_field(&self, field: Field, index: usize) -> FlowyResult<()> {
     let database_id = self.database.lock().get_database_id();
@@ -1214,15 +2308,12 @@ impl DatabaseEditor {
       (database_id, fields, is_linked)
     };
 
-    let rows = rows
-      .into_iter()
-      .map(|row_detail| RowMetaPB::from(row_detail.as_ref()))
-      .collect::<Vec<RowMetaPB>>();
+    let rows = database_view.v_get_row_metas(&rows).await;
     Ok(DatabasePB {
       id: database_id,
       fields,
       rows,
-      layout_type: view.layout.into(),
+      layout_type: database_layout_pb_from_view(&view),
       is_linked,
     })
   }
@@ -1239,6 +2330,93 @@ impl DatabaseEditor {
     Ok(csv)
   }
 
+  /// Renders the rows of the view to html, see [DatabaseHtml]. The rows are filtered and sorted
+  /// like the view, and the hidden fields are skipped. A board is rendered as its columns, the
+  /// other layouts as a table.
+  pub async fn export_html(&self, view_id: &str) -> FlowyResult<String> {
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    let mut fields = self.get_fields(view_id, None);
+    let field_ids = fields
+      .iter()
+      .map(|field| field.id.clone())
+      .collect::<Vec<String>>();
+    let field_settings = view_editor.v_get_field_settings(&field_ids).await;
+    fields.retain(|field| {
+      field.is_primary
+        || field_settings.get(&field.id).map_or(true, |settings| {
+          settings.visibility != FieldVisibility::AlwaysHidden
+        })
+    });
+    // The primary field is the first column of the table and the title of a card
+    fields.sort_by_key(|field| !field.is_primary);
+
+    let field_names = fields
+      .iter()
+      .map(|field| field.name.clone())
+      .collect::<Vec<String>>();
+    let row_to_cells = |row: &Row| {
+      fields
+        .iter()
+        .map(|field| {
+          row
+            .cells
+            .get(&field.id)
+            .map(|cell| stringify_cell(cell, field))
+            .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+    };
+    let rows = view_editor.v_get_rows().await;
+
+    let html = match view_editor.v_get_layout_type().await {
+      DatabaseLayout::Board => {
+        let rows_by_id = rows
+          .iter()
+          .map(|row_detail| (row_detail.row.id.to_string(), &row_detail.row))
+          .collect::<HashMap<String, &Row>>();
+        let mut columns = vec![];
+        for group in view_editor.v_load_groups().await.unwrap_or_default() {
+          if !group.is_visible {
+            continue;
+          }
+          let group_rows = group
+            .rows
+            .iter()
+            .filter_map(|row_meta| rows_by_id.get(&row_meta.id).copied())
+            .collect::<Vec<_>>();
+          let group_field = self.get_field(&group.field_id);
+          // The name of a group is the content of the grouping cell of its rows, so the empty
+          // groups are skipped.
+          let name = match (&group_field, group_rows.first()) {
+            (Some(field), _) if group.is_default => format!("No {}", field.name),
+            (Some(field), Some(row)) => row
+              .cells
+              .get(&field.id)
+              .map(|cell| stringify_cell(cell, field))
+              .unwrap_or_default(),
+            _ => continue,
+          };
+          columns.push(BoardColumnHtml {
+            name,
+            rows: group_rows.into_iter().map(&row_to_cells).collect(),
+          });
+        }
+        DatabaseHtml::Board {
+          field_names,
+          columns,
+        }
+      },
+      _ => DatabaseHtml::Table {
+        field_names,
+        rows: rows
+          .iter()
+          .map(|row_detail| row_to_cells(&row_detail.row))
+          .collect(),
+      },
+    };
+    Ok(html.to_html())
+  }
+
   pub async fn get_field_settings(
     &self,
     view_id: &str,
@@ -1275,6 +2453,37 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  pub async fn get_conditional_formats(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<Vec<ConditionalFormat>> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    Ok(view.v_get_conditional_formats().await)
+  }
+
+  pub async fn create_conditional_format(
+    &self,
+    view_id: &str,
+    format: ConditionalFormat,
+  ) -> FlowyResult<()> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    view.v_create_conditional_format(format).await
+  }
+
+  pub async fn update_conditional_format(
+    &self,
+    view_id: &str,
+    format: ConditionalFormat,
+  ) -> FlowyResult<()> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    view.v_update_conditional_format(format).await
+  }
+
+  pub async fn delete_conditional_format(&self, view_id: &str, format_id: &str) -> FlowyResult<()> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    view.v_delete_conditional_format(format_id).await
+  }
+
   pub async fn get_related_database_id(&self, field_id: &str) -> FlowyResult<String> {
     let mut field = self
       .database
@@ -1289,6 +2498,225 @@ impl DatabaseEditor {
     Ok(type_option.database_id)
   }
 
+  /// Returns true if the field is a summary or a translate field whose cells are filled
+  /// automatically.
+  pub fn is_auto_fill_ai_field(&self, field_id: &str) -> bool {
+    let field = match self.get_field(field_id) {
+      Some(field) => field,
+      None => return false,
+    };
+    match FieldType::from(field.field_type) {
+      FieldType::Summary => field
+        .get_type_option::<SummarizationTypeOption>(FieldType::Summary)
+        .map(|type_option| type_option.auto_fill)
+        .unwrap_or(false),
+      FieldType::Translate => field
+        .get_type_option::<TranslateTypeOption>(FieldType::Translate)
+        .map(|type_option| type_option.auto_fill)
+        .unwrap_or(false),
+      _ => false,
+    }
+  }
+
+  pub fn get_database_id(&self) -> String {
+    self.database.lock().get_database_id()
+  }
+
+  pub fn get_inline_view_id(&self) -> String {
+    self.database.lock().get_inline_view_id()
+  }
+
+  /// Returns all the rows of the database, regardless of the filters of the views.
+  pub fn get_all_rows(&self) -> Vec<Row> {
+    self.database.lock().get_database_rows()
+  }
+
+  /// Returns the type option of the field if it's a relation field.
+  pub fn get_relation_type_option(&self, field_id: &str) -> Option<RelationTypeOption> {
+    let field = self.get_field(field_id)?;
+    if !FieldType::from(field.field_type).is_relation() {
+      return None;
+    }
+    field.get_type_option::<RelationTypeOption>(FieldType::Relation)
+  }
+
+  /// Returns the ids of the two-way relation fields.
+  pub fn get_two_way_relation_field_ids(&self) -> Vec<String> {
+    let fields = self.database.lock().get_fields(None);
+    fields
+      .into_iter()
+      .filter(|field| {
+        FieldType::from(field.field_type).is_relation()
+          && field
+            .get_type_option::<RelationTypeOption>(FieldType::Relation)
+            .map(|type_option| type_option.is_two_way())
+            .unwrap_or(false)
+      })
+      .map(|field| field.id)
+      .collect()
+  }
+
+  /// Sets the id of the relation field that mirrors the relation field with `field_id`. An empty
+  /// `paired_field_id` makes the relation one-way.
+  pub fn set_paired_relation_field_id(
+    &self,
+    field_id: &str,
+    paired_field_id: &str,
+  ) -> FlowyResult<()> {
+    let field = self
+      .get_field(field_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Relation field not found"))?;
+    let mut type_option = field
+      .get_type_option::<RelationTypeOption>(FieldType::Relation)
+      .unwrap_or_default();
+    type_option.paired_field_id = paired_field_id.to_string();
+    self
+      .database
+      .lock()
+      .fields
+      .update_field(field_id, |update| {
+        update.update_type_options(|type_options_update| {
+          type_options_update.insert(&FieldType::Relation.to_string(), type_option.into());
+        });
+      });
+    notify_did_update_database_field(&self.database, field_id)
+  }
+
+  /// Creates a relation field that mirrors the relation field with `paired_field_id` of the
+  /// database with `database_id`.
+  pub async fn create_paired_relation_field(
+    &self,
+    name: &str,
+    database_id: &str,
+    paired_field_id: &str,
+  ) -> FlowyResult<Field> {
+    let view_id = self.database.lock().get_inline_view_id();
+    let type_option = RelationTypeOption {
+      database_id: database_id.to_string(),
+      paired_field_id: paired_field_id.to_string(),
+    };
+    let (index, field) = self.database.lock().create_field_with_mut(
+      &view_id,
+      name.to_string(),
+      FieldType::Relation.into(),
+      &OrderObjectPosition::End,
+      |field| {
+        field
+          .type_options
+          .insert(FieldType::Relation.to_string(), type_option.into());
+      },
+      default_field_settings_by_layout_map(),
+    );
+    let _ = self
+      .notify_did_insert_database_field(field.clone(), index)
+      .await;
+    Ok(field)
+  }
+
+  /// Returns the row ids of the non-empty cells of the relation field, with the ids of the rows
+  /// they link to.
+  pub fn get_relation_cells(&self, field_id: &str) -> Vec<(RowId, Vec<RowId>)> {
+    self
+      .database
+      .lock()
+      .get_database_rows()
+      .into_iter()
+      .filter_map(|row| {
+        let row_ids = RelationCellData::from(row.cells.get(field_id)?).row_ids;
+        if row_ids.is_empty() {
+          None
+        } else {
+          Some((row.id, row_ids))
+        }
+      })
+      .collect()
+  }
+
+  /// Applies the changeset to the relation cell of the row. Does nothing if the row doesn't exist,
+  /// which happens when it was deleted after being linked.
+  pub async fn update_relation_cell(
+    &self,
+    row_id: &RowId,
+    field_id: &str,
+    changeset: RelationCellChangeset,
+  ) -> FlowyResult<()> {
+    let view_id = {
+      let database = self.database.lock();
+      if database.get_row_detail(row_id).is_none() {
+        return Ok(());
+      }
+      database.get_inline_view_id()
+    };
+    self
+      .update_cell_with_system_changeset(&view_id, row_id, field_id, BoxAny::new(changeset))
+      .await
+  }
+
+  /// Updates the urls of the rows that link to the view with its publish name, which contains
+  /// the name of the view. Returns the number of the updated cells.
+  pub async fn update_view_urls(
+    &self,
+    row_ids: Vec<String>,
+    target_view_id: &str,
+    publish_name: &str,
+  ) -> FlowyResult<usize> {
+    let (view_id, changes) = {
+      let database = self.database.lock();
+      let url_fields = database
+        .get_fields(None)
+        .into_iter()
+        .filter(|field| FieldType::from(field.field_type).is_url())
+        .collect::<Vec<Field>>();
+      let mut changes = vec![];
+      for row_id in row_ids.into_iter().map(RowId::from) {
+        for field in &url_fields {
+          let url = match database.get_cell(&field.id, &row_id).cell {
+            Some(cell) => URLCellData::from(&cell).data,
+            None => continue,
+          };
+          if let Some(url) = replace_publish_name_in_url(&url, target_view_id, publish_name) {
+            changes.push((row_id.clone(), field.id.clone(), url));
+          }
+        }
+      }
+      (database.get_inline_view_id(), changes)
+    };
+
+    let count = changes.len();
+    for (row_id, field_id, url) in changes {
+      self
+        .update_cell_with_system_changeset(&view_id, &row_id, &field_id, BoxAny::new(url))
+        .await?;
+    }
+    Ok(count)
+  }
+
+  /// Removes the links to the rows with `row_ids` from the cells of the relation field.
+  pub async fn remove_related_row_ids(&self, field_id: &str, row_ids: &[RowId]) -> FlowyResult<()> {
+    for (row_id, linked_row_ids) in self.get_relation_cells(field_id) {
+      let removed_row_ids = linked_row_ids
+        .into_iter()
+        .filter(|linked_row_id| row_ids.contains(linked_row_id))
+        .collect::<Vec<_>>();
+      if !removed_row_ids.is_empty() {
+        let changeset = RelationCellChangeset {
+          inserted_row_ids: vec![],
+          removed_row_ids,
+        };
+        self
+          .update_relation_cell(&row_id, field_id, changeset)
+          .await?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Clears the cells of the field in every row of the database.
+  pub async fn clear_field_of_all_rows(&self, field_id: &str) -> FlowyResult<()> {
+    let view_id = self.database.lock().get_inline_view_id();
+    self.clear_field(&view_id, field_id).await
+  }
+
   pub async fn get_related_rows(
     &self,
     row_ids: Option<&Vec<String>>,
@@ -1311,12 +2739,12 @@ impl DatabaseEditor {
             .get_cell(&primary_field.id, &row.id)
             .cell
             .and_then(|cell| handler.handle_get_boxed_cell_data(&cell, &primary_field))
-            .and_then(|cell_data| cell_data.unbox_or_none())
-            .unwrap_or_else(|| StringCellData("".to_string()));
+            .and_then(|cell_data| cell_data.unbox_or_none::<RichTextCellData>())
+            .unwrap_or_default();
 
           RelatedRowDataPB {
             row_id: row.id.to_string(),
-            name: title.0,
+            name: title.text,
           }
         })
         .collect::<Vec<_>>()
@@ -1335,6 +2763,108 @@ impl DatabaseEditor {
       .collect::<Vec<Field>>()
   }
 
+  /// Replaces the fields and the rows of the database with the ones in `data`, which is usually
+  /// read from a snapshot. The fields and the rows are updated in place instead of recreating
+  /// the database, so the restore is synced like any other change.
+  pub async fn restore_database_data(&self, view_id: &str, data: DatabaseData) -> FlowyResult<()> {
+    let (database_id, current_fields, current_rows) = {
+      let database = self.database.lock();
+      (
+        database.get_database_id(),
+        database.get_fields(None),
+        database.get_database_rows(),
+      )
+    };
+
+    for field in data.fields.iter() {
+      if current_fields.iter().any(|current| current.id == field.id) {
+        self
+          .database
+          .lock()
+          .fields
+          .update_field(&field.id, |update| {
+            update
+              .set_name_if_not_none(Some(field.name.clone()))
+              .set_field_type(field.field_type)
+              .update_type_options(|type_options_update| {
+                for (field_type, type_option_data) in field.type_options.iter() {
+                  type_options_update.insert(field_type, type_option_data.clone());
+                }
+              });
+          });
+        notify_did_update_database_field(&self.database, &field.id)?;
+      } else {
+        let (index, new_field) = self.database.lock().create_field_with_mut(
+          view_id,
+          field.name.clone(),
+          field.field_type,
+          &OrderObjectPosition::End,
+          |new_field| {
+            new_field.id = field.id.clone();
+            new_field.type_options = field.type_options.clone();
+          },
+          default_field_settings_by_layout_map(),
+        );
+        let _ = self
+          .notify_did_insert_database_field(new_field, index)
+          .await;
+      }
+    }
+    for field in current_fields.iter() {
+      if !field.is_primary && !data.fields.iter().any(|restored| restored.id == field.id) {
+        self.delete_field(&field.id).await?;
+      }
+    }
+
+    let mut created_row_ids = vec![];
+    for row in data.rows.iter() {
+      match current_rows.iter().find(|current| current.id == row.id) {
+        Some(current) => {
+          for (field_id, cell) in row.cells.iter() {
+            if current.cells.get(field_id) != Some(cell) {
+              self
+                .update_cell(view_id, &row.id, field_id, cell.clone())
+                .await?;
+            }
+          }
+        },
+        None => {
+          let params = collab_database::rows::CreateRowParams {
+            id: row.id.clone(),
+            database_id: database_id.clone(),
+            cells: row.cells.clone(),
+            height: row.height,
+            visibility: row.visibility,
+            row_position: OrderObjectPosition::End,
+            created_at: row.created_at,
+            modified_at: row.modified_at,
+          };
+          let result = self.database.lock().create_row_in_view(view_id, params);
+          if let Some((index, row_order)) = result {
+            let row_detail = self.database.lock().get_row_detail(&row_order.id);
+            if let Some(row_detail) = row_detail {
+              for view in self.database_views.editors().await {
+                view.v_did_create_row(&row_detail, index).await;
+              }
+            }
+            created_row_ids.push(row_order.id);
+          }
+        },
+      }
+    }
+    self.index_rows(&created_row_ids);
+
+    let deleted_row_ids = current_rows
+      .into_iter()
+      .filter(|current| !data.rows.iter().any(|row| row.id == current.id))
+      .map(|current| current.id)
+      .collect::<Vec<_>>();
+    if !deleted_row_ids.is_empty() {
+      self.delete_rows(&deleted_row_ids).await;
+    }
+    Ok(())
+  }
+
   /// Only expose this method for testing
   #[cfg(debug_assertions)]
   pub fn get_mutex_database(&self) -> &MutexDatabase {
@@ -1686,6 +3216,39 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
     .send()
   }
 
+  fn update_conditional_formats(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    conditional_formats: Vec<ConditionalFormat>,
+  ) {
+    let field_settings = self
+      .get_field_settings(view_id, &[field_id.to_string()])
+      .remove(field_id)
+      .unwrap_or_else(|| {
+        let layout_type = self.get_layout_for_view(view_id);
+        let default_field_settings = default_field_settings_by_layout_map();
+        let default_field_settings = default_field_settings.get(&layout_type).unwrap();
+
+        FieldSettings::from_any_map(field_id, layout_type, default_field_settings)
+      });
+
+    let new_field_settings = FieldSettings {
+      conditional_formats,
+      ..field_settings
+    };
+
+    self.database.lock().update_field_settings(
+      view_id,
+      Some(vec![field_id.to_string()]),
+      new_field_settings.clone(),
+    );
+
+    send_notification(view_id, DatabaseNotification::DidUpdateFieldSettings)
+      .payload(FieldSettingsPB::from(new_field_settings))
+      .send()
+  }
+
   fn update_calculation(&self, view_id: &str, calculation: Calculation) {
     self
       .database
@@ -1698,6 +3261,14 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
   }
 }
 
+/// A rollup field of the database, together with the id of the database that its relation field
+/// links to.
+struct Rollup {
+  field: Field,
+  type_option: RollupTypeOption,
+  related_database_id: String,
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub async fn update_field_type_option_fn(
   database: &Arc<MutexDatabase>,
@@ -1771,3 +3342,32 @@ fn notify_did_update_database_field(
   }
   Ok(())
 }
+
+fn comment_not_found(comment_id: &str) -> FlowyError {
+  FlowyError::record_not_found().with_context(format!("Comment with id:{} not found", comment_id))
+}
+
+fn get_cell_of_row(row_detail: &Option<RowDetail>, field_id: &str) -> Option<Cell> {
+  row_detail
+    .as_ref()
+    .and_then(|row_detail| row_detail.row.cells.get(field_id).cloned())
+}
+
+/// Returns the change of the date if both the old and the new date cell have the same reminder.
+/// A new reminder is scheduled by the client along with the date, so it's not a change.
+fn reminder_date_change(old_cell: Option<&Cell>, new_cell: &Cell) -> Option<ReminderDateChange> {
+  let old_cell_data = DateCellData::from(old_cell?);
+  let new_cell_data = DateCellData::from(new_cell);
+  if new_cell_data.reminder_id.is_empty() || old_cell_data.reminder_id != new_cell_data.reminder_id
+  {
+    return None;
+  }
+  let old_date = old_cell_data.timestamp?;
+  let new_date = new_cell_data.timestamp?;
+  (old_date != new_date).then(|| ReminderDateChange {
+    reminder_id: new_cell_data.reminder_id,
+    old_date,
+    new_date,
+    row_id: None,
+  })
+}
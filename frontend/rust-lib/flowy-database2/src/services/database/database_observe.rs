@@ -1,6 +1,14 @@
-use crate::entities::{DatabaseSyncStatePB, DidFetchRowPB, RowsChangePB};
+use crate::entities::{
+  DatabaseSyncStatePB, DidFetchRowPB, RepeatedRowCommentPB, RowCommentMentionPB, RowCommentPB,
+  RowsChangePB,
+};
 use crate::notification::{send_notification, DatabaseNotification, DATABASE_OBSERVABLE_SOURCE};
+use crate::services::comment::{
+  get_row_comments_from_collab, observe_row_comments_in_collab, with_row_collab,
+};
 use crate::services::database::UpdatedRow;
+use crate::services::database_view::DatabaseViews;
+use collab::preclude::Subscription;
 use collab_database::blocks::BlockEvent;
 use collab_database::database::MutexDatabase;
 use collab_database::fields::FieldChange;
@@ -9,7 +17,11 @@ use collab_database::views::DatabaseViewChange;
 use flowy_notification::{DebounceNotificationSender, NotificationBuilder};
 use futures::StreamExt;
 use lib_dispatch::prelude::af_spawn;
-use std::sync::Arc;
+use lib_infra::util::timestamp;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Weak};
+use tokio::sync::mpsc;
 use tracing::{trace, warn};
 
 pub(crate) async fn observe_sync_state(database_id: &str, database: &Arc<MutexDatabase>) {
@@ -74,6 +86,122 @@ pub(crate) async fn observe_rows_change(
     }
   });
 }
+
+/// Notifies the changes of the comments of the rows, made on this device or synced from another
+/// one, and the comments of the other members that mention the user. The comments are stored in
+/// the collab of each row, so every row is observed on its own until it's removed or the
+/// observer is dropped.
+pub(crate) struct RowCommentsObserver {
+  database: Weak<MutexDatabase>,
+  subscriptions: Mutex<HashMap<RowId, Subscription>>,
+  tx: mpsc::UnboundedSender<RowId>,
+}
+
+impl RowCommentsObserver {
+  pub(crate) fn new(
+    database_id: &str,
+    database: &Arc<MutexDatabase>,
+    database_views: &Arc<DatabaseViews>,
+    uid: i64,
+  ) -> Self {
+    let (tx, mut rx) = mpsc::unbounded_channel::<RowId>();
+    let database_id = database_id.to_string();
+    let weak_database = Arc::downgrade(database);
+    let weak_database_views = Arc::downgrade(database_views);
+    // Only the comments that are written after the database is opened mention the user.
+    let opened_at = timestamp();
+    let mut mentioned_comment_ids = HashSet::new();
+    af_spawn(async move {
+      while let Some(row_id) = rx.recv().await {
+        let (database, database_views) =
+          match (weak_database.upgrade(), weak_database_views.upgrade()) {
+            (Some(database), Some(database_views)) => (database, database_views),
+            _ => break,
+          };
+        trace!(
+          "[Database Observe]: {} row comments change:{}",
+          database_id,
+          row_id
+        );
+        let comments =
+          with_row_collab(&database, &row_id, get_row_comments_from_collab).unwrap_or_default();
+        send_notification(row_id.as_str(), DatabaseNotification::DidUpdateRowComments)
+          .payload(RepeatedRowCommentPB::from(&comments))
+          .send();
+
+        let mentions = comments.iter_all().filter(|comment| {
+          comment.author_uid != uid && comment.updated_at >= opened_at && comment.mentions(uid)
+        });
+        for comment in mentions {
+          if mentioned_comment_ids.insert(comment.id.clone()) {
+            send_notification(
+              &uid.to_string(),
+              DatabaseNotification::DidMentionInRowComment,
+            )
+            .payload(RowCommentMentionPB {
+              database_id: database_id.clone(),
+              row_id: row_id.to_string(),
+              comment: RowCommentPB::from(comment),
+            })
+            .send();
+          }
+        }
+
+        for view in database_views.editors().await {
+          view.v_did_update_row_comments().await;
+        }
+      }
+    });
+
+    let observer = Self {
+      database: Arc::downgrade(database),
+      subscriptions: Mutex::new(HashMap::new()),
+      tx,
+    };
+    let inline_view_id = database.lock().get_inline_view_id();
+    let row_ids = database
+      .lock()
+      .get_row_orders_for_view(&inline_view_id)
+      .into_iter()
+      .map(|row_order| row_order.id)
+      .collect::<Vec<RowId>>();
+    observer.observe_rows(&row_ids);
+    observer
+  }
+
+  /// Starts observing the comments of the rows that are not observed yet.
+  pub(crate) fn observe_rows(&self, row_ids: &[RowId]) {
+    let database = match self.database.upgrade() {
+      Some(database) => database,
+      None => return,
+    };
+    let mut subscriptions = self.subscriptions.lock();
+    for row_id in row_ids {
+      if subscriptions.contains_key(row_id) {
+        continue;
+      }
+      let tx = self.tx.clone();
+      let cloned_row_id = row_id.clone();
+      let subscription = with_row_collab(&database, row_id, |collab| {
+        observe_row_comments_in_collab(collab, move || {
+          let _ = tx.send(cloned_row_id.clone());
+        })
+      });
+      if let Some(subscription) = subscription {
+        subscriptions.insert(row_id.clone(), subscription);
+      }
+    }
+  }
+
+  /// Stops observing the comments of the deleted rows.
+  pub(crate) fn remove_rows(&self, row_ids: &[RowId]) {
+    let mut subscriptions = self.subscriptions.lock();
+    for row_id in row_ids {
+      subscriptions.remove(row_id);
+    }
+  }
+}
+
 #[allow(dead_code)]
 pub(crate) async fn observe_field_change(database_id: &str, database: &Arc<MutexDatabase>) {
   let database_id = database_id.to_string();
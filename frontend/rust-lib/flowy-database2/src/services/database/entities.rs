@@ -58,6 +58,16 @@ impl UpdatedRow {
   }
 }
 
+/// The date of a date cell that has a reminder changed, see [crate::services::field::DateCellData].
+#[derive(Debug, Clone)]
+pub struct ReminderDateChange {
+  pub reminder_id: String,
+  pub old_date: i64,
+  pub new_date: i64,
+  /// The row the date cell has moved to, e.g. the row of the next occurrence of a recurring date.
+  pub row_id: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateDatabaseViewParams {
   pub name: String,
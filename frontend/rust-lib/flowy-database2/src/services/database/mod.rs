@@ -1,8 +1,10 @@
 mod database_editor;
 mod database_observe;
 mod entities;
+mod row_indexer;
 mod util;
 
 pub use database_editor::*;
 pub use entities::*;
+pub use row_indexer::*;
 pub(crate) use util::database_view_setting_pb_from_view;
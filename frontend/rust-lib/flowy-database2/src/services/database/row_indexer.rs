@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use collab_database::database::MutexDatabase;
+use collab_database::fields::Field;
+use collab_database::rows::{Row, RowId};
+use collab_database::workspace_database::WorkspaceDatabase;
+use flowy_search_pub::entities::{
+  BacklinkIndexData, BacklinkIndexManager, DatabaseCellIndexData, DatabaseIndexManager,
+  DatabaseRowIndexData, ViewReferenceData,
+};
+use lib_dispatch::prelude::af_spawn;
+use parking_lot::Mutex;
+use tracing::{error, trace};
+
+use crate::entities::FieldType;
+use crate::services::cell::stringify_cell;
+use crate::services::field::{RelationCellData, RelationTypeOption, URLCellData};
+use crate::services::share::url::view_id_from_url;
+
+/// Changes of the rows of the same database within this duration are indexed at once.
+const INDEX_DEBOUNCE_DURATION: Duration = Duration::from_secs(2);
+
+/// The views of the databases in the workspace, by the id of the database. It's shared by the
+/// row indexers of all the databases and cleared when a database or a view is added or removed.
+#[derive(Clone, Default)]
+pub struct DatabaseViewIdsCache {
+  view_ids_by_database_id: Arc<Mutex<Option<HashMap<String, Vec<String>>>>>,
+}
+
+impl DatabaseViewIdsCache {
+  pub fn clear(&self) {
+    *self.view_ids_by_database_id.lock() = None;
+  }
+
+  /// Returns the views of the given databases, the views are loaded from the workspace database
+  /// if the cache is empty.
+  fn get_view_ids(
+    &self,
+    database_ids: &[String],
+    workspace_database: &Weak<WorkspaceDatabase>,
+  ) -> HashMap<String, Vec<String>> {
+    let mut view_ids_by_database_id = self.view_ids_by_database_id.lock();
+    if view_ids_by_database_id.is_none() {
+      let workspace_database = match workspace_database.upgrade() {
+        Some(workspace_database) => workspace_database,
+        None => return HashMap::new(),
+      };
+      *view_ids_by_database_id = Some(
+        workspace_database
+          .get_all_database_meta()
+          .into_iter()
+          .map(|meta| (meta.database_id, meta.linked_views))
+          .collect(),
+      );
+    }
+    view_ids_by_database_id
+      .iter()
+      .flatten()
+      .filter(|(database_id, _)| database_ids.contains(database_id))
+      .map(|(database_id, view_ids)| (database_id.clone(), view_ids.clone()))
+      .collect()
+  }
+}
+
+/// Keeps the search index and the view references of the rows of a database up to date. The
+/// indexes themselves are provided by the search crate through the [DatabaseIndexManager] and
+/// the [BacklinkIndexManager].
+#[derive(Clone)]
+pub struct DatabaseRowIndexer {
+  index_manager: Arc<dyn DatabaseIndexManager>,
+  backlink_manager: Arc<dyn BacklinkIndexManager>,
+  /// Used to find the views of the databases that the relation cells link to.
+  workspace_database: Weak<WorkspaceDatabase>,
+  database_view_ids: DatabaseViewIdsCache,
+  database_id: String,
+  /// The references of the rows are attributed to the inline view of the database.
+  inline_view_id: String,
+  workspace_id: String,
+  /// The rows that are waiting to be indexed.
+  pending_row_ids: Arc<Mutex<HashSet<RowId>>>,
+}
+
+impl DatabaseRowIndexer {
+  pub fn new(
+    index_manager: Arc<dyn DatabaseIndexManager>,
+    backlink_manager: Arc<dyn BacklinkIndexManager>,
+    workspace_database: Weak<WorkspaceDatabase>,
+    database_view_ids: DatabaseViewIdsCache,
+    database_id: String,
+    inline_view_id: String,
+    workspace_id: String,
+  ) -> Self {
+    Self {
+      index_manager,
+      backlink_manager,
+      workspace_database,
+      database_view_ids,
+      database_id,
+      inline_view_id,
+      workspace_id,
+      pending_row_ids: Default::default(),
+    }
+  }
+
+  /// Called when the cells of the rows changed. The rows are indexed with their current cells
+  /// in a background task, so that editing a row never waits on the index. The rows changed
+  /// within [INDEX_DEBOUNCE_DURATION] are indexed at once.
+  pub fn did_change_rows(&self, row_ids: &[RowId], database: &Arc<MutexDatabase>) {
+    let should_schedule_flush = {
+      let mut pending_row_ids = self.pending_row_ids.lock();
+      // A flush is already scheduled if there are pending rows
+      let should_schedule_flush = pending_row_ids.is_empty() && !row_ids.is_empty();
+      pending_row_ids.extend(row_ids.iter().cloned());
+      should_schedule_flush
+    };
+    if !should_schedule_flush {
+      return;
+    }
+
+    let indexer = self.clone();
+    // The database is kept alive until the flush, so that the changes made right before the
+    // database is closed are indexed too.
+    let database = database.clone();
+    af_spawn(async move {
+      tokio::time::sleep(INDEX_DEBOUNCE_DURATION).await;
+      let row_ids = std::mem::take(&mut *indexer.pending_row_ids.lock());
+      let (rows, fields) = {
+        let database = database.lock();
+        // The rows that were deleted in the meantime are skipped
+        let rows = row_ids
+          .iter()
+          .filter_map(|row_id| database.get_row_detail(row_id))
+          .map(|row_detail| row_detail.row)
+          .collect::<Vec<Row>>();
+        (rows, database.get_fields(None))
+      };
+      indexer.index_rows(&rows, &fields);
+    });
+  }
+
+  /// Replaces the indexed cells of the given rows. Failing to index a row doesn't fail the
+  /// edit of the row, so the error is only logged.
+  fn index_rows(&self, rows: &[Row], fields: &[Field]) {
+    if rows.is_empty() {
+      return;
+    }
+
+    trace!(
+      "[Database Index] index {} rows of database: {}",
+      rows.len(),
+      self.database_id
+    );
+    let index_data = rows
+      .iter()
+      .map(|row| DatabaseRowIndexData {
+        database_id: self.database_id.clone(),
+        row_id: row.id.to_string(),
+        workspace_id: self.workspace_id.clone(),
+        cells: cell_index_data(row, fields),
+      })
+      .collect();
+    if let Err(err) = self.index_manager.index_rows(index_data) {
+      error!(
+        "[Database Index] failed to index rows of database {}: {}",
+        self.database_id, err
+      );
+    }
+
+    let related_view_ids = self.related_view_ids(fields);
+    let backlink_data = rows
+      .iter()
+      .map(|row| BacklinkIndexData {
+        object_id: row.id.to_string(),
+        workspace_id: self.workspace_id.clone(),
+        references: self.row_references(row, fields, &related_view_ids),
+      })
+      .collect();
+    if let Err(err) = self.backlink_manager.index_references(backlink_data) {
+      error!(
+        "[Database Index] failed to index references of database {}: {}",
+        self.database_id, err
+      );
+    }
+  }
+
+  /// Indexes all the rows of the database and marks the database as indexed, so that it
+  /// isn't indexed again on the next launch.
+  pub fn index_database(&self, rows: &[Row], fields: &[Field]) {
+    self.index_rows(rows, fields);
+    if let Err(err) = self
+      .index_manager
+      .mark_database_indexed(&self.database_id, &self.workspace_id)
+    {
+      error!(
+        "[Database Index] failed to mark database {} as indexed: {}",
+        self.database_id, err
+      );
+    }
+
+    // The database itself doesn't contain any references, it's only marked as indexed
+    let marker = BacklinkIndexData {
+      object_id: self.database_id.clone(),
+      workspace_id: self.workspace_id.clone(),
+      references: vec![],
+    };
+    if let Err(err) = self.backlink_manager.index_references(vec![marker]) {
+      error!(
+        "[Database Index] failed to mark references of database {} as indexed: {}",
+        self.database_id, err
+      );
+    }
+  }
+
+  pub fn remove_rows(&self, row_ids: &[RowId]) {
+    {
+      let mut pending_row_ids = self.pending_row_ids.lock();
+      row_ids.iter().for_each(|row_id| {
+        pending_row_ids.remove(row_id);
+      });
+    }
+    let row_ids = row_ids.iter().map(|row_id| row_id.to_string()).collect();
+    if let Err(err) = self.index_manager.remove_rows(row_ids.clone()) {
+      error!(
+        "[Database Index] failed to remove rows of database {}: {}",
+        self.database_id, err
+      );
+    }
+    if let Err(err) = self.backlink_manager.remove_references(row_ids) {
+      error!(
+        "[Database Index] failed to remove references of database {}: {}",
+        self.database_id, err
+      );
+    }
+  }
+
+  /// The views of the databases that the relation fields link to, by the id of the database.
+  fn related_view_ids(&self, fields: &[Field]) -> HashMap<String, Vec<String>> {
+    let related_database_ids = fields
+      .iter()
+      .filter_map(|field| field.get_type_option::<RelationTypeOption>(FieldType::Relation))
+      .map(|type_option| type_option.database_id)
+      .collect::<Vec<String>>();
+    if related_database_ids.is_empty() {
+      return HashMap::new();
+    }
+
+    self
+      .database_view_ids
+      .get_view_ids(&related_database_ids, &self.workspace_database)
+  }
+
+  /// The views that the cells of the row link to. A url cell links to a view if the last
+  /// segment of the url ends with the id of the view, like the url of a published view. A
+  /// relation cell links to the views of the related database.
+  fn row_references(
+    &self,
+    row: &Row,
+    fields: &[Field],
+    related_view_ids: &HashMap<String, Vec<String>>,
+  ) -> Vec<ViewReferenceData> {
+    let mut target_view_ids = vec![];
+    for field in fields {
+      let cell = match row.cells.get(&field.id) {
+        Some(cell) => cell,
+        None => continue,
+      };
+      match FieldType::from(field.field_type) {
+        FieldType::URL => {
+          if let Some(view_id) = view_id_from_url(&URLCellData::from(cell).data) {
+            target_view_ids.push(view_id);
+          }
+        },
+        FieldType::Relation => {
+          if RelationCellData::from(cell).row_ids.is_empty() {
+            continue;
+          }
+          let view_ids = field
+            .get_type_option::<RelationTypeOption>(FieldType::Relation)
+            .and_then(|type_option| related_view_ids.get(&type_option.database_id));
+          if let Some(view_ids) = view_ids {
+            target_view_ids.extend(view_ids.iter().cloned());
+          }
+        },
+        _ => {},
+      }
+    }
+
+    target_view_ids.sort();
+    target_view_ids.dedup();
+    target_view_ids
+      .into_iter()
+      .filter(|view_id| view_id != &self.inline_view_id)
+      .map(|view_id| ViewReferenceData {
+        source_view_id: self.inline_view_id.clone(),
+        block_id: row.id.to_string(),
+        target_view_id: view_id,
+      })
+      .collect()
+  }
+}
+
+/// The stringified content of each cell of the row. The cells whose text can't be searched
+/// for, like the checkbox or the relation cells, are skipped.
+fn cell_index_data(row: &Row, fields: &[Field]) -> Vec<DatabaseCellIndexData> {
+  fields
+    .iter()
+    .filter(|field| {
+      let field_type = FieldType::from(field.field_type);
+      !field_type.is_checkbox() && !field_type.is_checklist() && !field_type.is_relation()
+    })
+    .filter_map(|field| {
+      let cell = row.cells.get(&field.id)?;
+      let text = stringify_cell(cell, field);
+      if text.trim().is_empty() {
+        return None;
+      }
+      Some(DatabaseCellIndexData {
+        field_id: field.id.clone(),
+        field_name: field.name.clone(),
+        text,
+      })
+    })
+    .collect()
+}
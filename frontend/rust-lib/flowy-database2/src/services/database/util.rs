@@ -7,12 +7,28 @@ use crate::entities::{
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::Filter;
 use crate::services::group::GroupSetting;
+use crate::services::setting::GridLayoutSetting;
 use crate::services::sort::Sort;
 
+/// The gallery, list and timeline layouts are stored as the grid layout, the [GridLayoutSetting] of the
+/// view tells which of them is used.
+pub(crate) fn database_layout_pb_from_view(view: &DatabaseView) -> DatabaseLayoutPB {
+  let grid_setting = view
+    .layout_settings
+    .get(&DatabaseLayout::Grid)
+    .cloned()
+    .map(GridLayoutSetting::from);
+  DatabaseLayoutPB::from_view_layout(view.layout, grid_setting)
+}
+
 pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> DatabaseViewSettingPB {
-  let layout_type: DatabaseLayoutPB = view.layout.into();
+  let layout_type = database_layout_pb_from_view(&view);
   let layout_setting = if let Some(layout_setting) = view.layout_settings.get(&view.layout) {
     match view.layout {
+      DatabaseLayout::Grid => {
+        let grid_setting = layout_setting.clone().into();
+        DatabaseLayoutSettingPB::from_grid(grid_setting)
+      },
       DatabaseLayout::Board => {
         let board_setting = layout_setting.clone().into();
         DatabaseLayoutSettingPB::from_board(board_setting)
@@ -21,7 +37,6 @@ pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> Database
         let calendar_setting = layout_setting.clone().into();
         DatabaseLayoutSettingPB::from_calendar(calendar_setting)
       },
-      _ => DatabaseLayoutSettingPB::default(),
     }
   } else {
     DatabaseLayoutSettingPB::default()
@@ -7,8 +7,12 @@ use std::sync::Arc;
 
 use crate::entities::FieldType;
 use crate::services::field::{DateTypeOption, SingleSelectTypeOption};
-use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use crate::services::field_settings::{
+  default_field_settings_by_layout_map, default_field_settings_for_grid_layout,
+};
+use crate::services::setting::{
+  BoardLayoutSetting, CalendarLayoutSetting, GridLayout, GridLayoutSetting, TimelineLayoutSetting,
+};
 
 /// When creating a database, we need to resolve the dependencies of the views.
 /// Different database views have different dependencies. For example, a board
@@ -18,6 +22,8 @@ pub struct DatabaseLayoutDepsResolver {
   pub database: Arc<MutexDatabase>,
   /// The new database layout.
   pub database_layout: DatabaseLayout,
+  /// The layout that displays the rows when the new database layout is the grid layout.
+  pub grid_layout: GridLayout,
 }
 
 impl DatabaseLayoutDepsResolver {
@@ -25,9 +31,15 @@ impl DatabaseLayoutDepsResolver {
     Self {
       database,
       database_layout,
+      grid_layout: GridLayout::default(),
     }
   }
 
+  pub fn with_grid_layout(mut self, grid_layout: GridLayout) -> Self {
+    self.grid_layout = grid_layout;
+    self
+  }
+
   pub fn resolve_deps_when_create_database_linked_view(
     &self,
     view_id: &str,
@@ -36,9 +48,40 @@ impl DatabaseLayoutDepsResolver {
     Option<LayoutSetting>,
     Option<FieldSettingsByFieldIdMap>,
   ) {
-    match self.database_layout {
-      DatabaseLayout::Grid => (None, None, None),
-      DatabaseLayout::Board => {
+    match (self.database_layout, self.grid_layout) {
+      (DatabaseLayout::Grid, GridLayout::Table) => (None, None, None),
+      (DatabaseLayout::Grid, GridLayout::Gallery | GridLayout::List) => {
+        let layout_setting = GridLayoutSetting::new(self.grid_layout).into();
+        let fields = self.database.lock().get_fields(None);
+        let field_settings_map = default_field_settings_for_grid_layout(&fields, self.grid_layout);
+        (None, Some(layout_setting), Some(field_settings_map))
+      },
+      (DatabaseLayout::Grid, GridLayout::Timeline) => {
+        let fields = self.database.lock().get_fields(None);
+        let field_settings_map = default_field_settings_for_grid_layout(&fields, self.grid_layout);
+        let (date_field, date_field_id) = match fields
+          .into_iter()
+          .find(|field| FieldType::from(field.field_type) == FieldType::DateTime)
+        {
+          Some(field) => (None, field.id),
+          None => {
+            let date_field = self.create_date_field();
+            let date_field_id = date_field.id.clone();
+            (Some(date_field), date_field_id)
+          },
+        };
+        let layout_setting = GridLayoutSetting {
+          layout_ty: GridLayout::Timeline,
+          timeline: TimelineLayoutSetting::new(date_field_id),
+          ..Default::default()
+        };
+        (
+          date_field,
+          Some(layout_setting.into()),
+          Some(field_settings_map),
+        )
+      },
+      (DatabaseLayout::Board, _) => {
         let layout_settings = BoardLayoutSetting::new().into();
 
         let field = if !self
@@ -65,7 +108,7 @@ impl DatabaseLayoutDepsResolver {
           Some(field_settings_map.into()),
         )
       },
-      DatabaseLayout::Calendar => {
+      (DatabaseLayout::Calendar, _) => {
         match self
           .database
           .lock()
@@ -87,52 +130,95 @@ impl DatabaseLayoutDepsResolver {
     }
   }
 
-  /// If the new layout type is a calendar and there is not date field in the database, it will add
-  /// a new date field to the database and create the corresponding layout setting.
+  /// If the new layout type is a calendar or a timeline and there is not date field in the
+  /// database, it will add a new date field to the database and create the corresponding layout
+  /// setting.
   pub fn resolve_deps_when_update_layout_type(&self, view_id: &str) {
     let fields = self.database.lock().get_fields(None);
     // Insert the layout setting if it's not exist
-    match &self.database_layout {
-      DatabaseLayout::Grid => {},
-      DatabaseLayout::Board => {
+    match (self.database_layout, self.grid_layout) {
+      (DatabaseLayout::Grid, GridLayout::Table | GridLayout::Gallery | GridLayout::List) => {
+        self.update_grid_layout_setting(view_id, self.grid_layout, |_| {});
+      },
+      (DatabaseLayout::Grid, GridLayout::Timeline) => {
+        let date_field_id = self.get_or_create_date_field_id(fields);
+        self.update_grid_layout_setting(view_id, GridLayout::Timeline, |layout_setting| {
+          if layout_setting.timeline.start_field_id.is_empty() {
+            layout_setting.timeline.start_field_id = date_field_id;
+          }
+        });
+      },
+      (DatabaseLayout::Board, _) => {
         self.create_board_layout_setting_if_need(view_id);
       },
-      DatabaseLayout::Calendar => {
-        let date_field_id = match fields
-          .into_iter()
-          .find(|field| FieldType::from(field.field_type) == FieldType::DateTime)
-        {
-          None => {
-            tracing::trace!("Create a new date field after layout type change");
-            let field = self.create_date_field();
-            let field_id = field.id.clone();
-            self.database.lock().create_field(
-              None,
-              field,
-              &OrderObjectPosition::End,
-              default_field_settings_by_layout_map(),
-            );
-            field_id
-          },
-          Some(date_field) => date_field.id,
-        };
+      (DatabaseLayout::Calendar, _) => {
+        let date_field_id = self.get_or_create_date_field_id(fields);
         self.create_calendar_layout_setting_if_need(view_id, &date_field_id);
       },
     }
   }
 
+  fn get_or_create_date_field_id(&self, fields: Vec<Field>) -> String {
+    match fields
+      .into_iter()
+      .find(|field| FieldType::from(field.field_type) == FieldType::DateTime)
+    {
+      None => {
+        tracing::trace!("Create a new date field after layout type change");
+        let field = self.create_date_field();
+        let field_id = field.id.clone();
+        self.database.lock().create_field(
+          None,
+          field,
+          &OrderObjectPosition::End,
+          default_field_settings_by_layout_map(),
+        );
+        field_id
+      },
+      Some(date_field) => date_field.id,
+    }
+  }
+
+  /// The gallery, list and timeline layouts are stored as the grid layout. Switching between them
+  /// only updates the layout type of the grid layout setting, the settings of each layout are
+  /// kept.
+  fn update_grid_layout_setting(
+    &self,
+    view_id: &str,
+    grid_layout: GridLayout,
+    f: impl FnOnce(&mut GridLayoutSetting),
+  ) {
+    let layout_setting = self
+      .database
+      .lock()
+      .get_layout_setting::<GridLayoutSetting>(view_id, &DatabaseLayout::Grid);
+    let mut layout_setting = match layout_setting {
+      Some(mut layout_setting) => {
+        layout_setting.layout_ty = grid_layout;
+        layout_setting
+      },
+      None if grid_layout == GridLayout::Table => return,
+      None => GridLayoutSetting::new(grid_layout),
+    };
+    f(&mut layout_setting);
+    self
+      .database
+      .lock()
+      .insert_layout_setting(view_id, &DatabaseLayout::Grid, layout_setting);
+  }
+
   fn create_board_layout_setting_if_need(&self, view_id: &str) {
     if self
       .database
       .lock()
-      .get_layout_setting::<BoardLayoutSetting>(view_id, &self.database_layout)
+      .get_layout_setting::<BoardLayoutSetting>(view_id, &DatabaseLayout::Board)
       .is_none()
     {
       let layout_setting = BoardLayoutSetting::new();
       self
         .database
         .lock()
-        .insert_layout_setting(view_id, &self.database_layout, layout_setting);
+        .insert_layout_setting(view_id, &DatabaseLayout::Board, layout_setting);
     }
   }
 
@@ -140,14 +226,15 @@ impl DatabaseLayoutDepsResolver {
     if self
       .database
       .lock()
-      .get_layout_setting::<CalendarLayoutSetting>(view_id, &self.database_layout)
+      .get_layout_setting::<CalendarLayoutSetting>(view_id, &DatabaseLayout::Calendar)
       .is_none()
     {
       let layout_setting = CalendarLayoutSetting::new(field_id.to_string());
-      self
-        .database
-        .lock()
-        .insert_layout_setting(view_id, &self.database_layout, layout_setting);
+      self.database.lock().insert_layout_setting(
+        view_id,
+        &DatabaseLayout::Calendar,
+        layout_setting,
+      );
     }
   }
 
@@ -7,6 +7,7 @@ use lib_infra::future::{to_fut, Fut};
 use crate::services::calculations::{
   Calculation, CalculationsController, CalculationsDelegate, CalculationsTaskHandler,
 };
+use crate::services::comment::{get_row_comments_from_collab, with_row_collab};
 
 use crate::services::database_view::{
   gen_handler_id, DatabaseViewChangedNotifier, DatabaseViewOperation,
@@ -50,6 +51,22 @@ impl CalculationsDelegate for DatabaseViewCalculationsDelegateImpl {
     self.0.get_cells_for_field(view_id, field_id)
   }
 
+  fn count_row_comments(&self, view_id: &str) -> Fut<usize> {
+    let database = self.0.get_database();
+    let rows = self.0.get_rows(view_id);
+    to_fut(async move {
+      let rows = rows.await;
+      rows
+        .iter()
+        .filter_map(|row| {
+          with_row_collab(&database, &row.row.id, |collab| {
+            get_row_comments_from_collab(collab).count()
+          })
+        })
+        .sum()
+    })
+  }
+
   fn get_field(&self, field_id: &str) -> Option<Field> {
     self.0.get_field(field_id)
   }
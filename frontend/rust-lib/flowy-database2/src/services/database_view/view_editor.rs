@@ -18,11 +18,13 @@ use crate::entities::{
   DatabaseLayoutSettingPB, DeleteSortPayloadPB, FieldSettingsChangesetPB, FieldType,
   GroupChangesPB, GroupPB, LayoutSettingChangeset, LayoutSettingParams,
   RemoveCalculationChangesetPB, ReorderSortPayloadPB, RowMetaPB, RowsChangePB,
-  SortChangesetNotificationPB, SortPB, UpdateCalculationChangesetPB, UpdateSortPayloadPB,
+  SortChangesetNotificationPB, SortPB, TimelineEventPB, UpdateCalculationChangesetPB,
+  UpdateSortPayloadPB, UpdatedRowPB,
 };
 use crate::notification::{send_notification, DatabaseNotification};
 use crate::services::calculations::{Calculation, CalculationChangeset, CalculationsController};
 use crate::services::cell::{CellBuilder, CellCache};
+use crate::services::conditional_format::{ConditionalFormat, ConditionalFormatter};
 use crate::services::database::{database_view_setting_pb_from_view, DatabaseRowEvent, UpdatedRow};
 use crate::services::database_view::view_filter::make_filter_controller;
 use crate::services::database_view::view_group::{
@@ -35,10 +37,13 @@ use crate::services::database_view::{
   notify_did_update_setting, notify_did_update_sort, DatabaseLayoutDepsResolver,
   DatabaseViewChangedNotifier, DatabaseViewChangedReceiverRunner,
 };
+use crate::services::field::{DateCellData, DateTypeOption};
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::{Filter, FilterChangeset, FilterController};
 use crate::services::group::{GroupChangeset, GroupController, MoveGroupRowContext, RowChangeset};
-use crate::services::setting::CalendarLayoutSetting;
+use crate::services::setting::{
+  CalendarLayoutSetting, CardLayoutSetting, GridLayout, GridLayoutSetting, TimelineLayoutSetting,
+};
 use crate::services::sort::{Sort, SortChangeset, SortController};
 
 use super::notify_did_update_calculation;
@@ -52,6 +57,7 @@ pub struct DatabaseViewEditor {
   filter_controller: Arc<FilterController>,
   sort_controller: Arc<RwLock<SortController>>,
   calculations_controller: Arc<CalculationsController>,
+  cell_cache: CellCache,
   pub notifier: DatabaseViewChangedNotifier,
 }
 
@@ -86,7 +92,7 @@ impl DatabaseViewEditor {
       delegate.clone(),
       notifier.clone(),
       filter_controller.clone(),
-      cell_cache,
+      cell_cache.clone(),
     )
     .await;
 
@@ -113,6 +119,7 @@ impl DatabaseViewEditor {
       filter_controller,
       sort_controller,
       calculations_controller,
+      cell_cache,
       notifier,
     })
   }
@@ -171,13 +178,23 @@ impl DatabaseViewEditor {
   }
 
   pub async fn v_did_update_row_meta(&self, row_id: &RowId, row_detail: &RowDetail) {
-    let update_row = UpdatedRow::new(row_id.as_str()).with_row_meta(row_detail.clone());
-    let changeset = RowsChangePB::from_update(update_row.into());
+    let mut update_row = UpdatedRowPB::from(UpdatedRow::new(row_id.as_str()));
+    update_row.row_meta = Some(self.v_get_row_meta(row_detail).await);
+    let changeset = RowsChangePB::from_update(update_row);
     send_notification(&self.view_id, DatabaseNotification::DidUpdateRow)
       .payload(changeset)
       .send();
   }
 
+  pub async fn v_did_update_row_comments(&self) {
+    let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
+    af_spawn(async move {
+      if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
+        calculations_controller.did_receive_comments_changed().await;
+      }
+    });
+  }
+
   pub async fn v_did_create_row(&self, row_detail: &RowDetail, index: usize) {
     // Send the group notification if the current view has groups
     if let Some(controller) = self.group_controller.write().await.as_mut() {
@@ -196,6 +213,11 @@ impl DatabaseViewEditor {
     self
       .gen_did_create_row_view_tasks(index, row_detail.clone())
       .await;
+
+    let formatter = self.conditional_formatter().await;
+    if !formatter.is_empty() {
+      self.notify_did_update_row_formats(&formatter, row_detail);
+    }
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
@@ -287,6 +309,15 @@ impl DatabaseViewEditor {
       }
     }
 
+    let formatter = self.conditional_formatter().await;
+    let is_format_changed = match &field_id {
+      Some(field_id) => formatter.depends_on_field(field_id),
+      None => !formatter.is_empty(),
+    };
+    if is_format_changed {
+      self.notify_did_update_row_formats(&formatter, row_detail);
+    }
+
     // Each row update will trigger a calculations, filter and sort operation. We don't want
     // to block the main thread, so we spawn a new task to do the work.
     if let Some(field_id) = field_id {
@@ -682,9 +713,11 @@ impl DatabaseViewEditor {
   /// Returns the current calendar settings
   #[tracing::instrument(level = "trace", skip(self))]
   pub async fn v_get_layout_settings(&self, layout_ty: &DatabaseLayout) -> LayoutSettingParams {
-    let mut layout_setting = LayoutSettingParams::default();
+    let mut layout_setting = LayoutSettingParams::new(*layout_ty);
     match layout_ty {
-      DatabaseLayout::Grid => {},
+      DatabaseLayout::Grid => {
+        layout_setting.grid = Some(self.v_get_grid_layout_setting());
+      },
       DatabaseLayout::Board => {
         if let Some(value) = self.delegate.get_layout_setting(&self.view_id, layout_ty) {
           layout_setting.board = Some(value.into());
@@ -731,6 +764,29 @@ impl DatabaseViewEditor {
 
         Some(DatabaseLayoutSettingPB::from_board(layout_setting))
       },
+      DatabaseLayout::Grid => {
+        let mut grid_setting = self.v_get_grid_layout_setting();
+        if let Some(gallery) = params.gallery {
+          self.check_cover_field(&gallery)?;
+          grid_setting.gallery = gallery;
+        }
+        if let Some(list) = params.list {
+          self.check_cover_field(&list)?;
+          grid_setting.list = list;
+        }
+        if let Some(timeline) = params.timeline {
+          self.check_timeline_fields(&timeline)?;
+          grid_setting.timeline = timeline;
+        }
+
+        self.delegate.insert_layout_setting(
+          &self.view_id,
+          &params.layout_type,
+          grid_setting.clone().into(),
+        );
+
+        Some(DatabaseLayoutSettingPB::from_grid(grid_setting))
+      },
       DatabaseLayout::Calendar => {
         let layout_setting = params.calendar.unwrap();
 
@@ -750,7 +806,6 @@ impl DatabaseViewEditor {
           None
         }
       },
-      _ => None,
     };
 
     if let Some(payload) = layout_setting_pb {
@@ -762,6 +817,60 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// The gallery, list and timeline layouts are stored in the grid layout setting of the view
+  fn v_get_grid_layout_setting(&self) -> GridLayoutSetting {
+    self
+      .delegate
+      .get_layout_setting(&self.view_id, &DatabaseLayout::Grid)
+      .map(GridLayoutSetting::from)
+      .unwrap_or_default()
+  }
+
+  /// The cover of the cards can only be read from a url field
+  fn check_cover_field(&self, layout_setting: &CardLayoutSetting) -> FlowyResult<()> {
+    if layout_setting.cover_field_id.is_empty() {
+      return Ok(());
+    }
+
+    match self.delegate.get_field(&layout_setting.cover_field_id) {
+      Some(field) if FieldType::from(field.field_type) == FieldType::URL => Ok(()),
+      Some(_) => {
+        Err(FlowyError::invalid_data().with_context("The cover field must be a url field"))
+      },
+      None => Err(FlowyError::field_record_not_found()),
+    }
+  }
+
+  /// The start and end of the bars are read from date fields and the dependencies from a
+  /// relation field. The end and dependency fields are optional.
+  fn check_timeline_fields(&self, layout_setting: &TimelineLayoutSetting) -> FlowyResult<()> {
+    let fields = [
+      (&layout_setting.start_field_id, FieldType::DateTime, false),
+      (&layout_setting.end_field_id, FieldType::DateTime, true),
+      (
+        &layout_setting.dependency_field_id,
+        FieldType::Relation,
+        true,
+      ),
+    ];
+    for (field_id, expected_field_type, is_optional) in fields {
+      if field_id.is_empty() && is_optional {
+        continue;
+      }
+      let field = self
+        .delegate
+        .get_field(field_id)
+        .ok_or_else(FlowyError::field_record_not_found)?;
+      if FieldType::from(field.field_type) != expected_field_type {
+        return Err(FlowyError::invalid_data().with_context(format!(
+          "The timeline field {} must be a {} field",
+          field_id, expected_field_type
+        )));
+      }
+    }
+    Ok(())
+  }
+
   pub async fn v_did_delete_field(&self, deleted_field_id: &str) {
     let changeset = FilterChangeset::DeleteAllWithFieldId {
       field_id: deleted_field_id.to_string(),
@@ -898,11 +1007,8 @@ impl DatabaseViewEditor {
       .unwrap_or_default()
       .into();
 
-    let timestamp = date_cell
-      .into_date_field_cell_data()
-      .unwrap_or_default()
-      .timestamp
-      .unwrap_or_default();
+    let date_cell_data = date_cell.into_date_field_cell_data().unwrap_or_default();
+    let timestamp = date_cell_data.timestamp.unwrap_or_default();
 
     let (_, row_detail) = self.delegate.get_row(&self.view_id, &row_id).await?;
     Some(CalendarEventPB {
@@ -911,10 +1017,16 @@ impl DatabaseViewEditor {
       title,
       timestamp,
       is_scheduled: timestamp != 0,
+      is_recurring: !date_cell_data.recurrence.is_empty(),
     })
   }
 
-  pub async fn v_get_all_calendar_events(&self) -> Option<Vec<CalendarEventPB>> {
+  /// Returns an event for each row. If `range` is given, a recurring date has an event for each
+  /// of its occurrences in the range instead.
+  pub async fn v_get_all_calendar_events(
+    &self,
+    range: Option<(i64, i64)>,
+  ) -> Option<Vec<CalendarEventPB>> {
     let layout_ty = DatabaseLayout::Calendar;
     let calendar_setting = match self.v_get_layout_settings(&layout_ty).await.calendar {
       None => {
@@ -934,7 +1046,12 @@ impl DatabaseViewEditor {
       get_cells_for_field(self.delegate.clone(), &self.view_id, &primary_field.id).await;
 
     // Date
-    let timestamp_by_row_id = get_cells_for_field(
+    let date_type_option = self
+      .delegate
+      .get_field(&calendar_setting.field_id)
+      .and_then(|field| field.get_type_option::<DateTypeOption>(FieldType::DateTime))
+      .unwrap_or_default();
+    let date_by_row_id = get_cells_for_field(
       self.delegate.clone(),
       &self.view_id,
       &calendar_setting.field_id,
@@ -943,24 +1060,132 @@ impl DatabaseViewEditor {
     .into_iter()
     .map(|date_cell| {
       let row_id = date_cell.row_id.clone();
+      let date_cell_data = date_cell.into_date_field_cell_data().unwrap_or_default();
+      (row_id, date_cell_data)
+    })
+    .collect::<HashMap<RowId, DateCellData>>();
 
-      // timestamp
-      let timestamp = date_cell
-        .into_date_field_cell_data()
-        .map(|date_cell_data| date_cell_data.timestamp.unwrap_or_default())
-        .unwrap_or_default();
+    let mut events: Vec<CalendarEventPB> = vec![];
+    for text_cell in text_cells {
+      let row_id = text_cell.row_id.clone();
+      let date_cell_data = date_by_row_id.get(&row_id).cloned().unwrap_or_default();
+      let is_recurring = !date_cell_data.recurrence.is_empty();
+      let timestamps = match range {
+        Some((from, to)) if is_recurring => {
+          date_type_option.occurrences_between(&date_cell_data, from, to)
+        },
+        _ => vec![date_cell_data.timestamp.unwrap_or_default()],
+      };
+
+      let title: String = text_cell
+        .into_text_field_cell_data()
+        .unwrap_or_default()
+        .into();
 
-      (row_id, timestamp)
+      let (_, row_detail) = self.delegate.get_row(&self.view_id, &row_id).await?;
+      for timestamp in timestamps {
+        let event = CalendarEventPB {
+          row_meta: RowMetaPB::from(row_detail.as_ref()),
+          date_field_id: calendar_setting.field_id.clone(),
+          title: title.clone(),
+          timestamp,
+          is_scheduled: timestamp != 0,
+          is_recurring,
+        };
+        events.push(event);
+      }
+    }
+    Some(events)
+  }
+
+  pub async fn v_get_all_timeline_events(&self) -> Option<Vec<TimelineEventPB>> {
+    let timeline_setting = self.v_get_grid_layout_setting().timeline;
+    if timeline_setting.start_field_id.is_empty() {
+      // When switching to the timeline layout, the start field should be set
+      tracing::error!(
+        "Timeline start field not found in database view:{}",
+        self.view_id
+      );
+      return None;
+    }
+
+    // Text
+    let primary_field = self.delegate.get_primary_field().await?;
+    let text_cells =
+      get_cells_for_field(self.delegate.clone(), &self.view_id, &primary_field.id).await;
+
+    // Start and end dates
+    let start_date_by_row_id = get_cells_for_field(
+      self.delegate.clone(),
+      &self.view_id,
+      &timeline_setting.start_field_id,
+    )
+    .await
+    .into_iter()
+    .flat_map(|date_cell| {
+      let row_id = date_cell.row_id.clone();
+      date_cell
+        .into_date_field_cell_data()
+        .map(|date_cell_data| (row_id, date_cell_data))
     })
-    .collect::<HashMap<RowId, i64>>();
+    .collect::<HashMap<RowId, DateCellData>>();
 
-    let mut events: Vec<CalendarEventPB> = vec![];
+    let mut end_timestamp_by_row_id = HashMap::new();
+    if !timeline_setting.end_field_id.is_empty() {
+      end_timestamp_by_row_id = get_cells_for_field(
+        self.delegate.clone(),
+        &self.view_id,
+        &timeline_setting.end_field_id,
+      )
+      .await
+      .into_iter()
+      .flat_map(|date_cell| {
+        let row_id = date_cell.row_id.clone();
+        let timestamp = date_cell.into_date_field_cell_data()?.timestamp?;
+        Some((row_id, timestamp))
+      })
+      .collect::<HashMap<RowId, i64>>();
+    }
+
+    // Dependencies
+    let mut dependencies_by_row_id = HashMap::new();
+    if !timeline_setting.dependency_field_id.is_empty() {
+      dependencies_by_row_id = get_cells_for_field(
+        self.delegate.clone(),
+        &self.view_id,
+        &timeline_setting.dependency_field_id,
+      )
+      .await
+      .into_iter()
+      .flat_map(|relation_cell| {
+        let row_id = relation_cell.row_id.clone();
+        let row_ids = relation_cell
+          .into_relation_field_cell_data()?
+          .row_ids
+          .iter()
+          .map(|row_id| row_id.to_string())
+          .collect::<Vec<String>>();
+        Some((row_id, row_ids))
+      })
+      .collect::<HashMap<RowId, Vec<String>>>();
+    }
+
+    let mut events: Vec<TimelineEventPB> = vec![];
     for text_cell in text_cells {
       let row_id = text_cell.row_id.clone();
-      let timestamp = timestamp_by_row_id
-        .get(&row_id)
-        .cloned()
+      let start_date = start_date_by_row_id.get(&row_id);
+      let start_timestamp = start_date
+        .and_then(|date| date.timestamp)
         .unwrap_or_default();
+      let end_timestamp = if timeline_setting.end_field_id.is_empty() {
+        start_date
+          .filter(|date| date.is_range)
+          .and_then(|date| date.end_timestamp)
+      } else {
+        end_timestamp_by_row_id.get(&row_id).cloned()
+      }
+      .unwrap_or(start_timestamp)
+      .max(start_timestamp);
 
       let title = text_cell
         .into_text_field_cell_data()
@@ -968,12 +1193,13 @@ impl DatabaseViewEditor {
         .into();
 
       let (_, row_detail) = self.delegate.get_row(&self.view_id, &row_id).await?;
-      let event = CalendarEventPB {
+      let event = TimelineEventPB {
         row_meta: RowMetaPB::from(row_detail.as_ref()),
-        date_field_id: calendar_setting.field_id.clone(),
         title,
-        timestamp,
-        is_scheduled: timestamp != 0,
+        start_timestamp,
+        end_timestamp,
+        is_scheduled: start_timestamp != 0,
+        dependencies: dependencies_by_row_id.remove(&row_id).unwrap_or_default(),
       };
       events.push(event);
     }
@@ -985,7 +1211,13 @@ impl DatabaseViewEditor {
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn v_update_layout_type(&self, new_layout_type: DatabaseLayout) -> FlowyResult<()> {
+  /// Updates the layout of the view. The `grid_layout` tells how the rows are displayed if the
+  /// new layout is the grid layout, e.g. as a gallery.
+  pub async fn v_update_layout_type(
+    &self,
+    new_layout_type: DatabaseLayout,
+    grid_layout: GridLayout,
+  ) -> FlowyResult<()> {
     self
       .delegate
       .update_layout_type(&self.view_id, &new_layout_type);
@@ -993,7 +1225,8 @@ impl DatabaseViewEditor {
     // using the {} brackets to denote the lifetime of the resolver. Because the DatabaseLayoutDepsResolver
     // is not sync and send, so we can't pass it to the async block.
     {
-      let resolver = DatabaseLayoutDepsResolver::new(self.delegate.get_database(), new_layout_type);
+      let resolver = DatabaseLayoutDepsResolver::new(self.delegate.get_database(), new_layout_type)
+        .with_grid_layout(grid_layout);
       resolver.resolve_deps_when_update_layout_type(&self.view_id);
     }
 
@@ -1006,9 +1239,13 @@ impl DatabaseViewEditor {
     )
     .await?;
 
+    let layout = match new_layout_type {
+      DatabaseLayout::Grid => grid_layout.into(),
+      _ => new_layout_type.into(),
+    };
     let payload = DatabaseLayoutMetaPB {
       view_id: self.view_id.clone(),
-      layout: new_layout_type.into(),
+      layout,
     };
     send_notification(&self.view_id, DatabaseNotification::DidUpdateDatabaseLayout)
       .payload(payload)
@@ -1043,6 +1280,160 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// Returns the conditional formatting rules of the view, in the order of the fields.
+  pub async fn v_get_conditional_formats(&self) -> Vec<ConditionalFormat> {
+    let fields = self.delegate.get_fields(&self.view_id, None).await;
+    self.get_conditional_formats(&fields)
+  }
+
+  pub async fn v_create_conditional_format(&self, format: ConditionalFormat) -> FlowyResult<()> {
+    if self.delegate.get_field(&format.field_id).is_none() {
+      return Err(FlowyError::record_not_found().with_context("Field not found"));
+    }
+
+    let mut formats = self.get_field_conditional_formats(&format.field_id);
+    formats.push(format.clone());
+    self
+      .delegate
+      .update_conditional_formats(&self.view_id, &format.field_id, formats);
+    self.notify_did_update_all_row_formats().await;
+    Ok(())
+  }
+
+  pub async fn v_update_conditional_format(&self, format: ConditionalFormat) -> FlowyResult<()> {
+    let old_format = self
+      .v_get_conditional_formats()
+      .await
+      .into_iter()
+      .find(|old_format| old_format.id == format.id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Conditional format not found"))?;
+
+    if old_format.field_id == format.field_id {
+      let mut formats = self.get_field_conditional_formats(&format.field_id);
+      for old_format in formats.iter_mut() {
+        if old_format.id == format.id {
+          *old_format = format.clone();
+        }
+      }
+      self
+        .delegate
+        .update_conditional_formats(&self.view_id, &format.field_id, formats);
+    } else {
+      // The condition moved to another field, so does the rule
+      if self.delegate.get_field(&format.field_id).is_none() {
+        return Err(FlowyError::record_not_found().with_context("Field not found"));
+      }
+      let mut old_formats = self.get_field_conditional_formats(&old_format.field_id);
+      old_formats.retain(|old_format| old_format.id != format.id);
+      self
+        .delegate
+        .update_conditional_formats(&self.view_id, &old_format.field_id, old_formats);
+
+      let mut formats = self.get_field_conditional_formats(&format.field_id);
+      formats.push(format.clone());
+      self
+        .delegate
+        .update_conditional_formats(&self.view_id, &format.field_id, formats);
+    }
+    self.notify_did_update_all_row_formats().await;
+    Ok(())
+  }
+
+  pub async fn v_delete_conditional_format(&self, format_id: &str) -> FlowyResult<()> {
+    let format = self
+      .v_get_conditional_formats()
+      .await
+      .into_iter()
+      .find(|format| format.id == format_id)
+      .ok_or_else(|| FlowyError::record_not_found().with_context("Conditional format not found"))?;
+
+    let mut formats = self.get_field_conditional_formats(&format.field_id);
+    formats.retain(|format| format.id != format_id);
+    self
+      .delegate
+      .update_conditional_formats(&self.view_id, &format.field_id, formats);
+    self.notify_did_update_all_row_formats().await;
+    Ok(())
+  }
+
+  /// Returns the meta of the row, with the colors given by the conditional formatting rules.
+  pub async fn v_get_row_meta(&self, row_detail: &RowDetail) -> RowMetaPB {
+    self
+      .conditional_formatter()
+      .await
+      .format_row_meta(row_detail)
+  }
+
+  pub async fn v_get_row_metas(&self, row_details: &[Arc<RowDetail>]) -> Vec<RowMetaPB> {
+    let formatter = self.conditional_formatter().await;
+    row_details
+      .iter()
+      .map(|row_detail| formatter.format_row_meta(row_detail))
+      .collect()
+  }
+
+  fn get_conditional_formats(&self, fields: &[Field]) -> Vec<ConditionalFormat> {
+    let field_ids = fields
+      .iter()
+      .map(|field| field.id.clone())
+      .collect::<Vec<_>>();
+    let mut field_settings = self.delegate.get_field_settings(&self.view_id, &field_ids);
+    field_ids
+      .iter()
+      .filter_map(|field_id| field_settings.remove(field_id))
+      .flat_map(|field_settings| field_settings.conditional_formats)
+      .collect()
+  }
+
+  fn get_field_conditional_formats(&self, field_id: &str) -> Vec<ConditionalFormat> {
+    self
+      .delegate
+      .get_field_settings(&self.view_id, &[field_id.to_string()])
+      .remove(field_id)
+      .map(|field_settings| field_settings.conditional_formats)
+      .unwrap_or_default()
+  }
+
+  async fn conditional_formatter(&self) -> ConditionalFormatter {
+    let fields = self.delegate.get_fields(&self.view_id, None).await;
+    let formats = self.get_conditional_formats(&fields);
+    ConditionalFormatter::new(formats, fields, self.cell_cache.clone())
+  }
+
+  fn notify_did_update_row_formats(
+    &self,
+    formatter: &ConditionalFormatter,
+    row_detail: &RowDetail,
+  ) {
+    let mut update_row = UpdatedRowPB::from(UpdatedRow::new(row_detail.row.id.as_str()));
+    update_row.row_meta = Some(formatter.format_row_meta(row_detail));
+    send_notification(&self.view_id, DatabaseNotification::DidUpdateRow)
+      .payload(RowsChangePB::from_update(update_row))
+      .send();
+  }
+
+  /// Sends the formats of every row of the view after the rules change.
+  async fn notify_did_update_all_row_formats(&self) {
+    let row_details = self.v_get_rows().await;
+    let updated_rows = self
+      .v_get_row_metas(&row_details)
+      .await
+      .into_iter()
+      .map(|row_meta| {
+        let mut update_row = UpdatedRowPB::from(UpdatedRow::new(&row_meta.id));
+        update_row.row_meta = Some(row_meta);
+        update_row
+      })
+      .collect();
+    let changeset = RowsChangePB {
+      updated_rows,
+      ..Default::default()
+    };
+    send_notification(&self.view_id, DatabaseNotification::DidUpdateRow)
+      .payload(changeset)
+      .send();
+  }
+
   async fn mut_group_controller<F, T>(&self, f: F) -> Option<T>
   where
     F: FnOnce(&mut Box<dyn GroupController>, Field) -> FlowyResult<T>,
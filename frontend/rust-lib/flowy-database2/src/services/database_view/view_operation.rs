@@ -13,6 +13,7 @@ use lib_infra::priority_task::TaskDispatcher;
 
 use crate::entities::{FieldSettingsChangesetPB, FieldType};
 use crate::services::calculations::Calculation;
+use crate::services::conditional_format::ConditionalFormat;
 use crate::services::field::TypeOptionCellDataHandler;
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::Filter;
@@ -127,4 +128,12 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
   ) -> HashMap<String, FieldSettings>;
 
   fn update_field_settings(&self, params: FieldSettingsChangesetPB);
+
+  /// Replaces the conditional formatting rules of the field in the view
+  fn update_conditional_formats(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    conditional_formats: Vec<ConditionalFormat>,
+  );
 }
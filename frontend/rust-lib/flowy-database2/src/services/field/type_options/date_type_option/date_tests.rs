@@ -9,7 +9,7 @@ mod tests {
   use crate::entities::FieldType;
   use crate::services::cell::{CellDataChangeset, CellDataDecoder};
   use crate::services::field::{
-    DateCellChangeset, DateFormat, DateTypeOption, FieldBuilder, TimeFormat,
+    DateCellChangeset, DateCellData, DateFormat, DateTypeOption, FieldBuilder, TimeFormat,
   };
 
   #[test]
@@ -532,6 +532,90 @@ mod tests {
     );
   }
 
+  #[test]
+  fn recurring_date_test() {
+    let type_option = DateTypeOption::test();
+
+    let (_, cell_data) = type_option
+      .apply_changeset(
+        DateCellChangeset {
+          date: Some(1653609600),
+          end_date: Some(1653696000),
+          is_range: Some(true),
+          recurrence: Some("freq=weekly;count=3".to_owned()),
+          ..Default::default()
+        },
+        None,
+      )
+      .unwrap();
+    assert_eq!(cell_data.recurrence, "FREQ=WEEKLY;COUNT=3");
+    assert_eq!(
+      type_option.occurrences_between(&cell_data, 1653609600, 1656633600),
+      vec![1653609600, 1654214400, 1654819200]
+    );
+
+    let next_cell_data = type_option.next_occurrence(&cell_data).unwrap();
+    assert_eq!(next_cell_data.timestamp, Some(1654214400));
+    assert_eq!(next_cell_data.end_timestamp, Some(1654300800));
+    assert_eq!(next_cell_data.recurrence, "FREQ=WEEKLY;COUNT=2");
+
+    let (_, cell_data) = type_option
+      .apply_changeset(
+        DateCellChangeset {
+          recurrence: Some("".to_owned()),
+          ..Default::default()
+        },
+        Some((&cell_data).into()),
+      )
+      .unwrap();
+    assert!(cell_data.recurrence.is_empty());
+    assert!(type_option.next_occurrence(&cell_data).is_none());
+
+    // A timestamp out of the range of the dates has no occurrences
+    let cell_data = DateCellData {
+      timestamp: Some(i64::MAX),
+      recurrence: "FREQ=DAILY".to_owned(),
+      ..Default::default()
+    };
+    assert!(type_option
+      .occurrences_between(&cell_data, 0, i64::MAX)
+      .is_empty());
+    assert!(type_option.next_occurrence(&cell_data).is_none());
+  }
+
+  #[test]
+  fn recurring_date_follows_daylight_saving_time_test() {
+    let mut type_option = DateTypeOption::test();
+    type_option.timezone_id = "America/New_York".to_owned();
+
+    // 09:00 in New York, the day before daylight saving time starts
+    let cell_data = DateCellData {
+      timestamp: Some(1709992800),
+      recurrence: "FREQ=DAILY;COUNT=3".to_owned(),
+      ..Default::default()
+    };
+    assert_eq!(
+      type_option.occurrences_between(&cell_data, 0, i64::MAX),
+      vec![1709992800, 1710075600, 1710162000]
+    );
+    let next_cell_data = type_option.next_occurrence(&cell_data).unwrap();
+    assert_eq!(next_cell_data.timestamp, Some(1710075600));
+  }
+
+  #[test]
+  fn invalid_recurrence_test() {
+    let type_option = DateTypeOption::test();
+    let result = type_option.apply_changeset(
+      DateCellChangeset {
+        date: Some(1653609600),
+        recurrence: Some("FREQ=SOMETIMES".to_owned()),
+        ..Default::default()
+      },
+      None,
+    );
+    assert!(result.is_err());
+  }
+
   fn assert_date(
     type_option: &DateTypeOption,
     field: &Field,
@@ -9,6 +9,7 @@ use collab_database::rows::Cell;
 use serde::{Deserialize, Serialize};
 
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use lib_infra::recurrence::Recurrence;
 
 use crate::entities::{DateCellDataPB, DateFilterPB};
 use crate::services::cell::{CellDataChangeset, CellDataDecoder};
@@ -77,6 +78,7 @@ impl TypeOptionCellDataSerde for DateTypeOption {
     let (end_date, end_time) = self.formatted_date_time_from_timestamp(&end_timestamp);
 
     let reminder_id = cell_data.reminder_id;
+    let recurrence = cell_data.recurrence;
 
     DateCellDataPB {
       date,
@@ -88,6 +90,7 @@ impl TypeOptionCellDataSerde for DateTypeOption {
       include_time,
       is_range,
       reminder_id,
+      recurrence,
     }
   }
 
@@ -182,16 +185,59 @@ impl DateTypeOption {
     }
   }
 
+  /// Returns the occurrences of the recurring date between `from` and `to`, inclusive. Returns
+  /// the date itself if it doesn't repeat and is in the range.
+  pub fn occurrences_between(&self, cell_data: &DateCellData, from: i64, to: i64) -> Vec<i64> {
+    let timestamp = match cell_data.timestamp {
+      None => return vec![],
+      Some(timestamp) => timestamp,
+    };
+    match cell_data.get_recurrence() {
+      None if timestamp >= from && timestamp <= to => vec![timestamp],
+      None => vec![],
+      // A timestamp out of the range of the dates has no occurrences
+      Some(_) if NaiveDateTime::from_timestamp_opt(timestamp, 0).is_none() => vec![],
+      Some(recurrence) => match self.get_timezone() {
+        Some(timezone) => recurrence.occurrences_between(timestamp, from, to, timezone),
+        None => recurrence.occurrences_between(timestamp, from, to, Local),
+      },
+    }
+  }
+
+  /// Returns the date moved to its next occurrence, or None if the date doesn't repeat or the
+  /// series has ended. The end of a range moves along with its start.
+  pub fn next_occurrence(&self, cell_data: &DateCellData) -> Option<DateCellData> {
+    let timestamp = cell_data.timestamp?;
+    let recurrence = cell_data.get_recurrence()?;
+    NaiveDateTime::from_timestamp_opt(timestamp, 0)?;
+    let (next_timestamp, next_recurrence) = match self.get_timezone() {
+      Some(timezone) => recurrence.next(timestamp, timezone)?,
+      None => recurrence.next(timestamp, Local)?,
+    };
+    Some(DateCellData {
+      timestamp: Some(next_timestamp),
+      end_timestamp: cell_data
+        .end_timestamp
+        .map(|end_timestamp| end_timestamp + next_timestamp - timestamp),
+      recurrence: next_recurrence.to_string(),
+      ..cell_data.clone()
+    })
+  }
+
   /// returns offset of Tz timezone if provided or of the local timezone otherwise
   fn get_timezone_offset(&self, date_time: NaiveDateTime) -> FixedOffset {
-    let current_timezone_offset = Local::now().offset().fix();
+    match self.get_timezone() {
+      Some(timezone) => timezone.offset_from_utc_datetime(&date_time).fix(),
+      None => Local::now().offset().fix(),
+    }
+  }
+
+  /// returns the Tz timezone if provided, or None if the local timezone is used
+  fn get_timezone(&self) -> Option<Tz> {
     if self.timezone_id.is_empty() {
-      current_timezone_offset
+      None
     } else {
-      match Tz::from_str(&self.timezone_id) {
-        Ok(timezone) => timezone.offset_from_utc_datetime(&date_time).fix(),
-        Err(_) => current_timezone_offset,
-      }
+      Tz::from_str(&self.timezone_id).ok()
     }
   }
 }
@@ -243,20 +289,27 @@ impl CellDataChangeset for DateTypeOption {
     cell: Option<Cell>,
   ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
     // old date cell data
-    let (previous_timestamp, previous_end_timestamp, include_time, is_range, reminder_id) =
-      match cell {
-        Some(cell) => {
-          let cell_data = DateCellData::from(&cell);
-          (
-            cell_data.timestamp,
-            cell_data.end_timestamp,
-            cell_data.include_time,
-            cell_data.is_range,
-            cell_data.reminder_id,
-          )
-        },
-        None => (None, None, false, false, String::new()),
-      };
+    let (
+      previous_timestamp,
+      previous_end_timestamp,
+      include_time,
+      is_range,
+      reminder_id,
+      recurrence,
+    ) = match cell {
+      Some(cell) => {
+        let cell_data = DateCellData::from(&cell);
+        (
+          cell_data.timestamp,
+          cell_data.end_timestamp,
+          cell_data.include_time,
+          cell_data.is_range,
+          cell_data.reminder_id,
+          cell_data.recurrence,
+        )
+      },
+      None => (None, None, false, false, String::new(), String::new()),
+    };
 
     if changeset.clear_flag == Some(true) {
       let cell_data = DateCellData {
@@ -265,6 +318,7 @@ impl CellDataChangeset for DateTypeOption {
         include_time,
         is_range,
         reminder_id: String::new(),
+        recurrence: String::new(),
       };
 
       return Ok((Cell::from(&cell_data), cell_data));
@@ -274,6 +328,13 @@ impl CellDataChangeset for DateTypeOption {
     let include_time = changeset.include_time.unwrap_or(include_time);
     let is_range = changeset.is_range.unwrap_or(is_range);
     let reminder_id = changeset.reminder_id.unwrap_or(reminder_id);
+    let recurrence = match changeset.recurrence {
+      None => recurrence,
+      Some(recurrence) if recurrence.trim().is_empty() => String::new(),
+      Some(recurrence) => Recurrence::from_str(&recurrence)
+        .map_err(|err| FlowyError::new(ErrorCode::InvalidRecurrenceRule, err))?
+        .to_string(),
+    };
 
     // Calculate the timestamp in the time zone specified in type option. If
     // a new timestamp is included in the changeset without an accompanying
@@ -314,6 +375,7 @@ impl CellDataChangeset for DateTypeOption {
       include_time,
       is_range,
       reminder_id,
+      recurrence,
     };
 
     Ok((Cell::from(&cell_data), cell_data))
@@ -6,9 +6,11 @@ use collab_database::rows::{new_cell_builder, Cell};
 use serde::de::Visitor;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 use strum_macros::EnumIter;
 
 use flowy_error::{internal_error, FlowyResult};
+use lib_infra::recurrence::Recurrence;
 
 use crate::entities::{DateCellDataPB, FieldType};
 use crate::services::cell::CellProtobufBlobParser;
@@ -24,6 +26,8 @@ pub struct DateCellChangeset {
   pub is_range: Option<bool>,
   pub clear_flag: Option<bool>,
   pub reminder_id: Option<String>,
+  /// The recurrence rule, see [Recurrence]. An empty rule removes the recurrence.
+  pub recurrence: Option<String>,
 }
 
 #[derive(Default, Clone, Debug, Serialize)]
@@ -35,6 +39,9 @@ pub struct DateCellData {
   #[serde(default)]
   pub is_range: bool,
   pub reminder_id: String,
+  /// The recurrence rule of the date, empty if the date doesn't repeat. See [Recurrence].
+  #[serde(default)]
+  pub recurrence: String,
 }
 
 impl DateCellData {
@@ -45,8 +52,16 @@ impl DateCellData {
       include_time,
       is_range,
       reminder_id,
+      recurrence: String::new(),
     }
   }
+
+  pub fn get_recurrence(&self) -> Option<Recurrence> {
+    if self.recurrence.is_empty() {
+      return None;
+    }
+    Recurrence::from_str(&self.recurrence).ok()
+  }
 }
 
 impl TypeOptionCellData for DateCellData {
@@ -66,6 +81,7 @@ impl From<&Cell> for DateCellData {
     let include_time = cell.get_bool_value("include_time").unwrap_or_default();
     let is_range = cell.get_bool_value("is_range").unwrap_or_default();
     let reminder_id = cell.get_str_value("reminder_id").unwrap_or_default();
+    let recurrence = cell.get_str_value("recurrence").unwrap_or_default();
 
     Self {
       timestamp,
@@ -73,6 +89,7 @@ impl From<&Cell> for DateCellData {
       include_time,
       is_range,
       reminder_id,
+      recurrence,
     }
   }
 }
@@ -85,6 +102,7 @@ impl From<&DateCellDataPB> for DateCellData {
       include_time: data.include_time,
       is_range: data.is_range,
       reminder_id: data.reminder_id.to_owned(),
+      recurrence: data.recurrence.to_owned(),
     }
   }
 }
@@ -107,6 +125,7 @@ impl From<&DateCellData> for Cell {
       .insert_bool_value("include_time", cell_data.include_time)
       .insert_bool_value("is_range", cell_data.is_range)
       .insert_str_value("reminder_id", cell_data.reminder_id.to_owned())
+      .insert_str_value("recurrence", cell_data.recurrence.to_owned())
       .build()
   }
 }
@@ -137,6 +156,7 @@ impl<'de> serde::Deserialize<'de> for DateCellData {
           include_time: false,
           is_range: false,
           reminder_id: String::new(),
+          recurrence: String::new(),
         })
       }
 
@@ -156,6 +176,7 @@ impl<'de> serde::Deserialize<'de> for DateCellData {
         let mut include_time: Option<bool> = None;
         let mut is_range: Option<bool> = None;
         let mut reminder_id: Option<String> = None;
+        let mut recurrence: Option<String> = None;
 
         while let Some(key) = map.next_key()? {
           match key {
@@ -174,6 +195,9 @@ impl<'de> serde::Deserialize<'de> for DateCellData {
             "reminder_id" => {
               reminder_id = map.next_value()?;
             },
+            "recurrence" => {
+              recurrence = map.next_value()?;
+            },
             _ => {},
           }
         }
@@ -181,6 +205,7 @@ impl<'de> serde::Deserialize<'de> for DateCellData {
         let include_time = include_time.unwrap_or_default();
         let is_range = is_range.unwrap_or_default();
         let reminder_id = reminder_id.unwrap_or_default();
+        let recurrence = recurrence.unwrap_or_default();
 
         Ok(DateCellData {
           timestamp,
@@ -188,6 +213,7 @@ impl<'de> serde::Deserialize<'de> for DateCellData {
           include_time,
           is_range,
           reminder_id,
+          recurrence,
         })
       }
     }
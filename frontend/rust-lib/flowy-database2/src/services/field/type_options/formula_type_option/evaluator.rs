@@ -0,0 +1,486 @@
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Datelike, Duration, Months, TimeZone, Timelike, Utc};
+
+use crate::services::field::formula_type_option::parser::{BinaryOp, Expr, FormulaError, UnaryOp};
+
+/// The value produced by evaluating a formula expression.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum FormulaValue {
+  #[default]
+  Empty,
+  Number(f64),
+  Text(String),
+  Bool(bool),
+  /// Unix timestamp in seconds.
+  Date(i64),
+}
+
+impl FormulaValue {
+  pub fn as_number(&self) -> Option<f64> {
+    match self {
+      FormulaValue::Empty => Some(0.0),
+      FormulaValue::Number(num) => Some(*num),
+      FormulaValue::Text(s) => s.trim().parse::<f64>().ok(),
+      FormulaValue::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+      FormulaValue::Date(timestamp) => Some(*timestamp as f64),
+    }
+  }
+
+  pub fn is_truthy(&self) -> bool {
+    match self {
+      FormulaValue::Empty => false,
+      FormulaValue::Number(num) => *num != 0.0,
+      FormulaValue::Text(s) => !s.is_empty(),
+      FormulaValue::Bool(b) => *b,
+      FormulaValue::Date(_) => true,
+    }
+  }
+
+  pub fn is_empty(&self) -> bool {
+    match self {
+      FormulaValue::Empty => true,
+      FormulaValue::Text(s) => s.is_empty(),
+      _ => false,
+    }
+  }
+
+  pub fn to_text(&self) -> String {
+    match self {
+      FormulaValue::Empty => "".to_string(),
+      FormulaValue::Number(num) => format_number(*num),
+      FormulaValue::Text(s) => s.clone(),
+      FormulaValue::Bool(b) => b.to_string(),
+      FormulaValue::Date(timestamp) => match to_datetime(*timestamp) {
+        Some(date) if date.hour() == 0 && date.minute() == 0 => date.format("%Y/%m/%d").to_string(),
+        Some(date) => date.format("%Y/%m/%d %H:%M").to_string(),
+        None => "".to_string(),
+      },
+    }
+  }
+}
+
+/// Provides the values that a formula can't compute on its own, e.g. the cells of the row that the
+/// formula is evaluated against.
+pub trait FormulaContext {
+  fn resolve_prop(&self, name: &str) -> Result<FormulaValue, FormulaError>;
+
+  /// Returns the current time in seconds. Overridden in tests to get stable results.
+  fn now(&self) -> i64 {
+    Utc::now().timestamp()
+  }
+}
+
+pub fn evaluate(expr: &Expr, ctx: &dyn FormulaContext) -> Result<FormulaValue, FormulaError> {
+  match expr {
+    Expr::Number(num) => Ok(FormulaValue::Number(*num)),
+    Expr::Text(s) => Ok(FormulaValue::Text(s.clone())),
+    Expr::Bool(b) => Ok(FormulaValue::Bool(*b)),
+    Expr::Prop(name) => ctx.resolve_prop(name),
+    Expr::Unary { op, expr } => {
+      let value = evaluate(expr, ctx)?;
+      match op {
+        UnaryOp::Neg => Ok(FormulaValue::Number(-expect_number(&value)?)),
+        UnaryOp::Not => Ok(FormulaValue::Bool(!value.is_truthy())),
+      }
+    },
+    Expr::Binary { op, left, right } => evaluate_binary(*op, left, right, ctx),
+    Expr::Call { name, args } => evaluate_call(name, args, ctx),
+  }
+}
+
+fn evaluate_binary(
+  op: BinaryOp,
+  left: &Expr,
+  right: &Expr,
+  ctx: &dyn FormulaContext,
+) -> Result<FormulaValue, FormulaError> {
+  // Boolean operators short-circuit, so the right side is only evaluated when required.
+  match op {
+    BinaryOp::And => {
+      let left = evaluate(left, ctx)?;
+      if !left.is_truthy() {
+        return Ok(FormulaValue::Bool(false));
+      }
+      return Ok(FormulaValue::Bool(evaluate(right, ctx)?.is_truthy()));
+    },
+    BinaryOp::Or => {
+      let left = evaluate(left, ctx)?;
+      if left.is_truthy() {
+        return Ok(FormulaValue::Bool(true));
+      }
+      return Ok(FormulaValue::Bool(evaluate(right, ctx)?.is_truthy()));
+    },
+    _ => {},
+  }
+
+  let left = evaluate(left, ctx)?;
+  let right = evaluate(right, ctx)?;
+  match op {
+    BinaryOp::Add => match (&left, &right) {
+      (FormulaValue::Text(_), _) | (_, FormulaValue::Text(_)) => Ok(FormulaValue::Text(format!(
+        "{}{}",
+        left.to_text(),
+        right.to_text()
+      ))),
+      _ => Ok(FormulaValue::Number(
+        expect_number(&left)? + expect_number(&right)?,
+      )),
+    },
+    BinaryOp::Sub => Ok(FormulaValue::Number(
+      expect_number(&left)? - expect_number(&right)?,
+    )),
+    BinaryOp::Mul => Ok(FormulaValue::Number(
+      expect_number(&left)? * expect_number(&right)?,
+    )),
+    BinaryOp::Div => {
+      let divisor = expect_number(&right)?;
+      if divisor == 0.0 {
+        return Err(FormulaError::InvalidArgument(
+          "division by zero".to_string(),
+        ));
+      }
+      Ok(FormulaValue::Number(expect_number(&left)? / divisor))
+    },
+    BinaryOp::Mod => {
+      let divisor = expect_number(&right)?;
+      if divisor == 0.0 {
+        return Err(FormulaError::InvalidArgument(
+          "division by zero".to_string(),
+        ));
+      }
+      Ok(FormulaValue::Number(expect_number(&left)? % divisor))
+    },
+    BinaryOp::Eq => Ok(FormulaValue::Bool(
+      compare_values(&left, &right) == Ordering::Equal,
+    )),
+    BinaryOp::NotEq => Ok(FormulaValue::Bool(
+      compare_values(&left, &right) != Ordering::Equal,
+    )),
+    BinaryOp::Lt => Ok(FormulaValue::Bool(
+      compare_values(&left, &right) == Ordering::Less,
+    )),
+    BinaryOp::LtEq => Ok(FormulaValue::Bool(
+      compare_values(&left, &right) != Ordering::Greater,
+    )),
+    BinaryOp::Gt => Ok(FormulaValue::Bool(
+      compare_values(&left, &right) == Ordering::Greater,
+    )),
+    BinaryOp::GtEq => Ok(FormulaValue::Bool(
+      compare_values(&left, &right) != Ordering::Less,
+    )),
+    BinaryOp::And | BinaryOp::Or => unreachable!(),
+  }
+}
+
+fn evaluate_call(
+  name: &str,
+  args: &[Expr],
+  ctx: &dyn FormulaContext,
+) -> Result<FormulaValue, FormulaError> {
+  // `if` is lazy, only the selected branch gets evaluated.
+  if name == "if" {
+    expect_args(name, args, 3)?;
+    let condition = evaluate(&args[0], ctx)?;
+    return if condition.is_truthy() {
+      evaluate(&args[1], ctx)
+    } else {
+      evaluate(&args[2], ctx)
+    };
+  }
+
+  let values = args
+    .iter()
+    .map(|arg| evaluate(arg, ctx))
+    .collect::<Result<Vec<FormulaValue>, FormulaError>>()?;
+
+  match name {
+    "concat" => Ok(FormulaValue::Text(
+      values.iter().map(|value| value.to_text()).collect(),
+    )),
+    "length" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Number(
+        values[0].to_text().chars().count() as f64
+      ))
+    },
+    "lower" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Text(values[0].to_text().to_lowercase()))
+    },
+    "upper" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Text(values[0].to_text().to_uppercase()))
+    },
+    "trim" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Text(values[0].to_text().trim().to_string()))
+    },
+    "contains" => {
+      expect_args(name, args, 2)?;
+      Ok(FormulaValue::Bool(
+        values[0].to_text().contains(&values[1].to_text()),
+      ))
+    },
+    "replace" => {
+      expect_args(name, args, 3)?;
+      Ok(FormulaValue::Text(
+        values[0]
+          .to_text()
+          .replace(&values[1].to_text(), &values[2].to_text()),
+      ))
+    },
+    "empty" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Bool(values[0].is_empty()))
+    },
+    "toNumber" => {
+      expect_args(name, args, 1)?;
+      match &values[0] {
+        FormulaValue::Empty => Ok(FormulaValue::Empty),
+        value => Ok(FormulaValue::Number(expect_number(value)?)),
+      }
+    },
+    "format" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Text(values[0].to_text()))
+    },
+    "abs" => unary_math(name, &values, f64::abs),
+    "floor" => unary_math(name, &values, f64::floor),
+    "ceil" => unary_math(name, &values, f64::ceil),
+    "sqrt" => unary_math(name, &values, f64::sqrt),
+    "round" => {
+      if values.is_empty() || values.len() > 2 {
+        return Err(FormulaError::InvalidArgument(format!(
+          "{} expects 1 or 2 arguments",
+          name
+        )));
+      }
+      let num = expect_number(&values[0])?;
+      let digits = match values.get(1) {
+        Some(value) => expect_number(value)? as i32,
+        None => 0,
+      };
+      let factor = 10f64.powi(digits);
+      Ok(FormulaValue::Number((num * factor).round() / factor))
+    },
+    "pow" => {
+      expect_args(name, args, 2)?;
+      Ok(FormulaValue::Number(
+        expect_number(&values[0])?.powf(expect_number(&values[1])?),
+      ))
+    },
+    "min" | "max" | "sum" => {
+      if values.is_empty() {
+        return Err(FormulaError::InvalidArgument(format!(
+          "{} expects at least one argument",
+          name
+        )));
+      }
+      let nums = values
+        .iter()
+        .map(expect_number)
+        .collect::<Result<Vec<f64>, FormulaError>>()?;
+      let result = match name {
+        "min" => nums.into_iter().fold(f64::INFINITY, f64::min),
+        "max" => nums.into_iter().fold(f64::NEG_INFINITY, f64::max),
+        _ => nums.into_iter().sum(),
+      };
+      Ok(FormulaValue::Number(result))
+    },
+    "now" => {
+      expect_args(name, args, 0)?;
+      Ok(FormulaValue::Date(ctx.now()))
+    },
+    "today" => {
+      expect_args(name, args, 0)?;
+      let now = ctx.now();
+      Ok(FormulaValue::Date(now - now.rem_euclid(86400)))
+    },
+    "dateAdd" | "dateSubtract" => {
+      expect_args(name, args, 3)?;
+      let date = expect_date(&values[0])?;
+      let mut amount = expect_number(&values[1])? as i64;
+      if name == "dateSubtract" {
+        amount = -amount;
+      }
+      let unit = values[2].to_text();
+      add_to_date(date, amount, &unit).map(FormulaValue::Date)
+    },
+    "dateBetween" => {
+      expect_args(name, args, 3)?;
+      let start = expect_date(&values[0])?;
+      let end = expect_date(&values[1])?;
+      let unit = values[2].to_text();
+      date_between(start, end, &unit).map(|num| FormulaValue::Number(num as f64))
+    },
+    "formatDate" => {
+      expect_args(name, args, 2)?;
+      let date = to_datetime(expect_date(&values[0])?)
+        .ok_or_else(|| FormulaError::InvalidArgument("invalid date".to_string()))?;
+      Ok(FormulaValue::Text(
+        date.format(&values[1].to_text()).to_string(),
+      ))
+    },
+    "year" | "month" | "day" | "hour" | "minute" => {
+      expect_args(name, args, 1)?;
+      let date = to_datetime(expect_date(&values[0])?)
+        .ok_or_else(|| FormulaError::InvalidArgument("invalid date".to_string()))?;
+      let num = match name {
+        "year" => date.year() as f64,
+        "month" => date.month() as f64,
+        "day" => date.day() as f64,
+        "hour" => date.hour() as f64,
+        _ => date.minute() as f64,
+      };
+      Ok(FormulaValue::Number(num))
+    },
+    "timestamp" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Number(expect_date(&values[0])? as f64))
+    },
+    "fromTimestamp" => {
+      expect_args(name, args, 1)?;
+      Ok(FormulaValue::Date(expect_number(&values[0])? as i64))
+    },
+    _ => Err(FormulaError::UnknownFunction(name.to_string())),
+  }
+}
+
+/// Compares two values. Numbers, dates and booleans are compared numerically. Everything else is
+/// compared by its text representation.
+pub fn compare_values(left: &FormulaValue, right: &FormulaValue) -> Ordering {
+  match (left, right) {
+    (FormulaValue::Text(_), _) | (_, FormulaValue::Text(_)) => match (
+      left.to_text().parse::<f64>(),
+      right.to_text().parse::<f64>(),
+    ) {
+      (Ok(left), Ok(right)) => left.partial_cmp(&right).unwrap_or(Ordering::Equal),
+      _ => left.to_text().cmp(&right.to_text()),
+    },
+    _ => {
+      let left = left.as_number().unwrap_or_default();
+      let right = right.as_number().unwrap_or_default();
+      left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+    },
+  }
+}
+
+pub fn format_number(num: f64) -> String {
+  if num.fract() == 0.0 && num.abs() < 1e15 {
+    format!("{}", num as i64)
+  } else {
+    let s = format!("{:.10}", num);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+  }
+}
+
+fn expect_args(name: &str, args: &[Expr], len: usize) -> Result<(), FormulaError> {
+  if args.len() != len {
+    return Err(FormulaError::InvalidArgument(format!(
+      "{} expects {} argument(s), found {}",
+      name,
+      len,
+      args.len()
+    )));
+  }
+  Ok(())
+}
+
+fn expect_number(value: &FormulaValue) -> Result<f64, FormulaError> {
+  value
+    .as_number()
+    .ok_or_else(|| FormulaError::InvalidArgument(format!("'{}' is not a number", value.to_text())))
+}
+
+fn expect_date(value: &FormulaValue) -> Result<i64, FormulaError> {
+  match value {
+    FormulaValue::Date(timestamp) => Ok(*timestamp),
+    FormulaValue::Number(num) => Ok(*num as i64),
+    _ => Err(FormulaError::InvalidArgument(format!(
+      "'{}' is not a date",
+      value.to_text()
+    ))),
+  }
+}
+
+fn unary_math(
+  name: &str,
+  values: &[FormulaValue],
+  f: fn(f64) -> f64,
+) -> Result<FormulaValue, FormulaError> {
+  if values.len() != 1 {
+    return Err(FormulaError::InvalidArgument(format!(
+      "{} expects 1 argument(s), found {}",
+      name,
+      values.len()
+    )));
+  }
+  Ok(FormulaValue::Number(f(expect_number(&values[0])?)))
+}
+
+fn to_datetime(timestamp: i64) -> Option<DateTime<Utc>> {
+  Utc.timestamp_opt(timestamp, 0).single()
+}
+
+fn add_to_date(timestamp: i64, amount: i64, unit: &str) -> Result<i64, FormulaError> {
+  let date = to_datetime(timestamp)
+    .ok_or_else(|| FormulaError::InvalidArgument("invalid date".to_string()))?;
+  let add_months = |months: i64| {
+    if months >= 0 {
+      date.checked_add_months(Months::new(months as u32))
+    } else {
+      date.checked_sub_months(Months::new((-months) as u32))
+    }
+  };
+  let result = match unit.trim_end_matches('s') {
+    "year" => add_months(amount * 12),
+    "quarter" => add_months(amount * 3),
+    "month" => add_months(amount),
+    "week" => date.checked_add_signed(Duration::weeks(amount)),
+    "day" => date.checked_add_signed(Duration::days(amount)),
+    "hour" => date.checked_add_signed(Duration::hours(amount)),
+    "minute" => date.checked_add_signed(Duration::minutes(amount)),
+    "second" => date.checked_add_signed(Duration::seconds(amount)),
+    _ => {
+      return Err(FormulaError::InvalidArgument(format!(
+        "unknown date unit '{}'",
+        unit
+      )))
+    },
+  };
+  result
+    .map(|date| date.timestamp())
+    .ok_or_else(|| FormulaError::InvalidArgument("date out of range".to_string()))
+}
+
+fn date_between(start: i64, end: i64, unit: &str) -> Result<i64, FormulaError> {
+  let seconds = start - end;
+  let result = match unit.trim_end_matches('s') {
+    "year" | "quarter" | "month" => {
+      let (start, end) = match (to_datetime(start), to_datetime(end)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => return Err(FormulaError::InvalidArgument("invalid date".to_string())),
+      };
+      let months =
+        (start.year() as i64 - end.year() as i64) * 12 + start.month() as i64 - end.month() as i64;
+      match unit.trim_end_matches('s') {
+        "year" => months / 12,
+        "quarter" => months / 3,
+        _ => months,
+      }
+    },
+    "week" => seconds / (7 * 86400),
+    "day" => seconds / 86400,
+    "hour" => seconds / 3600,
+    "minute" => seconds / 60,
+    "second" => seconds,
+    _ => {
+      return Err(FormulaError::InvalidArgument(format!(
+        "unknown date unit '{}'",
+        unit
+      )))
+    },
+  };
+  Ok(result)
+}
@@ -0,0 +1,336 @@
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{Cell, Row};
+use flowy_error::FlowyResult;
+
+use crate::entities::{CheckboxCellDataPB, FieldType, FormulaCellDataPB, TextFilterPB};
+use crate::services::cell::{stringify_cell, CellDataChangeset, CellDataDecoder};
+use crate::services::field::formula_type_option::evaluator::{
+  evaluate, FormulaContext, FormulaValue,
+};
+use crate::services::field::formula_type_option::formula_entities::FormulaCellData;
+use crate::services::field::formula_type_option::parser::{
+  parse_formula, referenced_props, FormulaError,
+};
+use crate::services::field::{
+  DateCellData, RollupCellData, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionCellExt, TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+const FORMULA_EXPRESSION: &str = "expression";
+
+#[derive(Default, Debug, Clone)]
+pub struct FormulaTypeOption {
+  pub expression: String,
+}
+
+impl From<TypeOptionData> for FormulaTypeOption {
+  fn from(value: TypeOptionData) -> Self {
+    let expression = value.get_str_value(FORMULA_EXPRESSION).unwrap_or_default();
+    Self { expression }
+  }
+}
+
+impl From<FormulaTypeOption> for TypeOptionData {
+  fn from(value: FormulaTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_str_value(FORMULA_EXPRESSION, value.expression)
+      .build()
+  }
+}
+
+impl FormulaTypeOption {
+  pub fn new(expression: &str) -> Self {
+    Self {
+      expression: expression.to_string(),
+    }
+  }
+
+  /// Evaluates the expression. An empty expression produces an empty value.
+  pub fn evaluate(&self, ctx: &dyn FormulaContext) -> Result<FormulaValue, FormulaError> {
+    if self.expression.trim().is_empty() {
+      return Ok(FormulaValue::Empty);
+    }
+    let expr = parse_formula(&self.expression)?;
+    evaluate(&expr, ctx)
+  }
+
+  /// Returns the names of the fields referenced by the expression.
+  pub fn referenced_props(&self) -> Vec<String> {
+    referenced_props(&self.expression)
+  }
+}
+
+impl TypeOption for FormulaTypeOption {
+  type CellData = FormulaCellData;
+  type CellChangeset = FormulaCellData;
+  type CellProtobufType = FormulaCellDataPB;
+  type CellFilter = TextFilterPB;
+}
+
+impl CellDataChangeset for FormulaTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    Ok((Cell::from(&changeset), changeset))
+  }
+}
+
+impl TypeOptionCellDataFilter for FormulaTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for FormulaTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = match (cell_data.number, other_cell_data.number) {
+          (Some(left), Some(right)) => left.partial_cmp(&right).unwrap_or(Ordering::Equal),
+          _ => cell_data.value.cmp(&other_cell_data.value),
+        };
+        sort_condition.evaluate_order(order)
+      },
+    }
+  }
+}
+
+impl CellDataDecoder for FormulaTypeOption {
+  fn decode_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(FormulaCellData::from(cell))
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    FormulaCellData::from(cell).number
+  }
+}
+
+impl TypeOptionTransform for FormulaTypeOption {}
+
+impl TypeOptionCellDataSerde for FormulaTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    FormulaCellDataPB {
+      value: cell_data.value,
+      error: cell_data.error,
+    }
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(FormulaCellData::from(cell))
+  }
+}
+
+/// Resolves `prop(...)` references against the cells of a row. Formula fields referenced by the
+/// formula are evaluated recursively, which is where reference cycles are detected.
+pub struct RowFormulaContext<'a> {
+  fields: &'a [Field],
+  row: &'a Row,
+  visiting: RefCell<Vec<String>>,
+}
+
+impl<'a> RowFormulaContext<'a> {
+  pub fn new(fields: &'a [Field], row: &'a Row) -> Self {
+    Self {
+      fields,
+      row,
+      visiting: RefCell::new(vec![]),
+    }
+  }
+
+  fn evaluate_formula_field(&self, field: &Field) -> Result<FormulaValue, FormulaError> {
+    if self.visiting.borrow().contains(&field.id) {
+      return Err(FormulaError::Cycle(field.name.clone()));
+    }
+    let type_option = field
+      .get_type_option::<FormulaTypeOption>(FieldType::Formula)
+      .unwrap_or_default();
+    self.visiting.borrow_mut().push(field.id.clone());
+    let result = type_option.evaluate(self);
+    self.visiting.borrow_mut().pop();
+    result
+  }
+}
+
+impl<'a> FormulaContext for RowFormulaContext<'a> {
+  fn resolve_prop(&self, name: &str) -> Result<FormulaValue, FormulaError> {
+    let field = find_field_by_name(self.fields, name)?;
+    let field_type = FieldType::from(field.field_type);
+    match field_type {
+      FieldType::Formula => return self.evaluate_formula_field(field),
+      FieldType::CreatedTime => return Ok(FormulaValue::Date(self.row.created_at)),
+      FieldType::LastEditedTime => return Ok(FormulaValue::Date(self.row.modified_at)),
+      _ => {},
+    }
+
+    let cell = match self.row.cells.get(&field.id) {
+      None => return Ok(FormulaValue::Empty),
+      Some(cell) => cell,
+    };
+    let value = match field_type {
+      FieldType::Number | FieldType::Time => TypeOptionCellExt::new(field, None)
+        .get_type_option_cell_data_handler()
+        .and_then(|handler| handler.handle_numeric_cell(cell))
+        .map(FormulaValue::Number)
+        .unwrap_or_default(),
+      FieldType::Checkbox => FormulaValue::Bool(CheckboxCellDataPB::from(cell).is_checked),
+      FieldType::DateTime => DateCellData::from(cell)
+        .timestamp
+        .map(FormulaValue::Date)
+        .unwrap_or_default(),
+      FieldType::Rollup => {
+        let cell_data = RollupCellData::from(cell);
+        match cell_data.number {
+          Some(number) => FormulaValue::Number(number),
+          None if cell_data.value.is_empty() => FormulaValue::Empty,
+          None => FormulaValue::Text(cell_data.value),
+        }
+      },
+      _ => {
+        let s = stringify_cell(cell, field);
+        if s.is_empty() {
+          FormulaValue::Empty
+        } else {
+          FormulaValue::Text(s)
+        }
+      },
+    };
+    Ok(value)
+  }
+}
+
+/// Evaluates the formula of the given formula field against the row.
+pub fn evaluate_formula_for_row(field: &Field, fields: &[Field], row: &Row) -> FormulaCellData {
+  let ctx = RowFormulaContext::new(fields, row);
+  FormulaCellData::from_result(ctx.evaluate_formula_field(field))
+}
+
+/// Returns the formula fields whose result depends on the field with `field_id`, either directly
+/// or through other formula fields.
+pub fn formula_fields_depending_on(fields: &[Field], field_id: &str) -> Vec<Field> {
+  let mut affected_field_ids = HashSet::from([field_id.to_string()]);
+  let mut dependents = vec![];
+  loop {
+    let mut changed = false;
+    for field in fields.iter().filter(|field| is_formula_field(field)) {
+      if affected_field_ids.contains(&field.id) {
+        continue;
+      }
+      let depends_on_affected_field = formula_dependencies(field, fields)
+        .iter()
+        .any(|dependency| affected_field_ids.contains(&dependency.id));
+      if depends_on_affected_field {
+        affected_field_ids.insert(field.id.clone());
+        dependents.push(field.clone());
+        changed = true;
+      }
+    }
+
+    if !changed {
+      break;
+    }
+  }
+  dependents
+}
+
+/// Looks for a reference cycle between the formula fields. Returns the name of the field whose
+/// formula leads into the cycle if there is one.
+pub fn find_formula_cycle(fields: &[Field]) -> Option<String> {
+  let dependencies = fields
+    .iter()
+    .filter(|field| is_formula_field(field))
+    .map(|field| {
+      let dependency_ids = formula_dependencies(field, fields)
+        .into_iter()
+        .filter(|dependency| is_formula_field(dependency))
+        .map(|dependency| dependency.id.clone())
+        .collect::<Vec<String>>();
+      (field.id.clone(), dependency_ids)
+    })
+    .collect::<HashMap<String, Vec<String>>>();
+
+  // 1: visiting, 2: visited
+  let mut states: HashMap<String, u8> = HashMap::new();
+  fn visit(
+    field_id: &str,
+    dependencies: &HashMap<String, Vec<String>>,
+    states: &mut HashMap<String, u8>,
+  ) -> bool {
+    match states.get(field_id) {
+      Some(1) => return true,
+      Some(_) => return false,
+      None => {},
+    }
+    states.insert(field_id.to_string(), 1);
+    if let Some(dependency_ids) = dependencies.get(field_id) {
+      for dependency_id in dependency_ids {
+        if visit(dependency_id, dependencies, states) {
+          return true;
+        }
+      }
+    }
+    states.insert(field_id.to_string(), 2);
+    false
+  }
+
+  for field_id in dependencies.keys() {
+    if visit(field_id, &dependencies, &mut states) {
+      return fields
+        .iter()
+        .find(|field| &field.id == field_id)
+        .map(|field| field.name.clone());
+    }
+  }
+  None
+}
+
+fn formula_dependencies<'a>(field: &Field, fields: &'a [Field]) -> Vec<&'a Field> {
+  field
+    .get_type_option::<FormulaTypeOption>(FieldType::Formula)
+    .map(|type_option| type_option.referenced_props())
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|name| find_field_by_name(fields, name).ok())
+    .collect()
+}
+
+/// Finds the field whose name is exactly `name`. A name shared by several fields can't be
+/// resolved, so it's reported as ambiguous instead of picking one of them.
+fn find_field_by_name<'a>(fields: &'a [Field], name: &str) -> Result<&'a Field, FormulaError> {
+  let mut matches = fields.iter().filter(|field| field.name == name);
+  match (matches.next(), matches.next()) {
+    (Some(field), None) => Ok(field),
+    (Some(_), Some(_)) => Err(FormulaError::AmbiguousProperty(name.to_string())),
+    (None, _) => Err(FormulaError::UnknownProperty(name.to_string())),
+  }
+}
+
+fn is_formula_field(field: &Field) -> bool {
+  FieldType::from(field.field_type).is_formula()
+}
@@ -0,0 +1,95 @@
+use collab::core::any_map::AnyMapExtension;
+use collab_database::rows::{new_cell_builder, Cell};
+
+use crate::entities::FieldType;
+use crate::services::field::formula_type_option::evaluator::FormulaValue;
+use crate::services::field::formula_type_option::parser::FormulaError;
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+const FORMULA_NUMBER: &str = "number";
+const FORMULA_ERROR: &str = "error";
+
+/// The computed result of a formula cell. The result is calculated by the [DatabaseEditor] whenever
+/// the cells that the formula references change, and is stored in the cell so that it can be
+/// read, filtered and sorted like any other cell.
+///
+/// [DatabaseEditor]: crate::services::database::DatabaseEditor
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct FormulaCellData {
+  /// The stringified result of the formula.
+  pub value: String,
+  /// The numeric representation of the result if it has one. Dates are represented as timestamps.
+  pub number: Option<f64>,
+  /// Error message if the formula can't be evaluated.
+  pub error: Option<String>,
+}
+
+impl FormulaCellData {
+  pub fn from_result(result: Result<FormulaValue, FormulaError>) -> Self {
+    match result {
+      Ok(value) => {
+        let number = match &value {
+          FormulaValue::Number(num) => Some(*num),
+          FormulaValue::Date(timestamp) => Some(*timestamp as f64),
+          _ => None,
+        };
+        Self {
+          value: value.to_text(),
+          number,
+          error: None,
+        }
+      },
+      Err(err) => Self {
+        value: "".to_string(),
+        number: None,
+        error: Some(err.to_string()),
+      },
+    }
+  }
+}
+
+impl TypeOptionCellData for FormulaCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.value.is_empty()
+  }
+}
+
+impl From<&Cell> for FormulaCellData {
+  fn from(cell: &Cell) -> Self {
+    let value = cell.get_str_value(CELL_DATA).unwrap_or_default();
+    let number = cell
+      .get_str_value(FORMULA_NUMBER)
+      .and_then(|s| s.parse::<f64>().ok());
+    let error = cell
+      .get_str_value(FORMULA_ERROR)
+      .filter(|error| !error.is_empty());
+    Self {
+      value,
+      number,
+      error,
+    }
+  }
+}
+
+impl From<&FormulaCellData> for Cell {
+  fn from(data: &FormulaCellData) -> Self {
+    let number = data.number.map(|num| num.to_string()).unwrap_or_default();
+    new_cell_builder(FieldType::Formula)
+      .insert_str_value(CELL_DATA, data.value.clone())
+      .insert_str_value(FORMULA_NUMBER, number)
+      .insert_str_value(FORMULA_ERROR, data.error.clone().unwrap_or_default())
+      .build()
+  }
+}
+
+impl ToString for FormulaCellData {
+  fn to_string(&self) -> String {
+    self.value.clone()
+  }
+}
+
+impl AsRef<str> for FormulaCellData {
+  fn as_ref(&self) -> &str {
+    &self.value
+  }
+}
@@ -0,0 +1,254 @@
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use collab_database::fields::Field;
+  use collab_database::rows::Row;
+
+  use crate::entities::FieldType;
+  use crate::services::cell::{insert_number_cell, insert_text_cell};
+  use crate::services::field::type_options::formula_type_option::*;
+  use crate::services::field::{FieldBuilder, NumberTypeOption, RichTextTypeOption};
+
+  // 2024/03/15 10:30:00 UTC
+  const NOW: i64 = 1710498600;
+
+  struct TestContext {
+    props: HashMap<String, FormulaValue>,
+  }
+
+  impl TestContext {
+    fn new(props: Vec<(&str, FormulaValue)>) -> Self {
+      Self {
+        props: props
+          .into_iter()
+          .map(|(name, value)| (name.to_string(), value))
+          .collect(),
+      }
+    }
+  }
+
+  impl FormulaContext for TestContext {
+    fn resolve_prop(&self, name: &str) -> Result<FormulaValue, FormulaError> {
+      self
+        .props
+        .get(name)
+        .cloned()
+        .ok_or_else(|| FormulaError::UnknownProperty(name.to_string()))
+    }
+
+    fn now(&self) -> i64 {
+      NOW
+    }
+  }
+
+  #[test]
+  fn formula_arithmetic_test() {
+    let ctx = TestContext::new(vec![]);
+    assert_formula("1 + 2 * 3", "7", &ctx);
+    assert_formula("(1 + 2) * 3", "9", &ctx);
+    assert_formula("10 / 4", "2.5", &ctx);
+    assert_formula("-3 + 10 % 4", "-1", &ctx);
+    assert_formula("round(2.346, 2)", "2.35", &ctx);
+    assert_formula("max(1, 5, 3) + min(4, 2)", "7", &ctx);
+    assert_formula("pow(2, 10)", "1024", &ctx);
+  }
+
+  #[test]
+  fn formula_text_test() {
+    let ctx = TestContext::new(vec![("Name", FormulaValue::Text("AppFlowy".to_string()))]);
+    assert_formula(r#"prop("Name") + " rocks""#, "AppFlowy rocks", &ctx);
+    assert_formula(r#"concat(upper(prop("Name")), "-", 1)"#, "APPFLOWY-1", &ctx);
+    assert_formula(r#"length(prop("Name"))"#, "8", &ctx);
+    assert_formula(r#"contains(prop("Name"), "Flow")"#, "true", &ctx);
+    assert_formula(r#"replace("a-b-c", "-", "+")"#, "a+b+c", &ctx);
+  }
+
+  #[test]
+  fn formula_if_test() {
+    let ctx = TestContext::new(vec![("Price", FormulaValue::Number(120.0))]);
+    assert_formula(
+      r#"if(prop("Price") > 100, "expensive", "cheap")"#,
+      "expensive",
+      &ctx,
+    );
+    assert_formula(
+      r#"if(prop("Price") > 100 and prop("Price") < 200, 1, 0)"#,
+      "1",
+      &ctx,
+    );
+    // The branch that isn't selected is not evaluated.
+    assert_formula(r#"if(true, 1, prop("Unknown"))"#, "1", &ctx);
+  }
+
+  #[test]
+  fn formula_date_test() {
+    let ctx = TestContext::new(vec![("Due", FormulaValue::Date(NOW))]);
+    assert_formula("today()", "2024/03/15", &ctx);
+    assert_formula("now()", "2024/03/15 10:30", &ctx);
+    assert_formula(r#"dateAdd(today(), 1, "months")"#, "2024/04/15", &ctx);
+    assert_formula(r#"dateSubtract(today(), 2, "days")"#, "2024/03/13", &ctx);
+    assert_formula(
+      r#"dateBetween(dateAdd(prop("Due"), 3, "weeks"), prop("Due"), "days")"#,
+      "21",
+      &ctx,
+    );
+    assert_formula(r#"formatDate(prop("Due"), "%Y-%m")"#, "2024-03", &ctx);
+    assert_formula(
+      r#"year(prop("Due")) * 100 + month(prop("Due"))"#,
+      "202403",
+      &ctx,
+    );
+  }
+
+  #[test]
+  fn formula_error_test() {
+    let ctx = TestContext::new(vec![]);
+    assert!(matches!(parse_formula("1 +"), Err(FormulaError::Syntax(_))));
+    assert!(matches!(
+      parse_formula("(1 + 2"),
+      Err(FormulaError::Syntax(_))
+    ));
+    assert_eq!(
+      evaluate(&parse_formula("foo(1)").unwrap(), &ctx),
+      Err(FormulaError::UnknownFunction("foo".to_string()))
+    );
+    assert_eq!(
+      evaluate(&parse_formula(r#"prop("Missing")"#).unwrap(), &ctx),
+      Err(FormulaError::UnknownProperty("Missing".to_string()))
+    );
+  }
+
+  #[test]
+  fn formula_rename_prop_test() {
+    let expression = r#"prop("Price") * prop("Amount") + prop("Price Tax")"#;
+    assert_eq!(
+      rename_prop_references(expression, "Price", "Cost"),
+      r#"prop("Cost") * prop("Amount") + prop("Price Tax")"#
+    );
+    assert_eq!(
+      referenced_props(expression),
+      vec!["Price", "Amount", "Price Tax"]
+    );
+  }
+
+  #[test]
+  fn formula_row_evaluation_test() {
+    let price = number_field("Price");
+    let amount = number_field("Amount");
+    let name = FieldBuilder::new(FieldType::RichText, RichTextTypeOption::default()).build();
+    let total = formula_field("Total", r#"prop("Price") * prop("Amount")"#);
+    let label = formula_field("Label", r#"prop("Total") + " USD""#);
+    let fields = vec![
+      price.clone(),
+      amount.clone(),
+      name.clone(),
+      total.clone(),
+      label.clone(),
+    ];
+
+    let mut row = Row::new("row_id", "database_id");
+    row
+      .cells
+      .insert(price.id.clone(), insert_number_cell(12, &price));
+    row
+      .cells
+      .insert(amount.id.clone(), insert_number_cell(3, &amount));
+    row
+      .cells
+      .insert(name.id.clone(), insert_text_cell("A".to_string(), &name));
+
+    let cell_data = evaluate_formula_for_row(&total, &fields, &row);
+    assert_eq!(cell_data.value, "36");
+    assert_eq!(cell_data.number, Some(36.0));
+
+    let cell_data = evaluate_formula_for_row(&label, &fields, &row);
+    assert_eq!(cell_data.value, "36 USD");
+    assert_eq!(cell_data.error, None);
+
+    let dependents = formula_fields_depending_on(&fields, &price.id)
+      .into_iter()
+      .map(|field| field.name)
+      .collect::<Vec<String>>();
+    assert_eq!(dependents, vec!["Total", "Label"]);
+  }
+
+  #[test]
+  fn formula_cycle_test() {
+    let price = number_field("Price");
+    let a = formula_field("A", r#"prop("B") + prop("Price")"#);
+    let b = formula_field("B", r#"prop("A") * 2"#);
+    let fields = vec![price, a.clone(), b];
+    assert!(find_formula_cycle(&fields).is_some());
+
+    let cell_data = evaluate_formula_for_row(&a, &fields, &Row::new("row_id", "database_id"));
+    assert!(cell_data.value.is_empty());
+    assert!(cell_data.error.is_some());
+
+    let fields = vec![
+      number_field("Price"),
+      formula_field("A", r#"prop("Price") + 1"#),
+      formula_field("B", r#"prop("A") * 2"#),
+    ];
+    assert_eq!(find_formula_cycle(&fields), None);
+  }
+
+  #[test]
+  fn formula_prop_name_resolution_test() {
+    let price = number_field("Price");
+    let lower_price = number_field("price");
+    let fields = vec![
+      price.clone(),
+      lower_price.clone(),
+      formula_field("Total", r#"prop("price") * 2"#),
+    ];
+    let mut row = Row::new("row_id", "database_id");
+    row
+      .cells
+      .insert(price.id.clone(), insert_number_cell(10, &price));
+    row
+      .cells
+      .insert(lower_price.id.clone(), insert_number_cell(3, &lower_price));
+    let cell_data = evaluate_formula_for_row(&fields[2], &fields, &row);
+    assert_eq!(cell_data.value, "6");
+
+    let fields = vec![
+      price.clone(),
+      formula_field("Total", r#"prop("PRICE") * 2"#),
+    ];
+    let cell_data = evaluate_formula_for_row(&fields[1], &fields, &row);
+    assert!(cell_data.value.is_empty());
+    assert!(cell_data.error.is_some());
+
+    let fields = vec![
+      price,
+      number_field("Price"),
+      formula_field("Total", r#"prop("Price") * 2"#),
+    ];
+    let cell_data = evaluate_formula_for_row(&fields[2], &fields, &row);
+    assert!(cell_data.value.is_empty());
+    assert!(cell_data.error.unwrap().contains("Ambiguous"));
+  }
+
+  fn number_field(name: &str) -> Field {
+    FieldBuilder::new(FieldType::Number, NumberTypeOption::default())
+      .name(name)
+      .build()
+  }
+
+  fn formula_field(name: &str, expression: &str) -> Field {
+    FieldBuilder::new(FieldType::Formula, FormulaTypeOption::new(expression))
+      .name(name)
+      .build()
+  }
+
+  fn assert_formula(expression: &str, expected: &str, ctx: &TestContext) {
+    let expr = parse_formula(expression).unwrap();
+    assert_eq!(
+      evaluate(&expr, ctx).unwrap().to_text(),
+      expected,
+      "expression: {}",
+      expression
+    );
+  }
+}
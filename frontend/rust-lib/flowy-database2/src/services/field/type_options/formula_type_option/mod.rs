@@ -0,0 +1,10 @@
+mod evaluator;
+mod formula;
+mod formula_entities;
+mod formula_tests;
+mod parser;
+
+pub use evaluator::*;
+pub use formula::*;
+pub use formula_entities::*;
+pub use parser::*;
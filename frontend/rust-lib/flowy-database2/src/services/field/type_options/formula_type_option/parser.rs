@@ -0,0 +1,466 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaError {
+  Syntax(String),
+  UnknownFunction(String),
+  UnknownProperty(String),
+  /// More than one field has the referenced name.
+  AmbiguousProperty(String),
+  InvalidArgument(String),
+  /// The formula references itself, directly or through other formula fields.
+  Cycle(String),
+}
+
+impl Display for FormulaError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    match self {
+      FormulaError::Syntax(msg) => write!(f, "Syntax error: {}", msg),
+      FormulaError::UnknownFunction(name) => write!(f, "Unknown function: {}", name),
+      FormulaError::UnknownProperty(name) => write!(f, "Unknown property: {}", name),
+      FormulaError::AmbiguousProperty(name) => write!(f, "Ambiguous property: {}", name),
+      FormulaError::InvalidArgument(msg) => write!(f, "Invalid argument: {}", msg),
+      FormulaError::Cycle(name) => write!(f, "Circular reference: {}", name),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  Number(f64),
+  Str(String),
+  Ident(String),
+  LParen,
+  RParen,
+  Comma,
+  Plus,
+  Minus,
+  Star,
+  Slash,
+  Percent,
+  Eq,
+  NotEq,
+  Lt,
+  LtEq,
+  Gt,
+  GtEq,
+  And,
+  Or,
+  Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UnaryOp {
+  Neg,
+  Not,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Mod,
+  Eq,
+  NotEq,
+  Lt,
+  LtEq,
+  Gt,
+  GtEq,
+  And,
+  Or,
+}
+
+/// The syntax tree of a formula expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+  Number(f64),
+  Text(String),
+  Bool(bool),
+  /// `prop("Field name")` refers to the cell of another field in the same row.
+  Prop(String),
+  Unary {
+    op: UnaryOp,
+    expr: Box<Expr>,
+  },
+  Binary {
+    op: BinaryOp,
+    left: Box<Expr>,
+    right: Box<Expr>,
+  },
+  Call {
+    name: String,
+    args: Vec<Expr>,
+  },
+}
+
+impl Expr {
+  /// Collects the names of all the fields referenced by `prop(...)` in this expression.
+  pub fn referenced_props(&self, props: &mut Vec<String>) {
+    match self {
+      Expr::Prop(name) => {
+        if !props.contains(name) {
+          props.push(name.clone());
+        }
+      },
+      Expr::Unary { expr, .. } => expr.referenced_props(props),
+      Expr::Binary { left, right, .. } => {
+        left.referenced_props(props);
+        right.referenced_props(props);
+      },
+      Expr::Call { args, .. } => {
+        for arg in args {
+          arg.referenced_props(props);
+        }
+      },
+      Expr::Number(_) | Expr::Text(_) | Expr::Bool(_) => {},
+    }
+  }
+}
+
+/// Parses the formula expression into an [Expr].
+///
+/// The grammar supports arithmetic (`+ - * / %`), comparison (`== != < <= > >=`), boolean
+/// operators (`&& || !`, or `and`/`or`/`not`), string and number literals, `true`/`false`,
+/// function calls like `if(cond, a, b)` and field references with `prop("Field")`.
+pub fn parse_formula(input: &str) -> Result<Expr, FormulaError> {
+  let tokens = tokenize(input)?;
+  if tokens.is_empty() {
+    return Err(FormulaError::Syntax("empty expression".to_string()));
+  }
+  let mut parser = Parser { tokens, pos: 0 };
+  let expr = parser.parse_or()?;
+  if parser.pos < parser.tokens.len() {
+    return Err(FormulaError::Syntax(format!(
+      "unexpected token {:?}",
+      parser.tokens[parser.pos]
+    )));
+  }
+  Ok(expr)
+}
+
+/// Returns the names of the fields referenced by the expression. Returns an empty list if the
+/// expression can't be parsed.
+pub fn referenced_props(expression: &str) -> Vec<String> {
+  let mut props = vec![];
+  if let Ok(expr) = parse_formula(expression) {
+    expr.referenced_props(&mut props);
+  }
+  props
+}
+
+/// Rewrites the `prop(...)` references to `old_name` so that they point to `new_name`. Used to keep
+/// formulas working after the referenced field is renamed.
+pub fn rename_prop_references(expression: &str, old_name: &str, new_name: &str) -> String {
+  let escaped_new_name = new_name.replace('\\', "\\\\").replace('"', "\\\"");
+  let replacement = format!("prop(\"{}\")", escaped_new_name);
+  let escaped_old_name = old_name.replace('\\', "\\\\").replace('"', "\\\"");
+  expression
+    .replace(&format!("prop(\"{}\")", escaped_old_name), &replacement)
+    .replace(&format!("prop('{}')", old_name), &replacement)
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FormulaError> {
+  let chars = input.chars().collect::<Vec<char>>();
+  let mut tokens = vec![];
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    match c {
+      c if c.is_whitespace() => {
+        i += 1;
+      },
+      '0'..='9' | '.' => {
+        let start = i;
+        while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+          i += 1;
+        }
+        let s = chars[start..i].iter().collect::<String>();
+        let num = s
+          .parse::<f64>()
+          .map_err(|_| FormulaError::Syntax(format!("invalid number {}", s)))?;
+        tokens.push(Token::Number(num));
+      },
+      '"' | '\'' => {
+        let quote = c;
+        let mut s = String::new();
+        i += 1;
+        loop {
+          match chars.get(i) {
+            None => return Err(FormulaError::Syntax("unterminated string".to_string())),
+            Some('\\') => {
+              if let Some(escaped) = chars.get(i + 1) {
+                s.push(*escaped);
+              }
+              i += 2;
+            },
+            Some(ch) if *ch == quote => {
+              i += 1;
+              break;
+            },
+            Some(ch) => {
+              s.push(*ch);
+              i += 1;
+            },
+          }
+        }
+        tokens.push(Token::Str(s));
+      },
+      c if c.is_alphabetic() || c == '_' => {
+        let start = i;
+        while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+          i += 1;
+        }
+        let ident = chars[start..i].iter().collect::<String>();
+        let token = match ident.as_str() {
+          "and" => Token::And,
+          "or" => Token::Or,
+          "not" => Token::Not,
+          _ => Token::Ident(ident),
+        };
+        tokens.push(token);
+      },
+      _ => {
+        let next = chars.get(i + 1).copied();
+        let (token, len) = match (c, next) {
+          ('(', _) => (Token::LParen, 1),
+          (')', _) => (Token::RParen, 1),
+          (',', _) => (Token::Comma, 1),
+          ('+', _) => (Token::Plus, 1),
+          ('-', _) => (Token::Minus, 1),
+          ('*', _) => (Token::Star, 1),
+          ('/', _) => (Token::Slash, 1),
+          ('%', _) => (Token::Percent, 1),
+          ('=', Some('=')) => (Token::Eq, 2),
+          ('=', _) => (Token::Eq, 1),
+          ('!', Some('=')) => (Token::NotEq, 2),
+          ('!', _) => (Token::Not, 1),
+          ('<', Some('=')) => (Token::LtEq, 2),
+          ('<', Some('>')) => (Token::NotEq, 2),
+          ('<', _) => (Token::Lt, 1),
+          ('>', Some('=')) => (Token::GtEq, 2),
+          ('>', _) => (Token::Gt, 1),
+          ('&', Some('&')) => (Token::And, 2),
+          ('|', Some('|')) => (Token::Or, 2),
+          _ => {
+            return Err(FormulaError::Syntax(format!(
+              "unexpected character '{}'",
+              c
+            )))
+          },
+        };
+        tokens.push(token);
+        i += len;
+      },
+    }
+  }
+  Ok(tokens)
+}
+
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn next(&mut self) -> Option<Token> {
+    let token = self.tokens.get(self.pos).cloned();
+    self.pos += 1;
+    token
+  }
+
+  fn expect(&mut self, expected: Token) -> Result<(), FormulaError> {
+    match self.next() {
+      Some(token) if token == expected => Ok(()),
+      Some(token) => Err(FormulaError::Syntax(format!(
+        "expected {:?}, found {:?}",
+        expected, token
+      ))),
+      None => Err(FormulaError::Syntax(format!(
+        "expected {:?}, found end of expression",
+        expected
+      ))),
+    }
+  }
+
+  fn parse_or(&mut self) -> Result<Expr, FormulaError> {
+    let mut left = self.parse_and()?;
+    while let Some(Token::Or) = self.peek() {
+      self.pos += 1;
+      let right = self.parse_and()?;
+      left = binary(BinaryOp::Or, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_and(&mut self) -> Result<Expr, FormulaError> {
+    let mut left = self.parse_equality()?;
+    while let Some(Token::And) = self.peek() {
+      self.pos += 1;
+      let right = self.parse_equality()?;
+      left = binary(BinaryOp::And, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_equality(&mut self) -> Result<Expr, FormulaError> {
+    let mut left = self.parse_comparison()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Eq) => BinaryOp::Eq,
+        Some(Token::NotEq) => BinaryOp::NotEq,
+        _ => break,
+      };
+      self.pos += 1;
+      let right = self.parse_comparison()?;
+      left = binary(op, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_comparison(&mut self) -> Result<Expr, FormulaError> {
+    let mut left = self.parse_additive()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Lt) => BinaryOp::Lt,
+        Some(Token::LtEq) => BinaryOp::LtEq,
+        Some(Token::Gt) => BinaryOp::Gt,
+        Some(Token::GtEq) => BinaryOp::GtEq,
+        _ => break,
+      };
+      self.pos += 1;
+      let right = self.parse_additive()?;
+      left = binary(op, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_additive(&mut self) -> Result<Expr, FormulaError> {
+    let mut left = self.parse_multiplicative()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Plus) => BinaryOp::Add,
+        Some(Token::Minus) => BinaryOp::Sub,
+        _ => break,
+      };
+      self.pos += 1;
+      let right = self.parse_multiplicative()?;
+      left = binary(op, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_multiplicative(&mut self) -> Result<Expr, FormulaError> {
+    let mut left = self.parse_unary()?;
+    loop {
+      let op = match self.peek() {
+        Some(Token::Star) => BinaryOp::Mul,
+        Some(Token::Slash) => BinaryOp::Div,
+        Some(Token::Percent) => BinaryOp::Mod,
+        _ => break,
+      };
+      self.pos += 1;
+      let right = self.parse_unary()?;
+      left = binary(op, left, right);
+    }
+    Ok(left)
+  }
+
+  fn parse_unary(&mut self) -> Result<Expr, FormulaError> {
+    let op = match self.peek() {
+      Some(Token::Minus) => UnaryOp::Neg,
+      Some(Token::Not) => UnaryOp::Not,
+      Some(Token::Plus) => {
+        self.pos += 1;
+        return self.parse_unary();
+      },
+      _ => return self.parse_primary(),
+    };
+    self.pos += 1;
+    let expr = self.parse_unary()?;
+    Ok(Expr::Unary {
+      op,
+      expr: Box::new(expr),
+    })
+  }
+
+  fn parse_primary(&mut self) -> Result<Expr, FormulaError> {
+    match self.next() {
+      Some(Token::Number(num)) => Ok(Expr::Number(num)),
+      Some(Token::Str(s)) => Ok(Expr::Text(s)),
+      Some(Token::LParen) => {
+        let expr = self.parse_or()?;
+        self.expect(Token::RParen)?;
+        Ok(expr)
+      },
+      Some(Token::Ident(ident)) => {
+        if let Some(Token::LParen) = self.peek() {
+          self.pos += 1;
+          let args = self.parse_args()?;
+          if ident == "prop" {
+            return match args.as_slice() {
+              [Expr::Text(name)] => Ok(Expr::Prop(name.clone())),
+              _ => Err(FormulaError::Syntax(
+                "prop() expects a single field name".to_string(),
+              )),
+            };
+          }
+          return Ok(Expr::Call { name: ident, args });
+        }
+
+        match ident.as_str() {
+          "true" => Ok(Expr::Bool(true)),
+          "false" => Ok(Expr::Bool(false)),
+          _ => Err(FormulaError::Syntax(format!(
+            "unexpected identifier {}",
+            ident
+          ))),
+        }
+      },
+      Some(token) => Err(FormulaError::Syntax(format!(
+        "unexpected token {:?}",
+        token
+      ))),
+      None => Err(FormulaError::Syntax(
+        "unexpected end of expression".to_string(),
+      )),
+    }
+  }
+
+  fn parse_args(&mut self) -> Result<Vec<Expr>, FormulaError> {
+    let mut args = vec![];
+    if let Some(Token::RParen) = self.peek() {
+      self.pos += 1;
+      return Ok(args);
+    }
+
+    loop {
+      args.push(self.parse_or()?);
+      match self.next() {
+        Some(Token::Comma) => continue,
+        Some(Token::RParen) => break,
+        Some(token) => {
+          return Err(FormulaError::Syntax(format!(
+            "expected ',' or ')', found {:?}",
+            token
+          )))
+        },
+        None => return Err(FormulaError::Syntax("missing ')'".to_string())),
+      }
+    }
+    Ok(args)
+  }
+}
+
+fn binary(op: BinaryOp, left: Expr, right: Expr) -> Expr {
+  Expr::Binary {
+    op,
+    left: Box::new(left),
+    right: Box::new(right),
+  }
+}
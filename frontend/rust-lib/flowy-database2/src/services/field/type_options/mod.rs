@@ -1,8 +1,10 @@
 pub mod checkbox_type_option;
 pub mod checklist_type_option;
 pub mod date_type_option;
+pub mod formula_type_option;
 pub mod number_type_option;
 pub mod relation_type_option;
+pub mod rollup_type_option;
 pub mod selection_type_option;
 pub mod summary_type_option;
 pub mod text_type_option;
@@ -17,8 +19,10 @@ mod util;
 pub use checkbox_type_option::*;
 pub use checklist_type_option::*;
 pub use date_type_option::*;
+pub use formula_type_option::*;
 pub use number_type_option::*;
 pub use relation_type_option::*;
+pub use rollup_type_option::*;
 pub use selection_type_option::*;
 pub use text_type_option::*;
 pub use time_type_option::*;
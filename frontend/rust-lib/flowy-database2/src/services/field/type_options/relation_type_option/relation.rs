@@ -19,12 +19,26 @@ use super::{RelationCellChangeset, RelationCellData};
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RelationTypeOption {
   pub database_id: String,
+  /// The id of the relation field in the related database that mirrors this field. It's empty
+  /// if the relation is one-way.
+  #[serde(default)]
+  pub paired_field_id: String,
+}
+
+impl RelationTypeOption {
+  pub fn is_two_way(&self) -> bool {
+    !self.paired_field_id.is_empty()
+  }
 }
 
 impl From<TypeOptionData> for RelationTypeOption {
   fn from(value: TypeOptionData) -> Self {
     let database_id = value.get_str_value("database_id").unwrap_or_default();
-    Self { database_id }
+    let paired_field_id = value.get_str_value("paired_field_id").unwrap_or_default();
+    Self {
+      database_id,
+      paired_field_id,
+    }
   }
 }
 
@@ -32,6 +46,7 @@ impl From<RelationTypeOption> for TypeOptionData {
   fn from(value: RelationTypeOption) -> Self {
     TypeOptionDataBuilder::new()
       .insert_str_value("database_id", value.database_id)
+      .insert_str_value("paired_field_id", value.paired_field_id)
       .build()
   }
 }
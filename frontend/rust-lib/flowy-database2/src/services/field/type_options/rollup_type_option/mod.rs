@@ -0,0 +1,6 @@
+mod rollup;
+mod rollup_entities;
+mod rollup_tests;
+
+pub use rollup::*;
+pub use rollup_entities::*;
@@ -0,0 +1,181 @@
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::{Cell, RowCell};
+use flowy_error::FlowyResult;
+
+use crate::entities::{CalculationType, RollupCellDataPB, TextFilterPB};
+use crate::services::calculations::CalculationsService;
+use crate::services::cell::{stringify_cell, CellDataChangeset, CellDataDecoder};
+use crate::services::field::rollup_type_option::rollup_entities::RollupCellData;
+use crate::services::field::{
+  format_number, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
+  TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform,
+};
+use crate::services::sort::SortCondition;
+
+const RELATION_FIELD_ID: &str = "relation_field_id";
+const TARGET_FIELD_ID: &str = "target_field_id";
+const CALCULATION_TYPE: &str = "calculation_type";
+const SHOW_ORIGINAL: &str = "show_original";
+
+/// Aggregates the values of a field of the rows that are linked through a relation field.
+#[derive(Debug, Clone, Default)]
+pub struct RollupTypeOption {
+  pub relation_field_id: String,
+  /// The id of the field in the related database.
+  pub target_field_id: String,
+  pub calculation_type: CalculationType,
+  pub show_original: bool,
+}
+
+impl From<TypeOptionData> for RollupTypeOption {
+  fn from(value: TypeOptionData) -> Self {
+    let relation_field_id = value.get_str_value(RELATION_FIELD_ID).unwrap_or_default();
+    let target_field_id = value.get_str_value(TARGET_FIELD_ID).unwrap_or_default();
+    let calculation_type = value
+      .get_i64_value(CALCULATION_TYPE)
+      .map(CalculationType::from)
+      .unwrap_or_default();
+    let show_original = value.get_bool_value(SHOW_ORIGINAL).unwrap_or_default();
+    Self {
+      relation_field_id,
+      target_field_id,
+      calculation_type,
+      show_original,
+    }
+  }
+}
+
+impl From<RollupTypeOption> for TypeOptionData {
+  fn from(value: RollupTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_str_value(RELATION_FIELD_ID, value.relation_field_id)
+      .insert_str_value(TARGET_FIELD_ID, value.target_field_id)
+      .insert_i64_value(CALCULATION_TYPE, value.calculation_type.value())
+      .insert_bool_value(SHOW_ORIGINAL, value.show_original)
+      .build()
+  }
+}
+
+impl RollupTypeOption {
+  /// Rolls up the cells of the target field of the linked rows. Empty cells are skipped when
+  /// showing the original values.
+  pub fn calculate(
+    &self,
+    target_field: &Field,
+    related_cells: Vec<Arc<RowCell>>,
+  ) -> RollupCellData {
+    if self.show_original {
+      let value = related_cells
+        .iter()
+        .flat_map(|row_cell| row_cell.cell.as_ref())
+        .map(|cell| stringify_cell(cell, target_field))
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<String>>()
+        .join(", ");
+      return RollupCellData {
+        value,
+        number: None,
+      };
+    }
+
+    let value = CalculationsService::new().calculate(
+      target_field,
+      self.calculation_type.value(),
+      related_cells,
+    );
+    match value.parse::<f64>() {
+      Ok(number) => RollupCellData {
+        value: format_number(number),
+        number: Some(number),
+      },
+      Err(_) => RollupCellData {
+        value,
+        number: None,
+      },
+    }
+  }
+}
+
+impl TypeOption for RollupTypeOption {
+  type CellData = RollupCellData;
+  type CellChangeset = RollupCellData;
+  type CellProtobufType = RollupCellDataPB;
+  type CellFilter = TextFilterPB;
+}
+
+impl CellDataChangeset for RollupTypeOption {
+  fn apply_changeset(
+    &self,
+    changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    Ok((Cell::from(&changeset), changeset))
+  }
+}
+
+impl TypeOptionCellDataFilter for RollupTypeOption {
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data)
+  }
+}
+
+impl TypeOptionCellDataCompare for RollupTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.is_cell_empty(), other_cell_data.is_cell_empty()) {
+      (true, true) => Ordering::Equal,
+      (true, false) => Ordering::Greater,
+      (false, true) => Ordering::Less,
+      (false, false) => {
+        let order = match (cell_data.number, other_cell_data.number) {
+          (Some(left), Some(right)) => left.partial_cmp(&right).unwrap_or(Ordering::Equal),
+          _ => cell_data.value.cmp(&other_cell_data.value),
+        };
+        sort_condition.evaluate_order(order)
+      },
+    }
+  }
+}
+
+impl CellDataDecoder for RollupTypeOption {
+  fn decode_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(RollupCellData::from(cell))
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    RollupCellData::from(cell).number
+  }
+}
+
+impl TypeOptionTransform for RollupTypeOption {}
+
+impl TypeOptionCellDataSerde for RollupTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    RollupCellDataPB {
+      value: cell_data.value,
+    }
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(RollupCellData::from(cell))
+  }
+}
@@ -0,0 +1,54 @@
+use collab::core::any_map::AnyMapExtension;
+use collab_database::rows::{new_cell_builder, Cell};
+
+use crate::entities::FieldType;
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+const ROLLUP_NUMBER: &str = "number";
+
+/// The aggregated value of a rollup cell. It is calculated whenever the linked rows or the
+/// values of the rolled up field change, and is stored in the cell.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct RollupCellData {
+  pub value: String,
+  /// Set if the aggregation produces a number, used for sorting and calculations.
+  pub number: Option<f64>,
+}
+
+impl TypeOptionCellData for RollupCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.value.is_empty()
+  }
+}
+
+impl From<&Cell> for RollupCellData {
+  fn from(cell: &Cell) -> Self {
+    let value = cell.get_str_value(CELL_DATA).unwrap_or_default();
+    let number = cell
+      .get_str_value(ROLLUP_NUMBER)
+      .and_then(|s| s.parse::<f64>().ok());
+    Self { value, number }
+  }
+}
+
+impl From<&RollupCellData> for Cell {
+  fn from(data: &RollupCellData) -> Self {
+    let number = data.number.map(|num| num.to_string()).unwrap_or_default();
+    new_cell_builder(FieldType::Rollup)
+      .insert_str_value(CELL_DATA, data.value.clone())
+      .insert_str_value(ROLLUP_NUMBER, number)
+      .build()
+  }
+}
+
+impl ToString for RollupCellData {
+  fn to_string(&self) -> String {
+    self.value.clone()
+  }
+}
+
+impl AsRef<str> for RollupCellData {
+  fn as_ref(&self) -> &str {
+    &self.value
+  }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use collab_database::fields::{Field, TypeOptionData};
+  use collab_database::rows::RowCell;
+
+  use crate::entities::{CalculationType, FieldType};
+  use crate::services::cell::{insert_number_cell, insert_text_cell};
+  use crate::services::field::type_options::rollup_type_option::*;
+  use crate::services::field::{FieldBuilder, NumberTypeOption, RichTextTypeOption};
+
+  #[test]
+  fn rollup_type_option_serde_test() {
+    let type_option = RollupTypeOption {
+      relation_field_id: "relation".to_string(),
+      target_field_id: "price".to_string(),
+      calculation_type: CalculationType::Median,
+      show_original: false,
+    };
+    let data: TypeOptionData = type_option.into();
+    let type_option = RollupTypeOption::from(data);
+    assert_eq!(type_option.relation_field_id, "relation");
+    assert_eq!(type_option.target_field_id, "price");
+    assert_eq!(type_option.calculation_type, CalculationType::Median);
+    assert!(!type_option.show_original);
+  }
+
+  #[test]
+  fn rollup_calculation_test() {
+    let field = FieldBuilder::new(FieldType::Number, NumberTypeOption::default()).build();
+    let cells = || number_cells(&field, vec![Some(4), Some(1), None, Some(10)]);
+
+    assert_rollup(CalculationType::Sum, &field, cells(), "15", Some(15.0));
+    assert_rollup(CalculationType::Average, &field, cells(), "5", Some(5.0));
+    assert_rollup(CalculationType::Median, &field, cells(), "4", Some(4.0));
+    assert_rollup(CalculationType::Min, &field, cells(), "1", Some(1.0));
+    assert_rollup(CalculationType::Max, &field, cells(), "10", Some(10.0));
+    assert_rollup(CalculationType::Count, &field, cells(), "4", Some(4.0));
+    assert_rollup(CalculationType::CountEmpty, &field, cells(), "1", Some(1.0));
+    assert_rollup(
+      CalculationType::CountNonEmpty,
+      &field,
+      cells(),
+      "3",
+      Some(3.0),
+    );
+
+    // Nothing to aggregate if the row isn't linked to any row.
+    assert_rollup(CalculationType::Sum, &field, vec![], "", None);
+  }
+
+  #[test]
+  fn rollup_show_original_test() {
+    let field = FieldBuilder::new(FieldType::RichText, RichTextTypeOption::default()).build();
+    let type_option = RollupTypeOption {
+      show_original: true,
+      ..Default::default()
+    };
+    let cells = vec!["apple", "", "pear"]
+      .into_iter()
+      .enumerate()
+      .map(|(index, s)| {
+        Arc::new(RowCell {
+          row_id: index.to_string().into(),
+          cell: Some(insert_text_cell(s.to_string(), &field)),
+        })
+      })
+      .collect();
+
+    let cell_data = type_option.calculate(&field, cells);
+    assert_eq!(cell_data.value, "apple, pear");
+    assert_eq!(cell_data.number, None);
+  }
+
+  fn number_cells(field: &Field, numbers: Vec<Option<i64>>) -> Vec<Arc<RowCell>> {
+    numbers
+      .into_iter()
+      .enumerate()
+      .map(|(index, num)| {
+        Arc::new(RowCell {
+          row_id: index.to_string().into(),
+          cell: num.map(|num| insert_number_cell(num, field)),
+        })
+      })
+      .collect()
+  }
+
+  fn assert_rollup(
+    calculation_type: CalculationType,
+    field: &Field,
+    cells: Vec<Arc<RowCell>>,
+    expected_value: &str,
+    expected_number: Option<f64>,
+  ) {
+    let type_option = RollupTypeOption {
+      calculation_type,
+      ..Default::default()
+    };
+    let cell_data = type_option.calculate(field, cells);
+    assert_eq!(cell_data.value, expected_value, "{:?}", calculation_type);
+    assert_eq!(cell_data.number, expected_number, "{:?}", calculation_type);
+  }
+}
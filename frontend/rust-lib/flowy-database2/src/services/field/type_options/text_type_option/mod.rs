@@ -1,6 +1,8 @@
 #![allow(clippy::module_inception)]
+mod text_delta;
 mod text_filter;
 mod text_tests;
 mod text_type_option;
 
+pub use text_delta::*;
 pub use text_type_option::*;
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+const MENTION: &str = "mention";
+const MENTION_TYPE: &str = "type";
+const MENTION_PERSON: &str = "person";
+const MENTION_PERSON_ID: &str = "person_id";
+const MENTION_PERSON_NAME: &str = "person_name";
+const MENTION_DATE: &str = "date";
+
+/// An insert operation of a delta, the same format used by the text blocks of a document.
+/// For example: `{ "insert": "AppFlowy", "attributes": { "href": "https://appflowy.io" } }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextDeltaOp {
+  pub insert: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub attributes: Option<Map<String, Value>>,
+}
+
+impl TextDeltaOp {
+  /// Returns the text of the op. A mention is shown as the name of the mentioned person or as
+  /// the date it mentions, and the other mentions, eg. a page mention whose name isn't kept in
+  /// the delta, are shown as `@` followed by their type.
+  fn to_text(&self) -> String {
+    let mention = match self
+      .attributes
+      .as_ref()
+      .and_then(|attributes| attributes.get(MENTION))
+    {
+      None => return self.insert.clone(),
+      Some(mention) => mention,
+    };
+    let mention_type = mention
+      .get(MENTION_TYPE)
+      .and_then(Value::as_str)
+      .unwrap_or_default();
+    let text = match mention_type {
+      MENTION_PERSON => mention
+        .get(MENTION_PERSON_NAME)
+        .and_then(Value::as_str)
+        .map(|name| format!("@{}", name)),
+      MENTION_DATE => mention
+        .get(MENTION_DATE)
+        .and_then(Value::as_str)
+        .map(str::to_string),
+      _ => None,
+    };
+    text.unwrap_or_else(|| format!("@{}", mention_type))
+  }
+
+  /// Returns the id of the person if the op is a person mention, eg.
+  /// `{ "insert": "$", "attributes": { "mention": { "type": "person", "person_id": "1" } } }`
+  fn mentioned_person_id(&self) -> Option<String> {
+    let mention = self.attributes.as_ref()?.get(MENTION)?;
+    if mention.get(MENTION_TYPE)?.as_str()? != MENTION_PERSON {
+      return None;
+    }
+    match mention.get(MENTION_PERSON_ID)? {
+      Value::String(id) => Some(id.clone()),
+      Value::Number(id) => Some(id.to_string()),
+      _ => None,
+    }
+  }
+}
+
+/// Parses the json string of a delta. Returns None if the string isn't a delta, which means
+/// it's a plain text.
+pub fn parse_delta(s: &str) -> Option<Vec<TextDeltaOp>> {
+  if !s.trim_start().starts_with('[') {
+    return None;
+  }
+  serde_json::from_str::<Vec<TextDeltaOp>>(s).ok()
+}
+
+/// Returns the plain text of the delta. The formatting is dropped and the placeholder of a
+/// mention, eg. the `$` of a page mention, is replaced with the text of the mention.
+pub fn delta_to_text(delta: &[TextDeltaOp]) -> String {
+  delta.iter().map(|op| op.to_text()).collect()
+}
+
+/// Returns the ids of the persons mentioned in the delta.
+pub fn delta_mentioned_person_ids(delta: &[TextDeltaOp]) -> Vec<String> {
+  delta
+    .iter()
+    .flat_map(|op| op.mentioned_person_id())
+    .collect()
+}
+
+/// Returns the json string of a delta that inserts the plain text without any formatting.
+pub fn text_to_delta_json(text: &str) -> String {
+  let delta = if text.is_empty() {
+    vec![]
+  } else {
+    vec![TextDeltaOp {
+      insert: text.to_string(),
+      attributes: None,
+    }]
+  };
+  serde_json::to_string(&delta).unwrap_or_else(|_| "[]".to_string())
+}
@@ -1,9 +1,23 @@
 #[cfg(test)]
 mod tests {
-  use crate::entities::FieldType;
-  use crate::services::cell::{insert_select_option_cell, stringify_cell};
+  use std::cmp::Ordering;
+
+  use crate::entities::{FieldType, TextFilterConditionPB, TextFilterPB};
+  use crate::services::cell::{
+    insert_select_option_cell, insert_text_cell, stringify_cell, CellDataChangeset, CellDataDecoder,
+  };
   use crate::services::field::FieldBuilder;
   use crate::services::field::*;
+  use crate::services::sort::SortCondition;
+
+  const DELTA: &str = r#"[{"insert":"Visit "},{"insert":"AppFlowy","attributes":{"href":"https://appflowy.io","bold":true}},{"insert":"$","attributes":{"mention":{"type":"page","page_id":"abc"}}}]"#;
+
+  fn rich_text_type_option() -> RichTextTypeOption {
+    RichTextTypeOption {
+      is_rich_text: true,
+      ..Default::default()
+    }
+  }
 
   // Test parser the cell data which field's type is FieldType::Date to cell data
   // which field's type is FieldType::Text
@@ -25,6 +39,7 @@ mod tests {
       include_time: true,
       is_range: false,
       reminder_id: String::new(),
+      recurrence: String::new(),
     };
 
     assert_eq!(
@@ -38,6 +53,7 @@ mod tests {
       include_time: true,
       is_range: false,
       reminder_id: String::new(),
+      recurrence: String::new(),
     };
 
     assert_eq!(
@@ -51,6 +67,7 @@ mod tests {
       include_time: true,
       is_range: true,
       reminder_id: String::new(),
+      recurrence: String::new(),
     };
 
     assert_eq!(
@@ -101,4 +118,106 @@ mod tests {
       format!("{},{}", france.name, argentina.name)
     );
   }
+
+  #[test]
+  fn rich_text_cell_keeps_delta_and_plain_text() {
+    let type_option = rich_text_type_option();
+    let (cell, cell_data) = type_option
+      .apply_changeset(DELTA.to_string(), None)
+      .unwrap();
+
+    assert_eq!(cell_data.text, "Visit AppFlowy@page");
+    assert_eq!(cell_data.delta.as_deref(), Some(DELTA));
+
+    let decoded = type_option.decode_cell(&cell).unwrap();
+    assert_eq!(decoded.text, "Visit AppFlowy@page");
+    assert_eq!(
+      type_option.protobuf_encode(decoded).to_string(),
+      DELTA.to_string()
+    );
+
+    let field = FieldBuilder::new(FieldType::RichText, type_option).build();
+    assert_eq!(stringify_cell(&cell, &field), "Visit AppFlowy@page");
+  }
+
+  #[test]
+  fn rich_text_cell_shows_the_mentions() {
+    let type_option = rich_text_type_option();
+    let delta = r#"[{"insert":"Ask "},{"insert":"$","attributes":{"mention":{"type":"person","person_id":"1","person_name":"Lucas"}}},{"insert":" before "},{"insert":"$","attributes":{"mention":{"type":"date","date":"2024-03-01"}}}]"#;
+    let (_, cell_data) = type_option
+      .apply_changeset(delta.to_string(), None)
+      .unwrap();
+
+    assert_eq!(cell_data.text, "Ask @Lucas before 2024-03-01");
+  }
+
+  #[test]
+  fn rich_text_cell_length_includes_the_formatting() {
+    let type_option = rich_text_type_option();
+    let op = r#"{"insert":"a","attributes":{"bold":true,"italic":true,"underline":true}}"#;
+    let delta = format!("[{}]", vec![op; 1000].join(","));
+    let (_, cell_data) = type_option.apply_changeset(delta, None).unwrap();
+    assert_eq!(cell_data.text.len(), 1000);
+
+    // The text is short, but the delta is too long
+    let delta = format!("[{}]", vec![op; 2000].join(","));
+    assert!(type_option.apply_changeset(delta, None).is_err());
+  }
+
+  #[test]
+  fn rich_text_cell_with_plain_text_changeset() {
+    let type_option = rich_text_type_option();
+    let (cell, cell_data) = type_option
+      .apply_changeset("[draft] notes".to_string(), None)
+      .unwrap();
+
+    assert_eq!(cell_data.text, "[draft] notes");
+    assert!(cell_data.delta.is_none());
+    assert_eq!(
+      type_option
+        .protobuf_encode(type_option.decode_cell(&cell).unwrap())
+        .to_string(),
+      r#"[{"insert":"[draft] notes"}]"#
+    );
+  }
+
+  #[test]
+  fn plain_text_field_stores_delta_as_text() {
+    let field = FieldBuilder::new(FieldType::RichText, RichTextTypeOption::default()).build();
+    let cell = insert_text_cell(DELTA.to_string(), &field);
+
+    assert_eq!(stringify_cell(&cell, &field), DELTA);
+  }
+
+  #[test]
+  fn rich_text_cell_filter_and_sort_use_plain_text() {
+    let type_option = rich_text_type_option();
+    let (_, visit) = type_option
+      .apply_changeset(DELTA.to_string(), None)
+      .unwrap();
+    let (_, appflowy) = type_option
+      .apply_changeset(
+        r#"[{"insert":"AppFlowy","attributes":{"italic":true}}]"#.to_string(),
+        None,
+      )
+      .unwrap();
+
+    let filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextStartsWith,
+      content: "visit app".to_string(),
+    };
+    assert!(type_option.apply_filter(&filter, &visit));
+    assert!(!type_option.apply_filter(&filter, &appflowy));
+
+    let filter = TextFilterPB {
+      condition: TextFilterConditionPB::TextContains,
+      content: "href".to_string(),
+    };
+    assert!(!type_option.apply_filter(&filter, &visit));
+
+    assert_eq!(
+      type_option.apply_cmp(&appflowy, &visit, SortCondition::Ascending),
+      Ordering::Less
+    );
+  }
 }
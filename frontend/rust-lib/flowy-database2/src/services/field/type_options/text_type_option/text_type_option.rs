@@ -11,21 +11,34 @@ use crate::entities::{FieldType, TextFilterPB};
 use crate::services::cell::{stringify_cell, CellDataChangeset, CellDataDecoder};
 use crate::services::field::type_options::util::ProtobufStr;
 use crate::services::field::{
-  TypeOption, TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
-  TypeOptionCellDataSerde, TypeOptionTransform, CELL_DATA,
+  delta_to_text, parse_delta, text_to_delta_json, TypeOption, TypeOptionCellData,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
+  TypeOptionTransform, CELL_DATA,
 };
 use crate::services::sort::SortCondition;
 
-/// For the moment, the `RichTextTypeOptionPB` is empty. The `data` property is not
-/// used yet.
+/// The key of the delta of a rich-text cell. The plain text of the delta is stored under
+/// [CELL_DATA].
+pub const CELL_DELTA: &str = "delta";
+const IS_RICH_TEXT: &str = "is_rich_text";
+/// The maximum length of the plain text of a cell.
+const MAX_TEXT_LEN: usize = 10000;
+/// The maximum length of the delta of a rich-text cell.
+const MAX_DELTA_LEN: usize = 10 * MAX_TEXT_LEN;
+
+/// The `data` property of the `RichTextTypeOptionPB` is not used yet.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RichTextTypeOption {
   #[serde(default)]
   pub inner: String,
+  /// If true, the cells keep the formatting, links and mentions of the text as a delta.
+  /// Filtering, sorting and exporting the cells still use the plain text of the delta.
+  #[serde(default)]
+  pub is_rich_text: bool,
 }
 
 impl TypeOption for RichTextTypeOption {
-  type CellData = StringCellData;
+  type CellData = RichTextCellData;
   type CellChangeset = String;
   type CellProtobufType = ProtobufStr;
   type CellFilter = TextFilterPB;
@@ -34,7 +47,11 @@ impl TypeOption for RichTextTypeOption {
 impl From<TypeOptionData> for RichTextTypeOption {
   fn from(data: TypeOptionData) -> Self {
     let s = data.get_str_value(CELL_DATA).unwrap_or_default();
-    Self { inner: s }
+    let is_rich_text = data.get_bool_value(IS_RICH_TEXT).unwrap_or_default();
+    Self {
+      inner: s,
+      is_rich_text,
+    }
   }
 }
 
@@ -42,6 +59,7 @@ impl From<RichTextTypeOption> for TypeOptionData {
   fn from(data: RichTextTypeOption) -> Self {
     TypeOptionDataBuilder::new()
       .insert_str_value(CELL_DATA, data.inner)
+      .insert_bool_value(IS_RICH_TEXT, data.is_rich_text)
       .build()
   }
 }
@@ -53,17 +71,25 @@ impl TypeOptionCellDataSerde for RichTextTypeOption {
     &self,
     cell_data: <Self as TypeOption>::CellData,
   ) -> <Self as TypeOption>::CellProtobufType {
-    ProtobufStr::from(cell_data.0)
+    if self.is_rich_text {
+      // The cells that were filled before switching to rich-text only have the plain text
+      let delta = cell_data
+        .delta
+        .unwrap_or_else(|| text_to_delta_json(&cell_data.text));
+      ProtobufStr::from(delta)
+    } else {
+      ProtobufStr::from(cell_data.text)
+    }
   }
 
   fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
-    Ok(StringCellData::from(cell))
+    Ok(RichTextCellData::from(cell))
   }
 }
 
 impl CellDataDecoder for RichTextTypeOption {
   fn decode_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
-    Ok(StringCellData::from(cell))
+    Ok(RichTextCellData::from(cell))
   }
 
   fn decode_cell_with_transform(
@@ -82,7 +108,9 @@ impl CellDataDecoder for RichTextTypeOption {
       | FieldType::URL
       | FieldType::Summary
       | FieldType::Translate
-      | FieldType::Time => Some(StringCellData::from(stringify_cell(cell, field))),
+      | FieldType::Time
+      | FieldType::Formula
+      | FieldType::Rollup => Some(RichTextCellData::from(stringify_cell(cell, field))),
       FieldType::Checklist
       | FieldType::LastEditedTime
       | FieldType::CreatedTime
@@ -91,7 +119,7 @@ impl CellDataDecoder for RichTextTypeOption {
   }
 
   fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
-    cell_data.to_string()
+    cell_data.text
   }
 
   fn numeric_cell(&self, cell: &Cell) -> Option<f64> {
@@ -105,13 +133,26 @@ impl CellDataChangeset for RichTextTypeOption {
     changeset: <Self as TypeOption>::CellChangeset,
     _cell: Option<Cell>,
   ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
-    if changeset.len() > 10000 {
-      Err(
-        FlowyError::text_too_long()
-          .with_context("The len of the text should not be more than 10000"),
-      )
+    // In rich-text mode, the changeset is either a delta or a plain text
+    let text_cell_data = if self.is_rich_text {
+      RichTextCellData::from_delta_or_text(changeset)
+    } else {
+      RichTextCellData::from(changeset)
+    };
+
+    // The delta also keeps the formatting and the mentions, so it may be longer than the text
+    let delta_len = text_cell_data.delta.as_ref().map_or(0, |delta| delta.len());
+    if text_cell_data.text.len() > MAX_TEXT_LEN {
+      Err(FlowyError::text_too_long().with_context(format!(
+        "The len of the text should not be more than {}",
+        MAX_TEXT_LEN
+      )))
+    } else if delta_len > MAX_DELTA_LEN {
+      Err(FlowyError::text_too_long().with_context(format!(
+        "The len of the rich text should not be more than {}",
+        MAX_DELTA_LEN
+      )))
     } else {
-      let text_cell_data = StringCellData(changeset);
       Ok((text_cell_data.clone().into(), text_cell_data))
     }
   }
@@ -123,7 +164,7 @@ impl TypeOptionCellDataFilter for RichTextTypeOption {
     filter: &<Self as TypeOption>::CellFilter,
     cell_data: &<Self as TypeOption>::CellData,
   ) -> bool {
-    filter.is_visible(cell_data)
+    filter.is_visible(&cell_data.text)
   }
 }
 
@@ -139,13 +180,77 @@ impl TypeOptionCellDataCompare for RichTextTypeOption {
       (true, false) => Ordering::Greater,
       (false, true) => Ordering::Less,
       (false, false) => {
-        let order = cell_data.0.cmp(&other_cell_data.0);
+        let order = cell_data.text.cmp(&other_cell_data.text);
         sort_condition.evaluate_order(order)
       },
     }
   }
 }
 
+/// The data of a text cell. The `text` is the plain text of the cell, it's used to filter,
+/// sort and export the cell. The `delta` is only set when the text is edited in rich-text mode.
+#[derive(Default, Debug, Clone)]
+pub struct RichTextCellData {
+  pub text: String,
+  pub delta: Option<String>,
+}
+
+impl RichTextCellData {
+  /// Keeps the `s` as the delta of the cell if it's a delta, otherwise `s` is a plain text.
+  pub fn from_delta_or_text(s: String) -> Self {
+    match parse_delta(&s) {
+      Some(delta) => Self {
+        text: delta_to_text(&delta),
+        delta: Some(s),
+      },
+      None => Self::from(s),
+    }
+  }
+}
+
+impl TypeOptionCellData for RichTextCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.text.is_empty()
+  }
+}
+
+impl From<&Cell> for RichTextCellData {
+  fn from(cell: &Cell) -> Self {
+    Self {
+      text: cell.get_str_value(CELL_DATA).unwrap_or_default(),
+      delta: cell.get_str_value(CELL_DELTA),
+    }
+  }
+}
+
+impl From<RichTextCellData> for Cell {
+  fn from(data: RichTextCellData) -> Self {
+    let mut builder = new_cell_builder(FieldType::RichText).insert_str_value(CELL_DATA, data.text);
+    if let Some(delta) = data.delta {
+      builder = builder.insert_str_value(CELL_DELTA, delta);
+    }
+    builder.build()
+  }
+}
+
+impl std::convert::From<String> for RichTextCellData {
+  fn from(text: String) -> Self {
+    Self { text, delta: None }
+  }
+}
+
+impl std::convert::From<RichTextCellData> for String {
+  fn from(value: RichTextCellData) -> Self {
+    value.text
+  }
+}
+
+impl AsRef<str> for RichTextCellData {
+  fn as_ref(&self) -> &str {
+    self.text.as_str()
+  }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct StringCellData(pub String);
 impl std::ops::Deref for StringCellData {
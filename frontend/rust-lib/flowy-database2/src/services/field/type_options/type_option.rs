@@ -9,18 +9,19 @@ use protobuf::ProtobufError;
 use flowy_error::FlowyResult;
 
 use crate::entities::{
-  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType,
+  CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType, FormulaTypeOptionPB,
   MultiSelectTypeOptionPB, NumberTypeOptionPB, RelationTypeOptionPB, RichTextTypeOptionPB,
-  SingleSelectTypeOptionPB, SummarizationTypeOptionPB, TimeTypeOptionPB, TimestampTypeOptionPB,
-  TranslateTypeOptionPB, URLTypeOptionPB,
+  RollupTypeOptionPB, SingleSelectTypeOptionPB, SummarizationTypeOptionPB, TimeTypeOptionPB,
+  TimestampTypeOptionPB, TranslateTypeOptionPB, URLTypeOptionPB,
 };
 use crate::services::cell::CellDataDecoder;
 use crate::services::field::checklist_type_option::ChecklistTypeOption;
 use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
 use crate::services::field::{
-  CheckboxTypeOption, DateTypeOption, MultiSelectTypeOption, NumberTypeOption, RelationTypeOption,
-  RichTextTypeOption, SingleSelectTypeOption, TimeTypeOption, TimestampTypeOption, URLTypeOption,
+  CheckboxTypeOption, DateTypeOption, FormulaTypeOption, MultiSelectTypeOption, NumberTypeOption,
+  RelationTypeOption, RichTextTypeOption, RollupTypeOption, SingleSelectTypeOption, TimeTypeOption,
+  TimestampTypeOption, URLTypeOption,
 };
 use crate::services::filter::{ParseFilterData, PreFillCellsWithFilter};
 use crate::services::sort::SortCondition;
@@ -191,6 +192,12 @@ pub fn type_option_data_from_pb<T: Into<Bytes>>(
     FieldType::Translate => {
       TranslateTypeOptionPB::try_from(bytes).map(|pb| TranslateTypeOption::from(pb).into())
     },
+    FieldType::Formula => {
+      FormulaTypeOptionPB::try_from(bytes).map(|pb| FormulaTypeOption::from(pb).into())
+    },
+    FieldType::Rollup => {
+      RollupTypeOptionPB::try_from(bytes).map(|pb| RollupTypeOption::from(pb).into())
+    },
   }
 }
 
@@ -268,6 +275,18 @@ pub fn type_option_to_pb(type_option: TypeOptionData, field_type: &FieldType) ->
         .try_into()
         .unwrap()
     },
+    FieldType::Formula => {
+      let formula_type_option: FormulaTypeOption = type_option.into();
+      FormulaTypeOptionPB::from(formula_type_option)
+        .try_into()
+        .unwrap()
+    },
+    FieldType::Rollup => {
+      let rollup_type_option: RollupTypeOption = type_option.into();
+      RollupTypeOptionPB::from(rollup_type_option)
+        .try_into()
+        .unwrap()
+    },
   }
 }
 
@@ -290,5 +309,7 @@ pub fn default_type_option_data_from_type(field_type: FieldType) -> TypeOptionDa
     FieldType::Summary => SummarizationTypeOption::default().into(),
     FieldType::Translate => TranslateTypeOption::default().into(),
     FieldType::Time => TimeTypeOption.into(),
+    FieldType::Formula => FormulaTypeOption::default().into(),
+    FieldType::Rollup => RollupTypeOption::default().into(),
   }
 }
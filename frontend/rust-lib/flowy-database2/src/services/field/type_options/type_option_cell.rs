@@ -13,10 +13,11 @@ use crate::services::cell::{CellCache, CellDataChangeset, CellDataDecoder, CellP
 use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
 use crate::services::field::translate_type_option::translate::TranslateTypeOption;
 use crate::services::field::{
-  CheckboxTypeOption, ChecklistTypeOption, DateTypeOption, MultiSelectTypeOption, NumberTypeOption,
-  RelationTypeOption, RichTextTypeOption, SingleSelectTypeOption, TimeTypeOption,
-  TimestampTypeOption, TypeOption, TypeOptionCellData, TypeOptionCellDataCompare,
-  TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionTransform, URLTypeOption,
+  CheckboxTypeOption, ChecklistTypeOption, DateTypeOption, FormulaTypeOption,
+  MultiSelectTypeOption, NumberTypeOption, RelationTypeOption, RichTextTypeOption,
+  RollupTypeOption, SingleSelectTypeOption, TimeTypeOption, TimestampTypeOption, TypeOption,
+  TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
+  TypeOptionTransform, URLTypeOption,
 };
 use crate::services::sort::SortCondition;
 
@@ -470,6 +471,26 @@ impl<'a> TypeOptionCellExt<'a> {
             self.cell_data_cache.clone(),
           )
         }),
+      FieldType::Formula => self
+        .field
+        .get_type_option::<FormulaTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
+      FieldType::Rollup => self
+        .field
+        .get_type_option::<RollupTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
     }
   }
 
@@ -579,6 +600,12 @@ fn get_type_option_transform_handler(
     FieldType::Translate => {
       Box::new(TranslateTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
     },
+    FieldType::Formula => {
+      Box::new(FormulaTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
+    FieldType::Rollup => {
+      Box::new(RollupTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
   }
 }
 
@@ -633,4 +660,8 @@ impl RowSingleCellData {
     into_check_list_field_cell_data,
     <CheckboxTypeOption as TypeOption>::CellData
   );
+  into_cell_data!(
+    into_relation_field_cell_data,
+    <RelationTypeOption as TypeOption>::CellData
+  );
 }
@@ -2,6 +2,7 @@ use collab::core::any_map::AnyMapExtension;
 use collab_database::views::{DatabaseLayout, FieldSettingsMap, FieldSettingsMapBuilder};
 
 use crate::entities::FieldVisibility;
+use crate::services::conditional_format::ConditionalFormat;
 use crate::services::field_settings::default_field_visibility;
 
 /// Stores the field settings for a single field
@@ -11,12 +12,15 @@ pub struct FieldSettings {
   pub visibility: FieldVisibility,
   pub width: i32,
   pub wrap_cell_content: bool,
+  /// The conditional formatting rules whose condition is on this field.
+  pub conditional_formats: Vec<ConditionalFormat>,
 }
 
 pub const VISIBILITY: &str = "visibility";
 pub const WIDTH: &str = "width";
 pub const DEFAULT_WIDTH: i32 = 150;
 pub const WRAP_CELL_CONTENT: &str = "wrap";
+pub const CONDITIONAL_FORMATS: &str = "conditional_formats";
 
 impl FieldSettings {
   pub fn from_any_map(
@@ -35,22 +39,30 @@ impl FieldSettings {
     let wrap_cell_content = field_settings
       .get_bool_value(WRAP_CELL_CONTENT)
       .unwrap_or(true);
+    let conditional_formats = field_settings
+      .get_str_value(CONDITIONAL_FORMATS)
+      .and_then(|value| serde_json::from_str(&value).ok())
+      .unwrap_or_default();
 
     Self {
       field_id: field_id.to_string(),
       visibility,
       width,
       wrap_cell_content,
+      conditional_formats,
     }
   }
 }
 
 impl From<FieldSettings> for FieldSettingsMap {
   fn from(field_settings: FieldSettings) -> Self {
+    let conditional_formats =
+      serde_json::to_string(&field_settings.conditional_formats).unwrap_or_default();
     FieldSettingsMapBuilder::new()
       .insert_i64_value(VISIBILITY, field_settings.visibility.into())
       .insert_i64_value(WIDTH, field_settings.width as i64)
       .insert_bool_value(WRAP_CELL_CONTENT, field_settings.wrap_cell_content)
+      .insert_str_value(CONDITIONAL_FORMATS, conditional_formats)
       .build()
   }
 }
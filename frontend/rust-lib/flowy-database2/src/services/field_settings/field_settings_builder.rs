@@ -8,6 +8,7 @@ use strum::IntoEnumIterator;
 
 use crate::entities::FieldVisibility;
 use crate::services::field_settings::{FieldSettings, DEFAULT_WIDTH, VISIBILITY};
+use crate::services::setting::GridLayout;
 
 /// Helper struct to create a new field setting
 pub struct FieldSettingsBuilder {
@@ -82,6 +83,34 @@ pub fn field_settings_for_field(
     .into()
 }
 
+/// The gallery, list and timeline layouts are stored as the grid layout, but they hide the empty
+/// fields like the board and calendar layouts.
+pub fn default_field_settings_for_grid_layout(
+  fields: &[Field],
+  grid_layout: GridLayout,
+) -> FieldSettingsByFieldIdMap {
+  let visibility = match grid_layout {
+    GridLayout::Table => FieldVisibility::AlwaysShown,
+    GridLayout::Gallery | GridLayout::List | GridLayout::Timeline => FieldVisibility::HideWhenEmpty,
+  };
+  fields
+    .iter()
+    .map(|field| {
+      let visibility = if field.is_primary {
+        FieldVisibility::AlwaysShown
+      } else {
+        visibility.clone()
+      };
+      let field_settings: FieldSettingsMap = FieldSettingsBuilder::new(&field.id)
+        .visibility(visibility)
+        .build()
+        .into();
+      (field.id.clone(), field_settings)
+    })
+    .collect::<HashMap<_, _>>()
+    .into()
+}
+
 pub fn default_field_settings_by_layout_map() -> HashMap<DatabaseLayout, FieldSettingsMap> {
   let mut map = HashMap::new();
   for layout_ty in DatabaseLayout::iter() {
@@ -284,6 +284,8 @@ impl FilterInner {
       FieldType::Summary => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
       FieldType::Translate => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
       FieldType::Time => BoxAny::new(TimeFilterPB::parse(condition as u8, content)),
+      FieldType::Formula => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
+      FieldType::Rollup => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
     };
 
     FilterInner::Data {
@@ -378,6 +380,10 @@ impl<'a> From<&'a Filter> for FilterMap {
               let filter = condition_and_content.cloned::<TextFilterPB>()?;
               (filter.condition as u8, filter.content)
             },
+            FieldType::Formula | FieldType::Rollup => {
+              let filter = condition_and_content.cloned::<TextFilterPB>()?;
+              (filter.condition as u8, filter.content)
+            },
           };
           Some((condition, content))
         };
@@ -1,5 +1,8 @@
+pub mod automation;
 pub mod calculations;
 pub mod cell;
+pub mod comment;
+pub mod conditional_format;
 pub mod database;
 pub mod database_view;
 pub mod field;
@@ -10,3 +13,4 @@ pub mod setting;
 pub mod share;
 pub mod snapshot;
 pub mod sort;
+pub mod validation;
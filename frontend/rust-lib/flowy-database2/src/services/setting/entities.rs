@@ -1,4 +1,7 @@
+use std::sync::Arc;
+
 use collab::core::any_map::AnyMapExtension;
+use collab::preclude::Any;
 use collab_database::views::{LayoutSetting, LayoutSettingBuilder};
 use serde::{Deserialize, Serialize};
 use serde_repr::*;
@@ -123,3 +126,303 @@ impl From<BoardLayoutSetting> for LayoutSetting {
       .build()
   }
 }
+
+/// The layouts that display the rows of a grid view. The database view can only store the grid,
+/// board and calendar layouts, so the gallery, list and timeline layouts are stored as a grid
+/// layout whose [GridLayoutSetting] tells which of them is used.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum GridLayout {
+  #[default]
+  Table = 0,
+  Gallery = 1,
+  List = 2,
+  Timeline = 3,
+}
+
+impl From<i64> for GridLayout {
+  fn from(value: i64) -> Self {
+    match value {
+      0 => GridLayout::Table,
+      1 => GridLayout::Gallery,
+      2 => GridLayout::List,
+      3 => GridLayout::Timeline,
+      _ => GridLayout::Table,
+    }
+  }
+}
+
+impl GridLayout {
+  pub fn value(&self) -> i64 {
+    *self as i64
+  }
+}
+
+/// The layout setting of a grid view. The settings of the gallery, list and timeline layouts are
+/// kept when switching between the layouts.
+#[derive(Debug, Clone, Default)]
+pub struct GridLayoutSetting {
+  pub layout_ty: GridLayout,
+  pub gallery: CardLayoutSetting,
+  pub list: CardLayoutSetting,
+  pub timeline: TimelineLayoutSetting,
+}
+
+impl GridLayoutSetting {
+  pub fn new(layout_ty: GridLayout) -> Self {
+    Self {
+      layout_ty,
+      ..Default::default()
+    }
+  }
+}
+
+const GRID_LAYOUT_TY: &str = "layout_ty";
+
+impl From<LayoutSetting> for GridLayoutSetting {
+  fn from(setting: LayoutSetting) -> Self {
+    let layout_ty = setting
+      .get_i64_value(GRID_LAYOUT_TY)
+      .map(GridLayout::from)
+      .unwrap_or_default();
+    Self {
+      layout_ty,
+      gallery: CardLayoutSetting::from_layout_setting(&setting, &GALLERY_KEYS),
+      list: CardLayoutSetting::from_layout_setting(&setting, &LIST_KEYS),
+      timeline: TimelineLayoutSetting::from_layout_setting(&setting),
+    }
+  }
+}
+
+impl From<GridLayoutSetting> for LayoutSetting {
+  fn from(setting: GridLayoutSetting) -> Self {
+    let builder =
+      LayoutSettingBuilder::new().insert_i64_value(GRID_LAYOUT_TY, setting.layout_ty.value());
+    let builder = setting.gallery.insert_into(builder, &GALLERY_KEYS);
+    let builder = setting.list.insert_into(builder, &LIST_KEYS);
+    let builder = setting.timeline.insert_into(builder);
+    builder.build()
+  }
+}
+
+/// The setting of the layouts that display each row as a card, the gallery and the list
+/// layouts.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct CardLayoutSetting {
+  pub card_size: CardSize,
+  /// The id of the url field whose value is used as the cover of the card. The cover of the row
+  /// is used if it's empty.
+  pub cover_field_id: String,
+  pub fit_mode: CoverFitMode,
+  /// The fields shown on the card below the primary field, in order.
+  pub visible_field_ids: Vec<String>,
+}
+
+/// The keys of the [CardLayoutSetting] in the [GridLayoutSetting].
+struct CardLayoutKeys {
+  card_size: &'static str,
+  cover_field_id: &'static str,
+  fit_mode: &'static str,
+  visible_field_ids: &'static str,
+}
+
+const GALLERY_KEYS: CardLayoutKeys = CardLayoutKeys {
+  card_size: "gallery_card_size",
+  cover_field_id: "gallery_cover_field_id",
+  fit_mode: "gallery_fit_mode",
+  visible_field_ids: "gallery_visible_field_ids",
+};
+
+const LIST_KEYS: CardLayoutKeys = CardLayoutKeys {
+  card_size: "list_card_size",
+  cover_field_id: "list_cover_field_id",
+  fit_mode: "list_fit_mode",
+  visible_field_ids: "list_visible_field_ids",
+};
+
+impl CardLayoutSetting {
+  fn from_layout_setting(setting: &LayoutSetting, keys: &CardLayoutKeys) -> Self {
+    let card_size = setting
+      .get_i64_value(keys.card_size)
+      .map(CardSize::from)
+      .unwrap_or_default();
+    let cover_field_id = setting
+      .get_str_value(keys.cover_field_id)
+      .unwrap_or_default();
+    let fit_mode = setting
+      .get_i64_value(keys.fit_mode)
+      .map(CoverFitMode::from)
+      .unwrap_or_default();
+    let visible_field_ids = match setting.get(keys.visible_field_ids) {
+      Some(Any::Array(array)) => array
+        .iter()
+        .flat_map(|item| match item {
+          Any::String(field_id) => Some(field_id.to_string()),
+          _ => None,
+        })
+        .collect(),
+      _ => vec![],
+    };
+    Self {
+      card_size,
+      cover_field_id,
+      fit_mode,
+      visible_field_ids,
+    }
+  }
+
+  fn insert_into(
+    self,
+    builder: LayoutSettingBuilder,
+    keys: &CardLayoutKeys,
+  ) -> LayoutSettingBuilder {
+    let visible_field_ids = Any::Array(Arc::from(
+      self
+        .visible_field_ids
+        .into_iter()
+        .map(|field_id| Any::String(Arc::from(field_id)))
+        .collect::<Vec<_>>(),
+    ));
+    builder
+      .insert_i64_value(keys.card_size, self.card_size.value())
+      .insert_str_value(keys.cover_field_id, self.cover_field_id)
+      .insert_i64_value(keys.fit_mode, self.fit_mode.value())
+      .insert_any(keys.visible_field_ids, visible_field_ids)
+  }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum CardSize {
+  Small = 0,
+  #[default]
+  Medium = 1,
+  Large = 2,
+}
+
+impl From<i64> for CardSize {
+  fn from(value: i64) -> Self {
+    match value {
+      0 => CardSize::Small,
+      1 => CardSize::Medium,
+      2 => CardSize::Large,
+      _ => CardSize::Medium,
+    }
+  }
+}
+
+impl CardSize {
+  pub fn value(&self) -> i64 {
+    *self as i64
+  }
+}
+
+/// How the cover image is fitted into the cover area of the card.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum CoverFitMode {
+  /// The image covers the whole area and is cropped if needed.
+  #[default]
+  Fill = 0,
+  /// The whole image is shown inside the area.
+  Fit = 1,
+}
+
+impl From<i64> for CoverFitMode {
+  fn from(value: i64) -> Self {
+    match value {
+      0 => CoverFitMode::Fill,
+      1 => CoverFitMode::Fit,
+      _ => CoverFitMode::Fill,
+    }
+  }
+}
+
+impl CoverFitMode {
+  pub fn value(&self) -> i64 {
+    *self as i64
+  }
+}
+
+/// The setting of the timeline layout, which displays each row as a bar from its start date to
+/// its end date.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TimelineLayoutSetting {
+  /// The id of the date field that contains the start date of the bars.
+  pub start_field_id: String,
+  /// The id of the date field that contains the end date of the bars. The end date of the start
+  /// field is used if it's empty, which requires the date cells to be a range.
+  pub end_field_id: String,
+  pub zoom: TimelineZoom,
+  /// The id of the relation field that links a row to the rows it depends on.
+  pub dependency_field_id: String,
+}
+
+const TIMELINE_START_FIELD_ID: &str = "timeline_start_field_id";
+const TIMELINE_END_FIELD_ID: &str = "timeline_end_field_id";
+const TIMELINE_ZOOM: &str = "timeline_zoom";
+const TIMELINE_DEPENDENCY_FIELD_ID: &str = "timeline_dependency_field_id";
+
+impl TimelineLayoutSetting {
+  pub fn new(start_field_id: String) -> Self {
+    Self {
+      start_field_id,
+      ..Default::default()
+    }
+  }
+
+  fn from_layout_setting(setting: &LayoutSetting) -> Self {
+    Self {
+      start_field_id: setting
+        .get_str_value(TIMELINE_START_FIELD_ID)
+        .unwrap_or_default(),
+      end_field_id: setting
+        .get_str_value(TIMELINE_END_FIELD_ID)
+        .unwrap_or_default(),
+      zoom: setting
+        .get_i64_value(TIMELINE_ZOOM)
+        .map(TimelineZoom::from)
+        .unwrap_or_default(),
+      dependency_field_id: setting
+        .get_str_value(TIMELINE_DEPENDENCY_FIELD_ID)
+        .unwrap_or_default(),
+    }
+  }
+
+  fn insert_into(self, builder: LayoutSettingBuilder) -> LayoutSettingBuilder {
+    builder
+      .insert_str_value(TIMELINE_START_FIELD_ID, self.start_field_id)
+      .insert_str_value(TIMELINE_END_FIELD_ID, self.end_field_id)
+      .insert_i64_value(TIMELINE_ZOOM, self.zoom.value())
+      .insert_str_value(TIMELINE_DEPENDENCY_FIELD_ID, self.dependency_field_id)
+  }
+}
+
+/// The time unit of the columns of the timeline.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum TimelineZoom {
+  Day = 0,
+  #[default]
+  Week = 1,
+  Month = 2,
+  Quarter = 3,
+}
+
+impl From<i64> for TimelineZoom {
+  fn from(value: i64) -> Self {
+    match value {
+      0 => TimelineZoom::Day,
+      1 => TimelineZoom::Week,
+      2 => TimelineZoom::Month,
+      3 => TimelineZoom::Quarter,
+      _ => TimelineZoom::Week,
+    }
+  }
+}
+
+impl TimelineZoom {
+  pub fn value(&self) -> i64 {
+    *self as i64
+  }
+}
@@ -6,11 +6,13 @@ use collab_database::rows::{new_cell_builder, Cell, CreateRowParams};
 use collab_database::views::{CreateDatabaseParams, CreateViewParams, DatabaseLayout};
 
 use collab_entity::EncodedCollab;
+use encoding_rs::Encoding;
 use flowy_error::{FlowyError, FlowyResult};
 
 use crate::entities::FieldType;
 use crate::services::field::{default_type_option_data_from_type, CELL_DATA};
 use crate::services::field_settings::default_field_settings_for_fields;
+use crate::services::share::csv::infer::InferredField;
 use crate::services::share::csv::CSVFormat;
 
 #[derive(Default)]
@@ -22,13 +24,12 @@ impl CSVImporter {
     view_id: &str,
     path: &str,
     style: CSVFormat,
-  ) -> FlowyResult<CreateDatabaseParams> {
+    options: CSVImportOptions,
+  ) -> FlowyResult<CSVImportData> {
     let mut file = File::open(path)?;
-    let mut content = String::new();
-    file.read_to_string(&mut content)?;
-    let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(view_id, fields_with_rows, &style);
-    Ok(database_data)
+    let mut content = vec![];
+    file.read_to_end(&mut content)?;
+    self.import_csv_from_bytes(view_id.to_string(), content, style, options)
   }
 
   pub fn import_csv_from_string(
@@ -36,40 +37,147 @@ impl CSVImporter {
     view_id: String,
     content: String,
     format: CSVFormat,
-  ) -> FlowyResult<CreateDatabaseParams> {
-    let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(&view_id, fields_with_rows, &format);
-    Ok(database_data)
+  ) -> FlowyResult<CSVImportData> {
+    self.import_csv_from_bytes(
+      view_id,
+      content.into_bytes(),
+      format,
+      CSVImportOptions::default(),
+    )
   }
 
-  fn get_fields_and_rows(&self, content: String) -> Result<FieldsRows, FlowyError> {
+  /// Decodes the content with the encoding of the options and creates a database from it.
+  /// The rows that can't be parsed are skipped and returned in [CSVImportData::failed_rows].
+  pub fn import_csv_from_bytes(
+    &self,
+    view_id: String,
+    content: Vec<u8>,
+    format: CSVFormat,
+    options: CSVImportOptions,
+  ) -> FlowyResult<CSVImportData> {
+    let content = decode_content(&content, &options.encoding)?;
+    let fields_with_rows = self.get_fields_and_rows(content, &options)?;
+    let failed_rows = fields_with_rows.failed_rows.clone();
+    let params = database_from_fields_and_rows(&view_id, fields_with_rows, &format);
+    Ok(CSVImportData {
+      params,
+      failed_rows,
+    })
+  }
+
+  fn get_fields_and_rows(
+    &self,
+    content: String,
+    options: &CSVImportOptions,
+  ) -> Result<FieldsRows, FlowyError> {
     let mut fields: Vec<String> = vec![];
     if content.is_empty() {
       return Err(FlowyError::invalid_data().with_context("Import content is empty"));
     }
 
-    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let mut reader = csv::ReaderBuilder::new()
+      .delimiter(options.delimiter)
+      .has_headers(options.has_header)
+      .from_reader(content.as_bytes());
+    // Without a header row, the first record is only used to count the fields.
     if let Ok(headers) = reader.headers() {
-      for header in headers {
-        fields.push(header.to_string());
+      for (index, header) in headers.iter().enumerate() {
+        if options.has_header {
+          fields.push(header.to_string());
+        } else {
+          fields.push(format!("Column {}", index + 1));
+        }
       }
     } else {
       return Err(FlowyError::invalid_data().with_context("Header not found"));
     }
 
-    let rows = reader
-      .records()
-      .flat_map(|r| r.ok())
-      .map(|record| {
-        record
-          .into_iter()
-          .map(|s| s.to_string())
-          .collect::<Vec<String>>()
-      })
-      .collect();
+    let mut rows = vec![];
+    let mut failed_rows = vec![];
+    for (index, record) in reader.records().enumerate() {
+      match record {
+        Ok(record) => rows.push(
+          record
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>(),
+        ),
+        Err(err) => {
+          let line = err
+            .position()
+            .map(|position| position.line())
+            .unwrap_or_else(|| index as u64 + 1 + options.has_header as u64);
+          failed_rows.push(CSVRowError {
+            line,
+            message: err.to_string(),
+          });
+        },
+      }
+    }
+
+    Ok(FieldsRows {
+      fields,
+      rows,
+      failed_rows,
+    })
+  }
+}
+
+/// Options that control how the content of a csv file is read.
+#[derive(Debug, Clone)]
+pub struct CSVImportOptions {
+  /// The character that separates the fields of a row.
+  pub delimiter: u8,
+  /// Whether the first row contains the names of the fields. If not, the fields are named
+  /// `Column 1`, `Column 2`, etc.
+  pub has_header: bool,
+  /// The label of the encoding of the content, e.g. `utf-8`, `gbk` or `windows-1252`.
+  pub encoding: String,
+}
+
+impl Default for CSVImportOptions {
+  fn default() -> Self {
+    Self {
+      delimiter: b',',
+      has_header: true,
+      encoding: "utf-8".to_string(),
+    }
+  }
+}
+
+/// A row of the csv file that couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CSVRowError {
+  /// The line number of the row, starting from 1.
+  pub line: u64,
+  pub message: String,
+}
+
+#[derive(Debug)]
+pub struct CSVImportData {
+  pub params: CreateDatabaseParams,
+  pub failed_rows: Vec<CSVRowError>,
+}
 
-    Ok(FieldsRows { fields, rows })
+/// Decodes the content with the given encoding. A byte order mark takes precedence over the
+/// encoding.
+fn decode_content(content: &[u8], encoding: &str) -> FlowyResult<String> {
+  let label = if encoding.trim().is_empty() {
+    "utf-8"
+  } else {
+    encoding.trim()
+  };
+  let encoding = Encoding::for_label(label.as_bytes()).ok_or_else(|| {
+    FlowyError::invalid_data().with_context(format!("Unsupported encoding: {}", label))
+  })?;
+  let (content, encoding, has_malformed) = encoding.decode(content);
+  if has_malformed {
+    return Err(
+      FlowyError::invalid_data()
+        .with_context(format!("The content is not valid {}", encoding.name())),
+    );
   }
+  Ok(content.into_owned())
 }
 
 fn database_from_fields_and_rows(
@@ -80,11 +188,27 @@ fn database_from_fields_and_rows(
   let (fields, rows) = fields_and_rows.split();
   let database_id = gen_database_id();
 
+  // The type of each column is inferred from its values when importing a plain csv file.
+  let inferred_fields = match format {
+    CSVFormat::Original => fields
+      .iter()
+      .enumerate()
+      .map(|(index, name)| {
+        let values = rows
+          .iter()
+          .map(|row| row.get(index).map(|s| s.as_str()).unwrap_or_default())
+          .collect::<Vec<&str>>();
+        InferredField::new(name.clone(), index == 0, &values)
+      })
+      .collect::<Vec<InferredField>>(),
+    CSVFormat::META => vec![],
+  };
+
   let fields = fields
     .into_iter()
     .enumerate()
     .map(|(index, field_meta)| match format {
-      CSVFormat::Original => default_field(field_meta, index == 0),
+      CSVFormat::Original => inferred_fields[index].field.clone(),
       CSVFormat::META => {
         //
         match serde_json::from_str(&field_meta) {
@@ -110,9 +234,7 @@ fn database_from_fields_and_rows(
 
           // Make the cell based on the style.
           let cell = match format {
-            CSVFormat::Original => new_cell_builder(field_type)
-              .insert_str_value(CELL_DATA, cell_content.to_string())
-              .build(),
+            CSVFormat::Original => inferred_fields[index].cell_from_str(cell_content),
             CSVFormat::META => match serde_json::from_str::<Cell>(cell_content) {
               Ok(cell) => cell,
               Err(_) => new_cell_builder(field_type)
@@ -157,6 +279,7 @@ fn default_field(field_str: String, is_primary: bool) -> Field {
 struct FieldsRows {
   fields: Vec<String>,
   rows: Vec<Vec<String>>,
+  failed_rows: Vec<CSVRowError>,
 }
 impl FieldsRows {
   fn split(self) -> (Vec<String>, Vec<Vec<String>>) {
@@ -168,13 +291,15 @@ pub struct ImportResult {
   pub database_id: String,
   pub view_id: String,
   pub encoded_collab: EncodedCollab,
+  pub failed_rows: Vec<CSVRowError>,
 }
 
 #[cfg(test)]
 mod tests {
   use collab_database::database::gen_database_view_id;
 
-  use crate::services::share::csv::{CSVFormat, CSVImporter};
+  use crate::entities::FieldType;
+  use crate::services::share::csv::{CSVFormat, CSVImportOptions, CSVImporter};
 
   #[test]
   fn test_import_csv_from_str() {
@@ -185,7 +310,8 @@ mod tests {
     let importer = CSVImporter;
     let result = importer
       .import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original)
-      .unwrap();
+      .unwrap()
+      .params;
     assert_eq!(result.rows.len(), 3);
     assert_eq!(result.fields.len(), 6);
 
@@ -211,4 +337,94 @@ mod tests {
       importer.import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original);
     assert!(result.is_err());
   }
+
+  #[test]
+  fn import_csv_infer_field_type_test() {
+    let s = r#"Name,Tags,Price,Date,Done,Website,Status
+Apple,"fruit, red",$1.50,2024-01-05,yes,https://apple.com,Todo
+Banana,fruit,"$1,200",2024-01-06,no,banana.com,Done
+Cherry,"red, fruit",$3,2024-01-07,yes,,Todo
+Durian,fruit,,2024-01-08,no,durian.org,Done"#;
+    let result = CSVImporter
+      .import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original)
+      .unwrap();
+    assert!(result.failed_rows.is_empty());
+
+    let field_types = result
+      .params
+      .fields
+      .iter()
+      .map(|field| FieldType::from(field.field_type))
+      .collect::<Vec<FieldType>>();
+    assert_eq!(
+      field_types,
+      vec![
+        FieldType::RichText,
+        FieldType::MultiSelect,
+        FieldType::Number,
+        FieldType::DateTime,
+        FieldType::Checkbox,
+        FieldType::URL,
+        FieldType::SingleSelect,
+      ]
+    );
+
+    let price_field = &result.params.fields[2];
+    let cell = result.params.rows[1].cells.get(&price_field.id).unwrap();
+    assert_eq!(cell.get_str_value("data").unwrap(), "1200");
+  }
+
+  #[test]
+  fn import_csv_with_options_test() {
+    let importer = CSVImporter;
+    let options = CSVImportOptions {
+      delimiter: b';',
+      has_header: false,
+      encoding: "windows-1252".to_string(),
+    };
+    // "Café;1" encoded in windows-1252
+    let content = b"Caf\xe9;1\nTea;2".to_vec();
+    let result = importer
+      .import_csv_from_bytes(
+        gen_database_view_id(),
+        content,
+        CSVFormat::Original,
+        options,
+      )
+      .unwrap();
+
+    let params = result.params;
+    assert_eq!(params.fields.len(), 2);
+    assert_eq!(params.fields[0].name, "Column 1");
+    assert_eq!(params.fields[1].name, "Column 2");
+    assert_eq!(params.rows.len(), 2);
+    let cell = params.rows[0].cells.get(&params.fields[0].id).unwrap();
+    assert_eq!(cell.get_str_value("data").unwrap(), "Café");
+
+    let options = CSVImportOptions {
+      encoding: "unknown".to_string(),
+      ..Default::default()
+    };
+    let result = importer.import_csv_from_bytes(
+      gen_database_view_id(),
+      b"Name".to_vec(),
+      CSVFormat::Original,
+      options,
+    );
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn import_csv_report_failed_rows_test() {
+    let s = r#"Name,Number
+a,1
+b,2,3
+c,3"#;
+    let result = CSVImporter
+      .import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original)
+      .unwrap();
+    assert_eq!(result.params.rows.len(), 2);
+    assert_eq!(result.failed_rows.len(), 1);
+    assert_eq!(result.failed_rows[0].line, 3);
+  }
 }
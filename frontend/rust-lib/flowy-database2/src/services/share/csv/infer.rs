@@ -0,0 +1,492 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use collab_database::database::gen_field_id;
+use collab_database::fields::Field;
+use collab_database::rows::{new_cell_builder, Cell};
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
+use strum::IntoEnumIterator;
+
+use crate::entities::FieldType;
+use crate::services::cell::{
+  insert_checkbox_cell, insert_date_cell, insert_select_option_cell, insert_text_cell,
+  insert_url_cell,
+};
+use crate::services::field::{
+  default_type_option_data_from_type, new_select_option_color, DateFormat, DateTypeOption,
+  MultiSelectTypeOption, NumberFormat, NumberTypeOption, SelectOption, SingleSelectTypeOption,
+  CELL_DATA,
+};
+
+/// A column is imported as a select option field if it has at most this many distinct values.
+const MAX_SELECT_OPTIONS: usize = 10;
+const MULTI_SELECT_SEPARATOR: char = ',';
+
+/// The formats that are tried, in order, to parse the dates of a column.
+const DATE_FORMATS: [(&str, DateFormat); 8] = [
+  ("%Y-%m-%d", DateFormat::ISO),
+  ("%Y/%m/%d", DateFormat::US),
+  ("%m/%d/%Y", DateFormat::Local),
+  ("%d/%m/%Y", DateFormat::DayMonthYear),
+  ("%d.%m.%Y", DateFormat::DayMonthYear),
+  ("%B %d, %Y", DateFormat::Friendly),
+  ("%B %d %Y", DateFormat::Friendly),
+  ("%d %B %Y", DateFormat::Friendly),
+];
+const TIME_FORMATS: [&str; 4] = ["%H:%M:%S", "%H:%M", "%I:%M %p", "%I:%M:%S %p"];
+
+/// Currency symbols that are not the display symbol of the [NumberFormat].
+const CURRENCY_SYMBOL_ALIASES: [(&str, NumberFormat); 5] = [
+  ("US$", NumberFormat::USD),
+  ("₽", NumberFormat::Ruble),
+  ("₺", NumberFormat::Lira),
+  ("฿", NumberFormat::Baht),
+  ("Rp", NumberFormat::Rupiah),
+];
+
+lazy_static! {
+  static ref NUMBER_REGEX: Regex = Regex::new(r"^(\d{1,3}(,\d{3})+|\d+)?(\.\d+)?$").unwrap();
+  static ref URL_REGEX: Regex =
+    Regex::new(r"(?i)^(https?://)?([a-z0-9-]+\.)+[a-z]{2,}(:\d+)?([/?#]\S*)?$").unwrap();
+  /// The currency symbols sorted by length, so that `CA$` is matched before `$`.
+  static ref CURRENCY_SYMBOLS: Vec<(String, NumberFormat)> = {
+    let mut symbols = NumberFormat::iter()
+      .filter(|format| *format != NumberFormat::Num)
+      .map(|format| (format.symbol(), format))
+      .chain(
+        CURRENCY_SYMBOL_ALIASES
+          .iter()
+          .map(|(symbol, format)| (symbol.to_string(), *format)),
+      )
+      .collect::<Vec<_>>();
+    symbols.sort_by_key(|(symbol, _)| std::cmp::Reverse(symbol.chars().count()));
+    symbols
+  };
+}
+
+/// The field type that is detected from the values of a csv column.
+#[derive(Debug, Clone)]
+enum ColumnType {
+  Text,
+  Number(NumberFormat),
+  DateTime {
+    date_format: DateFormat,
+    format_str: &'static str,
+    include_time: bool,
+  },
+  Checkbox,
+  URL,
+  SingleSelect(Vec<SelectOption>),
+  MultiSelect(Vec<SelectOption>),
+}
+
+/// A field whose type is inferred from the values of the column. The cells of the column are
+/// converted to the inferred type.
+pub(crate) struct InferredField {
+  pub(crate) field: Field,
+  column_type: ColumnType,
+}
+
+impl InferredField {
+  /// Detects the type of the column from its values. Empty values are ignored. The primary field
+  /// is always a text field.
+  pub(crate) fn new(name: String, is_primary: bool, values: &[&str]) -> Self {
+    let values = values
+      .iter()
+      .map(|value| value.trim())
+      .filter(|value| !value.is_empty())
+      .collect::<Vec<&str>>();
+    let column_type = if is_primary {
+      ColumnType::Text
+    } else {
+      infer_column_type(&values)
+    };
+
+    let field = match &column_type {
+      ColumnType::Text => new_field(name, FieldType::RichText, is_primary),
+      ColumnType::Number(format) => {
+        let type_option = NumberTypeOption {
+          format: *format,
+          symbol: format.symbol(),
+          ..Default::default()
+        };
+        new_field(name, FieldType::Number, is_primary)
+          .with_type_option_data(FieldType::Number, type_option.into())
+      },
+      ColumnType::DateTime { date_format, .. } => {
+        // The dates are parsed as UTC.
+        let type_option = DateTypeOption {
+          date_format: *date_format,
+          timezone_id: "Etc/UTC".to_string(),
+          ..Default::default()
+        };
+        new_field(name, FieldType::DateTime, is_primary)
+          .with_type_option_data(FieldType::DateTime, type_option.into())
+      },
+      ColumnType::Checkbox => new_field(name, FieldType::Checkbox, is_primary),
+      ColumnType::URL => new_field(name, FieldType::URL, is_primary),
+      ColumnType::SingleSelect(options) => {
+        let type_option = SingleSelectTypeOption {
+          options: options.clone(),
+          disable_color: false,
+        };
+        new_field(name, FieldType::SingleSelect, is_primary)
+          .with_type_option_data(FieldType::SingleSelect, type_option.into())
+      },
+      ColumnType::MultiSelect(options) => {
+        let type_option = MultiSelectTypeOption {
+          options: options.clone(),
+          disable_color: false,
+        };
+        new_field(name, FieldType::MultiSelect, is_primary)
+          .with_type_option_data(FieldType::MultiSelect, type_option.into())
+      },
+    };
+
+    Self { field, column_type }
+  }
+
+  pub(crate) fn field_type(&self) -> FieldType {
+    FieldType::from(self.field.field_type)
+  }
+
+  /// Converts the content of a csv cell to a cell of the inferred field type.
+  pub(crate) fn cell_from_str(&self, content: &str) -> Cell {
+    let content = content.trim();
+    if content.is_empty() {
+      return self.empty_cell();
+    }
+
+    match &self.column_type {
+      ColumnType::Text => insert_text_cell(content.to_string(), &self.field),
+      // The number type option accepts the number as a string.
+      ColumnType::Number(_) => {
+        let number = parse_number(content)
+          .map(|(number, _)| number)
+          .unwrap_or_default();
+        insert_text_cell(number, &self.field)
+      },
+      ColumnType::DateTime { format_str, .. } => match parse_date(content, format_str) {
+        Some((date_time, include_time)) => insert_date_cell(
+          date_time.and_utc().timestamp(),
+          None,
+          Some(include_time),
+          &self.field,
+        ),
+        None => self.empty_cell(),
+      },
+      ColumnType::Checkbox => {
+        insert_checkbox_cell(parse_checkbox(content).unwrap_or(false), &self.field)
+      },
+      ColumnType::URL => insert_url_cell(content.to_string(), &self.field),
+      ColumnType::SingleSelect(options) => {
+        let option_ids = find_option_ids(options, [content]);
+        insert_select_option_cell(option_ids, &self.field)
+      },
+      ColumnType::MultiSelect(options) => {
+        let option_ids = find_option_ids(options, split_multi_select(content));
+        insert_select_option_cell(option_ids, &self.field)
+      },
+    }
+  }
+
+  fn empty_cell(&self) -> Cell {
+    new_cell_builder(self.field_type())
+      .insert_str_value(CELL_DATA, "".to_string())
+      .build()
+  }
+}
+
+fn new_field(name: String, field_type: FieldType, is_primary: bool) -> Field {
+  let type_option_data = default_type_option_data_from_type(field_type);
+  Field::new(gen_field_id(), name, field_type.into(), is_primary)
+    .with_type_option_data(field_type, type_option_data)
+}
+
+/// Returns the most specific type that all the values of the column can be converted to.
+fn infer_column_type(values: &[&str]) -> ColumnType {
+  if values.is_empty() {
+    return ColumnType::Text;
+  }
+
+  if values.iter().all(|value| parse_checkbox(value).is_some()) {
+    return ColumnType::Checkbox;
+  }
+
+  if let Some(format) = infer_number_format(values) {
+    return ColumnType::Number(format);
+  }
+
+  for (format_str, date_format) in DATE_FORMATS {
+    let dates = values
+      .iter()
+      .map(|value| parse_date(value, format_str))
+      .collect::<Option<Vec<_>>>();
+    if let Some(dates) = dates {
+      return ColumnType::DateTime {
+        date_format,
+        format_str,
+        include_time: dates.iter().any(|(_, include_time)| *include_time),
+      };
+    }
+  }
+
+  if values
+    .iter()
+    .all(|value| URL_REGEX.is_match(value).unwrap_or(false))
+  {
+    return ColumnType::URL;
+  }
+
+  if values
+    .iter()
+    .any(|value| value.contains(MULTI_SELECT_SEPARATOR))
+  {
+    let names = values
+      .iter()
+      .flat_map(|value| split_multi_select(value))
+      .collect::<Vec<&str>>();
+    if let Some(options) = select_options_if_low_cardinality(&names) {
+      return ColumnType::MultiSelect(options);
+    }
+  }
+
+  match select_options_if_low_cardinality(values) {
+    Some(options) => ColumnType::SingleSelect(options),
+    None => ColumnType::Text,
+  }
+}
+
+/// Generates an option for each distinct value if the values repeat enough to be categories.
+fn select_options_if_low_cardinality(values: &[&str]) -> Option<Vec<SelectOption>> {
+  let mut names = vec![];
+  let mut visited = HashSet::new();
+  for value in values {
+    if visited.insert(*value) {
+      names.push(*value);
+    }
+  }
+
+  if names.len() > MAX_SELECT_OPTIONS || names.len() * 2 > values.len() {
+    return None;
+  }
+
+  let mut options: Vec<SelectOption> = vec![];
+  for name in names {
+    let color = new_select_option_color(&options);
+    options.push(SelectOption::with_color(name, color));
+  }
+  Some(options)
+}
+
+fn split_multi_select(content: &str) -> impl Iterator<Item = &str> {
+  content
+    .split(MULTI_SELECT_SEPARATOR)
+    .map(|name| name.trim())
+    .filter(|name| !name.is_empty())
+}
+
+fn find_option_ids<'a>(
+  options: &[SelectOption],
+  names: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+  let mut option_ids = vec![];
+  for name in names {
+    if let Some(option) = options.iter().find(|option| option.name == name) {
+      if !option_ids.contains(&option.id) {
+        option_ids.push(option.id.clone());
+      }
+    }
+  }
+  option_ids
+}
+
+fn parse_checkbox(value: &str) -> Option<bool> {
+  match value.to_lowercase().as_str() {
+    "true" | "yes" => Some(true),
+    "false" | "no" => Some(false),
+    _ => None,
+  }
+}
+
+/// All the values must be numbers. The column is a currency column if the values that have a
+/// currency symbol share the same one.
+fn infer_number_format(values: &[&str]) -> Option<NumberFormat> {
+  let mut number_format = NumberFormat::Num;
+  for value in values {
+    let (_, format) = parse_number(value)?;
+    if format != NumberFormat::Num {
+      if number_format != NumberFormat::Num && number_format != format {
+        return None;
+      }
+      number_format = format;
+    }
+  }
+  Some(number_format)
+}
+
+/// Parses numbers like `-1,200.5`, `$12`, `12 €` or `50%`. Returns the number without the
+/// currency symbol and the thousands separators.
+fn parse_number(value: &str) -> Option<(String, NumberFormat)> {
+  let (is_negative, value) = strip_minus(value.trim());
+  let (value, format) = CURRENCY_SYMBOLS
+    .iter()
+    .find_map(|(symbol, format)| {
+      value
+        .strip_prefix(symbol.as_str())
+        .or_else(|| value.strip_suffix(symbol.as_str()))
+        .map(|value| (value, *format))
+    })
+    .unwrap_or((value, NumberFormat::Num));
+  let (is_negative_after_symbol, value) = strip_minus(value.trim());
+
+  // Numbers with leading zeros, e.g. zip codes, are not numbers.
+  let is_leading_zero = value.len() > 1 && value.starts_with('0') && !value.starts_with("0.");
+  if value.is_empty() || is_leading_zero || !NUMBER_REGEX.is_match(value).unwrap_or(false) {
+    return None;
+  }
+
+  let mut number = value.replace(',', "");
+  if number.starts_with('.') {
+    number.insert(0, '0');
+  }
+  if is_negative != is_negative_after_symbol {
+    number.insert(0, '-');
+  }
+  Some((number, format))
+}
+
+fn strip_minus(value: &str) -> (bool, &str) {
+  match value.strip_prefix('-') {
+    Some(value) => (true, value),
+    None => (false, value),
+  }
+}
+
+/// Parses the date with the given format, optionally followed by a time. Returns the date time
+/// and whether the value contains a time.
+fn parse_date(value: &str, format_str: &str) -> Option<(NaiveDateTime, bool)> {
+  if let Ok(date) = NaiveDate::parse_from_str(value, format_str) {
+    return date
+      .and_hms_opt(0, 0, 0)
+      .map(|date_time| (date_time, false));
+  }
+
+  for time_format in TIME_FORMATS {
+    for separator in [" ", "T"] {
+      let format = format!("{}{}{}", format_str, separator, time_format);
+      if let Ok(date_time) = NaiveDateTime::parse_from_str(value, &format) {
+        return Some((date_time, true));
+      }
+    }
+  }
+
+  // Dates with an offset, e.g. 2024-04-01T08:30:00+02:00
+  if format_str == "%Y-%m-%d" {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+      return Some((date_time.naive_utc(), true));
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_number_test() {
+    assert_eq!(
+      parse_number("12"),
+      Some(("12".to_string(), NumberFormat::Num))
+    );
+    assert_eq!(
+      parse_number("-1,200.50"),
+      Some(("-1200.50".to_string(), NumberFormat::Num))
+    );
+    assert_eq!(
+      parse_number(".5"),
+      Some(("0.5".to_string(), NumberFormat::Num))
+    );
+    assert_eq!(
+      parse_number("$12"),
+      Some(("12".to_string(), NumberFormat::USD))
+    );
+    assert_eq!(
+      parse_number("-$3.5"),
+      Some(("-3.5".to_string(), NumberFormat::USD))
+    );
+    assert_eq!(
+      parse_number("CA$12"),
+      Some(("12".to_string(), NumberFormat::CanadianDollar))
+    );
+    assert_eq!(
+      parse_number("12 €"),
+      Some(("12".to_string(), NumberFormat::EUR))
+    );
+    assert_eq!(
+      parse_number("50%"),
+      Some(("50".to_string(), NumberFormat::Percent))
+    );
+
+    assert_eq!(parse_number("00123"), None);
+    assert_eq!(parse_number("1,2"), None);
+    assert_eq!(parse_number("12 apples"), None);
+    assert_eq!(parse_number("$"), None);
+  }
+
+  #[test]
+  fn infer_column_type_test() {
+    let field_type =
+      |values: &[&str]| InferredField::new("".to_string(), false, values).field_type();
+
+    assert_eq!(field_type(&["1", "2.5", "", "-3"]), FieldType::Number);
+    assert_eq!(field_type(&["$1", "2", "$1,000"]), FieldType::Number);
+    assert_eq!(field_type(&["$1", "€2"]), FieldType::RichText);
+    assert_eq!(field_type(&["Yes", "no", "TRUE"]), FieldType::Checkbox);
+    assert_eq!(
+      field_type(&["2024-01-05", "2024-02-10 08:30"]),
+      FieldType::DateTime
+    );
+    assert_eq!(
+      field_type(&["May 26, 2023", "Jun 1, 2023"]),
+      FieldType::DateTime
+    );
+    assert_eq!(
+      field_type(&["https://appflowy.io", "appflowy.io/docs"]),
+      FieldType::URL
+    );
+    assert_eq!(field_type(&["a", "b", "a", "b"]), FieldType::SingleSelect);
+    assert_eq!(field_type(&["a, b", "b", "a"]), FieldType::MultiSelect);
+    assert_eq!(field_type(&["tag 1", "tag 2"]), FieldType::RichText);
+    assert_eq!(field_type(&["", ""]), FieldType::RichText);
+
+    // The primary field is always a text field
+    let field = InferredField::new("".to_string(), true, &["1", "2"]);
+    assert_eq!(field.field_type(), FieldType::RichText);
+  }
+
+  #[test]
+  fn infer_number_format_test() {
+    let field = InferredField::new("Price".to_string(), false, &["£1,200", "£3"]);
+    let type_option = field
+      .field
+      .get_type_option::<NumberTypeOption>(FieldType::Number)
+      .unwrap();
+    assert_eq!(type_option.format, NumberFormat::Pound);
+  }
+
+  #[test]
+  fn infer_date_cell_test() {
+    let field = InferredField::new("Date".to_string(), false, &["25/12/2023", "01/02/2024"]);
+    let type_option = field
+      .field
+      .get_type_option::<DateTypeOption>(FieldType::DateTime)
+      .unwrap();
+    assert!(matches!(type_option.date_format, DateFormat::DayMonthYear));
+
+    let cell = field.cell_from_str("25/12/2023");
+    assert_eq!(cell.get_str_value("data").unwrap(), "1703462400");
+  }
+}
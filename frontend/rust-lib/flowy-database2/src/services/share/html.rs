@@ -0,0 +1,100 @@
+use lib_infra::util::escape_html;
+
+/// The content of a database view that is rendered to html, see [DatabaseHtml::to_html].
+#[derive(Debug, Clone)]
+pub enum DatabaseHtml {
+  /// The rows of a grid or a calendar. Every row has a cell for every field.
+  Table {
+    field_names: Vec<String>,
+    rows: Vec<Vec<String>>,
+  },
+  /// The columns of a board. A card shows the cells of a row that are not empty.
+  Board {
+    field_names: Vec<String>,
+    columns: Vec<BoardColumnHtml>,
+  },
+}
+
+#[derive(Debug, Clone)]
+pub struct BoardColumnHtml {
+  pub name: String,
+  pub rows: Vec<Vec<String>>,
+}
+
+impl DatabaseHtml {
+  /// Returns the html fragment of the view, without the `<html>` and `<body>` tags.
+  pub fn to_html(&self) -> String {
+    match self {
+      DatabaseHtml::Table { field_names, rows } => {
+        let mut html = String::from("<table class=\"database-grid\"><thead><tr>");
+        for name in field_names {
+          html.push_str(&format!("<th>{}</th>", escape_html(name)));
+        }
+        html.push_str("</tr></thead><tbody>");
+        for row in rows {
+          html.push_str("<tr>");
+          for cell in row {
+            html.push_str(&format!("<td>{}</td>", escape_html(cell)));
+          }
+          html.push_str("</tr>");
+        }
+        html.push_str("</tbody></table>");
+        html
+      },
+      DatabaseHtml::Board {
+        field_names,
+        columns,
+      } => {
+        let mut html = String::from("<div class=\"database-board\">");
+        for column in columns {
+          html.push_str(&format!(
+            "<section class=\"board-column\"><h3>{}</h3>",
+            escape_html(&column.name)
+          ));
+          for row in &column.rows {
+            html.push_str("<article class=\"board-card\">");
+            for (index, cell) in row.iter().enumerate() {
+              if cell.is_empty() {
+                continue;
+              }
+              // The primary field is the title of the card
+              if index == 0 {
+                html.push_str(&format!("<h4>{}</h4>", escape_html(cell)));
+              } else {
+                let name = field_names.get(index).map(String::as_str).unwrap_or("");
+                html.push_str(&format!(
+                  "<p><span class=\"field-name\">{}</span> {}</p>",
+                  escape_html(name),
+                  escape_html(cell)
+                ));
+              }
+            }
+            html.push_str("</article>");
+          }
+          html.push_str("</section>");
+        }
+        html.push_str("</div>");
+        html
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn database_table_to_html_test() {
+    let html = DatabaseHtml::Table {
+      field_names: vec!["Name".to_string(), "Done".to_string()],
+      rows: vec![vec!["<b>Write</b>".to_string(), "Yes".to_string()]],
+    }
+    .to_html();
+    assert_eq!(
+      html,
+      "<table class=\"database-grid\"><thead><tr><th>Name</th><th>Done</th></tr></thead>\
+<tbody><tr><td>&lt;b&gt;Write&lt;/b&gt;</td><td>Yes</td></tr></tbody></table>"
+    );
+  }
+}
@@ -1 +1,4 @@
 pub mod csv;
+pub mod html;
+pub mod template;
+pub mod url;
@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use collab_database::database::{
+  gen_database_id, gen_database_view_id, gen_field_id, gen_option_id, gen_row_id, DatabaseData,
+};
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, Row, RowId};
+use collab_database::views::{
+  CalculationMap, DatabaseLayout, DatabaseView, FieldSettingsMap, FilterMap, GroupSettingMap,
+  SortMap,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use lib_infra::box_any::BoxAny;
+
+use crate::entities::{FieldType, SelectOptionFilterPB};
+use crate::services::calculations::Calculation;
+use crate::services::conditional_format::ConditionalFormat;
+use crate::services::field::{
+  select_type_option_from_field, RelationCellData, RelationTypeOption, RollupTypeOption,
+  SelectOptionIds,
+};
+use crate::services::field_settings::FieldSettings;
+use crate::services::filter::{Filter, FilterInner};
+use crate::services::group::GroupSetting;
+use crate::services::setting::{CalendarLayoutSetting, GridLayoutSetting};
+use crate::services::sort::Sort;
+
+const TEMPLATE_VERSION: u32 = 1;
+
+/// A self-contained copy of a database view that can be instantiated many times. It keeps the
+/// fields with their type options, the settings of the view (filters, sorts, groups, layout
+/// settings, field settings and calculations) and optionally the rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseTemplate {
+  pub version: u32,
+  /// The json of the [DatabaseData] of the template. Its only view is the exported view.
+  pub database: Value,
+}
+
+impl DatabaseTemplate {
+  pub fn from_database_data(
+    mut data: DatabaseData,
+    view_id: &str,
+    include_rows: bool,
+  ) -> FlowyResult<Self> {
+    data.views.retain(|view| view.id == view_id);
+    if data.views.is_empty() {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("The view with id:{} not found", view_id)),
+      );
+    }
+    data.inline_view_id = view_id.to_string();
+
+    if !include_rows {
+      data.rows.clear();
+      for view in data.views.iter_mut() {
+        view.row_orders.clear();
+      }
+    }
+
+    let database = serde_json::from_slice(&data.to_json_bytes()?)?;
+    Ok(Self {
+      version: TEMPLATE_VERSION,
+      database,
+    })
+  }
+
+  pub fn from_json_bytes(bytes: &[u8]) -> FlowyResult<Self> {
+    let template = serde_json::from_slice::<Self>(bytes).map_err(|err| {
+      FlowyError::new(
+        ErrorCode::InvalidParams,
+        format!("Invalid database template: {}", err),
+      )
+    })?;
+    if template.version > TEMPLATE_VERSION {
+      return Err(FlowyError::new(
+        ErrorCode::InvalidParams,
+        format!(
+          "The database template version {} is not supported",
+          template.version
+        ),
+      ));
+    }
+    Ok(template)
+  }
+
+  pub fn to_json_string(&self) -> FlowyResult<String> {
+    Ok(serde_json::to_string(self)?)
+  }
+
+  /// Returns the data of a new database made from the template. The ids of the database, the
+  /// view, the fields, the rows and the select options are regenerated, and every reference to
+  /// them is updated, e.g. the relation fields that point to the database itself, the cells of
+  /// the select fields and the settings of the view.
+  pub fn instantiate(&self) -> FlowyResult<DatabaseData> {
+    let mut data = DatabaseData::from_json_bytes(serde_json::to_vec(&self.database)?)?;
+    TemplateIds::new(&data).apply(&mut data);
+    Ok(data)
+  }
+}
+
+/// The new ids of a template by the old ids. Every kind of id is kept apart, so that a value is
+/// only replaced where it's known to be an id of that kind, never in the content of the cells.
+struct TemplateIds {
+  database_id: String,
+  new_database_id: String,
+  view_ids: HashMap<String, String>,
+  field_ids: HashMap<String, String>,
+  row_ids: HashMap<String, String>,
+  /// The new ids of the select options by the old id of their field.
+  option_ids: HashMap<String, HashMap<String, String>>,
+  /// The types of the fields by the old id of the field.
+  field_types: HashMap<String, FieldType>,
+}
+
+impl TemplateIds {
+  fn new(data: &DatabaseData) -> Self {
+    let mut option_ids = HashMap::new();
+    let mut field_types = HashMap::new();
+    for field in data.fields.iter() {
+      let field_type = FieldType::from(field.field_type);
+      field_types.insert(field.id.clone(), field_type);
+      if field_type.is_select_option() {
+        if let Ok(type_option) = select_type_option_from_field(field) {
+          let ids = type_option
+            .options()
+            .iter()
+            .map(|option| (option.id.clone(), gen_option_id()))
+            .collect();
+          option_ids.insert(field.id.clone(), ids);
+        }
+      }
+    }
+
+    Self {
+      database_id: data.database_id.clone(),
+      new_database_id: gen_database_id(),
+      view_ids: data
+        .views
+        .iter()
+        .map(|view| (view.id.clone(), gen_database_view_id()))
+        .collect(),
+      field_ids: data
+        .fields
+        .iter()
+        .map(|field| (field.id.clone(), gen_field_id()))
+        .collect(),
+      row_ids: data
+        .rows
+        .iter()
+        .map(|row| (row.id.to_string(), gen_row_id().to_string()))
+        .collect(),
+      option_ids,
+      field_types,
+    }
+  }
+
+  fn apply(&self, data: &mut DatabaseData) {
+    data.database_id = self.new_database_id.clone();
+    data.inline_view_id = new_id(&self.view_ids, &data.inline_view_id);
+    for field in data.fields.iter_mut() {
+      self.apply_to_field(field);
+    }
+    for row in data.rows.iter_mut() {
+      self.apply_to_row(row);
+    }
+    for view in data.views.iter_mut() {
+      self.apply_to_view(view);
+    }
+  }
+
+  fn apply_to_field(&self, field: &mut Field) {
+    let field_type = FieldType::from(field.field_type);
+    if field_type.is_select_option() {
+      if let (Ok(mut type_option), Some(option_ids)) = (
+        select_type_option_from_field(field),
+        self.option_ids.get(&field.id),
+      ) {
+        for option in type_option.mut_options() {
+          option.id = new_id(option_ids, &option.id);
+        }
+        field
+          .type_options
+          .insert(field_type.to_string(), type_option.to_type_option_data());
+      }
+    } else if field_type.is_relation() {
+      if let Some(mut type_option) = field.get_type_option::<RelationTypeOption>(field_type) {
+        // Only a relation to the database itself points to the new database, and so does the
+        // field it's paired with
+        if type_option.database_id == self.database_id {
+          type_option.database_id = self.new_database_id.clone();
+          type_option.paired_field_id = new_id(&self.field_ids, &type_option.paired_field_id);
+          field
+            .type_options
+            .insert(field_type.to_string(), type_option.into());
+        }
+      }
+    } else if field_type.is_rollup() {
+      if let Some(mut type_option) = field.get_type_option::<RollupTypeOption>(field_type) {
+        type_option.relation_field_id = new_id(&self.field_ids, &type_option.relation_field_id);
+        type_option.target_field_id = new_id(&self.field_ids, &type_option.target_field_id);
+        field
+          .type_options
+          .insert(field_type.to_string(), type_option.into());
+      }
+    }
+    field.id = new_id(&self.field_ids, &field.id);
+  }
+
+  fn apply_to_row(&self, row: &mut Row) {
+    row.id = RowId::from(new_id(&self.row_ids, &row.id));
+    row.database_id = self.new_database_id.clone();
+    let cells = row.cells.clone();
+    row.cells.clear();
+    for (field_id, cell) in cells.iter() {
+      let cell = match self.field_types.get(field_id) {
+        Some(field_type) if field_type.is_select_option() => {
+          let mut ids = SelectOptionIds::from(cell);
+          if let Some(option_ids) = self.option_ids.get(field_id) {
+            for id in ids.iter_mut() {
+              *id = new_id(option_ids, id);
+            }
+          }
+          ids.to_cell_data(*field_type)
+        },
+        Some(field_type) if field_type.is_relation() => {
+          let mut data = RelationCellData::from(cell);
+          for row_id in data.row_ids.iter_mut() {
+            *row_id = RowId::from(new_id(&self.row_ids, row_id));
+          }
+          Cell::from(&data)
+        },
+        _ => cell.clone(),
+      };
+      row.cells.insert(new_id(&self.field_ids, field_id), cell);
+    }
+  }
+
+  fn apply_to_view(&self, view: &mut DatabaseView) {
+    view.id = new_id(&self.view_ids, &view.id);
+    view.database_id = self.new_database_id.clone();
+    for row_order in view.row_orders.iter_mut() {
+      row_order.id = RowId::from(new_id(&self.row_ids, &row_order.id));
+    }
+    for field_order in view.field_orders.iter_mut() {
+      field_order.id = new_id(&self.field_ids, &field_order.id);
+    }
+
+    view.filters = std::mem::take(&mut view.filters)
+      .into_iter()
+      .map(|filter_map| match Filter::try_from(filter_map.clone()) {
+        Ok(mut filter) => {
+          self.apply_to_filter(&mut filter);
+          FilterMap::from(&filter)
+        },
+        Err(_) => filter_map,
+      })
+      .collect();
+    view.sorts = std::mem::take(&mut view.sorts)
+      .into_iter()
+      .map(|sort_map| match Sort::try_from(sort_map.clone()) {
+        Ok(mut sort) => {
+          sort.field_id = new_id(&self.field_ids, &sort.field_id);
+          SortMap::from(sort)
+        },
+        Err(_) => sort_map,
+      })
+      .collect();
+    view.group_settings = std::mem::take(&mut view.group_settings)
+      .into_iter()
+      .map(
+        |setting_map| match GroupSetting::try_from(setting_map.clone()) {
+          Ok(mut setting) => {
+            // The groups of a select field are the options of the field
+            if let Some(option_ids) = self.option_ids.get(&setting.field_id) {
+              for group in setting.groups.iter_mut() {
+                group.id = new_id(option_ids, &group.id);
+              }
+            }
+            setting.field_id = new_id(&self.field_ids, &setting.field_id);
+            GroupSettingMap::from(setting)
+          },
+          Err(_) => setting_map,
+        },
+      )
+      .collect();
+    view.calculations = std::mem::take(&mut view.calculations)
+      .into_iter()
+      .map(
+        |calculation_map| match Calculation::try_from(calculation_map.clone()) {
+          Ok(mut calculation) => {
+            calculation.field_id = new_id(&self.field_ids, &calculation.field_id);
+            CalculationMap::from(calculation)
+          },
+          Err(_) => calculation_map,
+        },
+      )
+      .collect();
+
+    if let Some(setting) = view.layout_settings.get(&DatabaseLayout::Grid).cloned() {
+      let mut setting = GridLayoutSetting::from(setting);
+      for card in [&mut setting.gallery, &mut setting.list] {
+        card.cover_field_id = new_id(&self.field_ids, &card.cover_field_id);
+        for field_id in card.visible_field_ids.iter_mut() {
+          *field_id = new_id(&self.field_ids, field_id);
+        }
+      }
+      let timeline = &mut setting.timeline;
+      timeline.start_field_id = new_id(&self.field_ids, &timeline.start_field_id);
+      timeline.end_field_id = new_id(&self.field_ids, &timeline.end_field_id);
+      timeline.dependency_field_id = new_id(&self.field_ids, &timeline.dependency_field_id);
+      view
+        .layout_settings
+        .insert(DatabaseLayout::Grid, setting.into());
+    }
+    if let Some(setting) = view.layout_settings.get(&DatabaseLayout::Calendar).cloned() {
+      let mut setting = CalendarLayoutSetting::from(setting);
+      setting.field_id = new_id(&self.field_ids, &setting.field_id);
+      view
+        .layout_settings
+        .insert(DatabaseLayout::Calendar, setting.into());
+    }
+
+    let layout = view.layout;
+    view.field_settings = view
+      .field_settings
+      .clone()
+      .into_inner()
+      .into_iter()
+      .map(|(field_id, field_settings)| {
+        let mut field_settings = FieldSettings::from_any_map(&field_id, layout, &field_settings);
+        for format in field_settings.conditional_formats.iter_mut() {
+          self.apply_to_conditional_format(format);
+        }
+        let new_field_id = new_id(&self.field_ids, &field_id);
+        field_settings.field_id = new_field_id.clone();
+        (new_field_id, FieldSettingsMap::from(field_settings))
+      })
+      .collect::<HashMap<_, _>>()
+      .into();
+  }
+
+  fn apply_to_filter(&self, filter: &mut Filter) {
+    match &mut filter.inner {
+      FilterInner::And { children } | FilterInner::Or { children } => {
+        for child in children.iter_mut() {
+          self.apply_to_filter(child);
+        }
+      },
+      FilterInner::Data {
+        field_id,
+        condition_and_content,
+        ..
+      } => {
+        if let (Some(mut content), Some(option_ids)) = (
+          condition_and_content.cloned::<SelectOptionFilterPB>(),
+          self.option_ids.get(field_id.as_str()),
+        ) {
+          for id in content.option_ids.iter_mut() {
+            *id = new_id(option_ids, id);
+          }
+          *condition_and_content = BoxAny::new(content);
+        }
+        *field_id = new_id(&self.field_ids, field_id);
+      },
+    }
+  }
+
+  fn apply_to_conditional_format(&self, format: &mut ConditionalFormat) {
+    if let Some(option_ids) = self.option_ids.get(&format.field_id) {
+      if let Ok(mut condition) = SelectOptionFilterPB::try_from(format.condition.as_slice()) {
+        for id in condition.option_ids.iter_mut() {
+          *id = new_id(option_ids, id);
+        }
+        if let Ok(bytes) = TryInto::<Bytes>::try_into(condition) {
+          format.condition = bytes.to_vec();
+        }
+      }
+    }
+    format.field_id = new_id(&self.field_ids, &format.field_id);
+  }
+}
+
+/// Returns the new id of `id`, or `id` itself if it isn't regenerated, e.g. the id of a row of
+/// another database in a relation cell.
+fn new_id(ids: &HashMap<String, String>, id: &str) -> String {
+  ids.get(id).cloned().unwrap_or_else(|| id.to_string())
+}
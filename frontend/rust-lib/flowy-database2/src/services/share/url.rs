@@ -0,0 +1,58 @@
+/// The length of a view id, which is a uuid, e.g. `5e4ba6a1-40f2-4b54-9c4a-0e1b0f2c1a3d`.
+const VIEW_ID_LEN: usize = 36;
+
+/// Returns the id of the view that the url links to. The last segment of the url is either
+/// the id of the view, or a publish name that ends with the id of the view, e.g.
+/// `https://appflowy.com/app/{workspace_id}/{view_id}` or `https://appflowy.com/{namespace}/{name}-{view_id}`.
+pub(crate) fn view_id_from_url(url: &str) -> Option<String> {
+  let url = url::Url::parse(url.trim()).ok()?;
+  let segment = url
+    .path_segments()?
+    .filter(|segment| !segment.is_empty())
+    .last()?;
+  let (slug, view_id) = segment.split_at(segment.len().checked_sub(VIEW_ID_LEN)?);
+  if !(slug.is_empty() || slug.ends_with('-')) || !is_uuid(view_id) {
+    return None;
+  }
+  Some(view_id.to_string())
+}
+
+/// Replaces the publish name in the url that links to the view, e.g. after the view is renamed.
+/// Returns None if the url doesn't link to the view with a publish name.
+pub(crate) fn replace_publish_name_in_url(
+  url: &str,
+  view_id: &str,
+  publish_name: &str,
+) -> Option<String> {
+  let mut url = url::Url::parse(url.trim()).ok()?;
+  let mut segments = url
+    .path_segments()?
+    .map(String::from)
+    .collect::<Vec<String>>();
+  let last = segments
+    .iter_mut()
+    .filter(|segment| !segment.is_empty())
+    .last()?;
+  if last.len() <= VIEW_ID_LEN
+    || !last.ends_with(view_id)
+    || view_id_from_url(url.as_str()).as_deref() != Some(view_id)
+    || last == publish_name
+  {
+    return None;
+  }
+  *last = publish_name.to_string();
+  url
+    .path_segments_mut()
+    .ok()?
+    .clear()
+    .extend(segments.iter());
+  Some(url.to_string())
+}
+
+fn is_uuid(s: &str) -> bool {
+  s.len() == VIEW_ID_LEN
+    && s.char_indices().all(|(index, c)| match index {
+      8 | 13 | 18 | 23 => c == '-',
+      _ => c.is_ascii_hexdigit(),
+    })
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use collab_database::database::DatabaseData;
+use collab_database::fields::Field;
+use collab_database::rows::{Cells, Row};
+
+use crate::entities::{CellDiffPB, DatabaseDiffPB, DiffChangeTypePB, FieldDiffPB, RowDiffPB};
+use crate::services::cell::stringify_cell;
+
+/// Returns the changes of the fields and the rows from the `old` database to the `new` database.
+/// The cells of the rows are only compared for the fields that exist in both databases, the
+/// added and the removed fields are reported as field changes.
+pub fn diff_database_data(old: &DatabaseData, new: &DatabaseData) -> DatabaseDiffPB {
+  let old_fields = fields_by_id(&old.fields);
+  let new_fields = fields_by_id(&new.fields);
+
+  let mut fields = vec![];
+  for field in new.fields.iter() {
+    match old_fields.get(field.id.as_str()) {
+      None => fields.push(field_diff(
+        &field.id,
+        DiffChangeTypePB::Added,
+        None,
+        Some(field),
+      )),
+      Some(old_field) => {
+        if old_field.name != field.name || old_field.field_type != field.field_type {
+          fields.push(field_diff(
+            &field.id,
+            DiffChangeTypePB::Updated,
+            Some(old_field),
+            Some(field),
+          ));
+        }
+      },
+    }
+  }
+  for field in old.fields.iter() {
+    if !new_fields.contains_key(field.id.as_str()) {
+      fields.push(field_diff(
+        &field.id,
+        DiffChangeTypePB::Removed,
+        Some(field),
+        None,
+      ));
+    }
+  }
+
+  let old_rows = old
+    .rows
+    .iter()
+    .map(|row| (row.id.as_str(), row))
+    .collect::<HashMap<_, _>>();
+  let new_row_ids = new
+    .rows
+    .iter()
+    .map(|row| row.id.as_str())
+    .collect::<Vec<_>>();
+
+  let mut rows = vec![];
+  for row in new.rows.iter() {
+    match old_rows.get(row.id.as_str()) {
+      None => rows.push(RowDiffPB {
+        row_id: row.id.to_string(),
+        change_type: DiffChangeTypePB::Added,
+        cells: row_cells(row, &new.fields, false),
+      }),
+      Some(old_row) => {
+        let cells = changed_cells(&old_row.cells, &row.cells, &old_fields, &new.fields);
+        if !cells.is_empty() {
+          rows.push(RowDiffPB {
+            row_id: row.id.to_string(),
+            change_type: DiffChangeTypePB::Updated,
+            cells,
+          });
+        }
+      },
+    }
+  }
+  for row in old.rows.iter() {
+    if !new_row_ids.contains(&row.id.as_str()) {
+      rows.push(RowDiffPB {
+        row_id: row.id.to_string(),
+        change_type: DiffChangeTypePB::Removed,
+        cells: row_cells(row, &old.fields, true),
+      });
+    }
+  }
+
+  DatabaseDiffPB { fields, rows }
+}
+
+fn fields_by_id(fields: &[Field]) -> HashMap<&str, &Field> {
+  fields
+    .iter()
+    .map(|field| (field.id.as_str(), field))
+    .collect()
+}
+
+fn field_diff(
+  field_id: &str,
+  change_type: DiffChangeTypePB,
+  old: Option<&Field>,
+  new: Option<&Field>,
+) -> FieldDiffPB {
+  FieldDiffPB {
+    field_id: field_id.to_string(),
+    change_type,
+    old_name: old.map(|field| field.name.clone()).unwrap_or_default(),
+    new_name: new.map(|field| field.name.clone()).unwrap_or_default(),
+    old_field_type: old.map(|field| field.field_type.into()).unwrap_or_default(),
+    new_field_type: new.map(|field| field.field_type.into()).unwrap_or_default(),
+  }
+}
+
+/// Returns the non-empty cells of the row. The values are put in `old_value` if the row is
+/// removed, otherwise in `new_value`.
+fn row_cells(row: &Row, fields: &[Field], is_removed: bool) -> Vec<CellDiffPB> {
+  fields
+    .iter()
+    .filter_map(|field| {
+      let value = stringify_cell(row.cells.get(&field.id)?, field);
+      if value.is_empty() {
+        return None;
+      }
+      let (old_value, new_value) = if is_removed {
+        (value, String::new())
+      } else {
+        (String::new(), value)
+      };
+      Some(CellDiffPB {
+        field_id: field.id.clone(),
+        old_value,
+        new_value,
+      })
+    })
+    .collect()
+}
+
+fn changed_cells(
+  old_cells: &Cells,
+  new_cells: &Cells,
+  old_fields: &HashMap<&str, &Field>,
+  new_fields: &[Field],
+) -> Vec<CellDiffPB> {
+  new_fields
+    .iter()
+    .filter_map(|field| {
+      let old_field = old_fields.get(field.id.as_str())?;
+      let old_cell = old_cells.get(&field.id);
+      let new_cell = new_cells.get(&field.id);
+      if old_cell == new_cell {
+        return None;
+      }
+      let old_value = old_cell
+        .map(|cell| stringify_cell(cell, old_field))
+        .unwrap_or_default();
+      let new_value = new_cell
+        .map(|cell| stringify_cell(cell, field))
+        .unwrap_or_default();
+      if old_value == new_value {
+        return None;
+      }
+      Some(CellDiffPB {
+        field_id: field.id.clone(),
+        old_value,
+        new_value,
+      })
+    })
+    .collect()
+}
@@ -0,0 +1,70 @@
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::RowId;
+use serde::{Deserialize, Serialize};
+
+/// The key of the validation in the type options of the field. The validation applies to the
+/// field in every view, so it's stored with the field.
+pub const FIELD_VALIDATION: &str = "validation";
+const VALIDATION_JSON: &str = "json";
+
+/// The validation constraints of a field.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldValidation {
+  pub is_required: bool,
+  /// No two rows of the database can have the same value.
+  pub is_unique: bool,
+  /// The regular expression that the text of a text or URL cell must match.
+  pub pattern: Option<String>,
+  /// The minimum of a number cell, or of the timestamp of a date cell.
+  pub min: Option<f64>,
+  /// The maximum of a number cell, or of the timestamp of a date cell.
+  pub max: Option<f64>,
+  /// The maximum number of options of a multi-select cell.
+  pub max_selected_options: Option<u32>,
+}
+
+impl FieldValidation {
+  pub fn is_empty(&self) -> bool {
+    self == &FieldValidation::default()
+  }
+
+  pub fn from_field(field: &Field) -> Self {
+    field
+      .type_options
+      .get(FIELD_VALIDATION)
+      .and_then(|data| data.get_str_value(VALIDATION_JSON))
+      .and_then(|json| serde_json::from_str(&json).ok())
+      .unwrap_or_default()
+  }
+}
+
+impl From<&FieldValidation> for TypeOptionData {
+  fn from(validation: &FieldValidation) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_str_value(
+        VALIDATION_JSON,
+        serde_json::to_string(validation).unwrap_or_default(),
+      )
+      .build()
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationRule {
+  Required,
+  Unique,
+  Pattern,
+  Min,
+  Max,
+  MaxSelectedOptions,
+}
+
+/// A cell that breaks a validation rule of its field.
+#[derive(Debug, Clone)]
+pub struct CellViolation {
+  pub row_id: RowId,
+  pub field_id: String,
+  pub rule: ValidationRule,
+  pub message: String,
+}
@@ -0,0 +1,5 @@
+mod entities;
+mod validator;
+
+pub use entities::*;
+pub use validator::*;
@@ -0,0 +1,168 @@
+use collab_database::fields::Field;
+use collab_database::rows::{Cell, RowId};
+use fancy_regex::Regex;
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+use crate::entities::FieldType;
+use crate::services::cell::{stringify_cell, CellCache};
+use crate::services::field::{DateCellData, SelectOptionIds, TypeOptionCellExt};
+use crate::services::validation::{CellViolation, FieldValidation, ValidationRule};
+
+/// Checks the cells of a field against the validation constraints of the field. The constraints
+/// that don't apply to the type of the field are ignored.
+pub struct FieldValidator<'a> {
+  field: &'a Field,
+  field_type: FieldType,
+  validation: &'a FieldValidation,
+  pattern: Option<Regex>,
+  cell_cache: Option<CellCache>,
+}
+
+impl<'a> FieldValidator<'a> {
+  pub fn new(
+    field: &'a Field,
+    validation: &'a FieldValidation,
+    cell_cache: Option<CellCache>,
+  ) -> FlowyResult<Self> {
+    let pattern = validation
+      .pattern
+      .as_deref()
+      .filter(|pattern| !pattern.is_empty())
+      .map(parse_validation_pattern)
+      .transpose()?;
+    Ok(Self {
+      field,
+      field_type: FieldType::from(field.field_type),
+      validation,
+      pattern,
+      cell_cache,
+    })
+  }
+
+  /// Returns the first rule that the cell breaks. `is_duplicate` returns true if another row has
+  /// the same text, it's only called if the values must be unique.
+  pub fn validate<F>(
+    &self,
+    row_id: &RowId,
+    cell: Option<&Cell>,
+    is_duplicate: F,
+  ) -> Option<CellViolation>
+  where
+    F: Fn(&str) -> bool,
+  {
+    let violation = |rule: ValidationRule, message: String| CellViolation {
+      row_id: row_id.clone(),
+      field_id: self.field.id.clone(),
+      rule,
+      message,
+    };
+
+    let cell = match cell.filter(|cell| !self.is_cell_empty(cell)) {
+      Some(cell) => cell,
+      None => {
+        return self.validation.is_required.then(|| {
+          violation(
+            ValidationRule::Required,
+            format!("{} is required", self.field.name),
+          )
+        });
+      },
+    };
+
+    if self.validation.is_unique {
+      let text = stringify_cell(cell, self.field);
+      if is_duplicate(text.trim()) {
+        return Some(violation(
+          ValidationRule::Unique,
+          format!(
+            "{} must be unique, {} is already used",
+            self.field.name, text
+          ),
+        ));
+      }
+    }
+
+    if let Some(pattern) = &self.pattern {
+      if matches!(self.field_type, FieldType::RichText | FieldType::URL) {
+        let text = stringify_cell(cell, self.field);
+        if !pattern.is_match(&text).unwrap_or(false) {
+          return Some(violation(
+            ValidationRule::Pattern,
+            format!(
+              "{} must match the pattern {}",
+              self.field.name,
+              pattern.as_str()
+            ),
+          ));
+        }
+      }
+    }
+
+    if let Some(value) = self.numeric_value(cell) {
+      if let Some(min) = self.validation.min.filter(|min| value < *min) {
+        return Some(violation(
+          ValidationRule::Min,
+          format!("{} must be at least {}", self.field.name, min),
+        ));
+      }
+      if let Some(max) = self.validation.max.filter(|max| value > *max) {
+        return Some(violation(
+          ValidationRule::Max,
+          format!("{} must be at most {}", self.field.name, max),
+        ));
+      }
+    }
+
+    if let Some(max_selected_options) = self.validation.max_selected_options {
+      if self.field_type == FieldType::MultiSelect
+        && SelectOptionIds::from(cell).len() > max_selected_options as usize
+      {
+        return Some(violation(
+          ValidationRule::MaxSelectedOptions,
+          format!(
+            "At most {} options of {} can be selected",
+            max_selected_options, self.field.name
+          ),
+        ));
+      }
+    }
+
+    None
+  }
+
+  /// A checkbox is empty if it's unchecked, so that a required checkbox must be checked.
+  fn is_cell_empty(&self, cell: &Cell) -> bool {
+    let handler = TypeOptionCellExt::new(self.field, self.cell_cache.clone())
+      .get_type_option_cell_data_handler();
+    match handler {
+      Some(handler) if self.field_type == FieldType::Checkbox => {
+        handler.handle_numeric_cell(cell) != Some(1.0)
+      },
+      Some(handler) => handler.handle_is_cell_empty(cell, self.field),
+      None => true,
+    }
+  }
+
+  /// The number of a number cell, or the timestamp of a date cell.
+  fn numeric_value(&self, cell: &Cell) -> Option<f64> {
+    match self.field_type {
+      FieldType::Number => TypeOptionCellExt::new(self.field, self.cell_cache.clone())
+        .get_type_option_cell_data_handler()?
+        .handle_numeric_cell(cell),
+      FieldType::DateTime => DateCellData::from(cell)
+        .timestamp
+        .map(|timestamp| timestamp as f64),
+      _ => None,
+    }
+  }
+}
+
+pub fn parse_validation_pattern(pattern: &str) -> FlowyResult<Regex> {
+  Regex::new(pattern).map_err(|err| {
+    FlowyError::new(
+      ErrorCode::InvalidParams,
+      format!("Invalid validation pattern {}: {}", pattern, err),
+    )
+  })
+}
@@ -65,6 +65,7 @@ impl FieldSettingsTest {
       visibility,
       width,
       wrap_cell_content: None,
+      validation: None,
     };
     let _ = self
       .editor
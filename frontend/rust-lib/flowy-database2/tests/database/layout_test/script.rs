@@ -1,18 +1,68 @@
 use collab_database::fields::Field;
 use collab_database::views::DatabaseLayout;
 
-use flowy_database2::entities::{FieldType, LayoutSettingChangeset, LayoutSettingParams};
-use flowy_database2::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use flowy_database2::entities::{
+  DatabaseLayoutPB, FieldType, LayoutSettingChangeset, LayoutSettingParams, MoveTimelineEventParams,
+};
+use flowy_database2::services::setting::{
+  BoardLayoutSetting, CalendarLayoutSetting, CardLayoutSetting, GridLayout, GridLayoutSetting,
+  TimelineLayoutSetting,
+};
 
 use crate::database::database_editor::DatabaseEditorTest;
 
 pub enum LayoutScript {
-  AssertBoardLayoutSetting { expected: BoardLayoutSetting },
-  AssertCalendarLayoutSetting { expected: CalendarLayoutSetting },
-  UpdateBoardLayoutSetting { new_setting: BoardLayoutSetting },
+  AssertBoardLayoutSetting {
+    expected: BoardLayoutSetting,
+  },
+  AssertCalendarLayoutSetting {
+    expected: CalendarLayoutSetting,
+  },
+  UpdateBoardLayoutSetting {
+    new_setting: BoardLayoutSetting,
+  },
   AssertDefaultAllCalendarEvents,
-  AssertAllCalendarEventsCount { expected: usize },
-  UpdateDatabaseLayout { layout: DatabaseLayout },
+  AssertAllCalendarEventsCount {
+    expected: usize,
+  },
+  UpdateDatabaseLayout {
+    layout: DatabaseLayout,
+  },
+  UpdateGridLayout {
+    grid_layout: GridLayout,
+  },
+  AssertDatabaseLayout {
+    expected: DatabaseLayoutPB,
+  },
+  AssertCardLayoutSetting {
+    grid_layout: GridLayout,
+    expected: CardLayoutSetting,
+  },
+  UpdateCardLayoutSetting {
+    grid_layout: GridLayout,
+    new_setting: CardLayoutSetting,
+    is_ok: bool,
+  },
+  AssertTimelineLayoutSetting {
+    expected: TimelineLayoutSetting,
+  },
+  UpdateTimelineLayoutSetting {
+    new_setting: TimelineLayoutSetting,
+    is_ok: bool,
+  },
+  AssertTimelineEventsCount {
+    expected: usize,
+  },
+  MoveTimelineEvent {
+    row_index: usize,
+    start_timestamp: i64,
+    end_timestamp: i64,
+  },
+  AssertTimelineEvent {
+    row_index: usize,
+    start_timestamp: i64,
+    end_timestamp: i64,
+  },
 }
 
 pub struct DatabaseLayoutTest {
@@ -20,6 +70,11 @@ pub struct DatabaseLayoutTest {
 }
 
 impl DatabaseLayoutTest {
+  pub async fn new_grid() -> Self {
+    let database_test = DatabaseEditorTest::new_grid().await;
+    Self { database_test }
+  }
+
   pub async fn new_no_date_grid() -> Self {
     let database_test = DatabaseEditorTest::new_no_date_grid().await;
     Self { database_test }
@@ -39,6 +94,10 @@ impl DatabaseLayoutTest {
     self.database_test.get_first_field(FieldType::DateTime)
   }
 
+  pub async fn get_first_field(&self, field_type: FieldType) -> Field {
+    self.database_test.get_first_field(field_type)
+  }
+
   async fn get_layout_setting(
     &self,
     view_id: &str,
@@ -52,6 +111,15 @@ impl DatabaseLayoutTest {
       .unwrap()
   }
 
+  async fn get_grid_layout_setting(&self) -> GridLayoutSetting {
+    let view_id = self.database_test.view_id.clone();
+    self
+      .get_layout_setting(&view_id, DatabaseLayout::Grid)
+      .await
+      .grid
+      .unwrap()
+  }
+
   pub async fn run_scripts(&mut self, scripts: Vec<LayoutScript>) {
     for script in scripts {
       self.run_script(script).await;
@@ -68,11 +136,130 @@ impl DatabaseLayoutTest {
           .await
           .unwrap();
       },
+      LayoutScript::UpdateGridLayout { grid_layout } => {
+        self
+          .database_test
+          .editor
+          .update_view_grid_layout(&self.database_test.view_id, grid_layout)
+          .await
+          .unwrap();
+      },
+      LayoutScript::AssertDatabaseLayout { expected } => {
+        let database = self
+          .database_test
+          .editor
+          .get_database_data(&self.database_test.view_id)
+          .await
+          .unwrap();
+        assert_eq!(database.layout_type, expected);
+      },
+      LayoutScript::AssertCardLayoutSetting {
+        grid_layout,
+        expected,
+      } => {
+        let grid_setting = self.get_grid_layout_setting().await;
+        let card_setting = match grid_layout {
+          GridLayout::Gallery => grid_setting.gallery,
+          _ => grid_setting.list,
+        };
+        assert_eq!(card_setting, expected);
+      },
+      LayoutScript::UpdateCardLayoutSetting {
+        grid_layout,
+        new_setting,
+        is_ok,
+      } => {
+        let mut changeset = LayoutSettingChangeset {
+          view_id: self.database_test.view_id.clone(),
+          layout_type: DatabaseLayout::Grid,
+          board: None,
+          calendar: None,
+          gallery: None,
+          list: None,
+          timeline: None,
+        };
+        match grid_layout {
+          GridLayout::Gallery => changeset.gallery = Some(new_setting),
+          _ => changeset.list = Some(new_setting),
+        }
+        let result = self
+          .database_test
+          .editor
+          .set_layout_setting(&self.database_test.view_id, changeset)
+          .await;
+        assert_eq!(result.is_ok(), is_ok);
+      },
+      LayoutScript::AssertTimelineLayoutSetting { expected } => {
+        let grid_setting = self.get_grid_layout_setting().await;
+        assert_eq!(grid_setting.timeline, expected);
+      },
+      LayoutScript::UpdateTimelineLayoutSetting { new_setting, is_ok } => {
+        let changeset = LayoutSettingChangeset {
+          view_id: self.database_test.view_id.clone(),
+          layout_type: DatabaseLayout::Grid,
+          board: None,
+          calendar: None,
+          gallery: None,
+          list: None,
+          timeline: Some(new_setting),
+        };
+        let result = self
+          .database_test
+          .editor
+          .set_layout_setting(&self.database_test.view_id, changeset)
+          .await;
+        assert_eq!(result.is_ok(), is_ok);
+      },
+      LayoutScript::AssertTimelineEventsCount { expected } => {
+        let events = self
+          .database_test
+          .editor
+          .get_all_timeline_events(&self.database_test.view_id)
+          .await;
+        assert_eq!(events.len(), expected);
+      },
+      LayoutScript::MoveTimelineEvent {
+        row_index,
+        start_timestamp,
+        end_timestamp,
+      } => {
+        let row_id = self.database_test.row_details[row_index].row.id.clone();
+        let params = MoveTimelineEventParams {
+          view_id: self.database_test.view_id.clone(),
+          row_id,
+          start_timestamp,
+          end_timestamp,
+        };
+        self
+          .database_test
+          .editor
+          .move_timeline_event(params)
+          .await
+          .unwrap();
+      },
+      LayoutScript::AssertTimelineEvent {
+        row_index,
+        start_timestamp,
+        end_timestamp,
+      } => {
+        let row_id = self.database_test.row_details[row_index].row.id.to_string();
+        let event = self
+          .database_test
+          .editor
+          .get_all_timeline_events(&self.database_test.view_id)
+          .await
+          .into_iter()
+          .find(|event| event.row_meta.id == row_id)
+          .unwrap();
+        assert!(event.is_scheduled);
+        assert_eq!(event.start_timestamp, start_timestamp);
+        assert_eq!(event.end_timestamp, end_timestamp);
+      },
       LayoutScript::AssertAllCalendarEventsCount { expected } => {
         let events = self
           .database_test
           .editor
-          .get_all_calendar_events(&self.database_test.view_id)
+          .get_all_calendar_events(&self.database_test.view_id, None)
           .await;
         assert_eq!(events.len(), expected);
       },
@@ -110,6 +297,9 @@ impl DatabaseLayoutTest {
           layout_type: DatabaseLayout::Board,
           board: Some(new_setting),
           calendar: None,
+          gallery: None,
+          list: None,
+          timeline: None,
         };
         self
           .database_test
@@ -122,7 +312,7 @@ impl DatabaseLayoutTest {
         let events = self
           .database_test
           .editor
-          .get_all_calendar_events(&self.database_test.view_id)
+          .get_all_calendar_events(&self.database_test.view_id, None)
           .await;
         assert_eq!(events.len(), 5);
 
@@ -1,6 +1,10 @@
 use collab_database::views::DatabaseLayout;
+use flowy_database2::entities::{DatabaseLayoutPB, FieldType};
 use flowy_database2::services::setting::BoardLayoutSetting;
 use flowy_database2::services::setting::CalendarLayoutSetting;
+use flowy_database2::services::setting::{
+  CardLayoutSetting, CardSize, CoverFitMode, GridLayout, TimelineLayoutSetting, TimelineZoom,
+};
 
 use crate::database::layout_test::script::DatabaseLayoutTest;
 use crate::database::layout_test::script::LayoutScript::*;
@@ -56,3 +60,212 @@ async fn grid_to_calendar_layout_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn grid_to_gallery_layout_test() {
+  let mut test = DatabaseLayoutTest::new_grid().await;
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::Gallery,
+    },
+    AssertDatabaseLayout {
+      expected: DatabaseLayoutPB::Gallery,
+    },
+    AssertCardLayoutSetting {
+      grid_layout: GridLayout::Gallery,
+      expected: CardLayoutSetting::default(),
+    },
+    UpdateDatabaseLayout {
+      layout: DatabaseLayout::Grid,
+    },
+    AssertDatabaseLayout {
+      expected: DatabaseLayoutPB::Grid,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn gallery_layout_setting_test() {
+  let mut test = DatabaseLayoutTest::new_grid().await;
+  let url_field = test.get_first_field(FieldType::URL).await;
+  let new_setting = CardLayoutSetting {
+    card_size: CardSize::Large,
+    cover_field_id: url_field.id.clone(),
+    fit_mode: CoverFitMode::Fit,
+    visible_field_ids: vec![url_field.id],
+  };
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::Gallery,
+    },
+    UpdateCardLayoutSetting {
+      grid_layout: GridLayout::Gallery,
+      new_setting: new_setting.clone(),
+      is_ok: true,
+    },
+    AssertCardLayoutSetting {
+      grid_layout: GridLayout::Gallery,
+      expected: new_setting.clone(),
+    },
+    // The gallery setting is kept after switching to the list layout and back
+    UpdateGridLayout {
+      grid_layout: GridLayout::List,
+    },
+    AssertCardLayoutSetting {
+      grid_layout: GridLayout::List,
+      expected: CardLayoutSetting::default(),
+    },
+    UpdateGridLayout {
+      grid_layout: GridLayout::Gallery,
+    },
+    AssertCardLayoutSetting {
+      grid_layout: GridLayout::Gallery,
+      expected: new_setting,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn gallery_cover_field_must_be_url_field_test() {
+  let mut test = DatabaseLayoutTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::Gallery,
+    },
+    UpdateCardLayoutSetting {
+      grid_layout: GridLayout::Gallery,
+      new_setting: CardLayoutSetting {
+        cover_field_id: text_field.id,
+        ..Default::default()
+      },
+      is_ok: false,
+    },
+    AssertCardLayoutSetting {
+      grid_layout: GridLayout::Gallery,
+      expected: CardLayoutSetting::default(),
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn list_layout_setting_test() {
+  let mut test = DatabaseLayoutTest::new_grid().await;
+  let new_setting = CardLayoutSetting {
+    card_size: CardSize::Small,
+    ..Default::default()
+  };
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::List,
+    },
+    AssertDatabaseLayout {
+      expected: DatabaseLayoutPB::List,
+    },
+    UpdateCardLayoutSetting {
+      grid_layout: GridLayout::List,
+      new_setting: new_setting.clone(),
+      is_ok: true,
+    },
+    AssertCardLayoutSetting {
+      grid_layout: GridLayout::List,
+      expected: new_setting,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_to_timeline_layout_test() {
+  let mut test = DatabaseLayoutTest::new_grid().await;
+  let date_field = test.get_first_date_field().await;
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::Timeline,
+    },
+    AssertDatabaseLayout {
+      expected: DatabaseLayoutPB::Timeline,
+    },
+    AssertTimelineLayoutSetting {
+      expected: TimelineLayoutSetting::new(date_field.id),
+    },
+    AssertTimelineEventsCount { expected: 7 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn no_date_grid_to_timeline_layout_test() {
+  let mut test = DatabaseLayoutTest::new_no_date_grid().await;
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::Timeline,
+    },
+    AssertTimelineEventsCount { expected: 3 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn timeline_layout_setting_test() {
+  let mut test = DatabaseLayoutTest::new_grid().await;
+  let date_field = test.get_first_date_field().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let new_setting = TimelineLayoutSetting {
+    zoom: TimelineZoom::Quarter,
+    ..TimelineLayoutSetting::new(date_field.id.clone())
+  };
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::Timeline,
+    },
+    UpdateTimelineLayoutSetting {
+      new_setting: new_setting.clone(),
+      is_ok: true,
+    },
+    AssertTimelineLayoutSetting {
+      expected: new_setting.clone(),
+    },
+    // The end field must be a date field
+    UpdateTimelineLayoutSetting {
+      new_setting: TimelineLayoutSetting {
+        end_field_id: text_field.id,
+        ..new_setting.clone()
+      },
+      is_ok: false,
+    },
+    AssertTimelineLayoutSetting {
+      expected: new_setting,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn move_timeline_event_test() {
+  let mut test = DatabaseLayoutTest::new_grid().await;
+  let scripts = vec![
+    UpdateGridLayout {
+      grid_layout: GridLayout::Timeline,
+    },
+    AssertTimelineEvent {
+      row_index: 0,
+      start_timestamp: 1647251762,
+      end_timestamp: 1647251762,
+    },
+    MoveTimelineEvent {
+      row_index: 0,
+      start_timestamp: 1678090778,
+      end_timestamp: 1678695578,
+    },
+    AssertTimelineEvent {
+      row_index: 0,
+      start_timestamp: 1678090778,
+      end_timestamp: 1678695578,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -141,6 +141,8 @@ pub fn make_test_board() -> DatabaseData {
         fields.push(time_field);
       },
       FieldType::Translate => {},
+      FieldType::Formula => {},
+      FieldType::Rollup => {},
     }
   }
 
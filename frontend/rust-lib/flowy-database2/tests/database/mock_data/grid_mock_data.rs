@@ -150,6 +150,8 @@ pub fn make_test_grid() -> DatabaseData {
           .build();
         fields.push(translate_field);
       },
+      FieldType::Formula => {},
+      FieldType::Rollup => {},
     }
   }
 
@@ -85,6 +85,8 @@ async fn export_and_then_import_meta_csv_test() {
           FieldType::Summary => {},
           FieldType::Time => {},
           FieldType::Translate => {},
+          FieldType::Formula => {},
+          FieldType::Rollup => {},
         }
       } else {
         panic!(
@@ -170,6 +172,8 @@ async fn history_database_import_test() {
           FieldType::Summary => {},
           FieldType::Time => {},
           FieldType::Translate => {},
+          FieldType::Formula => {},
+          FieldType::Rollup => {},
         }
       } else {
         panic!(
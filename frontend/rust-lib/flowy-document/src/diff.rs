@@ -0,0 +1,279 @@
+use std::collections::{HashMap, HashSet};
+
+use collab_document::blocks::{Block, DocumentData};
+use serde_json::Value;
+
+use crate::entities::{BlockChangePB, BlockChangeTypePB, TextDiffPB, TextDiffTypePB};
+use crate::parser::parser_entities::InsertDelta;
+use crate::parser::utils::{delta_to_text, get_delta_for_block};
+
+/// The texts are compared as a whole if the table of the character diff would exceed this size.
+const MAX_TEXT_DIFF_CELLS: usize = 4_000_000;
+
+/// A block of the document with the position, the plain text and the formatting of the text of
+/// the block.
+struct BlockEntry<'a> {
+  block: &'a Block,
+  parent_id: String,
+  text: String,
+  formats: Vec<FormatRun>,
+}
+
+/// The number of consecutive characters that have the same attributes, and the attributes.
+type FormatRun = (usize, HashMap<String, Value>);
+
+/// Returns the changes of the blocks from the `old` document to the `new` document. The blocks
+/// are matched by their ids, and a block is considered as moved if its parent or its previous
+/// sibling, ignoring the inserted and the deleted blocks, is changed. The formatting of a text is
+/// only compared if the text itself isn't changed.
+pub(crate) fn diff_document_data(old: &DocumentData, new: &DocumentData) -> Vec<BlockChangePB> {
+  let (old_order, old_blocks) = flatten_blocks(old);
+  let (new_order, new_blocks) = flatten_blocks(new);
+  let common_ids = old_blocks
+    .keys()
+    .filter(|id| new_blocks.contains_key(*id))
+    .cloned()
+    .collect::<HashSet<_>>();
+  let old_prev_ids = prev_sibling_ids(old, &common_ids);
+  let new_prev_ids = prev_sibling_ids(new, &common_ids);
+
+  let mut changes = vec![];
+  for block_id in new_order.iter() {
+    let new_entry = &new_blocks[block_id];
+    let old_entry = match old_blocks.get(block_id) {
+      None => {
+        changes.push(block_change(
+          BlockChangeTypePB::Inserted,
+          None,
+          Some(new_entry),
+        ));
+        continue;
+      },
+      Some(old_entry) => old_entry,
+    };
+
+    if old_entry.parent_id != new_entry.parent_id
+      || old_prev_ids.get(block_id) != new_prev_ids.get(block_id)
+    {
+      changes.push(block_change(
+        BlockChangeTypePB::Moved,
+        Some(old_entry),
+        Some(new_entry),
+      ));
+    }
+    if old_entry.text != new_entry.text {
+      let mut change = block_change(
+        BlockChangeTypePB::TextChanged,
+        Some(old_entry),
+        Some(new_entry),
+      );
+      change.text_diffs = diff_text(&old_entry.text, &new_entry.text);
+      changes.push(change);
+    } else if old_entry.formats != new_entry.formats {
+      changes.push(block_change(
+        BlockChangeTypePB::FormatChanged,
+        Some(old_entry),
+        Some(new_entry),
+      ));
+    }
+    if old_entry.block.ty != new_entry.block.ty || old_entry.block.data != new_entry.block.data {
+      changes.push(block_change(
+        BlockChangeTypePB::Updated,
+        Some(old_entry),
+        Some(new_entry),
+      ));
+    }
+  }
+
+  for block_id in old_order.iter() {
+    if !new_blocks.contains_key(block_id) {
+      changes.push(block_change(
+        BlockChangeTypePB::Deleted,
+        Some(&old_blocks[block_id]),
+        None,
+      ));
+    }
+  }
+  changes
+}
+
+fn block_change(
+  change_type: BlockChangeTypePB,
+  old: Option<&BlockEntry>,
+  new: Option<&BlockEntry>,
+) -> BlockChangePB {
+  let block = new.or(old).map(|entry| entry.block);
+  BlockChangePB {
+    block_id: block.map(|block| block.id.clone()).unwrap_or_default(),
+    ty: block.map(|block| block.ty.clone()).unwrap_or_default(),
+    change_type,
+    old_parent_id: old.map(|entry| entry.parent_id.clone()).unwrap_or_default(),
+    new_parent_id: new.map(|entry| entry.parent_id.clone()).unwrap_or_default(),
+    old_text: old.map(|entry| entry.text.clone()).unwrap_or_default(),
+    new_text: new.map(|entry| entry.text.clone()).unwrap_or_default(),
+    text_diffs: vec![],
+  }
+}
+
+/// Returns the ids of the blocks in the document order and the blocks by their ids. The page
+/// block itself isn't included.
+fn flatten_blocks(data: &DocumentData) -> (Vec<String>, HashMap<String, BlockEntry>) {
+  let mut order = vec![];
+  let mut blocks = HashMap::new();
+  let mut stack = vec![data.page_id.clone()];
+  while let Some(parent_id) = stack.pop() {
+    let child_ids = child_ids(data, &parent_id);
+    // Push the children in reverse order, so they're visited in the document order.
+    for child_id in child_ids.iter().rev() {
+      stack.push(child_id.clone());
+    }
+    for child_id in child_ids {
+      if let Some(block) = data.blocks.get(child_id) {
+        let delta = get_delta_for_block(child_id, data).unwrap_or_default();
+        blocks.insert(
+          child_id.clone(),
+          BlockEntry {
+            block,
+            parent_id: parent_id.clone(),
+            text: delta_to_text(&delta),
+            formats: format_runs(&delta),
+          },
+        );
+      }
+    }
+    if parent_id != data.page_id {
+      order.push(parent_id);
+    }
+  }
+  (order, blocks)
+}
+
+/// Returns the formatting of the text of the delta. The inserts with the same attributes are
+/// merged, so that the same formatting compares equal however the text is split.
+fn format_runs(delta: &[InsertDelta]) -> Vec<FormatRun> {
+  let mut runs: Vec<FormatRun> = vec![];
+  for insert in delta {
+    let len = insert.insert.chars().count();
+    if len == 0 {
+      continue;
+    }
+    // A null attribute is a removed one
+    let attributes = insert
+      .attributes
+      .iter()
+      .flatten()
+      .filter(|(_, value)| !value.is_null())
+      .map(|(key, value)| (key.clone(), value.clone()))
+      .collect::<HashMap<_, _>>();
+    match runs.last_mut() {
+      Some((last_len, last_attributes)) if *last_attributes == attributes => *last_len += len,
+      _ => runs.push((len, attributes)),
+    }
+  }
+  runs
+}
+
+/// Returns the id of the previous sibling of each block, only counting the blocks in `ids`.
+fn prev_sibling_ids(data: &DocumentData, ids: &HashSet<String>) -> HashMap<String, String> {
+  let mut prev_ids = HashMap::new();
+  for block in data.blocks.values() {
+    let mut prev_id = String::new();
+    for child_id in child_ids(data, &block.id) {
+      if ids.contains(child_id) {
+        prev_ids.insert(child_id.clone(), prev_id);
+        prev_id = child_id.clone();
+      }
+    }
+  }
+  prev_ids
+}
+
+fn child_ids<'a>(data: &'a DocumentData, block_id: &str) -> &'a [String] {
+  data
+    .blocks
+    .get(block_id)
+    .and_then(|block| data.meta.children_map.get(&block.children))
+    .map(|children| children.as_slice())
+    .unwrap_or_default()
+}
+
+/// Returns the operations that turn the `old` text into the `new` text, compared character by
+/// character. The consecutive operations of the same type are merged.
+pub(crate) fn diff_text(old: &str, new: &str) -> Vec<TextDiffPB> {
+  let old_chars = old.chars().collect::<Vec<_>>();
+  let new_chars = new.chars().collect::<Vec<_>>();
+  let prefix_len = old_chars
+    .iter()
+    .zip(new_chars.iter())
+    .take_while(|(a, b)| a == b)
+    .count();
+  let suffix_len = old_chars[prefix_len..]
+    .iter()
+    .rev()
+    .zip(new_chars[prefix_len..].iter().rev())
+    .take_while(|(a, b)| a == b)
+    .count();
+  let old_middle = &old_chars[prefix_len..old_chars.len() - suffix_len];
+  let new_middle = &new_chars[prefix_len..new_chars.len() - suffix_len];
+
+  let mut diffs = vec![];
+  push_text_diff(&mut diffs, TextDiffTypePB::Equal, &old_chars[..prefix_len]);
+  if old_middle.len() * new_middle.len() > MAX_TEXT_DIFF_CELLS {
+    push_text_diff(&mut diffs, TextDiffTypePB::Delete, old_middle);
+    push_text_diff(&mut diffs, TextDiffTypePB::Insert, new_middle);
+  } else {
+    diff_chars(&mut diffs, old_middle, new_middle);
+  }
+  push_text_diff(
+    &mut diffs,
+    TextDiffTypePB::Equal,
+    &old_chars[old_chars.len() - suffix_len..],
+  );
+  diffs
+}
+
+/// Diffs the characters with the longest common subsequence.
+fn diff_chars(diffs: &mut Vec<TextDiffPB>, old: &[char], new: &[char]) {
+  let width = new.len() + 1;
+  // lcs[i * width + j] is the length of the longest common subsequence of old[i..] and new[j..]
+  let mut lcs = vec![0u32; (old.len() + 1) * width];
+  for i in (0..old.len()).rev() {
+    for j in (0..new.len()).rev() {
+      lcs[i * width + j] = if old[i] == new[j] {
+        lcs[(i + 1) * width + j + 1] + 1
+      } else {
+        lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+      };
+    }
+  }
+
+  let (mut i, mut j) = (0, 0);
+  while i < old.len() && j < new.len() {
+    if old[i] == new[j] {
+      push_text_diff(diffs, TextDiffTypePB::Equal, &old[i..i + 1]);
+      i += 1;
+      j += 1;
+    } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+      push_text_diff(diffs, TextDiffTypePB::Delete, &old[i..i + 1]);
+      i += 1;
+    } else {
+      push_text_diff(diffs, TextDiffTypePB::Insert, &new[j..j + 1]);
+      j += 1;
+    }
+  }
+  push_text_diff(diffs, TextDiffTypePB::Delete, &old[i..]);
+  push_text_diff(diffs, TextDiffTypePB::Insert, &new[j..]);
+}
+
+fn push_text_diff(diffs: &mut Vec<TextDiffPB>, diff_type: TextDiffTypePB, chars: &[char]) {
+  if chars.is_empty() {
+    return;
+  }
+  match diffs.last_mut() {
+    Some(last) if last.diff_type == diff_type => last.text.extend(chars),
+    _ => diffs.push(TextDiffPB {
+      diff_type,
+      text: chars.iter().collect(),
+    }),
+  }
+}
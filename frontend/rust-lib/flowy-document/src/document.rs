@@ -1,6 +1,7 @@
 use crate::entities::{
   DocEventPB, DocumentAwarenessStatesPB, DocumentSnapshotStatePB, DocumentSyncStatePB,
 };
+use crate::indexer::DocumentIndexer;
 use crate::notification::{send_notification, DocumentNotification};
 use collab::core::collab::MutexCollab;
 use collab_document::document::DocumentIndexContent;
@@ -23,13 +24,18 @@ impl MutexDocument {
   /// Open a document with the given collab.
   /// # Arguments
   /// * `collab` - the identifier of the collaboration instance
+  /// * `indexer` - keeps the search index up to date with the changes of the document
   ///
   /// # Returns
   /// * `Result<Document, FlowyError>` - a Result containing either a new Document object or an Error if the document creation failed
-  pub fn open(doc_id: &str, collab: Arc<MutexCollab>) -> FlowyResult<Self> {
+  pub fn open(
+    doc_id: &str,
+    collab: Arc<MutexCollab>,
+    indexer: DocumentIndexer,
+  ) -> FlowyResult<Self> {
     #[allow(clippy::arc_with_non_send_sync)]
     let document = Document::open(collab.clone()).map(|inner| Self(Arc::new(Mutex::new(inner))))?;
-    subscribe_document_changed(doc_id, &document);
+    subscribe_document_changed(doc_id, &document, indexer);
     subscribe_document_snapshot_state(&collab);
     subscribe_document_sync_state(&collab);
     Ok(document)
@@ -63,8 +69,9 @@ impl MutexDocument {
   }
 }
 
-fn subscribe_document_changed(doc_id: &str, document: &MutexDocument) {
+fn subscribe_document_changed(doc_id: &str, document: &MutexDocument, indexer: DocumentIndexer) {
   let doc_id_clone_for_block_changed = doc_id.to_owned();
+  let weak_document = Arc::downgrade(&document.0);
   document
     .lock()
     .subscribe_block_changed(move |events, is_remote| {
@@ -78,6 +85,8 @@ fn subscribe_document_changed(doc_id: &str, document: &MutexDocument) {
       )
       .payload::<DocEventPB>((events, is_remote, None).into())
       .send();
+
+      indexer.did_change(&doc_id_clone_for_block_changed, weak_document.clone());
     });
 
   let doc_id_clone_for_awareness_state = doc_id.to_owned();
@@ -1,7 +1,29 @@
+use std::sync::Arc;
+
+use collab::core::collab::{DataSource, MutexCollab};
+use collab::core::origin::CollabOrigin;
+use collab::preclude::Collab;
 use collab_document::blocks::{Block, DocumentData, DocumentMeta};
+use collab_document::document::Document;
+use flowy_error::{internal_error, FlowyResult};
 
 use crate::entities::{BlockPB, ChildrenPB, DocumentDataPB, MetaPB};
 
+/// Returns the data of the document that is encoded in the doc state, e.g. the doc state of a
+/// snapshot or of a published view.
+pub fn document_data_from_doc_state(doc_id: &str, doc_state: Vec<u8>) -> FlowyResult<DocumentData> {
+  let collab = Collab::new_with_source(
+    CollabOrigin::Empty,
+    doc_id,
+    DataSource::DocStateV1(doc_state),
+    vec![],
+    false,
+  )
+  .map_err(internal_error)?;
+  let document = Document::open(Arc::new(MutexCollab::new(collab)))?;
+  document.get_document_data().map_err(internal_error)
+}
+
 impl From<DocumentData> for DocumentDataPB {
   fn from(data: DocumentData) -> Self {
     let blocks = data
@@ -445,6 +445,85 @@ pub struct DocumentSnapshotMetaPB {
 
   #[pb(index = 3)]
   pub created_at: i64,
+
+  /// Empty if the snapshot was taken automatically.
+  #[pb(index = 4)]
+  pub title: String,
+
+  #[pb(index = 5)]
+  pub desc: String,
+}
+
+impl From<DocumentSnapshotMeta> for DocumentSnapshotMetaPB {
+  fn from(meta: DocumentSnapshotMeta) -> Self {
+    Self {
+      snapshot_id: meta.snapshot_id,
+      object_id: meta.object_id,
+      created_at: meta.created_at,
+      title: meta.title,
+      desc: meta.desc,
+    }
+  }
+}
+
+/// Creates a named checkpoint of the document.
+#[derive(Debug, Default, ProtoBuf)]
+pub struct CreateDocumentSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  pub title: String,
+
+  #[pb(index = 3)]
+  pub desc: String,
+}
+
+pub struct CreateDocumentSnapshotParams {
+  pub document_id: String,
+  pub title: String,
+  pub desc: String,
+}
+
+impl TryInto<CreateDocumentSnapshotParams> for CreateDocumentSnapshotPayloadPB {
+  type Error = ErrorCode;
+  fn try_into(self) -> Result<CreateDocumentSnapshotParams, Self::Error> {
+    let document_id =
+      NotEmptyStr::parse(self.document_id).map_err(|_| ErrorCode::DocumentIdIsEmpty)?;
+    let title = NotEmptyStr::parse(self.title).map_err(|_| ErrorCode::InvalidParams)?;
+    Ok(CreateDocumentSnapshotParams {
+      document_id: document_id.0,
+      title: title.0,
+      desc: self.desc,
+    })
+  }
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RestoreDocumentSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  pub snapshot_id: String,
+}
+
+pub struct RestoreDocumentSnapshotParams {
+  pub document_id: String,
+  pub snapshot_id: String,
+}
+
+impl TryInto<RestoreDocumentSnapshotParams> for RestoreDocumentSnapshotPayloadPB {
+  type Error = ErrorCode;
+  fn try_into(self) -> Result<RestoreDocumentSnapshotParams, Self::Error> {
+    let document_id =
+      NotEmptyStr::parse(self.document_id).map_err(|_| ErrorCode::DocumentIdIsEmpty)?;
+    let snapshot_id = NotEmptyStr::parse(self.snapshot_id).map_err(|_| ErrorCode::InvalidParams)?;
+    Ok(RestoreDocumentSnapshotParams {
+      document_id: document_id.0,
+      snapshot_id: snapshot_id.0,
+    })
+  }
 }
 
 #[derive(Debug, Default, ProtoBuf)]
@@ -522,10 +601,13 @@ impl TryInto<TextDeltaParams> for TextDeltaPayloadPB {
   }
 }
 
+#[derive(Debug, Clone)]
 pub struct DocumentSnapshotMeta {
   pub snapshot_id: String,
   pub object_id: String,
   pub created_at: i64,
+  pub title: String,
+  pub desc: String,
 }
 
 pub struct DocumentSnapshotData {
@@ -533,6 +615,117 @@ pub struct DocumentSnapshotData {
   pub encoded_v1: Vec<u8>,
 }
 
+#[derive(Default, ProtoBuf, Debug)]
+pub struct DocumentDiffPayloadPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+
+  #[pb(index = 2)]
+  pub from_snapshot_id: String,
+
+  /// The snapshot is compared with the current state of the document if it's None.
+  #[pb(index = 3, one_of)]
+  pub to_snapshot_id: Option<String>,
+}
+
+pub struct DocumentDiffParams {
+  pub document_id: String,
+  pub from_snapshot_id: String,
+  pub to_snapshot_id: Option<String>,
+}
+
+impl TryInto<DocumentDiffParams> for DocumentDiffPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DocumentDiffParams, Self::Error> {
+    let document_id =
+      NotEmptyStr::parse(self.document_id).map_err(|_| ErrorCode::DocumentIdIsEmpty)?;
+    let from_snapshot_id =
+      NotEmptyStr::parse(self.from_snapshot_id).map_err(|_| ErrorCode::InvalidParams)?;
+    Ok(DocumentDiffParams {
+      document_id: document_id.0,
+      from_snapshot_id: from_snapshot_id.0,
+      to_snapshot_id: self.to_snapshot_id.filter(|id| !id.is_empty()),
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug)]
+pub struct DocumentDiffPB {
+  /// The changes are ordered by the position of the blocks in the newer document, followed by
+  /// the deleted blocks.
+  #[pb(index = 1)]
+  pub changes: Vec<BlockChangePB>,
+}
+
+#[derive(ProtoBuf_Enum, Debug, Clone, PartialEq, Eq)]
+pub enum BlockChangeTypePB {
+  Inserted = 0,
+  Deleted = 1,
+  Moved = 2,
+  TextChanged = 3,
+  /// The type or the data of the block, eg. the level of a heading, is changed.
+  Updated = 4,
+  /// The text of the block is the same, but its formatting, eg. bold or a link, is changed.
+  FormatChanged = 5,
+}
+
+impl Default for BlockChangeTypePB {
+  fn default() -> Self {
+    Self::Inserted
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct BlockChangePB {
+  #[pb(index = 1)]
+  pub block_id: String,
+
+  #[pb(index = 2)]
+  pub ty: String,
+
+  #[pb(index = 3)]
+  pub change_type: BlockChangeTypePB,
+
+  #[pb(index = 4)]
+  pub old_parent_id: String,
+
+  #[pb(index = 5)]
+  pub new_parent_id: String,
+
+  #[pb(index = 6)]
+  pub old_text: String,
+
+  #[pb(index = 7)]
+  pub new_text: String,
+
+  /// Only filled for the [BlockChangeTypePB::TextChanged] changes.
+  #[pb(index = 8)]
+  pub text_diffs: Vec<TextDiffPB>,
+}
+
+#[derive(ProtoBuf_Enum, Debug, Clone, PartialEq, Eq)]
+pub enum TextDiffTypePB {
+  Equal = 0,
+  Insert = 1,
+  Delete = 2,
+}
+
+impl Default for TextDiffTypePB {
+  fn default() -> Self {
+    Self::Equal
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone, PartialEq, Eq)]
+pub struct TextDiffPB {
+  #[pb(index = 1)]
+  pub diff_type: TextDiffTypePB,
+
+  #[pb(index = 2)]
+  pub text: String,
+}
+
 #[derive(ProtoBuf, Debug, Default)]
 pub struct DocumentAwarenessStatesPB {
   #[pb(index = 1)]
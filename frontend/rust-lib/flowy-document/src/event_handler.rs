@@ -149,7 +149,7 @@ pub(crate) async fn apply_text_delta_handler(
   if cfg!(feature = "verbose_log") {
     tracing::trace!("{} applying delta: {:?}", doc_id, delta);
   }
-  document.apply_text_delta(&text_id, delta);
+  manager.apply_text_delta(&document, &text_id, delta);
   Ok(())
 }
 
@@ -254,6 +254,46 @@ pub(crate) async fn get_snapshot_data_handler(
   data_result_ok(snapshot)
 }
 
+pub(crate) async fn create_snapshot_handler(
+  data: AFPluginData<CreateDocumentSnapshotPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<DocumentSnapshotMetaPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params: CreateDocumentSnapshotParams = data.into_inner().try_into()?;
+  let meta = manager
+    .create_document_snapshot(&params.document_id, &params.title, &params.desc)
+    .await?;
+  data_result_ok(meta)
+}
+
+pub(crate) async fn get_document_diff_handler(
+  data: AFPluginData<DocumentDiffPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> DataResult<DocumentDiffPB, FlowyError> {
+  let manager = upgrade_document(manager)?;
+  let params: DocumentDiffParams = data.into_inner().try_into()?;
+  let diff = manager
+    .get_document_diff(
+      &params.document_id,
+      &params.from_snapshot_id,
+      params.to_snapshot_id.as_deref(),
+    )
+    .await?;
+  data_result_ok(diff)
+}
+
+pub(crate) async fn restore_snapshot_handler(
+  data: AFPluginData<RestoreDocumentSnapshotPayloadPB>,
+  manager: AFPluginState<Weak<DocumentManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_document(manager)?;
+  let params: RestoreDocumentSnapshotParams = data.into_inner().try_into()?;
+  manager
+    .restore_document_snapshot(&params.document_id, &params.snapshot_id)
+    .await?;
+  Ok(())
+}
+
 impl From<BlockActionPB> for BlockAction {
   fn from(pb: BlockActionPB) -> Self {
     Self {
@@ -362,12 +402,14 @@ impl From<(&Vec<BlockEvent>, bool, Option<DocumentData>)> for DocEventPB {
 ///     json: true,
 ///     text: true,
 ///     html: true,
+///     markdown: true,
 ///   },
 /// };
 /// let result = test.convert_document(payload).await;
 /// assert_eq!(result.json, Some("[{ \"block_id\": \"1\", \"type\": \"paragraph\", \"data\": {\"delta\": [{ \"insert\": \"Hello\" }] } }, { \"block_id\": \"2\", \"type\": \"paragraph\", \"data\": {\"delta\": [{ \"insert\": \" World!\" }] } }".to_string()));
 /// assert_eq!(result.text, Some("Hello\n World!".to_string()));
 /// assert_eq!(result.html, Some("<p>Hello</p><p> World!</p>".to_string()));
+/// assert_eq!(result.markdown, Some("Hello\n\n World!\n".to_string()));
 /// ```
 /// #
 pub async fn convert_document_handler(
@@ -400,6 +442,10 @@ pub async fn convert_document_handler(
       .parse_types
       .text
       .then(|| parser.to_text_with_json(root)),
+    markdown: params
+      .parse_types
+      .markdown
+      .then(|| parser.to_markdown_with_json(root)),
   })
 }
 
@@ -37,6 +37,18 @@ pub fn init(document_manager: Weak<DocumentManager>) -> AFPlugin {
       DocumentEvent::GetDocumentSnapshot,
       get_snapshot_data_handler,
     )
+    .event(
+      DocumentEvent::CreateDocumentSnapshot,
+      create_snapshot_handler,
+    )
+    .event(
+      DocumentEvent::RestoreDocumentSnapshot,
+      restore_snapshot_handler,
+    )
+    .event(
+      DocumentEvent::GetDocumentSnapshotDiff,
+      get_document_diff_handler,
+    )
     .event(DocumentEvent::CreateText, create_text_handler)
     .event(DocumentEvent::ApplyTextDeltaEvent, apply_text_delta_handler)
     .event(DocumentEvent::ConvertDocument, convert_document_handler)
@@ -133,4 +145,20 @@ pub enum DocumentEvent {
 
   #[event(input = "OpenDocumentPayloadPB", output = "EncodedCollabPB")]
   GetDocEncodedCollab = 19,
+
+  /// Creates a named checkpoint of the document.
+  #[event(
+    input = "CreateDocumentSnapshotPayloadPB",
+    output = "DocumentSnapshotMetaPB"
+  )]
+  CreateDocumentSnapshot = 20,
+
+  /// Replaces the content of the document with the content of the snapshot.
+  #[event(input = "RestoreDocumentSnapshotPayloadPB")]
+  RestoreDocumentSnapshot = 21,
+
+  /// Returns the changes of the blocks between two snapshots, or between a snapshot and the
+  /// current state of the document.
+  #[event(input = "DocumentDiffPayloadPB", output = "DocumentDiffPB")]
+  GetDocumentSnapshotDiff = 22,
 }
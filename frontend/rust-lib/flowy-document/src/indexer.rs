@@ -0,0 +1,173 @@
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use collab_document::blocks::DocumentData;
+use collab_document::document::Document;
+use dashmap::DashSet;
+use flowy_error::FlowyResult;
+use flowy_search_pub::entities::{
+  BacklinkIndexData, BacklinkIndexManager, DocumentBlockIndexData, DocumentIndexData,
+  DocumentIndexManager, ViewReferenceData,
+};
+use lib_dispatch::prelude::af_spawn;
+use parking_lot::Mutex;
+use tracing::{error, trace};
+
+use crate::manager::DocumentUserService;
+use crate::mention::mentioned_page_ids;
+use crate::parser::utils::{delta_to_text, get_delta_for_block};
+
+/// Changes of the same document within this duration are indexed at once.
+const INDEX_DEBOUNCE_DURATION: Duration = Duration::from_secs(2);
+
+/// Keeps the content index and the page mentions of the documents up to date. The indexes
+/// themselves are provided by the search crate through the [DocumentIndexManager] and the
+/// [BacklinkIndexManager].
+#[derive(Clone)]
+pub struct DocumentIndexer {
+  index_manager: Arc<dyn DocumentIndexManager>,
+  backlink_manager: Arc<dyn BacklinkIndexManager>,
+  user_service: Arc<dyn DocumentUserService>,
+  /// The documents that are waiting to be indexed.
+  pending_documents: Arc<DashSet<String>>,
+}
+
+impl DocumentIndexer {
+  pub fn new(
+    index_manager: Arc<dyn DocumentIndexManager>,
+    backlink_manager: Arc<dyn BacklinkIndexManager>,
+    user_service: Arc<dyn DocumentUserService>,
+  ) -> Self {
+    Self {
+      index_manager,
+      backlink_manager,
+      user_service,
+      pending_documents: Arc::new(DashSet::new()),
+    }
+  }
+
+  pub fn is_indexed(&self, doc_id: &str) -> bool {
+    self.index_manager.is_document_indexed(doc_id)
+      && self.backlink_manager.is_object_indexed(doc_id)
+  }
+
+  pub fn index_document(&self, doc_id: &str, data: &DocumentData) -> FlowyResult<()> {
+    self.index_documents(vec![(doc_id.to_string(), data.clone())])
+  }
+
+  pub fn index_documents(&self, documents: Vec<(String, DocumentData)>) -> FlowyResult<()> {
+    let workspace_id = self.user_service.workspace_id()?;
+    let index_data = documents
+      .iter()
+      .map(|(doc_id, data)| document_index_data(doc_id, workspace_id.clone(), data))
+      .collect();
+    let backlink_data = documents
+      .iter()
+      .map(|(doc_id, data)| document_backlink_data(doc_id, workspace_id.clone(), data))
+      .collect();
+    // The indexes are independent, failing to update one of them doesn't skip the other
+    let result = self.index_manager.index_documents(index_data);
+    self.backlink_manager.index_references(backlink_data)?;
+    result
+  }
+
+  pub fn remove_document(&self, doc_id: &str) -> FlowyResult<()> {
+    let result = self.index_manager.remove_indices(vec![doc_id.to_string()]);
+    self
+      .backlink_manager
+      .remove_references(vec![doc_id.to_string()])?;
+    result
+  }
+
+  /// Called when the blocks of the document changed. The document can't be read inside the
+  /// change callback, so the indexing is deferred and debounced.
+  pub fn did_change(&self, doc_id: &str, document: Weak<Mutex<Document>>) {
+    if !self.pending_documents.insert(doc_id.to_string()) {
+      return;
+    }
+
+    let indexer = self.clone();
+    let doc_id = doc_id.to_string();
+    af_spawn(async move {
+      tokio::time::sleep(INDEX_DEBOUNCE_DURATION).await;
+      indexer.pending_documents.remove(&doc_id);
+
+      let data = match document.upgrade() {
+        Some(document) => document.lock().get_document_data(),
+        None => return,
+      };
+      match data {
+        Ok(data) => {
+          trace!("[Document Index] index document: {}", doc_id);
+          if let Err(err) = indexer.index_document(&doc_id, &data) {
+            error!(
+              "[Document Index] failed to index document {}: {}",
+              doc_id, err
+            );
+          }
+        },
+        Err(err) => error!(
+          "[Document Index] failed to get document {}: {}",
+          doc_id, err
+        ),
+      }
+    });
+  }
+}
+
+/// Each block that contains text is indexed separately.
+fn document_index_data(
+  doc_id: &str,
+  workspace_id: String,
+  data: &DocumentData,
+) -> DocumentIndexData {
+  let blocks = data
+    .blocks
+    .keys()
+    .filter_map(|block_id| {
+      let delta = get_delta_for_block(block_id, data)?;
+      let text = delta_to_text(&delta);
+      if text.trim().is_empty() {
+        return None;
+      }
+      Some(DocumentBlockIndexData {
+        block_id: block_id.clone(),
+        text,
+      })
+    })
+    .collect();
+
+  DocumentIndexData {
+    document_id: doc_id.to_string(),
+    workspace_id,
+    blocks,
+  }
+}
+
+/// The pages mentioned by the blocks of the document. The id of a document is the id of its view.
+fn document_backlink_data(
+  doc_id: &str,
+  workspace_id: String,
+  data: &DocumentData,
+) -> BacklinkIndexData {
+  let references = data
+    .blocks
+    .keys()
+    .flat_map(|block_id| {
+      mentioned_page_ids(block_id, data)
+        .into_iter()
+        .filter(|page_id| page_id != doc_id)
+        .map(|page_id| ViewReferenceData {
+          source_view_id: doc_id.to_string(),
+          block_id: block_id.clone(),
+          target_view_id: page_id,
+        })
+    })
+    .collect();
+
+  BacklinkIndexData {
+    object_id: doc_id.to_string(),
+    workspace_id,
+    references,
+  }
+}
@@ -3,11 +3,15 @@ pub mod document_data;
 pub mod entities;
 pub mod event_handler;
 pub mod event_map;
+pub mod indexer;
 pub mod manager;
+pub mod mention;
 pub mod parser;
 pub mod protobuf;
+mod snapshot;
 
 pub mod deps;
+mod diff;
 pub mod notification;
 mod parse;
 pub mod reminder;
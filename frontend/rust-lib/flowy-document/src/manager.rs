@@ -15,21 +15,33 @@ use collab_plugins::local_storage::kv::PersistenceError;
 use collab_plugins::CollabKVDB;
 use dashmap::DashMap;
 use lib_infra::util::timestamp;
+use tokio::sync::broadcast;
 use tracing::trace;
-use tracing::{event, instrument};
+use tracing::{error, event, instrument};
 
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_search_pub::entities::{BacklinkIndexManager, DocumentIndexManager};
 use flowy_storage_pub::storage::StorageService;
 use lib_dispatch::prelude::af_spawn;
 
+use crate::diff::diff_document_data;
 use crate::document::MutexDocument;
 use crate::entities::UpdateDocumentAwarenessStatePB;
 use crate::entities::{
-  DocumentSnapshotData, DocumentSnapshotMeta, DocumentSnapshotMetaPB, DocumentSnapshotPB,
+  DocumentDiffPB, DocumentSnapshotData, DocumentSnapshotMeta, DocumentSnapshotMetaPB,
+  DocumentSnapshotPB,
 };
-use crate::reminder::DocumentReminderAction;
+use crate::indexer::DocumentIndexer;
+use crate::mention::{date_mention_reminders, page_mention_update_delta, PageMentionUpdate};
+use crate::parser::parser_entities::InsertDelta;
+use crate::parser::utils::get_delta_for_block;
+use crate::reminder::{DocumentReminderAction, ReminderDateChange};
+use crate::snapshot::{document_data_from_snapshot, restore_document_actions};
+
+/// The title of the checkpoint that is created before restoring a snapshot of the document.
+const RESTORE_CHECKPOINT_TITLE: &str = "Before restore";
 
 pub trait DocumentUserService: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
@@ -44,6 +56,13 @@ pub trait DocumentSnapshotService: Send + Sync {
     document_id: &str,
   ) -> FlowyResult<Vec<DocumentSnapshotMeta>>;
   fn get_document_snapshot(&self, snapshot_id: &str) -> FlowyResult<DocumentSnapshotData>;
+  fn create_document_snapshot(
+    &self,
+    document_id: &str,
+    title: &str,
+    desc: &str,
+    encoded_v1: Vec<u8>,
+  ) -> FlowyResult<DocumentSnapshotMeta>;
 }
 
 pub struct DocumentManager {
@@ -54,6 +73,8 @@ pub struct DocumentManager {
   cloud_service: Arc<dyn DocumentCloudService>,
   storage_service: Weak<dyn StorageService>,
   snapshot_service: Arc<dyn DocumentSnapshotService>,
+  indexer: DocumentIndexer,
+  reminder_date_tx: broadcast::Sender<ReminderDateChange>,
 }
 
 impl DocumentManager {
@@ -63,7 +84,11 @@ impl DocumentManager {
     cloud_service: Arc<dyn DocumentCloudService>,
     storage_service: Weak<dyn StorageService>,
     snapshot_service: Arc<dyn DocumentSnapshotService>,
+    index_manager: Arc<dyn DocumentIndexManager>,
+    backlink_manager: Arc<dyn BacklinkIndexManager>,
   ) -> Self {
+    let indexer = DocumentIndexer::new(index_manager, backlink_manager, user_service.clone());
+    let (reminder_date_tx, _) = broadcast::channel(100);
     Self {
       user_service,
       collab_builder,
@@ -72,6 +97,48 @@ impl DocumentManager {
       cloud_service,
       storage_service,
       snapshot_service,
+      indexer,
+      reminder_date_tx,
+    }
+  }
+
+  /// Subscribes to the changes of the dates that have a reminder, so the reminders can be
+  /// rescheduled. Only the changes made on this device are sent.
+  pub fn subscribe_reminder_date_change(&self) -> broadcast::Receiver<ReminderDateChange> {
+    self.reminder_date_tx.subscribe()
+  }
+
+  /// Applies the delta to the text of the document, and sends the changes of the dates of the
+  /// date mentions that keep their reminder, see [Self::subscribe_reminder_date_change].
+  pub fn apply_text_delta(&self, document: &Document, text_id: &str, delta: String) {
+    let new_dates = serde_json::from_str::<Vec<InsertDelta>>(&delta)
+      .map(|delta| date_mention_reminders(&delta))
+      .unwrap_or_default();
+    // A mention with a new reminder is scheduled by the client along with the date.
+    let old_dates = if new_dates.is_empty() {
+      Default::default()
+    } else {
+      document
+        .get_document_data()
+        .ok()
+        .and_then(|data| data.meta.text_map?.remove(text_id))
+        .and_then(|delta| serde_json::from_str::<Vec<InsertDelta>>(&delta).ok())
+        .map(|delta| date_mention_reminders(&delta))
+        .unwrap_or_default()
+    };
+
+    document.apply_text_delta(text_id, delta);
+    for (reminder_id, new_date) in new_dates {
+      match old_dates.get(&reminder_id) {
+        Some(old_date) if *old_date != new_date => {
+          let _ = self.reminder_date_tx.send(ReminderDateChange {
+            reminder_id,
+            old_date: *old_date,
+            new_date,
+          });
+        },
+        _ => {},
+      }
     }
   }
 
@@ -132,11 +199,8 @@ impl DocumentManager {
         format!("document {} already exists", doc_id),
       ))
     } else {
-      let encoded_collab = doc_state_from_document_data(
-        doc_id,
-        data.unwrap_or_else(|| default_document_data(doc_id)),
-      )
-      .await?;
+      let data = data.unwrap_or_else(|| default_document_data(doc_id));
+      let encoded_collab = doc_state_from_document_data(doc_id, data.clone()).await?;
       let doc_state = encoded_collab.doc_state.to_vec();
       let collab = self
         .collab_for_document(
@@ -148,6 +212,9 @@ impl DocumentManager {
         .await?;
       collab.lock().flush();
 
+      if let Err(err) = self.indexer.index_document(doc_id, &data) {
+        error!("Failed to index document {}: {}", doc_id, err);
+      }
       Ok(encoded_collab)
     }
   }
@@ -204,7 +271,7 @@ impl DocumentManager {
       .collab_for_document(uid, doc_id, doc_state, true)
       .await?;
 
-    match MutexDocument::open(doc_id, collab) {
+    match MutexDocument::open(doc_id, collab, self.indexer.clone()) {
       Ok(document) => {
         let document = Arc::new(document);
         self.documents.insert(doc_id.to_string(), document.clone());
@@ -281,6 +348,62 @@ impl DocumentManager {
       // When deleting a document, we need to remove it from the cache.
       self.documents.remove(doc_id);
     }
+    if let Err(err) = self.indexer.remove_document(doc_id) {
+      error!("Failed to remove document {} from index: {}", doc_id, err);
+    }
+    Ok(())
+  }
+
+  /// Index the documents that are not indexed yet. Only the documents that exist in local disk
+  /// are indexed, the others will be indexed when they are opened.
+  #[instrument(level = "debug", skip_all, err)]
+  pub async fn index_documents(&self, doc_ids: Vec<String>) -> FlowyResult<()> {
+    let mut documents = vec![];
+    for doc_id in doc_ids {
+      if self.indexer.is_indexed(&doc_id) || !self.is_doc_exist(&doc_id).await? {
+        continue;
+      }
+
+      match self.get_document_data(&doc_id).await {
+        Ok(data) => documents.push((doc_id, data)),
+        Err(err) => error!("Failed to get document data of {}: {}", doc_id, err),
+      }
+    }
+
+    trace!("index {} documents", documents.len());
+    self.indexer.index_documents(documents)
+  }
+
+  /// Applies the update of the page to its mentions in the given blocks of the document. The
+  /// blocks that don't mention the page are left unchanged.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn update_page_mentions(
+    &self,
+    doc_id: &str,
+    block_ids: &[String],
+    page_id: &str,
+    update: PageMentionUpdate,
+  ) -> FlowyResult<()> {
+    let document = match self.get_document(doc_id).await {
+      Ok(document) => document,
+      Err(_) => self.create_document_instance(doc_id).await?,
+    };
+    let document = document.lock();
+    let data = document.get_document_data().map_err(internal_error)?;
+    for block_id in block_ids {
+      let text_id = match data
+        .blocks
+        .get(block_id)
+        .and_then(|block| block.external_id.clone())
+      {
+        Some(text_id) => text_id,
+        None => continue,
+      };
+      let delta = get_delta_for_block(block_id, &data).unwrap_or_default();
+      if let Some(delta) = page_mention_update_delta(&delta, page_id, &update) {
+        document.apply_text_delta(&text_id, delta);
+      }
+    }
     Ok(())
   }
 
@@ -320,11 +443,7 @@ impl DocumentManager {
       .snapshot_service
       .get_document_snapshot_metas(document_id)?
       .into_iter()
-      .map(|meta| DocumentSnapshotMetaPB {
-        snapshot_id: meta.snapshot_id,
-        object_id: meta.object_id,
-        created_at: meta.created_at,
-      })
+      .map(DocumentSnapshotMetaPB::from)
       .collect::<Vec<_>>();
 
     Ok(metas)
@@ -341,6 +460,83 @@ impl DocumentManager {
     Ok(snapshot)
   }
 
+  /// Creates a named checkpoint of the document. Unlike the automatic snapshots, the checkpoints
+  /// are never removed by the snapshot retention.
+  pub async fn create_document_snapshot(
+    &self,
+    document_id: &str,
+    title: &str,
+    desc: &str,
+  ) -> FlowyResult<DocumentSnapshotMetaPB> {
+    let encoded_v1 = self
+      .encode_collab(document_id)
+      .await?
+      .encode_to_bytes()
+      .map_err(internal_error)?;
+    let meta =
+      self
+        .snapshot_service
+        .create_document_snapshot(document_id, title, desc, encoded_v1)?;
+    Ok(meta.into())
+  }
+
+  /// Returns the changes of the blocks between two snapshots of the document. The snapshot is
+  /// compared with the current state of the document if `to_snapshot_id` is None.
+  pub async fn get_document_diff(
+    &self,
+    document_id: &str,
+    from_snapshot_id: &str,
+    to_snapshot_id: Option<&str>,
+  ) -> FlowyResult<DocumentDiffPB> {
+    let old = self.get_document_data_of_snapshot(document_id, from_snapshot_id)?;
+    let new = match to_snapshot_id {
+      Some(to_snapshot_id) => self.get_document_data_of_snapshot(document_id, to_snapshot_id)?,
+      None => self.get_document_data(document_id).await?,
+    };
+    Ok(DocumentDiffPB {
+      changes: diff_document_data(&old, &new),
+    })
+  }
+
+  fn get_document_data_of_snapshot(
+    &self,
+    document_id: &str,
+    snapshot_id: &str,
+  ) -> FlowyResult<DocumentData> {
+    let snapshot = self.snapshot_service.get_document_snapshot(snapshot_id)?;
+    if snapshot.object_id != document_id {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "Snapshot {} doesn't belong to document {}",
+        snapshot_id, document_id
+      )));
+    }
+    document_data_from_snapshot(document_id, &snapshot.encoded_v1)
+  }
+
+  /// Replaces the content of the document with the content of the snapshot. The current content
+  /// is saved as a checkpoint first, and the snapshot is applied as a new change of the document,
+  /// so the restore can be undone.
+  #[instrument(level = "debug", skip(self), err)]
+  pub async fn restore_document_snapshot(
+    &self,
+    document_id: &str,
+    snapshot_id: &str,
+  ) -> FlowyResult<()> {
+    let snapshot_data = self.get_document_data_of_snapshot(document_id, snapshot_id)?;
+    self
+      .create_document_snapshot(document_id, RESTORE_CHECKPOINT_TITLE, "")
+      .await?;
+
+    let document = match self.get_document(document_id).await {
+      Ok(document) => document,
+      Err(_) => self.create_document_instance(document_id).await?,
+    };
+    let document = document.lock();
+    let current_data = document.get_document_data().map_err(internal_error)?;
+    document.apply_action(restore_document_actions(&current_data, &snapshot_data));
+    Ok(())
+  }
+
   #[instrument(level = "debug", skip_all, err)]
   pub async fn upload_file(
     &self,
@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use collab_document::blocks::DocumentData;
+use serde_json::{json, Map, Value};
+
+use crate::parser::constant::{
+  MENTION, MENTION_DATE, MENTION_REMINDER_ID, MENTION_TYPE, PAGE, PAGE_ID, PAGE_IS_TRASHED,
+};
+use crate::parser::parser_entities::InsertDelta;
+use crate::parser::utils::get_delta_for_block;
+
+/// The change of a mentioned page that is applied to its mentions, see
+/// [crate::manager::DocumentManager::update_page_mentions].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageMentionUpdate {
+  /// The mentions are marked as trashed, so that they can be displayed as such without
+  /// looking up the page.
+  Trashed,
+  /// Removes the trashed mark of the mentions.
+  Restored,
+  /// A mention of a page that doesn't exist anymore is replaced by the last name of the page.
+  Deleted { name: String },
+}
+
+/// Returns the ids of the pages mentioned by the block, e.g.
+/// `{ "insert": "$", "attributes": { "mention": { "type": "page", "page_id": "..." } } }`
+pub fn mentioned_page_ids(block_id: &str, data: &DocumentData) -> Vec<String> {
+  let mut page_ids = vec![];
+  let mut visited = HashSet::new();
+  for op in get_delta_for_block(block_id, data).unwrap_or_default() {
+    if let Some(page_id) = mentioned_page_id(&op) {
+      if visited.insert(page_id.to_string()) {
+        page_ids.push(page_id.to_string());
+      }
+    }
+  }
+  page_ids
+}
+
+/// Returns the delta that applies the update to the mentions of the page in the text, or None
+/// if the text doesn't mention the page. The positions of a delta are counted in UTF-16 code
+/// units, the same as the editors do.
+pub fn page_mention_update_delta(
+  delta: &[InsertDelta],
+  page_id: &str,
+  update: &PageMentionUpdate,
+) -> Option<String> {
+  let mut ops = vec![];
+  let mut retain = 0;
+  for op in delta {
+    let len = op.insert.encode_utf16().count();
+    if mentioned_page_id(op) != Some(page_id) {
+      retain += len;
+      continue;
+    }
+
+    if retain > 0 {
+      ops.push(json!({ "retain": retain }));
+      retain = 0;
+    }
+    let attributes = op.attributes.clone().unwrap_or_default();
+    match update {
+      PageMentionUpdate::Trashed | PageMentionUpdate::Restored => {
+        let mut mention = attributes
+          .get(MENTION)
+          .and_then(|mention| mention.as_object())
+          .cloned()
+          .unwrap_or_default();
+        if *update == PageMentionUpdate::Trashed {
+          mention.insert(PAGE_IS_TRASHED.to_string(), Value::Bool(true));
+        } else {
+          mention.remove(PAGE_IS_TRASHED);
+        }
+        ops.push(json!({ "retain": len, "attributes": { MENTION: mention } }));
+      },
+      PageMentionUpdate::Deleted { name } => {
+        ops.push(json!({ "delete": len }));
+        // Keep the formatting of the mention, e.g. bold
+        let attributes = attributes
+          .into_iter()
+          .filter(|(key, _)| key != MENTION)
+          .collect::<Map<String, Value>>();
+        if attributes.is_empty() {
+          ops.push(json!({ "insert": name }));
+        } else {
+          ops.push(json!({ "insert": name, "attributes": attributes }));
+        }
+      },
+    }
+  }
+
+  if ops.is_empty() {
+    return None;
+  }
+  serde_json::to_string(&ops).ok()
+}
+
+/// Returns the dates of the date mentions that have a reminder, by the id of the reminder, e.g.
+/// `{ "insert": "$", "attributes": { "mention": { "type": "date", "date": "...", "reminder_id": "..." } } }`
+/// The dates are timestamps in seconds.
+pub fn date_mention_reminders(delta: &[InsertDelta]) -> HashMap<String, i64> {
+  delta
+    .iter()
+    .flat_map(|op| {
+      let mention = op.attributes.as_ref()?.get(MENTION)?;
+      if mention.get(MENTION_TYPE)?.as_str()? != MENTION_DATE {
+        return None;
+      }
+      let reminder_id = mention.get(MENTION_REMINDER_ID)?.as_str()?;
+      if reminder_id.is_empty() {
+        return None;
+      }
+      let date = parse_mention_date(mention.get(MENTION_DATE)?.as_str()?)?;
+      Some((reminder_id.to_string(), date))
+    })
+    .collect()
+}
+
+/// The date of a mention is an ISO 8601 string, without an offset if it's a local time.
+fn parse_mention_date(date: &str) -> Option<i64> {
+  if let Ok(date_time) = DateTime::parse_from_rfc3339(date) {
+    return Some(date_time.timestamp());
+  }
+  let date_time = NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+  Local
+    .from_local_datetime(&date_time)
+    .earliest()
+    .map(|date_time| date_time.timestamp())
+}
+
+fn mentioned_page_id(op: &InsertDelta) -> Option<&str> {
+  let mention = op.attributes.as_ref()?.get(MENTION)?;
+  if mention.get(MENTION_TYPE)?.as_str()? != PAGE {
+    return None;
+  }
+  mention.get(PAGE_ID)?.as_str()
+}
@@ -13,6 +13,11 @@ pub const URL: &str = "url";
 pub const CAPTION: &str = "caption";
 pub const ALIGN: &str = "align";
 
+pub const ROWS_LEN: &str = "rowsLen";
+pub const COLS_LEN: &str = "colsLen";
+pub const ROW_POSITION: &str = "rowPosition";
+pub const COL_POSITION: &str = "colPosition";
+
 pub const PAGE: &str = "page";
 pub const HEADING: &str = "heading";
 pub const PARAGRAPH: &str = "paragraph";
@@ -25,6 +30,8 @@ pub const CALLOUT: &str = "callout";
 pub const IMAGE: &str = "image";
 pub const DIVIDER: &str = "divider";
 pub const MATH_EQUATION: &str = "math_equation";
+pub const TABLE: &str = "table";
+pub const TABLE_CELL: &str = "table/cell";
 pub const BOLD: &str = "bold";
 pub const ITALIC: &str = "italic";
 pub const STRIKETHROUGH: &str = "strikethrough";
@@ -35,6 +42,11 @@ pub const BG_COLOR: &str = "bg_color";
 
 pub const FORMULA: &str = "formula";
 pub const MENTION: &str = "mention";
+pub const MENTION_TYPE: &str = "type";
+pub const PAGE_ID: &str = "page_id";
+pub const PAGE_IS_TRASHED: &str = "is_trashed";
+pub const MENTION_DATE: &str = "date";
+pub const MENTION_REMINDER_ID: &str = "reminder_id";
 
 pub const TEXT_DIRECTION: &str = "text_direction";
 
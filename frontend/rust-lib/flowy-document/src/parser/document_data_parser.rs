@@ -1,10 +1,12 @@
 use crate::parser::constant::DELTA;
-use crate::parser::parser_entities::{ConvertBlockToHtmlParams, InsertDelta, NestedBlock, Range};
+use crate::parser::parser_entities::{
+  ConvertBlockToHtmlParams, ConvertBlockToMarkdownParams, InsertDelta, NestedBlock, Range,
+};
 use crate::parser::utils::{get_delta_for_block, get_delta_for_selection};
 use collab_document::blocks::DocumentData;
 use std::sync::Arc;
 
-/// DocumentDataParser is a struct for parsing a document's data and converting it to JSON, HTML, or text.
+/// DocumentDataParser is a struct for parsing a document's data and converting it to JSON, HTML, text or markdown.
 pub struct DocumentDataParser {
   /// The document data to parse.
   pub document_data: Arc<DocumentData>,
@@ -43,6 +45,19 @@ impl DocumentDataParser {
     }
   }
 
+  /// Converts the JSON to markdown (CommonMark with the GFM extensions).
+  pub fn to_markdown_with_json(&self, json: &Option<NestedBlock>) -> String {
+    let markdown = json
+      .as_ref()
+      .map(|json| json.convert_to_markdown(ConvertBlockToMarkdownParams { number: 0 }))
+      .unwrap_or_default();
+    if markdown.is_empty() {
+      markdown
+    } else {
+      format!("{}\n", markdown)
+    }
+  }
+
   /// Converts the document data to HTML.
   pub fn to_html(&self) -> String {
     let json = self.to_json();
@@ -55,6 +70,12 @@ impl DocumentDataParser {
     self.to_text_with_json(&json)
   }
 
+  /// Converts the document data to markdown.
+  pub fn to_markdown(&self) -> String {
+    let json = self.to_json();
+    self.to_markdown_with_json(&json)
+  }
+
   /// Converts the document data to a nested JSON structure, considering the optional range.
   pub fn to_json(&self) -> Option<NestedBlock> {
     let root_id = &self.document_data.page_id;
@@ -0,0 +1,972 @@
+use crate::parser::constant::*;
+use crate::parser::parser_entities::{InsertDelta, NestedBlock};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Parse markdown to nested block
+///
+/// Supports CommonMark and the GFM extensions for tables, task lists, strikethrough and
+/// autolinks. Constructs that have no matching block type, for example raw html, are kept as
+/// plain text.
+pub fn parse_markdown_to_nested_block(markdown: &str) -> Option<NestedBlock> {
+  let lines = markdown.lines().map(expand_tabs).collect::<Vec<String>>();
+  let children = parse_blocks(&lines);
+  if children.is_empty() {
+    return None;
+  }
+
+  Some(NestedBlock {
+    ty: PAGE.to_string(),
+    children,
+    ..Default::default()
+  })
+}
+
+fn parse_blocks(lines: &[String]) -> Vec<NestedBlock> {
+  let mut blocks = vec![];
+  let mut i = 0;
+  while i < lines.len() {
+    let line = lines[i].as_str();
+    if line.trim().is_empty() {
+      i += 1;
+      continue;
+    }
+
+    // indented code block: ····let a = 1;
+    if indent_of(line) >= 4 {
+      let mut code_lines = vec![];
+      while i < lines.len() && (lines[i].trim().is_empty() || indent_of(&lines[i]) >= 4) {
+        code_lines.push(dedent(&lines[i], 4));
+        i += 1;
+      }
+      while code_lines
+        .last()
+        .map_or(false, |line| line.trim().is_empty())
+      {
+        code_lines.pop();
+      }
+      blocks.push(code_block(code_lines.join("\n"), None));
+      continue;
+    }
+
+    let trimmed = line.trim();
+    // ```rust
+    if let Some((fence, language)) = parse_code_fence(trimmed) {
+      let fence_indent = indent_of(line);
+      let mut code_lines = vec![];
+      i += 1;
+      while i < lines.len() {
+        let closing = lines[i].trim();
+        if closing.starts_with(&fence)
+          && closing.chars().all(|c| c == fence.chars().next().unwrap())
+        {
+          i += 1;
+          break;
+        }
+        code_lines.push(dedent(&lines[i], fence_indent));
+        i += 1;
+      }
+      blocks.push(code_block(code_lines.join("\n"), language));
+      continue;
+    }
+
+    // $$ x^2 $$
+    if let Some(formula) = trimmed.strip_prefix("$$") {
+      let mut formula_lines = vec![];
+      match formula.strip_suffix("$$") {
+        Some(formula) => {
+          formula_lines.push(formula.to_string());
+          i += 1;
+        },
+        None => {
+          formula_lines.push(formula.to_string());
+          i += 1;
+          while i < lines.len() {
+            let line = lines[i].trim();
+            i += 1;
+            if let Some(line) = line.strip_suffix("$$") {
+              formula_lines.push(line.to_string());
+              break;
+            }
+            formula_lines.push(line.to_string());
+          }
+        },
+      }
+      let formula = formula_lines.join("\n").trim().to_string();
+      let mut data = HashMap::new();
+      data.insert(FORMULA.to_string(), Value::String(formula));
+      blocks.push(NestedBlock::new(MATH_EQUATION.to_string(), data, vec![]));
+      continue;
+    }
+
+    // # Heading
+    if let Some((level, text)) = parse_atx_heading(trimmed) {
+      blocks.push(heading_block(level, &text));
+      i += 1;
+      continue;
+    }
+
+    // ---
+    if is_thematic_break(trimmed) {
+      blocks.push(NestedBlock::new(
+        DIVIDER.to_string(),
+        HashMap::new(),
+        vec![],
+      ));
+      i += 1;
+      continue;
+    }
+
+    // > Quote
+    if is_quote_line(line) {
+      let mut quote_lines = vec![];
+      while i < lines.len() {
+        let line = lines[i].as_str();
+        if is_quote_line(line) {
+          quote_lines.push(strip_quote_marker(line));
+        } else if !line.trim().is_empty()
+          && quote_lines
+            .last()
+            .map_or(false, |last: &String| !last.trim().is_empty())
+          && !starts_block(line)
+        {
+          // lazy continuation of the paragraph inside the quote
+          quote_lines.push(line.to_string());
+        } else {
+          break;
+        }
+        i += 1;
+      }
+      blocks.push(quote_block(parse_blocks(&quote_lines)));
+      continue;
+    }
+
+    // - item, 1. item, - [ ] item
+    if let Some(marker) = parse_list_marker(line) {
+      let (block, next) = parse_list_item(lines, i, marker, blocks.last());
+      blocks.push(block);
+      i = next;
+      continue;
+    }
+
+    // ![image](https://appflowy.io/image.png)
+    if let Some(url) = parse_image_line(trimmed) {
+      let mut data = HashMap::new();
+      data.insert(URL.to_string(), Value::String(url));
+      blocks.push(NestedBlock::new(IMAGE.to_string(), data, vec![]));
+      i += 1;
+      continue;
+    }
+
+    // | a | b |
+    // | - | - |
+    if i + 1 < lines.len() && trimmed.contains('|') && is_table_delimiter_row(&lines[i + 1]) {
+      let mut rows = vec![split_table_row(trimmed)];
+      i += 2;
+      while i < lines.len() && !lines[i].trim().is_empty() && lines[i].contains('|') {
+        rows.push(split_table_row(lines[i].trim()));
+        i += 1;
+      }
+      blocks.push(table_block(rows));
+      continue;
+    }
+
+    // paragraph, or a setext heading if the paragraph is underlined with `===` or `---`
+    let mut paragraph_lines = vec![line];
+    i += 1;
+    let mut setext_level = None;
+    while i < lines.len() {
+      let line = lines[i].as_str();
+      let trimmed = line.trim();
+      if trimmed.is_empty() {
+        break;
+      }
+      if trimmed.chars().all(|c| c == '=') {
+        setext_level = Some(1);
+        i += 1;
+        break;
+      }
+      if trimmed.chars().all(|c| c == '-') {
+        setext_level = Some(2);
+        i += 1;
+        break;
+      }
+      if starts_block(line) {
+        break;
+      }
+      paragraph_lines.push(line);
+      i += 1;
+    }
+
+    let text = join_paragraph_lines(&paragraph_lines);
+    match setext_level {
+      Some(level) => blocks.push(heading_block(level, &text)),
+      None => blocks.push(text_block(PARAGRAPH, &text, HashMap::new(), vec![])),
+    }
+  }
+  blocks
+}
+
+struct ListMarker {
+  ty: &'static str,
+  /// The start number of an ordered list item.
+  number: Option<u64>,
+  /// The indent of the marker itself.
+  indent: usize,
+  /// The position where the content of the item starts.
+  content_start: usize,
+}
+
+/// Parse the list item starting at `start` and return the block with the index of the next line.
+fn parse_list_item(
+  lines: &[String],
+  start: usize,
+  marker: ListMarker,
+  prev_block: Option<&NestedBlock>,
+) -> (NestedBlock, usize) {
+  let mut ty = marker.ty;
+  let mut data = HashMap::new();
+
+  let mut first_line = &lines[start][marker.content_start..];
+  if ty == BULLETED_LIST {
+    if let Some((checked, rest)) = parse_task_marker(first_line) {
+      ty = TODO_LIST;
+      data.insert(CHECKED.to_string(), Value::Bool(checked));
+      first_line = rest;
+    }
+  }
+  let mut text_lines = vec![];
+  if !first_line.is_empty() {
+    text_lines.push(first_line.to_string());
+  }
+
+  // only the first item of an ordered list keeps its start number
+  if let Some(number) = marker.number {
+    let is_first_item = prev_block.map_or(true, |block| block.ty != NUMBERED_LIST);
+    if is_first_item && number != 1 {
+      data.insert(NUMBER.to_string(), Value::from(number));
+    }
+  }
+
+  let mut child_lines: Vec<String> = vec![];
+  let mut saw_blank_line = false;
+  let mut i = start + 1;
+  while i < lines.len() {
+    let line = lines[i].as_str();
+    if line.trim().is_empty() {
+      // blank lines only belong to the item if the item continues after them
+      let next_line = lines[i..].iter().find(|line| !line.trim().is_empty());
+      match next_line {
+        Some(next_line) if indent_of(next_line) > marker.indent => {
+          saw_blank_line = true;
+          child_lines.push(String::new());
+          i += 1;
+          continue;
+        },
+        _ => break,
+      }
+    }
+
+    let is_continuation = child_lines.is_empty() && !saw_blank_line;
+    if indent_of(line) > marker.indent {
+      if is_continuation && !starts_block(line.trim_start()) {
+        text_lines.push(line.to_string());
+      } else {
+        child_lines.push(line.to_string());
+      }
+    } else if is_continuation && !starts_block(line) {
+      text_lines.push(line.to_string());
+    } else {
+      break;
+    }
+    i += 1;
+  }
+
+  let child_indent = child_lines
+    .iter()
+    .filter(|line| !line.trim().is_empty())
+    .map(|line| indent_of(line))
+    .min()
+    .unwrap_or_default();
+  let child_lines = child_lines
+    .iter()
+    .map(|line| dedent(line, child_indent))
+    .collect::<Vec<String>>();
+
+  let text_lines = text_lines
+    .iter()
+    .map(|line| line.as_str())
+    .collect::<Vec<_>>();
+  let text = join_paragraph_lines(&text_lines);
+  let block = text_block(ty, &text, data, parse_blocks(&child_lines));
+  (block, i)
+}
+
+fn parse_list_marker(line: &str) -> Option<ListMarker> {
+  let indent = indent_of(line);
+  let rest = &line[indent..];
+  let first = rest.chars().next()?;
+  let (ty, number, marker_len) = if matches!(first, '-' | '*' | '+') {
+    (BULLETED_LIST, None, 1)
+  } else {
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 || digits > 9 {
+      return None;
+    }
+    match rest[digits..].chars().next() {
+      Some('.') | Some(')') => {},
+      _ => return None,
+    }
+    let number = rest[..digits].parse::<u64>().ok()?;
+    (NUMBERED_LIST, Some(number), digits + 1)
+  };
+
+  let after_marker = &rest[marker_len..];
+  if after_marker.is_empty() {
+    return Some(ListMarker {
+      ty,
+      number,
+      indent,
+      content_start: indent + marker_len,
+    });
+  }
+  if !after_marker.starts_with(' ') {
+    return None;
+  }
+  let spaces = after_marker.chars().take_while(|c| *c == ' ').count();
+  // more than four spaces means the content is an indented code block, only one space belongs
+  // to the marker in that case
+  let spaces = if spaces > 4 { 1 } else { spaces };
+  Some(ListMarker {
+    ty,
+    number,
+    indent,
+    content_start: indent + marker_len + spaces,
+  })
+}
+
+// [ ] todo, [x] done
+fn parse_task_marker(text: &str) -> Option<(bool, &str)> {
+  let checked = match text.get(..3)? {
+    "[ ]" => false,
+    "[x]" | "[X]" => true,
+    _ => return None,
+  };
+  let rest = &text[3..];
+  if rest.is_empty() {
+    return Some((checked, rest));
+  }
+  rest.strip_prefix(' ').map(|rest| (checked, rest))
+}
+
+fn parse_code_fence(line: &str) -> Option<(String, Option<String>)> {
+  let first = line.chars().next()?;
+  if first != '`' && first != '~' {
+    return None;
+  }
+  let len = line.chars().take_while(|c| *c == first).count();
+  if len < 3 {
+    return None;
+  }
+  let info = line[len..].trim();
+  if first == '`' && info.contains('`') {
+    return None;
+  }
+  let language = info
+    .split_whitespace()
+    .next()
+    .map(|language| language.to_string());
+  Some((first.to_string().repeat(len), language))
+}
+
+fn parse_atx_heading(line: &str) -> Option<(usize, String)> {
+  let level = line.chars().take_while(|c| *c == '#').count();
+  if level == 0 || level > 6 {
+    return None;
+  }
+  let rest = &line[level..];
+  if !rest.is_empty() && !rest.starts_with(' ') {
+    return None;
+  }
+  // the closing sequence is optional: ## Heading ##
+  let mut text = rest.trim();
+  let without_closing = text.trim_end_matches('#');
+  if without_closing.is_empty() || without_closing.ends_with(' ') {
+    text = without_closing.trim_end();
+  }
+  Some((level, text.to_string()))
+}
+
+fn is_thematic_break(line: &str) -> bool {
+  let chars = line
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .collect::<Vec<char>>();
+  chars.len() >= 3 && matches!(chars[0], '-' | '*' | '_') && chars.iter().all(|c| *c == chars[0])
+}
+
+fn is_quote_line(line: &str) -> bool {
+  indent_of(line) < 4 && line.trim_start().starts_with('>')
+}
+
+fn strip_quote_marker(line: &str) -> String {
+  let line = line.trim_start();
+  let line = line.strip_prefix('>').unwrap_or(line);
+  line.strip_prefix(' ').unwrap_or(line).to_string()
+}
+
+fn parse_image_line(line: &str) -> Option<String> {
+  let chars = line.chars().collect::<Vec<char>>();
+  if chars.first() != Some(&'!') {
+    return None;
+  }
+  let link = parse_link(&chars, 1)?;
+  if link.end == chars.len() {
+    Some(link.url)
+  } else {
+    None
+  }
+}
+
+fn is_table_delimiter_row(line: &str) -> bool {
+  let line = line.trim();
+  if !line.contains('-') {
+    return false;
+  }
+  let cells = split_table_row(line);
+  !cells.is_empty()
+    && cells.iter().all(|cell| {
+      let cell = cell.trim().trim_start_matches(':').trim_end_matches(':');
+      !cell.is_empty() && cell.chars().all(|c| c == '-')
+    })
+}
+
+/// Split a table row into cells, the pipes inside code spans or escaped with a backslash don't
+/// separate cells.
+fn split_table_row(line: &str) -> Vec<String> {
+  let line = line.trim();
+  let line = line.strip_prefix('|').unwrap_or(line);
+  let line = if line.ends_with('|') && !line.ends_with("\\|") {
+    &line[..line.len() - 1]
+  } else {
+    line
+  };
+
+  let mut cells = vec![];
+  let mut cell = String::new();
+  let mut in_code = false;
+  let mut chars = line.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' if chars.peek() == Some(&'|') => {
+        cell.push('|');
+        chars.next();
+      },
+      '`' => {
+        in_code = !in_code;
+        cell.push(c);
+      },
+      '|' if !in_code => cells.push(std::mem::take(&mut cell).trim().to_string()),
+      _ => cell.push(c),
+    }
+  }
+  cells.push(cell.trim().to_string());
+  cells
+}
+
+fn starts_block(line: &str) -> bool {
+  let trimmed = line.trim();
+  indent_of(line) < 4
+    && (parse_atx_heading(trimmed).is_some()
+      || parse_code_fence(trimmed).is_some()
+      || is_thematic_break(trimmed)
+      || is_quote_line(line)
+      || trimmed.starts_with("$$")
+      || parse_list_marker(line).map_or(false, |marker| {
+        // an empty list item can't interrupt a paragraph
+        marker.content_start < line.len()
+      }))
+}
+
+/// Join the lines of a paragraph. A line ending with a backslash or with two spaces is a hard
+/// line break, other line endings are turned into a space.
+fn join_paragraph_lines(lines: &[&str]) -> String {
+  let mut text = String::new();
+  for (index, line) in lines.iter().enumerate() {
+    let line = line.trim_start();
+    if index == lines.len() - 1 {
+      text.push_str(line.trim_end());
+    } else if let Some(line) = line.strip_suffix('\\') {
+      text.push_str(line);
+      text.push('\n');
+    } else if line.ends_with("  ") {
+      text.push_str(line.trim_end());
+      text.push('\n');
+    } else {
+      text.push_str(line.trim_end());
+      text.push(' ');
+    }
+  }
+  text
+}
+
+fn text_block(
+  ty: &str,
+  text: &str,
+  mut data: HashMap<String, Value>,
+  children: Vec<NestedBlock>,
+) -> NestedBlock {
+  data.insert(DELTA.to_string(), delta_to_json(&parse_inline(text)));
+  NestedBlock::new(ty.to_string(), data, children)
+}
+
+fn heading_block(level: usize, text: &str) -> NestedBlock {
+  let mut data = HashMap::new();
+  data.insert(LEVEL.to_string(), Value::from(level));
+  text_block(HEADING, text, data, vec![])
+}
+
+fn code_block(code: String, language: Option<String>) -> NestedBlock {
+  let mut data = HashMap::new();
+  if let Some(language) = language {
+    data.insert(LANGUAGE.to_string(), Value::String(language));
+  }
+  let delta = vec![InsertDelta {
+    insert: code,
+    attributes: None,
+  }];
+  data.insert(DELTA.to_string(), delta_to_json(&delta));
+  NestedBlock::new(CODE.to_string(), data, vec![])
+}
+
+// The first paragraph of the quote is the text of the quote block, the rest are its children.
+fn quote_block(mut children: Vec<NestedBlock>) -> NestedBlock {
+  let mut data = HashMap::new();
+  if children
+    .first()
+    .map_or(false, |child| child.ty == PARAGRAPH)
+  {
+    let first_child = children.remove(0);
+    if let Some(delta) = first_child.data.get(DELTA) {
+      data.insert(DELTA.to_string(), delta.clone());
+    }
+  } else {
+    data.insert(DELTA.to_string(), delta_to_json(&[]));
+  }
+  NestedBlock::new(QUOTE.to_string(), data, children)
+}
+
+// The cells of a table are ordered by column and each cell contains a paragraph.
+fn table_block(rows: Vec<Vec<String>>) -> NestedBlock {
+  let rows_len = rows.len();
+  let cols_len = rows[0].len();
+  let mut children = vec![];
+  for col in 0..cols_len {
+    for (row, cells) in rows.iter().enumerate() {
+      let text = cells.get(col).map(|cell| cell.as_str()).unwrap_or_default();
+      let mut data = HashMap::new();
+      data.insert(ROW_POSITION.to_string(), Value::from(row));
+      data.insert(COL_POSITION.to_string(), Value::from(col));
+      let paragraph = text_block(PARAGRAPH, text, HashMap::new(), vec![]);
+      children.push(NestedBlock::new(
+        TABLE_CELL.to_string(),
+        data,
+        vec![paragraph],
+      ));
+    }
+  }
+
+  let mut data = HashMap::new();
+  data.insert(ROWS_LEN.to_string(), Value::from(rows_len));
+  data.insert(COLS_LEN.to_string(), Value::from(cols_len));
+  NestedBlock::new(TABLE.to_string(), data, children)
+}
+
+/// Parse the inline elements of markdown text to delta.
+///
+/// Example:
+/// - input: **Hello** [AppFlowy](https://appflowy.io)
+/// - output: [{ "insert": "Hello", "attributes": { "bold": true } }, { "insert": " " }, { "insert": "AppFlowy", "attributes": { "href": "https://appflowy.io" } }]
+fn parse_inline(text: &str) -> Vec<InsertDelta> {
+  let chars = text.chars().collect::<Vec<char>>();
+  let mut delta = vec![];
+  parse_inline_chars(&chars, &HashMap::new(), &mut delta);
+  delta
+}
+
+fn parse_inline_chars(
+  chars: &[char],
+  attributes: &HashMap<String, Value>,
+  delta: &mut Vec<InsertDelta>,
+) {
+  let mut text = String::new();
+  let mut i = 0;
+  while i < chars.len() {
+    let c = chars[i];
+    match c {
+      '\\' if i + 1 < chars.len() && chars[i + 1].is_ascii_punctuation() => {
+        text.push(chars[i + 1]);
+        i += 2;
+      },
+      '`' => {
+        let run = run_length(chars, i, '`');
+        match find_code_span_end(chars, i + run, run) {
+          Some(end) => {
+            push_delta(delta, std::mem::take(&mut text), attributes);
+            let code = chars[i + run..end].iter().collect::<String>();
+            let code = match code.strip_prefix(' ').and_then(|s| s.strip_suffix(' ')) {
+              Some(inner) if !inner.trim().is_empty() => inner.to_string(),
+              _ => code,
+            };
+            push_delta(
+              delta,
+              code,
+              &with_attribute(attributes, CODE, Value::Bool(true)),
+            );
+            i = end + run;
+          },
+          None => {
+            text.extend(&chars[i..i + run]);
+            i += run;
+          },
+        }
+      },
+      '!' | '[' => {
+        let start = if c == '!' { i + 1 } else { i };
+        match chars
+          .get(start)
+          .filter(|c| **c == '[')
+          .and_then(|_| parse_link(chars, start))
+        {
+          Some(link) => {
+            push_delta(delta, std::mem::take(&mut text), attributes);
+            let attributes = with_attribute(attributes, HREF, Value::String(link.url));
+            let link_text = &chars[link.text_start..link.text_end];
+            if link_text.is_empty() {
+              // use the url as the text of an image without alt text
+              let url = attributes[HREF].as_str().unwrap_or_default().to_string();
+              push_delta(delta, url, &attributes);
+            } else {
+              parse_inline_chars(link_text, &attributes, delta);
+            }
+            i = link.end;
+          },
+          None => {
+            text.push(c);
+            i += 1;
+          },
+        }
+      },
+      '<' => match parse_autolink(chars, i) {
+        Some((url, end)) => {
+          push_delta(delta, std::mem::take(&mut text), attributes);
+          let link_attributes = with_attribute(attributes, HREF, Value::String(url.clone()));
+          push_delta(delta, url, &link_attributes);
+          i = end;
+        },
+        None => {
+          text.push(c);
+          i += 1;
+        },
+      },
+      'h' if (i == 0 || !chars[i - 1].is_alphanumeric()) && !attributes.contains_key(HREF) => {
+        match parse_bare_url(chars, i) {
+          Some(end) => {
+            push_delta(delta, std::mem::take(&mut text), attributes);
+            let url = chars[i..end].iter().collect::<String>();
+            let link_attributes = with_attribute(attributes, HREF, Value::String(url.clone()));
+            push_delta(delta, url, &link_attributes);
+            i = end;
+          },
+          None => {
+            text.push(c);
+            i += 1;
+          },
+        }
+      },
+      '*' | '_' | '~' => {
+        let run = run_length(chars, i, c);
+        let closing = if is_valid_emphasis_run(c, run) && can_open(chars, i, run, c) {
+          find_emphasis_end(chars, i + run, c, run)
+        } else {
+          None
+        };
+        match closing {
+          Some(end) => {
+            push_delta(delta, std::mem::take(&mut text), attributes);
+            let attributes = emphasis_attributes(attributes, c, run);
+            parse_inline_chars(&chars[i + run..end], &attributes, delta);
+            i = end + run;
+          },
+          None => {
+            text.extend(&chars[i..i + run]);
+            i += run;
+          },
+        }
+      },
+      _ => {
+        text.push(c);
+        i += 1;
+      },
+    }
+  }
+  push_delta(delta, text, attributes);
+}
+
+struct Link {
+  text_start: usize,
+  text_end: usize,
+  url: String,
+  end: usize,
+}
+
+// [text](url "title"), the `start` is the index of the `[`
+fn parse_link(chars: &[char], start: usize) -> Option<Link> {
+  let mut depth = 0;
+  let mut i = start;
+  let text_end = loop {
+    match *chars.get(i)? {
+      '\\' => i += 1,
+      '`' => {
+        let run = run_length(chars, i, '`');
+        if let Some(end) = find_code_span_end(chars, i + run, run) {
+          i = end + run - 1;
+        }
+      },
+      '[' => depth += 1,
+      ']' => {
+        depth -= 1;
+        if depth == 0 {
+          break i;
+        }
+      },
+      _ => {},
+    }
+    i += 1;
+  };
+
+  if chars.get(text_end + 1) != Some(&'(') {
+    return None;
+  }
+  let mut depth = 0;
+  let mut i = text_end + 1;
+  let destination_end = loop {
+    match *chars.get(i)? {
+      '\\' => i += 1,
+      '(' => depth += 1,
+      ')' => {
+        depth -= 1;
+        if depth == 0 {
+          break i;
+        }
+      },
+      _ => {},
+    }
+    i += 1;
+  };
+
+  let destination = chars[text_end + 2..destination_end]
+    .iter()
+    .collect::<String>();
+  let destination = destination.trim();
+  let url = if let Some(rest) = destination.strip_prefix('<') {
+    rest.split('>').next().unwrap_or_default()
+  } else {
+    // drop the optional title
+    destination.split_whitespace().next().unwrap_or_default()
+  };
+  Some(Link {
+    text_start: start + 1,
+    text_end,
+    url: url.to_string(),
+    end: destination_end + 1,
+  })
+}
+
+// <https://appflowy.io>
+fn parse_autolink(chars: &[char], start: usize) -> Option<(String, usize)> {
+  let len = chars[start + 1..].iter().position(|c| *c == '>')?;
+  let url = chars[start + 1..start + 1 + len].iter().collect::<String>();
+  if url.contains(char::is_whitespace) || !(url.contains("://") || url.starts_with("mailto:")) {
+    return None;
+  }
+  Some((url, start + len + 2))
+}
+
+// https://appflowy.io
+fn parse_bare_url(chars: &[char], start: usize) -> Option<usize> {
+  let rest = chars[start..].iter().take(8).collect::<String>();
+  if !rest.starts_with("http://") && !rest.starts_with("https://") {
+    return None;
+  }
+  let mut end = start;
+  while end < chars.len() && !chars[end].is_whitespace() && chars[end] != '<' {
+    end += 1;
+  }
+  // trailing punctuation isn't part of the url
+  while end > start
+    && matches!(
+      chars[end - 1],
+      '.' | ',' | ':' | ';' | '!' | '?' | ')' | '*'
+    )
+  {
+    end -= 1;
+  }
+  let scheme_len = if rest.starts_with("https") { 8 } else { 7 };
+  if end <= start + scheme_len {
+    return None;
+  }
+  Some(end)
+}
+
+fn run_length(chars: &[char], start: usize, c: char) -> usize {
+  chars[start..].iter().take_while(|ch| **ch == c).count()
+}
+
+fn find_code_span_end(chars: &[char], start: usize, run: usize) -> Option<usize> {
+  let mut i = start;
+  while i < chars.len() {
+    if chars[i] == '`' {
+      let len = run_length(chars, i, '`');
+      if len == run {
+        return Some(i);
+      }
+      i += len;
+    } else {
+      i += 1;
+    }
+  }
+  None
+}
+
+fn is_valid_emphasis_run(c: char, run: usize) -> bool {
+  match c {
+    '~' => run <= 2,
+    _ => run <= 3,
+  }
+}
+
+fn can_open(chars: &[char], start: usize, run: usize, c: char) -> bool {
+  let next = chars.get(start + run);
+  let prev = start.checked_sub(1).map(|i| chars[i]);
+  let next_is_text = next.map_or(false, |next| !next.is_whitespace());
+  if c == '_' {
+    next_is_text && !prev.map_or(false, |prev| prev.is_alphanumeric())
+  } else {
+    next_is_text
+  }
+}
+
+fn can_close(chars: &[char], start: usize, run: usize, c: char) -> bool {
+  let next = chars.get(start + run);
+  let prev = start.checked_sub(1).map(|i| chars[i]);
+  let prev_is_text = prev.map_or(false, |prev| !prev.is_whitespace());
+  if c == '_' {
+    prev_is_text && !next.map_or(false, |next| next.is_alphanumeric())
+  } else {
+    prev_is_text
+  }
+}
+
+/// Find the delimiter run that closes the emphasis opened with `len` `c` characters. Returns
+/// the index of the closing delimiter. Nested emphasis with the same character is skipped.
+fn find_emphasis_end(chars: &[char], start: usize, c: char, len: usize) -> Option<usize> {
+  let mut i = start;
+  while i < chars.len() {
+    match chars[i] {
+      '\\' => i += 2,
+      '`' => {
+        let run = run_length(chars, i, '`');
+        i = match find_code_span_end(chars, i + run, run) {
+          Some(end) => end + run,
+          None => i + run,
+        };
+      },
+      ch if ch == c => {
+        let run = run_length(chars, i, c);
+        if i > start && run >= len && can_close(chars, i, run, c) {
+          // the rest of a longer run closes the outer emphasis: **bold *italic***
+          return Some(i);
+        }
+        if run != len && can_open(chars, i, run, c) {
+          if let Some(end) = find_emphasis_end(chars, i + run, c, run) {
+            i = end + run;
+            continue;
+          }
+        }
+        i += run;
+      },
+      _ => i += 1,
+    }
+  }
+  None
+}
+
+fn emphasis_attributes(
+  attributes: &HashMap<String, Value>,
+  c: char,
+  run: usize,
+) -> HashMap<String, Value> {
+  if c == '~' {
+    return with_attribute(attributes, STRIKETHROUGH, Value::Bool(true));
+  }
+  match run {
+    1 => with_attribute(attributes, ITALIC, Value::Bool(true)),
+    2 => with_attribute(attributes, BOLD, Value::Bool(true)),
+    _ => {
+      let attributes = with_attribute(attributes, BOLD, Value::Bool(true));
+      with_attribute(&attributes, ITALIC, Value::Bool(true))
+    },
+  }
+}
+
+fn with_attribute(
+  attributes: &HashMap<String, Value>,
+  key: &str,
+  value: Value,
+) -> HashMap<String, Value> {
+  let mut attributes = attributes.clone();
+  attributes.insert(key.to_string(), value);
+  attributes
+}
+
+// push the text to the delta, merging it into the last insert if the attributes are the same
+fn push_delta(delta: &mut Vec<InsertDelta>, text: String, attributes: &HashMap<String, Value>) {
+  if text.is_empty() {
+    return;
+  }
+  let attributes = if attributes.is_empty() {
+    None
+  } else {
+    Some(attributes.clone())
+  };
+  match delta.last_mut() {
+    Some(last) if last.attributes == attributes => last.insert.push_str(&text),
+    _ => delta.push(InsertDelta {
+      insert: text,
+      attributes,
+    }),
+  }
+}
+
+fn indent_of(line: &str) -> usize {
+  line.chars().take_while(|c| *c == ' ').count()
+}
+
+// remove at most `len` leading spaces
+fn dedent(line: &str, len: usize) -> String {
+  let indent = indent_of(line).min(len);
+  line[indent..].to_string()
+}
+
+fn expand_tabs(line: &str) -> String {
+  let indent = line
+    .chars()
+    .take_while(|c| *c == ' ' || *c == '\t')
+    .collect::<String>();
+  format!("{}{}", indent.replace('\t', "    "), &line[indent.len()..])
+}
+
+fn delta_to_json(delta: &[InsertDelta]) -> Value {
+  serde_json::to_value(delta).unwrap_or_default()
+}
@@ -1,2 +1,3 @@
+mod markdown;
 pub mod parser;
 mod utils;
@@ -1,8 +1,10 @@
 use crate::parse::NotEmptyStr;
 use crate::parser::constant::*;
 use crate::parser::utils::{
-  convert_insert_delta_from_json, convert_nested_block_children_to_html, delta_to_html,
-  delta_to_text, required_not_empty_str, serialize_color_attribute,
+  convert_insert_delta_from_json, convert_nested_block_children_to_html,
+  convert_nested_block_children_to_markdown, convert_table_to_markdown, delta_to_html,
+  delta_to_markdown, delta_to_text, escape_markdown, escape_markdown_block_start, prefix_lines,
+  required_not_empty_str, serialize_color_attribute,
 };
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
@@ -38,6 +40,7 @@ pub struct RangePB {
  * @field json: bool // export json data
  * @field html: bool // export html data
  * @field text: bool // export text data
+ * @field markdown: bool // export markdown data
  */
 #[derive(Default, ProtoBuf, Debug, Clone)]
 pub struct ParseTypePB {
@@ -49,6 +52,9 @@ pub struct ParseTypePB {
 
   #[pb(index = 3)]
   pub text: bool,
+
+  #[pb(index = 4)]
+  pub markdown: bool,
 }
 /**
 * ConvertDocumentPayloadPB
@@ -76,6 +82,8 @@ pub struct ConvertDocumentResponsePB {
   pub html: Option<String>,
   #[pb(index = 3, one_of)]
   pub text: Option<String>,
+  #[pb(index = 4, one_of)]
+  pub markdown: Option<String>,
 }
 
 pub struct Selection {
@@ -93,6 +101,7 @@ pub struct ParseType {
   pub json: bool,
   pub html: bool,
   pub text: bool,
+  pub markdown: bool,
 }
 
 pub struct ConvertDocumentParams {
@@ -103,7 +112,7 @@ pub struct ConvertDocumentParams {
 
 impl ParseType {
   pub fn any_enabled(&self) -> bool {
-    self.json || self.html || self.text
+    self.json || self.html || self.text || self.markdown
   }
 }
 
@@ -132,6 +141,7 @@ impl From<ParseTypePB> for ParseType {
       json: data.json,
       html: data.html,
       text: data.text,
+      markdown: data.markdown,
     }
   }
 }
@@ -243,6 +253,58 @@ impl InsertDelta {
     }
     html
   }
+
+  pub fn to_markdown(&self) -> String {
+    let content = self.insert.trim();
+    if content.is_empty() {
+      return self.insert.clone();
+    }
+    let attrs = self.attributes.clone().unwrap_or_default();
+    let is_enabled = |key: &str| attrs.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+
+    // Serialize the code attribute, the content of a code span isn't escaped.
+    let mut markdown = if is_enabled(CODE) {
+      let backticks = "`".repeat(longest_run(content, '`') + 1);
+      if content.starts_with('`') || content.ends_with('`') {
+        format!("{} {} {}", backticks, content, backticks)
+      } else {
+        format!("{}{}{}", backticks, content, backticks)
+      }
+    } else {
+      escape_markdown(content)
+    };
+    // Serialize the strikethrough, italic, bold and href attributes.
+    if is_enabled(STRIKETHROUGH) {
+      markdown = format!("~~{}~~", markdown);
+    }
+    if is_enabled(ITALIC) {
+      markdown = format!("*{}*", markdown);
+    }
+    if is_enabled(BOLD) {
+      markdown = format!("**{}**", markdown);
+    }
+    if let Some(href) = attrs.get(HREF).and_then(|v| v.as_str()) {
+      if markdown == href {
+        markdown = format!("<{}>", href);
+      } else if href.contains(char::is_whitespace) {
+        markdown = format!("[{}](<{}>)", markdown, href);
+      } else {
+        markdown = format!("[{}]({})", markdown, href);
+      }
+    }
+
+    // Keep the surrounding whitespace outside of the markers, `** Hello**` isn't bold.
+    let leading = &self.insert[..self.insert.len() - self.insert.trim_start().len()];
+    let trailing = &self.insert[self.insert.trim_end().len()..];
+    format!("{}{}{}", leading, markdown, trailing)
+  }
+}
+
+fn longest_run(s: &str, c: char) -> usize {
+  s.split(|ch| ch != c)
+    .map(|run| run.len())
+    .max()
+    .unwrap_or_default()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -491,6 +553,242 @@ impl NestedBlock {
     };
     text
   }
+
+  pub fn convert_to_markdown(&self, params: ConvertBlockToMarkdownParams) -> String {
+    let text_markdown = self
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+      .map(|delta| delta_to_markdown(&delta))
+      .unwrap_or_default();
+    let children_markdown = convert_nested_block_children_to_markdown(self);
+
+    match self.ty.as_str() {
+      // ## Hello
+      HEADING => {
+        let level = self
+          .data
+          .get(LEVEL)
+          .and_then(|v| v.as_u64())
+          .unwrap_or(1)
+          .clamp(1, 6);
+        format!("{} {}", "#".repeat(level as usize), text_markdown)
+      },
+      // > 😁 Hello
+      CALLOUT => {
+        let icon = self
+          .data
+          .get(ICON)
+          .and_then(|v| v.as_str())
+          .unwrap_or_default();
+        let callout = format!("{} {}", icon, text_markdown);
+        prefix_lines(&join_markdown(callout.trim(), &children_markdown), "> ")
+      },
+      // ![](https://www.google.com/images/branding/googlelogo/2x/googlelogo_color_272x92dp.png)
+      IMAGE => {
+        let url = self
+          .data
+          .get(URL)
+          .and_then(|v| v.as_str())
+          .unwrap_or_default();
+        format!("![]({})", url)
+      },
+      // ---
+      DIVIDER => "---".to_string(),
+      // $$
+      // x = {-b \pm \sqrt{b^2-4ac} \over 2a}.
+      // $$
+      MATH_EQUATION => {
+        let formula = self
+          .data
+          .get(FORMULA)
+          .and_then(|v| v.as_str())
+          .unwrap_or_default();
+        format!("$$\n{}\n$$", formula)
+      },
+      // ```js
+      // console.log('Hello World!');
+      // ```
+      CODE => {
+        let language = self
+          .data
+          .get(LANGUAGE)
+          .and_then(|v| v.as_str())
+          .unwrap_or_default();
+        let code = self
+          .data
+          .get(DELTA)
+          .and_then(convert_insert_delta_from_json)
+          .map(|delta| delta_to_text(&delta))
+          .unwrap_or_default();
+        let fence = "`".repeat(std::cmp::max(3, longest_run(&code, '`') + 1));
+        format!("{}{}\n{}\n{}", fence, language, code, fence)
+      },
+      // - Hello
+      BULLETED_LIST | TOGGLE_LIST => {
+        self.list_item_to_markdown("- ", &text_markdown, &children_markdown)
+      },
+      // 1. Hello
+      NUMBERED_LIST => self.list_item_to_markdown(
+        &format!("{}. ", params.number),
+        &text_markdown,
+        &children_markdown,
+      ),
+      // - [x] Hello
+      TODO_LIST => {
+        let checked = self
+          .data
+          .get(CHECKED)
+          .and_then(|v| v.as_bool())
+          .unwrap_or_default();
+        let marker = if checked { "- [x] " } else { "- [ ] " };
+        self.list_item_to_markdown(marker, &text_markdown, &children_markdown)
+      },
+      // > Hello
+      QUOTE => prefix_lines(&join_markdown(&text_markdown, &children_markdown), "> "),
+      // | Hello | World! |
+      // | --- | --- |
+      TABLE => convert_table_to_markdown(self),
+      _ => join_markdown(
+        &escape_markdown_block_start(&text_markdown),
+        &children_markdown,
+      ),
+    }
+  }
+
+  /// Returns the links of the block and its children: the urls of the images and the hrefs of
+  /// the text.
+  pub fn links(&self) -> Vec<String> {
+    let mut links = vec![];
+    if self.ty == IMAGE {
+      if let Some(url) = self.data.get(URL).and_then(|v| v.as_str()) {
+        links.push(url.to_string());
+      }
+    }
+    if let Some(delta) = self
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+    {
+      links.extend(delta.iter().filter_map(|insert| {
+        let href = insert.attributes.as_ref()?.get(HREF)?.as_str()?;
+        Some(href.to_string())
+      }));
+    }
+    for child in &self.children {
+      links.extend(child.links());
+    }
+    links
+  }
+
+  /// Replaces the links of the block and its children, see [NestedBlock::links]. A text link is
+  /// replaced by a mention of the page if `f` returns [LinkReplacement::PageMention]. An image
+  /// can't be a mention, so its url is only replaced by [LinkReplacement::Url].
+  pub fn replace_links<F>(&mut self, f: &F)
+  where
+    F: Fn(&str) -> Option<LinkReplacement>,
+  {
+    if self.ty == IMAGE {
+      let url = self.data.get(URL).and_then(|v| v.as_str()).map(f);
+      if let Some(Some(LinkReplacement::Url(url))) = url {
+        self.data.insert(URL.to_string(), Value::String(url));
+      }
+    }
+
+    if let Some(delta) = self
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+    {
+      let delta = delta
+        .into_iter()
+        .map(|mut insert| {
+          let href = insert
+            .attributes
+            .as_ref()
+            .and_then(|attrs| attrs.get(HREF))
+            .and_then(|v| v.as_str());
+          match href.and_then(f) {
+            Some(LinkReplacement::PageMention(page_id)) => page_mention(page_id),
+            Some(LinkReplacement::Url(url)) => {
+              if let Some(attrs) = insert.attributes.as_mut() {
+                attrs.insert(HREF.to_string(), Value::String(url));
+              }
+              insert
+            },
+            None => insert,
+          }
+        })
+        .collect::<Vec<InsertDelta>>();
+      if let Ok(delta) = serde_json::to_value(delta) {
+        self.data.insert(DELTA.to_string(), delta);
+      }
+    }
+
+    for child in self.children.iter_mut() {
+      child.replace_links(f);
+    }
+  }
+
+  // The children of a list item are indented to the start of its content.
+  fn list_item_to_markdown(
+    &self,
+    marker: &str,
+    text_markdown: &str,
+    children_markdown: &str,
+  ) -> String {
+    let mut markdown = format!("{}{}", marker, text_markdown);
+    if !children_markdown.is_empty() {
+      let first_child_is_list = self
+        .children
+        .first()
+        .map(|child| matches!(child.ty.as_str(), BULLETED_LIST | NUMBERED_LIST | TODO_LIST))
+        .unwrap_or_default();
+      markdown.push_str(if first_child_is_list { "\n" } else { "\n\n" });
+      markdown.push_str(children_markdown);
+    }
+    let indent = " ".repeat(marker.chars().count());
+    let mut lines = markdown.lines();
+    let first_line = lines.next().unwrap_or_default().to_string();
+    lines.fold(first_line, |mut markdown, line| {
+      markdown.push('\n');
+      if !line.is_empty() {
+        markdown.push_str(&indent);
+        markdown.push_str(line);
+      }
+      markdown
+    })
+  }
+}
+
+/// The replacement of a link of a block, see [NestedBlock::replace_links].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkReplacement {
+  /// Replaces the link with a mention of the page with the given id.
+  PageMention(String),
+  Url(String),
+}
+
+// A page mention is displayed as the name of the page, the `$` is only a placeholder.
+fn page_mention(page_id: String) -> InsertDelta {
+  let mut mention = serde_json::Map::new();
+  mention.insert(MENTION_TYPE.to_string(), Value::String(PAGE.to_string()));
+  mention.insert(PAGE_ID.to_string(), Value::String(page_id));
+  let mut attributes = HashMap::new();
+  attributes.insert(MENTION.to_string(), Value::Object(mention));
+  InsertDelta {
+    insert: "$".to_string(),
+    attributes: Some(attributes),
+  }
+}
+
+// Join the text of a block and the markdown of its children with a blank line.
+fn join_markdown(text: &str, children: &str) -> String {
+  match (text.is_empty(), children.is_empty()) {
+    (true, _) => children.to_string(),
+    (false, true) => text.to_string(),
+    (false, false) => format!("{}\n\n{}", text, children),
+  }
 }
 
 pub struct ConvertBlockToHtmlParams {
@@ -498,11 +796,17 @@ pub struct ConvertBlockToHtmlParams {
   pub next_block_ty: Option<String>,
 }
 
+pub struct ConvertBlockToMarkdownParams {
+  /// The number of the item if the block is a numbered list.
+  pub number: u64,
+}
+
 #[derive(PartialEq, Eq, Debug, ProtoBuf_Enum, Clone, Default)]
 pub enum InputType {
   #[default]
   Html = 0,
   PlainText = 1,
+  Markdown = 2,
 }
 
 #[derive(Default, ProtoBuf, Debug, Validate)]
@@ -1,5 +1,6 @@
+use crate::parser::constant::*;
 use crate::parser::parser_entities::{
-  ConvertBlockToHtmlParams, InsertDelta, NestedBlock, Selection,
+  ConvertBlockToHtmlParams, ConvertBlockToMarkdownParams, InsertDelta, NestedBlock, Selection,
 };
 use collab_document::blocks::DocumentData;
 use serde_json::Value;
@@ -99,6 +100,140 @@ pub fn convert_nested_block_children_to_html(block: Arc<NestedBlock>) -> String
   html
 }
 
+/// Converts the delta to markdown, the line breaks in the delta are written as hard line breaks.
+pub fn delta_to_markdown(delta: &Vec<InsertDelta>) -> String {
+  let mut result = String::new();
+  for d in delta {
+    result.push_str(d.to_markdown().as_str());
+  }
+  result.replace('\n', "\\\n")
+}
+
+pub fn convert_nested_block_children_to_markdown(block: &NestedBlock) -> String {
+  let mut markdown = String::new();
+  let mut prev_block_ty: Option<&str> = None;
+  let mut number = 0;
+
+  for child in &block.children {
+    if child.ty == NUMBERED_LIST {
+      number = match prev_block_ty {
+        Some(NUMBERED_LIST) => number + 1,
+        _ => child.data.get(NUMBER).and_then(|v| v.as_u64()).unwrap_or(1),
+      };
+    }
+
+    let child_markdown = child.convert_to_markdown(ConvertBlockToMarkdownParams { number });
+    // skip the empty paragraphs
+    if child_markdown.is_empty() {
+      continue;
+    }
+
+    // The items of the same list are separated by a line break, other blocks by a blank line.
+    if let Some(prev_block_ty) = prev_block_ty {
+      let is_same_list = prev_block_ty == child.ty
+        && matches!(
+          prev_block_ty,
+          BULLETED_LIST | NUMBERED_LIST | TODO_LIST | TOGGLE_LIST
+        );
+      markdown.push_str(if is_same_list { "\n" } else { "\n\n" });
+    }
+    markdown.push_str(&child_markdown);
+    prev_block_ty = Some(child.ty.as_str());
+  }
+  markdown
+}
+
+/// Converts the table block to a GFM table, the first row of the table is the header.
+pub fn convert_table_to_markdown(block: &NestedBlock) -> String {
+  let position = |block: &NestedBlock, key: &str| {
+    block
+      .data
+      .get(key)
+      .and_then(|v| v.as_u64())
+      .unwrap_or_default() as usize
+  };
+  let cells = block
+    .children
+    .iter()
+    .filter(|child| child.ty == TABLE_CELL)
+    .collect::<Vec<&NestedBlock>>();
+  let rows_len = cells
+    .iter()
+    .map(|cell| position(cell, ROW_POSITION) + 1)
+    .max()
+    .unwrap_or_default()
+    .max(position(block, ROWS_LEN));
+  let cols_len = cells
+    .iter()
+    .map(|cell| position(cell, COL_POSITION) + 1)
+    .max()
+    .unwrap_or_default()
+    .max(position(block, COLS_LEN));
+  if rows_len == 0 || cols_len == 0 {
+    return String::new();
+  }
+
+  let mut rows = vec![vec![String::new(); cols_len]; rows_len];
+  for cell in cells {
+    let text = cell
+      .children
+      .iter()
+      .filter_map(|child| child.data.get(DELTA))
+      .filter_map(convert_insert_delta_from_json)
+      .map(|delta| delta_to_markdown(&delta))
+      .collect::<Vec<String>>()
+      .join(" ");
+    rows[position(cell, ROW_POSITION)][position(cell, COL_POSITION)] =
+      text.replace("\\\n", " ").replace('|', "\\|");
+  }
+
+  let mut lines = rows
+    .iter()
+    .map(|row| format!("| {} |", row.join(" | ")))
+    .collect::<Vec<String>>();
+  lines.insert(1, format!("|{}", " --- |".repeat(cols_len)));
+  lines.join("\n")
+}
+
+/// Escapes the characters that would be parsed as inline markdown.
+pub fn escape_markdown(text: &str) -> String {
+  let mut result = String::new();
+  for c in text.chars() {
+    if matches!(c, '\\' | '`' | '*' | '_' | '~' | '[' | ']') {
+      result.push('\\');
+    }
+    result.push(c);
+  }
+  result
+}
+
+/// Escapes the start of a paragraph that would be parsed as a heading, quote or list.
+pub fn escape_markdown_block_start(text: &str) -> String {
+  if text.starts_with(['#', '>', '-', '+']) {
+    return format!("\\{}", text);
+  }
+  let digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+  if digits > 0 && text[digits..].starts_with(['.', ')']) {
+    return format!("{}\\{}", &text[..digits], &text[digits..]);
+  }
+  text.to_string()
+}
+
+/// Prefixes every line of the markdown, for example with `> ` for quotes.
+pub fn prefix_lines(markdown: &str, prefix: &str) -> String {
+  markdown
+    .lines()
+    .map(|line| {
+      if line.is_empty() {
+        prefix.trim_end().to_string()
+      } else {
+        format!("{}{}", prefix, line)
+      }
+    })
+    .collect::<Vec<String>>()
+    .join("\n")
+}
+
 pub fn convert_insert_delta_from_json(delta_value: &Value) -> Option<Vec<InsertDelta>> {
   serde_json::from_value::<Vec<InsertDelta>>(delta_value.to_owned()).ok()
 }
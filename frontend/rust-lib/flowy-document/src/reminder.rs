@@ -8,6 +8,15 @@ pub enum DocumentReminderAction {
   Update { reminder: DocumentReminder },
 }
 
+/// The date of a date mention that has a reminder changed, see
+/// [crate::mention::date_mention_reminders].
+#[derive(Debug, Clone)]
+pub struct ReminderDateChange {
+  pub reminder_id: String,
+  pub old_date: i64,
+  pub new_date: i64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocumentReminder {
   document_id: String, // defines the necessary fields for a reminder
@@ -0,0 +1,135 @@
+use collab::entity::EncodedCollab;
+use collab_document::blocks::{
+  Block, BlockAction, BlockActionPayload, BlockActionType, DocumentData,
+};
+use flowy_error::{internal_error, FlowyResult};
+use nanoid::nanoid;
+
+use crate::document_data::document_data_from_doc_state;
+
+/// Returns the data of the document that is stored in the snapshot.
+pub(crate) fn document_data_from_snapshot(
+  doc_id: &str,
+  encoded_v1: &[u8],
+) -> FlowyResult<DocumentData> {
+  let encoded_collab = EncodedCollab::decode_from_bytes(encoded_v1).map_err(internal_error)?;
+  document_data_from_doc_state(doc_id, encoded_collab.doc_state.to_vec())
+}
+
+/// Returns the actions that replace the content of the `current` document with the content of
+/// the `snapshot`. The blocks of the snapshot are inserted with new ids, so the restore is just
+/// another change of the document, which can be undone.
+pub(crate) fn restore_document_actions(
+  current: &DocumentData,
+  snapshot: &DocumentData,
+) -> Vec<BlockAction> {
+  let mut actions = vec![];
+  let page = match current.blocks.get(&current.page_id) {
+    None => return actions,
+    Some(page) => page,
+  };
+
+  for child_id in children_of(current, page) {
+    if let Some(block) = current.blocks.get(child_id) {
+      actions.push(BlockAction {
+        action: BlockActionType::Delete,
+        payload: BlockActionPayload {
+          block: Some(block.clone()),
+          parent_id: Some(page.id.clone()),
+          prev_id: None,
+          delta: None,
+          text_id: None,
+        },
+      });
+    }
+  }
+
+  if let Some(snapshot_page) = snapshot.blocks.get(&snapshot.page_id) {
+    // Restore the data of the page, for example, the cover of the document.
+    let mut restored_page = page.clone();
+    restored_page.data = snapshot_page.data.clone();
+    actions.push(BlockAction {
+      action: BlockActionType::Update,
+      payload: BlockActionPayload {
+        block: Some(restored_page),
+        parent_id: Some(page.parent.clone()),
+        prev_id: None,
+        delta: None,
+        text_id: None,
+      },
+    });
+    insert_children_actions(snapshot, snapshot_page, &page.id, &mut actions);
+  }
+  actions
+}
+
+fn insert_children_actions(
+  snapshot: &DocumentData,
+  parent: &Block,
+  new_parent_id: &str,
+  actions: &mut Vec<BlockAction>,
+) {
+  let mut prev_id: Option<String> = None;
+  for child_id in children_of(snapshot, parent) {
+    let child = match snapshot.blocks.get(child_id) {
+      None => continue,
+      Some(child) => child,
+    };
+
+    let mut block = Block {
+      id: nanoid!(10),
+      ty: child.ty.clone(),
+      parent: new_parent_id.to_string(),
+      children: nanoid!(10),
+      external_id: None,
+      external_type: None,
+      data: child.data.clone(),
+    };
+
+    let delta = child.external_id.as_ref().and_then(|text_id| {
+      snapshot
+        .meta
+        .text_map
+        .as_ref()
+        .and_then(|text_map| text_map.get(text_id).cloned())
+    });
+    if let Some(delta) = delta {
+      let text_id = nanoid!(10);
+      block.external_id = Some(text_id.clone());
+      block.external_type = child.external_type.clone();
+      actions.push(BlockAction {
+        action: BlockActionType::InsertText,
+        payload: BlockActionPayload {
+          block: None,
+          parent_id: None,
+          prev_id: None,
+          delta: Some(delta),
+          text_id: Some(text_id),
+        },
+      });
+    }
+
+    let block_id = block.id.clone();
+    actions.push(BlockAction {
+      action: BlockActionType::Insert,
+      payload: BlockActionPayload {
+        block: Some(block),
+        parent_id: Some(new_parent_id.to_string()),
+        prev_id: prev_id.clone(),
+        delta: None,
+        text_id: None,
+      },
+    });
+    insert_children_actions(snapshot, child, &block_id, actions);
+    prev_id = Some(block_id);
+  }
+}
+
+fn children_of<'a>(data: &'a DocumentData, block: &Block) -> &'a [String] {
+  data
+    .meta
+    .children_map
+    .get(&block.children)
+    .map(|children| children.as_slice())
+    .unwrap_or_default()
+}
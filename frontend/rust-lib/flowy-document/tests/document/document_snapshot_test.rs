@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use collab_document::blocks::{
+  Block, BlockAction, BlockActionPayload, BlockActionType, DocumentData,
+};
+use collab_document::document_data::{default_document_data, PARAGRAPH_BLOCK_TYPE};
+
+use flowy_document::entities::{BlockChangeTypePB, TextDiffPB, TextDiffTypePB};
+
+use crate::document::util::{gen_document_id, gen_id, DocumentTest};
+
+fn insert_paragraph_actions(page_id: &str, text: &str) -> Vec<BlockAction> {
+  let text_id = gen_id();
+  let block = Block {
+    id: gen_id(),
+    ty: PARAGRAPH_BLOCK_TYPE.to_string(),
+    parent: page_id.to_string(),
+    children: gen_id(),
+    external_id: Some(text_id.clone()),
+    external_type: Some("text".to_string()),
+    data: HashMap::new(),
+  };
+  vec![
+    BlockAction {
+      action: BlockActionType::InsertText,
+      payload: BlockActionPayload {
+        block: None,
+        parent_id: None,
+        prev_id: None,
+        delta: Some(format!(r#"[{{"insert":"{}"}}]"#, text)),
+        text_id: Some(text_id),
+      },
+    },
+    BlockAction {
+      action: BlockActionType::Insert,
+      payload: BlockActionPayload {
+        block: Some(block),
+        parent_id: Some(page_id.to_string()),
+        prev_id: None,
+        delta: None,
+        text_id: None,
+      },
+    },
+  ]
+}
+
+/// Returns the text of the children of the page.
+fn page_texts(data: &DocumentData) -> Vec<String> {
+  let page = data.blocks.get(&data.page_id).unwrap();
+  let text_map = data.meta.text_map.clone().unwrap_or_default();
+  data
+    .meta
+    .children_map
+    .get(&page.children)
+    .cloned()
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|child_id| data.blocks.get(child_id)?.external_id.clone())
+    .filter_map(|text_id| text_map.get(&text_id).cloned())
+    .collect()
+}
+
+#[tokio::test]
+async fn restore_document_snapshot_test() {
+  let test = DocumentTest::new();
+  let doc_id = gen_document_id();
+  let data = default_document_data(&doc_id);
+  let page_id = data.page_id.clone();
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &doc_id, Some(data))
+    .await
+    .unwrap();
+  test.open_document(&doc_id).await.unwrap();
+  let document = test.get_document(&doc_id).await.unwrap();
+
+  document
+    .lock()
+    .apply_action(insert_paragraph_actions(&page_id, "first"));
+  let meta = test
+    .create_document_snapshot(&doc_id, "v1", "first version")
+    .await
+    .unwrap();
+  assert_eq!(meta.title, "v1");
+  assert_eq!(meta.desc, "first version");
+
+  document
+    .lock()
+    .apply_action(insert_paragraph_actions(&page_id, "second"));
+  let texts = page_texts(&document.lock().get_document_data().unwrap());
+  assert_eq!(texts.len(), 2);
+
+  test
+    .restore_document_snapshot(&doc_id, &meta.snapshot_id)
+    .await
+    .unwrap();
+  let texts = page_texts(&document.lock().get_document_data().unwrap());
+  assert_eq!(texts.len(), 1);
+  assert!(texts[0].contains("first"));
+
+  // the content before restoring is saved as a checkpoint
+  let metas = test.get_document_snapshot_meta(&doc_id, 10).await.unwrap();
+  assert_eq!(metas.len(), 2);
+
+  // the restore can be undone
+  assert!(document.lock().undo());
+  let texts = page_texts(&document.lock().get_document_data().unwrap());
+  assert_eq!(texts.len(), 2);
+}
+
+#[tokio::test]
+async fn restore_not_exist_document_snapshot_test() {
+  let test = DocumentTest::new();
+  let doc_id = gen_document_id();
+  let data = default_document_data(&doc_id);
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &doc_id, Some(data))
+    .await
+    .unwrap();
+
+  let result = test.restore_document_snapshot(&doc_id, "not_exist").await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn document_snapshot_diff_test() {
+  let test = DocumentTest::new();
+  let doc_id = gen_document_id();
+  let data = default_document_data(&doc_id);
+  let page_id = data.page_id.clone();
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &doc_id, Some(data))
+    .await
+    .unwrap();
+  test.open_document(&doc_id).await.unwrap();
+  let document = test.get_document(&doc_id).await.unwrap();
+
+  document
+    .lock()
+    .apply_action(insert_paragraph_actions(&page_id, "hello world"));
+  let v1 = test
+    .create_document_snapshot(&doc_id, "v1", "")
+    .await
+    .unwrap();
+  document
+    .lock()
+    .apply_action(insert_paragraph_actions(&page_id, "second"));
+  let v2 = test
+    .create_document_snapshot(&doc_id, "v2", "")
+    .await
+    .unwrap();
+
+  let diff = test
+    .get_document_diff(&doc_id, &v1.snapshot_id, Some(&v2.snapshot_id))
+    .await
+    .unwrap();
+  assert_eq!(diff.changes.len(), 1);
+  assert_eq!(diff.changes[0].change_type, BlockChangeTypePB::Inserted);
+  assert_eq!(diff.changes[0].new_text, "second");
+
+  // compare the snapshot with the current state of the document
+  let first_block = {
+    let data = document.lock().get_document_data().unwrap();
+    let page = data.blocks.get(&data.page_id).unwrap().clone();
+    let first_id = data.meta.children_map.get(&page.children).unwrap()[0].clone();
+    data.blocks.get(&first_id).unwrap().clone()
+  };
+  document.lock().apply_action(vec![BlockAction {
+    action: BlockActionType::ApplyTextDelta,
+    payload: BlockActionPayload {
+      block: None,
+      parent_id: None,
+      prev_id: None,
+      delta: Some(r#"[{"retain":6},{"delete":5},{"insert":"there"}]"#.to_string()),
+      text_id: first_block.external_id.clone(),
+    },
+  }]);
+  let diff = test
+    .get_document_diff(&doc_id, &v2.snapshot_id, None)
+    .await
+    .unwrap();
+  assert_eq!(diff.changes.len(), 1);
+  let change = &diff.changes[0];
+  assert_eq!(change.block_id, first_block.id);
+  assert_eq!(change.change_type, BlockChangeTypePB::TextChanged);
+  assert_eq!(change.old_text, "hello world");
+  assert_eq!(change.new_text, "hello there");
+  assert_eq!(
+    change.text_diffs,
+    vec![
+      text_diff(TextDiffTypePB::Equal, "hello "),
+      text_diff(TextDiffTypePB::Delete, "world"),
+      text_diff(TextDiffTypePB::Insert, "there"),
+    ]
+  );
+
+  // the deleted blocks are reported when comparing in the other direction
+  let diff = test
+    .get_document_diff(&doc_id, &v2.snapshot_id, Some(&v1.snapshot_id))
+    .await
+    .unwrap();
+  assert_eq!(diff.changes.len(), 1);
+  assert_eq!(diff.changes[0].change_type, BlockChangeTypePB::Deleted);
+  assert_eq!(diff.changes[0].old_text, "second");
+}
+
+#[tokio::test]
+async fn document_snapshot_diff_format_test() {
+  let test = DocumentTest::new();
+  let doc_id = gen_document_id();
+  let data = default_document_data(&doc_id);
+  let page_id = data.page_id.clone();
+  let uid = test.user_service.user_id().unwrap();
+  test
+    .create_document(uid, &doc_id, Some(data))
+    .await
+    .unwrap();
+  test.open_document(&doc_id).await.unwrap();
+  let document = test.get_document(&doc_id).await.unwrap();
+
+  let actions = insert_paragraph_actions(&page_id, "hello world");
+  let block = actions[1].payload.block.clone().unwrap();
+  document.lock().apply_action(actions);
+  let v1 = test
+    .create_document_snapshot(&doc_id, "v1", "")
+    .await
+    .unwrap();
+
+  // only the formatting of the text is changed
+  document.lock().apply_action(vec![BlockAction {
+    action: BlockActionType::ApplyTextDelta,
+    payload: BlockActionPayload {
+      block: None,
+      parent_id: None,
+      prev_id: None,
+      delta: Some(r#"[{"retain":5,"attributes":{"bold":true}}]"#.to_string()),
+      text_id: block.external_id.clone(),
+    },
+  }]);
+  let diff = test
+    .get_document_diff(&doc_id, &v1.snapshot_id, None)
+    .await
+    .unwrap();
+  assert_eq!(diff.changes.len(), 1);
+  let change = &diff.changes[0];
+  assert_eq!(change.block_id, block.id);
+  assert_eq!(change.change_type, BlockChangeTypePB::FormatChanged);
+  assert_eq!(change.old_text, "hello world");
+  assert_eq!(change.new_text, "hello world");
+  assert!(change.text_diffs.is_empty());
+}
+
+fn text_diff(diff_type: TextDiffTypePB, text: &str) -> TextDiffPB {
+  TextDiffPB {
+    diff_type,
+    text: text.to_string(),
+  }
+}
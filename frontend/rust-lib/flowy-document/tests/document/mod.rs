@@ -1,5 +1,6 @@
 mod document_insert_test;
 mod document_redo_undo_test;
+mod document_snapshot_test;
 mod document_test;
 mod event_handler_test;
 pub mod util;
@@ -6,7 +6,7 @@ use collab::preclude::CollabPlugin;
 use collab_document::blocks::DocumentData;
 use collab_document::document_data::default_document_data;
 use nanoid::nanoid;
-use parking_lot::Once;
+use parking_lot::{Mutex, Once};
 use tempfile::TempDir;
 use tracing_subscriber::{fmt::Subscriber, util::SubscriberInitExt, EnvFilter};
 
@@ -20,6 +20,10 @@ use flowy_document::entities::{DocumentSnapshotData, DocumentSnapshotMeta};
 use flowy_document::manager::{DocumentManager, DocumentSnapshotService, DocumentUserService};
 use flowy_document_pub::cloud::*;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_search_pub::entities::{
+  BacklinkIndexData, BacklinkIndexManager, DocumentIndexData, DocumentIndexManager,
+  ViewReferenceData,
+};
 use flowy_storage_pub::chunked_byte::ChunkedBytes;
 use flowy_storage_pub::storage::{CreatedUpload, StorageService};
 use lib_infra::async_trait::async_trait;
@@ -35,7 +39,8 @@ impl DocumentTest {
     let user = FakeUser::new();
     let cloud_service = Arc::new(LocalTestDocumentCloudServiceImpl());
     let file_storage = Arc::new(DocumentTestFileStorageService) as Arc<dyn StorageService>;
-    let document_snapshot = Arc::new(DocumentTestSnapshot);
+    let document_snapshot = Arc::new(DocumentTestSnapshot::default());
+    let index_manager = Arc::new(DocumentTestIndexManager);
 
     let builder = Arc::new(AppFlowyCollabBuilder::new(
       DefaultCollabStorageProvider(),
@@ -50,6 +55,8 @@ impl DocumentTest {
       cloud_service,
       Arc::downgrade(&file_storage),
       document_snapshot,
+      index_manager,
+      Arc::new(DocumentTestBacklinkIndexManager),
     );
     Self { inner: manager }
   }
@@ -234,17 +241,108 @@ impl CollabCloudPluginProvider for DefaultCollabStorageProvider {
   }
 }
 
-struct DocumentTestSnapshot;
+/// Stores the snapshots in memory.
+#[derive(Default)]
+struct DocumentTestSnapshot {
+  snapshots: Mutex<Vec<(DocumentSnapshotMeta, Vec<u8>)>>,
+}
+
 impl DocumentSnapshotService for DocumentTestSnapshot {
   fn get_document_snapshot_metas(
     &self,
-    _document_id: &str,
+    document_id: &str,
   ) -> FlowyResult<Vec<DocumentSnapshotMeta>> {
-    todo!()
+    Ok(
+      self
+        .snapshots
+        .lock()
+        .iter()
+        .rev()
+        .filter(|(meta, _)| meta.object_id == document_id)
+        .map(|(meta, _)| meta.clone())
+        .collect(),
+    )
   }
 
-  fn get_document_snapshot(&self, _snapshot_id: &str) -> FlowyResult<DocumentSnapshotData> {
-    todo!()
+  fn get_document_snapshot(&self, snapshot_id: &str) -> FlowyResult<DocumentSnapshotData> {
+    self
+      .snapshots
+      .lock()
+      .iter()
+      .find(|(meta, _)| meta.snapshot_id == snapshot_id)
+      .map(|(meta, data)| DocumentSnapshotData {
+        object_id: meta.object_id.clone(),
+        encoded_v1: data.clone(),
+      })
+      .ok_or(FlowyError::record_not_found())
+  }
+
+  fn create_document_snapshot(
+    &self,
+    document_id: &str,
+    title: &str,
+    desc: &str,
+    encoded_v1: Vec<u8>,
+  ) -> FlowyResult<DocumentSnapshotMeta> {
+    let meta = DocumentSnapshotMeta {
+      snapshot_id: gen_id(),
+      object_id: document_id.to_string(),
+      created_at: 0,
+      title: title.to_string(),
+      desc: desc.to_string(),
+    };
+    self.snapshots.lock().push((meta.clone(), encoded_v1));
+    Ok(meta)
+  }
+}
+
+struct DocumentTestIndexManager;
+impl DocumentIndexManager for DocumentTestIndexManager {
+  fn index_documents(&self, _documents: Vec<DocumentIndexData>) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn remove_indices(&self, _document_ids: Vec<String>) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn remove_indices_for_workspace(&self, _workspace_id: String) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn is_document_indexed(&self, _document_id: &str) -> bool {
+    false
+  }
+
+  fn as_any(&self) -> &dyn std::any::Any {
+    self
+  }
+}
+
+struct DocumentTestBacklinkIndexManager;
+impl BacklinkIndexManager for DocumentTestBacklinkIndexManager {
+  fn index_references(&self, _objects: Vec<BacklinkIndexData>) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn remove_references(&self, _object_ids: Vec<String>) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn remove_indices_for_workspace(&self, _workspace_id: String) -> Result<(), FlowyError> {
+    Ok(())
+  }
+
+  fn is_object_indexed(&self, _object_id: &str) -> bool {
+    false
+  }
+
+  fn get_backlinks(&self, _target_view_id: &str) -> Result<Vec<ViewReferenceData>, FlowyError> {
+    Ok(vec![])
+  }
+
+  fn as_any(&self) -> &dyn std::any::Any {
+    self
   }
 }
 
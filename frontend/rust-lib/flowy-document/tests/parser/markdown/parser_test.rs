@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_document::parser::external::parser::ExternalDataToNestedJSONParser;
+use flowy_document::parser::json::parser::JsonToDocumentParser;
+use flowy_document::parser::parser_entities::{InputType, LinkReplacement, NestedBlock};
+
+/// test convert markdown to json
+/// - input markdown: # Heading with **bold**
+#[tokio::test]
+async fn markdown_to_document_test() {
+  let markdown = include_str!("../../assets/markdown/simple.md");
+  let parser = ExternalDataToNestedJSONParser::new(markdown.to_string(), InputType::Markdown);
+  let block = parser.to_nested_block();
+  assert!(block.is_some());
+  let block = block.unwrap();
+  let expect_json = include_str!("../../assets/json/markdown.json");
+  let expect_block = serde_json::from_str::<NestedBlock>(expect_json).unwrap();
+  assert_eq!(block, expect_block);
+}
+
+#[tokio::test]
+async fn empty_markdown_to_document_test() {
+  let parser = ExternalDataToNestedJSONParser::new("\n  \n".to_string(), InputType::Markdown);
+  assert!(parser.to_nested_block().is_none());
+}
+
+/// test that importing a markdown document and exporting it again gives the same markdown
+#[tokio::test]
+async fn markdown_round_trip_test() {
+  let markdown = include_str!("../../assets/markdown/round_trip.md");
+  let block = ExternalDataToNestedJSONParser::new(markdown.to_string(), InputType::Markdown)
+    .to_nested_block()
+    .unwrap();
+  let json = serde_json::to_string(&block).unwrap();
+  let document_data = JsonToDocumentParser::json_str_to_document(&json)
+    .unwrap()
+    .into();
+  let parser = DocumentDataParser::new(Arc::new(document_data), None);
+  assert_eq!(parser.to_markdown(), markdown);
+}
+
+#[tokio::test]
+async fn replace_markdown_links_test() {
+  let markdown =
+    "See [Child](Child%20abc.md) and [AppFlowy](https://appflowy.io)\n\n![image](image.png)";
+  let mut block = ExternalDataToNestedJSONParser::new(markdown.to_string(), InputType::Markdown)
+    .to_nested_block()
+    .unwrap();
+  assert_eq!(
+    block.links(),
+    vec!["Child%20abc.md", "https://appflowy.io", "image.png"]
+  );
+
+  block.replace_links(&|link| match link {
+    "Child%20abc.md" => Some(LinkReplacement::PageMention("child_id".to_string())),
+    "image.png" => Some(LinkReplacement::Url(
+      "https://appflowy.io/image.png".to_string(),
+    )),
+    _ => None,
+  });
+  assert_eq!(
+    block.links(),
+    vec!["https://appflowy.io", "https://appflowy.io/image.png"]
+  );
+  let mention = &block.children[0].data["delta"][1];
+  assert_eq!(mention["insert"], "$");
+  assert_eq!(mention["attributes"]["mention"]["type"], "page");
+  assert_eq!(mention["attributes"]["mention"]["page_id"], "child_id");
+}
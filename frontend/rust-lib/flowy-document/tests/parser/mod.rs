@@ -1,4 +1,5 @@
 mod document_data_parser_test;
 mod html;
 mod json;
+mod markdown;
 mod parse_to_html_text;
@@ -286,6 +286,21 @@ pub enum ErrorCode {
 
   #[error("Local AI unavailable")]
   LocalAIUnavailable = 99,
+
+  #[error("DocumentIndexManager or its dependencies are unavailable")]
+  DocumentIndexManagerUnavailable = 100,
+
+  #[error("DatabaseIndexManager or its dependencies are unavailable")]
+  DatabaseIndexManagerUnavailable = 101,
+
+  #[error("Invalid recurrence rule")]
+  InvalidRecurrenceRule = 102,
+
+  #[error("The cell breaks a validation rule of its field")]
+  CellValidationFailed = 103,
+
+  #[error("BacklinkIndexManager or its dependencies are unavailable")]
+  BacklinkIndexManagerUnavailable = 104,
 }
 
 impl ErrorCode {
@@ -120,6 +120,18 @@ impl FlowyError {
   static_flowy_error!(workspace_data_not_match, ErrorCode::WorkspaceDataNotMatch);
   static_flowy_error!(local_ai, ErrorCode::LocalAIError);
   static_flowy_error!(local_ai_unavailable, ErrorCode::LocalAIUnavailable);
+  static_flowy_error!(
+    document_index_manager_unavailable,
+    ErrorCode::DocumentIndexManagerUnavailable
+  );
+  static_flowy_error!(
+    database_index_manager_unavailable,
+    ErrorCode::DatabaseIndexManagerUnavailable
+  );
+  static_flowy_error!(
+    backlink_index_manager_unavailable,
+    ErrorCode::BacklinkIndexManagerUnavailable
+  );
 }
 
 impl std::convert::From<ErrorCode> for FlowyError {
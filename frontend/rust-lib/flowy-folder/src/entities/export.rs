@@ -0,0 +1,67 @@
+use crate::entities::parser::empty_str::NotEmptyStr;
+use crate::share::{DocumentExportFormat, ExportParams};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::FlowyError;
+
+#[derive(Clone, Debug, Default, ProtoBuf_Enum)]
+pub enum DocumentExportFormatPB {
+  #[default]
+  Markdown = 0,
+  Html = 1,
+}
+
+impl From<DocumentExportFormatPB> for DocumentExportFormat {
+  fn from(pb: DocumentExportFormatPB) -> Self {
+    match pb {
+      DocumentExportFormatPB::Markdown => DocumentExportFormat::Markdown,
+      DocumentExportFormatPB::Html => DocumentExportFormat::Html,
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct ExportWorkspacePayloadPB {
+  // the path of the zip file to create
+  #[pb(index = 1)]
+  pub file_path: String,
+
+  #[pb(index = 2)]
+  pub document_format: DocumentExportFormatPB,
+}
+
+impl TryInto<ExportParams> for ExportWorkspacePayloadPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<ExportParams, Self::Error> {
+    let file_path = NotEmptyStr::parse(self.file_path)
+      .map_err(|_| FlowyError::invalid_data().with_context("The export file path is empty"))?
+      .0;
+    Ok(ExportParams {
+      file_path,
+      document_format: self.document_format.into(),
+    })
+  }
+}
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct WorkspaceExportPB {
+  #[pb(index = 1)]
+  pub file_path: String,
+
+  #[pb(index = 2)]
+  pub num_of_views: i32,
+
+  /// The views that failed to be exported. Their files are missing from the zip file.
+  #[pb(index = 3)]
+  pub failed_view_ids: Vec<String>,
+}
+
+/// The progress of a workspace export, sent after every exported view.
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct WorkspaceExportProgressPB {
+  #[pb(index = 1)]
+  pub num_of_exported_views: i32,
+
+  #[pb(index = 2)]
+  pub num_of_views: i32,
+}
@@ -1,6 +1,6 @@
 use crate::entities::parser::empty_str::NotEmptyStr;
-use crate::entities::ViewLayoutPB;
-use crate::share::{ImportParams, ImportType, ImportValue};
+use crate::entities::{ViewLayoutPB, ViewPB};
+use crate::share::{CSVImportOptions, ImportFailedRow, ImportParams, ImportType, ImportValue};
 use collab_entity::CollabType;
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::FlowyError;
@@ -11,6 +11,9 @@ pub enum ImportTypePB {
   HistoryDatabase = 1,
   RawDatabase = 2,
   CSV = 3,
+  Markdown = 4,
+  DatabaseTemplate = 5,
+  Zip = 6,
 }
 
 impl From<ImportTypePB> for ImportType {
@@ -20,6 +23,9 @@ impl From<ImportTypePB> for ImportType {
       ImportTypePB::HistoryDatabase => ImportType::HistoryDatabase,
       ImportTypePB::RawDatabase => ImportType::RawDatabase,
       ImportTypePB::CSV => ImportType::CSV,
+      ImportTypePB::Markdown => ImportType::Markdown,
+      ImportTypePB::DatabaseTemplate => ImportType::DatabaseTemplate,
+      ImportTypePB::Zip => ImportType::Zip,
     }
   }
 }
@@ -31,6 +37,10 @@ impl From<ImportType> for CollabType {
       ImportType::HistoryDatabase => CollabType::Database,
       ImportType::RawDatabase => CollabType::Database,
       ImportType::CSV => CollabType::Database,
+      ImportType::Markdown => CollabType::Document,
+      ImportType::DatabaseTemplate => CollabType::Database,
+      // A zip contains both documents and databases
+      ImportType::Zip => CollabType::Unknown,
     }
   }
 }
@@ -64,6 +74,56 @@ pub struct ImportValuePayloadPB {
   // the type of the import page
   #[pb(index = 5)]
   pub import_type: ImportTypePB,
+
+  // the options of reading a csv file. The default options are used if it's not provided
+  #[pb(index = 6, one_of)]
+  pub csv_options: Option<CSVImportOptionsPB>,
+}
+
+#[derive(Clone, Debug, ProtoBuf)]
+pub struct CSVImportOptionsPB {
+  // a single character that separates the fields, e.g. ",", ";" or "\t"
+  #[pb(index = 1)]
+  pub delimiter: String,
+
+  #[pb(index = 2)]
+  pub has_header: bool,
+
+  // the label of the encoding, e.g. "utf-8" or "gbk"
+  #[pb(index = 3)]
+  pub encoding: String,
+}
+
+impl Default for CSVImportOptionsPB {
+  fn default() -> Self {
+    Self {
+      delimiter: ",".to_string(),
+      has_header: true,
+      encoding: "utf-8".to_string(),
+    }
+  }
+}
+
+impl TryInto<CSVImportOptions> for CSVImportOptionsPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<CSVImportOptions, Self::Error> {
+    let delimiter = match self.delimiter.as_bytes() {
+      [] => b',',
+      [delimiter] => *delimiter,
+      _ => {
+        return Err(
+          FlowyError::invalid_data().with_context("The csv delimiter must be a single character"),
+        )
+      },
+    };
+
+    Ok(CSVImportOptions {
+      delimiter,
+      has_header: self.has_header,
+      encoding: self.encoding,
+    })
+  }
 }
 
 #[derive(Clone, Debug, ProtoBuf, Default)]
@@ -78,6 +138,44 @@ pub struct ImportPayloadPB {
   pub sync_after_create: bool,
 }
 
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportResultPB {
+  #[pb(index = 1)]
+  pub items: Vec<ViewPB>,
+
+  // the rows of the csv files that can't be parsed, they are skipped
+  #[pb(index = 2)]
+  pub failed_rows: Vec<ImportFailedRowPB>,
+
+  // the paths of the files of a zip that can't be imported, relative to the root of the zip
+  #[pb(index = 3)]
+  pub failed_paths: Vec<String>,
+}
+
+#[derive(Clone, Debug, ProtoBuf, Default)]
+pub struct ImportFailedRowPB {
+  // the id of the database view that the csv file is imported to
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  // the line number of the row, starting from 1
+  #[pb(index = 2)]
+  pub line: u64,
+
+  #[pb(index = 3)]
+  pub message: String,
+}
+
+impl ImportFailedRowPB {
+  pub fn new(view_id: &str, row: ImportFailedRow) -> Self {
+    Self {
+      view_id: view_id.to_string(),
+      line: row.line,
+      message: row.message,
+    }
+  }
+}
+
 impl TryInto<ImportParams> for ImportPayloadPB {
   type Error = FlowyError;
 
@@ -104,12 +202,18 @@ impl TryInto<ImportParams> for ImportPayloadPB {
         ),
       };
 
+      let csv_options = match value.csv_options {
+        None => None,
+        Some(csv_options) => Some(csv_options.try_into()?),
+      };
+
       let params = ImportValue {
         name,
         data: value.data,
         file_path,
         view_layout: value.view_layout.into(),
         import_type: value.import_type.into(),
+        csv_options,
       };
 
       values.push(params);
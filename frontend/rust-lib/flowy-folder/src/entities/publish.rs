@@ -1,4 +1,8 @@
+use crate::entities::parser::empty_str::NotEmptyStr;
+use crate::entities::parser::view::ViewIdentify;
+use crate::share::LocalPublishParams;
 use flowy_derive::ProtoBuf;
+use flowy_error::FlowyError;
 use flowy_folder_pub::entities::PublishInfoResponse;
 
 #[derive(Default, ProtoBuf)]
@@ -46,3 +50,46 @@ pub struct PublishNamespacePB {
   #[pb(index = 1)]
   pub namespace: String,
 }
+
+#[derive(Default, ProtoBuf)]
+pub struct PublishViewToLocalSitePayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  /// The directory of the site. It's created if it doesn't exist.
+  #[pb(index = 2)]
+  pub site_dir: String,
+
+  #[pb(index = 3, one_of)]
+  pub publish_name: Option<String>,
+
+  #[pb(index = 4, one_of)]
+  pub namespace: Option<String>,
+}
+
+impl TryInto<LocalPublishParams> for PublishViewToLocalSitePayloadPB {
+  type Error = FlowyError;
+
+  fn try_into(self) -> Result<LocalPublishParams, Self::Error> {
+    let view_id = ViewIdentify::parse(self.view_id)?.0;
+    let site_dir = NotEmptyStr::parse(self.site_dir)
+      .map_err(|_| FlowyError::invalid_data().with_context("The site directory is empty"))?
+      .0;
+    Ok(LocalPublishParams {
+      view_id,
+      site_dir,
+      publish_name: self.publish_name,
+      namespace: self.namespace,
+    })
+  }
+}
+
+#[derive(Default, ProtoBuf)]
+pub struct LocalPublishedSitePB {
+  /// The path of the `index.html` of the namespace, which redirects to the published view.
+  #[pb(index = 1)]
+  pub index_path: String,
+
+  #[pb(index = 2)]
+  pub num_of_pages: i32,
+}
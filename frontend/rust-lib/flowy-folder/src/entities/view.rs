@@ -175,6 +175,23 @@ pub struct RepeatedViewPB {
   pub items: Vec<ViewPB>,
 }
 
+/// A view that references another view, e.g. a document that mentions the page.
+#[derive(Eq, PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct ViewBacklinkPB {
+  #[pb(index = 1)]
+  pub view: ViewPB,
+
+  /// The blocks of the document, or the rows of the database, that contain the references.
+  #[pb(index = 2)]
+  pub block_ids: Vec<String>,
+}
+
+#[derive(Eq, PartialEq, Debug, Default, ProtoBuf, Clone)]
+pub struct RepeatedViewBacklinkPB {
+  #[pb(index = 1)]
+  pub items: Vec<ViewBacklinkPB>,
+}
+
 #[derive(Eq, PartialEq, Debug, Default, ProtoBuf, Clone)]
 pub struct RepeatedFavoriteViewPB {
   #[pb(index = 1)]
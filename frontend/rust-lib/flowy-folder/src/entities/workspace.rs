@@ -200,6 +200,21 @@ pub struct FolderSnapshotPB {
   pub data: Vec<u8>,
 }
 
+#[derive(Debug, Default, ProtoBuf)]
+pub struct CreateFolderSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub title: String,
+
+  #[pb(index = 2)]
+  pub desc: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RestoreFolderSnapshotPayloadPB {
+  #[pb(index = 1)]
+  pub snapshot_id: i64,
+}
+
 #[derive(Debug, Default, ProtoBuf)]
 pub struct FolderSnapshotStatePB {
   #[pb(index = 1)]
@@ -1,12 +1,12 @@
 use std::sync::{Arc, Weak};
 use tracing::instrument;
 
-use flowy_error::{FlowyError, FlowyResult};
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use lib_dispatch::prelude::{data_result_ok, AFPluginData, AFPluginState, DataResult};
 
 use crate::entities::*;
 use crate::manager::FolderManager;
-use crate::share::ImportParams;
+use crate::share::{ExportParams, ImportParams, LocalPublishParams};
 
 fn upgrade_folder(
   folder_manager: AFPluginState<Weak<FolderManager>>,
@@ -375,11 +375,44 @@ pub(crate) async fn delete_my_trash_handler(
 pub(crate) async fn import_data_handler(
   data: AFPluginData<ImportPayloadPB>,
   folder: AFPluginState<Weak<FolderManager>>,
-) -> DataResult<RepeatedViewPB, FlowyError> {
+) -> DataResult<ImportResultPB, FlowyError> {
   let folder = upgrade_folder(folder)?;
   let params: ImportParams = data.into_inner().try_into()?;
-  let views = folder.import(params).await?;
-  data_result_ok(views)
+  let result = folder.import(params).await?;
+  data_result_ok(result)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn export_workspace_handler(
+  data: AFPluginData<ExportWorkspacePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<WorkspaceExportPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: ExportParams = data.into_inner().try_into()?;
+  let export = folder.export_workspace(params).await?;
+  data_result_ok(export)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn publish_view_to_local_site_handler(
+  data: AFPluginData<PublishViewToLocalSitePayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<LocalPublishedSitePB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let params: LocalPublishParams = data.into_inner().try_into()?;
+  let site = folder.publish_view_to_local_site(params).await?;
+  data_result_ok(site)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn get_view_backlinks_handler(
+  data: AFPluginData<ViewIdPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<RepeatedViewBacklinkPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let view_id: ViewIdPB = data.into_inner();
+  let backlinks = folder.get_view_backlinks(&view_id.value).await?;
+  data_result_ok(RepeatedViewBacklinkPB { items: backlinks })
 }
 
 #[tracing::instrument(level = "debug", skip(folder), err)]
@@ -393,6 +426,37 @@ pub(crate) async fn get_folder_snapshots_handler(
   data_result_ok(RepeatedFolderSnapshotPB { items: snapshots })
 }
 
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn create_folder_snapshot_handler(
+  data: AFPluginData<CreateFolderSnapshotPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> DataResult<FolderSnapshotPB, FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  let data = data.into_inner();
+  if data.title.trim().is_empty() {
+    return Err(FlowyError::new(
+      ErrorCode::InvalidParams,
+      "The title of the snapshot can't be empty",
+    ));
+  }
+  let snapshot = folder
+    .create_folder_snapshot(&data.title, &data.desc)
+    .await?;
+  data_result_ok(snapshot)
+}
+
+#[tracing::instrument(level = "debug", skip(data, folder), err)]
+pub(crate) async fn restore_folder_snapshot_handler(
+  data: AFPluginData<RestoreFolderSnapshotPayloadPB>,
+  folder: AFPluginState<Weak<FolderManager>>,
+) -> Result<(), FlowyError> {
+  let folder = upgrade_folder(folder)?;
+  folder
+    .restore_folder_snapshot(data.into_inner().snapshot_id)
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip(data, folder), err)]
 pub(crate) async fn update_view_visibility_status_handler(
   data: AFPluginData<UpdateViewVisibilityStatusPayloadPB>,
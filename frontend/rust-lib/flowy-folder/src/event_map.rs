@@ -33,6 +33,20 @@ pub fn init(folder: Weak<FolderManager>) -> AFPlugin {
     .event(FolderEvent::PermanentlyDeleteAllTrashItem, delete_my_trash_handler)
     .event(FolderEvent::ImportData, import_data_handler)
     .event(FolderEvent::GetFolderSnapshots, get_folder_snapshots_handler)
+    .event(
+      FolderEvent::CreateFolderSnapshot,
+      create_folder_snapshot_handler,
+    )
+    .event(
+      FolderEvent::RestoreFolderSnapshot,
+      restore_folder_snapshot_handler,
+    )
+    .event(FolderEvent::ExportWorkspace, export_workspace_handler)
+    .event(
+      FolderEvent::PublishViewToLocalSite,
+      publish_view_to_local_site_handler,
+    )
+    .event(FolderEvent::GetViewBacklinks, get_view_backlinks_handler)
     .event(FolderEvent::UpdateViewIcon, update_view_icon_handler)
     .event(FolderEvent::ReadFavorites, read_favorites_handler)
     .event(FolderEvent::ReadRecentViews, read_recent_views_handler)
@@ -137,7 +151,7 @@ pub enum FolderEvent {
   #[event()]
   PermanentlyDeleteAllTrashItem = 27,
 
-  #[event(input = "ImportPayloadPB", output = "RepeatedViewPB")]
+  #[event(input = "ImportPayloadPB", output = "ImportResultPB")]
   ImportData = 30,
 
   #[event(input = "WorkspaceIdPB", output = "RepeatedFolderSnapshotPB")]
@@ -196,4 +210,29 @@ pub enum FolderEvent {
 
   #[event(input = "UnpublishViewsPayloadPB")]
   UnpublishViews = 47,
+
+  /// Creates a named checkpoint of the current workspace.
+  #[event(input = "CreateFolderSnapshotPayloadPB", output = "FolderSnapshotPB")]
+  CreateFolderSnapshot = 48,
+
+  /// Restores the views of the current workspace to the state of the snapshot.
+  #[event(input = "RestoreFolderSnapshotPayloadPB")]
+  RestoreFolderSnapshot = 49,
+
+  /// Exports the views of the current workspace to a zip file. The progress is reported by
+  /// [crate::notification::FolderNotification::DidUpdateWorkspaceExportProgress].
+  #[event(input = "ExportWorkspacePayloadPB", output = "WorkspaceExportPB")]
+  ExportWorkspace = 50,
+
+  /// Publishes the view and its child views to a static html site in a local directory.
+  #[event(
+    input = "PublishViewToLocalSitePayloadPB",
+    output = "LocalPublishedSitePB"
+  )]
+  PublishViewToLocalSite = 51,
+
+  /// Returns the views that reference the view, e.g. the documents that mention the page, with
+  /// the blocks that contain the references.
+  #[event(input = "ViewIdPB", output = "RepeatedViewBacklinkPB")]
+  GetViewBacklinks = 52,
 }
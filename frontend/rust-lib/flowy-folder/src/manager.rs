@@ -2,8 +2,9 @@ use crate::entities::icon::UpdateViewIconParams;
 use crate::entities::{
   view_pb_with_child_views, view_pb_without_child_views, view_pb_without_child_views_from_arc,
   CreateViewParams, CreateWorkspaceParams, DeletedViewPB, DuplicateViewParams, FolderSnapshotPB,
-  MoveNestedViewParams, RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB, UpdateViewParams,
-  ViewLayoutPB, ViewPB, ViewSectionPB, WorkspacePB, WorkspaceSettingPB,
+  ImportFailedRowPB, ImportResultPB, LocalPublishedSitePB, MoveNestedViewParams, RepeatedTrashPB,
+  RepeatedViewIdPB, RepeatedViewPB, UpdateViewParams, ViewBacklinkPB, ViewLayoutPB, ViewPB,
+  ViewSectionPB, WorkspaceExportPB, WorkspaceExportProgressPB, WorkspacePB, WorkspaceSettingPB,
 };
 use crate::manager_observer::{
   notify_child_views_changed, notify_did_update_workspace, notify_parent_view_did_change,
@@ -12,33 +13,49 @@ use crate::manager_observer::{
 use crate::notification::{
   send_notification, send_workspace_setting_notification, FolderNotification,
 };
-use crate::publish_util::{generate_publish_name, view_pb_to_publish_view};
-use crate::share::{ImportParams, ImportValue};
+use crate::publish_util::{
+  generate_publish_name, replace_invalid_url_chars, view_pb_to_publish_view,
+};
+use crate::share::{
+  render_site_index, render_site_page, render_site_sidebar, scan_zip_dir, unique_file_stem,
+  ExportParams, ImportParams, ImportType, ImportValue, ImportedData, LocalPublishParams,
+  MarkdownPage, ZipImportLinks, ZipImportNode,
+};
 use crate::util::{
   folder_not_init_error, insert_parent_child_views, workspace_data_not_sync_error,
 };
-use crate::view_operation::{create_view, FolderOperationHandler, FolderOperationHandlers};
+use crate::view_operation::{
+  create_view, FolderOperationHandler, FolderOperationHandlers, ViewReferenceUpdate,
+};
 use collab::core::collab::{DataSource, MutexCollab};
+use collab::core::origin::CollabOrigin;
 use collab_entity::{CollabType, EncodedCollab};
 use collab_folder::error::FolderError;
 use collab_folder::{
-  Folder, FolderNotify, Section, SectionItem, TrashInfo, UserId, View, ViewLayout, ViewUpdate,
-  Workspace,
+  Folder, FolderData, FolderNotify, Section, SectionItem, TrashInfo, UserId, View, ViewLayout,
+  ViewUpdate, Workspace,
 };
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
 use collab_integrate::CollabKVDB;
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
-use flowy_folder_pub::cloud::{gen_view_id, FolderCloudService, FolderCollabParams};
+use flowy_folder_pub::cloud::{
+  gen_view_id, FolderCloudService, FolderCollabParams, FolderSnapshot,
+};
 use flowy_folder_pub::entities::{
   PublishInfoResponse, PublishViewInfo, PublishViewMeta, PublishViewMetaData, PublishViewPayload,
 };
 use flowy_folder_pub::folder_builder::ParentChildViews;
-use flowy_search_pub::entities::FolderIndexManager;
+use flowy_search_pub::entities::{BacklinkIndexManager, FolderIndexManager, ViewReferenceData};
 use flowy_sqlite::kv::KVStorePreferences;
 use futures::future;
+use lib_infra::file_util::{unzip_and_replace, zip_folder};
+use nanoid::nanoid;
 use parking_lot::RwLock;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
+use std::fs;
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Weak};
 use tracing::{error, info, instrument};
 
@@ -48,6 +65,23 @@ pub trait FolderUser: Send + Sync {
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
 }
 
+/// The title of the checkpoint that is created before restoring a snapshot of the folder.
+const RESTORE_CHECKPOINT_TITLE: &str = "Before restore";
+
+/// Stores the snapshots of the folder locally. The snapshots without a title are created
+/// automatically and are removed according to the snapshot retention.
+pub trait FolderSnapshotService: Send + Sync {
+  fn create_folder_snapshot(
+    &self,
+    workspace_id: &str,
+    title: &str,
+    desc: &str,
+    encoded_v1: Vec<u8>,
+  ) -> FlowyResult<FolderSnapshot>;
+
+  fn get_folder_snapshot(&self, snapshot_id: i64) -> FlowyResult<FolderSnapshot>;
+}
+
 pub struct FolderManager {
   /// MutexFolder is the folder that is used to store the data.
   pub(crate) mutex_folder: Arc<MutexFolder>,
@@ -56,7 +90,9 @@ pub struct FolderManager {
   pub(crate) operation_handlers: FolderOperationHandlers,
   pub cloud_service: Arc<dyn FolderCloudService>,
   pub(crate) folder_indexer: Arc<dyn FolderIndexManager>,
+  pub(crate) backlink_indexer: Arc<dyn BacklinkIndexManager>,
   pub(crate) store_preferences: Arc<KVStorePreferences>,
+  pub(crate) snapshot_service: Arc<dyn FolderSnapshotService>,
 }
 
 impl FolderManager {
@@ -66,7 +102,9 @@ impl FolderManager {
     operation_handlers: FolderOperationHandlers,
     cloud_service: Arc<dyn FolderCloudService>,
     folder_indexer: Arc<dyn FolderIndexManager>,
+    backlink_indexer: Arc<dyn BacklinkIndexManager>,
     store_preferences: Arc<KVStorePreferences>,
+    snapshot_service: Arc<dyn FolderSnapshotService>,
   ) -> FlowyResult<Self> {
     let mutex_folder = Arc::new(MutexFolder::default());
     let manager = Self {
@@ -76,7 +114,9 @@ impl FolderManager {
       operation_handlers,
       cloud_service,
       folder_indexer,
+      backlink_indexer,
       store_preferences,
+      snapshot_service,
     };
 
     Ok(manager)
@@ -612,6 +652,9 @@ impl FolderManager {
         }
       },
     );
+    self
+      .update_view_references(view_id, ViewReferenceUpdate::Trashed)
+      .await;
 
     Ok(())
   }
@@ -756,6 +799,7 @@ impl FolderManager {
   /// Update the view with the given params.
   #[tracing::instrument(level = "trace", skip(self), err)]
   pub async fn update_view_with_params(&self, params: UpdateViewParams) -> FlowyResult<()> {
+    let name = params.name.clone();
     self
       .update_view(&params.view_id, |update| {
         update
@@ -766,7 +810,13 @@ impl FolderManager {
           .set_extra_if_not_none(params.extra)
           .done()
       })
-      .await
+      .await?;
+    if let Some(name) = name {
+      self
+        .update_view_references(&params.view_id, ViewReferenceUpdate::Renamed { name })
+        .await;
+    }
+    Ok(())
   }
 
   /// Update the icon of the view with the given params.
@@ -1270,12 +1320,18 @@ impl FolderManager {
 
   #[tracing::instrument(level = "trace", skip(self))]
   pub(crate) async fn restore_all_trash(&self) {
+    let restored_trash = self.with_folder(Vec::new, |folder| folder.get_my_trash_info());
     self.with_folder(
       || (),
       |folder| {
         folder.remove_all_my_trash_sections();
       },
     );
+    for trash in restored_trash {
+      self
+        .update_view_references(&trash.id, ViewReferenceUpdate::Restored)
+        .await;
+    }
     send_notification("trash", FolderNotification::DidUpdateTrash)
       .payload(RepeatedTrashPB { items: vec![] })
       .send();
@@ -1289,6 +1345,9 @@ impl FolderManager {
         folder.delete_trash_view_ids(vec![trash_id.to_string()]);
       },
     );
+    self
+      .update_view_references(trash_id, ViewReferenceUpdate::Restored)
+      .await;
   }
 
   /// Delete all the trash permanently.
@@ -1320,16 +1379,83 @@ impl FolderManager {
       if let Ok(handler) = self.get_handler(&view.layout) {
         handler.delete_view(view_id).await?;
       }
+      let name = view.name.clone();
+      self
+        .update_view_references(view_id, ViewReferenceUpdate::Deleted { name })
+        .await;
     }
     Ok(())
   }
 
-  /// Imports a single file to the folder and returns the encoded collab for immediate cloud sync.
+  /// Returns the views that reference the view, e.g. the documents that mention the page. The
+  /// views in the trash and the private views of the other members aren't returned.
+  pub async fn get_view_backlinks(&self, view_id: &str) -> FlowyResult<Vec<ViewBacklinkPB>> {
+    let mut block_ids_by_view_id =
+      group_references_by_source(self.backlink_indexer.get_backlinks(view_id)?);
+    let source_view_ids = block_ids_by_view_id
+      .iter()
+      .map(|(source_view_id, _)| source_view_id.clone())
+      .collect();
+    let backlinks = self
+      .get_view_pbs_without_children(source_view_ids)
+      .await?
+      .into_iter()
+      .filter_map(|view| {
+        let index = block_ids_by_view_id
+          .iter()
+          .position(|(source_view_id, _)| source_view_id == &view.id)?;
+        let (_, block_ids) = block_ids_by_view_id.swap_remove(index);
+        Some(ViewBacklinkPB { view, block_ids })
+      })
+      .collect();
+    Ok(backlinks)
+  }
+
+  /// Applies the change of the view to the views that reference it, see
+  /// [FolderOperationHandler::update_view_references]. The failures are only logged, they
+  /// don't fail the change of the view.
+  async fn update_view_references(&self, view_id: &str, update: ViewReferenceUpdate) {
+    let references = match self.backlink_indexer.get_backlinks(view_id) {
+      Ok(references) => references,
+      Err(err) => {
+        error!("Failed to get the backlinks of view {}: {}", view_id, err);
+        return;
+      },
+    };
+
+    for (source_view_id, block_ids) in group_references_by_source(references) {
+      let layout = self.with_folder(
+        || None,
+        |folder| {
+          folder
+            .views
+            .get_view(&source_view_id)
+            .map(|view| view.layout.clone())
+        },
+      );
+      let handler = match layout.map(|layout| self.get_handler(&layout)) {
+        Some(Ok(handler)) => handler,
+        _ => continue,
+      };
+      if let Err(err) = handler
+        .update_view_references(&source_view_id, block_ids, view_id, update.clone())
+        .await
+      {
+        error!(
+          "Failed to update the references of view {} in view {}: {}",
+          view_id, source_view_id, err
+        );
+      }
+    }
+  }
+
+  /// Imports a single file to the folder and returns the encoded collab for immediate cloud sync,
+  /// with the rows of a csv file that can't be parsed.
   pub(crate) async fn import_single_file(
     &self,
     parent_view_id: String,
     import_data: ImportValue,
-  ) -> FlowyResult<(View, Option<EncodedCollab>)> {
+  ) -> FlowyResult<(View, Option<ImportedData>)> {
     // Ensure either data or file_path is provided
     if import_data.data.is_none() && import_data.file_path.is_none() {
       return Err(FlowyError::new(
@@ -1341,11 +1467,11 @@ impl FolderManager {
     let handler = self.get_handler(&import_data.view_layout)?;
     let view_id = gen_view_id().to_string();
     let uid = self.user.user_id()?;
-    let mut encoded_collab: Option<EncodedCollab> = None;
+    let mut imported_data: Option<ImportedData> = None;
 
     // Import data from bytes if available
     if let Some(data) = import_data.data {
-      encoded_collab = Some(
+      imported_data = Some(
         handler
           .import_from_bytes(
             uid,
@@ -1353,6 +1479,7 @@ impl FolderManager {
             &import_data.name,
             import_data.import_type,
             data,
+            import_data.csv_options.clone(),
           )
           .await?,
       );
@@ -1360,9 +1487,13 @@ impl FolderManager {
 
     // Import data from file path if available
     if let Some(file_path) = import_data.file_path {
-      // TODO(Lucas): return the collab
-      handler
-        .import_from_file_path(&view_id, &import_data.name, file_path)
+      imported_data = handler
+        .import_from_file_path(
+          &view_id,
+          &import_data.name,
+          file_path,
+          import_data.csv_options,
+        )
         .await?;
     }
 
@@ -1391,32 +1522,69 @@ impl FolderManager {
       },
     );
 
-    Ok((view, encoded_collab))
+    Ok((view, imported_data))
   }
 
-  /// Import function to handle the import of data.
-  pub(crate) async fn import(&self, import_data: ImportParams) -> FlowyResult<RepeatedViewPB> {
+  /// Import function to handle the import of data. The rows of the csv files that can't be
+  /// parsed, and the files of a zip that can't be imported, are skipped and returned in the
+  /// result.
+  pub(crate) async fn import(&self, import_data: ImportParams) -> FlowyResult<ImportResultPB> {
     let workspace_id = self.user.workspace_id()?;
 
     // Initialize an empty vector to store the objects
     let sync_after_create = import_data.sync_after_create;
     let mut objects = vec![];
     let mut views = vec![];
+    let mut failed_rows = vec![];
+    let mut failed_paths = vec![];
+
+    // A markdown import may point to a directory, which is expanded to one value per file
+    let mut values = vec![];
+    for value in import_data.values {
+      values.extend(value.resolve_markdown_files()?);
+    }
 
     // Iterate over the values in the import data
-    for data in import_data.values {
+    for data in values {
+      if let ImportType::Zip = data.import_type {
+        let result = self
+          .import_zip(import_data.parent_view_id.clone(), data)
+          .await?;
+        views.extend(result.views.into_iter().map(view_pb_without_child_views));
+        failed_rows.extend(result.failed_rows);
+        failed_paths.extend(result.failed_paths);
+        if sync_after_create {
+          for (object_id, collab_type, encoded_collab) in result.encoded_collabs {
+            match self.get_folder_collab_params(object_id, collab_type, encoded_collab) {
+              Ok(params) => objects.push(params),
+              Err(e) => {
+                error!("import error {}", e);
+              },
+            }
+          }
+        }
+        continue;
+      }
+
       let collab_type = data.import_type.clone().into();
 
       // Import a single file and get the view and encoded collab data
-      let (view, encoded_collab) = self
+      let (view, imported_data) = self
         .import_single_file(import_data.parent_view_id.clone(), data)
         .await?;
       let object_id = view.id.clone();
 
       views.push(view_pb_without_child_views(view));
 
-      if sync_after_create {
-        if let Some(encoded_collab) = encoded_collab {
+      if let Some(imported_data) = imported_data {
+        failed_rows.extend(
+          imported_data
+            .failed_rows
+            .into_iter()
+            .map(|row| ImportFailedRowPB::new(&object_id, row)),
+        );
+        if sync_after_create {
+          let encoded_collab = imported_data.encoded_collab;
           // don't block the whole import process if the view can't be encoded
           match self.get_folder_collab_params(object_id, collab_type, encoded_collab) {
             Ok(params) => objects.push(params),
@@ -1443,7 +1611,472 @@ impl FolderManager {
       vec![import_data.parent_view_id],
     );
 
-    Ok(RepeatedViewPB { items: views })
+    Ok(ImportResultPB {
+      items: views,
+      failed_rows,
+      failed_paths,
+    })
+  }
+
+  /// Imports a zip of markdown pages and csv databases, see [scan_zip_dir] for how the hierarchy
+  /// of the pages is recreated.
+  async fn import_zip(
+    &self,
+    parent_view_id: String,
+    import_data: ImportValue,
+  ) -> FlowyResult<ZipImportResult> {
+    let file_path = import_data.file_path.ok_or_else(|| {
+      FlowyError::new(
+        ErrorCode::InvalidParams,
+        "The file path of the zip is required",
+      )
+    })?;
+    let import_dir = std::env::temp_dir().join(format!("appflowy_import_{}", nanoid!(10)));
+    unzip_and_replace(&file_path, &import_dir)
+      .map_err(|err| FlowyError::invalid_data().with_context(err))?;
+
+    let result = self.import_zip_dir(parent_view_id, &import_dir).await;
+    if let Err(err) = fs::remove_dir_all(&import_dir) {
+      error!("Failed to remove the import directory: {}", err);
+    }
+    result
+  }
+
+  async fn import_zip_dir(
+    &self,
+    parent_view_id: String,
+    import_dir: &Path,
+  ) -> FlowyResult<ZipImportResult> {
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    let nodes = scan_zip_dir(import_dir)?;
+    let links = Arc::new(ZipImportLinks::new(import_dir, &nodes));
+
+    // The collabs are created first, the parent of a view is created before the view
+    let mut created_views = HashMap::new();
+    let mut encoded_collabs = vec![];
+    let mut failed_rows = vec![];
+    let mut failed_paths = vec![];
+    let mut stack = nodes
+      .iter()
+      .rev()
+      .map(|node| (parent_view_id.clone(), node))
+      .collect::<Vec<_>>();
+    while let Some((parent_view_id, node)) = stack.pop() {
+      let result = self
+        .import_zip_node(uid, &workspace_id, node, &node.view_id, links.clone())
+        .await;
+      let imported_data = match result {
+        Ok(imported_data) => imported_data,
+        Err(err) => {
+          // The view isn't created, its children are imported to its parent instead
+          error!("Failed to import {:?}: {}", node.file_path, err);
+          failed_paths.push(zip_node_path(node, import_dir));
+          stack.extend(
+            node
+              .children
+              .iter()
+              .rev()
+              .map(|child| (parent_view_id.clone(), child)),
+          );
+          continue;
+        },
+      };
+      let collab_type = match node.layout {
+        ViewLayout::Document => CollabType::Document,
+        _ => CollabType::Database,
+      };
+      encoded_collabs.push((
+        node.view_id.clone(),
+        collab_type,
+        imported_data.encoded_collab,
+      ));
+      failed_rows.extend(
+        imported_data
+          .failed_rows
+          .into_iter()
+          .map(|row| ImportFailedRowPB::new(&node.view_id, row)),
+      );
+
+      let params = CreateViewParams {
+        parent_view_id,
+        name: node.name.clone(),
+        desc: "".to_string(),
+        layout: node.layout.clone().into(),
+        initial_data: vec![],
+        view_id: node.view_id.clone(),
+        meta: Default::default(),
+        set_as_current: false,
+        index: None,
+        section: None,
+        extra: None,
+        icon: None,
+      };
+      let view = create_view(uid, params, node.layout.clone());
+      created_views.insert(node.view_id.clone(), view);
+
+      let child_nodes = match node.layout {
+        ViewLayout::Document => node.children.iter().collect::<Vec<_>>(),
+        _ => {
+          self
+            .import_zip_row_pages(
+              uid,
+              &workspace_id,
+              import_dir,
+              node,
+              links.clone(),
+              &mut encoded_collabs,
+              &mut failed_paths,
+            )
+            .await
+        },
+      };
+      stack.extend(
+        child_nodes
+          .into_iter()
+          .rev()
+          .map(|child| (node.view_id.clone(), child)),
+      );
+    }
+
+    let parent_child_views = nodes
+      .iter()
+      .flat_map(|node| zip_node_to_parent_child_views(node, &mut created_views))
+      .collect::<Vec<_>>();
+    let views = parent_child_views
+      .iter()
+      .map(|views| views.parent_view.clone())
+      .collect::<Vec<_>>();
+    self.with_folder(
+      || (),
+      |folder| {
+        for views in parent_child_views {
+          insert_parent_child_views(folder, views);
+        }
+      },
+    );
+    Ok(ZipImportResult {
+      views,
+      encoded_collabs,
+      failed_rows,
+      failed_paths,
+    })
+  }
+
+  /// Imports the pages of the rows of the database node to the documents of the rows, the page
+  /// of a row is the child page that has the name of the row. Returns the other children and the
+  /// children of the pages of the rows, they're imported as the child views of the database.
+  #[allow(clippy::too_many_arguments)]
+  async fn import_zip_row_pages<'a>(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+    import_dir: &Path,
+    node: &'a ZipImportNode,
+    links: Arc<ZipImportLinks>,
+    encoded_collabs: &mut Vec<(String, CollabType, EncodedCollab)>,
+    failed_paths: &mut Vec<String>,
+  ) -> Vec<&'a ZipImportNode> {
+    let handler = match self.get_handler(&node.layout) {
+      Ok(handler) => handler,
+      Err(_) => return node.children.iter().collect(),
+    };
+    let mut row_documents = match handler.get_row_documents(&node.view_id).await {
+      Ok(row_documents) => row_documents,
+      Err(err) => {
+        error!("Failed to get the rows of {:?}: {}", node.file_path, err);
+        return node.children.iter().collect();
+      },
+    };
+
+    let mut child_nodes = vec![];
+    let mut imported_row_ids = vec![];
+    for child in node.children.iter() {
+      let index = row_documents.iter().position(|row_document| {
+        child.layout == ViewLayout::Document
+          && child.file_path.is_some()
+          && row_document.name == child.name
+      });
+      let row_document = match index {
+        Some(index) => row_documents.remove(index),
+        None => {
+          child_nodes.push(child);
+          continue;
+        },
+      };
+      child_nodes.extend(child.children.iter());
+
+      let result = self
+        .import_zip_node(
+          uid,
+          workspace_id,
+          child,
+          &row_document.document_id,
+          links.clone(),
+        )
+        .await;
+      match result {
+        Ok(imported_data) => {
+          encoded_collabs.push((
+            row_document.document_id,
+            CollabType::Document,
+            imported_data.encoded_collab,
+          ));
+          imported_row_ids.push(row_document.row_id);
+        },
+        Err(err) => {
+          error!("Failed to import {:?}: {}", child.file_path, err);
+          failed_paths.push(zip_node_path(child, import_dir));
+        },
+      }
+    }
+
+    if !imported_row_ids.is_empty() {
+      if let Err(err) = handler
+        .did_import_row_documents(&node.view_id, imported_row_ids)
+        .await
+      {
+        error!("Failed to update the rows of {:?}: {}", node.file_path, err);
+      }
+    }
+    child_nodes
+  }
+
+  /// Imports the file of the node to the collab of `view_id`.
+  async fn import_zip_node(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+    node: &ZipImportNode,
+    view_id: &str,
+    links: Arc<ZipImportLinks>,
+  ) -> FlowyResult<ImportedData> {
+    let handler = self.get_handler(&node.layout)?;
+    let data = match &node.file_path {
+      Some(file_path) => fs::read(file_path)?,
+      None => vec![],
+    };
+    match node.layout {
+      ViewLayout::Document => {
+        let markdown =
+          String::from_utf8(data).map_err(|err| FlowyError::invalid_data().with_context(err))?;
+        let dir = node
+          .file_path
+          .as_ref()
+          .and_then(|file_path| file_path.parent())
+          .map(|dir| dir.to_path_buf())
+          .unwrap_or_default();
+        let page = MarkdownPage::new(workspace_id.to_string(), &node.name, markdown, dir, links);
+        Ok(
+          handler
+            .import_markdown_page(uid, view_id, page)
+            .await?
+            .into(),
+        )
+      },
+      _ => {
+        handler
+          .import_from_bytes(uid, view_id, &node.name, ImportType::CSV, data, None)
+          .await
+      },
+    }
+  }
+
+  /// Exports the views of the current workspace to a zip file. Every view is exported by the
+  /// handler of its layout, and the child views are put in a directory named after their parent.
+  /// The views in the trash and the private views of other members are skipped.
+  pub(crate) async fn export_workspace(
+    &self,
+    params: ExportParams,
+  ) -> FlowyResult<WorkspaceExportPB> {
+    let workspace_id = self.user.workspace_id()?;
+    let views = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| {
+        let view_ids_should_be_filtered = self.get_view_ids_should_be_filtered(folder);
+        let mut views = vec![];
+        let mut parents = vec![(workspace_id.clone(), PathBuf::new())];
+        while let Some((parent_id, dir)) = parents.pop() {
+          let mut used_stems = HashSet::new();
+          for view in folder.views.get_views_belong_to(&parent_id) {
+            if view_ids_should_be_filtered.contains(&view.id) {
+              continue;
+            }
+            let file_stem = unique_file_stem(&view.name, &mut used_stems);
+            parents.push((view.id.clone(), dir.join(&file_stem)));
+            views.push((view, dir.clone(), file_stem));
+          }
+        }
+        Ok(views)
+      },
+    )?;
+
+    // An empty workspace is exported as an empty zip file.
+    let export_dir = std::env::temp_dir().join(format!("appflowy_export_{}", nanoid!(10)));
+    tokio::fs::create_dir_all(&export_dir).await?;
+    let num_of_views = views.len() as i32;
+    let result = self
+      .export_views_to_zip(&workspace_id, views, &export_dir, &params)
+      .await;
+    if let Err(err) = tokio::fs::remove_dir_all(&export_dir).await {
+      error!("Failed to remove the export directory: {}", err);
+    }
+    let failed_view_ids = result?;
+
+    Ok(WorkspaceExportPB {
+      file_path: params.file_path,
+      num_of_views,
+      failed_view_ids,
+    })
+  }
+
+  /// Writes the files of the views to `export_dir` and zips it to the file of the export.
+  /// Returns the ids of the views that failed to export.
+  async fn export_views_to_zip(
+    &self,
+    workspace_id: &str,
+    views: Vec<(Arc<View>, PathBuf, String)>,
+    export_dir: &Path,
+    params: &ExportParams,
+  ) -> FlowyResult<Vec<String>> {
+    let num_of_views = views.len() as i32;
+    let mut failed_view_ids = vec![];
+    for (index, (view, dir, file_stem)) in views.into_iter().enumerate() {
+      let files = match self.get_handler(&view.layout) {
+        Ok(handler) => {
+          match handler
+            .export_view(&view.id, &file_stem, params.document_format)
+            .await
+          {
+            Ok(files) => files,
+            Err(err) => {
+              error!("Failed to export view {}: {}", view.id, err);
+              failed_view_ids.push(view.id.clone());
+              vec![]
+            },
+          }
+        },
+        Err(_) => vec![],
+      };
+      for file in files {
+        let path = export_dir.join(&dir).join(&file.path);
+        if let Some(parent) = path.parent() {
+          tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, file.data).await?;
+      }
+
+      send_notification(
+        workspace_id,
+        FolderNotification::DidUpdateWorkspaceExportProgress,
+      )
+      .payload(WorkspaceExportProgressPB {
+        num_of_exported_views: index as i32 + 1,
+        num_of_views,
+      })
+      .send();
+    }
+
+    let zip_dir = export_dir.to_path_buf();
+    let file_path = params.file_path.clone();
+    tokio::task::spawn_blocking(move || zip_folder(&zip_dir, Path::new(&file_path)))
+      .await
+      .map_err(internal_error)??;
+    Ok(failed_view_ids)
+  }
+
+  /// Publishes the view and its child views to a static html site in a local directory, so that
+  /// it can be served by any web server. Every view of [Self::get_batch_publish_payload], and
+  /// every database that is a child of one of them, becomes a page of the site.
+  pub(crate) async fn publish_view_to_local_site(
+    &self,
+    params: LocalPublishParams,
+  ) -> FlowyResult<LocalPublishedSitePB> {
+    let publish_name = params
+      .publish_name
+      .as_deref()
+      .map(replace_invalid_url_chars)
+      .filter(|name| !name.is_empty());
+    let payloads = self
+      .get_batch_publish_payload(&params.view_id, publish_name, Some(true))
+      .await?;
+    let root = payloads
+      .iter()
+      .find(|payload| payload.meta.view_id == params.view_id)
+      .ok_or_else(|| {
+        FlowyError::new(
+          ErrorCode::NotSupportYet,
+          "Only document view can be published".to_string(),
+        )
+      })?;
+
+    // The sidebar of every page shows the tree of the published view
+    let sidebar_root = PublishViewInfo {
+      child_views: Some(root.meta.metadata.child_views.clone()),
+      ..root.meta.metadata.view.clone()
+    };
+    let root_publish_name = root.meta.publish_name.clone();
+
+    let mut pages = vec![];
+    let mut publish_names = HashMap::new();
+    for payload in &payloads {
+      let view = &payload.meta.metadata.view;
+      publish_names.insert(view.view_id.clone(), payload.meta.publish_name.clone());
+      pages.push((view.clone(), Some(payload.data.clone())));
+    }
+    for payload in &payloads {
+      for child_view in &payload.meta.metadata.child_views {
+        let is_database = matches!(
+          child_view.layout,
+          ViewLayout::Grid | ViewLayout::Board | ViewLayout::Calendar
+        );
+        if is_database && !publish_names.contains_key(&child_view.view_id) {
+          publish_names.insert(
+            child_view.view_id.clone(),
+            generate_publish_name(&child_view.view_id, &child_view.name),
+          );
+          pages.push((child_view.clone(), None));
+        }
+      }
+    }
+
+    let namespace = match params.namespace {
+      Some(namespace) => namespace,
+      None => match self.get_publish_namespace().await {
+        Ok(namespace) => namespace,
+        Err(_) => self.user.workspace_id()?,
+      },
+    };
+    let site_dir = Path::new(&params.site_dir).join(replace_invalid_url_chars(&namespace));
+    let num_of_pages = pages.len() as i32;
+    for (view, data) in pages {
+      let html = self
+        .get_handler(&view.layout)?
+        .publish_view_html(&view.view_id, data)
+        .await?;
+      let sidebar = render_site_sidebar(&sidebar_root, &publish_names, &view.view_id);
+      let page_dir = site_dir.join(&publish_names[&view.view_id]);
+      tokio::fs::create_dir_all(&page_dir).await?;
+      tokio::fs::write(
+        page_dir.join("index.html"),
+        render_site_page(&view.name, &sidebar, &html.body),
+      )
+      .await?;
+      for file in html.files {
+        let path = page_dir.join(&file.path);
+        if let Some(parent) = path.parent() {
+          tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, file.data).await?;
+      }
+    }
+
+    let index_path = site_dir.join("index.html");
+    tokio::fs::write(&index_path, render_site_index(&root_publish_name)).await?;
+    Ok(LocalPublishedSitePB {
+      index_path: index_path.to_string_lossy().to_string(),
+      num_of_pages,
+    })
   }
 
   /// Update the view with the provided view_id using the specified function.
@@ -1572,6 +2205,156 @@ impl FolderManager {
     Ok(snapshots)
   }
 
+  /// Creates a named checkpoint of the folder. Unlike the automatic snapshots, the checkpoints
+  /// are never removed by the snapshot retention.
+  pub async fn create_folder_snapshot(
+    &self,
+    title: &str,
+    desc: &str,
+  ) -> FlowyResult<FolderSnapshotPB> {
+    let workspace_id = self.user.workspace_id()?;
+    let encoded_collab = self.with_folder(
+      || Err(folder_not_init_error()),
+      |folder| folder.encode_collab_v1().map_err(internal_error),
+    )?;
+    let encoded_v1 = encoded_collab.encode_to_bytes().map_err(internal_error)?;
+    let snapshot =
+      self
+        .snapshot_service
+        .create_folder_snapshot(&workspace_id, title, desc, encoded_v1)?;
+    Ok(FolderSnapshotPB {
+      snapshot_id: snapshot.snapshot_id,
+      snapshot_desc: desc.to_string(),
+      created_at: snapshot.created_at,
+      data: snapshot.data,
+    })
+  }
+
+  /// Restores the views of the workspace to the state of the snapshot. The views that were
+  /// trashed since the snapshot are put back, the names, the icons and the positions of the
+  /// views are reset, and the views that were created since the snapshot are moved to the trash.
+  /// No view is deleted and the current state is saved as a checkpoint first, so the restore can
+  /// be reverted.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn restore_folder_snapshot(&self, snapshot_id: i64) -> FlowyResult<()> {
+    let uid = self.user.user_id()?;
+    let workspace_id = self.user.workspace_id()?;
+    let snapshot = self.snapshot_service.get_folder_snapshot(snapshot_id)?;
+    if snapshot.database_id != workspace_id {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "Snapshot {} doesn't belong to workspace {}",
+        snapshot_id, workspace_id
+      )));
+    }
+    let encoded_collab =
+      EncodedCollab::decode_from_bytes(&snapshot.data).map_err(internal_error)?;
+    let snapshot_data = Folder::from_collab_doc_state(
+      uid,
+      CollabOrigin::Empty,
+      DataSource::DocStateV1(encoded_collab.doc_state.to_vec()),
+      &workspace_id,
+      vec![],
+    )
+    .map_err(internal_error)?
+    .get_folder_data(&workspace_id)
+    .ok_or_else(|| {
+      FlowyError::invalid_data().with_context("Can't read the folder data of the snapshot")
+    })?;
+    let current_data = self
+      .with_folder(|| None, |folder| folder.get_folder_data(&workspace_id))
+      .ok_or_else(folder_not_init_error)?;
+    self
+      .create_folder_snapshot(RESTORE_CHECKPOINT_TITLE, "")
+      .await?;
+
+    let snapshot_view_ids = view_ids_of_folder_data(&snapshot_data);
+    let current_view_ids = view_ids_of_folder_data(&current_data);
+    let snapshot_trash_ids = trash_ids_of_folder_data(&snapshot_data);
+    let current_trash_ids = trash_ids_of_folder_data(&current_data);
+
+    // 1. Put back the views that were trashed after the snapshot.
+    for trash_id in current_trash_ids.difference(&snapshot_trash_ids) {
+      if snapshot_view_ids.contains(trash_id) {
+        self.restore_trash(trash_id).await;
+      }
+    }
+
+    // 2. Reset the names and the icons of the views.
+    for view in snapshot_data.views.iter() {
+      let is_changed = current_data
+        .views
+        .iter()
+        .find(|current| current.id == view.id)
+        .map_or(false, |current| {
+          current.name != view.name || current.icon != view.icon
+        });
+      if is_changed {
+        let name = view.name.clone();
+        let icon = view.icon.clone();
+        self
+          .update_view(&view.id, |update| {
+            update
+              .set_name_if_not_none(Some(name))
+              .set_icon(icon)
+              .done()
+          })
+          .await?;
+      }
+    }
+
+    // 3. Reset the positions of the views.
+    let current_children = children_of_folder_data(&current_data);
+    let mut changed_parent_ids = vec![];
+    for (parent_id, child_ids) in children_of_folder_data(&snapshot_data) {
+      if parent_id != workspace_id && !current_view_ids.contains(&parent_id) {
+        continue;
+      }
+      let child_ids = child_ids
+        .into_iter()
+        .filter(|child_id| current_view_ids.contains(child_id))
+        .collect::<Vec<_>>();
+      if current_children.get(&parent_id) == Some(&child_ids) {
+        continue;
+      }
+
+      self.with_folder(
+        || (),
+        |folder| {
+          let mut prev_view_id = None;
+          for child_id in child_ids.iter() {
+            if let Some(old_parent_id) = folder
+              .views
+              .get_view(child_id)
+              .map(|view| view.parent_view_id.clone())
+            {
+              changed_parent_ids.push(old_parent_id);
+            }
+            folder.move_nested_view(child_id, &parent_id, prev_view_id);
+            prev_view_id = Some(child_id.clone());
+          }
+        },
+      );
+      changed_parent_ids.push(parent_id);
+    }
+    if !changed_parent_ids.is_empty() {
+      changed_parent_ids.sort();
+      changed_parent_ids.dedup();
+      notify_parent_view_did_change(&workspace_id, self.mutex_folder.clone(), changed_parent_ids);
+    }
+
+    // 4. Move the views that were created after the snapshot to the trash. The child views of a
+    // new view are trashed with it.
+    for view in current_data.views.iter() {
+      let is_new = !snapshot_view_ids.contains(&view.id) && !current_trash_ids.contains(&view.id);
+      let is_parent_restored =
+        view.parent_view_id == workspace_id || snapshot_view_ids.contains(&view.parent_view_id);
+      if is_new && is_parent_restored {
+        self.move_view_to_trash(&view.id).await?;
+      }
+    }
+    Ok(())
+  }
+
   pub fn set_views_visibility(&self, view_ids: Vec<String>, is_public: bool) {
     self.with_folder(
       || (),
@@ -1646,12 +2429,35 @@ impl FolderManager {
   pub fn remove_indices_for_workspace(&self, workspace_id: String) -> FlowyResult<()> {
     self
       .folder_indexer
+      .remove_indices_for_workspace(workspace_id.clone())?;
+    self
+      .backlink_indexer
       .remove_indices_for_workspace(workspace_id)?;
 
     Ok(())
   }
 }
 
+/// Groups the references by the view that contains them, in the order of the first reference of
+/// each view.
+fn group_references_by_source(references: Vec<ViewReferenceData>) -> Vec<(String, Vec<String>)> {
+  let mut groups: Vec<(String, Vec<String>)> = vec![];
+  for reference in references {
+    match groups
+      .iter_mut()
+      .find(|(source_view_id, _)| source_view_id == &reference.source_view_id)
+    {
+      Some((_, block_ids)) => {
+        if !block_ids.contains(&reference.block_id) {
+          block_ids.push(reference.block_id);
+        }
+      },
+      None => groups.push((reference.source_view_id, vec![reference.block_id])),
+    }
+  }
+  groups
+}
+
 /// Return the views that belong to the workspace. The views are filtered by the trash and all the private views.
 pub(crate) fn get_workspace_public_view_pbs(workspace_id: &str, folder: &Folder) -> Vec<ViewPB> {
   // get the trash ids
@@ -1737,6 +2543,88 @@ pub(crate) fn get_workspace_private_view_pbs(workspace_id: &str, folder: &Folder
     .collect()
 }
 
+fn view_ids_of_folder_data(data: &FolderData) -> HashSet<String> {
+  data.views.iter().map(|view| view.id.clone()).collect()
+}
+
+fn trash_ids_of_folder_data(data: &FolderData) -> HashSet<String> {
+  data
+    .trash
+    .values()
+    .flatten()
+    .map(|item| item.id.clone())
+    .collect()
+}
+
+/// Returns the ids of the child views by the id of their parent. The child views of the workspace
+/// are keyed by the workspace id.
+fn children_of_folder_data(data: &FolderData) -> HashMap<String, Vec<String>> {
+  let mut children = HashMap::new();
+  children.insert(
+    data.workspace.id.clone(),
+    data
+      .workspace
+      .child_views
+      .items
+      .iter()
+      .map(|child| child.id.clone())
+      .collect(),
+  );
+  for view in data.views.iter() {
+    children.insert(
+      view.id.clone(),
+      view
+        .children
+        .items
+        .iter()
+        .map(|child| child.id.clone())
+        .collect(),
+    );
+  }
+  children
+}
+
+/// The views that are created by a zip import, see [FolderManager::import_zip].
+struct ZipImportResult {
+  /// The top level views.
+  views: Vec<View>,
+  /// The collabs of all the views.
+  encoded_collabs: Vec<(String, CollabType, EncodedCollab)>,
+  failed_rows: Vec<ImportFailedRowPB>,
+  /// The paths of the files that can't be imported, relative to the root of the zip.
+  failed_paths: Vec<String>,
+}
+
+/// The path of the file of the node, relative to the root of the zip.
+fn zip_node_path(node: &ZipImportNode, import_dir: &Path) -> String {
+  node
+    .file_path
+    .as_ref()
+    .map(|path| path.strip_prefix(import_dir).unwrap_or(path))
+    .map(|path| path.to_string_lossy().to_string())
+    .unwrap_or_else(|| node.name.clone())
+}
+
+/// Takes the views of the node and its children that are created by the zip import. If the view
+/// of the node isn't created, its children take its place.
+fn zip_node_to_parent_child_views(
+  node: &ZipImportNode,
+  created_views: &mut HashMap<String, View>,
+) -> Vec<ParentChildViews> {
+  let child_views = node
+    .children
+    .iter()
+    .flat_map(|child| zip_node_to_parent_child_views(child, created_views))
+    .collect();
+  match created_views.remove(&node.view_id) {
+    Some(parent_view) => vec![ParentChildViews {
+      parent_view,
+      child_views,
+    }],
+    None => child_views,
+  }
+}
+
 /// The MutexFolder is a wrapper of the [Folder] that is used to share the folder between different
 /// threads.
 #[derive(Clone, Default)]
@@ -190,6 +190,9 @@ impl FolderManager {
       .store_preferences
       .get_object::<EncodedCollab>(&workspace_id);
 
+    // The index is empty if it was removed after the schema of the index changed, in which
+    // case all the views are indexed again
+    let encoded_collab = encoded_collab.filter(|_| self.folder_indexer.is_indexed());
     if let Some(encoded_collab) = encoded_collab {
       if let Ok(changes) = folder.calculate_view_changes(encoded_collab) {
         let folder_indexer = self.folder_indexer.clone();
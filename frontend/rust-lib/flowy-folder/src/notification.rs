@@ -38,6 +38,8 @@ pub enum FolderNotification {
 
   /// Trigger when the ROOT views (the first level) in section are updated
   DidUpdateSectionViews = 39,
+  /// The progress of the workspace export, see [crate::entities::WorkspaceExportProgressPB].
+  DidUpdateWorkspaceExportProgress = 40,
 }
 
 impl std::convert::From<FolderNotification> for i32 {
@@ -65,6 +67,7 @@ impl std::convert::From<i32> for FolderNotification {
       37 => FolderNotification::DidUnfavoriteView,
       38 => FolderNotification::DidUpdateRecentViews,
       39 => FolderNotification::DidUpdateSectionViews,
+      40 => FolderNotification::DidUpdateWorkspaceExportProgress,
       _ => FolderNotification::Unknown,
     }
   }
@@ -3,7 +3,7 @@ use flowy_folder_pub::entities::PublishViewInfo;
 use regex::Regex;
 use tracing::trace;
 
-fn replace_invalid_url_chars(input: &str) -> String {
+pub(crate) fn replace_invalid_url_chars(input: &str) -> String {
   let regex = Regex::new(r"[^\w-]").unwrap();
   regex.replace_all(input, "-").to_string()
 }
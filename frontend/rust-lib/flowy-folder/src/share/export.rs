@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+/// The format of the documents in a workspace export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentExportFormat {
+  Markdown,
+  Html,
+}
+
+#[derive(Clone, Debug)]
+pub struct ExportParams {
+  /// The path of the zip file to create.
+  pub file_path: String,
+  pub document_format: DocumentExportFormat,
+}
+
+/// A file of an exported view. The path is relative to the directory of the view, and starts
+/// with the file stem that is given to [crate::view_operation::FolderOperationHandler::export_view],
+/// e.g. `Meeting notes.md` or `Meeting notes_files/image.png`.
+#[derive(Clone, Debug)]
+pub struct ExportedFile {
+  pub path: String,
+  pub data: Vec<u8>,
+}
+
+const UNTITLED: &str = "Untitled";
+
+/// Returns a name that can be used as a file name on every platform, and that's not used by the
+/// other views in the same directory.
+pub(crate) fn unique_file_stem(name: &str, used_stems: &mut HashSet<String>) -> String {
+  let stem = name
+    .trim()
+    .chars()
+    .map(|c| match c {
+      '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+      c if c.is_control() => '_',
+      c => c,
+    })
+    .collect::<String>();
+  let stem = stem.trim_matches('.').to_string();
+  let stem = if stem.is_empty() {
+    UNTITLED.to_string()
+  } else {
+    stem
+  };
+
+  let mut unique_stem = stem.clone();
+  let mut index = 2;
+  while !used_stems.insert(unique_stem.to_lowercase()) {
+    unique_stem = format!("{} ({})", stem, index);
+    index += 1;
+  }
+  unique_stem
+}
@@ -1,4 +1,8 @@
+use collab::entity::EncodedCollab;
 use collab_folder::ViewLayout;
+use flowy_error::FlowyResult;
+use std::fs;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub enum ImportType {
@@ -6,6 +10,12 @@ pub enum ImportType {
   HistoryDatabase = 1,
   RawDatabase = 2,
   CSV = 3,
+  Markdown = 4,
+  /// The json of a database template exported by the database.
+  DatabaseTemplate = 5,
+  /// A zip of markdown pages and csv databases, e.g. the export of Notion. The file path of the
+  /// zip is required.
+  Zip = 6,
 }
 
 #[derive(Clone, Debug)]
@@ -15,6 +25,99 @@ pub struct ImportValue {
   pub file_path: Option<String>,
   pub view_layout: ViewLayout,
   pub import_type: ImportType,
+  pub csv_options: Option<CSVImportOptions>,
+}
+
+/// Options that control how a csv file is read.
+#[derive(Clone, Debug)]
+pub struct CSVImportOptions {
+  pub delimiter: u8,
+  pub has_header: bool,
+  pub encoding: String,
+}
+
+impl ImportValue {
+  /// Reads the markdown file into the data of the import value. If the file path is a directory,
+  /// every `.md` file in it is imported as a separate page named after the file.
+  /// A database template file is read into the data as well, the file path of a database is
+  /// otherwise imported as csv. Other import types are returned as is.
+  pub(crate) fn resolve_markdown_files(self) -> FlowyResult<Vec<ImportValue>> {
+    let path = match (&self.import_type, &self.file_path) {
+      (ImportType::Markdown, Some(file_path)) => PathBuf::from(file_path),
+      (ImportType::DatabaseTemplate, Some(file_path)) => {
+        let data = fs::read(file_path)?;
+        return Ok(vec![ImportValue {
+          data: Some(data),
+          file_path: None,
+          ..self
+        }]);
+      },
+      _ => return Ok(vec![self]),
+    };
+
+    if !path.is_dir() {
+      let data = fs::read(&path)?;
+      return Ok(vec![ImportValue {
+        data: Some(data),
+        file_path: None,
+        ..self
+      }]);
+    }
+
+    let mut paths = fs::read_dir(&path)?
+      .filter_map(|entry| entry.ok())
+      .map(|entry| entry.path())
+      .filter(|path| {
+        path.is_file()
+          && path
+            .extension()
+            .map_or(false, |ext| ext.eq_ignore_ascii_case("md"))
+      })
+      .collect::<Vec<PathBuf>>();
+    paths.sort();
+
+    paths
+      .into_iter()
+      .map(|path| {
+        let name = path
+          .file_stem()
+          .map(|name| name.to_string_lossy().to_string())
+          .unwrap_or_default();
+        Ok(ImportValue {
+          name,
+          data: Some(fs::read(&path)?),
+          file_path: None,
+          view_layout: self.view_layout.clone(),
+          import_type: ImportType::Markdown,
+          csv_options: None,
+        })
+      })
+      .collect()
+  }
+}
+
+/// The data of a view that is created by an import.
+#[derive(Debug)]
+pub struct ImportedData {
+  pub encoded_collab: EncodedCollab,
+  /// The rows of a csv file that can't be parsed, they are skipped.
+  pub failed_rows: Vec<ImportFailedRow>,
+}
+
+impl From<EncodedCollab> for ImportedData {
+  fn from(encoded_collab: EncodedCollab) -> Self {
+    Self {
+      encoded_collab,
+      failed_rows: vec![],
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct ImportFailedRow {
+  /// The line number of the row, starting from 1.
+  pub line: u64,
+  pub message: String,
 }
 
 #[derive(Clone, Debug)]
@@ -1,3 +1,9 @@
+mod export;
 mod import;
+mod publish;
+mod zip_import;
 
+pub use export::*;
 pub use import::*;
+pub use publish::*;
+pub use zip_import::*;
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use flowy_folder_pub::entities::PublishViewInfo;
+use lib_infra::util::escape_html;
+
+use crate::share::ExportedFile;
+
+#[derive(Clone, Debug)]
+pub struct LocalPublishParams {
+  pub view_id: String,
+  /// The pages are written to `{site_dir}/{namespace}/{publish_name}/index.html`.
+  pub site_dir: String,
+  /// The publish name of the view. The child views always use the default publish name.
+  pub publish_name: Option<String>,
+  /// If it's None, the namespace of the workspace is used.
+  pub namespace: Option<String>,
+}
+
+/// The content of a published page, see
+/// [crate::view_operation::FolderOperationHandler::publish_view_html]. The paths of the files,
+/// e.g. the images of a document, are relative to the directory of the page.
+#[derive(Clone, Debug, Default)]
+pub struct PublishedHtml {
+  pub body: String,
+  pub files: Vec<ExportedFile>,
+}
+
+/// Returns the html page of a published view. Every page links to the other pages of the site
+/// with the same sidebar.
+pub(crate) fn render_site_page(title: &str, sidebar: &str, body: &str) -> String {
+  format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n\
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+<title>{}</title>\n</head>\n<body>\n<nav class=\"sidebar\">{}</nav>\n\
+<main class=\"page\"><h1>{}</h1>{}</main>\n</body>\n</html>\n",
+    escape_html(title),
+    sidebar,
+    escape_html(title),
+    body
+  )
+}
+
+/// Returns the page of the namespace, which redirects to the page of the published view.
+pub(crate) fn render_site_index(publish_name: &str) -> String {
+  format!(
+    "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n\
+<meta http-equiv=\"refresh\" content=\"0; url=./{0}/index.html\">\n</head>\n<body>\n\
+<a href=\"./{0}/index.html\">{0}</a>\n</body>\n</html>\n",
+    publish_name
+  )
+}
+
+/// Returns the navigation of the site as nested lists. `publish_names` contains the publish
+/// names of the views that have a page; the other views are listed without a link.
+pub(crate) fn render_site_sidebar(
+  root: &PublishViewInfo,
+  publish_names: &HashMap<String, String>,
+  current_view_id: &str,
+) -> String {
+  let mut html = String::from("<ul>");
+  render_sidebar_item(root, publish_names, current_view_id, &mut html);
+  html.push_str("</ul>");
+  html
+}
+
+fn render_sidebar_item(
+  view: &PublishViewInfo,
+  publish_names: &HashMap<String, String>,
+  current_view_id: &str,
+  html: &mut String,
+) {
+  let name = escape_html(&view.name);
+  match publish_names.get(&view.view_id) {
+    Some(_) if view.view_id == current_view_id => {
+      html.push_str(&format!("<li class=\"current\"><span>{}</span>", name));
+    },
+    Some(publish_name) => {
+      html.push_str(&format!(
+        "<li><a href=\"../{}/index.html\">{}</a>",
+        publish_name, name
+      ));
+    },
+    None => html.push_str(&format!("<li><span>{}</span>", name)),
+  }
+
+  if let Some(child_views) = view.child_views.as_ref().filter(|views| !views.is_empty()) {
+    html.push_str("<ul>");
+    for child_view in child_views {
+      render_sidebar_item(child_view, publish_names, current_view_id, html);
+    }
+    html.push_str("</ul>");
+  }
+  html.push_str("</li>");
+}
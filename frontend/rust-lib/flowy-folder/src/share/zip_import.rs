@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use collab_folder::ViewLayout;
+use flowy_folder_pub::cloud::gen_view_id;
+
+/// The length of the id that Notion appends to the names of the exported files, e.g.
+/// `Meeting notes 8e3ff4e4a5a14a2fa5b8b2b2a7f2e4c1.md`.
+const NOTION_ID_LEN: usize = 32;
+
+/// A page or a database of an unpacked archive, see [scan_zip_dir].
+#[derive(Debug, Clone)]
+pub(crate) struct ZipImportNode {
+  pub(crate) view_id: String,
+  pub(crate) name: String,
+  /// The markdown or csv file of the node. A directory without a page of its own is imported as
+  /// an empty page that contains the pages of the directory.
+  pub(crate) file_path: Option<PathBuf>,
+  pub(crate) layout: ViewLayout,
+  pub(crate) children: Vec<ZipImportNode>,
+}
+
+/// Scans the directory for markdown pages and csv databases. The pages of the directory that has
+/// the same name as a page or a database, which is how Notion exports sub-pages and the pages of
+/// the rows, become the children of that page or database. The children of a database that
+/// have the name of a row are imported to the document of the row, see [RowDocument]. The other
+/// files are attachments, they are only imported if a page links to them.
+pub(crate) fn scan_zip_dir(dir: &Path) -> io::Result<Vec<ZipImportNode>> {
+  let mut paths = fs::read_dir(dir)?
+    .filter_map(|entry| entry.ok())
+    .map(|entry| entry.path())
+    .filter(|path| !is_hidden(path))
+    .collect::<Vec<PathBuf>>();
+  paths.sort();
+
+  let mut nodes = vec![];
+  let mut page_dirs = HashSet::new();
+  for path in paths.iter().filter(|path| path.is_file()) {
+    let layout = match extension(path).as_deref() {
+      Some("md") | Some("markdown") => ViewLayout::Document,
+      Some("csv") => ViewLayout::Grid,
+      _ => continue,
+    };
+    let stem = file_name(path, true);
+    // Notion exports the rows of every view as `Name.csv` and all the rows as `Name_all.csv`.
+    if let Some(name) = stem.strip_suffix("_all") {
+      if layout == ViewLayout::Grid && path.with_file_name(format!("{}.csv", name)).is_file() {
+        continue;
+      }
+    }
+
+    let page_dir = path.with_file_name(&stem);
+    let children = if page_dir.is_dir() {
+      page_dirs.insert(page_dir.clone());
+      scan_zip_dir(&page_dir)?
+    } else {
+      vec![]
+    };
+    nodes.push(ZipImportNode {
+      view_id: gen_view_id().to_string(),
+      name: strip_notion_id(&stem).to_string(),
+      file_path: Some(path.clone()),
+      layout,
+      children,
+    });
+  }
+
+  for path in paths.iter().filter(|path| path.is_dir()) {
+    if page_dirs.contains(path) {
+      continue;
+    }
+    // A directory that only contains attachments isn't a page
+    let children = scan_zip_dir(path)?;
+    if children.is_empty() {
+      continue;
+    }
+    nodes.push(ZipImportNode {
+      view_id: gen_view_id().to_string(),
+      name: strip_notion_id(&file_name(path, false)).to_string(),
+      file_path: None,
+      layout: ViewLayout::Document,
+      children,
+    });
+  }
+  Ok(nodes)
+}
+
+/// The document of a row of a database, the page of the row in a zip is imported to it.
+#[derive(Debug, Clone)]
+pub struct RowDocument {
+  pub row_id: String,
+  /// The text of the primary cell of the row.
+  pub name: String,
+  pub document_id: String,
+}
+
+/// Removes the id that Notion appends to the names of the exported files.
+fn strip_notion_id(name: &str) -> &str {
+  match name.rsplit_once(' ') {
+    Some((name, id))
+      if id.len() == NOTION_ID_LEN
+        && id.chars().all(|c| c.is_ascii_hexdigit())
+        && !name.trim().is_empty() =>
+    {
+      name.trim_end()
+    },
+    _ => name,
+  }
+}
+
+/// The views that are imported from the files of an unpacked archive. It's used to resolve the
+/// links between the files.
+#[derive(Debug, Default)]
+pub(crate) struct ZipImportLinks {
+  root: PathBuf,
+  view_ids: HashMap<PathBuf, String>,
+}
+
+impl ZipImportLinks {
+  pub(crate) fn new(root: &Path, nodes: &[ZipImportNode]) -> Self {
+    let mut links = Self {
+      root: fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf()),
+      view_ids: HashMap::new(),
+    };
+    links.insert_nodes(nodes);
+    links
+  }
+
+  fn insert_nodes(&mut self, nodes: &[ZipImportNode]) {
+    for node in nodes {
+      if let Some(path) = node
+        .file_path
+        .as_ref()
+        .and_then(|path| fs::canonicalize(path).ok())
+      {
+        self.view_ids.insert(path, node.view_id.clone());
+      }
+      self.insert_nodes(&node.children);
+    }
+  }
+}
+
+/// The target of a relative link of a page, see [MarkdownPage::resolve_link].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZipImportLink {
+  /// The id of the view that is imported from the linked page or database.
+  View(String),
+  /// An attachment of the archive.
+  File(PathBuf),
+}
+
+/// A markdown page of an archive, see [crate::view_operation::FolderOperationHandler::import_markdown_page].
+#[derive(Debug, Clone)]
+pub struct MarkdownPage {
+  pub workspace_id: String,
+  pub markdown: String,
+  /// The directory of the markdown file, the relative links are resolved against it.
+  dir: PathBuf,
+  links: Arc<ZipImportLinks>,
+}
+
+impl MarkdownPage {
+  /// Notion exports the name of the page as the first heading of the page, it's removed because
+  /// the name is already the name of the view.
+  pub(crate) fn new(
+    workspace_id: String,
+    name: &str,
+    markdown: String,
+    dir: PathBuf,
+    links: Arc<ZipImportLinks>,
+  ) -> Self {
+    let title = format!("# {}", name);
+    let markdown = match markdown.trim_start().split_once('\n') {
+      Some((first_line, rest)) if first_line.trim_end() == title => rest.to_string(),
+      None if markdown.trim() == title => String::new(),
+      _ => markdown,
+    };
+    Self {
+      workspace_id,
+      markdown,
+      dir,
+      links,
+    }
+  }
+
+  /// Resolves a relative link of the page, e.g. `Sub%20page%20abc.md` or `Page/image.png`.
+  /// Returns None for the urls and for the files that are outside of the archive.
+  pub fn resolve_link(&self, link: &str) -> Option<ZipImportLink> {
+    let path = link_to_relative_path(link)?;
+    let path = fs::canonicalize(self.dir.join(path)).ok()?;
+    if let Some(view_id) = self.links.view_ids.get(&path) {
+      return Some(ZipImportLink::View(view_id.clone()));
+    }
+    if path.is_file() && path.starts_with(&self.links.root) {
+      return Some(ZipImportLink::File(path));
+    }
+    None
+  }
+}
+
+fn link_to_relative_path(link: &str) -> Option<PathBuf> {
+  let link = link.trim();
+  if link.is_empty() || link.starts_with('#') || link.contains(':') {
+    return None;
+  }
+  let link = link.split(|c| c == '#' || c == '?').next()?;
+  let path = PathBuf::from(percent_decode(link));
+  if path.is_absolute() {
+    return None;
+  }
+  Some(path)
+}
+
+fn percent_decode(s: &str) -> String {
+  let bytes = s.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    let hex = bytes
+      .get(i + 1..i + 3)
+      .and_then(|hex| std::str::from_utf8(hex).ok())
+      .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+    match (bytes[i], hex) {
+      (b'%', Some(byte)) => {
+        decoded.push(byte);
+        i += 3;
+      },
+      (byte, _) => {
+        decoded.push(byte);
+        i += 1;
+      },
+    }
+  }
+  String::from_utf8_lossy(&decoded).to_string()
+}
+
+fn extension(path: &Path) -> Option<String> {
+  path
+    .extension()
+    .map(|ext| ext.to_string_lossy().to_lowercase())
+}
+
+fn file_name(path: &Path, without_extension: bool) -> String {
+  let name = if without_extension {
+    path.file_stem()
+  } else {
+    path.file_name()
+  };
+  name
+    .map(|name| name.to_string_lossy().to_string())
+    .unwrap_or_default()
+}
+
+// The files that are added by the archivers, e.g. `.DS_Store` and `__MACOSX`.
+fn is_hidden(path: &Path) -> bool {
+  let name = file_name(path, false);
+  name.starts_with('.') || name == "__MACOSX"
+}
@@ -14,7 +14,10 @@ use lib_infra::future::FutureResult;
 use lib_infra::util::timestamp;
 
 use crate::entities::{CreateViewParams, ViewLayoutPB};
-use crate::share::ImportType;
+use crate::share::{
+  CSVImportOptions, DocumentExportFormat, ExportedFile, ImportType, ImportedData, MarkdownPage,
+  PublishedHtml, RowDocument,
+};
 
 pub type ViewData = Bytes;
 
@@ -88,7 +91,7 @@ pub trait FolderOperationHandler {
 
   /// Create a view by importing data
   ///
-  /// The return value
+  /// The csv options are only provided when importing a csv file.
   fn import_from_bytes(
     &self,
     uid: i64,
@@ -96,20 +99,102 @@ pub trait FolderOperationHandler {
     name: &str,
     import_type: ImportType,
     bytes: Vec<u8>,
-  ) -> FutureResult<EncodedCollab, FlowyError>;
+    csv_options: Option<CSVImportOptions>,
+  ) -> FutureResult<ImportedData, FlowyError>;
 
-  /// Create a view by importing data from a file
+  /// Create a view by importing data from a file. Returns None if the view is created without
+  /// data.
+  ///
+  /// The csv options are only provided when importing a csv file.
   fn import_from_file_path(
     &self,
     view_id: &str,
     name: &str,
     path: String,
-  ) -> FutureResult<(), FlowyError>;
+    csv_options: Option<CSVImportOptions>,
+  ) -> FutureResult<Option<ImportedData>, FlowyError>;
+
+  /// Create a document by importing a markdown page of a zip, see [ImportType::Zip]. The links
+  /// to the other pages of the zip are converted to mentions, and the attachments that the page
+  /// links to are uploaded.
+  fn import_markdown_page(
+    &self,
+    _uid: i64,
+    _view_id: &str,
+    _page: MarkdownPage,
+  ) -> FutureResult<EncodedCollab, FlowyError> {
+    FutureResult::new(async move { Err(FlowyError::not_support()) })
+  }
+
+  /// Returns the documents of the rows of the database view. The views without rows return no
+  /// documents.
+  fn get_row_documents(&self, _view_id: &str) -> FutureResult<Vec<RowDocument>, FlowyError> {
+    FutureResult::new(async move { Ok(vec![]) })
+  }
+
+  /// Called when content is imported to the documents of the rows of the database view.
+  fn did_import_row_documents(
+    &self,
+    _view_id: &str,
+    _row_ids: Vec<String>,
+  ) -> FutureResult<(), FlowyError> {
+    FutureResult::new(async move { Ok(()) })
+  }
+
+  /// Exports the view to files of an open format, e.g. a markdown file for a document. The paths
+  /// of the files start with `file_stem`. The views that can't be exported return no files.
+  fn export_view(
+    &self,
+    _view_id: &str,
+    _file_stem: &str,
+    _document_format: DocumentExportFormat,
+  ) -> FutureResult<Vec<ExportedFile>, FlowyError> {
+    FutureResult::new(async move { Ok(vec![]) })
+  }
+
+  /// Renders the view to the body of a page of a static site. `data` is the doc_state of the
+  /// view that is returned by [crate::manager::FolderManager::get_batch_publish_payload]; it's
+  /// None for the views that aren't in the payload, e.g. the databases.
+  fn publish_view_html(
+    &self,
+    _view_id: &str,
+    _data: Option<Vec<u8>>,
+  ) -> FutureResult<PublishedHtml, FlowyError> {
+    FutureResult::new(async move { Err(FlowyError::not_support()) })
+  }
 
   /// Called when the view is updated. The handler is the `old` registered handler.
   fn did_update_view(&self, _old: &View, _new: &View) -> FutureResult<(), FlowyError> {
     FutureResult::new(async move { Ok(()) })
   }
+
+  /// Applies the change of the `target_view_id` view to its references in the `view_id` view,
+  /// e.g. the page mentions of a document. `block_ids` are the blocks that contain the
+  /// references, see [crate::manager::FolderManager::get_view_backlinks].
+  fn update_view_references(
+    &self,
+    _view_id: &str,
+    _block_ids: Vec<String>,
+    _target_view_id: &str,
+    _update: ViewReferenceUpdate,
+  ) -> FutureResult<(), FlowyError> {
+    FutureResult::new(async move { Ok(()) })
+  }
+}
+
+/// The change of a view that is applied to the views that reference it, see
+/// [FolderOperationHandler::update_view_references].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ViewReferenceUpdate {
+  Renamed {
+    name: String,
+  },
+  Trashed,
+  Restored,
+  /// The view is deleted permanently, `name` is the last name of the view.
+  Deleted {
+    name: String,
+  },
 }
 
 pub type FolderOperationHandlers =
@@ -45,3 +45,81 @@ pub trait FolderIndexManager: IndexManager {
     workspace_id: String,
   );
 }
+
+pub struct DocumentBlockIndexData {
+  pub block_id: String,
+  pub text: String,
+}
+
+pub struct DocumentIndexData {
+  pub document_id: String,
+  pub workspace_id: String,
+  pub blocks: Vec<DocumentBlockIndexData>,
+}
+
+pub trait DocumentIndexManager: Send + Sync {
+  /// Replaces the indexed content of each document with the given blocks.
+  fn index_documents(&self, documents: Vec<DocumentIndexData>) -> Result<(), FlowyError>;
+  fn remove_indices(&self, document_ids: Vec<String>) -> Result<(), FlowyError>;
+  fn remove_indices_for_workspace(&self, workspace_id: String) -> Result<(), FlowyError>;
+  fn is_document_indexed(&self, document_id: &str) -> bool;
+
+  fn as_any(&self) -> &dyn Any;
+}
+
+pub struct DatabaseCellIndexData {
+  pub field_id: String,
+  pub field_name: String,
+  pub text: String,
+}
+
+pub struct DatabaseRowIndexData {
+  pub database_id: String,
+  pub row_id: String,
+  pub workspace_id: String,
+  pub cells: Vec<DatabaseCellIndexData>,
+}
+
+pub trait DatabaseIndexManager: Send + Sync {
+  /// Replaces the indexed cells of each row with the given cells.
+  fn index_rows(&self, rows: Vec<DatabaseRowIndexData>) -> Result<(), FlowyError>;
+  /// Marks the database as indexed, even if it doesn't have any rows.
+  fn mark_database_indexed(&self, database_id: &str, workspace_id: &str) -> Result<(), FlowyError>;
+  fn remove_rows(&self, row_ids: Vec<String>) -> Result<(), FlowyError>;
+  fn remove_indices_for_workspace(&self, workspace_id: String) -> Result<(), FlowyError>;
+  fn is_database_indexed(&self, database_id: &str) -> bool;
+
+  fn as_any(&self) -> &dyn Any;
+}
+
+/// A reference to a view, e.g. a page mention in a block of a document or a url cell of a row
+/// that links to the view.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ViewReferenceData {
+  /// The view that contains the reference.
+  pub source_view_id: String,
+  /// The id of the block of the document, or the id of the row of the database, that contains
+  /// the reference.
+  pub block_id: String,
+  pub target_view_id: String,
+}
+
+pub struct BacklinkIndexData {
+  /// The document or the row that contains the references.
+  pub object_id: String,
+  pub workspace_id: String,
+  pub references: Vec<ViewReferenceData>,
+}
+
+pub trait BacklinkIndexManager: Send + Sync {
+  /// Replaces the indexed references of each object with the given references. The object is
+  /// marked as indexed, even if it doesn't contain any references.
+  fn index_references(&self, objects: Vec<BacklinkIndexData>) -> Result<(), FlowyError>;
+  fn remove_references(&self, object_ids: Vec<String>) -> Result<(), FlowyError>;
+  fn remove_indices_for_workspace(&self, workspace_id: String) -> Result<(), FlowyError>;
+  fn is_object_indexed(&self, object_id: &str) -> bool;
+  /// Returns the references to the view.
+  fn get_backlinks(&self, target_view_id: &str) -> Result<Vec<ViewReferenceData>, FlowyError>;
+
+  fn as_any(&self) -> &dyn Any;
+}
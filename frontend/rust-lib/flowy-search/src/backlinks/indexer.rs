@@ -0,0 +1,288 @@
+use std::{
+  any::Any,
+  fs,
+  ops::Deref,
+  path::Path,
+  sync::{Arc, Mutex, MutexGuard, Weak},
+};
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::{BacklinkIndexData, BacklinkIndexManager, ViewReferenceData};
+use flowy_user::services::authenticate_user::AuthenticateUser;
+use tantivy::{
+  collector::{Count, TopDocs},
+  directory::MmapDirectory,
+  doc,
+  query::{BooleanQuery, Occur, Query, TermQuery},
+  schema::{Field, IndexRecordOption},
+  Index, IndexReader, IndexWriter, TantivyDocument, Term,
+};
+
+use crate::util::{get_text, remove_outdated_indexes};
+
+use super::schema::{
+  BacklinkSchema, BACKLINK_BLOCK_ID_FIELD_NAME, BACKLINK_OBJECT_ID_FIELD_NAME,
+  BACKLINK_SCHEMA_VERSION, BACKLINK_SOURCE_VIEW_ID_FIELD_NAME, BACKLINK_TARGET_VIEW_ID_FIELD_NAME,
+  BACKLINK_WORKSPACE_ID_FIELD_NAME,
+};
+
+const BACKLINK_INDEX_DIR: &str = "backlink_index";
+const MAX_BACKLINKS: usize = 1000;
+
+struct SchemaFields {
+  object_id: Field,
+  source_view_id: Field,
+  block_id: Field,
+  target_view_id: Field,
+  workspace_id: Field,
+}
+
+/// Indexes the references of the documents and the rows of the databases to the views, each
+/// reference is stored as a separate tantivy document, so that the references to a view can be
+/// looked up by the id of the view.
+///
+#[derive(Clone)]
+pub struct BacklinkIndexManagerImpl {
+  backlink_schema: Option<BacklinkSchema>,
+  index_reader: Option<IndexReader>,
+  index_writer: Option<Arc<Mutex<IndexWriter>>>,
+}
+
+impl BacklinkIndexManagerImpl {
+  pub fn new(auth_user: Option<Weak<AuthenticateUser>>) -> Self {
+    let auth_user = match auth_user {
+      Some(auth_user) => auth_user,
+      None => {
+        return BacklinkIndexManagerImpl::empty();
+      },
+    };
+
+    // Storage path is the users data path with an index directory
+    // Eg. /usr/flowy-data/indexes
+    let storage_path = match auth_user.upgrade() {
+      Some(auth_user) => auth_user.get_index_path(),
+      None => {
+        tracing::error!("BacklinkIndexManager: AuthenticateUser is not available");
+        return BacklinkIndexManagerImpl::empty();
+      },
+    };
+
+    // Each version of the schema has its own directory
+    // Eg. /usr/flowy-data/indexes/backlink_index/v1
+    let index_dir = storage_path.join(Path::new(BACKLINK_INDEX_DIR));
+    let version_dir_name = format!("v{}", BACKLINK_SCHEMA_VERSION);
+    remove_outdated_indexes(&index_dir, &version_dir_name);
+
+    let index_path = index_dir.join(version_dir_name);
+    if !index_path.exists() {
+      if let Err(e) = fs::create_dir_all(&index_path) {
+        tracing::error!(
+          "BacklinkIndexManager failed to create index directory: {:?}",
+          e
+        );
+        return BacklinkIndexManagerImpl::empty();
+      }
+    }
+
+    let backlink_schema = BacklinkSchema::new();
+    let index = match MmapDirectory::open(index_path) {
+      Ok(dir) => match Index::open_or_create(dir, backlink_schema.schema.clone()) {
+        Ok(index) => index,
+        Err(e) => {
+          tracing::error!("BacklinkIndexManager failed to open index: {:?}", e);
+          return BacklinkIndexManagerImpl::empty();
+        },
+      },
+      Err(e) => {
+        tracing::error!(
+          "BacklinkIndexManager failed to open index directory: {:?}",
+          e
+        );
+        return BacklinkIndexManagerImpl::empty();
+      },
+    };
+
+    let index_reader = index.reader();
+    let index_writer = index.writer(15_000_000);
+
+    let (index_reader, index_writer) = match (index_reader, index_writer) {
+      (Ok(reader), Ok(writer)) => (reader, writer),
+      _ => {
+        tracing::error!("BacklinkIndexManager failed to instantiate index writer and/or reader");
+        return BacklinkIndexManagerImpl::empty();
+      },
+    };
+
+    Self {
+      backlink_schema: Some(backlink_schema),
+      index_reader: Some(index_reader),
+      index_writer: Some(Arc::new(Mutex::new(index_writer))),
+    }
+  }
+
+  fn empty() -> Self {
+    Self {
+      backlink_schema: None,
+      index_reader: None,
+      index_writer: None,
+    }
+  }
+
+  fn get_index_writer(&self) -> FlowyResult<MutexGuard<IndexWriter>> {
+    match &self.index_writer {
+      Some(index_writer) => match index_writer.deref().lock() {
+        Ok(writer) => Ok(writer),
+        Err(e) => {
+          tracing::error!("BacklinkIndexManager failed to lock index writer: {:?}", e);
+          Err(FlowyError::backlink_index_manager_unavailable())
+        },
+      },
+      None => Err(FlowyError::backlink_index_manager_unavailable()),
+    }
+  }
+
+  fn get_schema_fields(&self) -> FlowyResult<SchemaFields> {
+    let schema = match &self.backlink_schema {
+      Some(backlink_schema) => &backlink_schema.schema,
+      None => return Err(FlowyError::backlink_index_manager_unavailable()),
+    };
+
+    Ok(SchemaFields {
+      object_id: schema.get_field(BACKLINK_OBJECT_ID_FIELD_NAME)?,
+      source_view_id: schema.get_field(BACKLINK_SOURCE_VIEW_ID_FIELD_NAME)?,
+      block_id: schema.get_field(BACKLINK_BLOCK_ID_FIELD_NAME)?,
+      target_view_id: schema.get_field(BACKLINK_TARGET_VIEW_ID_FIELD_NAME)?,
+      workspace_id: schema.get_field(BACKLINK_WORKSPACE_ID_FIELD_NAME)?,
+    })
+  }
+
+  /// Commits the pending changes and reloads the reader, so that the changes are visible
+  /// to the next lookup.
+  fn commit(&self, mut index_writer: MutexGuard<IndexWriter>) -> FlowyResult<()> {
+    index_writer.commit()?;
+    drop(index_writer);
+    if let Some(index_reader) = &self.index_reader {
+      index_reader.reload()?;
+    }
+    Ok(())
+  }
+}
+
+impl BacklinkIndexManager for BacklinkIndexManagerImpl {
+  fn index_references(&self, objects: Vec<BacklinkIndexData>) -> Result<(), FlowyError> {
+    if objects.is_empty() {
+      return Ok(());
+    }
+
+    let index_writer = self.get_index_writer()?;
+    let fields = self.get_schema_fields()?;
+    for object in objects {
+      // Remove old index
+      index_writer.delete_term(Term::from_field_text(fields.object_id, &object.object_id));
+
+      // A reference without target, it marks the object as indexed
+      let _ = index_writer.add_document(doc![
+        fields.object_id => object.object_id.clone(),
+        fields.source_view_id => "",
+        fields.block_id => "",
+        fields.target_view_id => "",
+        fields.workspace_id => object.workspace_id.clone(),
+      ]);
+
+      for reference in object.references {
+        let _ = index_writer.add_document(doc![
+          fields.object_id => object.object_id.clone(),
+          fields.source_view_id => reference.source_view_id,
+          fields.block_id => reference.block_id,
+          fields.target_view_id => reference.target_view_id,
+          fields.workspace_id => object.workspace_id.clone(),
+        ]);
+      }
+    }
+
+    self.commit(index_writer)
+  }
+
+  fn remove_references(&self, object_ids: Vec<String>) -> Result<(), FlowyError> {
+    let index_writer = self.get_index_writer()?;
+    let fields = self.get_schema_fields()?;
+    for object_id in object_ids {
+      index_writer.delete_term(Term::from_field_text(fields.object_id, &object_id));
+    }
+
+    self.commit(index_writer)
+  }
+
+  /// Removes all indexes that are related by workspace id. This is useful
+  /// for cleaning indexes when eg. removing/leaving a workspace.
+  ///
+  fn remove_indices_for_workspace(&self, workspace_id: String) -> Result<(), FlowyError> {
+    let index_writer = self.get_index_writer()?;
+    let fields = self.get_schema_fields()?;
+    index_writer.delete_term(Term::from_field_text(fields.workspace_id, &workspace_id));
+
+    self.commit(index_writer)
+  }
+
+  fn is_object_indexed(&self, object_id: &str) -> bool {
+    let (index_reader, fields) = match (&self.index_reader, self.get_schema_fields()) {
+      (Some(index_reader), Ok(fields)) => (index_reader, fields),
+      _ => return false,
+    };
+
+    let query = BooleanQuery::new(vec![
+      (
+        Occur::Must,
+        Box::new(TermQuery::new(
+          Term::from_field_text(fields.object_id, object_id),
+          IndexRecordOption::Basic,
+        )) as Box<dyn Query>,
+      ),
+      (
+        Occur::Must,
+        Box::new(TermQuery::new(
+          Term::from_field_text(fields.target_view_id, ""),
+          IndexRecordOption::Basic,
+        )),
+      ),
+    ]);
+    index_reader
+      .searcher()
+      .search(&query, &Count)
+      .map(|count| count > 0)
+      .unwrap_or(false)
+  }
+
+  fn get_backlinks(&self, target_view_id: &str) -> Result<Vec<ViewReferenceData>, FlowyError> {
+    if target_view_id.is_empty() {
+      return Ok(vec![]);
+    }
+
+    let index_reader = self
+      .index_reader
+      .as_ref()
+      .ok_or_else(FlowyError::backlink_index_manager_unavailable)?;
+    let fields = self.get_schema_fields()?;
+    let query = TermQuery::new(
+      Term::from_field_text(fields.target_view_id, target_view_id),
+      IndexRecordOption::Basic,
+    );
+
+    let searcher = index_reader.searcher();
+    let top_docs = searcher.search(&query, &TopDocs::with_limit(MAX_BACKLINKS))?;
+    let mut references = vec![];
+    for (_, doc_address) in top_docs {
+      let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
+      references.push(ViewReferenceData {
+        source_view_id: get_text(&retrieved_doc, fields.source_view_id),
+        block_id: get_text(&retrieved_doc, fields.block_id),
+        target_view_id: get_text(&retrieved_doc, fields.target_view_id),
+      });
+    }
+    Ok(references)
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+}
@@ -0,0 +1,2 @@
+pub mod indexer;
+pub mod schema;
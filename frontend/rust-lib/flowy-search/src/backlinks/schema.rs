@@ -0,0 +1,54 @@
+use tantivy::schema::Schema;
+
+pub const BACKLINK_OBJECT_ID_FIELD_NAME: &str = "object_id";
+pub const BACKLINK_SOURCE_VIEW_ID_FIELD_NAME: &str = "source_view_id";
+pub const BACKLINK_BLOCK_ID_FIELD_NAME: &str = "block_id";
+pub const BACKLINK_TARGET_VIEW_ID_FIELD_NAME: &str = "target_view_id";
+pub const BACKLINK_WORKSPACE_ID_FIELD_NAME: &str = "workspace_id";
+
+/// The version of the [BacklinkSchema]. The index is stored in a directory named after
+/// the version, see `BacklinkIndexManagerImpl::new`.
+pub const BACKLINK_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone)]
+pub struct BacklinkSchema {
+  pub schema: Schema,
+}
+
+/// Do not change the schema after the index has been created.
+/// Changing field_options or fields, will result in the schema being different
+/// from previously created index, causing tantivy to panic.
+///
+/// If you need to change the schema, bump the [BACKLINK_SCHEMA_VERSION]. The indexes of
+/// the older versions will be removed, and the references will be indexed again with the
+/// new schema.
+///
+impl BacklinkSchema {
+  pub fn new() -> Self {
+    let mut schema_builder = Schema::builder();
+    schema_builder.add_text_field(
+      BACKLINK_OBJECT_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(BACKLINK_SOURCE_VIEW_ID_FIELD_NAME, tantivy::schema::STORED);
+    schema_builder.add_text_field(BACKLINK_BLOCK_ID_FIELD_NAME, tantivy::schema::STORED);
+    schema_builder.add_text_field(
+      BACKLINK_TARGET_VIEW_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      BACKLINK_WORKSPACE_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+
+    let schema = schema_builder.build();
+
+    Self { schema }
+  }
+}
+
+impl Default for BacklinkSchema {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,16 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+pub struct DatabaseSearchResult {
+  pub database_id: String,
+  pub row_id: String,
+  /// The id of the field whose cell matches the query best.
+  pub field_id: String,
+  pub field_name: String,
+  pub workspace_id: String,
+  /// A fragment of the cell's content around the matched terms.
+  pub preview: String,
+  /// The char ranges of the matched terms within the preview.
+  pub highlights: Vec<Range<usize>>,
+  pub score: f64,
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::trace;
+
+use flowy_database2::DatabaseManager;
+use flowy_error::FlowyResult;
+use flowy_folder::manager::FolderManager;
+use lib_infra::async_trait::async_trait;
+
+use crate::{
+  entities::{
+    IndexTypePB, ResultIconPB, SearchFilterPB, SearchHighlightPB, SearchResultFieldPB,
+    SearchResultPB,
+  },
+  services::{
+    filter::ViewFilter,
+    manager::{SearchHandler, SearchType},
+    query::SearchQuery,
+  },
+};
+
+use super::indexer::DatabaseIndexManagerImpl;
+
+pub struct DatabaseSearchHandler {
+  pub index_manager: Arc<DatabaseIndexManagerImpl>,
+  pub folder_manager: Arc<FolderManager>,
+  pub database_manager: Arc<DatabaseManager>,
+}
+
+impl DatabaseSearchHandler {
+  pub fn new(
+    index_manager: Arc<DatabaseIndexManagerImpl>,
+    folder_manager: Arc<FolderManager>,
+    database_manager: Arc<DatabaseManager>,
+  ) -> Self {
+    Self {
+      index_manager,
+      folder_manager,
+      database_manager,
+    }
+  }
+}
+
+#[async_trait]
+impl SearchHandler for DatabaseSearchHandler {
+  fn search_type(&self) -> SearchType {
+    SearchType::Database
+  }
+
+  async fn perform_search(
+    &self,
+    query: SearchQuery,
+    filter: Option<SearchFilterPB>,
+  ) -> FlowyResult<Vec<SearchResultPB>> {
+    // The cells are only searched for the words and phrases of the query
+    if query.is_text_empty() {
+      return Ok(vec![]);
+    }
+
+    let view_filter = ViewFilter::new(filter.as_ref(), &query);
+    let workspace_id = filter.and_then(|filter| filter.workspace_id);
+    let results = self
+      .index_manager
+      .search(&query.to_tantivy_query(), workspace_id.as_deref())?;
+    trace!("[Search] database search results: {:?}", results);
+    if results.is_empty() {
+      return Ok(vec![]);
+    }
+
+    // Grab all views from folder cache
+    // Notice that `get_all_view_pb` returns Views that don't include trashed and private views
+    let views = self.folder_manager.get_all_views_pb().await?;
+    let views_by_id = views
+      .iter()
+      .map(|view| (view.id.as_str(), view))
+      .collect::<HashMap<_, _>>();
+    let database_metas = self.database_manager.get_all_databases_meta().await;
+    let mut search_results: Vec<SearchResultPB> = vec![];
+
+    for result in results {
+      // The row is shown in the first accessible view of its database that matches the
+      // filter. If there is no such view, we don't add it to the results
+      let view = database_metas
+        .iter()
+        .find(|meta| meta.database_id == result.database_id)
+        .and_then(|meta| {
+          meta.linked_views.iter().find_map(|view_id| {
+            views_by_id
+              .get(view_id.as_str())
+              .copied()
+              .filter(|view| view_filter.matches(view, &views_by_id))
+          })
+        });
+
+      match view {
+        Some(view) => search_results.push(SearchResultPB {
+          index_type: IndexTypePB::DatabaseRow,
+          view_id: view.id.clone(),
+          id: result.row_id,
+          data: view.name.clone(),
+          icon: Some(ResultIconPB::from(view)),
+          score: result.score,
+          workspace_id: result.workspace_id,
+          preview: Some(result.preview),
+          highlights: result
+            .highlights
+            .into_iter()
+            .map(SearchHighlightPB::from)
+            .collect(),
+          field: Some(SearchResultFieldPB {
+            field_id: result.field_id,
+            name: result.field_name,
+          }),
+        }),
+        None => trace!("No view found for database search result: {:?}", result),
+      }
+    }
+
+    Ok(search_results)
+  }
+
+  /// Returns the number of indexed cells
+  fn index_count(&self) -> u64 {
+    self.index_manager.num_docs()
+  }
+}
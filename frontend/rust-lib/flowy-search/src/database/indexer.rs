@@ -0,0 +1,369 @@
+use std::{
+  any::Any,
+  collections::HashSet,
+  fs,
+  ops::Deref,
+  path::Path,
+  sync::{Arc, Mutex, MutexGuard, Weak},
+};
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::{DatabaseIndexManager, DatabaseRowIndexData};
+use flowy_user::services::authenticate_user::AuthenticateUser;
+use tantivy::{
+  collector::{Count, TopDocs},
+  directory::MmapDirectory,
+  doc,
+  query::{BooleanQuery, Occur, Query, QueryParser, TermQuery},
+  schema::{Field, IndexRecordOption},
+  Index, IndexReader, IndexWriter, SnippetGenerator, TantivyDocument, Term,
+};
+
+use crate::util::{get_text, preview_from_snippet, remove_outdated_indexes};
+
+use super::{
+  entities::DatabaseSearchResult,
+  schema::{
+    DatabaseSchema, DATABASE_CONTENT_FIELD_NAME, DATABASE_FIELD_ID_FIELD_NAME,
+    DATABASE_FIELD_NAME_FIELD_NAME, DATABASE_ID_FIELD_NAME, DATABASE_ROW_ID_FIELD_NAME,
+    DATABASE_SCHEMA_VERSION, DATABASE_WORKSPACE_ID_FIELD_NAME,
+  },
+};
+
+const DATABASE_INDEX_DIR: &str = "database_index";
+/// The number of matched cells that are collected before picking the best cell of each row.
+const MAX_MATCHED_CELLS: usize = 100;
+const MAX_SEARCH_RESULTS: usize = 10;
+const PREVIEW_MAX_CHARS: usize = 150;
+
+struct SchemaFields {
+  database_id: Field,
+  row_id: Field,
+  field_id: Field,
+  field_name: Field,
+  content: Field,
+  workspace_id: Field,
+}
+
+/// Indexes the stringified cells of the rows of the databases, each cell is stored as a
+/// separate tantivy document, so that a search result can point to the field that matches
+/// the query.
+///
+#[derive(Clone)]
+pub struct DatabaseIndexManagerImpl {
+  database_schema: Option<DatabaseSchema>,
+  index: Option<Index>,
+  index_reader: Option<IndexReader>,
+  index_writer: Option<Arc<Mutex<IndexWriter>>>,
+}
+
+impl DatabaseIndexManagerImpl {
+  pub fn new(auth_user: Option<Weak<AuthenticateUser>>) -> Self {
+    let auth_user = match auth_user {
+      Some(auth_user) => auth_user,
+      None => {
+        return DatabaseIndexManagerImpl::empty();
+      },
+    };
+
+    // Storage path is the users data path with an index directory
+    // Eg. /usr/flowy-data/indexes
+    let storage_path = match auth_user.upgrade() {
+      Some(auth_user) => auth_user.get_index_path(),
+      None => {
+        tracing::error!("DatabaseIndexManager: AuthenticateUser is not available");
+        return DatabaseIndexManagerImpl::empty();
+      },
+    };
+
+    // Each version of the schema has its own directory
+    // Eg. /usr/flowy-data/indexes/database_index/v1
+    let index_dir = storage_path.join(Path::new(DATABASE_INDEX_DIR));
+    let version_dir_name = format!("v{}", DATABASE_SCHEMA_VERSION);
+    remove_outdated_indexes(&index_dir, &version_dir_name);
+
+    let index_path = index_dir.join(version_dir_name);
+    if !index_path.exists() {
+      if let Err(e) = fs::create_dir_all(&index_path) {
+        tracing::error!(
+          "DatabaseIndexManager failed to create index directory: {:?}",
+          e
+        );
+        return DatabaseIndexManagerImpl::empty();
+      }
+    }
+
+    let database_schema = DatabaseSchema::new();
+    let index = match MmapDirectory::open(index_path) {
+      Ok(dir) => match Index::open_or_create(dir, database_schema.schema.clone()) {
+        Ok(index) => index,
+        Err(e) => {
+          tracing::error!("DatabaseIndexManager failed to open index: {:?}", e);
+          return DatabaseIndexManagerImpl::empty();
+        },
+      },
+      Err(e) => {
+        tracing::error!(
+          "DatabaseIndexManager failed to open index directory: {:?}",
+          e
+        );
+        return DatabaseIndexManagerImpl::empty();
+      },
+    };
+
+    let index_reader = index.reader();
+    let index_writer = index.writer(50_000_000);
+
+    let (index_reader, index_writer) = match (index_reader, index_writer) {
+      (Ok(reader), Ok(writer)) => (reader, writer),
+      _ => {
+        tracing::error!("DatabaseIndexManager failed to instantiate index writer and/or reader");
+        return DatabaseIndexManagerImpl::empty();
+      },
+    };
+
+    Self {
+      database_schema: Some(database_schema),
+      index: Some(index),
+      index_reader: Some(index_reader),
+      index_writer: Some(Arc::new(Mutex::new(index_writer))),
+    }
+  }
+
+  pub fn num_docs(&self) -> u64 {
+    self
+      .index_reader
+      .clone()
+      .map(|reader| reader.searcher().num_docs())
+      .unwrap_or(0)
+  }
+
+  fn empty() -> Self {
+    Self {
+      database_schema: None,
+      index: None,
+      index_reader: None,
+      index_writer: None,
+    }
+  }
+
+  fn get_index_writer(&self) -> FlowyResult<MutexGuard<IndexWriter>> {
+    match &self.index_writer {
+      Some(index_writer) => match index_writer.deref().lock() {
+        Ok(writer) => Ok(writer),
+        Err(e) => {
+          tracing::error!("DatabaseIndexManager failed to lock index writer: {:?}", e);
+          Err(FlowyError::database_index_manager_unavailable())
+        },
+      },
+      None => Err(FlowyError::database_index_manager_unavailable()),
+    }
+  }
+
+  fn get_schema_fields(&self) -> FlowyResult<SchemaFields> {
+    let schema = match &self.database_schema {
+      Some(database_schema) => &database_schema.schema,
+      None => return Err(FlowyError::database_index_manager_unavailable()),
+    };
+
+    Ok(SchemaFields {
+      database_id: schema.get_field(DATABASE_ID_FIELD_NAME)?,
+      row_id: schema.get_field(DATABASE_ROW_ID_FIELD_NAME)?,
+      field_id: schema.get_field(DATABASE_FIELD_ID_FIELD_NAME)?,
+      field_name: schema.get_field(DATABASE_FIELD_NAME_FIELD_NAME)?,
+      content: schema.get_field(DATABASE_CONTENT_FIELD_NAME)?,
+      workspace_id: schema.get_field(DATABASE_WORKSPACE_ID_FIELD_NAME)?,
+    })
+  }
+
+  /// Commits the pending changes and reloads the reader, so that the changes are visible
+  /// to the next search.
+  fn commit(&self, mut index_writer: MutexGuard<IndexWriter>) -> FlowyResult<()> {
+    index_writer.commit()?;
+    drop(index_writer);
+    if let Some(index_reader) = &self.index_reader {
+      index_reader.reload()?;
+    }
+    Ok(())
+  }
+
+  /// Returns the best matching cell of each row, ordered by relevance. If the workspace_id
+  /// is given, only the databases of that workspace are searched.
+  pub fn search(
+    &self,
+    query: &str,
+    workspace_id: Option<&str>,
+  ) -> Result<Vec<DatabaseSearchResult>, FlowyError> {
+    if query.trim().is_empty() {
+      return Ok(vec![]);
+    }
+
+    let (index, index_reader) = self
+      .index
+      .as_ref()
+      .zip(self.index_reader.as_ref())
+      .ok_or_else(FlowyError::database_index_manager_unavailable)?;
+    let fields = self.get_schema_fields()?;
+
+    // All the terms of the query must appear in the cell. Syntax errors in the query
+    // are ignored, since the query is typed by the user.
+    let mut query_parser = QueryParser::for_index(index, vec![fields.content]);
+    query_parser.set_conjunction_by_default();
+    let (content_query, _) = query_parser.parse_query_lenient(query);
+
+    let searcher = index_reader.searcher();
+    let mut snippet_generator =
+      SnippetGenerator::create(&searcher, &*content_query, fields.content)?;
+    snippet_generator.set_max_num_chars(PREVIEW_MAX_CHARS);
+
+    let built_query: Box<dyn Query> = match workspace_id {
+      Some(workspace_id) => Box::new(BooleanQuery::new(vec![
+        (Occur::Must, content_query),
+        (
+          Occur::Must,
+          Box::new(TermQuery::new(
+            Term::from_field_text(fields.workspace_id, workspace_id),
+            IndexRecordOption::Basic,
+          )),
+        ),
+      ])),
+      None => content_query,
+    };
+
+    let top_docs = searcher.search(&built_query, &TopDocs::with_limit(MAX_MATCHED_CELLS))?;
+    let mut row_ids = HashSet::new();
+    let mut search_results = vec![];
+    for (score, doc_address) in top_docs {
+      let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
+      let row_id = get_text(&retrieved_doc, fields.row_id);
+      if !row_ids.insert(row_id.clone()) {
+        // The row already has a cell with a higher score
+        continue;
+      }
+
+      let snippet = snippet_generator.snippet_from_doc(&retrieved_doc);
+      let content = get_text(&retrieved_doc, fields.content);
+      let (preview, highlights) = preview_from_snippet(&snippet, &content, PREVIEW_MAX_CHARS);
+
+      search_results.push(DatabaseSearchResult {
+        database_id: get_text(&retrieved_doc, fields.database_id),
+        row_id,
+        field_id: get_text(&retrieved_doc, fields.field_id),
+        field_name: get_text(&retrieved_doc, fields.field_name),
+        workspace_id: get_text(&retrieved_doc, fields.workspace_id),
+        preview,
+        highlights,
+        // The BM25 score is unbounded, map it into [0, 1) to make it comparable with the
+        // scores of the other search handlers.
+        score: score as f64 / (1.0 + score as f64),
+      });
+
+      if search_results.len() >= MAX_SEARCH_RESULTS {
+        break;
+      }
+    }
+
+    Ok(search_results)
+  }
+}
+
+impl DatabaseIndexManager for DatabaseIndexManagerImpl {
+  fn index_rows(&self, rows: Vec<DatabaseRowIndexData>) -> Result<(), FlowyError> {
+    if rows.is_empty() {
+      return Ok(());
+    }
+
+    let index_writer = self.get_index_writer()?;
+    let fields = self.get_schema_fields()?;
+    for row in rows {
+      // Remove old index
+      index_writer.delete_term(Term::from_field_text(fields.row_id, &row.row_id));
+
+      for cell in row.cells {
+        let _ = index_writer.add_document(doc![
+          fields.database_id => row.database_id.clone(),
+          fields.row_id => row.row_id.clone(),
+          fields.field_id => cell.field_id,
+          fields.field_name => cell.field_name,
+          fields.content => cell.text,
+          fields.workspace_id => row.workspace_id.clone(),
+        ]);
+      }
+    }
+
+    self.commit(index_writer)
+  }
+
+  fn mark_database_indexed(&self, database_id: &str, workspace_id: &str) -> Result<(), FlowyError> {
+    let index_writer = self.get_index_writer()?;
+    let fields = self.get_schema_fields()?;
+
+    // A cell without row, it never matches a query
+    let _ = index_writer.add_document(doc![
+      fields.database_id => database_id,
+      fields.row_id => "",
+      fields.field_id => "",
+      fields.field_name => "",
+      fields.content => "",
+      fields.workspace_id => workspace_id,
+    ]);
+
+    self.commit(index_writer)
+  }
+
+  fn remove_rows(&self, row_ids: Vec<String>) -> Result<(), FlowyError> {
+    let index_writer = self.get_index_writer()?;
+    let fields = self.get_schema_fields()?;
+    for row_id in row_ids {
+      index_writer.delete_term(Term::from_field_text(fields.row_id, &row_id));
+    }
+
+    self.commit(index_writer)
+  }
+
+  /// Removes all indexes that are related by workspace id. This is useful
+  /// for cleaning indexes when eg. removing/leaving a workspace.
+  ///
+  fn remove_indices_for_workspace(&self, workspace_id: String) -> Result<(), FlowyError> {
+    let index_writer = self.get_index_writer()?;
+    let fields = self.get_schema_fields()?;
+    index_writer.delete_term(Term::from_field_text(fields.workspace_id, &workspace_id));
+
+    self.commit(index_writer)
+  }
+
+  fn is_database_indexed(&self, database_id: &str) -> bool {
+    let (index_reader, fields) = match (&self.index_reader, self.get_schema_fields()) {
+      (Some(index_reader), Ok(fields)) => (index_reader, fields),
+      _ => return false,
+    };
+
+    // Only the marker of the database counts, the rows that were indexed while editing the
+    // database don't mean that all its rows are indexed.
+    let query = BooleanQuery::new(vec![
+      (
+        Occur::Must,
+        Box::new(TermQuery::new(
+          Term::from_field_text(fields.database_id, database_id),
+          IndexRecordOption::Basic,
+        )) as Box<dyn Query>,
+      ),
+      (
+        Occur::Must,
+        Box::new(TermQuery::new(
+          Term::from_field_text(fields.row_id, ""),
+          IndexRecordOption::Basic,
+        )),
+      ),
+    ]);
+    index_reader
+      .searcher()
+      .search(&query, &Count)
+      .map(|count| count > 0)
+      .unwrap_or(false)
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+}
@@ -0,0 +1,62 @@
+use tantivy::schema::Schema;
+
+pub const DATABASE_ID_FIELD_NAME: &str = "database_id";
+pub const DATABASE_ROW_ID_FIELD_NAME: &str = "row_id";
+pub const DATABASE_FIELD_ID_FIELD_NAME: &str = "field_id";
+pub const DATABASE_FIELD_NAME_FIELD_NAME: &str = "field_name";
+pub const DATABASE_CONTENT_FIELD_NAME: &str = "content";
+pub const DATABASE_WORKSPACE_ID_FIELD_NAME: &str = "workspace_id";
+
+/// The version of the [DatabaseSchema]. The index is stored in a directory named after
+/// the version, see `DatabaseIndexManagerImpl::new`.
+pub const DATABASE_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone)]
+pub struct DatabaseSchema {
+  pub schema: Schema,
+}
+
+/// Do not change the schema after the index has been created.
+/// Changing field_options or fields, will result in the schema being different
+/// from previously created index, causing tantivy to panic and search to stop functioning.
+///
+/// If you need to change the schema, bump the [DATABASE_SCHEMA_VERSION]. The indexes of
+/// the older versions will be removed, and the rows will be indexed again with the
+/// new schema.
+///
+impl DatabaseSchema {
+  pub fn new() -> Self {
+    let mut schema_builder = Schema::builder();
+    schema_builder.add_text_field(
+      DATABASE_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      DATABASE_ROW_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      DATABASE_FIELD_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(DATABASE_FIELD_NAME_FIELD_NAME, tantivy::schema::STORED);
+    schema_builder.add_text_field(
+      DATABASE_CONTENT_FIELD_NAME,
+      tantivy::schema::TEXT | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      DATABASE_WORKSPACE_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+
+    let schema = schema_builder.build();
+
+    Self { schema }
+  }
+}
+
+impl Default for DatabaseSchema {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,14 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+pub struct DocumentSearchResult {
+  pub document_id: String,
+  /// The id of the block that matches the query best.
+  pub block_id: String,
+  pub workspace_id: String,
+  /// A fragment of the block's content around the matched terms.
+  pub preview: String,
+  /// The char ranges of the matched terms within the preview.
+  pub highlights: Vec<Range<usize>>,
+  pub score: f64,
+}
@@ -1,29 +1,39 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{trace, warn};
 
 use flowy_error::FlowyResult;
-use flowy_folder::{manager::FolderManager, ViewLayout};
+use flowy_folder::manager::FolderManager;
 use flowy_search_pub::cloud::SearchCloudService;
 use lib_infra::async_trait::async_trait;
 
 use crate::{
-  entities::{IndexTypePB, ResultIconPB, ResultIconTypePB, SearchFilterPB, SearchResultPB},
-  services::manager::{SearchHandler, SearchType},
+  entities::{IndexTypePB, ResultIconPB, SearchFilterPB, SearchHighlightPB, SearchResultPB},
+  services::{
+    filter::ViewFilter,
+    manager::{SearchHandler, SearchType},
+    query::SearchQuery,
+  },
 };
 
+use super::indexer::DocumentIndexManagerImpl;
+
 pub struct DocumentSearchHandler {
   pub cloud_service: Arc<dyn SearchCloudService>,
   pub folder_manager: Arc<FolderManager>,
+  pub index_manager: Arc<DocumentIndexManagerImpl>,
 }
 
 impl DocumentSearchHandler {
   pub fn new(
     cloud_service: Arc<dyn SearchCloudService>,
     folder_manager: Arc<FolderManager>,
+    index_manager: Arc<DocumentIndexManagerImpl>,
   ) -> Self {
     Self {
       cloud_service,
       folder_manager,
+      index_manager,
     }
   }
 }
@@ -36,7 +46,7 @@ impl SearchHandler for DocumentSearchHandler {
 
   async fn perform_search(
     &self,
-    query: String,
+    query: SearchQuery,
     filter: Option<SearchFilterPB>,
   ) -> FlowyResult<Vec<SearchResultPB>> {
     let filter = match filter {
@@ -44,49 +54,98 @@ impl SearchHandler for DocumentSearchHandler {
       None => return Ok(vec![]),
     };
 
-    let workspace_id = match filter.workspace_id {
+    let workspace_id = match filter.workspace_id.clone() {
       Some(workspace_id) => workspace_id,
       None => return Ok(vec![]),
     };
 
-    let results = self
+    // The content of the documents is only searched for the words and phrases of the query
+    if query.is_text_empty() {
+      return Ok(vec![]);
+    }
+    let view_filter = ViewFilter::new(Some(&filter), &query);
+
+    let local_results = self
+      .index_manager
+      .search(&query.to_tantivy_query(), Some(&workspace_id))?;
+    trace!("[Search] local search results: {:?}", local_results);
+
+    // The cloud search is not available when using the local server, in which case
+    // only the local results are returned.
+    let remote_results = match self
       .cloud_service
-      .document_search(&workspace_id, query)
-      .await?;
-    trace!("[Search] remote search results: {:?}", results);
+      .document_search(&workspace_id, query.text())
+      .await
+    {
+      Ok(results) => results,
+      Err(err) => {
+        trace!("[Search] remote search is unavailable: {}", err);
+        vec![]
+      },
+    };
+    trace!("[Search] remote search results: {:?}", remote_results);
 
     // Grab all views from folder cache
     // Notice that `get_all_view_pb` returns Views that don't include trashed and private views
-    let mut views = self.folder_manager.get_all_views_pb().await?.into_iter();
+    let views = self.folder_manager.get_all_views_pb().await?;
+    let views_by_id = views
+      .iter()
+      .map(|view| (view.id.as_str(), view))
+      .collect::<HashMap<_, _>>();
     let mut search_results: Vec<SearchResultPB> = vec![];
 
-    for result in results {
-      if let Some(view) = views.find(|v| v.id == result.object_id) {
-        // If there is no View for the result, we don't add it to the results
-        // If possible we will extract the icon to display for the result
-        let icon: Option<ResultIconPB> = match view.icon.clone() {
-          Some(view_icon) => Some(ResultIconPB::from(view_icon)),
-          None => {
-            let view_layout_ty: i64 = ViewLayout::from(view.layout.clone()).into();
-            Some(ResultIconPB {
-              ty: ResultIconTypePB::Icon,
-              value: view_layout_ty.to_string(),
-            })
-          },
-        };
+    for result in local_results {
+      // If there is no View for the result, we don't add it to the results
+      if let Some(view) = views_by_id.get(result.document_id.as_str()) {
+        if !view_filter.matches(view, &views_by_id) {
+          continue;
+        }
+        search_results.push(SearchResultPB {
+          index_type: IndexTypePB::DocumentBlock,
+          view_id: result.document_id,
+          id: result.block_id,
+          data: view.name.clone(),
+          icon: Some(ResultIconPB::from(*view)),
+          score: result.score,
+          workspace_id: result.workspace_id,
+          preview: Some(result.preview),
+          highlights: result
+            .highlights
+            .into_iter()
+            .map(SearchHighlightPB::from)
+            .collect(),
+          field: None,
+        });
+      } else {
+        trace!("No view found for local search result: {:?}", result);
+      }
+    }
+
+    for result in remote_results {
+      // The local result is preferred, since it points to the matched block
+      if search_results.iter().any(|r| r.view_id == result.object_id) {
+        continue;
+      }
 
+      if let Some(view) = views_by_id.get(result.object_id.as_str()) {
+        if !view_filter.matches(view, &views_by_id) {
+          continue;
+        }
+        // If there is no View for the result, we don't add it to the results
         search_results.push(SearchResultPB {
           index_type: IndexTypePB::Document,
           view_id: result.object_id.clone(),
           id: result.object_id.clone(),
           data: view.name.clone(),
-          icon,
+          icon: Some(ResultIconPB::from(*view)),
           // We reverse the score, the cloud search score is based on
           // 1 being the worst result, and closer to 0 being good result, that is
           // the opposite of local search.
           score: 1.0 - result.score,
           workspace_id: result.workspace_id,
           preview: result.preview,
+          highlights: vec![],
+          field: None,
         });
       } else {
         warn!("No view found for search result: {:?}", result);
@@ -97,8 +156,8 @@ impl SearchHandler for DocumentSearchHandler {
     Ok(search_results)
   }
 
-  /// Ignore for [DocumentSearchHandler]
+  /// Returns the number of indexed blocks
   fn index_count(&self) -> u64 {
-    0
+    self.index_manager.num_docs()
   }
 }
@@ -0,0 +1,342 @@
+use std::{
+  any::Any,
+  collections::HashSet,
+  fs,
+  ops::Deref,
+  path::Path,
+  sync::{Arc, Mutex, MutexGuard, Weak},
+};
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_search_pub::entities::{DocumentIndexData, DocumentIndexManager};
+use flowy_user::services::authenticate_user::AuthenticateUser;
+use tantivy::{
+  collector::{Count, TopDocs},
+  directory::MmapDirectory,
+  doc,
+  query::{BooleanQuery, Occur, Query, QueryParser, TermQuery},
+  schema::{Field, IndexRecordOption},
+  Index, IndexReader, IndexWriter, SnippetGenerator, TantivyDocument, Term,
+};
+
+use crate::util::{get_text, preview_from_snippet, remove_outdated_indexes};
+
+use super::{
+  entities::DocumentSearchResult,
+  schema::{
+    DocumentSchema, DOCUMENT_BLOCK_ID_FIELD_NAME, DOCUMENT_CONTENT_FIELD_NAME,
+    DOCUMENT_ID_FIELD_NAME, DOCUMENT_SCHEMA_VERSION, DOCUMENT_WORKSPACE_ID_FIELD_NAME,
+  },
+};
+
+const DOCUMENT_INDEX_DIR: &str = "document_index";
+/// The number of matched blocks that are collected before picking the best block of each document.
+const MAX_MATCHED_BLOCKS: usize = 100;
+const MAX_SEARCH_RESULTS: usize = 10;
+const PREVIEW_MAX_CHARS: usize = 150;
+
+/// Indexes the text of the blocks of the documents, each block is stored as a separate
+/// tantivy document, so that a search result can point to the block that matches the query.
+///
+#[derive(Clone)]
+pub struct DocumentIndexManagerImpl {
+  document_schema: Option<DocumentSchema>,
+  index: Option<Index>,
+  index_reader: Option<IndexReader>,
+  index_writer: Option<Arc<Mutex<IndexWriter>>>,
+}
+
+impl DocumentIndexManagerImpl {
+  pub fn new(auth_user: Option<Weak<AuthenticateUser>>) -> Self {
+    let auth_user = match auth_user {
+      Some(auth_user) => auth_user,
+      None => {
+        return DocumentIndexManagerImpl::empty();
+      },
+    };
+
+    // Storage path is the users data path with an index directory
+    // Eg. /usr/flowy-data/indexes
+    let storage_path = match auth_user.upgrade() {
+      Some(auth_user) => auth_user.get_index_path(),
+      None => {
+        tracing::error!("DocumentIndexManager: AuthenticateUser is not available");
+        return DocumentIndexManagerImpl::empty();
+      },
+    };
+
+    // Each version of the schema has its own directory
+    // Eg. /usr/flowy-data/indexes/document_index/v1
+    let index_dir = storage_path.join(Path::new(DOCUMENT_INDEX_DIR));
+    let version_dir_name = format!("v{}", DOCUMENT_SCHEMA_VERSION);
+    remove_outdated_indexes(&index_dir, &version_dir_name);
+
+    let index_path = index_dir.join(version_dir_name);
+    if !index_path.exists() {
+      if let Err(e) = fs::create_dir_all(&index_path) {
+        tracing::error!(
+          "DocumentIndexManager failed to create index directory: {:?}",
+          e
+        );
+        return DocumentIndexManagerImpl::empty();
+      }
+    }
+
+    let document_schema = DocumentSchema::new();
+    let index = match MmapDirectory::open(index_path) {
+      Ok(dir) => match Index::open_or_create(dir, document_schema.schema.clone()) {
+        Ok(index) => index,
+        Err(e) => {
+          tracing::error!("DocumentIndexManager failed to open index: {:?}", e);
+          return DocumentIndexManagerImpl::empty();
+        },
+      },
+      Err(e) => {
+        tracing::error!(
+          "DocumentIndexManager failed to open index directory: {:?}",
+          e
+        );
+        return DocumentIndexManagerImpl::empty();
+      },
+    };
+
+    let index_reader = index.reader();
+    let index_writer = index.writer(50_000_000);
+
+    let (index_reader, index_writer) = match (index_reader, index_writer) {
+      (Ok(reader), Ok(writer)) => (reader, writer),
+      _ => {
+        tracing::error!("DocumentIndexManager failed to instantiate index writer and/or reader");
+        return DocumentIndexManagerImpl::empty();
+      },
+    };
+
+    Self {
+      document_schema: Some(document_schema),
+      index: Some(index),
+      index_reader: Some(index_reader),
+      index_writer: Some(Arc::new(Mutex::new(index_writer))),
+    }
+  }
+
+  pub fn num_docs(&self) -> u64 {
+    self
+      .index_reader
+      .clone()
+      .map(|reader| reader.searcher().num_docs())
+      .unwrap_or(0)
+  }
+
+  fn empty() -> Self {
+    Self {
+      document_schema: None,
+      index: None,
+      index_reader: None,
+      index_writer: None,
+    }
+  }
+
+  fn get_index_writer(&self) -> FlowyResult<MutexGuard<IndexWriter>> {
+    match &self.index_writer {
+      Some(index_writer) => match index_writer.deref().lock() {
+        Ok(writer) => Ok(writer),
+        Err(e) => {
+          tracing::error!("DocumentIndexManager failed to lock index writer: {:?}", e);
+          Err(FlowyError::document_index_manager_unavailable())
+        },
+      },
+      None => Err(FlowyError::document_index_manager_unavailable()),
+    }
+  }
+
+  fn get_document_schema(&self) -> FlowyResult<DocumentSchema> {
+    match &self.document_schema {
+      Some(document_schema) => Ok(document_schema.clone()),
+      None => Err(FlowyError::document_index_manager_unavailable()),
+    }
+  }
+
+  fn get_schema_fields(&self) -> Result<(Field, Field, Field, Field), FlowyError> {
+    let document_schema = self.get_document_schema()?;
+    let id_field = document_schema.schema.get_field(DOCUMENT_ID_FIELD_NAME)?;
+    let block_id_field = document_schema
+      .schema
+      .get_field(DOCUMENT_BLOCK_ID_FIELD_NAME)?;
+    let content_field = document_schema
+      .schema
+      .get_field(DOCUMENT_CONTENT_FIELD_NAME)?;
+    let workspace_id_field = document_schema
+      .schema
+      .get_field(DOCUMENT_WORKSPACE_ID_FIELD_NAME)?;
+
+    Ok((id_field, block_id_field, content_field, workspace_id_field))
+  }
+
+  /// Commits the pending changes and reloads the reader, so that the changes are visible
+  /// to the next search.
+  fn commit(&self, mut index_writer: MutexGuard<IndexWriter>) -> FlowyResult<()> {
+    index_writer.commit()?;
+    drop(index_writer);
+    if let Some(index_reader) = &self.index_reader {
+      index_reader.reload()?;
+    }
+    Ok(())
+  }
+
+  /// Returns the best matching block of each document, ordered by relevance. If the
+  /// workspace_id is given, only the documents of that workspace are searched.
+  pub fn search(
+    &self,
+    query: &str,
+    workspace_id: Option<&str>,
+  ) -> Result<Vec<DocumentSearchResult>, FlowyError> {
+    if query.trim().is_empty() {
+      return Ok(vec![]);
+    }
+
+    let (index, index_reader) = self
+      .index
+      .as_ref()
+      .zip(self.index_reader.as_ref())
+      .ok_or_else(FlowyError::document_index_manager_unavailable)?;
+    let (id_field, block_id_field, content_field, workspace_id_field) = self.get_schema_fields()?;
+
+    // All the terms of the query must appear in the block. Syntax errors in the query
+    // are ignored, since the query is typed by the user.
+    let mut query_parser = QueryParser::for_index(index, vec![content_field]);
+    query_parser.set_conjunction_by_default();
+    let (content_query, _) = query_parser.parse_query_lenient(query);
+
+    let searcher = index_reader.searcher();
+    let mut snippet_generator =
+      SnippetGenerator::create(&searcher, &*content_query, content_field)?;
+    snippet_generator.set_max_num_chars(PREVIEW_MAX_CHARS);
+
+    let built_query: Box<dyn Query> = match workspace_id {
+      Some(workspace_id) => Box::new(BooleanQuery::new(vec![
+        (Occur::Must, content_query),
+        (
+          Occur::Must,
+          Box::new(TermQuery::new(
+            Term::from_field_text(workspace_id_field, workspace_id),
+            IndexRecordOption::Basic,
+          )),
+        ),
+      ])),
+      None => content_query,
+    };
+
+    let top_docs = searcher.search(&built_query, &TopDocs::with_limit(MAX_MATCHED_BLOCKS))?;
+    let mut document_ids = HashSet::new();
+    let mut search_results = vec![];
+    for (score, doc_address) in top_docs {
+      let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
+      let document_id = get_text(&retrieved_doc, id_field);
+      if !document_ids.insert(document_id.clone()) {
+        // The document already has a block with a higher score
+        continue;
+      }
+
+      let snippet = snippet_generator.snippet_from_doc(&retrieved_doc);
+      let content = get_text(&retrieved_doc, content_field);
+      let (preview, highlights) = preview_from_snippet(&snippet, &content, PREVIEW_MAX_CHARS);
+
+      search_results.push(DocumentSearchResult {
+        document_id,
+        block_id: get_text(&retrieved_doc, block_id_field),
+        workspace_id: get_text(&retrieved_doc, workspace_id_field),
+        preview,
+        highlights,
+        // The BM25 score is unbounded, map it into [0, 1) to make it comparable with the
+        // scores of the other search handlers.
+        score: score as f64 / (1.0 + score as f64),
+      });
+
+      if search_results.len() >= MAX_SEARCH_RESULTS {
+        break;
+      }
+    }
+
+    Ok(search_results)
+  }
+}
+
+impl DocumentIndexManager for DocumentIndexManagerImpl {
+  fn index_documents(&self, documents: Vec<DocumentIndexData>) -> Result<(), FlowyError> {
+    if documents.is_empty() {
+      return Ok(());
+    }
+
+    let index_writer = self.get_index_writer()?;
+    let (id_field, block_id_field, content_field, workspace_id_field) = self.get_schema_fields()?;
+
+    for document in documents {
+      // Remove old index
+      index_writer.delete_term(Term::from_field_text(id_field, &document.document_id));
+
+      if document.blocks.is_empty() {
+        // Index an empty block to mark the document as indexed
+        let _ = index_writer.add_document(doc![
+          id_field => document.document_id.clone(),
+          block_id_field => "",
+          content_field => "",
+          workspace_id_field => document.workspace_id.clone(),
+        ]);
+        continue;
+      }
+
+      for block in document.blocks {
+        let _ = index_writer.add_document(doc![
+          id_field => document.document_id.clone(),
+          block_id_field => block.block_id,
+          content_field => block.text,
+          workspace_id_field => document.workspace_id.clone(),
+        ]);
+      }
+    }
+
+    self.commit(index_writer)
+  }
+
+  fn remove_indices(&self, document_ids: Vec<String>) -> Result<(), FlowyError> {
+    let index_writer = self.get_index_writer()?;
+    let (id_field, ..) = self.get_schema_fields()?;
+    for document_id in document_ids {
+      index_writer.delete_term(Term::from_field_text(id_field, &document_id));
+    }
+
+    self.commit(index_writer)
+  }
+
+  /// Removes all indexes that are related by workspace id. This is useful
+  /// for cleaning indexes when eg. removing/leaving a workspace.
+  ///
+  fn remove_indices_for_workspace(&self, workspace_id: String) -> Result<(), FlowyError> {
+    let index_writer = self.get_index_writer()?;
+    let (.., workspace_id_field) = self.get_schema_fields()?;
+    index_writer.delete_term(Term::from_field_text(workspace_id_field, &workspace_id));
+
+    self.commit(index_writer)
+  }
+
+  fn is_document_indexed(&self, document_id: &str) -> bool {
+    let (index_reader, (id_field, ..)) = match (&self.index_reader, self.get_schema_fields()) {
+      (Some(index_reader), Ok(fields)) => (index_reader, fields),
+      _ => return false,
+    };
+
+    let query = TermQuery::new(
+      Term::from_field_text(id_field, document_id),
+      IndexRecordOption::Basic,
+    );
+    index_reader
+      .searcher()
+      .search(&query, &Count)
+      .map(|count| count > 0)
+      .unwrap_or(false)
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+}
@@ -1 +1,4 @@
+pub mod entities;
 pub mod handler;
+pub mod indexer;
+pub mod schema;
@@ -0,0 +1,55 @@
+use tantivy::schema::Schema;
+
+pub const DOCUMENT_ID_FIELD_NAME: &str = "document_id";
+pub const DOCUMENT_BLOCK_ID_FIELD_NAME: &str = "block_id";
+pub const DOCUMENT_CONTENT_FIELD_NAME: &str = "content";
+pub const DOCUMENT_WORKSPACE_ID_FIELD_NAME: &str = "workspace_id";
+
+/// The version of the [DocumentSchema]. The index is stored in a directory named after
+/// the version, see `DocumentIndexManagerImpl::new`.
+pub const DOCUMENT_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Clone)]
+pub struct DocumentSchema {
+  pub schema: Schema,
+}
+
+/// Do not change the schema after the index has been created.
+/// Changing field_options or fields, will result in the schema being different
+/// from previously created index, causing tantivy to panic and search to stop functioning.
+///
+/// If you need to change the schema, bump the [DOCUMENT_SCHEMA_VERSION]. The indexes of
+/// the older versions will be removed, and the documents will be indexed again with the
+/// new schema.
+///
+impl DocumentSchema {
+  pub fn new() -> Self {
+    let mut schema_builder = Schema::builder();
+    schema_builder.add_text_field(
+      DOCUMENT_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      DOCUMENT_BLOCK_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      DOCUMENT_CONTENT_FIELD_NAME,
+      tantivy::schema::TEXT | tantivy::schema::STORED,
+    );
+    schema_builder.add_text_field(
+      DOCUMENT_WORKSPACE_ID_FIELD_NAME,
+      tantivy::schema::STRING | tantivy::schema::STORED,
+    );
+
+    let schema = schema_builder.build();
+
+    Self { schema }
+  }
+}
+
+impl Default for DocumentSchema {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -1,6 +1,7 @@
 use collab_folder::{IconType, ViewIcon};
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
-use flowy_folder::entities::ViewIconPB;
+use flowy_folder::entities::{ViewIconPB, ViewPB};
+use flowy_folder::ViewLayout;
 
 use super::IndexTypePB;
 
@@ -35,6 +36,42 @@ pub struct SearchResultPB {
 
   #[pb(index = 8, one_of)]
   pub preview: Option<String>,
+
+  /// The ranges of the matched terms within the preview.
+  #[pb(index = 9)]
+  pub highlights: Vec<SearchHighlightPB>,
+
+  /// The field of the matched cell, only set for [IndexTypePB::DatabaseRow] results.
+  #[pb(index = 10, one_of)]
+  pub field: Option<SearchResultFieldPB>,
+}
+
+#[derive(ProtoBuf, Default, Debug, Clone, PartialEq, Eq)]
+pub struct SearchResultFieldPB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+}
+
+/// A range of chars, `start` is inclusive and `end` is exclusive.
+#[derive(ProtoBuf, Default, Debug, Clone, PartialEq, Eq)]
+pub struct SearchHighlightPB {
+  #[pb(index = 1)]
+  pub start: i64,
+
+  #[pb(index = 2)]
+  pub end: i64,
+}
+
+impl From<std::ops::Range<usize>> for SearchHighlightPB {
+  fn from(range: std::ops::Range<usize>) -> Self {
+    Self {
+      start: range.start as i64,
+      end: range.end as i64,
+    }
+  }
 }
 
 impl SearchResultPB {
@@ -48,6 +85,8 @@ impl SearchResultPB {
       score,
       workspace_id: self.workspace_id.clone(),
       preview: self.preview.clone(),
+      highlights: self.highlights.clone(),
+      field: self.field.clone(),
     }
   }
 }
@@ -128,6 +167,22 @@ impl From<ViewIcon> for ResultIconPB {
   }
 }
 
+/// Uses the icon of the view, or the icon of the view's layout if the view has no icon.
+impl From<&ViewPB> for ResultIconPB {
+  fn from(view: &ViewPB) -> Self {
+    match view.icon.clone() {
+      Some(view_icon) => ResultIconPB::from(view_icon),
+      None => {
+        let view_layout_ty: i64 = ViewLayout::from(view.layout.clone()).into();
+        ResultIconPB {
+          ty: ResultIconTypePB::Icon,
+          value: view_layout_ty.to_string(),
+        }
+      },
+    }
+  }
+}
+
 impl From<ViewIconPB> for ResultIconPB {
   fn from(val: ViewIconPB) -> Self {
     ResultIconPB {
@@ -1,7 +1,69 @@
-use flowy_derive::ProtoBuf;
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_folder::entities::ViewLayoutPB;
 
 #[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
 pub struct SearchFilterPB {
   #[pb(index = 1, one_of)]
   pub workspace_id: Option<String>,
+
+  /// Only the views with one of the layouts are returned. All the layouts are allowed if empty.
+  #[pb(index = 2)]
+  pub layouts: Vec<SearchLayoutItemPB>,
+
+  /// Only the views inside the given view are returned, eg. the views of a space.
+  #[pb(index = 3, one_of)]
+  pub ancestor_view_id: Option<String>,
+
+  #[pb(index = 4, one_of)]
+  pub created_time: Option<SearchTimeRangePB>,
+
+  #[pb(index = 5, one_of)]
+  pub edited_time: Option<SearchTimeRangePB>,
+
+  /// The user id of the creator of the views.
+  #[pb(index = 6, one_of)]
+  pub created_by: Option<i64>,
+
+  #[pb(index = 7)]
+  pub favorites_only: bool,
+}
+
+/// A range of timestamps in seconds, both ends are inclusive. The range is unbounded on the
+/// side that is not set.
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
+pub struct SearchTimeRangePB {
+  #[pb(index = 1, one_of)]
+  pub start: Option<i64>,
+
+  #[pb(index = 2, one_of)]
+  pub end: Option<i64>,
+}
+
+/// Wraps the [SearchLayoutPB], since a list of enums can't be used as a field.
+#[derive(Eq, PartialEq, ProtoBuf, Default, Debug, Clone)]
+pub struct SearchLayoutItemPB {
+  #[pb(index = 1)]
+  pub layout: SearchLayoutPB,
+}
+
+#[derive(ProtoBuf_Enum, Clone, Debug, PartialEq, Eq, Default)]
+pub enum SearchLayoutPB {
+  #[default]
+  Document = 0,
+  Grid = 1,
+  Board = 2,
+  Calendar = 3,
+  Chat = 4,
+}
+
+impl From<SearchLayoutPB> for ViewLayoutPB {
+  fn from(layout: SearchLayoutPB) -> Self {
+    match layout {
+      SearchLayoutPB::Document => ViewLayoutPB::Document,
+      SearchLayoutPB::Grid => ViewLayoutPB::Grid,
+      SearchLayoutPB::Board => ViewLayoutPB::Board,
+      SearchLayoutPB::Calendar => ViewLayoutPB::Calendar,
+      SearchLayoutPB::Chat => ViewLayoutPB::Chat,
+    }
+  }
 }
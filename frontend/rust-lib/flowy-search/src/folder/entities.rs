@@ -31,6 +31,8 @@ impl From<FolderIndexData> for SearchResultPB {
       icon,
       workspace_id: data.workspace_id,
       preview: None,
+      highlights: vec![],
+      field: None,
     }
   }
 }
@@ -1,20 +1,37 @@
 use crate::{
   entities::{SearchFilterPB, SearchResultPB},
-  services::manager::{SearchHandler, SearchType},
+  services::{
+    filter::ViewFilter,
+    manager::{SearchHandler, SearchType},
+    query::SearchQuery,
+  },
 };
 use flowy_error::FlowyResult;
+use flowy_folder::manager::FolderManager;
 use lib_infra::async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::indexer::FolderIndexManagerImpl;
 
+const MAX_SEARCH_RESULTS: usize = 10;
+/// The number of matched views that are collected before the views are filtered.
+const MAX_MATCHED_VIEWS: usize = 100;
+
 pub struct FolderSearchHandler {
   pub index_manager: Arc<FolderIndexManagerImpl>,
+  pub folder_manager: Arc<FolderManager>,
 }
 
 impl FolderSearchHandler {
-  pub fn new(index_manager: Arc<FolderIndexManagerImpl>) -> Self {
-    Self { index_manager }
+  pub fn new(
+    index_manager: Arc<FolderIndexManagerImpl>,
+    folder_manager: Arc<FolderManager>,
+  ) -> Self {
+    Self {
+      index_manager,
+      folder_manager,
+    }
   }
 }
 
@@ -26,15 +43,35 @@ impl SearchHandler for FolderSearchHandler {
 
   async fn perform_search(
     &self,
-    query: String,
+    query: SearchQuery,
     filter: Option<SearchFilterPB>,
   ) -> FlowyResult<Vec<SearchResultPB>> {
-    let mut results = self.index_manager.search(query, filter.clone())?;
-    if let Some(filter) = filter {
-      if let Some(workspace_id) = filter.workspace_id {
-        // Filter results by workspace ID
-        results.retain(|result| result.workspace_id == workspace_id);
-      }
+    let view_filter = ViewFilter::new(filter.as_ref(), &query);
+    let limit = if view_filter.is_empty() {
+      MAX_SEARCH_RESULTS
+    } else {
+      MAX_MATCHED_VIEWS
+    };
+
+    let mut results = self.index_manager.search(&query, limit)?;
+    if let Some(workspace_id) = filter.and_then(|filter| filter.workspace_id) {
+      // Filter results by workspace ID
+      results.retain(|result| result.workspace_id == workspace_id);
+    }
+
+    if !view_filter.is_empty() {
+      // Notice that `get_all_view_pb` returns Views that don't include trashed and private views
+      let views = self.folder_manager.get_all_views_pb().await?;
+      let views_by_id = views
+        .iter()
+        .map(|view| (view.id.as_str(), view))
+        .collect::<HashMap<_, _>>();
+      results.retain(|result| {
+        views_by_id
+          .get(result.view_id.as_str())
+          .map_or(false, |view| view_filter.matches(view, &views_by_id))
+      });
+      results.truncate(MAX_SEARCH_RESULTS);
     }
 
     Ok(results)
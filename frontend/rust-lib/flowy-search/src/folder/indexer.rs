@@ -8,11 +8,14 @@ use std::{
 };
 
 use crate::{
-  entities::{ResultIconTypePB, SearchFilterPB, SearchResultPB},
+  entities::{ResultIconTypePB, SearchResultPB},
   folder::schema::{
     FolderSchema, FOLDER_ICON_FIELD_NAME, FOLDER_ICON_TY_FIELD_NAME, FOLDER_ID_FIELD_NAME,
-    FOLDER_TITLE_FIELD_NAME, FOLDER_WORKSPACE_ID_FIELD_NAME,
+    FOLDER_LAYOUT_FIELD_NAME, FOLDER_SCHEMA_VERSION, FOLDER_TITLE_FIELD_NAME,
+    FOLDER_WORKSPACE_ID_FIELD_NAME,
   },
+  services::query::{quote, SearchQuery},
+  util::remove_outdated_indexes,
 };
 use collab::core::collab::{IndexContent, IndexContentReceiver};
 use collab_folder::{folder_diff::FolderViewChange, View, ViewIcon, ViewIndexContent, ViewLayout};
@@ -22,8 +25,12 @@ use flowy_user::services::authenticate_user::AuthenticateUser;
 use lib_dispatch::prelude::af_spawn;
 use strsim::levenshtein;
 use tantivy::{
-  collector::TopDocs, directory::MmapDirectory, doc, query::QueryParser, schema::Field, Document,
-  Index, IndexReader, IndexWriter, TantivyDocument, Term,
+  collector::TopDocs,
+  directory::MmapDirectory,
+  doc,
+  query::{AllQuery, BooleanQuery, Occur, Query, QueryParser, TermQuery},
+  schema::{Field, IndexRecordOption},
+  Document, Index, IndexReader, IndexWriter, TantivyDocument, Term,
 };
 
 use super::entities::FolderIndexData;
@@ -60,8 +67,14 @@ impl FolderIndexManagerImpl {
       },
     };
 
-    // We check if the `folder_index` directory exists, if not we create it
-    let index_path = storage_path.join(Path::new(FOLDER_INDEX_DIR));
+    // Each version of the schema has its own directory
+    // Eg. /usr/flowy-data/indexes/folder_index/v1
+    let index_dir = storage_path.join(Path::new(FOLDER_INDEX_DIR));
+    let version_dir_name = format!("v{}", FOLDER_SCHEMA_VERSION);
+    remove_outdated_indexes(&index_dir, &version_dir_name);
+
+    // We check if the directory of the index exists, if not we create it
+    let index_path = index_dir.join(version_dir_name);
     if !index_path.exists() {
       let res = fs::create_dir_all(&index_path);
       if let Err(e) = res {
@@ -77,7 +90,7 @@ impl FolderIndexManagerImpl {
     // with how they are stored and if the field is indexed
     let folder_schema = FolderSchema::new();
 
-    // We open the existing or newly created folder_index directory
+    // We open the existing or newly created index directory
     // This is required by the Tantivy Index, as it will use it to store
     // and read index data
     let index = match MmapDirectory::open(index_path) {
@@ -130,8 +143,10 @@ impl FolderIndexManagerImpl {
     let workspace_id_field = folder_schema
       .schema
       .get_field(FOLDER_WORKSPACE_ID_FIELD_NAME)?;
+    let layout_field = folder_schema.schema.get_field(FOLDER_LAYOUT_FIELD_NAME)?;
 
     for data in indexes {
+      let layout: i64 = data.layout.clone().into();
       let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
 
       let _ = index_writer.add_document(doc![
@@ -140,6 +155,7 @@ impl FolderIndexManagerImpl {
         icon_field => icon.unwrap_or_default(),
         icon_ty_field => icon_ty,
         workspace_id_field => data.workspace_id.clone(),
+        layout_field => layout,
       ]);
     }
 
@@ -207,10 +223,14 @@ impl FolderIndexManagerImpl {
     (icon, icon_ty)
   }
 
+  /// Returns the views whose title matches the query, at most `limit` results. The words of
+  /// the query are matched fuzzily, while the phrases and the excluded words must match
+  /// exactly. The query only made of `type:`/`in:` prefixes or excluded words matches all
+  /// the views of the given layouts.
   pub fn search(
     &self,
-    query: String,
-    _filter: Option<SearchFilterPB>,
+    query: &SearchQuery,
+    limit: usize,
   ) -> Result<Vec<SearchResultPB>, FlowyError> {
     let folder_schema = self.get_folder_schema()?;
 
@@ -221,17 +241,16 @@ impl FolderIndexManagerImpl {
       .ok_or_else(FlowyError::folder_index_manager_unavailable)?;
 
     let title_field = folder_schema.schema.get_field(FOLDER_TITLE_FIELD_NAME)?;
+    let layout_field = folder_schema.schema.get_field(FOLDER_LAYOUT_FIELD_NAME)?;
+    let built_query = match self.build_query(index, title_field, layout_field, query) {
+      Some(built_query) => built_query,
+      None => return Ok(vec![]),
+    };
 
-    let length = query.len();
-    let distance: u8 = if length >= 2 { 2 } else { 1 };
-
-    let mut query_parser = QueryParser::for_index(&index.clone(), vec![title_field]);
-    query_parser.set_field_fuzzy(title_field, true, distance, true);
-    let built_query = query_parser.parse_query(&query.clone())?;
-
+    let text = query.text();
     let searcher = index_reader.searcher();
     let mut search_results: Vec<SearchResultPB> = vec![];
-    let top_docs = searcher.search(&built_query, &TopDocs::with_limit(10))?;
+    let top_docs = searcher.search(&built_query, &TopDocs::with_limit(limit))?;
     for (_score, doc_address) in top_docs {
       let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
 
@@ -247,20 +266,81 @@ impl FolderIndexManagerImpl {
 
       let s = serde_json::to_string(&content)?;
       let result: SearchResultPB = serde_json::from_str::<FolderIndexData>(&s)?.into();
-      let score = self.score_result(&query, &result.data);
+      let score = self.score_result(&text, &result.data);
       search_results.push(result.with_score(score));
     }
 
     Ok(search_results)
   }
 
+  /// Translates the query into a boolean query, returns None if the query can't match any
+  /// view.
+  fn build_query(
+    &self,
+    index: &Index,
+    title_field: Field,
+    layout_field: Field,
+    query: &SearchQuery,
+  ) -> Option<Box<dyn Query>> {
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = vec![];
+
+    if !query.terms.is_empty() {
+      let length = query.terms.join(" ").len();
+      let distance: u8 = if length >= 2 { 2 } else { 1 };
+
+      let mut query_parser = QueryParser::for_index(index, vec![title_field]);
+      query_parser.set_field_fuzzy(title_field, true, distance, true);
+      query_parser.set_conjunction_by_default();
+      let (terms_query, _) = query_parser.parse_query_lenient(&query.tantivy_terms());
+      clauses.push((Occur::Must, terms_query));
+    }
+
+    // The phrases and the excluded words are not matched fuzzily
+    let query_parser = QueryParser::for_index(index, vec![title_field]);
+    for phrase in &query.phrases {
+      let (phrase_query, _) = query_parser.parse_query_lenient(&quote(phrase));
+      clauses.push((Occur::Must, phrase_query));
+    }
+
+    if !query.layouts.is_empty() {
+      let layout_queries = query
+        .layouts
+        .iter()
+        .map(|layout| {
+          let layout: i64 = ViewLayout::from(layout.clone()).into();
+          let layout_query: Box<dyn Query> = Box::new(TermQuery::new(
+            Term::from_field_i64(layout_field, layout),
+            IndexRecordOption::Basic,
+          ));
+          (Occur::Should, layout_query)
+        })
+        .collect();
+      clauses.push((Occur::Must, Box::new(BooleanQuery::new(layout_queries))));
+    }
+
+    if clauses.is_empty() {
+      // The query without any word to search for only lists the views if it has a filter
+      if query.excluded.is_empty() && query.ancestors.is_empty() {
+        return None;
+      }
+      clauses.push((Occur::Must, Box::new(AllQuery)));
+    }
+
+    for excluded in &query.excluded {
+      let (excluded_query, _) = query_parser.parse_query_lenient(&quote(excluded));
+      clauses.push((Occur::MustNot, excluded_query));
+    }
+
+    Some(Box::new(BooleanQuery::new(clauses)))
+  }
+
   // Score result by distance
   fn score_result(&self, query: &str, term: &str) -> f64 {
     let distance = levenshtein(query, term) as f64;
     1.0 / (distance + 1.0)
   }
 
-  fn get_schema_fields(&self) -> Result<(Field, Field, Field, Field, Field), FlowyError> {
+  fn get_schema_fields(&self) -> Result<(Field, Field, Field, Field, Field, Field), FlowyError> {
     let folder_schema = match self.folder_schema.clone() {
       Some(schema) => schema,
       _ => return Err(FlowyError::folder_index_manager_unavailable()),
@@ -273,6 +353,7 @@ impl FolderIndexManagerImpl {
     let workspace_id_field = folder_schema
       .schema
       .get_field(FOLDER_WORKSPACE_ID_FIELD_NAME)?;
+    let layout_field = folder_schema.schema.get_field(FOLDER_LAYOUT_FIELD_NAME)?;
 
     Ok((
       id_field,
@@ -280,6 +361,7 @@ impl FolderIndexManagerImpl {
       icon_field,
       icon_ty_field,
       workspace_id_field,
+      layout_field,
     ))
   }
 }
@@ -336,7 +418,7 @@ impl IndexManager for FolderIndexManagerImpl {
   fn update_index(&self, data: IndexableData) -> Result<(), FlowyError> {
     let mut index_writer = self.get_index_writer()?;
 
-    let (id_field, title_field, icon_field, icon_ty_field, workspace_id_field) =
+    let (id_field, title_field, icon_field, icon_ty_field, workspace_id_field, layout_field) =
       self.get_schema_fields()?;
 
     let delete_term = Term::from_field_text(id_field, &data.id.clone());
@@ -344,6 +426,7 @@ impl IndexManager for FolderIndexManagerImpl {
     // Remove old index
     index_writer.delete_term(delete_term);
 
+    let layout: i64 = data.layout.clone().into();
     let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
 
     // Add new index
@@ -353,6 +436,7 @@ impl IndexManager for FolderIndexManagerImpl {
       icon_field => icon.unwrap_or_default(),
       icon_ty_field => icon_ty,
       workspace_id_field => data.workspace_id.clone(),
+      layout_field => layout,
     ]);
 
     index_writer.commit()?;
@@ -378,9 +462,10 @@ impl IndexManager for FolderIndexManagerImpl {
   fn add_index(&self, data: IndexableData) -> Result<(), FlowyError> {
     let mut index_writer = self.get_index_writer()?;
 
-    let (id_field, title_field, icon_field, icon_ty_field, workspace_id_field) =
+    let (id_field, title_field, icon_field, icon_ty_field, workspace_id_field, layout_field) =
       self.get_schema_fields()?;
 
+    let layout: i64 = data.layout.clone().into();
     let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
 
     // Add new index
@@ -390,6 +475,7 @@ impl IndexManager for FolderIndexManagerImpl {
       icon_field => icon.unwrap_or_default(),
       icon_ty_field => icon_ty,
       workspace_id_field => data.workspace_id,
+      layout_field => layout,
     ]);
 
     index_writer.commit()?;
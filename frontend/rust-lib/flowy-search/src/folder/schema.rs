@@ -5,6 +5,12 @@ pub const FOLDER_TITLE_FIELD_NAME: &str = "title";
 pub const FOLDER_ICON_FIELD_NAME: &str = "icon";
 pub const FOLDER_ICON_TY_FIELD_NAME: &str = "icon_ty";
 pub const FOLDER_WORKSPACE_ID_FIELD_NAME: &str = "workspace_id";
+pub const FOLDER_LAYOUT_FIELD_NAME: &str = "layout";
+
+/// The version of the schema, the index is stored in a directory named after the version.
+/// Increase the version when changing the schema, the views are indexed again after the
+/// index of the previous version is removed.
+pub const FOLDER_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Clone)]
 pub struct FolderSchema {
@@ -15,8 +21,7 @@ pub struct FolderSchema {
 /// Changing field_options or fields, will result in the schema being different
 /// from previously created index, causing tantivy to panic and search to stop functioning.
 ///
-/// If you need to change the schema, create a migration that removes the old index,
-/// and creates a new one with the new schema.
+/// If you need to change the schema, increase the [FOLDER_SCHEMA_VERSION].
 ///
 impl FolderSchema {
   pub fn new() -> Self {
@@ -38,6 +43,10 @@ impl FolderSchema {
       FOLDER_WORKSPACE_ID_FIELD_NAME,
       tantivy::schema::TEXT | tantivy::schema::STORED,
     );
+    schema_builder.add_i64_field(
+      FOLDER_LAYOUT_FIELD_NAME,
+      tantivy::schema::INDEXED | tantivy::schema::STORED,
+    );
 
     let schema = schema_builder.build();
 
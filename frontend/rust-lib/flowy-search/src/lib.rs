@@ -1,3 +1,5 @@
+pub mod backlinks;
+pub mod database;
 pub mod document;
 pub mod entities;
 pub mod event_handler;
@@ -5,3 +7,4 @@ pub mod event_map;
 pub mod folder;
 pub mod protobuf;
 pub mod services;
+mod util;
@@ -0,0 +1,125 @@
+use std::collections::{HashMap, HashSet};
+
+use flowy_folder::entities::{ViewLayoutPB, ViewPB};
+
+use crate::entities::{SearchFilterPB, SearchTimeRangePB};
+
+use super::query::SearchQuery;
+
+/// The conditions on the view of a search result, combined from the [SearchFilterPB] and the
+/// `type:`/`in:` prefixes of the [SearchQuery]. All the conditions must be met.
+#[derive(Debug, Clone, Default)]
+pub struct ViewFilter {
+  filter_layouts: Vec<ViewLayoutPB>,
+  query_layouts: Vec<ViewLayoutPB>,
+  ancestor_view_id: Option<String>,
+  /// The names or ids of the ancestors given with the `in:` prefix.
+  query_ancestors: Vec<String>,
+  created_time: Option<SearchTimeRangePB>,
+  edited_time: Option<SearchTimeRangePB>,
+  created_by: Option<i64>,
+  favorites_only: bool,
+}
+
+impl ViewFilter {
+  pub fn new(filter: Option<&SearchFilterPB>, query: &SearchQuery) -> Self {
+    let mut view_filter = ViewFilter {
+      query_layouts: query.layouts.clone(),
+      query_ancestors: query.ancestors.clone(),
+      ..Default::default()
+    };
+
+    if let Some(filter) = filter {
+      view_filter.filter_layouts = filter
+        .layouts
+        .iter()
+        .map(|item| ViewLayoutPB::from(item.layout.clone()))
+        .collect();
+      view_filter.ancestor_view_id = filter.ancestor_view_id.clone();
+      view_filter.created_time = filter.created_time.clone();
+      view_filter.edited_time = filter.edited_time.clone();
+      view_filter.created_by = filter.created_by;
+      view_filter.favorites_only = filter.favorites_only;
+    }
+    view_filter
+  }
+
+  /// Returns true if any view passes the filter.
+  pub fn is_empty(&self) -> bool {
+    self.filter_layouts.is_empty()
+      && self.query_layouts.is_empty()
+      && self.ancestor_view_id.is_none()
+      && self.query_ancestors.is_empty()
+      && self.created_time.is_none()
+      && self.edited_time.is_none()
+      && self.created_by.is_none()
+      && !self.favorites_only
+  }
+
+  /// Returns true if the `view` meets all the conditions. The `views` are used to look up the
+  /// ancestors of the view.
+  pub fn matches(&self, view: &ViewPB, views: &HashMap<&str, &ViewPB>) -> bool {
+    if !self.filter_layouts.is_empty() && !self.filter_layouts.contains(&view.layout) {
+      return false;
+    }
+    if !self.query_layouts.is_empty() && !self.query_layouts.contains(&view.layout) {
+      return false;
+    }
+    if self.favorites_only && !view.is_favorite {
+      return false;
+    }
+    if self.created_by.is_some() && view.created_by != self.created_by {
+      return false;
+    }
+    if !is_in_time_range(view.create_time, self.created_time.as_ref()) {
+      return false;
+    }
+    if !is_in_time_range(view.last_edited, self.edited_time.as_ref()) {
+      return false;
+    }
+
+    if self.ancestor_view_id.is_none() && self.query_ancestors.is_empty() {
+      return true;
+    }
+    let ancestors = ancestors_of(view, views);
+    if let Some(ancestor_view_id) = &self.ancestor_view_id {
+      if !ancestors
+        .iter()
+        .any(|ancestor| &ancestor.id == ancestor_view_id)
+      {
+        return false;
+      }
+    }
+    self.query_ancestors.iter().all(|name_or_id| {
+      ancestors.iter().any(|ancestor| {
+        &ancestor.id == name_or_id || ancestor.name.eq_ignore_ascii_case(name_or_id)
+      })
+    })
+  }
+}
+
+fn is_in_time_range(timestamp: i64, range: Option<&SearchTimeRangePB>) -> bool {
+  match range {
+    None => true,
+    Some(range) => {
+      range.start.map_or(true, |start| timestamp >= start)
+        && range.end.map_or(true, |end| timestamp <= end)
+    },
+  }
+}
+
+/// Returns the parent, grandparent, etc. of the view.
+fn ancestors_of<'a>(view: &ViewPB, views: &HashMap<&str, &'a ViewPB>) -> Vec<&'a ViewPB> {
+  let mut ancestors = vec![];
+  let mut visited = HashSet::new();
+  let mut parent_view_id = view.parent_view_id.as_str();
+  while let Some(parent) = views.get(parent_view_id) {
+    // Guard against a cycle in the folder
+    if !visited.insert(parent.id.as_str()) {
+      break;
+    }
+    ancestors.push(*parent);
+    parent_view_id = parent.parent_view_id.as_str();
+  }
+  ancestors
+}
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::notifier::{SearchNotifier, SearchResultChanged, SearchResultReceiverRunner};
+use super::query::SearchQuery;
 use crate::entities::{SearchFilterPB, SearchResultNotificationPB, SearchResultPB};
 use flowy_error::FlowyResult;
 use lib_dispatch::prelude::af_spawn;
@@ -12,6 +13,7 @@ use tokio::sync::broadcast;
 pub enum SearchType {
   Folder,
   Document,
+  Database,
 }
 
 #[async_trait]
@@ -19,10 +21,11 @@ pub trait SearchHandler: Send + Sync + 'static {
   /// returns the type of search this handler is responsible for
   fn search_type(&self) -> SearchType;
 
-  /// performs a search and returns the results
+  /// performs a search and returns the results, the results must match both the query and
+  /// the filter
   async fn perform_search(
     &self,
-    query: String,
+    query: SearchQuery,
     filter: Option<SearchFilterPB>,
   ) -> FlowyResult<Vec<SearchResultPB>>;
 
@@ -65,14 +68,16 @@ impl SearchManager {
   ) {
     let max: usize = self.handlers.len();
     let handlers = self.handlers.clone();
+    let search_query = SearchQuery::parse(&query);
     for (_, handler) in handlers {
       let q = query.clone();
+      let sq = search_query.clone();
       let f = filter.clone();
       let ch = channel.clone();
       let notifier = self.notifier.clone();
 
       af_spawn(async move {
-        let res = handler.perform_search(q.clone(), f).await;
+        let res = handler.perform_search(sq, f).await;
 
         let items = res.unwrap_or_default();
 
@@ -1,2 +1,4 @@
+pub mod filter;
 pub mod manager;
 pub mod notifier;
+pub mod query;
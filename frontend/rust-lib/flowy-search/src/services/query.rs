@@ -0,0 +1,172 @@
+use flowy_folder::entities::ViewLayoutPB;
+
+const TYPE_PREFIX: &str = "type:";
+const IN_PREFIX: &str = "in:";
+
+/// The query typed by the user, parsed into its parts.
+///
+/// The supported syntax is:
+/// - `word`: the word must appear in the result
+/// - `"some words"`: the words must appear in the result in this order
+/// - `-word` or `-"some words"`: the word or phrase must not appear in the result
+/// - `type:grid`: the result must be a view of the given layout, one of `document`, `grid`,
+///   `board`, `calendar` or `chat`
+/// - `in:name`: the result must be inside the view with the given name or id. Names with
+///   spaces can be quoted, eg. `in:"Team space"`
+///
+/// The prefixes that can't be parsed, eg. `type:unknown`, are searched as plain words.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+  /// The query as typed by the user.
+  pub raw: String,
+  pub terms: Vec<String>,
+  pub phrases: Vec<String>,
+  pub excluded: Vec<String>,
+  pub layouts: Vec<ViewLayoutPB>,
+  /// The names or ids of the views that the result must be inside of.
+  pub ancestors: Vec<String>,
+}
+
+impl SearchQuery {
+  pub fn parse(query: &str) -> Self {
+    let mut search_query = SearchQuery {
+      raw: query.to_string(),
+      ..Default::default()
+    };
+
+    for token in tokenize(query) {
+      if let Some(excluded) = token.strip_prefix('-') {
+        let excluded = unquote(excluded);
+        if !excluded.is_empty() {
+          search_query.excluded.push(excluded.to_string());
+        }
+      } else if let Some(layout) = token
+        .strip_prefix(TYPE_PREFIX)
+        .and_then(|value| layout_from_str(unquote(value)))
+      {
+        search_query.layouts.push(layout);
+      } else if let Some(ancestor) = token
+        .strip_prefix(IN_PREFIX)
+        .map(unquote)
+        .filter(|value| !value.is_empty())
+      {
+        search_query.ancestors.push(ancestor.to_string());
+      } else if token.starts_with('"') {
+        let phrase = unquote(&token);
+        if !phrase.trim().is_empty() {
+          search_query.phrases.push(phrase.to_string());
+        }
+      } else {
+        search_query.terms.push(token);
+      }
+    }
+
+    search_query
+  }
+
+  /// Returns true if the query doesn't contain any word or phrase to search for.
+  pub fn is_text_empty(&self) -> bool {
+    self.terms.is_empty() && self.phrases.is_empty()
+  }
+
+  /// The words and phrases that must appear in the result, separated by whitespace.
+  pub fn text(&self) -> String {
+    self
+      .terms
+      .iter()
+      .cloned()
+      .chain(self.phrases.iter().cloned())
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+
+  /// Returns the words of the query in the tantivy query syntax.
+  pub fn tantivy_terms(&self) -> String {
+    self
+      .terms
+      .iter()
+      .map(|term| escape_term(term))
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+
+  /// Returns the words, phrases and exclusions of the query in the tantivy query syntax.
+  pub fn to_tantivy_query(&self) -> String {
+    let phrases = self.phrases.iter().map(|phrase| quote(phrase));
+    let excluded = self
+      .excluded
+      .iter()
+      .map(|excluded| format!("-{}", quote(excluded)));
+    std::iter::once(self.tantivy_terms())
+      .chain(phrases)
+      .chain(excluded)
+      .filter(|part| !part.trim().is_empty())
+      .collect::<Vec<_>>()
+      .join(" ")
+  }
+}
+
+impl From<&str> for SearchQuery {
+  fn from(query: &str) -> Self {
+    SearchQuery::parse(query)
+  }
+}
+
+/// Splits the query by whitespace, except the whitespace inside double quotes. The quotes
+/// are kept in the tokens.
+fn tokenize(query: &str) -> Vec<String> {
+  let mut tokens = vec![];
+  let mut token = String::new();
+  let mut in_quotes = false;
+  for c in query.chars() {
+    match c {
+      '"' => {
+        in_quotes = !in_quotes;
+        token.push(c);
+      },
+      c if c.is_whitespace() && !in_quotes => {
+        if !token.is_empty() {
+          tokens.push(std::mem::take(&mut token));
+        }
+      },
+      c => token.push(c),
+    }
+  }
+  if !token.is_empty() {
+    tokens.push(token);
+  }
+  tokens
+}
+
+/// Returns the text as a tantivy phrase query.
+pub(crate) fn quote(text: &str) -> String {
+  format!("\"{}\"", text.replace('"', ""))
+}
+
+fn unquote(value: &str) -> &str {
+  let value = value.strip_prefix('"').unwrap_or(value);
+  value.strip_suffix('"').unwrap_or(value)
+}
+
+/// Escapes the characters that have a special meaning in the tantivy query syntax.
+fn escape_term(term: &str) -> String {
+  term
+    .chars()
+    .map(|c| match c {
+      '+' | '^' | '`' | ':' | '{' | '}' | '"' | '[' | ']' | '(' | ')' | '!' | '\\' | '*' | '~'
+      | '-' => ' ',
+      c => c,
+    })
+    .collect()
+}
+
+fn layout_from_str(value: &str) -> Option<ViewLayoutPB> {
+  match value.to_lowercase().as_str() {
+    "document" | "doc" | "page" => Some(ViewLayoutPB::Document),
+    "grid" | "table" => Some(ViewLayoutPB::Grid),
+    "board" | "kanban" => Some(ViewLayoutPB::Board),
+    "calendar" => Some(ViewLayoutPB::Calendar),
+    "chat" => Some(ViewLayoutPB::Chat),
+    _ => None,
+  }
+}
@@ -0,0 +1,68 @@
+use std::{fs, ops::Range, path::Path};
+
+use tantivy::{
+  schema::{Field, Value},
+  Snippet, TantivyDocument,
+};
+
+/// Removes the index directories of the older schema versions in `index_dir`, the data is
+/// indexed again with the schema of the current version. The files of an index that was
+/// created before the schema was versioned are removed as well.
+pub(crate) fn remove_outdated_indexes(index_dir: &Path, version_dir_name: &str) {
+  let entries = match fs::read_dir(index_dir) {
+    Ok(entries) => entries,
+    Err(_) => return,
+  };
+
+  for entry in entries.flatten() {
+    let path = entry.path();
+    if entry.file_name() == version_dir_name {
+      continue;
+    }
+
+    tracing::info!("Remove outdated search index: {:?}", path);
+    let result = if path.is_dir() {
+      fs::remove_dir_all(&path)
+    } else {
+      fs::remove_file(&path)
+    };
+    if let Err(e) = result {
+      tracing::error!("Failed to remove outdated search index: {:?}", e);
+    }
+  }
+}
+
+pub(crate) fn get_text(doc: &TantivyDocument, field: Field) -> String {
+  doc
+    .get_first(field)
+    .and_then(|value| value.as_str())
+    .unwrap_or_default()
+    .to_string()
+}
+
+/// Returns the fragment of the snippet and the char ranges of the matched terms in it. Falls
+/// back to the beginning of the `content` if the snippet is empty.
+pub(crate) fn preview_from_snippet(
+  snippet: &Snippet,
+  content: &str,
+  max_num_chars: usize,
+) -> (String, Vec<Range<usize>>) {
+  let fragment = snippet.fragment();
+  if fragment.is_empty() {
+    return (content.chars().take(max_num_chars).collect(), vec![]);
+  }
+
+  let highlights = snippet
+    .highlighted()
+    .iter()
+    .map(|range| char_range(fragment, range))
+    .collect();
+  (fragment.to_string(), highlights)
+}
+
+/// Converts the byte range returned by tantivy into a char range.
+fn char_range(text: &str, range: &Range<usize>) -> Range<usize> {
+  let start = text[..range.start].chars().count();
+  let end = start + text[range.start..range.end].chars().count();
+  start..end
+}
@@ -1,3 +1,4 @@
 // mod search;
 
+mod query_test;
 mod tantivy_test;
@@ -0,0 +1,48 @@
+use flowy_folder::entities::ViewLayoutPB;
+use flowy_search::services::query::SearchQuery;
+
+#[test]
+fn parse_plain_query_test() {
+  let query = SearchQuery::parse("  grocery   list ");
+  assert_eq!(query.terms, vec!["grocery", "list"]);
+  assert!(query.phrases.is_empty());
+  assert_eq!(query.text(), "grocery list");
+  assert_eq!(query.to_tantivy_query(), "grocery list");
+}
+
+#[test]
+fn parse_phrase_and_excluded_query_test() {
+  let query = SearchQuery::parse(r#"launch "mobile app" -desktop -"party time""#);
+  assert_eq!(query.terms, vec!["launch"]);
+  assert_eq!(query.phrases, vec!["mobile app"]);
+  assert_eq!(query.excluded, vec!["desktop", "party time"]);
+  assert_eq!(query.text(), "launch mobile app");
+  assert_eq!(
+    query.to_tantivy_query(),
+    r#"launch "mobile app" -"desktop" -"party time""#
+  );
+}
+
+#[test]
+fn parse_prefixed_query_test() {
+  let query = SearchQuery::parse(r#"roadmap type:board type:Doc in:"Team space" in:projects"#);
+  assert_eq!(query.terms, vec!["roadmap"]);
+  assert_eq!(
+    query.layouts,
+    vec![ViewLayoutPB::Board, ViewLayoutPB::Document]
+  );
+  assert_eq!(query.ancestors, vec!["Team space", "projects"]);
+
+  // Unknown layouts are searched as plain words
+  let query = SearchQuery::parse("type:spreadsheet");
+  assert!(query.layouts.is_empty());
+  assert_eq!(query.terms, vec!["type:spreadsheet"]);
+  assert_eq!(query.to_tantivy_query(), "type spreadsheet");
+}
+
+#[test]
+fn parse_query_without_text_test() {
+  let query = SearchQuery::parse("type:grid -archive");
+  assert!(query.is_text_empty());
+  assert_eq!(query.to_tantivy_query(), r#"-"archive""#);
+}
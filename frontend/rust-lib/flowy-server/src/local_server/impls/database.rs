@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::Error;
 use collab::preclude::Collab;
 use collab_entity::define::{DATABASE, DATABASE_ROW_DATA, WORKSPACE_DATABASES};
@@ -10,7 +12,11 @@ use flowy_database_pub::cloud::{
 };
 use lib_infra::future::FutureResult;
 
-pub(crate) struct LocalServerDatabaseCloudServiceImpl();
+use crate::local_server::LocalServerDB;
+
+pub(crate) struct LocalServerDatabaseCloudServiceImpl {
+  pub db: Arc<dyn LocalServerDB>,
+}
 
 impl DatabaseCloudService for LocalServerDatabaseCloudServiceImpl {
   fn get_database_object_doc_state(
@@ -71,10 +77,22 @@ impl DatabaseCloudService for LocalServerDatabaseCloudServiceImpl {
 
   fn get_database_collab_object_snapshots(
     &self,
-    _object_id: &str,
-    _limit: usize,
+    object_id: &str,
+    limit: usize,
   ) -> FutureResult<Vec<DatabaseSnapshot>, Error> {
-    FutureResult::new(async move { Ok(vec![]) })
+    let snapshots = self.db.get_collab_snapshots(object_id, limit);
+    FutureResult::new(async move {
+      let snapshots = snapshots?
+        .into_iter()
+        .map(|snapshot| DatabaseSnapshot {
+          snapshot_id: snapshot.snapshot_id,
+          database_id: snapshot.object_id,
+          data: snapshot.data,
+          created_at: snapshot.created_at,
+        })
+        .collect();
+      Ok(snapshots)
+    })
   }
 
   fn summary_database_row(
@@ -83,8 +101,11 @@ impl DatabaseCloudService for LocalServerDatabaseCloudServiceImpl {
     _object_id: &str,
     _summary_row: SummaryRowContent,
   ) -> FutureResult<String, Error> {
-    // TODO(lucas): local ai
-    FutureResult::new(async move { Ok("".to_string()) })
+    FutureResult::new(async move {
+      Err(anyhow::anyhow!(
+        "Summarizing a row requires the local AI, enable it in the settings"
+      ))
+    })
   }
 
   fn translate_database_row(
@@ -93,7 +114,10 @@ impl DatabaseCloudService for LocalServerDatabaseCloudServiceImpl {
     _translate_row: TranslateRowContent,
     _language: &str,
   ) -> FutureResult<TranslateRowResponse, Error> {
-    // TODO(lucas): local ai
-    FutureResult::new(async move { Ok(TranslateRowResponse::default()) })
+    FutureResult::new(async move {
+      Err(anyhow::anyhow!(
+        "Translating a row requires the local AI, enable it in the settings"
+      ))
+    })
   }
 }
@@ -1,10 +1,16 @@
+use std::sync::Arc;
+
 use anyhow::Error;
 
 use flowy_document_pub::cloud::*;
 use flowy_error::{ErrorCode, FlowyError};
 use lib_infra::future::FutureResult;
 
-pub(crate) struct LocalServerDocumentCloudServiceImpl();
+use crate::local_server::LocalServerDB;
+
+pub(crate) struct LocalServerDocumentCloudServiceImpl {
+  pub db: Arc<dyn LocalServerDB>,
+}
 
 impl DocumentCloudService for LocalServerDocumentCloudServiceImpl {
   fn get_document_doc_state(
@@ -23,11 +29,23 @@ impl DocumentCloudService for LocalServerDocumentCloudServiceImpl {
 
   fn get_document_snapshots(
     &self,
-    _document_id: &str,
-    _limit: usize,
+    document_id: &str,
+    limit: usize,
     _workspace_id: &str,
   ) -> FutureResult<Vec<DocumentSnapshot>, Error> {
-    FutureResult::new(async move { Ok(vec![]) })
+    let snapshots = self.db.get_collab_snapshots(document_id, limit);
+    FutureResult::new(async move {
+      let snapshots = snapshots?
+        .into_iter()
+        .map(|snapshot| DocumentSnapshot {
+          snapshot_id: snapshot.snapshot_id,
+          document_id: snapshot.object_id,
+          data: snapshot.data,
+          created_at: snapshot.created_at,
+        })
+        .collect();
+      Ok(snapshots)
+    })
   }
 
   fn get_document_data(
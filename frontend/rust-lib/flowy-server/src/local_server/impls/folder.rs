@@ -13,7 +13,6 @@ use lib_infra::future::FutureResult;
 use crate::local_server::LocalServerDB;
 
 pub(crate) struct LocalServerFolderCloudServiceImpl {
-  #[allow(dead_code)]
   pub db: Arc<dyn LocalServerDB>,
 }
 
@@ -47,10 +46,23 @@ impl FolderCloudService for LocalServerFolderCloudServiceImpl {
 
   fn get_folder_snapshots(
     &self,
-    _workspace_id: &str,
-    _limit: usize,
+    workspace_id: &str,
+    limit: usize,
   ) -> FutureResult<Vec<FolderSnapshot>, Error> {
-    FutureResult::new(async move { Ok(vec![]) })
+    // The object id of the folder is the workspace id.
+    let snapshots = self.db.get_collab_snapshots(workspace_id, limit);
+    FutureResult::new(async move {
+      let snapshots = snapshots?
+        .into_iter()
+        .map(|snapshot| FolderSnapshot {
+          snapshot_id: snapshot.snapshot_id,
+          database_id: snapshot.object_id,
+          data: snapshot.data,
+          created_at: snapshot.created_at,
+        })
+        .collect();
+      Ok(snapshots)
+    })
   }
 
   fn get_folder_doc_state(
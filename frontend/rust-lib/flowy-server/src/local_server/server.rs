@@ -24,6 +24,19 @@ use crate::AppFlowyServer;
 pub trait LocalServerDB: Send + Sync + 'static {
   fn get_user_profile(&self, uid: i64) -> Result<UserProfile, FlowyError>;
   fn get_user_workspace(&self, uid: i64) -> Result<Option<UserWorkspace>, FlowyError>;
+  /// Returns the newest snapshots of the collab object that are stored locally.
+  fn get_collab_snapshots(
+    &self,
+    object_id: &str,
+    limit: usize,
+  ) -> Result<Vec<LocalCollabSnapshot>, FlowyError>;
+}
+
+pub struct LocalCollabSnapshot {
+  pub snapshot_id: i64,
+  pub object_id: String,
+  pub data: Vec<u8>,
+  pub created_at: i64,
 }
 
 pub struct LocalServer {
@@ -61,11 +74,15 @@ impl AppFlowyServer for LocalServer {
   }
 
   fn database_service(&self) -> Arc<dyn DatabaseCloudService> {
-    Arc::new(LocalServerDatabaseCloudServiceImpl())
+    Arc::new(LocalServerDatabaseCloudServiceImpl {
+      db: self.local_db.clone(),
+    })
   }
 
   fn document_service(&self) -> Arc<dyn DocumentCloudService> {
-    Arc::new(LocalServerDocumentCloudServiceImpl())
+    Arc::new(LocalServerDocumentCloudServiceImpl {
+      db: self.local_db.clone(),
+    })
   }
 
   fn file_storage(&self) -> Option<Arc<dyn StorageCloudService>> {
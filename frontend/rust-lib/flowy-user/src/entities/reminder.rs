@@ -2,6 +2,22 @@ use collab_entity::reminder::{ObjectType, Reminder, ReminderMeta};
 use flowy_derive::ProtoBuf;
 use std::collections::HashMap;
 
+/// The key of the recurrence rule in the meta of a reminder.
+const REMINDER_RECURRENCE_KEY: &str = "recurrence";
+
+/// The key of the scheduled time that has fired in the meta of a reminder. A reminder fires
+/// once per scheduled time, so it fires again after it's snoozed or rescheduled.
+pub const REMINDER_FIRED_AT_KEY: &str = "fired_at";
+
+/// The key of the offset, in seconds, of a reminder from the date it's linked to in the meta of
+/// a reminder. It's kept when the date changes, see
+/// [crate::user_manager::UserManager::reschedule_reminder].
+pub const REMINDER_DATE_OFFSET_KEY: &str = "date_offset";
+
+/// The key of the id of the row whose date cell a reminder is linked to in the meta of a
+/// reminder.
+pub const REMINDER_ROW_ID_KEY: &str = "row_id";
+
 #[derive(ProtoBuf, Default, Clone)]
 pub struct ReminderPB {
   #[pb(index = 1)]
@@ -27,6 +43,11 @@ pub struct ReminderPB {
 
   #[pb(index = 8)]
   pub meta: HashMap<String, String>,
+
+  /// The recurrence rule of a repeating reminder in the RRULE style, e.g. `FREQ=DAILY`. Empty if
+  /// the reminder fires once.
+  #[pb(index = 9)]
+  pub recurrence: String,
 }
 
 #[derive(ProtoBuf, Default, Clone)]
@@ -37,6 +58,10 @@ pub struct RepeatedReminderPB {
 
 impl From<ReminderPB> for Reminder {
   fn from(value: ReminderPB) -> Self {
+    let mut meta = value.meta;
+    if !value.recurrence.is_empty() {
+      meta.insert(REMINDER_RECURRENCE_KEY.to_string(), value.recurrence);
+    }
     Self {
       id: value.id,
       scheduled_at: value.scheduled_at,
@@ -45,7 +70,7 @@ impl From<ReminderPB> for Reminder {
       ty: ObjectType::Document,
       title: value.title,
       message: value.message,
-      meta: ReminderMeta::from(value.meta),
+      meta: ReminderMeta::from(meta),
       object_id: value.object_id,
     }
   }
@@ -53,6 +78,8 @@ impl From<ReminderPB> for Reminder {
 
 impl From<Reminder> for ReminderPB {
   fn from(value: Reminder) -> Self {
+    let mut meta = value.meta.into_inner();
+    let recurrence = meta.remove(REMINDER_RECURRENCE_KEY).unwrap_or_default();
     Self {
       id: value.id,
       object_id: value.object_id,
@@ -61,7 +88,8 @@ impl From<Reminder> for ReminderPB {
       is_read: value.is_read,
       title: value.title,
       message: value.message,
-      meta: value.meta.into_inner(),
+      meta,
+      recurrence,
     }
   }
 }
@@ -77,3 +105,25 @@ pub struct ReminderIdentifierPB {
   #[pb(index = 1)]
   pub id: String,
 }
+
+#[derive(ProtoBuf, Default, Clone)]
+pub struct SnoozeReminderPB {
+  #[pb(index = 1)]
+  pub id: String,
+
+  /// The time the reminder fires again, in seconds.
+  #[pb(index = 2)]
+  pub snooze_until: i64,
+}
+
+/// Sent when a reminder comes due. The `object_id` and the meta of the reminder link it to its
+/// document block or database row.
+#[derive(ProtoBuf, Default, Clone)]
+pub struct ReminderFiredPB {
+  #[pb(index = 1)]
+  pub reminder: ReminderPB,
+
+  /// The reminder came due while the app was closed.
+  #[pb(index = 2)]
+  pub is_missed: bool,
+}
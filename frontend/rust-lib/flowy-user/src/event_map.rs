@@ -48,6 +48,7 @@ pub fn init(user_manager: Weak<UserManager>) -> AFPlugin {
     .event(UserEvent::GetAllReminders, get_all_reminder_event_handler)
     .event(UserEvent::RemoveReminder, remove_reminder_event_handler)
     .event(UserEvent::UpdateReminder, update_reminder_event_handler)
+    .event(UserEvent::SnoozeReminder, snooze_reminder_event_handler)
     .event(UserEvent::ResetWorkspace, reset_workspace_handler)
     .event(UserEvent::SetDateTimeSettings, set_date_time_settings)
     .event(UserEvent::GetDateTimeSettings, get_date_time_settings)
@@ -262,6 +263,11 @@ pub enum UserEvent {
 
   #[event(input = "UserWorkspaceIdPB", output = "UseAISettingPB")]
   GetWorkspaceSetting = 58,
+
+  /// Moves a reminder to a later time. The reminder fires again at that time, see
+  /// [crate::notification::UserNotification::DidFireReminder].
+  #[event(input = "SnoozeReminderPB")]
+  SnoozeReminder = 59,
 }
 
 pub trait UserStatusCallback: Send + Sync + 'static {
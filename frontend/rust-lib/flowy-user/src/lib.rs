@@ -6,7 +6,7 @@ pub mod entities;
 mod event_handler;
 pub mod event_map;
 mod migrations;
-mod notification;
+pub mod notification;
 pub mod protobuf;
 pub mod services;
 pub mod user_manager;
@@ -6,7 +6,7 @@ use crate::entities::AuthStateChangedPB;
 const USER_OBSERVABLE_SOURCE: &str = "User";
 
 #[derive(ProtoBuf_Enum, Debug, Default)]
-pub(crate) enum UserNotification {
+pub enum UserNotification {
   #[default]
   Unknown = 0,
   UserAuthStateChanged = 1,
@@ -15,6 +15,8 @@ pub(crate) enum UserNotification {
   DidUpdateCloudConfig = 4,
   DidUpdateUserWorkspace = 5,
   DidUpdateAISetting = 6,
+  /// A reminder of the user came due, the payload is a [crate::entities::ReminderFiredPB].
+  DidFireReminder = 7,
 }
 
 impl std::convert::From<UserNotification> for i32 {
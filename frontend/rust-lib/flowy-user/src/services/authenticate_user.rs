@@ -72,6 +72,10 @@ impl AuthenticateUser {
     Ok(session.user_workspace.database_indexer_id.clone())
   }
 
+  pub fn get_store_preferences(&self) -> Weak<KVStorePreferences> {
+    Arc::downgrade(&self.store_preferences)
+  }
+
   pub fn get_collab_db(&self, uid: i64) -> FlowyResult<Weak<CollabKVDB>> {
     self
       .database
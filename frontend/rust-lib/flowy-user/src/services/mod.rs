@@ -4,4 +4,5 @@ pub mod collab_interact;
 pub mod data_import;
 pub mod db;
 pub mod entities;
+pub mod reminder_scheduler;
 pub mod sqlite_sql;
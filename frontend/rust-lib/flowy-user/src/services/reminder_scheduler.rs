@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use tokio::sync::Notify;
+
+use crate::entities::{ReminderPB, REMINDER_FIRED_AT_KEY};
+
+/// The longest time the scheduler waits between two checks, so the reminders that are synced
+/// from other devices are picked up.
+const MAX_WAIT: Duration = Duration::from_secs(60);
+
+pub struct DueReminder {
+  pub reminder: ReminderPB,
+  /// The reminder came due before the scheduler started, which means the app was closed.
+  pub is_missed: bool,
+}
+
+/// Decides which reminders of the user are due. The scheduler is woken up whenever a reminder
+/// is added or updated, and checks the reminders again when the next one comes due.
+pub struct ReminderScheduler {
+  started_at: i64,
+  notify: Notify,
+}
+
+impl ReminderScheduler {
+  pub fn new(started_at: i64) -> Self {
+    Self {
+      started_at,
+      notify: Notify::new(),
+    }
+  }
+
+  pub fn wake(&self) {
+    self.notify.notify_one();
+  }
+
+  /// Waits until `next_scheduled_at` or until the scheduler is woken up.
+  pub async fn wait(&self, next_scheduled_at: Option<i64>, now: i64) {
+    let duration = next_scheduled_at
+      .map(|scheduled_at| Duration::from_secs(scheduled_at.saturating_sub(now).max(0) as u64))
+      .map_or(MAX_WAIT, |duration| duration.min(MAX_WAIT));
+    let _ = tokio::time::timeout(duration, self.notify.notified()).await;
+  }
+
+  /// Returns the reminders that are due at `now` and the time the next pending reminder comes
+  /// due. The acknowledged reminders and the ones that have fired at their scheduled time are
+  /// skipped.
+  pub fn due_reminders(
+    &self,
+    reminders: Vec<ReminderPB>,
+    now: i64,
+  ) -> (Vec<DueReminder>, Option<i64>) {
+    let mut due_reminders = vec![];
+    let mut next_scheduled_at: Option<i64> = None;
+    for reminder in reminders {
+      if reminder.is_ack || is_fired(&reminder) {
+        continue;
+      }
+      if reminder.scheduled_at <= now {
+        due_reminders.push(DueReminder {
+          is_missed: reminder.scheduled_at < self.started_at,
+          reminder,
+        });
+      } else {
+        next_scheduled_at = Some(next_scheduled_at.map_or(reminder.scheduled_at, |next| {
+          next.min(reminder.scheduled_at)
+        }));
+      }
+    }
+    (due_reminders, next_scheduled_at)
+  }
+}
+
+fn is_fired(reminder: &ReminderPB) -> bool {
+  reminder
+    .meta
+    .get(REMINDER_FIRED_AT_KEY)
+    .map_or(false, |fired_at| {
+      *fired_at == reminder.scheduled_at.to_string()
+    })
+}
@@ -22,6 +22,7 @@ use tracing::{debug, error, event, info, instrument, trace, warn};
 
 use lib_dispatch::prelude::af_spawn;
 use lib_infra::box_any::BoxAny;
+use lib_infra::util::timestamp;
 
 use crate::anon_user::{migration_anon_user_on_sign_up, sync_supabase_user_data_to_cloud};
 use crate::entities::{AuthStateChangedPB, AuthStatePB, UserProfilePB, UserSettingPB};
@@ -36,6 +37,7 @@ use crate::migrations::AnonUser;
 use crate::services::authenticate_user::AuthenticateUser;
 use crate::services::cloud_config::get_cloud_config;
 use crate::services::collab_interact::{CollabInteract, DefaultCollabInteract};
+use crate::services::reminder_scheduler::ReminderScheduler;
 
 use crate::services::sqlite_sql::user_sql::{select_user_profile, UserTable, UserTableChangeset};
 use crate::user_manager::manager_user_encryption::validate_encryption_sign;
@@ -58,6 +60,7 @@ pub struct UserManager {
   pub(crate) authenticate_user: Arc<AuthenticateUser>,
   refresh_user_profile_since: AtomicI64,
   pub(crate) is_loading_awareness: Arc<AtomicBool>,
+  pub(crate) reminder_scheduler: Arc<ReminderScheduler>,
 }
 
 impl UserManager {
@@ -84,6 +87,7 @@ impl UserManager {
       refresh_user_profile_since,
       user_workspace_service,
       is_loading_awareness: Arc::new(AtomicBool::new(false)),
+      reminder_scheduler: Arc::new(ReminderScheduler::new(timestamp())),
     });
 
     let weak_user_manager = Arc::downgrade(&user_manager);
@@ -101,6 +105,20 @@ impl UserManager {
       }
     }
 
+    let weak_user_manager = Arc::downgrade(&user_manager);
+    let reminder_scheduler = user_manager.reminder_scheduler.clone();
+    af_spawn(async move {
+      loop {
+        let next_scheduled_at = match weak_user_manager.upgrade() {
+          None => break,
+          Some(user_manager) => user_manager.fire_due_reminders().await,
+        };
+        reminder_scheduler
+          .wait(next_scheduled_at, timestamp())
+          .await;
+      }
+    });
+
     user_manager
   }
 
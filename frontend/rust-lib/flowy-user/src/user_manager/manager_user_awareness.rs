@@ -1,7 +1,9 @@
+use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Weak};
 
 use anyhow::Context;
+use chrono::Local;
 use collab::core::collab::{DataSource, MutexCollab};
 use collab_entity::reminder::Reminder;
 use collab_entity::CollabType;
@@ -13,9 +15,15 @@ use collab_integrate::CollabKVDB;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use flowy_user_pub::entities::user_awareness_object_id;
 
-use crate::entities::ReminderPB;
+use crate::entities::{
+  ReminderFiredPB, ReminderPB, REMINDER_DATE_OFFSET_KEY, REMINDER_FIRED_AT_KEY, REMINDER_ROW_ID_KEY,
+};
+use crate::notification::{send_notification, UserNotification};
+use crate::services::reminder_scheduler::DueReminder;
 use crate::user_manager::UserManager;
 use flowy_user_pub::session::Session;
+use lib_infra::recurrence::Recurrence;
+use lib_infra::util::timestamp;
 
 impl UserManager {
   /// Adds a new reminder based on the given payload.
@@ -32,6 +40,7 @@ impl UserManager {
   /// - May return errors of type `FlowyError` if any issues arise during the process.
   ///
   pub async fn add_reminder(&self, reminder_pb: ReminderPB) -> FlowyResult<()> {
+    parse_reminder_recurrence(&reminder_pb)?;
     let reminder = Reminder::from(reminder_pb);
     self
       .with_awareness((), |user_awareness| {
@@ -44,6 +53,7 @@ impl UserManager {
       .await
       .add_reminder(reminder)
       .await?;
+    self.reminder_scheduler.wake();
     Ok(())
   }
 
@@ -61,12 +71,25 @@ impl UserManager {
       .await
       .remove_reminder(reminder_id)
       .await?;
+    self.reminder_scheduler.wake();
     Ok(())
   }
 
   /// Updates an existing reminder
   ///
-  pub async fn update_reminder(&self, reminder_pb: ReminderPB) -> FlowyResult<()> {
+  /// A repeating reminder that is acknowledged is moved to its next occurrence, so it fires
+  /// again. It stays acknowledged if its series has ended.
+  ///
+  pub async fn update_reminder(&self, mut reminder_pb: ReminderPB) -> FlowyResult<()> {
+    if let Some(recurrence) = parse_reminder_recurrence(&reminder_pb)? {
+      if reminder_pb.is_ack {
+        schedule_next_occurrence(&mut reminder_pb, &recurrence);
+      }
+    }
+    self.save_reminder(reminder_pb).await
+  }
+
+  async fn save_reminder(&self, reminder_pb: ReminderPB) -> FlowyResult<()> {
     let reminder = Reminder::from(reminder_pb);
     self
       .with_awareness((), |user_awareness| {
@@ -81,7 +104,7 @@ impl UserManager {
       .await
       .update_reminder(reminder)
       .await?;
-
+    self.reminder_scheduler.wake();
     Ok(())
   }
 
@@ -100,6 +123,128 @@ impl UserManager {
       .await
   }
 
+  /// Moves the reminder to `snooze_until`, so it fires again at that time.
+  pub async fn snooze_reminder(&self, reminder_id: &str, snooze_until: i64) -> FlowyResult<()> {
+    if snooze_until <= timestamp() {
+      return Err(FlowyError::new(
+        ErrorCode::InvalidParams,
+        "A reminder can only be snoozed until a later time",
+      ));
+    }
+    let mut reminder_pb = self.get_reminder(reminder_id).await?;
+    reminder_pb.scheduled_at = snooze_until;
+    reminder_pb.is_ack = false;
+    reminder_pb.is_read = false;
+    self.save_reminder(reminder_pb).await
+  }
+
+  /// Moves the reminder along with the date it's linked to, e.g. the date cell or the date
+  /// mention with the id of the reminder. The reminder keeps its offset from the date, which is
+  /// taken from `old_date` the first time the date changes. The reminder is left unchanged if it's
+  /// already scheduled at the new time, e.g. when the client has moved it.
+  ///
+  /// `row_id` is the row the date cell has moved to, e.g. the row of the next occurrence of a
+  /// recurring date, and the reminder is linked to it.
+  pub async fn reschedule_reminder(
+    &self,
+    reminder_id: &str,
+    old_date: i64,
+    new_date: i64,
+    row_id: Option<&str>,
+  ) -> FlowyResult<()> {
+    let mut reminder_pb = self.get_reminder(reminder_id).await?;
+    let offset = reminder_pb
+      .meta
+      .get(REMINDER_DATE_OFFSET_KEY)
+      .and_then(|offset| offset.parse::<i64>().ok())
+      .unwrap_or(reminder_pb.scheduled_at - old_date);
+    let scheduled_at = new_date + offset;
+    let is_moved_to_row = row_id.map_or(false, |row_id| {
+      reminder_pb
+        .meta
+        .get(REMINDER_ROW_ID_KEY)
+        .map(String::as_str)
+        != Some(row_id)
+    });
+    if reminder_pb.scheduled_at == scheduled_at && !is_moved_to_row {
+      return Ok(());
+    }
+
+    if let Some(row_id) = row_id {
+      reminder_pb
+        .meta
+        .insert(REMINDER_ROW_ID_KEY.to_string(), row_id.to_string());
+    }
+
+    reminder_pb
+      .meta
+      .insert(REMINDER_DATE_OFFSET_KEY.to_string(), offset.to_string());
+    reminder_pb.scheduled_at = scheduled_at;
+    reminder_pb.is_ack = false;
+    reminder_pb.is_read = false;
+    self.save_reminder(reminder_pb).await
+  }
+
+  async fn get_reminder(&self, reminder_id: &str) -> FlowyResult<ReminderPB> {
+    self
+      .get_all_reminders()
+      .await
+      .into_iter()
+      .find(|reminder| reminder.id == reminder_id)
+      .map(ReminderPB::from)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("The reminder with id:{} not found", reminder_id))
+      })
+  }
+
+  /// Sends a [UserNotification::DidFireReminder] for every reminder that is due, and marks the
+  /// reminders as fired. Returns the time the next pending reminder comes due. The user
+  /// awareness isn't loaded here, the reminders are checked again once it's loaded.
+  pub(crate) async fn fire_due_reminders(&self) -> Option<i64> {
+    let uid = self.user_id().ok()?;
+    let reminders = {
+      let user_awareness = self.user_awareness.lock().await;
+      user_awareness
+        .as_ref()?
+        .lock()
+        .get_all_reminders()
+        .into_iter()
+        .map(ReminderPB::from)
+        .collect::<Vec<_>>()
+    };
+
+    let (due_reminders, next_scheduled_at) = self
+      .reminder_scheduler
+      .due_reminders(reminders, timestamp());
+    for DueReminder {
+      mut reminder,
+      is_missed,
+    } in due_reminders
+    {
+      trace!("Fire reminder: {}, missed: {}", reminder.id, is_missed);
+      reminder.meta.insert(
+        REMINDER_FIRED_AT_KEY.to_string(),
+        reminder.scheduled_at.to_string(),
+      );
+      let fired_reminder = Reminder::from(reminder.clone());
+      self
+        .with_awareness((), |user_awareness| {
+          user_awareness.update_reminder(&fired_reminder.id, |new_reminder| {
+            new_reminder.clone_from(&fired_reminder)
+          });
+        })
+        .await;
+      send_notification(&uid.to_string(), UserNotification::DidFireReminder)
+        .payload(ReminderFiredPB {
+          reminder,
+          is_missed,
+        })
+        .send();
+    }
+    next_scheduled_at
+  }
+
   pub async fn initialize_user_awareness(&self, session: &Session) {
     match self.try_initial_user_awareness(session).await {
       Ok(_) => {},
@@ -143,6 +288,7 @@ impl UserManager {
     let cloned_is_loading = self.is_loading_awareness.clone();
     let session = session.clone();
     let workspace_id = session.user_workspace.id.clone();
+    let reminder_scheduler = self.reminder_scheduler.clone();
     tokio::spawn(async move {
       if cloned_is_loading.load(Ordering::SeqCst) {
         return Ok(());
@@ -199,6 +345,8 @@ impl UserManager {
 
         trace!("User awareness initialized");
         lock_awareness.replace(awareness);
+        // The reminders that came due while the app was closed fire once they are loaded.
+        reminder_scheduler.wake();
       }
       Ok(())
     });
@@ -268,3 +416,26 @@ impl UserManager {
     }
   }
 }
+
+fn parse_reminder_recurrence(reminder_pb: &ReminderPB) -> FlowyResult<Option<Recurrence>> {
+  if reminder_pb.recurrence.is_empty() {
+    return Ok(None);
+  }
+  Recurrence::from_str(&reminder_pb.recurrence)
+    .map(Some)
+    .map_err(|err| FlowyError::new(ErrorCode::InvalidRecurrenceRule, err))
+}
+
+/// The occurrences of a reminder keep the local time of the device, following its changes of
+/// daylight saving time.
+fn schedule_next_occurrence(reminder_pb: &mut ReminderPB, recurrence: &Recurrence) {
+  match recurrence.next(reminder_pb.scheduled_at, Local) {
+    Some((scheduled_at, recurrence)) => {
+      reminder_pb.scheduled_at = scheduled_at;
+      reminder_pb.is_ack = false;
+      reminder_pb.is_read = false;
+      reminder_pb.recurrence = recurrence.to_string();
+    },
+    None => reminder_pb.recurrence = String::new(),
+  }
+}
@@ -22,6 +22,7 @@ if_wasm! {
 #[cfg(feature = "isolate_flutter")]
 pub mod isolate_stream;
 pub mod priority_task;
+pub mod recurrence;
 pub mod ref_map;
 pub mod stream_util;
 pub mod util;
@@ -0,0 +1,326 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail};
+use chrono::{Datelike, Days, LocalResult, Months, NaiveDateTime, Offset, TimeZone, Weekday};
+
+/// The maximum number of days, weeks, months or years that are walked through when expanding a
+/// rule. It stops the expansion of rules whose occurrences are far apart or never happen.
+const MAX_RECURRENCE_PERIODS: i64 = 100_000;
+/// The maximum interval of a rule, e.g. every 1000 days or every 1000 years.
+const MAX_RECURRENCE_INTERVAL: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceFrequency {
+  Daily,
+  Weekly,
+  Monthly,
+  Yearly,
+}
+
+impl fmt::Display for RecurrenceFrequency {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let s = match self {
+      RecurrenceFrequency::Daily => "DAILY",
+      RecurrenceFrequency::Weekly => "WEEKLY",
+      RecurrenceFrequency::Monthly => "MONTHLY",
+      RecurrenceFrequency::Yearly => "YEARLY",
+    };
+    f.write_str(s)
+  }
+}
+
+impl FromStr for RecurrenceFrequency {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.to_uppercase().as_str() {
+      "DAILY" => Ok(RecurrenceFrequency::Daily),
+      "WEEKLY" => Ok(RecurrenceFrequency::Weekly),
+      "MONTHLY" => Ok(RecurrenceFrequency::Monthly),
+      "YEARLY" => Ok(RecurrenceFrequency::Yearly),
+      _ => Err(anyhow!("Unsupported recurrence frequency: {}", s)),
+    }
+  }
+}
+
+/// A recurrence rule in the style of the RRULE of iCalendar, for example
+/// `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10`. Unlike RRULE, `UNTIL` and `EXDATE` are unix
+/// timestamps in seconds.
+///
+/// The occurrences keep the local time of the first occurrence. As in RRULE, a monthly or yearly
+/// occurrence is skipped if its day doesn't exist in the month, e.g. the 31st of April.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+  pub frequency: RecurrenceFrequency,
+  /// Repeats every `interval` days, weeks, months or years.
+  pub interval: u32,
+  /// The days of a weekly rule. The weekday of the first occurrence is used if empty.
+  pub weekdays: Vec<Weekday>,
+  /// The last time an occurrence can happen, inclusive.
+  pub until: Option<i64>,
+  /// The number of occurrences, the skipped ones included.
+  pub count: Option<u32>,
+  /// The occurrences that are skipped.
+  pub exceptions: Vec<i64>,
+}
+
+impl Recurrence {
+  pub fn new(frequency: RecurrenceFrequency) -> Self {
+    Self {
+      frequency,
+      interval: 1,
+      weekdays: vec![],
+      until: None,
+      count: None,
+      exceptions: vec![],
+    }
+  }
+
+  /// Returns the occurrences of the series whose first occurrence is `start`. `timezone` is the
+  /// time zone in which the local time of the occurrences is kept, so the offset of each
+  /// occurrence follows the changes of daylight saving time.
+  pub fn occurrences<'a, Tz: TimeZone + 'a>(
+    &'a self,
+    start: i64,
+    timezone: Tz,
+  ) -> impl Iterator<Item = i64> + 'a {
+    self
+      .all_occurrences(start, timezone)
+      .filter(move |timestamp| !self.exceptions.contains(timestamp))
+  }
+
+  /// Returns the occurrences that happen between `from` and `to`, inclusive.
+  pub fn occurrences_between<Tz: TimeZone>(
+    &self,
+    start: i64,
+    from: i64,
+    to: i64,
+    timezone: Tz,
+  ) -> Vec<i64> {
+    self
+      .occurrences(start, timezone)
+      .skip_while(|timestamp| *timestamp < from)
+      .take_while(|timestamp| *timestamp <= to)
+      .collect()
+  }
+
+  /// Returns the first occurrence after `start` and the rule of the series that continues from
+  /// it, or None if the series ends at `start`.
+  pub fn next<Tz: TimeZone>(&self, start: i64, timezone: Tz) -> Option<(i64, Recurrence)> {
+    let (index, next) = self
+      .all_occurrences(start, timezone)
+      .enumerate()
+      .find(|(_, timestamp)| *timestamp > start && !self.exceptions.contains(timestamp))?;
+
+    let mut recurrence = self.clone();
+    recurrence.count = self.count.map(|count| count - index as u32);
+    recurrence.exceptions.retain(|exception| *exception > next);
+    Some((next, recurrence))
+  }
+
+  fn all_occurrences<'a, Tz: TimeZone + 'a>(
+    &'a self,
+    start: i64,
+    timezone: Tz,
+  ) -> impl Iterator<Item = i64> + 'a {
+    let first = local_date_time(start, &timezone);
+    let interval = i64::from(self.interval.max(1));
+    let mut period = 0;
+    let mut pending = VecDeque::new();
+    std::iter::from_fn(move || loop {
+      if let Some(date_time) = pending.pop_front() {
+        return Some(date_time);
+      }
+      if period >= MAX_RECURRENCE_PERIODS {
+        return None;
+      }
+      // The series ends when its occurrences are out of the range of the dates
+      pending.extend(self.date_times_of_period(first, period * interval)?);
+      period += 1;
+    })
+    .filter(move |date_time| *date_time >= first)
+    .filter_map(move |date_time| timestamp_of(date_time, &timezone))
+    .take_while(move |timestamp| self.until.map_or(true, |until| *timestamp <= until))
+    .take(self.count.map_or(usize::MAX, |count| count as usize))
+  }
+
+  /// Returns the local date times of the n-th day, week, month or year after `first`, or None if
+  /// the period is out of the range of the dates.
+  fn date_times_of_period(&self, first: NaiveDateTime, n: i64) -> Option<Vec<NaiveDateTime>> {
+    let n = u64::try_from(n).ok()?;
+    match self.frequency {
+      RecurrenceFrequency::Daily => Some(vec![first.checked_add_days(Days::new(n))?]),
+      RecurrenceFrequency::Weekly => {
+        let monday = first
+          .checked_sub_days(Days::new(u64::from(first.weekday().num_days_from_monday())))?
+          .checked_add_days(Days::new(n.checked_mul(7)?))?;
+        let mut weekdays = if self.weekdays.is_empty() {
+          vec![first.weekday()]
+        } else {
+          self.weekdays.clone()
+        };
+        weekdays.sort_by_key(|weekday| weekday.num_days_from_monday());
+        weekdays.dedup();
+        weekdays
+          .into_iter()
+          .map(|weekday| {
+            monday.checked_add_days(Days::new(u64::from(weekday.num_days_from_monday())))
+          })
+          .collect()
+      },
+      RecurrenceFrequency::Monthly => add_months(first, n),
+      RecurrenceFrequency::Yearly => add_months(first, n.checked_mul(12)?),
+    }
+  }
+}
+
+impl fmt::Display for Recurrence {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "FREQ={}", self.frequency)?;
+    if self.interval > 1 {
+      write!(f, ";INTERVAL={}", self.interval)?;
+    }
+    if !self.weekdays.is_empty() {
+      let weekdays = self
+        .weekdays
+        .iter()
+        .map(|weekday| weekday_code(*weekday))
+        .collect::<Vec<_>>();
+      write!(f, ";BYDAY={}", weekdays.join(","))?;
+    }
+    if let Some(until) = self.until {
+      write!(f, ";UNTIL={}", until)?;
+    }
+    if let Some(count) = self.count {
+      write!(f, ";COUNT={}", count)?;
+    }
+    if !self.exceptions.is_empty() {
+      let exceptions = self
+        .exceptions
+        .iter()
+        .map(|exception| exception.to_string())
+        .collect::<Vec<_>>();
+      write!(f, ";EXDATE={}", exceptions.join(","))?;
+    }
+    Ok(())
+  }
+}
+
+impl FromStr for Recurrence {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    let s = s.strip_prefix("RRULE:").unwrap_or(s);
+    let mut frequency = None;
+    let mut recurrence = Recurrence::new(RecurrenceFrequency::Daily);
+    for part in s.split(';').filter(|part| !part.trim().is_empty()) {
+      let (key, value) = part
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Invalid recurrence rule part: {}", part))?;
+      let value = value.trim();
+      match key.trim().to_uppercase().as_str() {
+        "FREQ" => frequency = Some(RecurrenceFrequency::from_str(value)?),
+        "INTERVAL" => recurrence.interval = value.parse()?,
+        "BYDAY" => {
+          recurrence.weekdays = split_list(value)
+            .map(parse_weekday)
+            .collect::<Result<_, _>>()?
+        },
+        "UNTIL" => recurrence.until = Some(value.parse()?),
+        "COUNT" => recurrence.count = Some(value.parse()?),
+        "EXDATE" => {
+          recurrence.exceptions = split_list(value)
+            .map(|exception| exception.parse::<i64>())
+            .collect::<Result<_, _>>()?
+        },
+        _ => bail!("Unsupported recurrence rule part: {}", key),
+      }
+    }
+
+    recurrence.frequency = frequency.ok_or_else(|| anyhow!("The recurrence rule requires FREQ"))?;
+    if recurrence.interval == 0 {
+      bail!("The interval of a recurrence rule must be positive");
+    }
+    if recurrence.interval > MAX_RECURRENCE_INTERVAL {
+      bail!(
+        "The interval of a recurrence rule must not exceed {}",
+        MAX_RECURRENCE_INTERVAL
+      );
+    }
+    if recurrence.count == Some(0) {
+      bail!("The count of a recurrence rule must be positive");
+    }
+    if !recurrence.weekdays.is_empty() && recurrence.frequency != RecurrenceFrequency::Weekly {
+      bail!("BYDAY is only supported by weekly recurrence rules");
+    }
+    Ok(recurrence)
+  }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+  value
+    .split(',')
+    .map(|item| item.trim())
+    .filter(|item| !item.is_empty())
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+  match weekday {
+    Weekday::Mon => "MO",
+    Weekday::Tue => "TU",
+    Weekday::Wed => "WE",
+    Weekday::Thu => "TH",
+    Weekday::Fri => "FR",
+    Weekday::Sat => "SA",
+    Weekday::Sun => "SU",
+  }
+}
+
+fn parse_weekday(code: &str) -> Result<Weekday, anyhow::Error> {
+  match code.to_uppercase().as_str() {
+    "MO" => Ok(Weekday::Mon),
+    "TU" => Ok(Weekday::Tue),
+    "WE" => Ok(Weekday::Wed),
+    "TH" => Ok(Weekday::Thu),
+    "FR" => Ok(Weekday::Fri),
+    "SA" => Ok(Weekday::Sat),
+    "SU" => Ok(Weekday::Sun),
+    _ => Err(anyhow!("Invalid weekday: {}", code)),
+  }
+}
+
+/// Returns None if the result is out of the range of the dates, and no date time if the day
+/// doesn't exist in the month.
+fn add_months(date_time: NaiveDateTime, months: u64) -> Option<Vec<NaiveDateTime>> {
+  let months = Months::new(u32::try_from(months).ok()?);
+  let result = date_time.checked_add_months(months)?;
+  Some(
+    Some(result)
+      .filter(|result| result.day() == date_time.day())
+      .into_iter()
+      .collect(),
+  )
+}
+
+fn local_date_time<Tz: TimeZone>(timestamp: i64, timezone: &Tz) -> NaiveDateTime {
+  let utc = NaiveDateTime::from_timestamp_opt(timestamp, 0).unwrap_or_default();
+  timezone.from_utc_datetime(&utc).naive_local()
+}
+
+/// Returns the timestamp of a local time. A time that happens twice when daylight saving time
+/// ends is the first of the two, and a time skipped when it starts uses the offset from before
+/// the change, like RFC 5545 does.
+fn timestamp_of<Tz: TimeZone>(date_time: NaiveDateTime, timezone: &Tz) -> Option<i64> {
+  match timezone.from_local_datetime(&date_time) {
+    LocalResult::Single(zoned) | LocalResult::Ambiguous(zoned, _) => Some(zoned.timestamp()),
+    LocalResult::None => {
+      let before = date_time.checked_sub_days(Days::new(1))?;
+      let offset = timezone.offset_from_utc_datetime(&before).fix();
+      Some(date_time.timestamp() - i64::from(offset.local_minus_utc()))
+    },
+  }
+}
@@ -1 +1,2 @@
+mod recurrence_test;
 mod task_test;
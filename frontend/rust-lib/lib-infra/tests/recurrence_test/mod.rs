@@ -0,0 +1 @@
+mod recurrence_rule_test;
@@ -0,0 +1,177 @@
+use std::str::FromStr;
+
+use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+use chrono_tz::America::New_York;
+use lib_infra::recurrence::{Recurrence, RecurrenceFrequency};
+
+fn utc() -> FixedOffset {
+  FixedOffset::east_opt(0).unwrap()
+}
+
+fn timestamp(year: i32, month: u32, day: u32, hour: u32) -> i64 {
+  let date_time = NaiveDate::from_ymd_opt(year, month, day)
+    .unwrap()
+    .and_hms_opt(hour, 0, 0)
+    .unwrap();
+  Utc.from_utc_datetime(&date_time).timestamp()
+}
+
+#[test]
+fn recurrence_rule_round_trip_test() {
+  let s = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10;EXDATE=1700000000";
+  let recurrence = Recurrence::from_str(s).unwrap();
+  assert_eq!(recurrence.frequency, RecurrenceFrequency::Weekly);
+  assert_eq!(recurrence.interval, 2);
+  assert_eq!(recurrence.weekdays.len(), 2);
+  assert_eq!(recurrence.count, Some(10));
+  assert_eq!(recurrence.exceptions, vec![1700000000]);
+  assert_eq!(recurrence.to_string(), s);
+
+  let recurrence = Recurrence::from_str("RRULE:freq=daily").unwrap();
+  assert_eq!(recurrence.to_string(), "FREQ=DAILY");
+}
+
+#[test]
+fn invalid_recurrence_rule_test() {
+  assert!(Recurrence::from_str("").is_err());
+  assert!(Recurrence::from_str("FREQ=HOURLY").is_err());
+  assert!(Recurrence::from_str("FREQ=DAILY;INTERVAL=0").is_err());
+  assert!(Recurrence::from_str("FREQ=DAILY;INTERVAL=100000000").is_err());
+  assert!(Recurrence::from_str("FREQ=DAILY;BYDAY=MO").is_err());
+  assert!(Recurrence::from_str("FREQ=WEEKLY;BYDAY=XX").is_err());
+}
+
+#[test]
+fn daily_recurrence_with_count_and_exception_test() {
+  let start = timestamp(2024, 3, 1, 9);
+  let mut recurrence = Recurrence::new(RecurrenceFrequency::Daily);
+  recurrence.count = Some(4);
+  recurrence.exceptions = vec![timestamp(2024, 3, 2, 9)];
+
+  let occurrences = recurrence.occurrences(start, utc()).collect::<Vec<_>>();
+  assert_eq!(
+    occurrences,
+    vec![
+      timestamp(2024, 3, 1, 9),
+      timestamp(2024, 3, 3, 9),
+      timestamp(2024, 3, 4, 9),
+    ]
+  );
+}
+
+#[test]
+fn weekly_recurrence_between_test() {
+  // 2024-03-04 is a Monday.
+  let start = timestamp(2024, 3, 4, 9);
+  let recurrence = Recurrence::from_str("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,FR").unwrap();
+  let occurrences = recurrence.occurrences_between(
+    start,
+    timestamp(2024, 3, 5, 0),
+    timestamp(2024, 3, 31, 0),
+    utc(),
+  );
+  assert_eq!(
+    occurrences,
+    vec![
+      timestamp(2024, 3, 8, 9),
+      timestamp(2024, 3, 18, 9),
+      timestamp(2024, 3, 22, 9),
+    ]
+  );
+}
+
+#[test]
+fn monthly_recurrence_skips_missing_days_test() {
+  let start = timestamp(2024, 1, 31, 9);
+  let mut recurrence = Recurrence::new(RecurrenceFrequency::Monthly);
+  recurrence.until = Some(timestamp(2024, 6, 1, 0));
+
+  let occurrences = recurrence.occurrences(start, utc()).collect::<Vec<_>>();
+  assert_eq!(
+    occurrences,
+    vec![
+      timestamp(2024, 1, 31, 9),
+      timestamp(2024, 3, 31, 9),
+      timestamp(2024, 5, 31, 9),
+    ]
+  );
+}
+
+#[test]
+fn recurrence_keeps_local_time_test() {
+  // 09:00 in UTC+8 is 01:00 in UTC.
+  let offset = FixedOffset::east_opt(8 * 3600).unwrap();
+  let start = timestamp(2024, 3, 4, 1);
+  let recurrence = Recurrence::from_str("FREQ=WEEKLY;BYDAY=TU").unwrap();
+  let (next, _) = recurrence.next(start, offset).unwrap();
+  assert_eq!(next, timestamp(2024, 3, 5, 1));
+}
+
+#[test]
+fn recurrence_follows_daylight_saving_time_test() {
+  // 09:00 in New York is 14:00 in UTC before daylight saving time starts and 13:00 after.
+  let start = timestamp(2024, 3, 9, 14);
+  let recurrence = Recurrence::from_str("FREQ=DAILY;COUNT=3").unwrap();
+  let occurrences = recurrence.occurrences(start, New_York).collect::<Vec<_>>();
+  assert_eq!(
+    occurrences,
+    vec![
+      timestamp(2024, 3, 9, 14),
+      timestamp(2024, 3, 10, 13),
+      timestamp(2024, 3, 11, 13),
+    ]
+  );
+
+  // 02:30 is skipped on 2024-03-10, so the offset from before the change is used.
+  let half_hour = 30 * 60;
+  let start = timestamp(2024, 3, 9, 7) + half_hour;
+  let occurrences = recurrence.occurrences(start, New_York).collect::<Vec<_>>();
+  assert_eq!(
+    occurrences,
+    vec![
+      timestamp(2024, 3, 9, 7) + half_hour,
+      timestamp(2024, 3, 10, 7) + half_hour,
+      timestamp(2024, 3, 11, 6) + half_hour,
+    ]
+  );
+
+  // 01:30 happens twice on 2024-11-03, so the first one is used.
+  let start = timestamp(2024, 11, 2, 5) + half_hour;
+  let occurrences = recurrence.occurrences(start, New_York).collect::<Vec<_>>();
+  assert_eq!(
+    occurrences,
+    vec![
+      timestamp(2024, 11, 2, 5) + half_hour,
+      timestamp(2024, 11, 3, 5) + half_hour,
+      timestamp(2024, 11, 4, 6) + half_hour,
+    ]
+  );
+}
+
+#[test]
+fn next_recurrence_reduces_count_test() {
+  let start = timestamp(2024, 3, 1, 9);
+  let recurrence = Recurrence::from_str("FREQ=DAILY;COUNT=2").unwrap();
+
+  let (next, recurrence) = recurrence.next(start, utc()).unwrap();
+  assert_eq!(next, timestamp(2024, 3, 2, 9));
+  assert_eq!(recurrence.count, Some(1));
+  assert!(recurrence.next(next, utc()).is_none());
+}
+
+#[test]
+fn recurrence_ends_when_out_of_range_test() {
+  let start = timestamp(2024, 3, 1, 9);
+  for frequency in [
+    RecurrenceFrequency::Daily,
+    RecurrenceFrequency::Weekly,
+    RecurrenceFrequency::Monthly,
+    RecurrenceFrequency::Yearly,
+  ] {
+    let mut recurrence = Recurrence::new(frequency);
+    recurrence.interval = u32::MAX;
+    let occurrences = recurrence.occurrences(start, utc()).collect::<Vec<_>>();
+    assert_eq!(occurrences, vec![start], "frequency: {}", frequency);
+    assert!(recurrence.next(start, utc()).is_none());
+  }
+}